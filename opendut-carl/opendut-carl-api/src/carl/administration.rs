@@ -0,0 +1,117 @@
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ExportSnapshotError {
+    #[error("An internal error occurred while exporting a snapshot:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum GetVpnReconciliationReportError {
+    #[error("The VPN is disabled, so there is nothing to reconcile.")]
+    VpnDisabled,
+    #[error("An internal error occurred while reconciling the VPN:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use opendut_types::vpn::reconciliation::VpnReconciliationReport;
+
+    use crate::carl::{extract, ClientError};
+    use crate::carl::administration::{ExportSnapshotError, GetVpnReconciliationReportError};
+    use crate::proto::services::administration;
+    use crate::proto::services::administration::administration_client::AdministrationClient;
+
+    #[derive(Clone, Debug)]
+    pub struct Administration<T> {
+        inner: AdministrationClient<T>,
+    }
+
+    impl<T> Administration<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: AdministrationClient<T>) -> Administration<T> {
+            Administration { inner }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> Administration<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = AdministrationClient::new(InterceptedService::new(inner, interceptor));
+            Administration {
+                inner: inner_client
+            }
+        }
+
+        /// Exports the persistent state of CARL. Returns the bytes of the snapshot archive.
+        pub async fn export_snapshot(&mut self) -> Result<Vec<u8>, ClientError<ExportSnapshotError>> {
+
+            let request = tonic::Request::new(administration::ExportSnapshotRequest {});
+
+            let mut stream = self.inner.export_snapshot(request).await?
+                .into_inner();
+
+            let mut archive = Vec::new();
+            while let Some(response) = stream.message().await? {
+                match extract!(response.reply)? {
+                    administration::export_snapshot_response::Reply::Failure(failure) => {
+                        let error = ExportSnapshotError::try_from(failure)?;
+                        return Err(ClientError::UsageError(error));
+                    }
+                    administration::export_snapshot_response::Reply::Chunk(chunk) => {
+                        archive.extend(chunk.data);
+                    }
+                }
+            }
+            Ok(archive)
+        }
+
+        /// Returns the report of the last reconciliation of the VPN, or reconciles the VPN first, if `reconcile` is set.
+        /// Returns `None`, if the VPN was not reconciled yet.
+        pub async fn get_vpn_reconciliation_report(&mut self, reconcile: bool) -> Result<Option<VpnReconciliationReport>, ClientError<GetVpnReconciliationReportError>> {
+
+            let request = tonic::Request::new(administration::GetVpnReconciliationReportRequest { reconcile });
+
+            let response = self.inner.get_vpn_reconciliation_report(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                administration::get_vpn_reconciliation_report_response::Reply::Failure(failure) => {
+                    let error = GetVpnReconciliationReportError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                administration::get_vpn_reconciliation_report_response::Reply::Success(success) => {
+                    let report = success.report
+                        .map(VpnReconciliationReport::try_from)
+                        .transpose()?;
+                    Ok(report)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListAuditEntriesError {
+    #[error("An internal error occurred while listing audit entries:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use opendut_types::audit::{AuditEntry, AuditLogFilter};
+
+    use crate::carl::{extract, ClientError};
+    use crate::carl::audit::ListAuditEntriesError;
+    use crate::proto::services::audit_log;
+    use crate::proto::services::audit_log::audit_log_client::AuditLogClient;
+
+    #[derive(Clone, Debug)]
+    pub struct AuditLog<T> {
+        inner: AuditLogClient<T>,
+    }
+
+    impl<T> AuditLog<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: AuditLogClient<T>) -> AuditLog<T> {
+            AuditLog { inner }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> AuditLog<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = AuditLogClient::new(InterceptedService::new(inner, interceptor));
+            AuditLog {
+                inner: inner_client
+            }
+        }
+
+        pub async fn list_audit_entries(&mut self, filter: AuditLogFilter) -> Result<Vec<AuditEntry>, ClientError<ListAuditEntriesError>> {
+
+            let request = tonic::Request::new(audit_log::ListAuditEntriesRequest::from(filter));
+
+            let response = self.inner.list_audit_entries(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                audit_log::list_audit_entries_response::Reply::Failure(failure) => {
+                    let error = ListAuditEntriesError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                audit_log::list_audit_entries_response::Reply::Success(success) => {
+                    let entries = success.entries.into_iter()
+                        .map(AuditEntry::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(entries)
+                }
+            }
+        }
+    }
+}
@@ -89,10 +89,13 @@ mod client {
                 request.metadata_mut().insert(stream_header::REMOTE_HOST, MetadataValue::from_str(&remote_address.to_string()).unwrap());
 
                 {
-                    let stream_header::ExtraHeaders { client_version: version } = extra_headers;
+                    let stream_header::ExtraHeaders { client_version: version, vpn_endpoint_host } = extra_headers;
                     if let Some(version) = version {
                         request.metadata_mut().insert(stream_header::CLIENT_VERSION, MetadataValue::from_str(&version.value).unwrap());
                     }
+                    if let Some(vpn_endpoint_host) = vpn_endpoint_host {
+                        request.metadata_mut().insert(stream_header::VPN_ENDPOINT_HOST, MetadataValue::from_str(&vpn_endpoint_host.to_string()).unwrap());
+                    }
                 }
 
                 ExplicitSendFutureWrapper::from(
@@ -115,11 +118,14 @@ pub mod stream_header {
     pub const ID: &str = "id";
     pub const REMOTE_HOST: &str = "remote-host";
     pub const CLIENT_VERSION: &str = "client_version";
+    pub const VPN_ENDPOINT_HOST: &str = "vpn-endpoint-host";
 
     #[derive(Debug, Default)]
     pub struct ExtraHeaders {
         /// Version string of the client connecting.
         pub client_version: Option<PeerVersion>,
+        /// Address under which other peers can reach the client, if the VPN requires CARL to distribute it.
+        pub vpn_endpoint_host: Option<std::net::IpAddr>,
     }
 
     #[derive(Debug)]
@@ -5,6 +5,8 @@ use opendut_types::cluster::{ClusterDisplay, ClusterId, ClusterName};
 use opendut_types::cluster::state::ClusterState;
 use opendut_types::peer::PeerId;
 use opendut_types::peer::state::PeerState;
+use opendut_types::project::ProjectId;
+use opendut_types::resources::Revision;
 use opendut_types::ShortName;
 
 #[derive(thiserror::Error, Debug)]
@@ -14,7 +16,21 @@ pub enum CreateClusterConfigurationError {
         cluster_id: ClusterId,
         cluster_name: ClusterName,
         cause: String
-    }
+    },
+    #[error("ClusterConfiguration '{cluster_name}' <{cluster_id}> was modified since revision {expected_revision} and cannot be updated! Reload the configuration and apply the changes again.")]
+    RevisionConflict {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        expected_revision: Revision,
+        actual_revision: Option<Revision>,
+    },
+    #[error("ClusterConfiguration '{cluster_name}' <{cluster_id}> uses peer <{peer_id}>, which neither belongs to nor is shared with project '{project}'.")]
+    PeerOutsideProject {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        peer_id: PeerId,
+        project: ProjectId,
+    },
 }
 
 #[derive(thiserror::Error, Debug, PartialEq)]
@@ -43,6 +59,12 @@ pub enum DeleteClusterConfigurationError {
         cluster_id: ClusterId,
         cluster_name: Option<ClusterName>,
         cause: String
+    },
+    #[error("ClusterConfiguration <{cluster_id}> was modified since revision {expected_revision} and is not deleted! Reload the configuration and check whether it should still be deleted.")]
+    RevisionConflict {
+        cluster_id: ClusterId,
+        expected_revision: Revision,
+        actual_revision: Revision,
     }
 }
 
@@ -67,6 +89,13 @@ pub enum StoreClusterDeploymentError {
         cluster_name: Option<ClusterName>,
         invalid_peers: Vec<PeerId>,
     },
+    #[error("ClusterDeployment for cluster '{cluster_name}' <{cluster_id}> failed, because peer <{peer_id}> neither belongs to nor is shared with project '{project}' anymore.")]
+    PeerOutsideProject {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        peer_id: PeerId,
+        project: ProjectId,
+    },
     #[error("ClusterDeployment for cluster {cluster} could not be changed, due to internal errors:\n  {cause}", cluster=ClusterDisplay::new(cluster_name, cluster_id))]
     Internal {
         cluster_id: ClusterId,
@@ -131,6 +160,7 @@ pub enum ListClusterPeerStatesResponse {
 mod client {
     use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
     use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+    use opendut_types::query::{ListQuery, Page, PageToken};
     use crate::carl::{ClientError, extract};
     use crate::proto::services::cluster_manager;
     use crate::proto::services::cluster_manager::cluster_manager_client::ClusterManagerClient;
@@ -177,9 +207,21 @@ mod client {
         }
 
         pub async fn store_cluster_configuration(&mut self, configuration: ClusterConfiguration) -> Result<ClusterId, ClientError<CreateClusterConfigurationError>> {
+            let (cluster_id, _) = self.store_cluster_configuration_with_precondition(configuration, None).await?;
+            Ok(cluster_id)
+        }
+
+        /// Stores the configuration only if the stored configuration still has the `expected_revision`, returning the new revision.
+        pub async fn store_cluster_configuration_if_unmodified(&mut self, configuration: ClusterConfiguration, expected_revision: Revision) -> Result<Revision, ClientError<CreateClusterConfigurationError>> {
+            let (_, revision) = self.store_cluster_configuration_with_precondition(configuration, Some(expected_revision)).await?;
+            Ok(revision)
+        }
+
+        async fn store_cluster_configuration_with_precondition(&mut self, configuration: ClusterConfiguration, expected_revision: Option<Revision>) -> Result<(ClusterId, Revision), ClientError<CreateClusterConfigurationError>> {
 
             let request = tonic::Request::new(cluster_manager::CreateClusterConfigurationRequest {
                 cluster_configuration: Some(configuration.into()),
+                expected_revision: expected_revision.map(Into::into),
             });
 
             let response = self.inner.create_cluster_configuration(request).await?
@@ -191,16 +233,26 @@ mod client {
                     Err(ClientError::UsageError(error))
                 }
                 cluster_manager::create_cluster_configuration_response::Reply::Success(success) => {
-                    let peer_id = extract!(success.cluster_id)?;
-                    Ok(peer_id)
+                    let cluster_id = extract!(success.cluster_id)?;
+                    Ok((cluster_id, Revision::from(success.revision)))
                 }
             }
         }
 
         pub async fn delete_cluster_configuration(&mut self, cluster_id: ClusterId) -> Result<ClusterConfiguration, ClientError<DeleteClusterConfigurationError>> {
+            self.delete_cluster_configuration_with_precondition(cluster_id, None).await
+        }
+
+        /// Deletes the configuration only if it still has the `expected_revision`.
+        pub async fn delete_cluster_configuration_if_unmodified(&mut self, cluster_id: ClusterId, expected_revision: Revision) -> Result<ClusterConfiguration, ClientError<DeleteClusterConfigurationError>> {
+            self.delete_cluster_configuration_with_precondition(cluster_id, Some(expected_revision)).await
+        }
+
+        async fn delete_cluster_configuration_with_precondition(&mut self, cluster_id: ClusterId, expected_revision: Option<Revision>) -> Result<ClusterConfiguration, ClientError<DeleteClusterConfigurationError>> {
 
             let request = tonic::Request::new(cluster_manager::DeleteClusterConfigurationRequest {
                 cluster_id: Some(cluster_id.into()),
+                expected_revision: expected_revision.map(Into::into),
             });
 
             let response = self.inner.delete_cluster_configuration(request).await?
@@ -219,6 +271,11 @@ mod client {
         }
 
         pub async fn get_cluster_configuration(&mut self, cluster_id: ClusterId) -> Result<ClusterConfiguration, GetClusterConfigurationError> {
+            let (configuration, _) = self.get_cluster_configuration_with_revision(cluster_id).await?;
+            Ok(configuration)
+        }
+
+        pub async fn get_cluster_configuration_with_revision(&mut self, cluster_id: ClusterId) -> Result<(ClusterConfiguration, Revision), GetClusterConfigurationError> {
             let request = tonic::Request::new(cluster_manager::GetClusterConfigurationRequest {
                 id: Some(cluster_id.into()),
             });
@@ -231,11 +288,12 @@ mod client {
                         cluster_manager::get_cluster_configuration_response::Result::Failure(_) => {
                             Err(GetClusterConfigurationError { cluster_id, message: String::from("Failed to get cluster configuration!") })
                         }
-                        cluster_manager::get_cluster_configuration_response::Result::Success(cluster_manager::GetClusterConfigurationSuccess { configuration }) => {
+                        cluster_manager::get_cluster_configuration_response::Result::Success(cluster_manager::GetClusterConfigurationSuccess { configuration, revision }) => {
                             let configuration = configuration
                                 .ok_or(GetClusterConfigurationError { cluster_id, message: String::from("Response contains no cluster configuration!") })?;
-                            ClusterConfiguration::try_from(configuration)
-                                .map_err(|_| GetClusterConfigurationError { cluster_id, message: String::from("Conversion failed for cluster configurations!") })
+                            let configuration = ClusterConfiguration::try_from(configuration)
+                                .map_err(|_| GetClusterConfigurationError { cluster_id, message: String::from("Conversion failed for cluster configurations!") })?;
+                            Ok((configuration, Revision::from(revision)))
                         }
                     }
                 },
@@ -246,7 +304,23 @@ mod client {
         }
 
         pub async fn list_cluster_configurations(&mut self) -> Result<Vec<ClusterConfiguration>, ListClusterConfigurationsError> {
-            let request = tonic::Request::new(cluster_manager::ListClusterConfigurationsRequest {});
+            let configurations = self.list_cluster_configurations_with_revisions().await?
+                .into_iter()
+                .map(|(configuration, _)| configuration)
+                .collect();
+            Ok(configurations)
+        }
+
+        pub async fn list_cluster_configurations_with_revisions(&mut self) -> Result<Vec<(ClusterConfiguration, Revision)>, ListClusterConfigurationsError> {
+            let page = self.query_cluster_configurations(ListQuery::default()).await?;
+            Ok(page.items)
+        }
+
+        /// Lists the cluster configurations matching the query, evaluated in CARL.
+        pub async fn query_cluster_configurations(&mut self, query: ListQuery) -> Result<Page<(ClusterConfiguration, Revision)>, ListClusterConfigurationsError> {
+            let request = tonic::Request::new(cluster_manager::ListClusterConfigurationsRequest {
+                query: Some(query.into()),
+            });
 
             match self.inner.list_cluster_configurations(request).await {
                 Ok(response) => {
@@ -256,11 +330,21 @@ mod client {
                         cluster_manager::list_cluster_configurations_response::Result::Failure(_) => {
                             Err(ListClusterConfigurationsError { message: String::from("Failed to list clusters!") })
                         }
-                        cluster_manager::list_cluster_configurations_response::Result::Success(cluster_manager::ListClusterConfigurationsSuccess { configurations }) => {
-                            configurations.into_iter()
+                        cluster_manager::list_cluster_configurations_response::Result::Success(cluster_manager::ListClusterConfigurationsSuccess { configurations, revisions, next_page_token }) => {
+                            let revisions = revisions.into_iter()
+                                .map(Revision::from)
+                                .chain(std::iter::repeat(Revision::default()));
+                            let items = configurations.into_iter()
                                 .map(ClusterConfiguration::try_from)
-                                .collect::<Result<Vec<ClusterConfiguration>, _>>()
-                                .map_err(|_| ListClusterConfigurationsError { message: String::from("Conversion failed for list of cluster configurations!") })
+                                .zip(revisions)
+                                .map(|(configuration, revision)| configuration.map(|configuration| (configuration, revision)))
+                                .collect::<Result<Vec<_>, _>>()
+                                .map_err(|_| ListClusterConfigurationsError { message: String::from("Conversion failed for list of cluster configurations!") })?;
+                            let next_page_token = next_page_token
+                                .map(|token| token.parse::<PageToken>())
+                                .transpose()
+                                .map_err(|cause| ListClusterConfigurationsError { message: cause.to_string() })?;
+                            Ok(Page { items, next_page_token })
                         }
                     }
                 },
@@ -1,10 +1,14 @@
 use cfg_if::cfg_if;
 
+pub mod administration;
+pub mod audit;
 pub mod broker;
 pub mod cluster;
 pub mod metadata;
+pub mod notification;
 pub mod peer;
 pub mod observer;
+pub mod token;
 
 cfg_if! {
     if #[cfg(any(feature = "client", feature = "wasm-client"))] {
@@ -110,27 +114,39 @@ cfg_if! {
         use opendut_auth::confidential::client::ConfidentialClient;
         use opendut_auth::confidential::tonic_service::TonicAuthenticationService;
 
+        use crate::carl::administration::Administration;
+        use crate::carl::audit::AuditLog;
         use crate::carl::cluster::ClusterManager;
         use crate::carl::metadata::MetadataProvider;
+        use crate::carl::notification::NotificationManager;
         use crate::carl::peer::PeersRegistrar;
         use crate::carl::broker::PeerMessagingBroker;
         use crate::carl::observer::ObserverMessagingBroker;
+        use crate::carl::token::TokenManager;
 
+        use crate::proto::services::administration::administration_client::AdministrationClient;
+        use crate::proto::services::audit_log::audit_log_client::AuditLogClient;
         use crate::proto::services::cluster_manager::cluster_manager_client::ClusterManagerClient;
         use crate::proto::services::metadata_provider::metadata_provider_client::MetadataProviderClient;
+        use crate::proto::services::notification_manager::notification_manager_client::NotificationManagerClient;
         use crate::proto::services::peer_manager::peer_manager_client::PeerManagerClient;
         use crate::proto::services::peer_messaging_broker::peer_messaging_broker_client::PeerMessagingBrokerClient;
         use crate::proto::services::observer_messaging_broker::observer_messaging_broker_client::ObserverMessagingBrokerClient;
+        use crate::proto::services::token_manager::token_manager_client::TokenManagerClient;
 
         use tower::ServiceBuilder;
 
         #[derive(Debug, Clone)]
         pub struct CarlClient {
+            pub administration: Administration<TonicAuthenticationService>,
+            pub audit: AuditLog<TonicAuthenticationService>,
             pub broker: PeerMessagingBroker<TonicAuthenticationService>,
             pub cluster: ClusterManager<TonicAuthenticationService>,
             pub metadata: MetadataProvider<TonicAuthenticationService>,
+            pub notifications: NotificationManager<TonicAuthenticationService>,
             pub peers: PeersRegistrar<TonicAuthenticationService>,
             pub observer: ObserverMessagingBroker<TonicAuthenticationService>,
+            pub tokens: TokenManager<TonicAuthenticationService>,
         }
 
         pub enum CaCertInfo {
@@ -170,6 +186,10 @@ cfg_if! {
                         debug!("Using override for verified domain name of '{domain_name_override}'.");
                         config = config.domain_name(domain_name_override);
                     }
+
+                    if let Some(client_identity) = load_client_identity(settings)? {
+                        config = config.identity(client_identity);
+                    }
                     config
                 };
 
@@ -181,15 +201,23 @@ cfg_if! {
                     .tls_config(tls_config)
                     .map_err(|cause| InitializationError::TlsConfiguration { message: String::from("Failed to initialize secure channel with specified TLS configuration"), cause: cause.into() })?;
 
-                let oidc_client = ConfidentialClient::from_settings(settings).await
-                    .map_err(|cause| InitializationError::OidcConfiguration { message: String::from("Failed to initialize OIDC authentication manager"), cause: cause.into() })?;
-                match oidc_client {
-                    None => {}
-                    Some(ref client) => {
-                        client.check_login().await
-                        .map_err(|cause| InitializationError::ConnectError { address: address.clone(), cause: cause.into() })?;
+                let personal_access_token = settings.get_string("network.personal.access.token").unwrap_or_default();
+
+                let oidc_client = if personal_access_token.is_empty() {
+                    let oidc_client = ConfidentialClient::from_settings(settings).await
+                        .map_err(|cause| InitializationError::OidcConfiguration { message: String::from("Failed to initialize OIDC authentication manager"), cause: cause.into() })?;
+                    match oidc_client {
+                        None => {}
+                        Some(ref client) => {
+                            client.check_login().await
+                            .map_err(|cause| InitializationError::ConnectError { address: address.clone(), cause: cause.into() })?;
+                        }
                     }
-                }
+                    oidc_client
+                } else {
+                    debug!("Authenticating with personal access token instead of OIDC client.");
+                    None
+                };
 
                 debug!("Set up endpoint for connection to CARL at '{address}'.");
                 let channel = endpoint.connect().await
@@ -197,18 +225,45 @@ cfg_if! {
                 info!("Connected to CARL at '{address}'.");
 
                 let auth_svc = ServiceBuilder::new()
-                    .layer_fn(|channel| TonicAuthenticationService::new(channel, oidc_client.clone()))
+                    .layer_fn(|channel| {
+                        if personal_access_token.is_empty() {
+                            TonicAuthenticationService::new(channel, oidc_client.clone())
+                        } else {
+                            TonicAuthenticationService::with_static_token(channel, &personal_access_token)
+                        }
+                    })
                     .service(channel);
 
                 Ok(CarlClient {
+                    administration: Administration::new(AdministrationClient::new(Clone::clone(&auth_svc))),
+                    audit: AuditLog::new(AuditLogClient::new(Clone::clone(&auth_svc))),
                     broker: PeerMessagingBroker::new(PeerMessagingBrokerClient::new(Clone::clone(&auth_svc))),
                     cluster: ClusterManager::new(ClusterManagerClient::new(Clone::clone(&auth_svc))),
                     metadata: MetadataProvider::new(MetadataProviderClient::new(Clone::clone(&auth_svc))),
+                    notifications: NotificationManager::new(NotificationManagerClient::new(Clone::clone(&auth_svc))),
                     peers: PeersRegistrar::new(PeerManagerClient::new(Clone::clone(&auth_svc))),
                     observer: ObserverMessagingBroker::new(ObserverMessagingBrokerClient::new(Clone::clone(&auth_svc))),
+                    tokens: TokenManager::new(TokenManagerClient::new(Clone::clone(&auth_svc))),
                 })
             }
         }
+
+        /// Loads the certificate and key for authenticating via mutual TLS, if configured, as EDGAR does when CARL issued a client certificate to it.
+        fn load_client_identity(settings: &config::Config) -> Result<Option<tonic::transport::Identity>, InitializationError> {
+            let certificate_path = settings.get_string("network.tls.client.certificate").unwrap_or_default();
+            let key_path = settings.get_string("network.tls.client.key").unwrap_or_default();
+
+            if certificate_path.is_empty() || key_path.is_empty() {
+                return Ok(None);
+            }
+
+            let read = |path: &str| std::fs::read_to_string(path)
+                .map_err(|cause| InitializationError::TlsConfiguration { message: format!("Failed to read client certificate or key from path '{path}'"), cause: cause.into() });
+
+            debug!("Using TLS client certificate: {certificate_path}");
+            let identity = tonic::transport::Identity::from_pem(read(&certificate_path)?, read(&key_path)?);
+            Ok(Some(identity))
+        }
     }
 }
 
@@ -222,13 +277,17 @@ pub mod wasm {
     use crate::carl::cluster::ClusterManager;
     use crate::carl::InitializationError;
     use crate::carl::metadata::MetadataProvider;
+    use crate::carl::observer::ObserverMessagingBroker;
     use crate::carl::peer::PeersRegistrar;
+    use crate::carl::token::TokenManager;
 
     #[derive(Debug, Clone)]
     pub struct CarlClient {
         pub cluster: ClusterManager<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub metadata: MetadataProvider<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
+        pub observer: ObserverMessagingBroker<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub peers: PeersRegistrar<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
+        pub tokens: TokenManager<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
     }
 
     impl CarlClient {
@@ -247,7 +306,9 @@ pub mod wasm {
             Ok(CarlClient {
                 cluster: ClusterManager::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 metadata: MetadataProvider::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
+                observer: ObserverMessagingBroker::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 peers: PeersRegistrar::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
+                tokens: TokenManager::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
             })
         }
     }
@@ -0,0 +1,161 @@
+use opendut_types::notification::{NotificationSubscriptionId, NotificationSubscriptionName};
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum CreateNotificationSubscriptionError {
+    #[error("Notification subscription '{name}' could not be created, because it does not subscribe to any events!")]
+    NoEvents {
+        name: NotificationSubscriptionName,
+    },
+    #[error("Notification subscription '{name}' could not be created, because its webhook secret is illegal:\n  {cause}")]
+    IllegalSecret {
+        name: NotificationSubscriptionName,
+        cause: String,
+    },
+    #[error("An internal error occurred while creating notification subscription '{name}':\n  {cause}")]
+    Internal {
+        name: NotificationSubscriptionName,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ListNotificationSubscriptionsError {
+    #[error("An internal error occurred while listing notification subscriptions:\n  {cause}")]
+    Internal {
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum DeleteNotificationSubscriptionError {
+    #[error("Notification subscription <{id}> could not be deleted, because it does not exist!")]
+    NotFound {
+        id: NotificationSubscriptionId,
+    },
+    #[error("An internal error occurred while deleting notification subscription <{id}>:\n  {cause}")]
+    Internal {
+        id: NotificationSubscriptionId,
+        cause: String,
+    },
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use opendut_types::notification::{NotificationSubscription, NotificationSubscriptionId};
+    use opendut_types::project::ProjectId;
+
+    use crate::carl::{extract, ClientError};
+    use crate::carl::notification::{CreateNotificationSubscriptionError, DeleteNotificationSubscriptionError, ListNotificationSubscriptionsError};
+    use crate::proto::services::notification_manager;
+    use crate::proto::services::notification_manager::notification_manager_client::NotificationManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct NotificationManager<T> {
+        inner: NotificationManagerClient<T>,
+    }
+
+    impl<T> NotificationManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: NotificationManagerClient<T>) -> NotificationManager<T> {
+            NotificationManager { inner }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> NotificationManager<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = NotificationManagerClient::new(InterceptedService::new(inner, interceptor));
+            NotificationManager {
+                inner: inner_client
+            }
+        }
+
+        pub async fn create_notification_subscription(&mut self, subscription: NotificationSubscription) -> Result<NotificationSubscriptionId, ClientError<CreateNotificationSubscriptionError>> {
+
+            let request = tonic::Request::new(notification_manager::CreateNotificationSubscriptionRequest {
+                subscription: Some(subscription.into()),
+            });
+
+            let response = self.inner.create_notification_subscription(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                notification_manager::create_notification_subscription_response::Reply::Failure(failure) => {
+                    let error = CreateNotificationSubscriptionError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                notification_manager::create_notification_subscription_response::Reply::Success(success) => {
+                    let id = extract!(success.id)?;
+                    Ok(id)
+                }
+            }
+        }
+
+        /// Lists the notification subscriptions of the projects, which the current user is a member of. Webhook secrets are redacted.
+        pub async fn list_notification_subscriptions(&mut self, project: Option<ProjectId>) -> Result<Vec<NotificationSubscription>, ClientError<ListNotificationSubscriptionsError>> {
+
+            let request = tonic::Request::new(notification_manager::ListNotificationSubscriptionsRequest {
+                project: project.map(Into::into),
+            });
+
+            let response = self.inner.list_notification_subscriptions(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                notification_manager::list_notification_subscriptions_response::Reply::Failure(failure) => {
+                    let error = ListNotificationSubscriptionsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                notification_manager::list_notification_subscriptions_response::Reply::Success(success) => {
+                    let subscriptions = success.subscriptions.into_iter()
+                        .map(NotificationSubscription::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(subscriptions)
+                }
+            }
+        }
+
+        pub async fn delete_notification_subscription(&mut self, id: NotificationSubscriptionId) -> Result<NotificationSubscription, ClientError<DeleteNotificationSubscriptionError>> {
+
+            let request = tonic::Request::new(notification_manager::DeleteNotificationSubscriptionRequest {
+                id: Some(id.into()),
+            });
+
+            let response = self.inner.delete_notification_subscription(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                notification_manager::delete_notification_subscription_response::Reply::Failure(failure) => {
+                    let error = DeleteNotificationSubscriptionError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                notification_manager::delete_notification_subscription_response::Reply::Success(success) => {
+                    let subscription = extract!(success.subscription)?;
+                    Ok(subscription)
+                }
+            }
+        }
+    }
+}
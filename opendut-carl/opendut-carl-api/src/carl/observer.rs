@@ -1,9 +1,12 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Duration;
-#[cfg(feature = "client")]
+#[cfg(any(feature = "client", feature = "wasm-client"))]
 pub use client::*;
-use opendut_types::peer::PeerId;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::peer::{PeerDescriptor, PeerId};
 use opendut_types::peer::state::PeerConnectionState;
+use opendut_types::resources::Revision;
+use uuid::Uuid;
 
 pub mod error {
     #[derive(thiserror::Error, Debug)]
@@ -13,6 +16,10 @@ pub mod error {
     #[derive(thiserror::Error, Debug)]
     #[error("{message}")]
     pub struct OpenStream { pub message: String }
+
+    #[derive(thiserror::Error, Debug)]
+    #[error("{message}")]
+    pub struct Watch { pub message: String }
 }
 
 #[derive(Debug, Clone)]
@@ -42,13 +49,70 @@ pub struct WaitForPeersOnlineResponse {
     pub status: WaitForPeersOnlineResponseStatus,
 }
 
-#[cfg(feature = "client")]
+
+/// Kind of resource, whose modifications can be watched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchedResourceKind {
+    PeerDescriptor,
+    PeerConnectionState,
+    ClusterConfiguration,
+    ClusterDeployment,
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchRequest {
+    pub kind: WatchedResourceKind,
+    /// Revision up to which the client already knows the resources, e.g. from a previous watch.
+    /// The initial snapshot omits the values of resources, which were not modified since.
+    pub resume_revision: Option<Revision>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchedResource {
+    PeerDescriptor(PeerDescriptor),
+    PeerConnectionState { peer_id: PeerId, state: PeerConnectionState },
+    ClusterConfiguration(ClusterConfiguration),
+    ClusterDeployment(ClusterDeployment),
+}
+impl WatchedResource {
+    pub fn id(&self) -> Uuid {
+        match self {
+            WatchedResource::PeerDescriptor(peer) => peer.id.uuid,
+            WatchedResource::PeerConnectionState { peer_id, .. } => peer_id.uuid,
+            WatchedResource::ClusterConfiguration(configuration) => configuration.id.0,
+            WatchedResource::ClusterDeployment(deployment) => deployment.id.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchEvent {
+    /// Lists all currently existing resources. Resources known to the client, which are not listed, were removed.
+    /// Sent when the watch starts and again, if the client fell too far behind to receive every single modification.
+    Snapshot { entries: Vec<WatchSnapshotEntry>, revision: Revision },
+    /// Resource was created or updated.
+    Inserted { resource: WatchedResource, revision: Revision },
+    Removed { resource: WatchedResource, revision: Revision },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchSnapshotEntry {
+    pub id: Uuid,
+    pub revision: Revision,
+    /// `None`, if the resource was not modified since the requested resume revision.
+    pub resource: Option<WatchedResource>,
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
 mod client {
     use std::collections::HashSet;
+    use std::pin::Pin;
     use std::time::Duration;
-    use crate::carl::observer::error;
+    use futures::{Stream, StreamExt};
+    use crate::carl::observer::{error, WatchEvent, WatchedResourceKind};
     use crate::proto::services::observer_messaging_broker::observer_messaging_broker_client::ObserverMessagingBrokerClient;
     use opendut_types::peer::PeerId;
+    use opendut_types::resources::Revision;
     use tonic::codegen::{Body, Bytes, InterceptedService, StdError};
 
     #[derive(Clone, Debug)]
@@ -94,6 +158,8 @@ mod client {
     
     pub type WaitForPeerOnlineResponseStream = tonic::Streaming<crate::proto::services::observer_messaging_broker::WaitForPeersOnlineResponse>;
 
+    pub type WatchEventStream = Pin<Box<dyn Stream<Item=Result<WatchEvent, error::Watch>> + Send>>;
+
     impl<T> ObserverMessagingBroker<T>
     where T: tonic::client::GrpcService<tonic::body::BoxBody>,
           T::Error: Into<StdError>,
@@ -117,6 +183,29 @@ mod client {
             Ok(inbound)
 
         }
+
+        /// Streams the modifications of all resources of the requested kind, starting with a snapshot of the current resources.
+        pub async fn watch(&mut self, kind: WatchedResourceKind, resume_revision: Option<Revision>) -> Result<WatchEventStream, error::OpenStream> {
+            let request = crate::carl::observer::WatchRequest {
+                kind,
+                resume_revision,
+            };
+            let proto_request: crate::proto::services::observer_messaging_broker::WatchRequest = request.into();
+            let response = self.inner
+                .watch(proto_request)
+                .await
+                .map_err(|cause| error::OpenStream { message: format!("Error while opening stream: {cause}") })?;
+
+            let inbound = response.into_inner()
+                .map(|response| {
+                    let response = response
+                        .map_err(|cause| error::Watch { message: format!("Error while receiving watch event: {}", cause.message()) })?;
+                    WatchEvent::try_from(response)
+                        .map_err(|cause| error::Watch { message: format!("Failed to decode watch event: {cause}") })
+                });
+
+            Ok(Box::pin(inbound))
+        }
     }
 
 }
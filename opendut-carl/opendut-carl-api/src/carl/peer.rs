@@ -2,9 +2,12 @@
 pub use client::*;
 use opendut_types::cluster::ClusterId;
 use opendut_types::peer::{PeerDisplay, PeerId, PeerName};
+use opendut_types::peer::setup::PeerSetupId;
 use opendut_types::peer::state::PeerState;
+use opendut_types::resources::Revision;
 use opendut_types::ShortName;
 use opendut_types::topology::DeviceId;
+use opendut_types::util::net::{Certificate, ClientCredentials};
 
 #[derive(thiserror::Error, Debug)]
 pub enum StorePeerDescriptorError {
@@ -26,6 +29,13 @@ pub enum StorePeerDescriptorError {
         peer_id: PeerId,
         peer_name: PeerName,
         cause: String
+    },
+    #[error("Peer '{peer_name}' <{peer_id}> was modified since revision {expected_revision} and cannot be updated! Reload the peer and apply the changes again.")]
+    RevisionConflict {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        expected_revision: Revision,
+        actual_revision: Option<Revision>,
     }
 }
 
@@ -56,6 +66,12 @@ pub enum DeletePeerDescriptorError {
         peer_id: PeerId,
         peer_name: Option<PeerName>,
         cause: String,
+    },
+    #[error("Peer <{peer_id}> was modified since revision {expected_revision} and is not deleted! Reload the peer and check whether it should still be deleted.")]
+    RevisionConflict {
+        peer_id: PeerId,
+        expected_revision: Revision,
+        actual_revision: Revision,
     }
 }
 
@@ -117,6 +133,115 @@ pub enum IllegalDevicesError {
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum ListPeerSetupsError {
+    #[error("An internal error occurred while listing the Setup-Strings:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum RedeemPeerSetupError {
+    #[error("Setup-String <{setup_id}> is not known to CARL! Generate a new Setup-String for the peer.")]
+    SetupNotFound {
+        setup_id: PeerSetupId,
+    },
+    #[error("Setup-String <{setup_id}> was already used! Generate a new Setup-String for the peer.")]
+    AlreadyUsed {
+        setup_id: PeerSetupId,
+    },
+    #[error("Setup-String <{setup_id}> is expired! Generate a new Setup-String for the peer.")]
+    Expired {
+        setup_id: PeerSetupId,
+    },
+    #[error("Setup-String <{setup_id}> was revoked! Generate a new Setup-String for the peer.")]
+    Revoked {
+        setup_id: PeerSetupId,
+    },
+    #[error("An internal error occurred while redeeming Setup-String <{setup_id}>:\n  {cause}")]
+    Internal {
+        setup_id: PeerSetupId,
+        cause: String
+    }
+}
+
+/// Result of redeeming a Setup-String.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RedeemedPeerSetup {
+    pub setup_id: PeerSetupId,
+    /// OIDC client credentials, which replace the ones contained in the Setup-String.
+    /// `None`, if CARL does not issue individual OIDC clients to peers.
+    pub credentials: Option<ClientCredentials>,
+    /// Certificate issued for the requested key, with which EDGAR authenticates via mutual TLS.
+    /// `None`, if CARL does not issue client certificates.
+    pub client_certificate: Option<Certificate>,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum RevokePeerSetupError {
+    #[error("Setup-String <{setup_id}> could not be revoked, because it is not known to CARL!")]
+    SetupNotFound {
+        setup_id: PeerSetupId,
+    },
+    #[error("Setup-String <{setup_id}> could not be revoked, because it was already used! Delete or decommission the peer instead.")]
+    AlreadyUsed {
+        setup_id: PeerSetupId,
+    },
+    #[error("Setup-String <{setup_id}> is already revoked!")]
+    AlreadyRevoked {
+        setup_id: PeerSetupId,
+    },
+    #[error("An internal error occurred while revoking Setup-String <{setup_id}>:\n  {cause}")]
+    Internal {
+        setup_id: PeerSetupId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum DecommissionPeerError {
+    #[error("Peer <{peer_id}> could not be decommissioned, because a peer with that id does not exist!")]
+    PeerNotFound {
+        peer_id: PeerId,
+    },
+    #[error("An internal error occurred while decommissioning peer <{peer_id}>:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum RotatePeerCredentialsError {
+    #[error("Credentials of peer <{peer_id}> could not be rotated, because a peer with that id does not exist!")]
+    PeerNotFound {
+        peer_id: PeerId,
+    },
+    #[error("Credentials of peer <{peer_id}> could not be rotated, because the peer is not connected! Start EDGAR on the peer and try again.")]
+    PeerNotConnected {
+        peer_id: PeerId,
+    },
+    #[error("Credentials of peer <{peer_id}> cannot be rotated: {reason}")]
+    NotSupported {
+        peer_id: PeerId,
+        reason: String,
+    },
+    #[error("Credentials of peer <{peer_id}> are already being rotated! Wait for the rotation to finish, respectively for EDGAR to reconnect after an unconfirmed rotation, and try again.")]
+    AlreadyInProgress {
+        peer_id: PeerId,
+    },
+    #[error("Peer <{peer_id}> did not confirm that it stored its rotated credentials! Its previous and new credentials stay valid, until it reconnects with one of them.")]
+    Unconfirmed {
+        peer_id: PeerId,
+    },
+    #[error("An internal error occurred while rotating credentials of peer <{peer_id}>:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        cause: String
+    }
+}
+
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 mod client {
     use std::collections::HashMap;
@@ -125,11 +250,17 @@ mod client {
     use opendut_types::cleo::CleoSetup;
 
     use opendut_types::peer::{PeerDescriptor, PeerId, PeerSetup};
+    use opendut_types::peer::certificate::{ClientCertificateId, RotatedPeerCredentials};
+    use opendut_types::peer::decommission::PeerDecommissionReport;
+    use opendut_types::peer::setup::{PeerSetupId, PeerSetupRecord};
     use opendut_types::peer::state::PeerState;
+    use opendut_types::query::{ListQuery, Page, PageToken};
+    use opendut_types::resources::Revision;
     use opendut_types::topology::DeviceDescriptor;
+    use opendut_types::util::net::{Certificate, ClientCredentials, ClientId};
 
     use crate::carl::{ClientError, extract};
-    use crate::carl::peer::{DeletePeerDescriptorError, GetPeerDescriptorError, GetPeerStateError, ListDevicesError, ListPeerDescriptorsError, ListPeerStatesError, StorePeerDescriptorError};
+    use crate::carl::peer::{DecommissionPeerError, DeletePeerDescriptorError, GetPeerDescriptorError, GetPeerStateError, ListDevicesError, ListPeerDescriptorsError, ListPeerSetupsError, ListPeerStatesError, RedeemedPeerSetup, RedeemPeerSetupError, RevokePeerSetupError, RotatePeerCredentialsError, StorePeerDescriptorError};
     use crate::proto::services::peer_manager;
     use crate::proto::services::peer_manager::peer_manager_client::PeerManagerClient;
 
@@ -174,9 +305,22 @@ mod client {
         }
 
         pub async fn store_peer_descriptor(&mut self, descriptor: PeerDescriptor) -> Result<PeerId, ClientError<StorePeerDescriptorError>> {
+            let (peer_id, _) = self.store_peer_descriptor_with_precondition(descriptor, None).await?;
+            Ok(peer_id)
+        }
+
+        /// Stores the descriptor only if the stored peer still has the `expected_revision`, returning the new revision.
+        pub async fn store_peer_descriptor_if_unmodified(&mut self, descriptor: PeerDescriptor, expected_revision: Revision) -> Result<Revision, ClientError<StorePeerDescriptorError>> {
+            let (_, revision) = self.store_peer_descriptor_with_precondition(descriptor, Some(expected_revision)).await?;
+            Ok(revision)
+        }
+
+        /// Stores the descriptor, failing with a revision conflict if an `expected_revision` is given and the stored peer has a different one.
+        async fn store_peer_descriptor_with_precondition(&mut self, descriptor: PeerDescriptor, expected_revision: Option<Revision>) -> Result<(PeerId, Revision), ClientError<StorePeerDescriptorError>> {
 
             let request = tonic::Request::new(peer_manager::StorePeerDescriptorRequest {
                 peer: Some(descriptor.into()),
+                expected_revision: expected_revision.map(Into::into),
             });
 
             let response = self.inner.store_peer_descriptor(request).await?
@@ -189,15 +333,25 @@ mod client {
                 }
                 peer_manager::store_peer_descriptor_response::Reply::Success(success) => {
                     let peer_id = extract!(success.peer_id)?;
-                    Ok(peer_id)
+                    Ok((peer_id, Revision::from(success.revision)))
                 }
             }
         }
 
         pub async fn delete_peer_descriptor(&mut self, peer_id: PeerId) -> Result<PeerId, ClientError<DeletePeerDescriptorError>> {
+            self.delete_peer_descriptor_with_precondition(peer_id, None).await
+        }
+
+        /// Deletes the peer only if it still has the `expected_revision`.
+        pub async fn delete_peer_descriptor_if_unmodified(&mut self, peer_id: PeerId, expected_revision: Revision) -> Result<PeerId, ClientError<DeletePeerDescriptorError>> {
+            self.delete_peer_descriptor_with_precondition(peer_id, Some(expected_revision)).await
+        }
+
+        async fn delete_peer_descriptor_with_precondition(&mut self, peer_id: PeerId, expected_revision: Option<Revision>) -> Result<PeerId, ClientError<DeletePeerDescriptorError>> {
 
             let request = tonic::Request::new(peer_manager::DeletePeerDescriptorRequest {
                 peer_id: Some(peer_id.into()),
+                expected_revision: expected_revision.map(Into::into),
             });
 
             let response = self.inner.delete_peer_descriptor(request).await?
@@ -216,6 +370,11 @@ mod client {
         }
 
         pub async fn get_peer_descriptor(&mut self, peer_id: PeerId) -> Result<PeerDescriptor, ClientError<GetPeerDescriptorError>> {
+            let (peer_descriptor, _) = self.get_peer_descriptor_with_revision(peer_id).await?;
+            Ok(peer_descriptor)
+        }
+
+        pub async fn get_peer_descriptor_with_revision(&mut self, peer_id: PeerId) -> Result<(PeerDescriptor, Revision), ClientError<GetPeerDescriptorError>> {
 
             let request = tonic::Request::new(peer_manager::GetPeerDescriptorRequest {
                 peer_id: Some(peer_id.into()),
@@ -231,14 +390,30 @@ mod client {
                 }
                 peer_manager::get_peer_descriptor_response::Reply::Success(success) => {
                     let peer_descriptor = extract!(success.descriptor)?;
-                    Ok(peer_descriptor)
+                    Ok((peer_descriptor, Revision::from(success.revision)))
                 }
             }
         }
 
         pub async fn list_peer_descriptors(&mut self) -> Result<Vec<PeerDescriptor>, ClientError<ListPeerDescriptorsError>> {
+            let peers = self.list_peer_descriptors_with_revisions().await?
+                .into_iter()
+                .map(|(peer_descriptor, _)| peer_descriptor)
+                .collect();
+            Ok(peers)
+        }
 
-            let request = tonic::Request::new(peer_manager::ListPeerDescriptorsRequest {});
+        pub async fn list_peer_descriptors_with_revisions(&mut self) -> Result<Vec<(PeerDescriptor, Revision)>, ClientError<ListPeerDescriptorsError>> {
+            let page = self.query_peer_descriptors(ListQuery::default()).await?;
+            Ok(page.items)
+        }
+
+        /// Lists the peers matching the query, evaluated in CARL.
+        pub async fn query_peer_descriptors(&mut self, query: ListQuery) -> Result<Page<(PeerDescriptor, Revision)>, ClientError<ListPeerDescriptorsError>> {
+
+            let request = tonic::Request::new(peer_manager::ListPeerDescriptorsRequest {
+                query: Some(query.into()),
+            });
 
             let response = self.inner.list_peer_descriptors(request).await?
                 .into_inner();
@@ -249,10 +424,19 @@ mod client {
                     Err(ClientError::UsageError(error))
                 }
                 peer_manager::list_peer_descriptors_response::Reply::Success(success) => {
-                    Ok(success.peers.into_iter()
+                    let revisions = success.revisions.into_iter()
+                        .map(Revision::from)
+                        .chain(std::iter::repeat(Revision::default()));
+                    let peers = success.peers.into_iter()
                         .map(PeerDescriptor::try_from)
-                        .collect::<Result<Vec<_>, _>>()?
-                    )
+                        .zip(revisions)
+                        .map(|(peer_descriptor, revision)| peer_descriptor.map(|peer_descriptor| (peer_descriptor, revision)))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let next_page_token = success.next_page_token
+                        .map(|token| token.parse::<PageToken>())
+                        .transpose()
+                        .map_err(|cause| ClientError::InvalidResponse(cause.to_string()))?;
+                    Ok(Page { items: peers, next_page_token })
                 }
             }
         }
@@ -371,16 +555,162 @@ mod client {
             }
         }
 
+        /// Lists the Setup-Strings issued for all peers, including used, expired and revoked ones.
+        pub async fn list_peer_setups(&mut self) -> Result<Vec<PeerSetupRecord>, ClientError<ListPeerSetupsError>> {
+
+            let request = tonic::Request::new(peer_manager::ListPeerSetupsRequest {});
+
+            let response = self.inner.list_peer_setups(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::list_peer_setups_response::Reply::Failure(failure) => {
+                    let error = ListPeerSetupsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::list_peer_setups_response::Reply::Success(success) => {
+                    let setups = success.setups.into_iter()
+                        .map(PeerSetupRecord::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(setups)
+                }
+            }
+        }
+
+        /// Marks the Setup-String as used by the given peer. Fails, if it was used before, is expired or was revoked.
+        /// The OIDC client credentials contained in the Setup-String become invalid. The returned ones have to be used instead.
+        /// If the Setup-String requests a client certificate, the DER-encoded certificate signing request has to be passed.
+        pub async fn redeem_peer_setup(&mut self, setup_id: PeerSetupId, peer_id: PeerId, certificate_signing_request: Option<Vec<u8>>) -> Result<RedeemedPeerSetup, ClientError<RedeemPeerSetupError>> {
+
+            let request = tonic::Request::new(peer_manager::RedeemPeerSetupRequest {
+                setup_id: Some(setup_id.into()),
+                peer_id: Some(peer_id.into()),
+                certificate_signing_request,
+            });
+
+            let response = self.inner.redeem_peer_setup(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::redeem_peer_setup_response::Reply::Failure(failure) => {
+                    let error = RedeemPeerSetupError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::redeem_peer_setup_response::Reply::Success(success) => {
+                    let setup_id = extract!(success.setup_id)?;
+                    let credentials = match (success.client_id, success.client_secret) {
+                        (Some(client_id), Some(client_secret)) => Some(ClientCredentials {
+                            client_id: client_id.try_into()?,
+                            client_secret: client_secret.try_into()?,
+                        }),
+                        _ => None,
+                    };
+                    let client_certificate = success.client_certificate
+                        .map(Certificate::try_from)
+                        .transpose()?;
+                    Ok(RedeemedPeerSetup { setup_id, credentials, client_certificate })
+                }
+            }
+        }
+
+        /// Revokes a Setup-String, which was not used yet, so that it cannot be used for setting up EDGAR anymore.
+        pub async fn revoke_peer_setup(&mut self, setup_id: PeerSetupId) -> Result<PeerSetupId, ClientError<RevokePeerSetupError>> {
+
+            let request = tonic::Request::new(peer_manager::RevokePeerSetupRequest {
+                setup_id: Some(setup_id.into()),
+            });
+
+            let response = self.inner.revoke_peer_setup(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::revoke_peer_setup_response::Reply::Failure(failure) => {
+                    let error = RevokePeerSetupError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::revoke_peer_setup_response::Reply::Success(success) => {
+                    let setup_id = extract!(success.setup_id)?;
+                    Ok(setup_id)
+                }
+            }
+        }
+
+        /// Revokes everything, which grants the peer access, i.e. its Setup-Strings, OIDC clients, VPN peer and stream.
+        /// The peer descriptor is kept. Check the report, whether all steps succeeded.
+        pub async fn decommission_peer(&mut self, peer_id: PeerId) -> Result<PeerDecommissionReport, ClientError<DecommissionPeerError>> {
+
+            let request = tonic::Request::new(peer_manager::DecommissionPeerRequest {
+                peer_id: Some(peer_id.into()),
+            });
+
+            let response = self.inner.decommission_peer(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::decommission_peer_response::Reply::Failure(failure) => {
+                    let error = DecommissionPeerError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::decommission_peer_response::Reply::Success(success) => {
+                    let report = extract!(success.report)?;
+                    Ok(report)
+                }
+            }
+        }
+
+        /// Issues new OIDC client credentials and a new client certificate for the connected peer and sends them to its EDGAR, which stores them.
+        /// Returns the IDs of the new credentials.
+        pub async fn rotate_peer_credentials(&mut self, peer_id: PeerId, user_id: String) -> Result<RotatedPeerCredentials, ClientError<RotatePeerCredentialsError>> {
+
+            let request = tonic::Request::new(peer_manager::RotatePeerCredentialsRequest {
+                peer_id: Some(peer_id.into()),
+                user_id,
+            });
+
+            let response = self.inner.rotate_peer_credentials(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::rotate_peer_credentials_response::Reply::Failure(failure) => {
+                    let error = RotatePeerCredentialsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::rotate_peer_credentials_response::Reply::Success(success) => {
+                    let client_id = success.client_id
+                        .map(ClientId::try_from)
+                        .transpose()?;
+                    let client_certificate_id = success.client_certificate_id
+                        .map(ClientCertificateId::try_from)
+                        .transpose()?;
+                    Ok(RotatedPeerCredentials { client_id, client_certificate_id })
+                }
+            }
+        }
+
         pub async fn list_devices(&mut self) -> Result<Vec<DeviceDescriptor>, ListDevicesError> {
-            let request = tonic::Request::new(peer_manager::ListDevicesRequest {});
+            let page = self.query_devices(ListQuery::default()).await?;
+            Ok(page.items)
+        }
+
+        /// Lists the devices matching the query, evaluated in CARL.
+        pub async fn query_devices(&mut self, query: ListQuery) -> Result<Page<DeviceDescriptor>, ListDevicesError> {
+            let request = tonic::Request::new(peer_manager::ListDevicesRequest {
+                query: Some(query.into()),
+            });
 
             match self.inner.list_devices(request).await {
                 Ok(response) => {
-                    response.into_inner().devices
+                    let response = response.into_inner();
+                    let items = response.devices
                         .into_iter()
                         .map(DeviceDescriptor::try_from)
                         .collect::<Result<_, _>>()
-                        .map_err(|cause| ListDevicesError::Internal { cause: cause.to_string() })
+                        .map_err(|cause| ListDevicesError::Internal { cause: cause.to_string() })?;
+                    let next_page_token = response.next_page_token
+                        .map(|token| token.parse::<PageToken>())
+                        .transpose()
+                        .map_err(|cause| ListDevicesError::Internal { cause: cause.to_string() })?;
+                    Ok(Page { items, next_page_token })
                 },
                 Err(status) => {
                     Err(ListDevicesError::Internal { cause: format!("gRPC failure: {status}") })
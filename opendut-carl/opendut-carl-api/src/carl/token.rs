@@ -0,0 +1,186 @@
+use std::fmt;
+use std::time::Duration;
+
+use opendut_types::user::token::{PersonalAccessTokenDescriptor, PersonalAccessTokenId, PersonalAccessTokenScope};
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+
+/// Newly created personal access token. The token itself is only returned once and cannot be retrieved later.
+#[derive(Clone)]
+pub struct CreatedPersonalAccessToken {
+    pub descriptor: PersonalAccessTokenDescriptor,
+    pub token: String,
+}
+
+impl fmt::Debug for CreatedPersonalAccessToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreatedPersonalAccessToken")
+            .field("descriptor", &self.descriptor)
+            .field("token", &"<redacted>")
+            .finish()
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum CreatePersonalAccessTokenError {
+    #[error("Personal access token could not be created, because you do not have the role '{scope}' yourself!")]
+    ScopeNotPermitted {
+        scope: PersonalAccessTokenScope,
+    },
+    #[error("Personal access token could not be created, because it has to expire after more than zero and at most {} days, but {} days were requested!", maximum.as_secs() / 86400, requested.as_secs() / 86400)]
+    InvalidValidity {
+        requested: Duration,
+        maximum: Duration,
+    },
+    #[error("An internal error occurred while creating a personal access token:\n  {cause}")]
+    Internal {
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ListPersonalAccessTokensError {
+    #[error("Only administrators may list the personal access tokens of all users!")]
+    NotPermitted,
+    #[error("An internal error occurred while listing personal access tokens:\n  {cause}")]
+    Internal {
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum RevokePersonalAccessTokenError {
+    #[error("Personal access token <{id}> could not be revoked, because it does not exist or belongs to another user!")]
+    NotFound {
+        id: PersonalAccessTokenId,
+    },
+    #[error("An internal error occurred while revoking personal access token <{id}>:\n  {cause}")]
+    Internal {
+        id: PersonalAccessTokenId,
+        cause: String,
+    },
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use std::time::Duration;
+
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use opendut_types::user::token::{PersonalAccessTokenDescriptor, PersonalAccessTokenId, PersonalAccessTokenName, PersonalAccessTokenScope};
+
+    use crate::carl::{extract, ClientError};
+    use crate::carl::token::{CreatedPersonalAccessToken, CreatePersonalAccessTokenError, ListPersonalAccessTokensError, RevokePersonalAccessTokenError};
+    use crate::proto::services::token_manager;
+    use crate::proto::services::token_manager::token_manager_client::TokenManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct TokenManager<T> {
+        inner: TokenManagerClient<T>,
+    }
+
+    impl<T> TokenManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: TokenManagerClient<T>) -> TokenManager<T> {
+            TokenManager { inner }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> TokenManager<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = TokenManagerClient::new(InterceptedService::new(inner, interceptor));
+            TokenManager {
+                inner: inner_client
+            }
+        }
+
+        /// Creates a personal access token, which acts on behalf of the current user, limited to the given scopes.
+        pub async fn create_personal_access_token(&mut self, name: PersonalAccessTokenName, scopes: Vec<PersonalAccessTokenScope>, validity: Duration) -> Result<CreatedPersonalAccessToken, ClientError<CreatePersonalAccessTokenError>> {
+
+            let request = tonic::Request::new(token_manager::CreatePersonalAccessTokenRequest {
+                name: Some(name.into()),
+                scopes: scopes.into_iter().map(Into::into).collect(),
+                validity_ms: validity.as_millis() as u64,
+            });
+
+            let response = self.inner.create_personal_access_token(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                token_manager::create_personal_access_token_response::Reply::Failure(failure) => {
+                    let error = CreatePersonalAccessTokenError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                token_manager::create_personal_access_token_response::Reply::Success(success) => {
+                    let descriptor = extract!(success.descriptor)?;
+                    Ok(CreatedPersonalAccessToken { descriptor, token: success.token })
+                }
+            }
+        }
+
+        /// Lists the personal access tokens of the current user or, for administrators, of all users.
+        pub async fn list_personal_access_tokens(&mut self, all_users: bool) -> Result<Vec<PersonalAccessTokenDescriptor>, ClientError<ListPersonalAccessTokensError>> {
+
+            let request = tonic::Request::new(token_manager::ListPersonalAccessTokensRequest {
+                all_users,
+            });
+
+            let response = self.inner.list_personal_access_tokens(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                token_manager::list_personal_access_tokens_response::Reply::Failure(failure) => {
+                    let error = ListPersonalAccessTokensError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                token_manager::list_personal_access_tokens_response::Reply::Success(success) => {
+                    let tokens = success.tokens.into_iter()
+                        .map(PersonalAccessTokenDescriptor::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(tokens)
+                }
+            }
+        }
+
+        /// Revokes a personal access token of the current user or, for administrators, of any user.
+        pub async fn revoke_personal_access_token(&mut self, id: PersonalAccessTokenId) -> Result<PersonalAccessTokenDescriptor, ClientError<RevokePersonalAccessTokenError>> {
+
+            let request = tonic::Request::new(token_manager::RevokePersonalAccessTokenRequest {
+                id: Some(id.into()),
+            });
+
+            let response = self.inner.revoke_personal_access_token(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                token_manager::revoke_personal_access_token_response::Reply::Failure(failure) => {
+                    let error = RevokePersonalAccessTokenError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                token_manager::revoke_personal_access_token_response::Reply::Success(success) => {
+                    let descriptor = extract!(success.descriptor)?;
+                    Ok(descriptor)
+                }
+            }
+        }
+    }
+}
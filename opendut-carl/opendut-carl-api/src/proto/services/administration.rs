@@ -0,0 +1,71 @@
+use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+
+use crate::carl::administration::{ExportSnapshotError, GetVpnReconciliationReportError};
+
+tonic::include_proto!("opendut.carl.services.administration");
+
+impl From<ExportSnapshotError> for ExportSnapshotFailure {
+    fn from(error: ExportSnapshotError) -> Self {
+        let proto_error = match error {
+            ExportSnapshotError::Internal { cause } => {
+                export_snapshot_failure::Error::Internal(ExportSnapshotFailureInternal {
+                    cause
+                })
+            }
+        };
+        ExportSnapshotFailure {
+            error: Some(proto_error)
+        }
+    }
+}
+
+impl TryFrom<ExportSnapshotFailure> for ExportSnapshotError {
+    type Error = ConversionError;
+    fn try_from(failure: ExportSnapshotFailure) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExportSnapshotFailure, ExportSnapshotError>;
+        let error = failure.error
+            .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+        let error = match error {
+            export_snapshot_failure::Error::Internal(error) => {
+                ExportSnapshotError::Internal { cause: error.cause }
+            }
+        };
+        Ok(error)
+    }
+}
+
+impl From<GetVpnReconciliationReportError> for GetVpnReconciliationReportFailure {
+    fn from(error: GetVpnReconciliationReportError) -> Self {
+        let proto_error = match error {
+            GetVpnReconciliationReportError::VpnDisabled => {
+                get_vpn_reconciliation_report_failure::Error::VpnDisabled(GetVpnReconciliationReportFailureVpnDisabled {})
+            }
+            GetVpnReconciliationReportError::Internal { cause } => {
+                get_vpn_reconciliation_report_failure::Error::Internal(GetVpnReconciliationReportFailureInternal {
+                    cause
+                })
+            }
+        };
+        GetVpnReconciliationReportFailure {
+            error: Some(proto_error)
+        }
+    }
+}
+
+impl TryFrom<GetVpnReconciliationReportFailure> for GetVpnReconciliationReportError {
+    type Error = ConversionError;
+    fn try_from(failure: GetVpnReconciliationReportFailure) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<GetVpnReconciliationReportFailure, GetVpnReconciliationReportError>;
+        let error = failure.error
+            .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+        let error = match error {
+            get_vpn_reconciliation_report_failure::Error::VpnDisabled(_) => {
+                GetVpnReconciliationReportError::VpnDisabled
+            }
+            get_vpn_reconciliation_report_failure::Error::Internal(error) => {
+                GetVpnReconciliationReportError::Internal { cause: error.cause }
+            }
+        };
+        Ok(error)
+    }
+}
@@ -0,0 +1,57 @@
+use opendut_types::audit::AuditLogFilter;
+use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+
+use crate::carl::audit::ListAuditEntriesError;
+
+tonic::include_proto!("opendut.carl.services.audit_log");
+
+impl From<AuditLogFilter> for ListAuditEntriesRequest {
+    fn from(filter: AuditLogFilter) -> Self {
+        ListAuditEntriesRequest {
+            resource_id: filter.resource_id.map(Into::into),
+            resource_kind: filter.resource_kind,
+            actor: filter.actor.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<ListAuditEntriesRequest> for AuditLogFilter {
+    type Error = ConversionError;
+    fn try_from(request: ListAuditEntriesRequest) -> Result<Self, Self::Error> {
+        Ok(AuditLogFilter {
+            resource_id: request.resource_id.map(Into::into),
+            resource_kind: request.resource_kind,
+            actor: request.actor.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<ListAuditEntriesError> for ListAuditEntriesFailure {
+    fn from(error: ListAuditEntriesError) -> Self {
+        let proto_error = match error {
+            ListAuditEntriesError::Internal { cause } => {
+                list_audit_entries_failure::Error::Internal(ListAuditEntriesFailureInternal {
+                    cause
+                })
+            }
+        };
+        ListAuditEntriesFailure {
+            error: Some(proto_error)
+        }
+    }
+}
+
+impl TryFrom<ListAuditEntriesFailure> for ListAuditEntriesError {
+    type Error = ConversionError;
+    fn try_from(failure: ListAuditEntriesFailure) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ListAuditEntriesFailure, ListAuditEntriesError>;
+        let error = failure.error
+            .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+        let error = match error {
+            list_audit_entries_failure::Error::Internal(error) => {
+                ListAuditEntriesError::Internal { cause: error.cause }
+            }
+        };
+        Ok(error)
+    }
+}
@@ -5,6 +5,7 @@ use opendut_types::proto;
 use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
 use opendut_types::conversion;
 use opendut_types::proto::ConversionResult;
+use opendut_types::resources::Revision;
 use crate::carl;
 use crate::carl::cluster::{CreateClusterConfigurationError, DeleteClusterConfigurationError, DeleteClusterDeploymentError, StoreClusterDeploymentError};
 
@@ -67,6 +68,22 @@ impl From<CreateClusterConfigurationError> for CreateClusterConfigurationFailure
                     cause
                 })
             }
+            CreateClusterConfigurationError::RevisionConflict { cluster_id, cluster_name, expected_revision, actual_revision } => {
+                create_cluster_configuration_failure::Error::RevisionConflict(CreateClusterConfigurationFailureRevisionConflict {
+                    cluster_id: Some(cluster_id.into()),
+                    cluster_name: Some(cluster_name.into()),
+                    expected_revision: expected_revision.into(),
+                    actual_revision: actual_revision.map(Into::into),
+                })
+            }
+            CreateClusterConfigurationError::PeerOutsideProject { cluster_id, cluster_name, peer_id, project } => {
+                create_cluster_configuration_failure::Error::PeerOutsideProject(CreateClusterConfigurationFailurePeerOutsideProject {
+                    cluster_id: Some(cluster_id.into()),
+                    cluster_name: Some(cluster_name.into()),
+                    peer_id: Some(peer_id.into()),
+                    project: Some(project.into()),
+                })
+            }
         };
         CreateClusterConfigurationFailure {
             error: Some(proto_error)
@@ -84,6 +101,12 @@ impl TryFrom<CreateClusterConfigurationFailure> for CreateClusterConfigurationEr
             create_cluster_configuration_failure::Error::Internal(error) => {
                 error.try_into()?
             }
+            create_cluster_configuration_failure::Error::RevisionConflict(error) => {
+                error.try_into()?
+            }
+            create_cluster_configuration_failure::Error::PeerOutsideProject(error) => {
+                error.try_into()?
+            }
         };
         Ok(error)
     }
@@ -103,6 +126,45 @@ impl TryFrom<CreateClusterConfigurationFailureInternal> for CreateClusterConfigu
     }
 }
 
+impl TryFrom<CreateClusterConfigurationFailureRevisionConflict> for CreateClusterConfigurationError {
+    type Error = ConversionError;
+    fn try_from(failure: CreateClusterConfigurationFailureRevisionConflict) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CreateClusterConfigurationFailureRevisionConflict, CreateClusterConfigurationError>;
+        let cluster_id: ClusterId = failure.cluster_id
+            .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+            .try_into()?;
+        let cluster_name: ClusterName = failure.cluster_name
+            .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+            .try_into()?;
+        Ok(CreateClusterConfigurationError::RevisionConflict {
+            cluster_id,
+            cluster_name,
+            expected_revision: Revision::from(failure.expected_revision),
+            actual_revision: failure.actual_revision.map(Revision::from),
+        })
+    }
+}
+
+impl TryFrom<CreateClusterConfigurationFailurePeerOutsideProject> for CreateClusterConfigurationError {
+    type Error = ConversionError;
+    fn try_from(failure: CreateClusterConfigurationFailurePeerOutsideProject) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CreateClusterConfigurationFailurePeerOutsideProject, CreateClusterConfigurationError>;
+        let cluster_id: ClusterId = failure.cluster_id
+            .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+            .try_into()?;
+        let cluster_name: ClusterName = failure.cluster_name
+            .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+            .try_into()?;
+        let peer_id = failure.peer_id
+            .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+        let project = failure.project
+            .ok_or_else(|| ErrorBuilder::field_not_set("project"))?
+            .try_into()?;
+        Ok(CreateClusterConfigurationError::PeerOutsideProject { cluster_id, cluster_name, peer_id, project })
+    }
+}
+
 impl From<DeleteClusterConfigurationError> for DeleteClusterConfigurationFailure {
     fn from(error: DeleteClusterConfigurationError) -> Self {
         let proto_error = match error {
@@ -131,6 +193,13 @@ impl From<DeleteClusterConfigurationError> for DeleteClusterConfigurationFailure
                     cluster_id: Some(cluster_id.into()),
                 })
             }
+            DeleteClusterConfigurationError::RevisionConflict { cluster_id, expected_revision, actual_revision } => {
+                delete_cluster_configuration_failure::Error::RevisionConflict(DeleteClusterConfigurationFailureRevisionConflict {
+                    cluster_id: Some(cluster_id.into()),
+                    expected_revision: expected_revision.into(),
+                    actual_revision: actual_revision.into(),
+                })
+            }
         };
         DeleteClusterConfigurationFailure {
             error: Some(proto_error)
@@ -157,6 +226,9 @@ impl TryFrom<DeleteClusterConfigurationFailure> for DeleteClusterConfigurationEr
             delete_cluster_configuration_failure::Error::ClusterDeploymentExists(error) => {
                 error.try_into()?
             }
+            delete_cluster_configuration_failure::Error::RevisionConflict(error) => {
+                error.try_into()?
+            }
         };
         Ok(error)
     }
@@ -219,6 +291,21 @@ impl TryFrom<DeleteClusterConfigurationFailureInternal> for DeleteClusterConfigu
     }
 }
 
+impl TryFrom<DeleteClusterConfigurationFailureRevisionConflict> for DeleteClusterConfigurationError {
+    type Error = ConversionError;
+    fn try_from(failure: DeleteClusterConfigurationFailureRevisionConflict) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<DeleteClusterConfigurationFailureRevisionConflict, DeleteClusterConfigurationError>;
+        let cluster_id: ClusterId = failure.cluster_id
+            .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+            .try_into()?;
+        Ok(DeleteClusterConfigurationError::RevisionConflict {
+            cluster_id,
+            expected_revision: Revision::from(failure.expected_revision),
+            actual_revision: Revision::from(failure.actual_revision),
+        })
+    }
+}
+
 impl From<StoreClusterDeploymentError> for StoreClusterDeploymentFailure {
     fn from(error: StoreClusterDeploymentError) -> Self {
         let proto_error = match error {
@@ -236,6 +323,14 @@ impl From<StoreClusterDeploymentError> for StoreClusterDeploymentFailure {
                     invalid_peers: invalid_peers.into_iter().map(Into::into).collect(),
                 })
             }
+            StoreClusterDeploymentError::PeerOutsideProject { cluster_id, cluster_name, peer_id, project } => {
+                store_cluster_deployment_failure::Error::PeerOutsideProject(StoreClusterDeploymentFailurePeerOutsideProject {
+                    cluster_id: Some(cluster_id.into()),
+                    cluster_name: Some(cluster_name.into()),
+                    peer_id: Some(peer_id.into()),
+                    project: Some(project.into()),
+                })
+            }
         };
         StoreClusterDeploymentFailure {
             error: Some(proto_error)
@@ -256,6 +351,9 @@ impl TryFrom<StoreClusterDeploymentFailure> for StoreClusterDeploymentError {
             store_cluster_deployment_failure::Error::IllegalPeerState(error) => {
                 error.try_into()?
             }
+            store_cluster_deployment_failure::Error::PeerOutsideProject(error) => {
+                error.try_into()?
+            }
         };
         Ok(error)
     }
@@ -292,6 +390,26 @@ impl TryFrom<StoreClusterDeploymentFailureIllegalPeerState> for StoreClusterDepl
     }
 }
 
+impl TryFrom<StoreClusterDeploymentFailurePeerOutsideProject> for StoreClusterDeploymentError {
+    type Error = ConversionError;
+    fn try_from(failure: StoreClusterDeploymentFailurePeerOutsideProject) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<StoreClusterDeploymentFailurePeerOutsideProject, StoreClusterDeploymentError>;
+        let cluster_id: ClusterId = failure.cluster_id
+            .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+            .try_into()?;
+        let cluster_name: ClusterName = failure.cluster_name
+            .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+            .try_into()?;
+        let peer_id = failure.peer_id
+            .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+        let project = failure.project
+            .ok_or_else(|| ErrorBuilder::field_not_set("project"))?
+            .try_into()?;
+        Ok(StoreClusterDeploymentError::PeerOutsideProject { cluster_id, cluster_name, peer_id, project })
+    }
+}
+
 impl From<DeleteClusterDeploymentError> for DeleteClusterDeploymentFailure {
     fn from(error: DeleteClusterDeploymentError) -> Self {
         let proto_error = match error {
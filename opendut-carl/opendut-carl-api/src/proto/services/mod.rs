@@ -1,5 +1,9 @@
+pub mod administration;
+pub mod audit_log;
 pub mod cluster_manager;
 pub mod metadata_provider;
+pub mod notification_manager;
 pub mod peer_manager;
 pub mod peer_messaging_broker;
+pub mod token_manager;
 pub mod observer_messaging_broker;
@@ -0,0 +1,122 @@
+use opendut_types::conversion;
+use opendut_types::proto::{ConversionError, ConversionErrorBuilder, ConversionResult};
+
+use crate::carl::notification::{CreateNotificationSubscriptionError, DeleteNotificationSubscriptionError, ListNotificationSubscriptionsError};
+
+tonic::include_proto!("opendut.carl.services.notification_manager");
+
+conversion!{
+    type Model = crate::carl::notification::CreateNotificationSubscriptionError;
+    type Proto = CreateNotificationSubscriptionFailure;
+
+    fn from(value: Model) -> Proto {
+        let proto_error = match value {
+            CreateNotificationSubscriptionError::NoEvents { name } => {
+                create_notification_subscription_failure::Error::NoEvents(CreateNotificationSubscriptionFailureNoEvents {
+                    name: Some(name.into()),
+                })
+            }
+            CreateNotificationSubscriptionError::IllegalSecret { name, cause } => {
+                create_notification_subscription_failure::Error::IllegalSecret(CreateNotificationSubscriptionFailureIllegalSecret {
+                    name: Some(name.into()),
+                    cause,
+                })
+            }
+            CreateNotificationSubscriptionError::Internal { name, cause } => {
+                create_notification_subscription_failure::Error::Internal(CreateNotificationSubscriptionFailureInternal {
+                    name: Some(name.into()),
+                    cause,
+                })
+            }
+        };
+        CreateNotificationSubscriptionFailure {
+            error: Some(proto_error)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let error = extract!(value.error)?;
+
+        match error {
+            create_notification_subscription_failure::Error::NoEvents(error) => {
+                let name = extract!(error.name)?.try_into()?;
+                Ok(Model::NoEvents { name })
+            }
+            create_notification_subscription_failure::Error::IllegalSecret(error) => {
+                let name = extract!(error.name)?.try_into()?;
+                Ok(Model::IllegalSecret { name, cause: error.cause })
+            }
+            create_notification_subscription_failure::Error::Internal(error) => {
+                let name = extract!(error.name)?.try_into()?;
+                Ok(Model::Internal { name, cause: error.cause })
+            }
+        }
+    }
+}
+
+conversion!{
+    type Model = crate::carl::notification::ListNotificationSubscriptionsError;
+    type Proto = ListNotificationSubscriptionsFailure;
+
+    fn from(value: Model) -> Proto {
+        let proto_error = match value {
+            ListNotificationSubscriptionsError::Internal { cause } => {
+                list_notification_subscriptions_failure::Error::Internal(ListNotificationSubscriptionsFailureInternal {
+                    cause,
+                })
+            }
+        };
+        ListNotificationSubscriptionsFailure {
+            error: Some(proto_error)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let error = extract!(value.error)?;
+
+        match error {
+            list_notification_subscriptions_failure::Error::Internal(error) => {
+                Ok(Model::Internal { cause: error.cause })
+            }
+        }
+    }
+}
+
+conversion!{
+    type Model = crate::carl::notification::DeleteNotificationSubscriptionError;
+    type Proto = DeleteNotificationSubscriptionFailure;
+
+    fn from(value: Model) -> Proto {
+        let proto_error = match value {
+            DeleteNotificationSubscriptionError::NotFound { id } => {
+                delete_notification_subscription_failure::Error::NotFound(DeleteNotificationSubscriptionFailureNotFound {
+                    id: Some(id.into()),
+                })
+            }
+            DeleteNotificationSubscriptionError::Internal { id, cause } => {
+                delete_notification_subscription_failure::Error::Internal(DeleteNotificationSubscriptionFailureInternal {
+                    id: Some(id.into()),
+                    cause,
+                })
+            }
+        };
+        DeleteNotificationSubscriptionFailure {
+            error: Some(proto_error)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let error = extract!(value.error)?;
+
+        match error {
+            delete_notification_subscription_failure::Error::NotFound(error) => {
+                let id = extract!(error.id)?.try_into()?;
+                Ok(Model::NotFound { id })
+            }
+            delete_notification_subscription_failure::Error::Internal(error) => {
+                let id = extract!(error.id)?.try_into()?;
+                Ok(Model::Internal { id, cause: error.cause })
+            }
+        }
+    }
+}
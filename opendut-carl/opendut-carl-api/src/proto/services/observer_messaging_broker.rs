@@ -4,7 +4,8 @@ use opendut_types::proto::ConversionErrorBuilder;
 use opendut_types::proto::ConversionResult;
 use opendut_types::proto::ConversionError;
 use crate::carl;
-use crate::carl::observer::WaitForPeersOnlineResponseStatus;
+use crate::carl::observer::{WaitForPeersOnlineResponseStatus, WatchedResourceKind};
+use opendut_types::resources::Revision;
 
 tonic::include_proto!("opendut.carl.services.observer_messaging_broker");
 
@@ -98,3 +99,138 @@ conversion! {
         })
     }
 }
+
+
+conversion! {
+    type Model = carl::observer::WatchRequest;
+    type Proto = WatchRequest;
+
+    fn from(value: Model) -> Proto {
+        let resource = match value.kind {
+            WatchedResourceKind::PeerDescriptor => watch_request::Resource::PeerDescriptors(WatchPeerDescriptors {}),
+            WatchedResourceKind::PeerConnectionState => watch_request::Resource::PeerConnectionStates(WatchPeerConnectionStates {}),
+            WatchedResourceKind::ClusterConfiguration => watch_request::Resource::ClusterConfigurations(WatchClusterConfigurations {}),
+            WatchedResourceKind::ClusterDeployment => watch_request::Resource::ClusterDeployments(WatchClusterDeployments {}),
+        };
+        Proto {
+            resource: Some(resource),
+            resume_revision: value.resume_revision.map(u64::from),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let kind = match extract!(value.resource)? {
+            watch_request::Resource::PeerDescriptors(_) => WatchedResourceKind::PeerDescriptor,
+            watch_request::Resource::PeerConnectionStates(_) => WatchedResourceKind::PeerConnectionState,
+            watch_request::Resource::ClusterConfigurations(_) => WatchedResourceKind::ClusterConfiguration,
+            watch_request::Resource::ClusterDeployments(_) => WatchedResourceKind::ClusterDeployment,
+        };
+        Ok(Model {
+            kind,
+            resume_revision: value.resume_revision.map(Revision::from),
+        })
+    }
+}
+
+conversion! {
+    type Model = carl::observer::WatchedResource;
+    type Proto = WatchedResource;
+
+    fn from(value: Model) -> Proto {
+        let resource = match value {
+            Model::PeerDescriptor(peer) => watched_resource::Resource::PeerDescriptor(peer.into()),
+            Model::PeerConnectionState { peer_id, state } => watched_resource::Resource::PeerConnectionState(WatchedPeerConnectionState {
+                peer_id: Some(peer_id.into()),
+                state: Some(state.into()),
+            }),
+            Model::ClusterConfiguration(configuration) => watched_resource::Resource::ClusterConfiguration(configuration.into()),
+            Model::ClusterDeployment(deployment) => watched_resource::Resource::ClusterDeployment(deployment.into()),
+        };
+        Proto {
+            resource: Some(resource),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let resource = match extract!(value.resource)? {
+            watched_resource::Resource::PeerDescriptor(peer) => Model::PeerDescriptor(peer.try_into()?),
+            watched_resource::Resource::PeerConnectionState(WatchedPeerConnectionState { peer_id, state }) => Model::PeerConnectionState {
+                peer_id: extract!(peer_id)?.try_into()?,
+                state: extract!(state)?.try_into()?,
+            },
+            watched_resource::Resource::ClusterConfiguration(configuration) => Model::ClusterConfiguration(configuration.try_into()?),
+            watched_resource::Resource::ClusterDeployment(deployment) => Model::ClusterDeployment(deployment.try_into()?),
+        };
+        Ok(resource)
+    }
+}
+
+conversion! {
+    type Model = carl::observer::WatchSnapshotEntry;
+    type Proto = WatchSnapshotEntry;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            id: Some(value.id.into()),
+            revision: value.revision.into(),
+            resource: value.resource.map(WatchedResource::from),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let id = extract!(value.id)?.into();
+        let resource = value.resource
+            .map(carl::observer::WatchedResource::try_from)
+            .transpose()?;
+        Ok(Model {
+            id,
+            revision: Revision::from(value.revision),
+            resource,
+        })
+    }
+}
+
+conversion! {
+    type Model = carl::observer::WatchEvent;
+    type Proto = WatchResponse;
+
+    fn from(value: Model) -> Proto {
+        let event = match value {
+            Model::Snapshot { entries, revision } => watch_response::Event::Snapshot(WatchSnapshot {
+                entries: entries.into_iter().map(WatchSnapshotEntry::from).collect(),
+                revision: revision.into(),
+            }),
+            Model::Inserted { resource, revision } => watch_response::Event::Inserted(WatchInserted {
+                resource: Some(resource.into()),
+                revision: revision.into(),
+            }),
+            Model::Removed { resource, revision } => watch_response::Event::Removed(WatchRemoved {
+                resource: Some(resource.into()),
+                revision: revision.into(),
+            }),
+        };
+        Proto {
+            event: Some(event),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let event = match extract!(value.event)? {
+            watch_response::Event::Snapshot(WatchSnapshot { entries, revision }) => Model::Snapshot {
+                entries: entries.into_iter()
+                    .map(carl::observer::WatchSnapshotEntry::try_from)
+                    .collect::<Result<Vec<_>, _>>()?,
+                revision: Revision::from(revision),
+            },
+            watch_response::Event::Inserted(WatchInserted { resource, revision }) => Model::Inserted {
+                resource: extract!(resource)?.try_into()?,
+                revision: Revision::from(revision),
+            },
+            watch_response::Event::Removed(WatchRemoved { resource, revision }) => Model::Removed {
+                resource: extract!(resource)?.try_into()?,
+                revision: Revision::from(revision),
+            },
+        };
+        Ok(event)
+    }
+}
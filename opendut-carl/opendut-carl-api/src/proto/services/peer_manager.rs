@@ -2,11 +2,12 @@
 
 use opendut_types::peer::{PeerId, PeerName};
 use opendut_types::peer::state::PeerState;
+use opendut_types::resources::Revision;
 use opendut_types::{conversion, proto};
 use opendut_types::proto::{ConversionError, ConversionErrorBuilder, ConversionResult};
 use opendut_types::topology::DeviceId;
 
-use crate::carl::peer::{StorePeerDescriptorError, DeletePeerDescriptorError, GetPeerDescriptorError, ListPeerDescriptorsError, GetPeerStateError, ListPeerStatesError};
+use crate::carl::peer::{StorePeerDescriptorError, DeletePeerDescriptorError, GetPeerDescriptorError, ListPeerDescriptorsError, GetPeerStateError, ListPeerStatesError, ListPeerSetupsError, RedeemPeerSetupError, RevokePeerSetupError, DecommissionPeerError, RotatePeerCredentialsError};
 
 tonic::include_proto!("opendut.carl.services.peer_manager");
 
@@ -35,6 +36,14 @@ impl From<StorePeerDescriptorError> for StorePeerDescriptorFailure {
                     cause
                 })
             }
+            StorePeerDescriptorError::RevisionConflict { peer_id, peer_name, expected_revision, actual_revision } => {
+                store_peer_descriptor_failure::Error::RevisionConflict(StorePeerDescriptorFailureRevisionConflict {
+                    peer_id: Some(peer_id.into()),
+                    peer_name: Some(peer_name.into()),
+                    expected_revision: expected_revision.into(),
+                    actual_revision: actual_revision.map(Into::into),
+                })
+            }
         };
         StorePeerDescriptorFailure {
             error: Some(proto_error)
@@ -58,6 +67,9 @@ impl TryFrom<StorePeerDescriptorFailure> for StorePeerDescriptorError {
             store_peer_descriptor_failure::Error::Internal(error) => {
                 error.try_into()?
             }
+            store_peer_descriptor_failure::Error::RevisionConflict(error) => {
+                error.try_into()?
+            }
         };
         Ok(error)
     }
@@ -114,6 +126,25 @@ impl TryFrom<StorePeerDescriptorFailureInternal> for StorePeerDescriptorError {
     }
 }
 
+impl TryFrom<StorePeerDescriptorFailureRevisionConflict> for StorePeerDescriptorError {
+    type Error = ConversionError;
+    fn try_from(failure: StorePeerDescriptorFailureRevisionConflict) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureRevisionConflict, StorePeerDescriptorError>;
+        let peer_id: PeerId = failure.peer_id
+            .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+        let peer_name: PeerName = failure.peer_name
+            .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+            .try_into()?;
+        Ok(StorePeerDescriptorError::RevisionConflict {
+            peer_id,
+            peer_name,
+            expected_revision: Revision::from(failure.expected_revision),
+            actual_revision: failure.actual_revision.map(Revision::from),
+        })
+    }
+}
+
 
 conversion!{
     type Model = crate::carl::peer::DeletePeerDescriptorError;
@@ -147,6 +178,13 @@ conversion!{
                     cluster_id: Some(cluster_id.into()),
                 })
             }
+            DeletePeerDescriptorError::RevisionConflict { peer_id, expected_revision, actual_revision } => {
+                delete_peer_descriptor_failure::Error::RevisionConflict(DeletePeerDescriptorFailureRevisionConflict {
+                    peer_id: Some(peer_id.into()),
+                    expected_revision: expected_revision.into(),
+                    actual_revision: actual_revision.into(),
+                })
+            }
         };
         DeletePeerDescriptorFailure {
             error: Some(proto_error)
@@ -196,6 +234,15 @@ conversion!{
                     cluster_id,
                 })
             }
+            delete_peer_descriptor_failure::Error::RevisionConflict(error) => {
+                let peer_id = extract!(error.peer_id)?.try_into()?;
+
+                Ok(Model::RevisionConflict {
+                    peer_id,
+                    expected_revision: Revision::from(error.expected_revision),
+                    actual_revision: Revision::from(error.actual_revision),
+                })
+            }
         }
     }
 }
@@ -436,3 +483,269 @@ impl TryFrom<ListPeerStatesFailureInternal> for ListPeerStatesError {
         Ok(ListPeerStatesError::Internal{ cause: failure.cause})
     }
 }
+
+conversion!{
+    type Model = crate::carl::peer::ListPeerSetupsError;
+    type Proto = ListPeerSetupsFailure;
+
+    fn from(value: Model) -> Proto {
+        let proto_error = match value {
+            ListPeerSetupsError::Internal { cause } => {
+                list_peer_setups_failure::Error::Internal(ListPeerSetupsFailureInternal {
+                    cause
+                })
+            }
+        };
+        ListPeerSetupsFailure {
+            error: Some(proto_error)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let error = extract!(value.error)?;
+
+        match error {
+            list_peer_setups_failure::Error::Internal(error) => {
+                Ok(Model::Internal { cause: error.cause })
+            }
+        }
+    }
+}
+
+conversion!{
+    type Model = crate::carl::peer::RedeemPeerSetupError;
+    type Proto = RedeemPeerSetupFailure;
+
+    fn from(value: Model) -> Proto {
+        let proto_error = match value {
+            RedeemPeerSetupError::SetupNotFound { setup_id } => {
+                redeem_peer_setup_failure::Error::SetupNotFound(PeerSetupFailureSetupNotFound {
+                    setup_id: Some(setup_id.into()),
+                })
+            }
+            RedeemPeerSetupError::AlreadyUsed { setup_id } => {
+                redeem_peer_setup_failure::Error::AlreadyUsed(PeerSetupFailureAlreadyUsed {
+                    setup_id: Some(setup_id.into()),
+                })
+            }
+            RedeemPeerSetupError::Expired { setup_id } => {
+                redeem_peer_setup_failure::Error::Expired(PeerSetupFailureExpired {
+                    setup_id: Some(setup_id.into()),
+                })
+            }
+            RedeemPeerSetupError::Revoked { setup_id } => {
+                redeem_peer_setup_failure::Error::Revoked(PeerSetupFailureRevoked {
+                    setup_id: Some(setup_id.into()),
+                })
+            }
+            RedeemPeerSetupError::Internal { setup_id, cause } => {
+                redeem_peer_setup_failure::Error::Internal(PeerSetupFailureInternal {
+                    setup_id: Some(setup_id.into()),
+                    cause,
+                })
+            }
+        };
+        RedeemPeerSetupFailure {
+            error: Some(proto_error)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let error = extract!(value.error)?;
+
+        match error {
+            redeem_peer_setup_failure::Error::SetupNotFound(error) => {
+                let setup_id = extract!(error.setup_id)?.try_into()?;
+                Ok(Model::SetupNotFound { setup_id })
+            }
+            redeem_peer_setup_failure::Error::AlreadyUsed(error) => {
+                let setup_id = extract!(error.setup_id)?.try_into()?;
+                Ok(Model::AlreadyUsed { setup_id })
+            }
+            redeem_peer_setup_failure::Error::Expired(error) => {
+                let setup_id = extract!(error.setup_id)?.try_into()?;
+                Ok(Model::Expired { setup_id })
+            }
+            redeem_peer_setup_failure::Error::Revoked(error) => {
+                let setup_id = extract!(error.setup_id)?.try_into()?;
+                Ok(Model::Revoked { setup_id })
+            }
+            redeem_peer_setup_failure::Error::Internal(error) => {
+                let setup_id = extract!(error.setup_id)?.try_into()?;
+                Ok(Model::Internal { setup_id, cause: error.cause })
+            }
+        }
+    }
+}
+
+conversion!{
+    type Model = crate::carl::peer::RevokePeerSetupError;
+    type Proto = RevokePeerSetupFailure;
+
+    fn from(value: Model) -> Proto {
+        let proto_error = match value {
+            RevokePeerSetupError::SetupNotFound { setup_id } => {
+                revoke_peer_setup_failure::Error::SetupNotFound(PeerSetupFailureSetupNotFound {
+                    setup_id: Some(setup_id.into()),
+                })
+            }
+            RevokePeerSetupError::AlreadyUsed { setup_id } => {
+                revoke_peer_setup_failure::Error::AlreadyUsed(PeerSetupFailureAlreadyUsed {
+                    setup_id: Some(setup_id.into()),
+                })
+            }
+            RevokePeerSetupError::AlreadyRevoked { setup_id } => {
+                revoke_peer_setup_failure::Error::AlreadyRevoked(PeerSetupFailureRevoked {
+                    setup_id: Some(setup_id.into()),
+                })
+            }
+            RevokePeerSetupError::Internal { setup_id, cause } => {
+                revoke_peer_setup_failure::Error::Internal(PeerSetupFailureInternal {
+                    setup_id: Some(setup_id.into()),
+                    cause,
+                })
+            }
+        };
+        RevokePeerSetupFailure {
+            error: Some(proto_error)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let error = extract!(value.error)?;
+
+        match error {
+            revoke_peer_setup_failure::Error::SetupNotFound(error) => {
+                let setup_id = extract!(error.setup_id)?.try_into()?;
+                Ok(Model::SetupNotFound { setup_id })
+            }
+            revoke_peer_setup_failure::Error::AlreadyUsed(error) => {
+                let setup_id = extract!(error.setup_id)?.try_into()?;
+                Ok(Model::AlreadyUsed { setup_id })
+            }
+            revoke_peer_setup_failure::Error::AlreadyRevoked(error) => {
+                let setup_id = extract!(error.setup_id)?.try_into()?;
+                Ok(Model::AlreadyRevoked { setup_id })
+            }
+            revoke_peer_setup_failure::Error::Internal(error) => {
+                let setup_id = extract!(error.setup_id)?.try_into()?;
+                Ok(Model::Internal { setup_id, cause: error.cause })
+            }
+        }
+    }
+}
+
+conversion!{
+    type Model = crate::carl::peer::DecommissionPeerError;
+    type Proto = DecommissionPeerFailure;
+
+    fn from(value: Model) -> Proto {
+        let proto_error = match value {
+            DecommissionPeerError::PeerNotFound { peer_id } => {
+                decommission_peer_failure::Error::PeerNotFound(DecommissionPeerFailurePeerNotFound {
+                    peer_id: Some(peer_id.into()),
+                })
+            }
+            DecommissionPeerError::Internal { peer_id, cause } => {
+                decommission_peer_failure::Error::Internal(DecommissionPeerFailureInternal {
+                    peer_id: Some(peer_id.into()),
+                    cause,
+                })
+            }
+        };
+        DecommissionPeerFailure {
+            error: Some(proto_error)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let error = extract!(value.error)?;
+
+        match error {
+            decommission_peer_failure::Error::PeerNotFound(error) => {
+                let peer_id = extract!(error.peer_id)?.try_into()?;
+                Ok(Model::PeerNotFound { peer_id })
+            }
+            decommission_peer_failure::Error::Internal(error) => {
+                let peer_id = extract!(error.peer_id)?.try_into()?;
+                Ok(Model::Internal { peer_id, cause: error.cause })
+            }
+        }
+    }
+}
+
+conversion!{
+    type Model = crate::carl::peer::RotatePeerCredentialsError;
+    type Proto = RotatePeerCredentialsFailure;
+
+    fn from(value: Model) -> Proto {
+        let proto_error = match value {
+            RotatePeerCredentialsError::PeerNotFound { peer_id } => {
+                rotate_peer_credentials_failure::Error::PeerNotFound(RotatePeerCredentialsFailurePeerNotFound {
+                    peer_id: Some(peer_id.into()),
+                })
+            }
+            RotatePeerCredentialsError::PeerNotConnected { peer_id } => {
+                rotate_peer_credentials_failure::Error::PeerNotConnected(RotatePeerCredentialsFailurePeerNotConnected {
+                    peer_id: Some(peer_id.into()),
+                })
+            }
+            RotatePeerCredentialsError::NotSupported { peer_id, reason } => {
+                rotate_peer_credentials_failure::Error::NotSupported(RotatePeerCredentialsFailureNotSupported {
+                    peer_id: Some(peer_id.into()),
+                    reason,
+                })
+            }
+            RotatePeerCredentialsError::AlreadyInProgress { peer_id } => {
+                rotate_peer_credentials_failure::Error::AlreadyInProgress(RotatePeerCredentialsFailureAlreadyInProgress {
+                    peer_id: Some(peer_id.into()),
+                })
+            }
+            RotatePeerCredentialsError::Unconfirmed { peer_id } => {
+                rotate_peer_credentials_failure::Error::Unconfirmed(RotatePeerCredentialsFailureUnconfirmed {
+                    peer_id: Some(peer_id.into()),
+                })
+            }
+            RotatePeerCredentialsError::Internal { peer_id, cause } => {
+                rotate_peer_credentials_failure::Error::Internal(RotatePeerCredentialsFailureInternal {
+                    peer_id: Some(peer_id.into()),
+                    cause,
+                })
+            }
+        };
+        RotatePeerCredentialsFailure {
+            error: Some(proto_error)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let error = extract!(value.error)?;
+
+        match error {
+            rotate_peer_credentials_failure::Error::PeerNotFound(error) => {
+                let peer_id = extract!(error.peer_id)?.try_into()?;
+                Ok(Model::PeerNotFound { peer_id })
+            }
+            rotate_peer_credentials_failure::Error::PeerNotConnected(error) => {
+                let peer_id = extract!(error.peer_id)?.try_into()?;
+                Ok(Model::PeerNotConnected { peer_id })
+            }
+            rotate_peer_credentials_failure::Error::NotSupported(error) => {
+                let peer_id = extract!(error.peer_id)?.try_into()?;
+                Ok(Model::NotSupported { peer_id, reason: error.reason })
+            }
+            rotate_peer_credentials_failure::Error::AlreadyInProgress(error) => {
+                let peer_id = extract!(error.peer_id)?.try_into()?;
+                Ok(Model::AlreadyInProgress { peer_id })
+            }
+            rotate_peer_credentials_failure::Error::Unconfirmed(error) => {
+                let peer_id = extract!(error.peer_id)?.try_into()?;
+                Ok(Model::Unconfirmed { peer_id })
+            }
+            rotate_peer_credentials_failure::Error::Internal(error) => {
+                let peer_id = extract!(error.peer_id)?.try_into()?;
+                Ok(Model::Internal { peer_id, cause: error.cause })
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use opendut_types::conversion;
+use opendut_types::proto::{ConversionError, ConversionErrorBuilder, ConversionResult};
+
+use crate::carl::token::{CreatePersonalAccessTokenError, ListPersonalAccessTokensError, RevokePersonalAccessTokenError};
+
+tonic::include_proto!("opendut.carl.services.token_manager");
+
+conversion!{
+    type Model = crate::carl::token::CreatePersonalAccessTokenError;
+    type Proto = CreatePersonalAccessTokenFailure;
+
+    fn from(value: Model) -> Proto {
+        let proto_error = match value {
+            CreatePersonalAccessTokenError::ScopeNotPermitted { scope } => {
+                create_personal_access_token_failure::Error::ScopeNotPermitted(CreatePersonalAccessTokenFailureScopeNotPermitted {
+                    scope: Some(scope.into()),
+                })
+            }
+            CreatePersonalAccessTokenError::InvalidValidity { requested, maximum } => {
+                create_personal_access_token_failure::Error::InvalidValidity(CreatePersonalAccessTokenFailureInvalidValidity {
+                    requested_ms: requested.as_millis() as u64,
+                    maximum_ms: maximum.as_millis() as u64,
+                })
+            }
+            CreatePersonalAccessTokenError::Internal { cause } => {
+                create_personal_access_token_failure::Error::Internal(CreatePersonalAccessTokenFailureInternal {
+                    cause,
+                })
+            }
+        };
+        CreatePersonalAccessTokenFailure {
+            error: Some(proto_error)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let error = extract!(value.error)?;
+
+        match error {
+            create_personal_access_token_failure::Error::ScopeNotPermitted(error) => {
+                let scope = extract!(error.scope)?.try_into()?;
+                Ok(Model::ScopeNotPermitted { scope })
+            }
+            create_personal_access_token_failure::Error::InvalidValidity(error) => {
+                Ok(Model::InvalidValidity {
+                    requested: Duration::from_millis(error.requested_ms),
+                    maximum: Duration::from_millis(error.maximum_ms),
+                })
+            }
+            create_personal_access_token_failure::Error::Internal(error) => {
+                Ok(Model::Internal { cause: error.cause })
+            }
+        }
+    }
+}
+
+conversion!{
+    type Model = crate::carl::token::ListPersonalAccessTokensError;
+    type Proto = ListPersonalAccessTokensFailure;
+
+    fn from(value: Model) -> Proto {
+        let proto_error = match value {
+            ListPersonalAccessTokensError::NotPermitted => {
+                list_personal_access_tokens_failure::Error::NotPermitted(ListPersonalAccessTokensFailureNotPermitted {})
+            }
+            ListPersonalAccessTokensError::Internal { cause } => {
+                list_personal_access_tokens_failure::Error::Internal(ListPersonalAccessTokensFailureInternal {
+                    cause,
+                })
+            }
+        };
+        ListPersonalAccessTokensFailure {
+            error: Some(proto_error)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let error = extract!(value.error)?;
+
+        match error {
+            list_personal_access_tokens_failure::Error::NotPermitted(_) => {
+                Ok(Model::NotPermitted)
+            }
+            list_personal_access_tokens_failure::Error::Internal(error) => {
+                Ok(Model::Internal { cause: error.cause })
+            }
+        }
+    }
+}
+
+conversion!{
+    type Model = crate::carl::token::RevokePersonalAccessTokenError;
+    type Proto = RevokePersonalAccessTokenFailure;
+
+    fn from(value: Model) -> Proto {
+        let proto_error = match value {
+            RevokePersonalAccessTokenError::NotFound { id } => {
+                revoke_personal_access_token_failure::Error::NotFound(RevokePersonalAccessTokenFailureNotFound {
+                    id: Some(id.into()),
+                })
+            }
+            RevokePersonalAccessTokenError::Internal { id, cause } => {
+                revoke_personal_access_token_failure::Error::Internal(RevokePersonalAccessTokenFailureInternal {
+                    id: Some(id.into()),
+                    cause,
+                })
+            }
+        };
+        RevokePersonalAccessTokenFailure {
+            error: Some(proto_error)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let error = extract!(value.error)?;
+
+        match error {
+            revoke_personal_access_token_failure::Error::NotFound(error) => {
+                let id = extract!(error.id)?.try_into()?;
+                Ok(Model::NotFound { id })
+            }
+            revoke_personal_access_token_failure::Error::Internal(error) => {
+                let id = extract!(error.id)?.try_into()?;
+                Ok(Model::Internal { id, cause: error.cause })
+            }
+        }
+    }
+}
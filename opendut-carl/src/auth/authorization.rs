@@ -0,0 +1,776 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use tonic::Status;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use opendut_auth::registration::client::RegistrationClientRef;
+use opendut_auth::types::MyAdditionalClaims;
+use opendut_carl_api::carl::broker::stream_header;
+use opendut_carl_api::proto::services::administration::administration_server;
+use opendut_carl_api::proto::services::audit_log::audit_log_server;
+use opendut_carl_api::proto::services::cluster_manager::cluster_manager_server;
+use opendut_carl_api::proto::services::metadata_provider::metadata_provider_server;
+use opendut_carl_api::proto::services::notification_manager::notification_manager_server;
+use opendut_carl_api::proto::services::observer_messaging_broker::observer_messaging_broker_server;
+use opendut_carl_api::proto::services::peer_manager::peer_manager_server;
+use opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_broker_server;
+use opendut_carl_api::proto::services::token_manager::token_manager_server;
+use opendut_types::peer::certificate::{ClientCertificateId, ClientCertificateRecord};
+use opendut_types::peer::decommission::PeerDecommissionRecord;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::user::token::PersonalAccessTokenScope;
+use opendut_util::settings::LoadError;
+
+use crate::auth::CurrentUser;
+use crate::auth::ownership::OwnershipScope;
+use crate::auth::projects::{ProjectPolicy, ProjectScope};
+use crate::resource::manager::ResourceManagerRef;
+use crate::startup::tls::{CertifiedPeer, PeerCertificate};
+
+/// Permissions required by the RPCs of CARL's gRPC API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    ReadMetadata,
+    ReadPeers,
+    WritePeers,
+    ReadClusters,
+    WriteClusters,
+    ObservePeers,
+    WatchResources,
+    OpenPeerStream,
+    ReadAuditLog,
+    ExportSnapshot,
+    ReconcileVpn,
+    RedeemPeerSetup,
+    /// Create, list and revoke one's own personal access tokens. Cannot be used with a personal access token.
+    ManagePersonalAccessTokens,
+    /// Create, list and delete notification subscriptions of one's projects.
+    ManageNotifications,
+}
+
+/// Roles which are assigned to a user based on the roles and groups in their token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// May do everything.
+    Administrator,
+    /// May read and modify peers and clusters.
+    Editor,
+    /// May only read peers and clusters.
+    Viewer,
+    /// Service account of an EDGAR, which may only open the stream of its own peer and redeem its Setup-String.
+    Edgar,
+}
+impl Role {
+    const ALL: [Role; 4] = [Role::Administrator, Role::Editor, Role::Viewer, Role::Edgar];
+
+    fn config_key(&self) -> &'static str {
+        match self {
+            Role::Administrator => "administrator",
+            Role::Editor => "editor",
+            Role::Viewer => "viewer",
+            Role::Edgar => "edgar",
+        }
+    }
+
+    pub fn permissions(&self) -> &'static [Permission] {
+        use Permission::*;
+        match self {
+            Role::Administrator => &[ReadMetadata, ReadPeers, WritePeers, ReadClusters, WriteClusters, ObservePeers, WatchResources, OpenPeerStream, ReadAuditLog, ExportSnapshot, ReconcileVpn, RedeemPeerSetup, ManagePersonalAccessTokens, ManageNotifications],
+            Role::Editor => &[ReadMetadata, ReadPeers, WritePeers, ReadClusters, WriteClusters, ObservePeers, WatchResources, ReadAuditLog, ManagePersonalAccessTokens, ManageNotifications],
+            Role::Viewer => &[ReadMetadata, ReadPeers, ReadClusters, ObservePeers, WatchResources, ReadAuditLog, ManagePersonalAccessTokens],
+            Role::Edgar => &[ReadMetadata, OpenPeerStream, RedeemPeerSetup],
+        }
+    }
+}
+
+impl From<PersonalAccessTokenScope> for Role {
+    fn from(value: PersonalAccessTokenScope) -> Self {
+        match value {
+            PersonalAccessTokenScope::Administrator => Role::Administrator,
+            PersonalAccessTokenScope::Editor => Role::Editor,
+            PersonalAccessTokenScope::Viewer => Role::Viewer,
+        }
+    }
+}
+
+/// OIDC roles and groups which grant a [`Role`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RoleMapping {
+    pub roles: Vec<String>,
+    pub groups: Vec<String>,
+}
+impl RoleMapping {
+    fn matches(&self, claims: &MyAdditionalClaims) -> bool {
+        self.roles.iter().any(|role| claims.has_role(role))
+            || self.groups.iter().any(|group| claims.has_group(group))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthorizationPolicy {
+    Disabled,
+    Enabled { mappings: Vec<(Role, RoleMapping)> },
+}
+
+const AUTHORIZATION_CONFIG_PREFIX: &str = "network.oidc.authorization";
+
+impl AuthorizationPolicy {
+    pub fn load(config: &config::Config) -> Result<Self, LoadError> {
+        let enabled = config.get_bool(&format!("{AUTHORIZATION_CONFIG_PREFIX}.enabled"))?;
+
+        if enabled {
+            let mappings = Role::ALL.into_iter()
+                .map(|role| {
+                    let roles = load_list(config, &format!("{AUTHORIZATION_CONFIG_PREFIX}.{}.roles", role.config_key()))?;
+                    let groups = load_list(config, &format!("{AUTHORIZATION_CONFIG_PREFIX}.{}.groups", role.config_key()))?;
+                    Ok((role, RoleMapping { roles, groups }))
+                })
+                .collect::<Result<Vec<_>, LoadError>>()?;
+
+            Ok(AuthorizationPolicy::Enabled { mappings })
+        } else {
+            Ok(AuthorizationPolicy::Disabled)
+        }
+    }
+
+    /// Grants users of the built-in user store the roles stored for them, which are named like the respective configuration keys, e.g. `editor`.
+    pub fn for_local_users() -> Self {
+        let mappings = Role::ALL.into_iter()
+            .filter(|role| *role != Role::Edgar)
+            .map(|role| (role, RoleMapping { roles: vec![role.config_key().to_owned()], groups: vec![] }))
+            .collect();
+
+        AuthorizationPolicy::Enabled { mappings }
+    }
+
+    /// Users authenticated with a personal access token only have those roles the token is scoped to,
+    /// which they still have according to the current claims of the owner, carried by the token.
+    pub fn roles_of(&self, user: &CurrentUser) -> HashSet<Role> {
+        let roles: HashSet<Role> = match self {
+            AuthorizationPolicy::Disabled => HashSet::from(Role::ALL),
+            AuthorizationPolicy::Enabled { mappings } => {
+                mappings.iter()
+                    .filter(|(_, mapping)| mapping.matches(user.claims.additional_claims()))
+                    .map(|(role, _)| *role)
+                    .collect()
+            }
+        };
+
+        match &user.personal_access_token {
+            Some(personal_access_token) => {
+                personal_access_token.scopes.iter()
+                    .map(|scope| Role::from(*scope))
+                    .filter(|role| roles.contains(role))
+                    .collect()
+            }
+            None => roles,
+        }
+    }
+
+    pub fn authorize(&self, user: &CurrentUser, permission: Permission) -> Result<HashSet<Role>, AuthorizationError> {
+        let roles = self.roles_of(user);
+
+        if permission == Permission::ManagePersonalAccessTokens && user.personal_access_token.is_some() {
+            return Err(AuthorizationError::PermissionDenied { user: user.name.clone(), permission });
+        }
+
+        if roles.iter().any(|role| role.permissions().contains(&permission)) {
+            Ok(roles)
+        } else {
+            Err(AuthorizationError::PermissionDenied { user: user.name.clone(), permission })
+        }
+    }
+
+    /// Administrators and users of a CARL without authorization may modify all resources, other users only the ones they created.
+    pub fn ownership_scope_of(&self, user: &CurrentUser, roles: &HashSet<Role>) -> OwnershipScope {
+        if matches!(self, AuthorizationPolicy::Disabled) || roles.contains(&Role::Administrator) {
+            OwnershipScope::Unrestricted
+        } else {
+            OwnershipScope::CreatedBy { user: Clone::clone(&user.name) }
+        }
+    }
+
+    /// Checks whether the user may open the stream of the given peer.
+    /// Users which are not administrators have to authenticate with an OIDC client registered for this peer.
+    pub async fn authorize_peer_stream(&self, user: &CurrentUser, peer_id: PeerId, peer_clients: &impl PeerClientRegistry) -> Result<(), AuthorizationError> {
+        let roles = self.authorize(user, Permission::OpenPeerStream)?;
+
+        if matches!(self, AuthorizationPolicy::Disabled) || roles.contains(&Role::Administrator) {
+            return Ok(());
+        }
+
+        let client_id = user.claims.authorized_party()
+            .ok_or_else(|| AuthorizationError::PeerNotBound { user: user.name.clone(), peer_id })?;
+
+        let peer_client_ids = peer_clients.client_ids_of_peer(peer_id).await?;
+
+        if peer_client_ids.iter().any(|peer_client_id| peer_client_id == client_id.as_str()) {
+            Ok(())
+        } else {
+            Err(AuthorizationError::PeerNotBound { user: user.name.clone(), peer_id })
+        }
+    }
+}
+
+fn load_list(config: &config::Config, field: &str) -> Result<Vec<String>, LoadError> {
+    let value = config.get_string(field)?;
+    let list = value.replace('\"', "")
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(String::from)
+        .collect();
+    Ok(list)
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum AuthorizationError {
+    #[error("User '{user}' is missing permission {permission:?}.")]
+    PermissionDenied { user: String, permission: Permission },
+    #[error("User '{user}' is not bound to peer <{peer_id}>.")]
+    PeerNotBound { user: String, peer_id: PeerId },
+    #[error("Could not determine the OIDC clients of peer <{peer_id}>: {cause}")]
+    PeerClientLookup { peer_id: PeerId, cause: String },
+    #[error("Opening the stream of a peer requires a client certificate.")]
+    ClientCertificateRequired,
+    #[error("The client certificate of peer <{certified_peer}> does not permit access to '{path}'.")]
+    CertificateNotBound { certified_peer: PeerId, path: String },
+    #[error("The client certificate <{certificate_id}> of peer <{certified_peer}> was revoked or is unknown.")]
+    CertificateRevoked { certified_peer: PeerId, certificate_id: ClientCertificateId },
+    #[error("The client certificate of peer <{certified_peer}> is no longer valid, as the peer does not exist anymore.")]
+    CertifiedPeerNotFound { certified_peer: PeerId },
+    #[error("Could not look up the client certificate of peer <{certified_peer}>: {cause}")]
+    CertificateLookup { certified_peer: PeerId, cause: String },
+    #[error("Peer <{peer_id}> was decommissioned. It has to be set up again with a new Setup-String.")]
+    PeerDecommissioned { peer_id: PeerId },
+    #[error("Could not determine whether peer <{peer_id}> was decommissioned: {cause}")]
+    DecommissionLookup { peer_id: PeerId, cause: String },
+}
+
+/// Provides the OIDC client IDs which were registered for a peer.
+pub trait PeerClientRegistry {
+    async fn client_ids_of_peer(&self, peer_id: PeerId) -> Result<Vec<String>, AuthorizationError>;
+}
+
+impl PeerClientRegistry for Option<RegistrationClientRef> {
+    async fn client_ids_of_peer(&self, peer_id: PeerId) -> Result<Vec<String>, AuthorizationError> {
+        match self {
+            None => Ok(Vec::new()),
+            Some(registration_client) => {
+                match &registration_client.config.peer_credentials {
+                    Some(peer_credentials) => {
+                        // All peers share the same, statically configured client
+                        Ok(vec![Clone::clone(&peer_credentials.client_id).value()])
+                    }
+                    None => {
+                        let clients = registration_client.list_clients().await
+                            .map_err(|cause| AuthorizationError::PeerClientLookup { peer_id, cause: cause.to_string() })?;
+
+                        let client_ids = clients.filter_clients_by_resource_id(peer_id.into())
+                            .into_iter()
+                            .map(|client| client.client_id)
+                            .collect();
+                        Ok(client_ids)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Determines the permission required to call the RPC designated by the path of a gRPC request, e.g. `/opendut.carl.services.peer_manager.PeerManager/StorePeerDescriptor`.
+/// Returns `None` for unknown RPCs, which are then only accessible to administrators.
+pub fn required_permission(path: &str) -> Option<Permission> {
+    let (service, method) = path.trim_start_matches('/').split_once('/')?;
+
+    let permission = match (service, method) {
+        (metadata_provider_server::SERVICE_NAME, "Version") => Permission::ReadMetadata,
+
+        (peer_manager_server::SERVICE_NAME, "GetPeerDescriptor" | "ListPeerDescriptors" | "GetPeerState" | "ListPeerStates" | "ListDevices" | "ListPeerSetups") => Permission::ReadPeers,
+        (peer_manager_server::SERVICE_NAME, "StorePeerDescriptor" | "DeletePeerDescriptor" | "GeneratePeerSetup" | "GenerateCleoSetup" | "RevokePeerSetup" | "DecommissionPeer" | "RotatePeerCredentials") => Permission::WritePeers,
+        (peer_manager_server::SERVICE_NAME, "RedeemPeerSetup") => Permission::RedeemPeerSetup,
+
+        (cluster_manager_server::SERVICE_NAME, "GetClusterConfiguration" | "ListClusterConfigurations" | "GetClusterDeployment" | "ListClusterDeployments" | "ListClusterPeerStates") => Permission::ReadClusters,
+        (cluster_manager_server::SERVICE_NAME, "CreateClusterConfiguration" | "DeleteClusterConfiguration" | "StoreClusterDeployment" | "DeleteClusterDeployment") => Permission::WriteClusters,
+
+        (observer_messaging_broker_server::SERVICE_NAME, "WaitForPeersOnline") => Permission::ObservePeers,
+        (observer_messaging_broker_server::SERVICE_NAME, "Watch") => Permission::WatchResources,
+
+        (peer_messaging_broker_server::SERVICE_NAME, "Open") => Permission::OpenPeerStream,
+
+        (audit_log_server::SERVICE_NAME, "ListAuditEntries") => Permission::ReadAuditLog,
+
+        (administration_server::SERVICE_NAME, "ExportSnapshot") => Permission::ExportSnapshot,
+        (administration_server::SERVICE_NAME, "GetVpnReconciliationReport") => Permission::ReconcileVpn,
+
+        (token_manager_server::SERVICE_NAME, "CreatePersonalAccessToken" | "ListPersonalAccessTokens" | "RevokePersonalAccessToken") => Permission::ManagePersonalAccessTokens,
+
+        (notification_manager_server::SERVICE_NAME, "CreateNotificationSubscription" | "ListNotificationSubscriptions" | "DeleteNotificationSubscription") => Permission::ManageNotifications,
+
+        _ => return None,
+    };
+    Some(permission)
+}
+
+/// Authorizes requests, which carry no OIDC token, because authentication is disabled or EDGAR authenticated with a client certificate.
+/// A client certificate only permits EDGAR to open the stream of the peer it was issued for, to redeem its Setup-String and to read metadata.
+pub fn authorize_without_token(client_certificates_enabled: bool, path: &str, certified_peer: Option<PeerId>, stream_peer: Option<PeerId>) -> Result<(), AuthorizationError> {
+    match certified_peer {
+        Some(certified_peer) => {
+            let permitted = match required_permission(path) {
+                Some(Permission::OpenPeerStream) => stream_peer == Some(certified_peer),
+                Some(Permission::ReadMetadata | Permission::RedeemPeerSetup) => true,
+                _ => false,
+            };
+            if permitted {
+                Ok(())
+            } else {
+                Err(AuthorizationError::CertificateNotBound { certified_peer, path: path.to_owned() })
+            }
+        }
+        None => {
+            if client_certificates_enabled && required_permission(path) == Some(Permission::OpenPeerStream) {
+                Err(AuthorizationError::ClientCertificateRequired)
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Checks a client certificate against the record CARL keeps of it, as the certificate itself remains valid until it expires.
+/// Certificates are rejected, if they are unknown, were revoked or belong to a peer, which was deleted.
+pub fn check_client_certificate(certificate: PeerCertificate, record: Option<&ClientCertificateRecord>, peer_exists: bool, now: SystemTime) -> Result<(), AuthorizationError> {
+    let PeerCertificate { peer_id: certified_peer, certificate_id } = certificate;
+
+    let is_valid = record.is_some_and(|record| record.peer_id == certified_peer && record.is_valid(now));
+    if !is_valid {
+        return Err(AuthorizationError::CertificateRevoked { certified_peer, certificate_id });
+    }
+    if !peer_exists {
+        return Err(AuthorizationError::CertifiedPeerNotFound { certified_peer });
+    }
+    Ok(())
+}
+
+async fn verify_client_certificate(resource_manager: &ResourceManagerRef, certificate: PeerCertificate) -> Result<(), AuthorizationError> {
+    let to_error = |cause: crate::resource::persistence::error::PersistenceError| AuthorizationError::CertificateLookup { certified_peer: certificate.peer_id, cause: cause.to_string() };
+
+    let record = resource_manager.get::<ClientCertificateRecord>(certificate.certificate_id).await
+        .map_err(to_error)?;
+    let peer = resource_manager.get::<PeerDescriptor>(certificate.peer_id).await
+        .map_err(to_error)?;
+
+    check_client_certificate(certificate, record.as_ref(), peer.is_some(), SystemTime::now())
+}
+
+/// Rejects opening the stream of a decommissioned peer, regardless of the presented credentials,
+/// as OIDC tokens issued before the decommissioning remain valid until they expire.
+async fn check_peer_not_decommissioned(resource_manager: &ResourceManagerRef, peer_id: PeerId) -> Result<(), AuthorizationError> {
+    let decommission = resource_manager.get::<PeerDecommissionRecord>(peer_id).await
+        .map_err(|cause| AuthorizationError::DecommissionLookup { peer_id, cause: cause.to_string() })?;
+    match decommission {
+        Some(_) => Err(AuthorizationError::PeerDecommissioned { peer_id }),
+        None => Ok(()),
+    }
+}
+
+#[derive(Clone)]
+pub struct GrpcAuthorizationLayer {
+    pub policy: AuthorizationPolicy,
+    pub peer_clients: Option<RegistrationClientRef>,
+    /// Whether EDGAR has to authenticate its stream with a client certificate, unless it presents an OIDC token.
+    pub client_certificates_enabled: bool,
+    pub projects: ProjectPolicy,
+    /// Used for checking presented client certificates against their records.
+    pub resource_manager: ResourceManagerRef,
+}
+
+/// Middleware which checks the [`CurrentUser`] inserted by the [`GrpcAuthenticationLayer`](crate::auth::grpc_auth_layer::GrpcAuthenticationLayer) against the [`AuthorizationPolicy`].
+/// Authorized requests are annotated with the [`ProjectScope`] and the [`OwnershipScope`] of the user.
+pub async fn authorization_middleware(State(layer): State<Arc<GrpcAuthorizationLayer>>, mut request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_owned();
+
+    if let (Some(Permission::OpenPeerStream), Some(peer_id)) = (required_permission(&path), extract_peer_id(&request)) {
+        if let Err(cause) = check_peer_not_decommissioned(&layer.resource_manager, peer_id).await {
+            debug!("Blocking request to '{path}': {cause}");
+            return match cause {
+                AuthorizationError::DecommissionLookup { .. } => Status::unavailable(cause.to_string()).into_http().map(axum::body::Body::new),
+                _ => permission_denied(format!("CARL says, you are not allowed to do this: {cause}")),
+            };
+        }
+    }
+
+    let Some(user) = request.extensions().get::<CurrentUser>().cloned() else {
+        let certified_peer = request.extensions().get::<CertifiedPeer>().and_then(|peer| peer.0);
+
+        if let Some(certificate) = certified_peer {
+            if let Err(cause) = verify_client_certificate(&layer.resource_manager, certificate).await {
+                debug!("Blocking request to '{path}': {cause}");
+                return match cause {
+                    AuthorizationError::CertificateLookup { .. } => Status::unavailable(cause.to_string()).into_http().map(axum::body::Body::new),
+                    _ => Status::unauthenticated(format!("CARL says, your client certificate is not valid: {cause}")).into_http().map(axum::body::Body::new),
+                };
+            }
+        }
+        let certified_peer = certified_peer.map(|certificate| certificate.peer_id);
+
+        return match authorize_without_token(layer.client_certificates_enabled, &path, certified_peer, extract_peer_id(&request)) {
+            Ok(()) => {
+                if certified_peer.is_none() {
+                    // authentication is disabled, otherwise the request would have been rejected for lacking credentials
+                    request.extensions_mut().insert(ProjectScope::Unrestricted);
+                    request.extensions_mut().insert(OwnershipScope::Unrestricted);
+                }
+                next.run(request).await
+            }
+            Err(cause) => {
+                debug!("Blocking request to '{path}': {cause}");
+                permission_denied(format!("CARL says, you are not allowed to do this: {cause}"))
+            }
+        };
+    };
+
+    let result = match required_permission(&path) {
+        Some(Permission::OpenPeerStream) => {
+            match extract_peer_id(&request) {
+                Some(peer_id) => layer.policy.authorize_peer_stream(&user, peer_id, &layer.peer_clients).await
+                    .map(|()| layer.policy.roles_of(&user)),
+                None => layer.policy.authorize(&user, Permission::OpenPeerStream),
+            }
+        }
+        Some(permission) => layer.policy.authorize(&user, permission),
+        None => {
+            let roles = layer.policy.roles_of(&user);
+            if matches!(layer.policy, AuthorizationPolicy::Disabled) || roles.contains(&Role::Administrator) {
+                Ok(roles)
+            } else {
+                warn!("Denying access of user '{}' to unknown RPC '{path}'.", user.name);
+                return permission_denied(format!("CARL says, access to '{path}' is restricted to administrators!"));
+            }
+        }
+    };
+
+    match result {
+        Ok(roles) => {
+            let scope = layer.projects.scope_of(&layer.policy, &user, &roles);
+            request.extensions_mut().insert(scope);
+            let ownership = layer.policy.ownership_scope_of(&user, &roles);
+            request.extensions_mut().insert(ownership);
+            next.run(request).await
+        }
+        Err(cause) => {
+            debug!("Blocking request to '{path}': {cause}");
+            match cause {
+                AuthorizationError::PeerClientLookup { .. } => Status::unavailable(cause.to_string()).into_http().map(axum::body::Body::new),
+                _ => permission_denied(format!("CARL says, you are not allowed to do this: {cause}")),
+            }
+        }
+    }
+}
+
+fn extract_peer_id(request: &Request) -> Option<PeerId> {
+    let value = request.headers().get(stream_header::ID)?.to_str().ok()?;
+    Uuid::from_str(value).ok().map(PeerId::from)
+}
+
+fn permission_denied(message: String) -> Response {
+    Status::permission_denied(message).into_http().map(axum::body::Body::new)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::{fixture, rstest};
+    use url::Url;
+
+    use super::*;
+    use opendut_types::user::token::PersonalAccessTokenId;
+
+    use crate::auth::in_memory_cache::CustomInMemoryCache;
+    use crate::auth::personal_access_tokens::PersonalAccessTokenGrant;
+    use crate::auth::validation::authorize_user;
+    use crate::auth::validation::tests::{MockJwk, ISSUER_URL, JWK_RAW_DATA, TEST_TOKEN};
+
+    /// The test token was issued by the stand-in issuer for user `opendut`,
+    /// with the role `testrole`, the group `/testgroup` and the authorized party `opendut-lea-client`.
+    #[fixture]
+    async fn current_user() -> CurrentUser {
+        let issuer_url = Url::parse(ISSUER_URL).unwrap();
+        let stand_in_issuer = MockJwk { jwk: String::from(JWK_RAW_DATA) };
+
+        authorize_user(issuer_url.clone(), issuer_url, TEST_TOKEN, CustomInMemoryCache::new(), stand_in_issuer, false).await
+            .expect("Token of stand-in issuer should be valid")
+    }
+
+    fn policy_granting(role: Role, mapping: RoleMapping) -> AuthorizationPolicy {
+        AuthorizationPolicy::Enabled { mappings: vec![(role, mapping)] }
+    }
+
+    fn by_role(role: &str) -> RoleMapping {
+        RoleMapping { roles: vec![role.to_owned()], groups: vec![] }
+    }
+
+    fn by_group(group: &str) -> RoleMapping {
+        RoleMapping { roles: vec![], groups: vec![group.to_owned()] }
+    }
+
+    struct MockPeerClients(Vec<String>);
+
+    impl PeerClientRegistry for MockPeerClients {
+        async fn client_ids_of_peer(&self, _peer_id: PeerId) -> Result<Vec<String>, AuthorizationError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_grant_everything_if_authorization_is_disabled(#[future] current_user: CurrentUser) -> anyhow::Result<()> {
+        let user = current_user.await;
+
+        let result = AuthorizationPolicy::Disabled.authorize(&user, Permission::WriteClusters);
+
+        assert!(result.is_ok());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_map_roles_and_groups_of_token(#[future] current_user: CurrentUser) -> anyhow::Result<()> {
+        let user = current_user.await;
+        let policy = AuthorizationPolicy::Enabled {
+            mappings: vec![
+                (Role::Administrator, by_role("admin")),
+                (Role::Editor, by_group("testgroup")),
+                (Role::Viewer, by_role("testrole")),
+            ],
+        };
+
+        let roles = policy.roles_of(&user);
+
+        assert_that!(roles, unordered_elements_are![eq(&Role::Editor), eq(&Role::Viewer)]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn viewer_should_not_be_allowed_to_write(#[future] current_user: CurrentUser) -> anyhow::Result<()> {
+        let user = current_user.await;
+        let policy = policy_granting(Role::Viewer, by_role("testrole"));
+
+        assert!(policy.authorize(&user, Permission::ReadPeers).is_ok());
+        assert!(policy.authorize(&user, Permission::ReadClusters).is_ok());
+        assert_that!(
+            policy.authorize(&user, Permission::WritePeers),
+            err(eq(&AuthorizationError::PermissionDenied { user: String::from("opendut"), permission: Permission::WritePeers }))
+        );
+        assert!(policy.authorize(&user, Permission::WriteClusters).is_err());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn user_without_matching_role_should_be_denied(#[future] current_user: CurrentUser) -> anyhow::Result<()> {
+        let user = current_user.await;
+        let policy = policy_granting(Role::Administrator, by_role("admin"));
+
+        assert!(policy.authorize(&user, Permission::ReadMetadata).is_err());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn edgar_should_only_open_stream_of_own_peer(#[future] current_user: CurrentUser) -> anyhow::Result<()> {
+        let user = current_user.await;
+        let policy = policy_granting(Role::Edgar, by_group("testgroup"));
+        let peer_id = PeerId::random();
+
+        let own_peer = MockPeerClients(vec![String::from("opendut-lea-client")]);
+        assert!(policy.authorize_peer_stream(&user, peer_id, &own_peer).await.is_ok());
+
+        let other_peer = MockPeerClients(vec![String::from("some-other-client")]);
+        assert_that!(
+            policy.authorize_peer_stream(&user, peer_id, &other_peer).await,
+            err(eq(&AuthorizationError::PeerNotBound { user: String::from("opendut"), peer_id }))
+        );
+
+        assert!(policy.authorize(&user, Permission::ReadPeers).is_err());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn administrator_should_open_stream_of_any_peer(#[future] current_user: CurrentUser) -> anyhow::Result<()> {
+        let user = current_user.await;
+        let policy = policy_granting(Role::Administrator, by_role("testrole"));
+
+        let result = policy.authorize_peer_stream(&user, PeerId::random(), &MockPeerClients(vec![])).await;
+
+        assert!(result.is_ok());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn personal_access_token_should_limit_roles_to_its_scopes(#[future] current_user: CurrentUser) -> anyhow::Result<()> {
+        let user = CurrentUser {
+            personal_access_token: Some(PersonalAccessTokenGrant {
+                id: PersonalAccessTokenId::random(),
+                scopes: vec![PersonalAccessTokenScope::Viewer],
+            }),
+            ..current_user.await
+        };
+        let policy = AuthorizationPolicy::Enabled {
+            mappings: vec![
+                (Role::Administrator, by_role("testrole")),
+                (Role::Viewer, by_role("testrole")),
+            ],
+        };
+
+        assert_that!(policy.roles_of(&user), unordered_elements_are![eq(&Role::Viewer)]);
+        assert!(policy.authorize(&user, Permission::ReadPeers).is_ok());
+        assert!(policy.authorize(&user, Permission::WritePeers).is_err());
+        assert_that!(
+            policy.authorize(&user, Permission::ManagePersonalAccessTokens),
+            err(eq(&AuthorizationError::PermissionDenied { user: String::from("opendut"), permission: Permission::ManagePersonalAccessTokens }))
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn personal_access_token_should_not_grant_roles_the_owner_lost(#[future] current_user: CurrentUser) -> anyhow::Result<()> {
+        let user = CurrentUser {
+            personal_access_token: Some(PersonalAccessTokenGrant {
+                id: PersonalAccessTokenId::random(),
+                scopes: vec![PersonalAccessTokenScope::Editor, PersonalAccessTokenScope::Viewer],
+            }),
+            ..current_user.await
+        };
+        let policy = AuthorizationPolicy::Enabled {
+            mappings: vec![
+                (Role::Editor, by_role("editor")),
+                (Role::Viewer, by_role("testrole")),
+            ],
+        };
+
+        assert_that!(policy.roles_of(&user), unordered_elements_are![eq(&Role::Viewer)]);
+        assert!(policy.authorize(&user, Permission::WritePeers).is_err());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn only_administrators_should_modify_resources_created_by_others(#[future] current_user: CurrentUser) -> anyhow::Result<()> {
+        let user = current_user.await;
+
+        let editor = policy_granting(Role::Editor, by_role("testrole"));
+        assert_that!(editor.ownership_scope_of(&user, &editor.roles_of(&user)), eq(&OwnershipScope::CreatedBy { user: String::from("opendut") }));
+
+        let administrator = policy_granting(Role::Administrator, by_role("testrole"));
+        assert_that!(administrator.ownership_scope_of(&user, &administrator.roles_of(&user)), eq(&OwnershipScope::Unrestricted));
+
+        assert_that!(AuthorizationPolicy::Disabled.ownership_scope_of(&user, &HashSet::new()), eq(&OwnershipScope::Unrestricted));
+        Ok(())
+    }
+
+    #[test]
+    fn client_certificate_should_only_permit_stream_of_own_peer() {
+        const OPEN_STREAM: &str = "/opendut.carl.services.peer_messaging_broker.PeerMessagingBroker/Open";
+        let peer_id = PeerId::random();
+        let other_peer_id = PeerId::random();
+
+        assert!(authorize_without_token(true, OPEN_STREAM, Some(peer_id), Some(peer_id)).is_ok());
+        assert!(authorize_without_token(true, "/opendut.carl.services.peer_manager.PeerManager/RedeemPeerSetup", Some(peer_id), None).is_ok());
+        assert_that!(
+            authorize_without_token(true, OPEN_STREAM, Some(peer_id), Some(other_peer_id)),
+            err(eq(&AuthorizationError::CertificateNotBound { certified_peer: peer_id, path: String::from(OPEN_STREAM) }))
+        );
+        assert!(authorize_without_token(true, "/opendut.carl.services.peer_manager.PeerManager/ListPeerDescriptors", Some(peer_id), None).is_err());
+
+        assert_that!(authorize_without_token(true, OPEN_STREAM, None, Some(peer_id)), err(eq(&AuthorizationError::ClientCertificateRequired)));
+        assert!(authorize_without_token(false, OPEN_STREAM, None, Some(peer_id)).is_ok(), "Without client certificates and OIDC, authentication is disabled.");
+    }
+
+    #[test]
+    fn should_reject_revoked_or_unknown_client_certificates() {
+        let now = SystemTime::now();
+        let peer_id = PeerId::random();
+        let record = ClientCertificateRecord {
+            id: ClientCertificateId::random(),
+            peer_id,
+            issued_at: now,
+            expires_at: now + std::time::Duration::from_secs(3600),
+            revoked_at: None,
+        };
+        let certificate = PeerCertificate { peer_id, certificate_id: record.id };
+        let revoked = ClientCertificateRecord { revoked_at: Some(now), ..Clone::clone(&record) };
+        let foreign = ClientCertificateRecord { peer_id: PeerId::random(), ..Clone::clone(&record) };
+
+        assert!(check_client_certificate(certificate, Some(&record), true, now).is_ok());
+
+        assert_that!(check_client_certificate(certificate, None, true, now), err(eq(&AuthorizationError::CertificateRevoked { certified_peer: peer_id, certificate_id: record.id })));
+        assert_that!(check_client_certificate(certificate, Some(&revoked), true, now), err(eq(&AuthorizationError::CertificateRevoked { certified_peer: peer_id, certificate_id: record.id })));
+        assert_that!(check_client_certificate(certificate, Some(&foreign), true, now), err(eq(&AuthorizationError::CertificateRevoked { certified_peer: peer_id, certificate_id: record.id })));
+        assert_that!(check_client_certificate(certificate, Some(&record), false, now), err(eq(&AuthorizationError::CertifiedPeerNotFound { certified_peer: peer_id })));
+    }
+
+    #[test]
+    fn should_determine_required_permission_of_rpc() {
+        assert_eq!(required_permission("/opendut.carl.services.peer_manager.PeerManager/ListPeerDescriptors"), Some(Permission::ReadPeers));
+        assert_eq!(required_permission("/opendut.carl.services.peer_manager.PeerManager/DeletePeerDescriptor"), Some(Permission::WritePeers));
+        assert_eq!(required_permission("/opendut.carl.services.peer_manager.PeerManager/RevokePeerSetup"), Some(Permission::WritePeers));
+        assert_eq!(required_permission("/opendut.carl.services.peer_manager.PeerManager/RedeemPeerSetup"), Some(Permission::RedeemPeerSetup));
+        assert_eq!(required_permission("/opendut.carl.services.peer_manager.PeerManager/DecommissionPeer"), Some(Permission::WritePeers));
+        assert_eq!(required_permission("/opendut.carl.services.cluster_manager.ClusterManager/StoreClusterDeployment"), Some(Permission::WriteClusters));
+        assert_eq!(required_permission("/opendut.carl.services.peer_messaging_broker.PeerMessagingBroker/Open"), Some(Permission::OpenPeerStream));
+        assert_eq!(required_permission("/opendut.carl.services.audit_log.AuditLog/ListAuditEntries"), Some(Permission::ReadAuditLog));
+        assert_eq!(required_permission("/opendut.carl.services.observer_messaging_broker.ObserverMessagingBroker/Watch"), Some(Permission::WatchResources));
+        assert_eq!(required_permission("/opendut.carl.services.administration.Administration/ExportSnapshot"), Some(Permission::ExportSnapshot));
+        assert_eq!(required_permission("/opendut.carl.services.administration.Administration/GetVpnReconciliationReport"), Some(Permission::ReconcileVpn));
+        assert_eq!(required_permission("/opendut.carl.services.token_manager.TokenManager/CreatePersonalAccessToken"), Some(Permission::ManagePersonalAccessTokens));
+        assert_eq!(required_permission("/opendut.carl.services.notification_manager.NotificationManager/DeleteNotificationSubscription"), Some(Permission::ManageNotifications));
+        assert_eq!(required_permission("/opendut.carl.services.peer_manager.PeerManager/Unknown"), None);
+        assert_eq!(required_permission("/index.html"), None);
+    }
+
+    #[tokio::test]
+    async fn should_reject_stream_of_decommissioned_peer() -> anyhow::Result<()> {
+        let resource_manager = crate::resource::manager::ResourceManager::new_in_memory();
+        let peer_id = PeerId::random();
+        assert_that!(check_peer_not_decommissioned(&resource_manager, peer_id).await, ok(()));
+
+        resource_manager.insert(peer_id, PeerDecommissionRecord { peer_id, decommissioned_at: SystemTime::now() }).await?;
+
+        assert_that!(check_peer_not_decommissioned(&resource_manager, peer_id).await, err(eq(&AuthorizationError::PeerDecommissioned { peer_id })));
+        Ok(())
+    }
+
+    #[test]
+    fn should_load_policy_from_settings() -> anyhow::Result<()> {
+        let settings = crate::settings::load_with_overrides(
+            config::Config::builder()
+                .set_override("network.oidc.authorization.enabled", true)?
+                .set_override("network.oidc.authorization.administrator.groups", "admins")?
+                .set_override("network.oidc.authorization.viewer.roles", "viewer, \"guest\"")?
+                .build()?
+        )?;
+
+        let policy = AuthorizationPolicy::load(&settings.config)?;
+
+        let AuthorizationPolicy::Enabled { mappings } = policy else {
+            panic!("Authorization should be enabled.");
+        };
+        assert_that!(mappings, contains(eq(&(Role::Administrator, RoleMapping { roles: vec![], groups: vec![String::from("admins")] }))));
+        assert_that!(mappings, contains(eq(&(Role::Viewer, RoleMapping { roles: vec![String::from("viewer"), String::from("guest")], groups: vec![] }))));
+        Ok(())
+    }
+}
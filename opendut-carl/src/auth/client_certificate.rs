@@ -0,0 +1,256 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{anyhow, Context};
+use config::Config;
+use pem::Pem;
+use rcgen::{CertificateParams, DistinguishedName, DnType, ExtendedKeyUsagePurpose, KeyPair, KeyUsagePurpose, SerialNumber};
+use rustls::pki_types::{CertificateDer, CertificateSigningRequestDer};
+use tracing::debug;
+
+use opendut_types::peer::certificate::{ClientCertificateId, ClientCertificateRecord};
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::{Certificate, ClientCertificate, ClientCertificateKey};
+use opendut_util::project;
+
+pub type ClientCertificateAuthorityRef = Arc<ClientCertificateAuthority>;
+
+/// Issues the client certificates, with which EDGAR authenticates towards CARL via mutual TLS.
+/// The ID of the peer is written as common name into the subject, which binds the certificate to that peer.
+/// The ID of the certificate is written as serial number, so that it can be looked up and revoked.
+pub struct ClientCertificateAuthority {
+    ca_certificate_der: CertificateDer<'static>,
+    issuer: rcgen::Certificate,
+    issuer_key: KeyPair,
+    validity: Duration,
+}
+
+impl ClientCertificateAuthority {
+    /// Returns `None`, if CARL is configured to not issue client certificates.
+    pub fn load(settings: &Config) -> anyhow::Result<Option<ClientCertificateAuthorityRef>> {
+        let enabled = settings.get_bool("network.tls.client.enabled")
+            .map_err(|cause| anyhow!("Expected configuration flag 'network.tls.client.enabled' to be parseable as boolean! {}", cause))?;
+
+        if !enabled {
+            return Ok(None);
+        }
+
+        let ca_certificate = {
+            let path = project::make_path_absolute(settings.get_string("network.tls.client.ca.certificate")?)?;
+            debug!("Using CA certificate for issuing client certificates: {}", path.display());
+            std::fs::read_to_string(&path)
+                .context(format!("Error while reading CA certificate for client certificates at {}", path.display()))?
+        };
+        let ca_key = {
+            let path = project::make_path_absolute(settings.get_string("network.tls.client.ca.key")?)?;
+            std::fs::read_to_string(&path)
+                .context(format!("Error while reading CA key for client certificates at {}", path.display()))?
+        };
+        let validity = Duration::from_millis(settings.get::<u64>("network.tls.client.certificate.validity.ms")?);
+
+        let authority = Self::from_pem(&ca_certificate, &ca_key, validity)?;
+        Ok(Some(Arc::new(authority)))
+    }
+
+    pub fn from_pem(ca_certificate: &str, ca_key: &str, validity: Duration) -> anyhow::Result<Self> {
+        let ca_certificate_der = pem::parse(ca_certificate)
+            .context("Failed to parse CA certificate for client certificates as PEM.")?
+            .into_contents();
+
+        let issuer_key = KeyPair::from_pem(ca_key)
+            .context("Failed to parse CA key for client certificates.")?;
+        // Signing requires the CA in the form of rcgen. The re-created certificate carries the same subject and key as the original one.
+        let issuer = CertificateParams::from_ca_cert_pem(ca_certificate)
+            .and_then(|params| params.self_signed(&issuer_key))
+            .context("Failed to load CA certificate for client certificates.")?;
+
+        Ok(Self {
+            ca_certificate_der: CertificateDer::from(ca_certificate_der),
+            issuer,
+            issuer_key,
+            validity,
+        })
+    }
+
+    /// The CA certificate, against which the client certificates are verified.
+    pub fn ca_certificate(&self) -> CertificateDer<'static> {
+        Clone::clone(&self.ca_certificate_der)
+    }
+
+    /// Issues a new client certificate for the given peer, including its key.
+    /// The returned record needs to be persisted, otherwise CARL rejects the certificate.
+    pub fn issue(&self, peer_id: PeerId) -> Result<IssuedClientCertificate, IssueClientCertificateError> {
+        let to_error = |cause| IssueClientCertificateError { peer_id, cause };
+
+        let key = KeyPair::generate().map_err(to_error)?;
+        let (record, params) = self.certificate_params(peer_id).map_err(to_error)?;
+
+        let certificate = params.signed_by(&key, &self.issuer, &self.issuer_key).map_err(to_error)?;
+        debug!("Issued client certificate <{}> for peer <{peer_id}>, valid until {}.", record.id, params.not_after);
+
+        Ok(IssuedClientCertificate {
+            record,
+            client_certificate: ClientCertificate {
+                certificate: Certificate(Pem::new("CERTIFICATE", certificate.der().to_vec())),
+                key: ClientCertificateKey(Pem::new("PRIVATE KEY", key.serialize_der())),
+            },
+        })
+    }
+
+    /// Issues a new client certificate for the given peer for the key, which the peer generated itself and signed the request with.
+    /// Only the public key is taken from the request. All other contents of the certificate are determined by CARL.
+    /// The returned record needs to be persisted, otherwise CARL rejects the certificate.
+    pub fn issue_for_request(&self, peer_id: PeerId, certificate_signing_request: &[u8]) -> Result<SignedClientCertificate, IssueClientCertificateError> {
+        let to_error = |cause| IssueClientCertificateError { peer_id, cause };
+
+        let mut request = rcgen::CertificateSigningRequestParams::from_der(&CertificateSigningRequestDer::from(certificate_signing_request))
+            .map_err(to_error)?;
+        let (record, params) = self.certificate_params(peer_id).map_err(to_error)?;
+        request.params = params;
+        let not_after = request.params.not_after;
+
+        let certificate = request.signed_by(&self.issuer, &self.issuer_key).map_err(to_error)?;
+        debug!("Issued client certificate <{}> for peer <{peer_id}> on request, valid until {not_after}.", record.id);
+
+        Ok(SignedClientCertificate {
+            record,
+            certificate: Certificate(Pem::new("CERTIFICATE", certificate.der().to_vec())),
+        })
+    }
+
+    /// Binds a certificate to the peer by writing the ID of the peer as common name and the ID of the certificate as serial number.
+    fn certificate_params(&self, peer_id: PeerId) -> Result<(ClientCertificateRecord, CertificateParams), rcgen::Error> {
+        let id = ClientCertificateId::random();
+
+        let mut params = CertificateParams::new(Vec::<String>::new())?;
+        params.distinguished_name = {
+            let mut name = DistinguishedName::new();
+            name.push(DnType::CommonName, peer_id.to_string());
+            name
+        };
+        params.serial_number = Some(SerialNumber::from_slice(id.0.as_bytes()));
+        let now = SystemTime::now();
+        params.not_before = time::OffsetDateTime::from(now);
+        params.not_after = time::OffsetDateTime::from(now + self.validity);
+        params.key_usages = vec![KeyUsagePurpose::DigitalSignature];
+        params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+
+        let record = ClientCertificateRecord {
+            id,
+            peer_id,
+            issued_at: now,
+            expires_at: now + self.validity,
+            revoked_at: None,
+        };
+        Ok((record, params))
+    }
+}
+
+pub struct IssuedClientCertificate {
+    pub record: ClientCertificateRecord,
+    pub client_certificate: ClientCertificate,
+}
+
+/// A client certificate issued for a key, which CARL does not know.
+pub struct SignedClientCertificate {
+    pub record: ClientCertificateRecord,
+    pub certificate: Certificate,
+}
+
+/// Determines the peer, for which a client certificate was issued, and the ID of the certificate.
+/// Only meaningful for certificates, which were verified against the [`ClientCertificateAuthority`].
+pub fn peer_of_certificate(certificate: &[u8]) -> Option<(PeerId, ClientCertificateId)> {
+    let (_, certificate) = x509_parser::parse_x509_certificate(certificate).ok()?;
+    let common_name = certificate.subject().iter_common_name().next()?.as_str().ok()?;
+    let peer_id = PeerId::try_from(common_name).ok()?;
+
+    // DER-encoding strips leading zero bytes and may prepend one to keep the number positive.
+    let serial = certificate.raw_serial();
+    let significant = &serial[serial.iter().take_while(|byte| **byte == 0).count()..];
+    let mut uuid = [0u8; 16];
+    uuid.get_mut(16usize.checked_sub(significant.len())?..)?.copy_from_slice(significant);
+
+    Some((peer_id, ClientCertificateId::from(uuid::Uuid::from_bytes(uuid))))
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("Failed to issue client certificate for peer <{peer_id}>")]
+pub struct IssueClientCertificateError {
+    peer_id: PeerId,
+    #[source] cause: rcgen::Error,
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use googletest::prelude::*;
+    use rcgen::{BasicConstraints, IsCa};
+
+    use super::*;
+
+    pub fn create_client_certificate_authority() -> anyhow::Result<ClientCertificateAuthority> {
+        let ca_key = KeyPair::generate()?;
+        let mut params = CertificateParams::new(Vec::<String>::new())?;
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        params.distinguished_name.push(DnType::CommonName, "openDuT Test Client CA");
+        let ca_certificate = params.self_signed(&ca_key)?;
+
+        ClientCertificateAuthority::from_pem(&ca_certificate.pem(), &ca_key.serialize_pem(), Duration::from_secs(3600))
+    }
+
+    #[test]
+    fn should_issue_client_certificate_bound_to_peer() -> anyhow::Result<()> {
+        let authority = create_client_certificate_authority()?;
+        let peer_id = PeerId::random();
+
+        let IssuedClientCertificate { record, client_certificate } = authority.issue(peer_id)?;
+
+        assert_that!(client_certificate.certificate.0.tag(), eq("CERTIFICATE"));
+        assert_that!(client_certificate.key.0.tag(), eq("PRIVATE KEY"));
+        assert_that!(peer_of_certificate(client_certificate.certificate.0.contents()), some(eq((peer_id, record.id))));
+        assert_that!(peer_of_certificate(authority.ca_certificate().as_ref()), none());
+        assert_that!(record.peer_id, eq(peer_id));
+        assert_that!(record.revoked_at, none());
+        Ok(())
+    }
+
+    #[test]
+    fn should_issue_client_certificate_for_key_of_the_peer() -> anyhow::Result<()> {
+        let authority = create_client_certificate_authority()?;
+        let peer_id = PeerId::random();
+
+        let peer_key = KeyPair::generate()?;
+        let mut requested = CertificateParams::new(vec![String::from("carl.opendut.local")])?;
+        requested.distinguished_name.push(DnType::CommonName, PeerId::random().to_string());
+        let request = requested.serialize_request(&peer_key)?;
+
+        let SignedClientCertificate { record, certificate } = authority.issue_for_request(peer_id, request.der())?;
+
+        assert_that!(peer_of_certificate(certificate.0.contents()), some(eq((peer_id, record.id))));
+        let (_, parsed) = x509_parser::parse_x509_certificate(certificate.0.contents())?;
+        assert_that!(parsed.public_key().raw, eq(peer_key.public_key_der().as_slice()));
+        assert_that!(parsed.subject_alternative_name()?, none());
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_issue_client_certificate_for_malformed_request() -> anyhow::Result<()> {
+        let authority = create_client_certificate_authority()?;
+
+        let result = authority.issue_for_request(PeerId::random(), &[1, 2, 3]);
+
+        assert_that!(result, err(anything()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_recover_certificate_id_from_serial_number_regardless_of_leading_bits() -> anyhow::Result<()> {
+        let authority = create_client_certificate_authority()?;
+
+        for _ in 0..32 {
+            let IssuedClientCertificate { record, client_certificate } = authority.issue(PeerId::random())?;
+            let (_, id) = peer_of_certificate(client_certificate.certificate.0.contents()).unwrap();
+            assert_that!(id, eq(record.id));
+        }
+        Ok(())
+    }
+}
@@ -3,8 +3,11 @@ use crate::auth::json_web_key::JwkCacheValue;
 use crate::auth::validation::{authorize_user, Jwk, ValidationError};
 use crate::auth::CurrentUser;
 use crate::auth::in_memory_cache::CustomInMemoryCache;
+use crate::auth::local_users::LocalUserStoreRef;
+use crate::auth::personal_access_tokens::{is_personal_access_token, PersonalAccessTokenAuthenticator};
+use crate::startup::tls::CertifiedPeer;
 use tonic::Status;
-use tracing::debug;
+use tracing::{debug, warn};
 use url::Url;
 
 #[allow(clippy::large_enum_variant)]
@@ -15,6 +18,12 @@ pub enum GrpcAuthenticationLayer {
         issuer_url: Url,
         issuer_remote_url: Url,
         cache: CustomInMemoryCache<String, JwkCacheValue>,
+        personal_access_tokens: PersonalAccessTokenAuthenticator,
+    },
+    /// Users authenticate with tokens issued by CARL's built-in user store.
+    LocalUsers {
+        store: LocalUserStoreRef,
+        personal_access_tokens: PersonalAccessTokenAuthenticator,
     },
 }
 
@@ -25,24 +34,43 @@ impl GrpcAuthenticationLayer {
             GrpcAuthenticationLayer::AuthDisabled => {
                 Ok(request)
             }
-            GrpcAuthLayerEnabled { issuer_url, issuer_remote_url, cache } => {
-                let auth_header = match request.metadata().get("authorization") {
-                    None => {
-                        return Err(Status::unauthenticated("CARL says, you did not provide credentials!"))
+            GrpcAuthLayerEnabled { issuer_url, issuer_remote_url, cache, personal_access_tokens } => {
+                let Some(auth_header) = authorization_header(&request)? else {
+                    return Ok(request);
+                };
+                if is_personal_access_token(bearer_token(&auth_header)) {
+                    return authenticate_personal_access_token(request, &auth_header, &personal_access_tokens).await;
+                }
+
+                match authorize_current_user(&auth_header, issuer_url, issuer_remote_url, cache).await {
+                    Ok(user) => {
+                        if let Err(cause) = personal_access_tokens.record_owner_claims(&user).await {
+                            warn!("Failed to record current roles and groups of user '{}' for their personal access tokens: {cause}", user.name);
+                        }
+                        request.extensions_mut().insert(user);
+                        Ok(request)
                     }
-                    Some(token) => {
-                        token.to_str()
-                            .map_err(|_| Status::unauthenticated("CARL says, your credentials are malformed!"))?
+                    Err(cause) => {
+                        debug!("Blocking authentication attempt due to error while validating credentials: {cause}");
+                        Err(Status::unauthenticated("CARL says, invalid credentials!"))
                     }
+                }
+            }
+            GrpcAuthenticationLayer::LocalUsers { store, personal_access_tokens } => {
+                let Some(auth_header) = authorization_header(&request)? else {
+                    return Ok(request);
                 };
+                if is_personal_access_token(bearer_token(&auth_header)) {
+                    return authenticate_personal_access_token(request, &auth_header, &personal_access_tokens).await;
+                }
 
-                match authorize_current_user(auth_header, issuer_url, issuer_remote_url, cache).await {
+                match store.authenticate(bearer_token(&auth_header)) {
                     Ok(user) => {
                         request.extensions_mut().insert(user);
                         Ok(request)
                     }
                     Err(cause) => {
-                        debug!("Blocking authentication attempt due to error while validating credentials: {cause}");
+                        debug!("Blocking authentication attempt of local user due to error while validating credentials: {cause}");
                         Err(Status::unauthenticated("CARL says, invalid credentials!"))
                     }
                 }
@@ -51,6 +79,48 @@ impl GrpcAuthenticationLayer {
     }
 }
 
+/// Returns `None`, if the request carries no token, but EDGAR authenticated with a client certificate instead.
+fn authorization_header(request: &tonic::Request<()>) -> Result<Option<String>, Status> {
+    match request.metadata().get("authorization") {
+        None => {
+            let certified_peer = request.extensions().get::<CertifiedPeer>().and_then(|peer| peer.0);
+            if certified_peer.is_some() {
+                // EDGAR authenticated with a client certificate, which the authorization middleware restricts to its own peer
+                return Ok(None);
+            }
+            Err(Status::unauthenticated("CARL says, you did not provide credentials!"))
+        }
+        Some(token) => {
+            let token = token.to_str()
+                .map_err(|_| Status::unauthenticated("CARL says, your credentials are malformed!"))?;
+            Ok(Some(token.to_owned()))
+        }
+    }
+}
+
+async fn authenticate_personal_access_token(mut request: tonic::Request<()>, auth_header: &str, personal_access_tokens: &PersonalAccessTokenAuthenticator) -> Result<tonic::Request<()>, Status> {
+    match personal_access_tokens.authenticate(bearer_token(auth_header)).await {
+        Ok(user) => {
+            request.extensions_mut().insert(user);
+            Ok(request)
+        }
+        Err(cause @ ValidationError::OutdatedClaims(_)) => {
+            debug!("Blocking authentication attempt with personal access token, since the roles and groups of its owner are outdated: {cause}");
+            Err(Status::unauthenticated("CARL says, the roles and groups of the token's owner are outdated. The owner needs to sign in via the identity provider to refresh them."))
+        }
+        Err(cause) => {
+            debug!("Blocking authentication attempt with personal access token due to error while validating credentials: {cause}");
+            Err(Status::unauthenticated("CARL says, invalid credentials!"))
+        }
+    }
+}
+
+fn bearer_token(auth_header: &str) -> &str {
+    auth_header.split_once(' ')
+        .map(|(_, token)| token)
+        .unwrap_or_default()
+}
+
 async fn authorize_current_user(auth_token: &str, issuer_url: Url, issuer_remote_url: Url, cache: CustomInMemoryCache<String, JwkCacheValue>) -> Result<CurrentUser, ValidationError> {
     // decode token
     let token_part: Vec<&str> = auth_token.split(' ').collect();
@@ -0,0 +1,457 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::num::NonZeroU32;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::{anyhow, bail};
+use base64::engine::general_purpose::{STANDARD_NO_PAD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use config::Config;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Serialize;
+use tokio::sync::Semaphore;
+use tracing::{debug, warn};
+use url::Url;
+
+use opendut_auth::registration::resources::ResourceHomeUrl;
+use opendut_types::user::{LocalUser, LocalUserId, LocalUserName, LocalUserRole, LocalUserSecretHash};
+
+use crate::auth::validation::ValidationError;
+use crate::auth::{Claims, CurrentUser, MyAdditionalClaims};
+use crate::resource::manager::ResourceManagerRef;
+use crate::resource::persistence::error::PersistenceError;
+use crate::resource::storage::ResourcesStorageApi;
+
+pub type LocalUserStoreRef = Arc<LocalUserStore>;
+
+/// Path below the CARL URL, which serves as issuer of the tokens for users of the built-in user store.
+/// The token endpoint is located at the same path relative to the issuer as with Keycloak, so CLEO can be configured like for an OIDC provider.
+pub const LOCAL_ISSUER_PATH: &str = "auth/";
+/// Path of the token endpoint below the issuer.
+pub const TOKEN_ENDPOINT_PATH: &str = "protocol/openid-connect/token";
+const TOKEN_AUDIENCE: &str = "opendut-carl";
+
+const SECRET_HASH_ALGORITHM: &str = "pbkdf2-sha256";
+const SECRET_HASH_ITERATIONS: u32 = 600_000;
+
+/// Authenticates users with the password or API token stored for them in CARL's database, as an alternative to an OIDC provider.
+/// Issues short-lived tokens, which are signed with a key generated on startup, so tokens become invalid when CARL restarts.
+pub struct LocalUserStore {
+    resource_manager: ResourceManagerRef,
+    issuer: Url,
+    token_validity: Duration,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    /// Verified against, if a user does not exist, so that the response time does not reveal which users exist.
+    unknown_user_hash: LocalUserSecretHash,
+    login_throttle: LoginThrottle,
+}
+
+/// Limits the guessing of passwords and API tokens via the token endpoint.
+#[derive(Clone, Copy, Debug)]
+pub struct LoginLimits {
+    /// How many secrets are verified at once. Each verification occupies a thread of the blocking pool for a noticeable time.
+    pub concurrency: usize,
+    /// How often verification may fail for a client ID, before further attempts are refused.
+    pub max_failures: u32,
+    /// How long failed attempts are counted and further attempts are refused.
+    pub lockout: Duration,
+}
+
+impl LocalUserStore {
+    /// Returns `None`, if CARL is configured to not use the built-in user store.
+    pub fn load(settings: &Config, resource_manager: ResourceManagerRef, carl_url: &ResourceHomeUrl) -> anyhow::Result<Option<LocalUserStoreRef>> {
+        let enabled = settings.get_bool("network.local.users.enabled")
+            .map_err(|cause| anyhow!("Expected configuration flag 'network.local.users.enabled' to be parseable as boolean! {}", cause))?;
+
+        if !enabled {
+            return Ok(None);
+        }
+        if settings.get_bool("network.oidc.enabled").unwrap_or(false) {
+            bail!("The built-in user store ('network.local.users.enabled') cannot be used together with OIDC ('network.oidc.enabled').");
+        }
+
+        let token_validity = Duration::from_millis(settings.get::<u64>("network.local.users.token.validity.ms")?);
+        let issuer = carl_url.value().join(LOCAL_ISSUER_PATH)?;
+        let login_limits = LoginLimits {
+            concurrency: settings.get::<usize>("network.local.users.login.concurrency")?,
+            max_failures: settings.get::<u32>("network.local.users.login.failures.max")?,
+            lockout: Duration::from_millis(settings.get::<u64>("network.local.users.login.lockout.ms")?),
+        };
+
+        let store = Self::new(resource_manager, issuer, token_validity, login_limits)?;
+        Ok(Some(Arc::new(store)))
+    }
+
+    pub fn new(resource_manager: ResourceManagerRef, issuer: Url, token_validity: Duration, login_limits: LoginLimits) -> anyhow::Result<Self> {
+        let mut signing_key = [0u8; 32];
+        SystemRandom::new().fill(&mut signing_key)
+            .map_err(|_| anyhow!("Failed to generate key for signing tokens of the built-in user store."))?;
+
+        Ok(Self {
+            resource_manager,
+            issuer,
+            token_validity,
+            encoding_key: EncodingKey::from_secret(&signing_key),
+            decoding_key: DecodingKey::from_secret(&signing_key),
+            unknown_user_hash: hash_secret(&generate_secret()?)?,
+            login_throttle: LoginThrottle::new(login_limits),
+        })
+    }
+
+    /// URL of the token endpoint, from which clients fetch tokens for users of the built-in user store.
+    pub fn token_url(&self) -> anyhow::Result<Url> {
+        Ok(self.issuer.join(TOKEN_ENDPOINT_PATH)?)
+    }
+
+    /// Checks the password or API token of a user and issues a token for them.
+    /// The same limits apply to names of users, which do not exist, so that guessing names is as slow as guessing secrets.
+    pub async fn issue_token(&self, name: &str, secret: &str) -> Result<IssuedToken, IssueTokenError> {
+        if self.login_throttle.is_locked_out(name) {
+            return Err(IssueTokenError::TooManyFailedAttempts { user: name.to_owned() });
+        }
+
+        let user = match LocalUserName::try_from(name) {
+            Ok(name) => self.resource_manager.get::<LocalUser>(LocalUserId::of(&name)).await?,
+            Err(_) => None,
+        };
+
+        let secret_hash = match &user {
+            Some(user) => Clone::clone(&user.secret_hash),
+            None => Clone::clone(&self.unknown_user_hash),
+        };
+        let verified = self.login_throttle.verify(secret, secret_hash).await
+            .map_err(|cause| IssueTokenError::Verification { user: name.to_owned(), cause })?;
+
+        let user = match user {
+            Some(user) if verified => user,
+            _ => {
+                self.login_throttle.record_failure(name);
+                return Err(IssueTokenError::InvalidCredentials { user: name.to_owned() });
+            }
+        };
+        self.login_throttle.record_success(name);
+
+        let issued_at = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+        let expires_at = issued_at + self.token_validity;
+        let user_name = user.name.to_string();
+
+        let claims = LocalUserClaims {
+            issuer: self.issuer_claim(),
+            audience: TOKEN_AUDIENCE,
+            subject: user.id.to_string(),
+            issued_at: issued_at.as_secs(),
+            expiration: expires_at.as_secs(),
+            name: &user_name,
+            preferred_username: &user_name,
+            roles: user.roles.iter().map(LocalUserRole::name).collect(),
+            groups: Vec::new(),
+        };
+        let access_token = jsonwebtoken::encode(&Header::new(Algorithm::HS256), &claims, &self.encoding_key)
+            .map_err(|source| IssueTokenError::Encoding { user: user_name.clone(), source })?;
+
+        debug!("Issued token for user '{user_name}' of the built-in user store.");
+        Ok(IssuedToken { access_token, expires_in: self.token_validity })
+    }
+
+    /// Validates a token, which was issued by [`Self::issue_token`].
+    pub fn authenticate(&self, access_token: &str) -> Result<CurrentUser, ValidationError> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_issuer(&[self.issuer_claim()]);
+        validation.set_audience(&[TOKEN_AUDIENCE]);
+
+        let token = jsonwebtoken::decode::<Claims<MyAdditionalClaims>>(access_token, &self.decoding_key, &validation)
+            .map_err(|cause| ValidationError::Failed(format!("failed to decode token: {}", cause)))?;
+
+        let name = token.claims.preferred_username()
+            .ok_or_else(|| ValidationError::Configuration(String::from("Missing preferred username")))?
+            .to_string();
+
+        Ok(CurrentUser {
+            name,
+            claims: token.claims,
+            personal_access_token: None,
+        })
+    }
+
+    fn issuer_claim(&self) -> &str {
+        self.issuer.as_str().trim_end_matches('/')
+    }
+}
+
+/// Runs the verification of secrets on the blocking thread pool, limited to a few at once,
+/// and refuses further attempts for a client ID, after verification failed repeatedly for it.
+struct LoginThrottle {
+    limits: LoginLimits,
+    verifications: Semaphore,
+    failures: Mutex<HashMap<String, FailedLogins>>,
+}
+
+struct FailedLogins {
+    count: u32,
+    since: Instant,
+}
+
+impl LoginThrottle {
+    fn new(limits: LoginLimits) -> Self {
+        Self {
+            limits,
+            verifications: Semaphore::new(limits.concurrency.max(1)),
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn is_locked_out(&self, name: &str) -> bool {
+        let failures = self.failures.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        failures.get(name)
+            .is_some_and(|failed| failed.count >= self.limits.max_failures && failed.since.elapsed() < self.limits.lockout)
+    }
+
+    async fn verify(&self, secret: &str, secret_hash: LocalUserSecretHash) -> Result<bool, String> {
+        let _permit = self.verifications.acquire().await
+            .map_err(|cause| cause.to_string())?;
+
+        let secret = secret.to_owned();
+        tokio::task::spawn_blocking(move || verify_secret(&secret, &secret_hash)).await
+            .map_err(|cause| cause.to_string())
+    }
+
+    fn record_failure(&self, name: &str) {
+        let mut failures = self.failures.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        // The number of tracked client IDs is bounded by how many secrets can be verified during the lockout.
+        failures.retain(|_, failed| failed.since.elapsed() < self.limits.lockout);
+
+        let failed = failures.entry(name.to_owned())
+            .or_insert_with(|| FailedLogins { count: 0, since: Instant::now() });
+        failed.count += 1;
+        if failed.count == self.limits.max_failures {
+            warn!("Verifying the secret of user '{name}' of the built-in user store failed {} times. Refusing further attempts for {} seconds.", failed.count, self.limits.lockout.as_secs());
+        }
+    }
+
+    fn record_success(&self, name: &str) {
+        let mut failures = self.failures.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        failures.remove(name);
+    }
+}
+
+impl fmt::Debug for LocalUserStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalUserStore")
+            .field("issuer", &self.issuer)
+            .field("token_validity", &self.token_validity)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Serialize)]
+struct LocalUserClaims<'a> {
+    #[serde(rename = "iss")]
+    issuer: &'a str,
+    #[serde(rename = "aud")]
+    audience: &'a str,
+    #[serde(rename = "sub")]
+    subject: String,
+    #[serde(rename = "iat")]
+    issued_at: u64,
+    #[serde(rename = "exp")]
+    expiration: u64,
+    name: &'a str,
+    preferred_username: &'a str,
+    roles: Vec<&'static str>,
+    groups: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct IssuedToken {
+    pub access_token: String,
+    pub expires_in: Duration,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum IssueTokenError {
+    #[error("Invalid credentials for user '{user}'.")]
+    InvalidCredentials { user: String },
+    #[error("Too many failed attempts for user '{user}'. Refusing further attempts for now.")]
+    TooManyFailedAttempts { user: String },
+    #[error("Failed to verify the secret of user '{user}':\n  {cause}")]
+    Verification { user: String, cause: String },
+    #[error("Failed to sign token for user '{user}'.")]
+    Encoding {
+        user: String,
+        #[source] source: jsonwebtoken::errors::Error,
+    },
+    #[error("Error when accessing persistence while issuing a token")]
+    Persistence(#[from] PersistenceError),
+}
+
+/// Generates a random API token, which can be used as secret of a user instead of a password.
+pub fn generate_secret() -> anyhow::Result<String> {
+    let mut secret = [0u8; 32];
+    SystemRandom::new().fill(&mut secret)
+        .map_err(|_| anyhow!("Failed to generate random secret."))?;
+    Ok(URL_SAFE_NO_PAD.encode(secret))
+}
+
+pub fn hash_secret(secret: &str) -> anyhow::Result<LocalUserSecretHash> {
+    let mut salt = [0u8; 16];
+    SystemRandom::new().fill(&mut salt)
+        .map_err(|_| anyhow!("Failed to generate salt for hashing secret."))?;
+
+    let mut hash = [0u8; ring::digest::SHA256_OUTPUT_LEN];
+    pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, iterations(SECRET_HASH_ITERATIONS), &salt, secret.as_bytes(), &mut hash);
+
+    Ok(LocalUserSecretHash(format!(
+        "{SECRET_HASH_ALGORITHM}${SECRET_HASH_ITERATIONS}${}${}",
+        STANDARD_NO_PAD.encode(salt),
+        STANDARD_NO_PAD.encode(hash),
+    )))
+}
+
+pub fn verify_secret(secret: &str, secret_hash: &LocalUserSecretHash) -> bool {
+    let parts = secret_hash.0.split('$').collect::<Vec<_>>();
+    let [algorithm, iteration_count, salt, hash] = parts.as_slice() else {
+        return false;
+    };
+    if *algorithm != SECRET_HASH_ALGORITHM {
+        return false;
+    }
+
+    let (Ok(iteration_count), Ok(salt), Ok(hash)) = (iteration_count.parse::<u32>(), STANDARD_NO_PAD.decode(salt), STANDARD_NO_PAD.decode(hash)) else {
+        return false;
+    };
+    pbkdf2::verify(pbkdf2::PBKDF2_HMAC_SHA256, iterations(iteration_count), &salt, secret.as_bytes(), &hash).is_ok()
+}
+
+fn iterations(count: u32) -> NonZeroU32 {
+    NonZeroU32::new(count).unwrap_or(NonZeroU32::MIN)
+}
+
+/// Creates a user of the built-in user store. An existing user with the same name is only replaced, if `replace` is set.
+pub async fn add_user(resource_manager: &ResourceManagerRef, name: LocalUserName, roles: Vec<LocalUserRole>, secret: &str, replace: bool) -> anyhow::Result<()> {
+    let user = LocalUser {
+        id: LocalUserId::of(&name),
+        name,
+        roles,
+        secret_hash: hash_secret(secret)?,
+    };
+    let user_name = Clone::clone(&user.name);
+
+    let added = resource_manager.resources_mut(async |resources| {
+        if !replace && resources.get::<LocalUser>(user.id)?.is_some() {
+            return Ok(false);
+        }
+        resources.insert(user.id, user)?;
+        Ok::<_, PersistenceError>(true)
+    }).await??;
+
+    if !added {
+        bail!("User '{user_name}' already exists. Pass `--force` to replace it.");
+    }
+    Ok(())
+}
+
+/// Returns `false`, if no user with the given name exists.
+pub async fn remove_user(resource_manager: &ResourceManagerRef, name: &LocalUserName) -> anyhow::Result<bool> {
+    let removed = resource_manager.resources_mut(async |resources| {
+        resources.remove::<LocalUser>(LocalUserId::of(name))
+    }).await??;
+    Ok(removed.is_some())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use googletest::prelude::*;
+
+    use crate::auth::authorization::{AuthorizationPolicy, Role};
+    use crate::resource::manager::ResourceManager;
+
+    use super::*;
+
+    pub fn login_limits() -> LoginLimits {
+        LoginLimits { concurrency: 4, max_failures: 5, lockout: Duration::from_secs(300) }
+    }
+
+    #[test]
+    fn should_verify_hashed_secret() -> anyhow::Result<()> {
+        let secret_hash = hash_secret("correct horse battery staple")?;
+
+        assert!(verify_secret("correct horse battery staple", &secret_hash));
+        assert!(!verify_secret("correct horse battery", &secret_hash));
+        assert!(!verify_secret("correct horse battery staple", &LocalUserSecretHash(String::from("plain"))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_issue_token_for_valid_credentials_only() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let store = LocalUserStore::new(Arc::clone(&resource_manager), Url::parse("https://carl/auth/")?, Duration::from_secs(300), login_limits())?;
+
+        let name = LocalUserName::try_from("alice")?;
+        add_user(&resource_manager, Clone::clone(&name), vec![LocalUserRole::Editor], "secret", false).await?;
+
+        let token = store.issue_token("alice", "secret").await?;
+        let user = store.authenticate(&token.access_token)?;
+        assert_that!(user.name, eq("alice"));
+        assert_that!(AuthorizationPolicy::for_local_users().roles_of(&user), unordered_elements_are![eq(&Role::Editor)]);
+
+        assert_that!(store.issue_token("alice", "wrong").await, err(matches_pattern!(IssueTokenError::InvalidCredentials { .. })));
+        assert_that!(store.issue_token("bob", "secret").await, err(matches_pattern!(IssueTokenError::InvalidCredentials { .. })));
+
+        assert!(remove_user(&resource_manager, &name).await?);
+        assert_that!(store.issue_token("alice", "secret").await, err(matches_pattern!(IssueTokenError::InvalidCredentials { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_token_of_another_instance() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let issuer = Url::parse("https://carl/auth/")?;
+        let store = LocalUserStore::new(Arc::clone(&resource_manager), Clone::clone(&issuer), Duration::from_secs(300), login_limits())?;
+        let restarted_store = LocalUserStore::new(Arc::clone(&resource_manager), issuer, Duration::from_secs(300), login_limits())?;
+
+        add_user(&resource_manager, LocalUserName::try_from("alice")?, vec![LocalUserRole::Viewer], "secret", false).await?;
+        let token = store.issue_token("alice", "secret").await?;
+
+        assert!(restarted_store.authenticate(&token.access_token).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refuse_attempts_after_repeated_failures_also_for_unknown_users() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let limits = LoginLimits { concurrency: 2, max_failures: 2, lockout: Duration::from_secs(300) };
+        let store = LocalUserStore::new(Arc::clone(&resource_manager), Url::parse("https://carl/auth/")?, Duration::from_secs(300), limits)?;
+        add_user(&resource_manager, LocalUserName::try_from("alice")?, vec![LocalUserRole::Viewer], "secret", false).await?;
+
+        for name in ["alice", "mallory"] {
+            for _ in 0..2 {
+                assert_that!(store.issue_token(name, "wrong").await, err(matches_pattern!(IssueTokenError::InvalidCredentials { .. })));
+            }
+        }
+
+        assert_that!(store.issue_token("alice", "secret").await, err(matches_pattern!(IssueTokenError::TooManyFailedAttempts { .. })));
+        assert_that!(store.issue_token("mallory", "secret").await, err(matches_pattern!(IssueTokenError::TooManyFailedAttempts { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_only_replace_an_existing_user_when_forced() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let name = LocalUserName::try_from("alice")?;
+        add_user(&resource_manager, Clone::clone(&name), vec![LocalUserRole::Viewer], "secret", false).await?;
+
+        let result = add_user(&resource_manager, Clone::clone(&name), vec![LocalUserRole::Administrator], "other", false).await;
+        assert!(result.is_err());
+        let user = resource_manager.get::<LocalUser>(LocalUserId::of(&name)).await?.unwrap();
+        assert_that!(user.roles, elements_are![eq(&LocalUserRole::Viewer)]);
+
+        add_user(&resource_manager, Clone::clone(&name), vec![LocalUserRole::Administrator], "other", true).await?;
+        let user = resource_manager.get::<LocalUser>(LocalUserId::of(&name)).await?.unwrap();
+        assert_that!(user.roles, elements_are![eq(&LocalUserRole::Administrator)]);
+        Ok(())
+    }
+}
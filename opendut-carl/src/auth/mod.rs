@@ -1,17 +1,37 @@
 mod validation;
+pub(crate) mod authorization;
+pub(crate) mod client_certificate;
 pub(crate) mod json_web_key;
+pub(crate) mod local_users;
+pub(crate) mod ownership;
+pub(crate) mod personal_access_tokens;
+pub(crate) mod projects;
 pub(crate) mod grpc_auth_layer;
 pub mod in_memory_cache;
 
 use openidconnect::core::CoreGenderClaim;
 use openidconnect::IdTokenClaims;
 use opendut_auth::types::MyAdditionalClaims;
+use opendut_types::audit::AuditActor;
+
+use crate::auth::personal_access_tokens::PersonalAccessTokenGrant;
 
 pub type Claims<AC> = IdTokenClaims<AC, CoreGenderClaim>;
 
-#[allow(unused)] //TODO implement authorization
 #[derive(Clone, Debug)]
 pub struct CurrentUser {
     pub name: String,
     pub claims: Claims<MyAdditionalClaims>,
+    /// Set, if the user authenticated with a personal access token, which limits their roles to its scopes.
+    pub personal_access_token: Option<PersonalAccessTokenGrant>,
+}
+
+impl CurrentUser {
+    /// Determines on whose behalf a gRPC request modifies resources, to be recorded in the audit log.
+    pub fn audit_actor_of<T>(request: &tonic::Request<T>) -> AuditActor {
+        match request.extensions().get::<CurrentUser>() {
+            Some(user) => AuditActor::User { name: user.name.clone() },
+            None => AuditActor::Anonymous,
+        }
+    }
 }
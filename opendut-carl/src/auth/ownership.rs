@@ -0,0 +1,77 @@
+use tonic::Status;
+use tracing::error;
+
+use opendut_types::audit::AuditActor;
+
+/// Whose resources a request may modify. Inserted into the request by the authorization middleware.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OwnershipScope {
+    /// Administrators and users of a CARL without authorization may modify all resources.
+    Unrestricted,
+    /// Other users may only modify the resources they created, as recorded when the resources were inserted.
+    CreatedBy { user: String },
+}
+
+impl OwnershipScope {
+    /// Determines the scope of a gRPC request.
+    /// Fails, if the authorization middleware did not annotate the request, rather than leaving it unrestricted.
+    pub fn of<T>(request: &tonic::Request<T>) -> Result<OwnershipScope, Status> {
+        request.extensions().get::<OwnershipScope>()
+            .cloned()
+            .ok_or_else(|| {
+                error!("Request was not annotated with an ownership scope by the authorization middleware.");
+                Status::internal("Internal error when determining the resources a request may modify")
+            })
+    }
+
+    /// Resources without a recorded creator were created before the audit log was introduced and nobody owns them,
+    /// so they remain modifiable by all users permitted to modify resources, as they were before.
+    pub fn check_owner(&self, creator: Option<&AuditActor>) -> Result<(), OwnershipError> {
+        match self {
+            OwnershipScope::Unrestricted => Ok(()),
+            OwnershipScope::CreatedBy { user } => match creator {
+                None => Ok(()),
+                Some(AuditActor::User { name }) if name == user => Ok(()),
+                Some(creator) => Err(OwnershipError::NotTheOwner { user: Clone::clone(user), creator: Some(Clone::clone(creator)) }),
+            }
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum OwnershipError {
+    #[error("User '{user}' did not create the resource.")]
+    NotTheOwner { user: String, creator: Option<AuditActor> },
+}
+
+impl From<OwnershipError> for Status {
+    fn from(cause: OwnershipError) -> Self {
+        Status::permission_denied(format!("CARL says, you are not allowed to do this: {cause} Only its creator or an administrator may modify it."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn users_should_only_modify_resources_they_created() {
+        let alice = AuditActor::User { name: String::from("alice") };
+        let bob = AuditActor::User { name: String::from("bob") };
+        let scope = OwnershipScope::CreatedBy { user: String::from("alice") };
+
+        assert!(scope.check_owner(Some(&alice)).is_ok());
+        assert_that!(
+            scope.check_owner(Some(&bob)),
+            err(eq(&OwnershipError::NotTheOwner { user: String::from("alice"), creator: Some(bob.clone()) }))
+        );
+        assert!(scope.check_owner(Some(&AuditActor::Anonymous)).is_err());
+        assert!(scope.check_owner(Some(&AuditActor::System)).is_err());
+        assert!(scope.check_owner(None).is_ok(), "Resources stored before the audit log was introduced should remain modifiable.");
+
+        assert!(OwnershipScope::Unrestricted.check_owner(Some(&bob)).is_ok());
+        assert!(OwnershipScope::Unrestricted.check_owner(None).is_ok());
+    }
+}
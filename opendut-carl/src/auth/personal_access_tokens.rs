@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::anyhow;
+use base64::engine::general_purpose::{STANDARD_NO_PAD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use ring::digest;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde_json::json;
+use tokio::sync::RwLock;
+use tracing::debug;
+use uuid::Uuid;
+
+use opendut_types::user::token::{PersonalAccessToken, PersonalAccessTokenId, PersonalAccessTokenOwner, PersonalAccessTokenOwnerId, PersonalAccessTokenScope, PersonalAccessTokenSecretHash, PersonalAccessTokenStatus};
+use opendut_types::user::{LocalUser, LocalUserId, LocalUserName, LocalUserRole};
+
+use crate::auth::validation::ValidationError;
+use crate::auth::CurrentUser;
+use crate::resource::manager::ResourceManagerRef;
+use crate::resource::persistence::error::PersistenceError;
+
+/// Prefix of personal access tokens, which tells them apart from JWTs and makes them recognizable for secret scanners.
+pub const TOKEN_PREFIX: &str = "odt_";
+/// Issuer of the claims of users, who authenticated with a personal access token.
+const TOKEN_ISSUER: &str = "urn:opendut:carl:personal-access-token";
+
+// The secret is random, so a fast hash suffices and keeps authenticating each request cheap.
+const SECRET_HASH_ALGORITHM: &str = "sha256";
+
+/// Personal access token, with which a user authenticated, instead of a token of the identity provider.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersonalAccessTokenGrant {
+    pub id: PersonalAccessTokenId,
+    /// Limits the roles of the user for requests authenticated with this token.
+    /// The token never grants roles, which the user does not have (anymore).
+    pub scopes: Vec<PersonalAccessTokenScope>,
+}
+
+/// Where the current roles and groups of the owners of personal access tokens are taken from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenOwners {
+    /// Owners are users of the built-in user store, whose roles are looked up on each request.
+    LocalUsers,
+    /// Owners are users of the identity provider, whose roles and groups are recorded for their tokens,
+    /// whenever they authenticate with a token of the identity provider.
+    Oidc {
+        /// Tokens are rejected, if the roles and groups of their owner were recorded longer ago,
+        /// so that users, who were removed from the identity provider, do not keep their access via tokens.
+        owner_claims_max_age: Duration,
+    },
+}
+
+pub fn is_personal_access_token(token: &str) -> bool {
+    token.starts_with(TOKEN_PREFIX)
+}
+
+/// Generates a token in the format `odt_<id>_<secret>` and the hash of its secret, to be stored instead of the token.
+pub fn generate_token(id: PersonalAccessTokenId) -> anyhow::Result<(String, PersonalAccessTokenSecretHash)> {
+    let mut secret = [0u8; 32];
+    SystemRandom::new().fill(&mut secret)
+        .map_err(|_| anyhow!("Failed to generate random secret for personal access token."))?;
+    let secret = URL_SAFE_NO_PAD.encode(secret);
+
+    let token = format!("{TOKEN_PREFIX}{}_{secret}", id.0.simple());
+    Ok((token, hash_secret(&secret)))
+}
+
+fn parse_token(token: &str) -> Option<(PersonalAccessTokenId, &str)> {
+    let (id, secret) = token.strip_prefix(TOKEN_PREFIX)?.split_once('_')?;
+    let id = Uuid::try_parse(id).ok()?;
+    Some((PersonalAccessTokenId(id), secret))
+}
+
+fn hash_secret(secret: &str) -> PersonalAccessTokenSecretHash {
+    let hash = digest::digest(&digest::SHA256, secret.as_bytes());
+    PersonalAccessTokenSecretHash(format!("{SECRET_HASH_ALGORITHM}${}", STANDARD_NO_PAD.encode(hash)))
+}
+
+/// Authenticates requests, which carry a personal access token instead of a JWT.
+#[derive(Clone)]
+pub struct PersonalAccessTokenAuthenticator {
+    resource_manager: ResourceManagerRef,
+    owners: TokenOwners,
+    /// The roles and groups of each owner, which were last recorded for their tokens, to avoid writing them on each request.
+    recorded_owners: Arc<RwLock<HashMap<String, PersonalAccessTokenOwner>>>,
+}
+
+#[derive(Clone, Debug)]
+struct OwnerClaims {
+    roles: Vec<String>,
+    groups: Vec<String>,
+}
+
+impl PersonalAccessTokenAuthenticator {
+    pub fn new(resource_manager: ResourceManagerRef, owners: TokenOwners) -> Self {
+        Self { resource_manager, owners, recorded_owners: Default::default() }
+    }
+
+    /// Looks up the personal access token and authenticates its owner, restricted to the scopes of the token and the current roles of the owner.
+    pub async fn authenticate(&self, token: &str) -> Result<CurrentUser, ValidationError> {
+        authenticate(&self.resource_manager, self.owners, token).await
+    }
+
+    /// Records the current roles and groups of a user, who authenticated with a token of the identity provider, for their personal access tokens.
+    /// This way, the tokens stop granting roles and projects, which were taken away from the user, and stay valid while the user keeps signing in.
+    /// Unchanged roles and groups are recorded again after half of their maximum age, so that the tokens are not rejected in the meantime.
+    pub async fn record_owner_claims(&self, user: &CurrentUser) -> Result<(), PersistenceError> {
+        let TokenOwners::Oidc { owner_claims_max_age } = self.owners else {
+            return Ok(());
+        };
+        let now = SystemTime::now();
+        let roles = &user.claims.additional_claims().roles;
+        let groups = &user.claims.additional_claims().groups;
+
+        let is_recorded = |owner: &PersonalAccessTokenOwner| {
+            &owner.roles == roles
+                && &owner.groups == groups
+                && now.duration_since(owner.recorded_at).unwrap_or_default() < owner_claims_max_age / 2
+        };
+        if self.recorded_owners.read().await.get(&user.name).is_some_and(is_recorded) {
+            return Ok(());
+        }
+
+        let id = PersonalAccessTokenOwnerId::of(&user.name);
+        let Some(recorded) = self.resource_manager.get::<PersonalAccessTokenOwner>(id).await? else {
+            // The user has no tokens, for which to record anything. Creating a token records the roles and groups.
+            return Ok(());
+        };
+        let owner = if is_recorded(&recorded) {
+            recorded
+        } else {
+            debug!("Recording current roles and groups of user '{}' for their personal access tokens.", user.name);
+            let owner = PersonalAccessTokenOwner {
+                owner: Clone::clone(&user.name),
+                roles: Clone::clone(roles),
+                groups: Clone::clone(groups),
+                recorded_at: now,
+            };
+            self.resource_manager.insert(id, Clone::clone(&owner)).await?;
+            owner
+        };
+        self.recorded_owners.write().await.insert(Clone::clone(&user.name), owner);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for PersonalAccessTokenAuthenticator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersonalAccessTokenAuthenticator").finish_non_exhaustive()
+    }
+}
+
+/// Determines the current roles and groups of the owner of a personal access token.
+async fn owner_claims(resource_manager: &ResourceManagerRef, owners: TokenOwners, token: &PersonalAccessToken) -> Result<OwnerClaims, ValidationError> {
+    let id = token.descriptor.id;
+    match owners {
+        TokenOwners::Oidc { owner_claims_max_age } => {
+            let owner = resource_manager.get::<PersonalAccessTokenOwner>(PersonalAccessTokenOwnerId::of(&token.descriptor.owner)).await
+                .map_err(|cause| ValidationError::Failed(format!("failed to look up roles and groups of owner of personal access token <{id}>: {cause}")))?
+                .ok_or_else(|| ValidationError::Failed(format!("roles and groups of owner of personal access token <{id}> are not recorded")))?;
+
+            let age = SystemTime::now().duration_since(owner.recorded_at).unwrap_or_default();
+            if age > owner_claims_max_age {
+                return Err(ValidationError::OutdatedClaims(format!(
+                    "roles and groups of owner '{}' of personal access token <{id}> were recorded {}s ago, which exceeds the maximum of {}s",
+                    owner.owner, age.as_secs(), owner_claims_max_age.as_secs(),
+                )));
+            }
+
+            Ok(OwnerClaims {
+                roles: owner.roles,
+                groups: owner.groups,
+            })
+        }
+        TokenOwners::LocalUsers => {
+            let owner = LocalUserName::try_from(token.descriptor.owner.as_str())
+                .map_err(|cause| ValidationError::Failed(format!("owner of personal access token <{id}> is not a valid user name: {cause}")))?;
+            let owner = resource_manager.get::<LocalUser>(LocalUserId::of(&owner)).await
+                .map_err(|cause| ValidationError::Failed(format!("failed to look up owner of personal access token <{id}>: {cause}")))?
+                .ok_or_else(|| ValidationError::Failed(format!("owner of personal access token <{id}> does not exist anymore")))?;
+
+            Ok(OwnerClaims {
+                roles: owner.roles.iter().map(LocalUserRole::name).map(String::from).collect(),
+                groups: Vec::new(),
+            })
+        }
+    }
+}
+
+async fn authenticate(resource_manager: &ResourceManagerRef, owners: TokenOwners, token: &str) -> Result<CurrentUser, ValidationError> {
+    let (id, secret) = parse_token(token)
+        .ok_or_else(|| ValidationError::Failed(String::from("malformed personal access token")))?;
+
+    let stored_token = resource_manager.get::<PersonalAccessToken>(id).await
+        .map_err(|cause| ValidationError::Failed(format!("failed to look up personal access token <{id}>: {cause}")))?
+        .ok_or_else(|| ValidationError::Failed(format!("unknown personal access token <{id}>")))?;
+
+    // Both sides are hashes of a random secret, so comparing them does not leak anything usable via timing.
+    if hash_secret(secret) != stored_token.secret_hash {
+        return Err(ValidationError::Failed(format!("invalid secret for personal access token <{id}>")));
+    }
+
+    let status = stored_token.descriptor.status(SystemTime::now());
+    if status != PersonalAccessTokenStatus::Active {
+        return Err(ValidationError::Failed(format!("personal access token <{id}> is {status}")));
+    }
+
+    let owner_claims = owner_claims(resource_manager, owners, &stored_token).await?;
+    let descriptor = stored_token.descriptor;
+
+    let claims = serde_json::from_value(json!({
+        "iss": TOKEN_ISSUER,
+        "aud": [TOKEN_ISSUER],
+        "sub": descriptor.owner,
+        "iat": unix_seconds(descriptor.created_at),
+        "exp": unix_seconds(descriptor.expires_at),
+        "preferred_username": descriptor.owner,
+        "roles": owner_claims.roles,
+        "groups": owner_claims.groups,
+    })).map_err(|cause| ValidationError::Failed(format!("failed to create claims for personal access token <{id}>: {cause}")))?;
+
+    debug!("Authenticated user '{}' with personal access token '{}' <{id}>.", descriptor.owner, descriptor.name);
+    Ok(CurrentUser {
+        name: descriptor.owner,
+        claims,
+        personal_access_token: Some(PersonalAccessTokenGrant {
+            id,
+            scopes: descriptor.scopes,
+        }),
+    })
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_types::user::token::{PersonalAccessTokenDescriptor, PersonalAccessTokenName};
+
+    use crate::auth::local_users;
+    use crate::resource::manager::ResourceManager;
+
+    use super::*;
+
+    const OIDC_OWNERS: TokenOwners = TokenOwners::Oidc { owner_claims_max_age: Duration::from_secs(3600) };
+
+    async fn store_token(resource_manager: &ResourceManagerRef, expires_at: SystemTime) -> anyhow::Result<String> {
+        let id = PersonalAccessTokenId::random();
+        let (token, secret_hash) = generate_token(id)?;
+
+        let descriptor = PersonalAccessTokenDescriptor {
+            id,
+            name: PersonalAccessTokenName::try_from("nightly-build")?,
+            owner: String::from("alice"),
+            scopes: vec![PersonalAccessTokenScope::Viewer],
+            created_at: SystemTime::now(),
+            expires_at,
+            revoked_at: None,
+        };
+        resource_manager.insert(id, PersonalAccessToken { descriptor, secret_hash }).await?;
+        resource_manager.insert(PersonalAccessTokenOwnerId::of("alice"), PersonalAccessTokenOwner {
+            owner: String::from("alice"),
+            roles: vec![String::from("testrole")],
+            groups: vec![String::from("/opendut-project-chassis")],
+            recorded_at: SystemTime::now(),
+        }).await?;
+        Ok(token)
+    }
+
+    fn current_user(name: &str, groups: &[&str]) -> anyhow::Result<CurrentUser> {
+        let claims = serde_json::from_value(json!({
+            "iss": TOKEN_ISSUER,
+            "aud": [TOKEN_ISSUER],
+            "sub": name,
+            "iat": 0,
+            "exp": unix_seconds(SystemTime::now() + Duration::from_secs(60)),
+            "preferred_username": name,
+            "roles": [],
+            "groups": groups,
+        }))?;
+        Ok(CurrentUser { name: String::from(name), claims, personal_access_token: None })
+    }
+
+    #[tokio::test]
+    async fn should_authenticate_owner_of_valid_token() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let token = store_token(&resource_manager, SystemTime::now() + Duration::from_secs(60)).await?;
+
+        assert!(is_personal_access_token(&token));
+        let user = authenticate(&resource_manager, OIDC_OWNERS, &token).await?;
+
+        assert_that!(user.name, eq("alice"));
+        assert_that!(user.personal_access_token, some(field!(PersonalAccessTokenGrant.scopes, elements_are![eq(&PersonalAccessTokenScope::Viewer)])));
+        assert_that!(user.claims.additional_claims().roles, elements_are![eq("testrole")]);
+        assert!(user.claims.additional_claims().has_group("opendut-project-chassis"), "Token should carry the groups of its owner, which determine their projects.");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_carry_current_roles_of_local_owner() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let token = store_token(&resource_manager, SystemTime::now() + Duration::from_secs(60)).await?;
+        let owner = LocalUserName::try_from("alice")?;
+
+        local_users::add_user(&resource_manager, Clone::clone(&owner), vec![LocalUserRole::Viewer, LocalUserRole::Editor], "secret", false).await?;
+        let user = authenticate(&resource_manager, TokenOwners::LocalUsers, &token).await?;
+        assert_that!(user.claims.additional_claims().roles, unordered_elements_are![eq("viewer"), eq("editor")]);
+
+        local_users::add_user(&resource_manager, Clone::clone(&owner), vec![LocalUserRole::Editor], "secret", true).await?;
+        let user = authenticate(&resource_manager, TokenOwners::LocalUsers, &token).await?;
+        assert_that!(user.claims.additional_claims().roles, elements_are![eq("editor")]);
+
+        local_users::remove_user(&resource_manager, &owner).await?;
+        assert!(authenticate(&resource_manager, TokenOwners::LocalUsers, &token).await.is_err(), "Tokens of removed users should not authenticate anymore.");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_record_changed_roles_and_groups_of_owner() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let token = store_token(&resource_manager, SystemTime::now() + Duration::from_secs(60)).await?;
+        let authenticator = PersonalAccessTokenAuthenticator::new(Arc::clone(&resource_manager), OIDC_OWNERS);
+
+        authenticator.record_owner_claims(&current_user("alice", &["/opendut-project-doors"])?).await?;
+
+        let user = authenticator.authenticate(&token).await?;
+        assert_that!(user.claims.additional_claims().roles, empty());
+        assert!(user.claims.additional_claims().has_group("opendut-project-doors"));
+        assert!(!user.claims.additional_claims().has_group("opendut-project-chassis"), "Token should not grant projects, which were taken away from its owner.");
+
+        authenticator.record_owner_claims(&current_user("bob", &[])?).await?;
+        assert!(resource_manager.get::<PersonalAccessTokenOwner>(PersonalAccessTokenOwnerId::of("bob")).await?.is_none(), "Nothing should be recorded for users without tokens.");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_tokens_until_the_outdated_roles_and_groups_of_their_owner_are_recorded_again() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let token = store_token(&resource_manager, SystemTime::now() + Duration::from_secs(7200)).await?;
+        resource_manager.insert(PersonalAccessTokenOwnerId::of("alice"), PersonalAccessTokenOwner {
+            owner: String::from("alice"),
+            roles: vec![],
+            groups: vec![String::from("/opendut-project-chassis")],
+            recorded_at: SystemTime::now() - Duration::from_secs(3601),
+        }).await?;
+        let authenticator = PersonalAccessTokenAuthenticator::new(Arc::clone(&resource_manager), OIDC_OWNERS);
+
+        let result = authenticator.authenticate(&token).await;
+        assert_that!(result, err(matches_pattern!(ValidationError::OutdatedClaims(anything()))));
+
+        authenticator.record_owner_claims(&current_user("alice", &["/opendut-project-chassis"])?).await?;
+        let user = authenticator.authenticate(&token).await?;
+        assert!(user.claims.additional_claims().has_group("opendut-project-chassis"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_or_expired_tokens() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let token = store_token(&resource_manager, SystemTime::now() + Duration::from_secs(60)).await?;
+        let expired_token = store_token(&resource_manager, SystemTime::now() - Duration::from_secs(1)).await?;
+
+        let (prefix, _) = token.rsplit_once('_').unwrap();
+        assert!(authenticate(&resource_manager, OIDC_OWNERS, &format!("{prefix}_guessed")).await.is_err());
+        assert!(authenticate(&resource_manager, OIDC_OWNERS, "odt_malformed").await.is_err());
+        assert!(authenticate(&resource_manager, OIDC_OWNERS, &expired_token).await.is_err());
+
+        let (unknown_token, _) = generate_token(PersonalAccessTokenId::random())?;
+        assert!(authenticate(&resource_manager, OIDC_OWNERS, &unknown_token).await.is_err());
+        Ok(())
+    }
+}
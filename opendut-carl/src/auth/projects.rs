@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+
+use tonic::Status;
+use tracing::error;
+
+use opendut_auth::types::MyAdditionalClaims;
+use opendut_types::project::{ProjectAssignment, ProjectId};
+use opendut_util::settings::LoadError;
+
+use crate::auth::authorization::{AuthorizationPolicy, Role};
+use crate::auth::CurrentUser;
+
+/// Derives the projects a user is a member of from their OIDC groups, e.g. the group `opendut-project-chassis` grants membership in the project `chassis`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectPolicy {
+    pub group_prefix: String,
+}
+
+const PROJECTS_CONFIG_PREFIX: &str = "projects";
+
+impl ProjectPolicy {
+    pub fn load(config: &config::Config) -> Result<Self, LoadError> {
+        let group_prefix = config.get_string(&format!("{PROJECTS_CONFIG_PREFIX}.groups.prefix"))?;
+        Ok(Self { group_prefix })
+    }
+
+    /// Projects of the user, whose OIDC groups start with the configured prefix. Groups with an invalid project ID are ignored.
+    pub fn projects_of(&self, claims: &MyAdditionalClaims) -> HashSet<ProjectId> {
+        if self.group_prefix.is_empty() {
+            return HashSet::new();
+        }
+
+        claims.groups.iter()
+            .map(|group| group.trim_start_matches('/'))
+            .filter_map(|group| group.strip_prefix(&self.group_prefix))
+            .filter_map(|project| ProjectId::try_from(project).ok())
+            .collect()
+    }
+
+    /// Administrators and users of a CARL without authorization may access all projects.
+    pub fn scope_of(&self, authorization: &AuthorizationPolicy, user: &CurrentUser, roles: &HashSet<Role>) -> ProjectScope {
+        if matches!(authorization, AuthorizationPolicy::Disabled) || roles.contains(&Role::Administrator) {
+            ProjectScope::Unrestricted
+        } else {
+            let mut projects = self.projects_of(user.claims.additional_claims());
+            projects.insert(ProjectId::default());
+            ProjectScope::Members { projects }
+        }
+    }
+}
+
+/// Projects whose resources a request may access. Inserted into the request by the authorization middleware.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectScope {
+    Unrestricted,
+    /// Every user is a member of the default project.
+    Members { projects: HashSet<ProjectId> },
+}
+
+impl ProjectScope {
+    /// Determines the scope of a gRPC request.
+    /// Fails, if the authorization middleware did not annotate the request, rather than leaving it unrestricted.
+    pub fn of<T>(request: &tonic::Request<T>) -> Result<ProjectScope, Status> {
+        request.extensions().get::<ProjectScope>()
+            .cloned()
+            .ok_or_else(|| {
+                error!("Request was not annotated with a project scope by the authorization middleware.");
+                Status::internal("Internal error when determining the projects of a request")
+            })
+    }
+
+    pub fn is_member(&self, project: &ProjectId) -> bool {
+        match self {
+            ProjectScope::Unrestricted => true,
+            ProjectScope::Members { projects } => projects.contains(project),
+        }
+    }
+
+    /// Whether a resource belongs to or is shared with one of the projects.
+    pub fn can_read(&self, assignment: &ProjectAssignment) -> bool {
+        assignment.projects().any(|project| self.is_member(project))
+    }
+
+    /// Resources may only be modified by members of the project they belong to. Shared resources are read-only.
+    pub fn check_write(&self, project: &ProjectId) -> Result<(), ProjectAccessError> {
+        if self.is_member(project) {
+            Ok(())
+        } else {
+            Err(ProjectAccessError::NotAMember { project: Clone::clone(project) })
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum ProjectAccessError {
+    #[error("You are not a member of project '{project}'.")]
+    NotAMember { project: ProjectId },
+}
+
+impl From<ProjectAccessError> for Status {
+    fn from(cause: ProjectAccessError) -> Self {
+        Status::permission_denied(format!("CARL says, you are not allowed to do this: {cause}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn claims_with_groups(groups: &[&str]) -> MyAdditionalClaims {
+        serde_json::from_value(serde_json::json!({
+            "roles": [],
+            "groups": groups,
+        })).unwrap()
+    }
+
+    #[test]
+    fn should_derive_projects_from_groups() -> anyhow::Result<()> {
+        let policy = ProjectPolicy { group_prefix: String::from("opendut-project-") };
+        let claims = claims_with_groups(&["/opendut-project-chassis", "opendut-project-powertrain", "/opendut-project-Invalid", "/testgroup"]);
+
+        assert_that!(
+            policy.projects_of(&claims),
+            unordered_elements_are![eq(&ProjectId::try_from("chassis")?), eq(&ProjectId::try_from("powertrain")?)]
+        );
+
+        let disabled = ProjectPolicy { group_prefix: String::new() };
+        assert_that!(disabled.projects_of(&claims), empty());
+        Ok(())
+    }
+
+    #[test]
+    fn members_should_only_modify_their_own_projects() -> anyhow::Result<()> {
+        let chassis = ProjectId::try_from("chassis")?;
+        let powertrain = ProjectId::try_from("powertrain")?;
+        let scope = ProjectScope::Members { projects: HashSet::from([ProjectId::default(), chassis.clone()]) };
+
+        assert!(scope.check_write(&chassis).is_ok());
+        assert_that!(scope.check_write(&powertrain), err(eq(&ProjectAccessError::NotAMember { project: powertrain.clone() })));
+
+        let mut shared_peer = ProjectAssignment::owned_by(powertrain.clone());
+        assert!(!scope.can_read(&shared_peer));
+        shared_peer.shared_with.insert(chassis);
+        assert!(scope.can_read(&shared_peer));
+        assert!(scope.check_write(&shared_peer.owner).is_err(), "Shared peers should be read-only.");
+
+        assert!(ProjectScope::Unrestricted.check_write(&powertrain).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn requests_without_scope_should_be_rejected() {
+        let request = tonic::Request::new(());
+        assert_that!(ProjectScope::of(&request).map_err(|status| status.code()), err(eq(tonic::Code::Internal)));
+
+        let mut request = tonic::Request::new(());
+        request.extensions_mut().insert(ProjectScope::Unrestricted);
+        assert_that!(ProjectScope::of(&request), ok(eq(&ProjectScope::Unrestricted)));
+    }
+}
@@ -11,7 +11,7 @@ pub async fn download_edgar(
     State(carl_install_directory): State<CarlInstallDirectory>,
 ) -> impl IntoResponse {
 
-    let file_name = format!("{}-{}.tar.gz", &architecture.distribution_name(), crate::app_info::PKG_VERSION);
+    let file_name = distribution_file_name(&architecture);
     let edgar_path = carl_install_directory.path.join(EDGAR_IDENTIFIER).join(&file_name);
 
     let mut response = ServeFile::new_with_mime(edgar_path, &mime::APPLICATION_OCTET_STREAM)
@@ -27,6 +27,43 @@ pub async fn download_edgar(
     response
 }
 
+/// Serves the manifest of the EDGAR distribution, which states its version, target and SHA-256 digest.
+/// EDGAR only installs the distribution, if the manifest matches it and the version advertised by CARL.
+pub async fn download_edgar_manifest(
+    Path(architecture): Path<EdgarArch>,
+    State(carl_install_directory): State<CarlInstallDirectory>,
+) -> impl IntoResponse {
+
+    let manifest_path = carl_install_directory.path.join(EDGAR_IDENTIFIER).join(manifest_file_name(&architecture));
+
+    ServeFile::new_with_mime(manifest_path, &mime::APPLICATION_JSON)
+        .try_call(Request::new(Body::empty())).await
+        .unwrap()
+}
+
+/// Serves the Ed25519 signature of the manifest of the EDGAR distribution, which EDGAR verifies before updating itself.
+/// The signature is created when packaging the distribution, as CARL does not hold the signing key.
+pub async fn download_edgar_signature(
+    Path(architecture): Path<EdgarArch>,
+    State(carl_install_directory): State<CarlInstallDirectory>,
+) -> impl IntoResponse {
+
+    let file_name = format!("{}.sig", manifest_file_name(&architecture));
+    let signature_path = carl_install_directory.path.join(EDGAR_IDENTIFIER).join(file_name);
+
+    ServeFile::new_with_mime(signature_path, &mime::APPLICATION_OCTET_STREAM)
+        .try_call(Request::new(Body::empty())).await
+        .unwrap()
+}
+
+fn distribution_file_name(architecture: &EdgarArch) -> String {
+    format!("{}-{}.tar.gz", architecture.distribution_name(), crate::app_info::PKG_VERSION)
+}
+
+fn manifest_file_name(architecture: &EdgarArch) -> String {
+    format!("{}-{}.manifest.json", architecture.distribution_name(), crate::app_info::PKG_VERSION)
+}
+
 #[cfg(test)]
 mod test {
     use std::fs;
@@ -39,7 +76,7 @@ mod test {
     use googletest::matchers::eq;
     use http::header;
     use crate::CarlInstallDirectory;
-    use crate::http::router::edgar::download_edgar;
+    use crate::http::router::edgar::{download_edgar, download_edgar_manifest, download_edgar_signature};
 
     use crate::http::router::arch::{EDGAR_IDENTIFIER, EdgarArch};
 
@@ -69,4 +106,46 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test()]
+    async fn download_edgar_signature_succeeds_only_if_signature_exists() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+
+        let dir = temp.child(EDGAR_IDENTIFIER);
+        fs::create_dir_all(&dir).expect("Unable to create dir.");
+
+        let file_name = format!("{}-{}.manifest.json.sig", &EdgarArch::X86_64.distribution_name(), crate::app_info::PKG_VERSION);
+        fs::write(dir.child(&file_name), [0u8; 64])?;
+
+        let state = State::<CarlInstallDirectory>(CarlInstallDirectory { path: temp.to_path_buf() });
+        let result = download_edgar_signature(Path(EdgarArch::X86_64), state).await.into_response();
+        assert_that!(result.status(), eq(http::StatusCode::OK));
+
+        let state = State::<CarlInstallDirectory>(CarlInstallDirectory { path: temp.to_path_buf() });
+        let result = download_edgar_signature(Path(EdgarArch::Arm64), state).await.into_response();
+        assert_that!(result.status(), eq(http::StatusCode::NOT_FOUND));
+
+        Ok(())
+    }
+
+    #[tokio::test()]
+    async fn download_edgar_manifest_succeeds_only_if_manifest_exists() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+
+        let dir = temp.child(EDGAR_IDENTIFIER);
+        fs::create_dir_all(&dir).expect("Unable to create dir.");
+
+        let file_name = format!("{}-{}.manifest.json", &EdgarArch::X86_64.distribution_name(), crate::app_info::PKG_VERSION);
+        fs::write(dir.child(&file_name), "{}")?;
+
+        let state = State::<CarlInstallDirectory>(CarlInstallDirectory { path: temp.to_path_buf() });
+        let result = download_edgar_manifest(Path(EdgarArch::X86_64), state).await.into_response();
+        assert_that!(result.status(), eq(http::StatusCode::OK));
+
+        let state = State::<CarlInstallDirectory>(CarlInstallDirectory { path: temp.to_path_buf() });
+        let result = download_edgar_manifest(Path(EdgarArch::Arm64), state).await.into_response();
+        assert_that!(result.status(), eq(http::StatusCode::NOT_FOUND));
+
+        Ok(())
+    }
 }
@@ -5,6 +5,7 @@ use opendut_types::lea::LeaConfig;
 pub mod cleo;
 pub mod edgar;
 pub mod arch;
+pub mod token;
 
 pub async fn lea_config(State(config): State<LeaConfig>) -> Json<LeaConfig> {
     Json(Clone::clone(&config))
@@ -0,0 +1,175 @@
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+use axum::{Form, Json};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use http::{header, HeaderMap, StatusCode};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error};
+use url::form_urlencoded;
+
+use crate::auth::local_users::{IssueTokenError, LocalUserStoreRef};
+
+/// Form parameters of an OAuth 2.0 token request.
+#[derive(Debug, Default, Deserialize)]
+pub struct TokenRequest {
+    grant_type: String,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    token_type: String,
+    expires_in: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct TokenErrorResponse {
+    error: &'static str,
+    error_description: String,
+}
+
+/// Token endpoint of the built-in user store, which issues tokens like the token endpoint of an OIDC provider.
+/// Supports the client credentials grant, with which CLEO fetches tokens, using the name of the user as client ID and their password or API token as client secret,
+/// as well as the resource owner password grant.
+pub async fn issue_token(
+    State(local_user_store): State<Option<LocalUserStoreRef>>,
+    headers: HeaderMap,
+    Form(request): Form<TokenRequest>,
+) -> Response {
+    let Some(local_user_store) = local_user_store else {
+        return token_error(StatusCode::NOT_FOUND, "invalid_request", "The built-in user store of CARL is disabled.");
+    };
+
+    let (name, secret, invalid_credentials_status, invalid_credentials_error) = match request.grant_type.as_str() {
+        "client_credentials" => {
+            let credentials = basic_auth_credentials(&headers)
+                .or_else(|| request.client_id.zip(request.client_secret));
+            let Some((client_id, client_secret)) = credentials else {
+                return token_error(StatusCode::UNAUTHORIZED, "invalid_client", "Client ID and client secret are required.");
+            };
+            (client_id, client_secret, StatusCode::UNAUTHORIZED, "invalid_client")
+        }
+        "password" => {
+            let Some((username, password)) = request.username.zip(request.password) else {
+                return token_error(StatusCode::BAD_REQUEST, "invalid_request", "Username and password are required.");
+            };
+            (username, password, StatusCode::BAD_REQUEST, "invalid_grant")
+        }
+        other => {
+            return token_error(StatusCode::BAD_REQUEST, "unsupported_grant_type", &format!("Grant type '{other}' is not supported."));
+        }
+    };
+
+    match local_user_store.issue_token(&name, &secret).await {
+        Ok(token) => {
+            Json(TokenResponse {
+                access_token: token.access_token,
+                token_type: String::from("Bearer"),
+                expires_in: token.expires_in.as_secs(),
+            }).into_response()
+        }
+        Err(cause @ IssueTokenError::InvalidCredentials { .. }) => {
+            debug!("Refusing to issue token: {cause}");
+            token_error(invalid_credentials_status, invalid_credentials_error, "Invalid user name or secret.")
+        }
+        Err(cause @ IssueTokenError::TooManyFailedAttempts { .. }) => {
+            debug!("Refusing to issue token: {cause}");
+            token_error(StatusCode::TOO_MANY_REQUESTS, "temporarily_unavailable", "Too many failed attempts. Try again later.")
+        }
+        Err(cause) => {
+            error!("Failed to issue token:\n  {cause}");
+            token_error(StatusCode::INTERNAL_SERVER_ERROR, "server_error", "Failed to issue token.")
+        }
+    }
+}
+
+/// Client ID and secret are form-encoded before being joined and Base64-encoded into the header, as required by RFC 6749, section 2.3.1.
+fn basic_auth_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let encoded = value.strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(STANDARD.decode(encoded.trim()).ok()?).ok()?;
+    let (client_id, client_secret) = decoded.split_once(':')?;
+
+    let decode = |value: &str| {
+        form_urlencoded::parse(format!("value={value}").as_bytes())
+            .next()
+            .map(|(_, value)| value.into_owned())
+    };
+    Some((decode(client_id)?, decode(client_secret)?))
+}
+
+fn token_error(status: StatusCode, error: &'static str, description: &str) -> Response {
+    (status, Json(TokenErrorResponse { error, error_description: description.to_owned() })).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+    use http::HeaderValue;
+    use opendut_types::user::{LocalUserName, LocalUserRole};
+    use url::Url;
+
+    use crate::auth::local_users;
+    use crate::auth::local_users::LocalUserStore;
+    use crate::resource::manager::ResourceManager;
+
+    use super::*;
+
+    async fn local_user_store() -> anyhow::Result<LocalUserStoreRef> {
+        let resource_manager = ResourceManager::new_in_memory();
+        local_users::add_user(&resource_manager, LocalUserName::try_from("ci-pipeline")?, vec![LocalUserRole::Viewer], "s3cr3t&key", false).await?;
+
+        let store = LocalUserStore::new(resource_manager, Url::parse("https://carl/auth/")?, Duration::from_secs(300), local_users::tests::login_limits())?;
+        Ok(Arc::new(store))
+    }
+
+    fn basic_auth(client_id: &str, client_secret: &str) -> HeaderMap {
+        let encode = |value: &str| form_urlencoded::byte_serialize(value.as_bytes()).collect::<String>();
+        let credentials = STANDARD.encode(format!("{}:{}", encode(client_id), encode(client_secret)));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_str(&format!("Basic {credentials}")).unwrap());
+        headers
+    }
+
+    fn client_credentials_grant() -> TokenRequest {
+        TokenRequest { grant_type: String::from("client_credentials"), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn should_issue_token_for_client_credentials_grant() -> anyhow::Result<()> {
+        let store = local_user_store().await?;
+
+        let response = issue_token(State(Some(Arc::clone(&store))), basic_auth("ci-pipeline", "s3cr3t&key"), Form(client_credentials_grant())).await;
+        assert_that!(response.status(), eq(StatusCode::OK));
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let token = serde_json::from_slice::<TokenResponse>(&body)?;
+        assert_that!(token.expires_in, eq(300));
+        assert_that!(store.authenticate(&token.access_token)?.name, eq("ci-pipeline"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refuse_invalid_credentials_or_disabled_store() -> anyhow::Result<()> {
+        let store = local_user_store().await?;
+
+        let response = issue_token(State(Some(Arc::clone(&store))), basic_auth("ci-pipeline", "wrong"), Form(client_credentials_grant())).await;
+        assert_that!(response.status(), eq(StatusCode::UNAUTHORIZED));
+
+        let response = issue_token(State(Some(store)), HeaderMap::new(), Form(TokenRequest { grant_type: String::from("authorization_code"), ..Default::default() })).await;
+        assert_that!(response.status(), eq(StatusCode::BAD_REQUEST));
+
+        let response = issue_token(State(None), basic_auth("ci-pipeline", "s3cr3t&key"), Form(client_credentials_grant())).await;
+        assert_that!(response.status(), eq(StatusCode::NOT_FOUND));
+        Ok(())
+    }
+}
@@ -8,11 +8,13 @@ use shadow_rs::formatcp;
 use url::Url;
 use opendut_auth::confidential::config::ConfidentialClientConfigData;
 use opendut_types::lea::{LeaConfig, LeaIdentityProviderConfig};
+use crate::auth::local_users::LocalUserStoreRef;
 
 #[derive(Clone)]
 pub struct HttpState {
     pub lea_config: LeaConfig,
     pub carl_installation_directory: CarlInstallDirectory,
+    pub local_user_store: Option<LocalUserStoreRef>,
 }
 
 pub struct LoadableLeaIdentityProviderConfig(pub(crate) LeaIdentityProviderConfig);
@@ -76,3 +78,9 @@ impl FromRef<HttpState> for CarlInstallDirectory {
         Clone::clone(&app_state.carl_installation_directory)
     }
 }
+
+impl FromRef<HttpState> for Option<LocalUserStoreRef> {
+    fn from_ref(app_state: &HttpState) -> Self {
+        Clone::clone(&app_state.local_user_store)
+    }
+}
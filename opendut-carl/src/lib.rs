@@ -1,7 +1,12 @@
 use pem::Pem;
+use std::fs::File;
+use std::io::BufReader;
 use std::net::SocketAddr;
+use std::path::Path;
 use std::str::FromStr;
-use anyhow::Context;
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::{bail, Context};
 use tonic::service::Routes;
 use tonic_async_interceptor::async_interceptor;
 use tower::make::Shared;
@@ -12,6 +17,7 @@ use uuid::Uuid;
 use opendut_auth::confidential::pem::PemFromConfig;
 use opendut_auth::registration::client::RegistrationClient;
 use opendut_auth::registration::resources::ResourceHomeUrl;
+use opendut_types::user::{LocalUserName, LocalUserRole};
 use opendut_util::settings::LoadedConfig;
 use opendut_util::telemetry::logging::LoggingConfig;
 use opendut_util::telemetry::opentelemetry_types;
@@ -19,12 +25,20 @@ use opendut_util::telemetry::opentelemetry_types::Opentelemetry;
 use opendut_util::{project, telemetry};
 use auth::in_memory_cache::CustomInMemoryCache;
 
+use crate::auth::authorization::{authorization_middleware, AuthorizationPolicy, GrpcAuthorizationLayer};
+use crate::auth::client_certificate::ClientCertificateAuthority;
 use crate::auth::grpc_auth_layer::GrpcAuthenticationLayer;
 use crate::auth::json_web_key::JwkCacheValue;
+use crate::auth::local_users::LocalUserStore;
+use crate::auth::personal_access_tokens::{PersonalAccessTokenAuthenticator, TokenOwners};
+use crate::auth::projects::ProjectPolicy;
 use crate::http::state::CarlInstallDirectory;
-use crate::resource::manager::ResourceManager;
+use crate::manager::snapshot;
+use crate::manager::vpn_reconciler::{VpnReconciler, VpnReconcilerOptions};
+use crate::resource::manager::{ResourceManager, ResourceManagerRef};
 use crate::resource::storage::PersistenceOptions;
-use crate::startup::tls::TlsConfig;
+use crate::settings::vpn::Vpn;
+use crate::startup::tls::{ClientCertificateAcceptor, TlsConfig};
 
 shadow_rs::shadow!(app_info);
 
@@ -57,6 +71,80 @@ pub async fn create_with_telemetry(settings_override: config::Config) -> anyhow:
     Ok(())
 }
 
+/// Writes the persistent resources into a snapshot archive. See [`snapshot::Snapshot`] for what is contained.
+/// Requires exclusive access to the database, i.e. CARL must not be running. For a running CARL, use the Administration API instead.
+pub async fn export_snapshot(settings_override: config::Config, file: &Path) -> anyhow::Result<()> {
+    let resource_manager = open_persistent_resources(settings_override).await?;
+
+    let archive = snapshot::export_archive(&resource_manager).await?;
+
+    std::fs::write(file, archive)
+        .context(format!("Could not write snapshot archive to '{}'.", file.display()))?;
+    Ok(())
+}
+
+/// Restores the resources of a snapshot archive in one transaction. Nothing is imported, if any resource already exists.
+/// Afterwards, the imported peers and deployed clusters are created in the VPN by reconciling it.
+/// Requires exclusive access to the database, i.e. CARL must not be running.
+pub async fn import_snapshot(settings_override: config::Config, file: &Path) -> anyhow::Result<()> {
+    let archive = File::open(file)
+        .context(format!("Could not open snapshot archive '{}'.", file.display()))?;
+    let snapshot = snapshot::archive::read_archive(BufReader::new(archive))
+        .context(format!("Could not read snapshot archive '{}'.", file.display()))?;
+
+    let settings = settings::load_with_overrides(settings_override)?;
+    let resource_manager = open_persistent_resources_with(&settings.config).await?;
+
+    resource_manager.resources_mut(async |resources| {
+        resources.import_snapshot(snapshot)
+    }).await??;
+
+    let vpn = settings::vpn::create(&settings.config).await
+        .context("Error while parsing VPN configuration.")?;
+    if let Vpn::Enabled { .. } = vpn {
+        let reconciler = VpnReconciler::create(resource_manager, None, vpn, VpnReconcilerOptions { interval: None });
+        let report = reconciler.reconcile().await
+            .context("Imported the snapshot, but could not create its peers and deployed clusters in the VPN. Reconcile the VPN once CARL is running, via `opendut-cleo reconcile-vpn`.")?;
+        info!("Reconciled the VPN with the imported resources. Found {} mismatches.", report.findings.len());
+    }
+    Ok(())
+}
+
+/// Creates a user of the built-in user store, replacing an existing one only if `replace` is set.
+/// Requires exclusive access to the database, i.e. CARL must not be running.
+pub async fn add_local_user(settings_override: config::Config, name: LocalUserName, roles: Vec<LocalUserRole>, secret: &str, replace: bool) -> anyhow::Result<()> {
+    let resource_manager = open_persistent_resources(settings_override).await?;
+    auth::local_users::add_user(&resource_manager, name, roles, secret, replace).await
+}
+
+/// Removes a user of the built-in user store and returns `false`, if the user did not exist.
+/// Requires exclusive access to the database, i.e. CARL must not be running.
+pub async fn remove_local_user(settings_override: config::Config, name: &LocalUserName) -> anyhow::Result<bool> {
+    let resource_manager = open_persistent_resources(settings_override).await?;
+    auth::local_users::remove_user(&resource_manager, name).await
+}
+
+/// Generates a random API token, which can be used as secret of a user of the built-in user store.
+pub fn generate_local_user_secret() -> anyhow::Result<String> {
+    auth::local_users::generate_secret()
+}
+
+async fn open_persistent_resources(settings_override: config::Config) -> anyhow::Result<ResourceManagerRef> {
+    let settings = settings::load_with_overrides(settings_override)?;
+    open_persistent_resources_with(&settings.config).await
+}
+
+async fn open_persistent_resources_with(config: &config::Config) -> anyhow::Result<ResourceManagerRef> {
+    let persistence_options = PersistenceOptions::load(config)?;
+
+    if let PersistenceOptions::Disabled = persistence_options {
+        bail!("Persistence is disabled in the configuration. There are no persistent resources to access.");
+    }
+
+    ResourceManager::create(&persistence_options).await
+        .context("Creating ResourceManager failed")
+}
+
 pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
     opendut_util::crypto::install_default_provider();
 
@@ -81,6 +169,10 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
             std::process::exit(0);
         }
 
+        resource_manager.resources_mut(async |resources| resources.restore_creators()).await
+            .and_then(|result| result)
+            .context("Error while restoring the creators of resources from the audit log.")?;
+
         resource_manager
     };
 
@@ -91,11 +183,34 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
     let oidc_registration_client = RegistrationClient::from_settings(&settings).await
         .expect("Failed to load oidc registration client!");
 
+    let client_certificate_authority = ClientCertificateAuthority::load(&settings)
+        .context("Error while loading CA for issuing client certificates.")?;
+
+    let local_user_store = LocalUserStore::load(&settings, Arc::clone(&resource_manager), &carl_url)
+        .context("Error while loading built-in user store.")?;
+
+    let authorization_policy = match &local_user_store {
+        Some(_) => AuthorizationPolicy::for_local_users(),
+        None => AuthorizationPolicy::load(&settings)?,
+    };
+
+    let project_policy = ProjectPolicy::load(&settings)?;
+
+    let token_owners = match (&oidc_registration_client, &local_user_store) {
+        (None, Some(_)) => TokenOwners::LocalUsers,
+        _ => TokenOwners::Oidc {
+            owner_claims_max_age: Duration::from_millis(settings.get::<u64>("network.personal.access.tokens.owner.claims.max.age.ms")?),
+        },
+    };
+    let personal_access_tokens = PersonalAccessTokenAuthenticator::new(Arc::clone(&resource_manager), token_owners);
+
     let grpc_facades = startup::grpc::GrpcFacades::create(
-        resource_manager,
+        Arc::clone(&resource_manager),
         &carl_url,
         ca_certificate.clone(),
         oidc_registration_client.clone(),
+        client_certificate_authority.clone(),
+        authorization_policy.clone(),
         &settings
     ).await?;
 
@@ -112,38 +227,53 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
             ).expect("Could not create CLEO install script.");
         }
 
-        let http_state = startup::http::create_http_state(&carl_url, carl_installation_directory, &settings)?;
+        let http_state = startup::http::create_http_state(&carl_url, carl_installation_directory, local_user_store.clone(), &settings)?;
 
         startup::http::create_http_service(&settings)?
             .with_state(http_state)
     };
 
     let grpc = {
-        let grpc_auth_layer = match oidc_registration_client {
-            None => GrpcAuthenticationLayer::AuthDisabled,
-            Some(oidc_client_ref) => {
+        let grpc_auth_layer = match (&oidc_registration_client, &local_user_store) {
+            (None, None) => GrpcAuthenticationLayer::AuthDisabled,
+            (None, Some(local_user_store)) => GrpcAuthenticationLayer::LocalUsers { store: Arc::clone(local_user_store), personal_access_tokens },
+            (Some(oidc_client_ref), _) => {
                 let jwk_cache: CustomInMemoryCache<String, JwkCacheValue> = CustomInMemoryCache::new();
 
                 GrpcAuthenticationLayer::GrpcAuthLayerEnabled {
                     issuer_url: oidc_client_ref.inner.config.issuer_url.clone(),
                     issuer_remote_url: oidc_client_ref.config.issuer_remote_url.clone(),
                     cache: jwk_cache,
+                    personal_access_tokens,
                 }
             }
         };
 
+        let grpc_authorization_layer = Arc::new(GrpcAuthorizationLayer {
+            policy: authorization_policy,
+            peer_clients: oidc_registration_client,
+            client_certificates_enabled: client_certificate_authority.is_some(),
+            projects: project_policy,
+            resource_manager,
+        });
+
         let mut routes_builder = Routes::builder();
 
         routes_builder
+            .add_service(grpc_facades.administration_facade.into_grpc_service())
+            .add_service(grpc_facades.audit_log_facade.into_grpc_service())
             .add_service(grpc_facades.cluster_manager_facade.into_grpc_service())
             .add_service(grpc_facades.metadata_provider_facade.into_grpc_service())
+            .add_service(grpc_facades.notification_manager_facade.into_grpc_service())
             .add_service(grpc_facades.peer_manager_facade.into_grpc_service())
             .add_service(grpc_facades.peer_messaging_broker_facade.into_grpc_service())
-            .add_service(grpc_facades.observer_messaging_broker_facade.into_grpc_service());
+            .add_service(grpc_facades.observer_messaging_broker_facade.into_grpc_service())
+            .add_service(grpc_facades.token_manager_facade.into_grpc_service());
 
         routes_builder
             .routes()
             .into_axum_router()
+            .layer(axum::middleware::from_fn_with_state(grpc_authorization_layer, authorization_middleware))
             .layer(async_interceptor(move |request| {
                 Clone::clone(&grpc_auth_layer).auth_interceptor(request)
             }))
@@ -173,10 +303,11 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
         SocketAddr::from_str(&format!("{host}:{port}"))?
     };
 
-    match TlsConfig::load(&settings).await? {
+    match TlsConfig::load(&settings, client_certificate_authority.as_ref()).await? {
         TlsConfig::Enabled(tls_config) => {
             info!("Server listening at {address}...");
-            axum_server::bind_rustls(address, tls_config)
+            axum_server::bind(address)
+                .acceptor(ClientCertificateAcceptor::new(tls_config))
                 .serve(Shared::new(http_grpc))
                 .await?;
         }
@@ -1,4 +1,10 @@
+use std::io::BufRead;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
 use opendut_carl::app_info;
+use opendut_types::user::{LocalUserName, LocalUserRole};
 
 shadow_formatted_version::from_shadow!(app_info);
 
@@ -18,9 +24,103 @@ const BANNER: &str = r"
 
               - He Fixes the Cable -";
 
+#[derive(Debug, Parser)]
+#[command(name = "opendut-carl")]
+#[command(about = "Control and management server of openDuT.")]
+#[command(long_version = FORMATTED_VERSION)]
+struct Cli {
+    /// Without a command, the server is started.
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    /// Export all persistent resources into a snapshot archive. Requires CARL to not be running.
+    Export {
+        /// Path of the archive to create, e.g. `carl-snapshot.tar.gz`
+        file: PathBuf,
+    },
+    /// Import the resources of a snapshot archive. Requires CARL to not be running.
+    Import {
+        /// Path of an archive created via `export`
+        file: PathBuf,
+    },
+    /// Manage the users of the built-in user store. Requires CARL to not be running.
+    User {
+        #[command(subcommand)]
+        command: UserCommands,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum UserCommands {
+    /// Create a user
+    Add {
+        /// Name of the user, which is used as client ID when fetching a token
+        name: LocalUserName,
+        /// Role of the user: administrator, editor or viewer
+        #[arg(long = "role", required = true)]
+        roles: Vec<LocalUserRole>,
+        /// Read the password from the first line of stdin instead of generating an API token
+        #[arg(long)]
+        password_stdin: bool,
+        /// Replace an existing user with the same name, including their roles and secret
+        #[arg(long)]
+        force: bool,
+    },
+    /// Remove a user
+    Remove {
+        /// Name of the user
+        name: LocalUserName,
+    },
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    println!("{BANNER}\n{version_info}", version_info=FORMATTED_VERSION);
+    let cli = Cli::parse();
+
+    match cli.command {
+        None => {
+            println!("{BANNER}\n{version_info}", version_info=FORMATTED_VERSION);
+
+            opendut_carl::create_with_telemetry(opendut_util::settings::Config::default()).await
+        }
+        Some(Commands::Export { file }) => {
+            opendut_carl::export_snapshot(opendut_util::settings::Config::default(), &file).await?;
+            println!("Exported snapshot to '{}'.", file.display());
+            Ok(())
+        }
+        Some(Commands::Import { file }) => {
+            opendut_carl::import_snapshot(opendut_util::settings::Config::default(), &file).await?;
+            println!("Imported snapshot from '{}'.", file.display());
+            Ok(())
+        }
+        Some(Commands::User { command: UserCommands::Add { name, roles, password_stdin, force } }) => {
+            let secret = if password_stdin {
+                let mut password = String::new();
+                std::io::stdin().lock().read_line(&mut password)?;
+                let password = password.trim_end_matches(['\r', '\n']).to_owned();
+                anyhow::ensure!(!password.is_empty(), "The password read from stdin must not be empty.");
+                password
+            } else {
+                opendut_carl::generate_local_user_secret()?
+            };
 
-    opendut_carl::create_with_telemetry(opendut_util::settings::Config::default()).await
+            opendut_carl::add_local_user(opendut_util::settings::Config::default(), name.clone(), roles, &secret, force).await?;
+            println!("Added user '{name}'.");
+            if !password_stdin {
+                println!("API token (only shown once): {secret}");
+            }
+            Ok(())
+        }
+        Some(Commands::User { command: UserCommands::Remove { name } }) => {
+            if opendut_carl::remove_local_user(opendut_util::settings::Config::default(), &name).await? {
+                println!("Removed user '{name}'.");
+                Ok(())
+            } else {
+                anyhow::bail!("User '{name}' does not exist.")
+            }
+        }
+    }
 }
@@ -0,0 +1,334 @@
+use crate::auth::projects::ProjectScope;
+use crate::resource::api::id::ResourceId;
+use crate::resource::api::resources::Resources;
+use crate::resource::api::Resource;
+use crate::resource::persistence::error::{PersistenceError, PersistenceResult};
+use crate::resource::persistence::persistable::Persistable;
+use crate::resource::storage::ResourcesStorageApi;
+use opendut_types::audit::{AuditAction, AuditActor, AuditEntry, AuditLogFilter, AuditedResource};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::notification::NotificationSubscription;
+use opendut_types::peer::certificate::ClientCertificateRecord;
+use opendut_types::peer::decommission::PeerDecommissionRecord;
+use opendut_types::peer::setup::PeerSetupRecord;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::resources::Id;
+use std::collections::HashMap;
+use tracing::debug;
+
+
+impl Resources<'_> {
+    /// Lists the entries of the audit log matching the filter, ordered from oldest to newest.
+    #[tracing::instrument(skip_all, level="trace")]
+    pub fn list_audit_entries(&self, filter: &AuditLogFilter) -> Result<Vec<AuditEntry>, ListAuditEntriesError> {
+
+        debug!("Querying audit entries matching: {filter:?}");
+
+        let mut entries = self.list::<AuditEntry>()
+            .map_err(|source| ListAuditEntriesError::Persistence { source })?
+            .into_values()
+            .filter(|entry| filter.matches(entry))
+            .collect::<Vec<_>>();
+
+        entries.sort_by_key(|entry| entry.timestamp);
+
+        Ok(entries)
+    }
+
+    /// Like [`Self::list_audit_entries`], but only lists entries of resources, which belong to or are shared with one of the projects in scope.
+    /// Removed resources and resources outside of projects, like personal access tokens, cannot be attributed to a project,
+    /// so their entries are only listed with unrestricted scope.
+    pub fn list_audit_entries_in_scope(&self, filter: &AuditLogFilter, scope: &ProjectScope) -> Result<Vec<AuditEntry>, ListAuditEntriesError> {
+        let entries = self.list_audit_entries(filter)?;
+        if scope == &ProjectScope::Unrestricted {
+            return Ok(entries);
+        }
+
+        let mut visible_entries = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let is_visible = self.is_audited_resource_visible(&entry.resource, scope)
+                .map_err(|source| ListAuditEntriesError::Persistence { source })?;
+            if is_visible {
+                visible_entries.push(entry);
+            }
+        }
+        Ok(visible_entries)
+    }
+
+    fn is_audited_resource_visible(&self, resource: &AuditedResource, scope: &ProjectScope) -> PersistenceResult<bool> {
+        let is_peer_visible = |peer_id: PeerId| -> PersistenceResult<bool> {
+            Ok(self.get::<PeerDescriptor>(peer_id)?
+                .is_some_and(|peer| scope.can_read(&peer.project)))
+        };
+        let is_cluster_visible = |cluster_id: ClusterId| -> PersistenceResult<bool> {
+            Ok(self.get::<ClusterConfiguration>(cluster_id)?
+                .is_some_and(|configuration| scope.is_member(&configuration.project)))
+        };
+
+        match resource.kind.as_str() {
+            kind if kind == PeerDescriptor::TABLE => is_peer_visible(PeerId::from(resource.id)),
+            kind if kind == PeerSetupRecord::TABLE => match self.get_audited::<PeerSetupRecord>(resource)? {
+                Some(setup) => is_peer_visible(setup.peer_id),
+                None => Ok(false),
+            },
+            kind if kind == ClientCertificateRecord::TABLE => match self.get_audited::<ClientCertificateRecord>(resource)? {
+                Some(certificate) => is_peer_visible(certificate.peer_id),
+                None => Ok(false),
+            },
+            kind if kind == PeerDecommissionRecord::TABLE => is_peer_visible(PeerId::from(resource.id)),
+            kind if kind == ClusterConfiguration::TABLE || kind == ClusterDeployment::TABLE => is_cluster_visible(ClusterId::from(resource.id)),
+            kind if kind == NotificationSubscription::TABLE => Ok(self.get_audited::<NotificationSubscription>(resource)?
+                .is_some_and(|subscription| scope.is_member(&subscription.project))),
+            _ => Ok(false),
+        }
+    }
+
+    fn get_audited<R>(&self, resource: &AuditedResource) -> PersistenceResult<Option<R>>
+    where R: Resource + Persistable + Clone {
+        self.get::<R>(<R::Id as ResourceId<R>>::from_id(Id::from(resource.id)))
+    }
+
+    /// Records the creators of peers and cluster configurations, which were stored without one, as far as the audit log knows them.
+    /// This is the case for resources stored before creators were recorded and for resources imported from a snapshot.
+    pub fn restore_creators(&mut self) -> PersistenceResult<()> {
+        let missing_peers = self.without_creator::<PeerDescriptor>()?;
+        let missing_cluster_configurations = self.without_creator::<ClusterConfiguration>()?;
+        if missing_peers.is_empty() && missing_cluster_configurations.is_empty() {
+            return Ok(());
+        }
+
+        let mut entries = self.list::<AuditEntry>()?
+            .into_values()
+            .collect::<Vec<_>>();
+        //the resources exist, so on equal timestamps, their removal must have preceded their insertion
+        entries.sort_by_key(|entry| (entry.timestamp, entry.action == AuditAction::Inserted, entry.id.0));
+
+        let mut creators = HashMap::<AuditedResource, AuditActor>::new();
+        for entry in entries {
+            match entry.action {
+                AuditAction::Inserted => { creators.entry(entry.resource).or_insert(entry.actor); }
+                AuditAction::Removed => { creators.remove(&entry.resource); }
+            }
+        }
+
+        self.restore_creators_from::<PeerDescriptor>(missing_peers, &creators)?;
+        self.restore_creators_from::<ClusterConfiguration>(missing_cluster_configurations, &creators)?;
+        Ok(())
+    }
+
+    fn without_creator<R>(&self) -> PersistenceResult<Vec<R::Id>>
+    where R: Resource + Persistable + Clone {
+        let mut missing = Vec::new();
+        for id in self.list::<R>()?.into_keys() {
+            if self.creator_of::<R>(id.clone())?.is_none() {
+                missing.push(id);
+            }
+        }
+        Ok(missing)
+    }
+
+    fn restore_creators_from<R>(&mut self, ids: Vec<R::Id>, creators: &HashMap<AuditedResource, AuditActor>) -> PersistenceResult<()>
+    where R: Resource + Persistable {
+        for id in ids {
+            let resource = AuditedResource {
+                kind: String::from(R::TABLE),
+                id: ResourceId::<R>::into_id(id.clone()).value(),
+            };
+            match creators.get(&resource) {
+                Some(creator) => self.restore_creator::<R>(id, creator)?,
+                None => debug!("No creator of {} <{}> is known from the audit log.", R::TABLE, resource.id),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListAuditEntriesError {
+    #[error("Error when accessing persistence while listing audit entries")]
+    Persistence {
+        #[source] source: PersistenceError,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::testing::PeerFixture;
+    use crate::resource::manager::ResourceManager;
+    use googletest::prelude::*;
+    use opendut_types::audit::{AuditAction, AuditActor, AuditEntryId};
+    use opendut_types::peer::state::PeerConnectionState;
+    use opendut_types::project::{ProjectAssignment, ProjectId};
+    use std::collections::HashSet;
+    use std::time::{Duration, SystemTime};
+
+    #[tokio::test]
+    async fn should_record_actor_of_inserts_and_removals() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer = PeerFixture::new();
+        let alice = AuditActor::User { name: String::from("alice") };
+
+        resource_manager.resources_mut_as(alice.clone(), async |resources| {
+            resources.insert(peer.id, peer.descriptor.clone())
+        }).await??;
+        resource_manager.remove::<PeerDescriptor>(peer.id).await?;
+
+        let entries = resource_manager.resources(async |resources| {
+            resources.list_audit_entries(&AuditLogFilter::default())
+        }).await??;
+
+        assert_that!(entries.len(), eq(2));
+        assert_that!(entries[0].actor, eq(&alice));
+        assert_that!(entries[0].action, eq(AuditAction::Inserted));
+        assert_that!(entries[0].resource.kind, eq("peer_descriptor"));
+        assert_that!(entries[0].resource.id, eq(peer.id.uuid));
+        assert_that!(entries[1].actor, eq(&AuditActor::System));
+        assert_that!(entries[1].action, eq(AuditAction::Removed));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_filter_audit_entries_by_user_and_resource() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer_a = PeerFixture::new();
+        let peer_b = PeerFixture::new();
+
+        resource_manager.resources_mut_as(AuditActor::User { name: String::from("alice") }, async |resources| {
+            resources.insert(peer_a.id, peer_a.descriptor.clone())
+        }).await??;
+        resource_manager.resources_mut_as(AuditActor::User { name: String::from("bob") }, async |resources| {
+            resources.insert(peer_b.id, peer_b.descriptor.clone())
+        }).await??;
+
+        let entries_by_bob = resource_manager.resources(async |resources| {
+            resources.list_audit_entries(&AuditLogFilter { actor: Some(AuditActor::User { name: String::from("bob") }), ..Default::default() })
+        }).await??;
+        assert_that!(entries_by_bob.len(), eq(1));
+        assert_that!(entries_by_bob[0].resource.id, eq(peer_b.id.uuid));
+
+        let entries_of_peer_a = resource_manager.resources(async |resources| {
+            resources.list_audit_entries(&AuditLogFilter { resource_id: Some(peer_a.id.uuid), ..Default::default() })
+        }).await??;
+        assert_that!(entries_of_peer_a.len(), eq(1));
+        assert_that!(entries_of_peer_a[0].actor, eq(&AuditActor::User { name: String::from("alice") }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_determine_creator_of_resource() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer = PeerFixture::new();
+        let alice = AuditActor::User { name: String::from("alice") };
+        let bob = AuditActor::User { name: String::from("bob") };
+
+        let creator = resource_manager.resources(async |resources| resources.creator_of::<PeerDescriptor>(peer.id)).await??;
+        assert_that!(creator, none());
+
+        for actor in [&alice, &bob] {
+            resource_manager.resources_mut_as(Clone::clone(actor), async |resources| {
+                resources.insert(peer.id, peer.descriptor.clone())
+            }).await??;
+        }
+        let creator = resource_manager.resources(async |resources| resources.creator_of::<PeerDescriptor>(peer.id)).await??;
+        assert_that!(creator, some(eq(&alice)));
+
+        resource_manager.remove::<PeerDescriptor>(peer.id).await?;
+        resource_manager.resources_mut_as(Clone::clone(&bob), async |resources| {
+            resources.insert(peer.id, peer.descriptor.clone())
+        }).await??;
+        let creator = resource_manager.resources(async |resources| resources.creator_of::<PeerDescriptor>(peer.id)).await??;
+        assert_that!(creator, some(eq(&bob)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_restore_creators_from_audit_log() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer = PeerFixture::new();
+        let alice = AuditActor::User { name: String::from("alice") };
+        let bob = AuditActor::User { name: String::from("bob") };
+        let timestamp = SystemTime::now();
+        let entry = |actor: &AuditActor, action: AuditAction, timestamp: SystemTime| AuditEntry {
+            id: AuditEntryId::random(),
+            timestamp,
+            actor: Clone::clone(actor),
+            action,
+            resource: AuditedResource { kind: String::from(PeerDescriptor::TABLE), id: peer.id.uuid },
+        };
+
+        resource_manager.resources_mut(async |resources| {
+            resources.restore(peer.id, peer.descriptor.clone())?;
+            resources.restore_audit_entry(entry(&alice, AuditAction::Inserted, timestamp - Duration::from_secs(60)))?;
+            resources.restore_audit_entry(entry(&bob, AuditAction::Inserted, timestamp))?;
+            resources.restore_audit_entry(entry(&alice, AuditAction::Removed, timestamp))?;
+            resources.restore_audit_entry(entry(&alice, AuditAction::Inserted, timestamp + Duration::from_secs(60)))?;
+            PersistenceResult::Ok(())
+        }).await??;
+        let creator = resource_manager.resources(async |resources| resources.creator_of::<PeerDescriptor>(peer.id)).await??;
+        assert_that!(creator, none());
+
+        resource_manager.resources_mut(async |resources| resources.restore_creators()).await??;
+
+        let creator = resource_manager.resources(async |resources| resources.creator_of::<PeerDescriptor>(peer.id)).await??;
+        assert_that!(creator, some(eq(&bob)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_only_list_audit_entries_of_resources_in_scope() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let chassis = ProjectId::try_from("chassis")?;
+        let own_peer = PeerFixture::new();
+        let mut foreign_peer = PeerFixture::new();
+        foreign_peer.descriptor.project = ProjectAssignment::owned_by(ProjectId::try_from("powertrain")?);
+        let removed_peer = PeerFixture::new();
+
+        for peer in [&own_peer, &foreign_peer, &removed_peer] {
+            resource_manager.insert(peer.id, peer.descriptor.clone()).await?;
+        }
+        resource_manager.remove::<PeerDescriptor>(removed_peer.id).await?;
+
+        let scope = ProjectScope::Members { projects: HashSet::from([ProjectId::default(), chassis]) };
+        let entries = resource_manager.resources(async |resources| {
+            resources.list_audit_entries_in_scope(&AuditLogFilter::default(), &scope)
+        }).await??;
+        let audited_peers = entries.into_iter().map(|entry| entry.resource.id).collect::<Vec<_>>();
+        assert_that!(audited_peers, elements_are![eq(&own_peer.id.uuid)]);
+
+        let entries = resource_manager.resources(async |resources| {
+            resources.list_audit_entries_in_scope(&AuditLogFilter::default(), &ProjectScope::Unrestricted)
+        }).await??;
+        assert_that!(entries.len(), eq(4));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_count_revisions_for_audit_entries() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer = PeerFixture::new();
+
+        resource_manager.insert(peer.id, peer.descriptor.clone()).await?;
+
+        let (peer_revision, latest_revision) = resource_manager.resources(async |resources| {
+            PersistenceResult::Ok((resources.revision::<PeerDescriptor>(peer.id)?, resources.latest_revision()?))
+        }).await??;
+        assert_that!(peer_revision, some(eq(latest_revision)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_audit_volatile_resources() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer = PeerFixture::new();
+
+        resource_manager.insert(peer.id, PeerConnectionState::Offline).await?;
+
+        let entries = resource_manager.resources(async |resources| {
+            resources.list_audit_entries(&AuditLogFilter::default())
+        }).await??;
+
+        assert_that!(entries, empty());
+        Ok(())
+    }
+}
@@ -1,11 +1,17 @@
 use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::project::ProjectId;
+use opendut_types::resources::Revision;
 use tracing::{debug, info};
-use crate::resource::api::resources::Resources;
+use crate::resource::api::resources::{Resources, RevisionConflict};
 use crate::resource::persistence::error::PersistenceError;
 use crate::resource::storage::ResourcesStorageApi;
 
 pub struct CreateClusterConfigurationParams {
+    /// Devices matching the device selector of the configuration are added to its devices, before it is stored.
     pub cluster_configuration: ClusterConfiguration,
+    /// Only store the cluster configuration, if it is still at this revision.
+    pub expected_revision: Option<Revision>,
 }
 
 impl Resources<'_> {
@@ -15,9 +21,42 @@ impl Resources<'_> {
         let cluster_id = params.cluster_configuration.id;
         let cluster_name = Clone::clone(&params.cluster_configuration.name);
 
+        self.check_revision::<ClusterConfiguration>(cluster_id, params.expected_revision)
+            .map_err(|source| CreateClusterConfigurationError::Persistence { cluster_id, cluster_name: cluster_name.clone(), source })?
+            .map_err(|RevisionConflict { expected, actual }| CreateClusterConfigurationError::RevisionConflict {
+                cluster_id,
+                cluster_name: cluster_name.clone(),
+                expected_revision: expected,
+                actual_revision: actual,
+            })?;
+
         debug!("Creating cluster configuration '{cluster_name}' <{cluster_id}>.");
 
-        self.insert(cluster_id, params.cluster_configuration)
+        let mut cluster_configuration = params.cluster_configuration;
+
+        // Clusters may only use peers, which belong to or are shared with their project.
+        let peers = self.list::<PeerDescriptor>()
+            .map_err(|source| CreateClusterConfigurationError::Persistence { cluster_id, cluster_name: cluster_name.clone(), source })?
+            .into_values()
+            .collect::<Vec<_>>();
+
+        if cluster_configuration.device_selector.is_some() {
+            let project = Clone::clone(&cluster_configuration.project);
+            let project_peers = peers.iter()
+                .filter(|peer| peer.project.is_visible_to(&project));
+            cluster_configuration.select_devices(project_peers.flat_map(|peer| &peer.topology.devices));
+        }
+
+        if let Some(peer) = find_peer_outside_project(&cluster_configuration, &peers) {
+            return Err(CreateClusterConfigurationError::PeerOutsideProject {
+                cluster_id,
+                cluster_name,
+                peer_id: peer.id,
+                project: cluster_configuration.project,
+            });
+        }
+
+        self.insert(cluster_id, cluster_configuration)
             .map_err(|source| CreateClusterConfigurationError::Persistence { cluster_id, cluster_name: cluster_name.clone(), source })?;
 
         info!("Successfully created cluster configuration '{cluster_name}' <{cluster_id}>.");
@@ -26,9 +65,36 @@ impl Resources<'_> {
     }
 }
 
+/// Whether the peer is the leader of the cluster or provides any of its devices.
+pub fn cluster_uses_peer(cluster_configuration: &ClusterConfiguration, peer: &PeerDescriptor) -> bool {
+    peer.id == cluster_configuration.leader
+        || peer.topology.devices.iter().any(|device| cluster_configuration.devices.contains(&device.id))
+}
+
+/// Finds a peer used by the cluster, which neither belongs to nor is shared with the project of the cluster.
+pub fn find_peer_outside_project<'a>(cluster_configuration: &ClusterConfiguration, peers: &'a [PeerDescriptor]) -> Option<&'a PeerDescriptor> {
+    peers.iter()
+        .filter(|peer| !peer.project.is_visible_to(&cluster_configuration.project))
+        .find(|peer| cluster_uses_peer(cluster_configuration, peer))
+}
+
 #[derive(thiserror::Error, Debug)]
-#[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be created")]
 pub enum CreateClusterConfigurationError {
+    #[error("ClusterConfiguration '{cluster_name}' <{cluster_id}> was modified since revision {expected_revision} and cannot be updated! Reload the cluster configuration and apply the changes again.")]
+    RevisionConflict {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        expected_revision: Revision,
+        actual_revision: Option<Revision>,
+    },
+    #[error("ClusterConfiguration '{cluster_name}' <{cluster_id}> uses peer <{peer_id}>, which neither belongs to nor is shared with project '{project}'.")]
+    PeerOutsideProject {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        peer_id: PeerId,
+        project: ProjectId,
+    },
+    #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be created")]
     Persistence {
         cluster_id: ClusterId,
         cluster_name: ClusterName,
@@ -40,9 +106,82 @@ pub enum CreateClusterConfigurationError {
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
-    use crate::manager::testing::ClusterFixture;
+    use std::str::FromStr;
+    use opendut_types::labels::{LabelSelector, Labels};
+    use opendut_types::project::ProjectAssignment;
+    use crate::manager::testing::{ClusterFixture, PeerFixture};
+    use crate::resource::manager::ResourceManager;
     use super::*;
 
+    #[tokio::test]
+    async fn should_add_devices_matching_the_device_selector() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let mut peer = PeerFixture::new();
+        peer.descriptor.topology.devices[1].labels = Labels::from_str("team=chassis")?;
+        resource_manager.insert(peer.id, peer.descriptor.clone()).await?;
+
+        let cluster_id = ClusterId::random();
+        let cluster_configuration = ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("SelectedCluster")?,
+            leader: peer.id,
+            devices: HashSet::from([peer.device_1]),
+            labels: Labels::default(),
+            device_selector: Some(LabelSelector::from_str("team=chassis")?),
+            project: Default::default(),
+        };
+        resource_manager.resources_mut(async |resources| {
+            resources.create_cluster_configuration(CreateClusterConfigurationParams {
+                cluster_configuration,
+                expected_revision: None,
+            })
+        }).await??;
+
+        assert_eq!(
+            resource_manager.get::<ClusterConfiguration>(cluster_id).await?.unwrap().devices,
+            HashSet::from([peer.device_1, peer.device_2])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_peers_which_are_not_visible_to_the_project_of_the_cluster() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let chassis = ProjectId::try_from("chassis")?;
+        let mut peer = PeerFixture::new();
+        peer.descriptor.project = ProjectAssignment::owned_by(ProjectId::try_from("powertrain")?);
+        resource_manager.insert(peer.id, peer.descriptor.clone()).await?;
+
+        let cluster_configuration = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("ChassisCluster")?,
+            leader: peer.id,
+            devices: HashSet::from([peer.device_1, peer.device_2]),
+            labels: Labels::default(),
+            device_selector: None,
+            project: chassis.clone(),
+        };
+        let params = || CreateClusterConfigurationParams {
+            cluster_configuration: cluster_configuration.clone(),
+            expected_revision: None,
+        };
+
+        let result = resource_manager.resources_mut(async |resources| {
+            resources.create_cluster_configuration(params())
+        }).await?;
+        assert!(matches!(result, Err(CreateClusterConfigurationError::PeerOutsideProject { peer_id, .. }) if peer_id == peer.id));
+
+        peer.descriptor.project.shared_with.insert(chassis);
+        resource_manager.insert(peer.id, peer.descriptor.clone()).await?;
+
+        resource_manager.resources_mut(async |resources| {
+            resources.create_cluster_configuration(params())
+        }).await??;
+
+        Ok(())
+    }
+
     /// Our data model does not match well with RDBMS, since we send full models to the backend, whereas RDBMSs work with diffs.
     /// As such, this verifies that we correctly diff the full models to delete removed child elements.
     #[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
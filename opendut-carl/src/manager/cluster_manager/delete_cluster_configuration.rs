@@ -3,12 +3,15 @@ use opendut_types::ShortName;
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId, ClusterName};
 use tracing::{debug, info};
 use opendut_types::cluster::state::ClusterState;
-use crate::resource::api::resources::Resources;
+use opendut_types::resources::Revision;
+use crate::resource::api::resources::{Resources, RevisionConflict};
 use crate::resource::persistence::error::PersistenceError;
 use crate::resource::storage::ResourcesStorageApi;
 
 pub struct DeleteClusterConfigurationParams {
     pub cluster_id: ClusterId,
+    /// Only delete the cluster configuration, if it is still at this revision.
+    pub expected_revision: Option<Revision>,
 }
 
 impl Resources<'_> {
@@ -17,6 +20,17 @@ impl Resources<'_> {
 
         let cluster_id = params.cluster_id;
 
+        self.check_revision::<ClusterConfiguration>(cluster_id, params.expected_revision)
+            .map_err(|source| DeleteClusterConfigurationError::Persistence { cluster_id, cluster_name: None, source })?
+            .map_err(|conflict| match conflict {
+                RevisionConflict { actual: None, .. } => DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id },
+                RevisionConflict { expected, actual: Some(actual) } => DeleteClusterConfigurationError::RevisionConflict {
+                    cluster_id,
+                    expected_revision: expected,
+                    actual_revision: actual,
+                },
+            })?;
+
         let cluster_deployment = self.get::<ClusterDeployment>(cluster_id)
             .map_err(|source| DeleteClusterConfigurationError::Persistence { cluster_id, cluster_name: None, source })?;
 
@@ -51,6 +65,12 @@ pub enum DeleteClusterConfigurationError {
     ClusterConfigurationNotFound {
         cluster_id: ClusterId
     },
+    #[error("ClusterConfiguration <{cluster_id}> was modified since revision {expected_revision} and is not deleted! Reload the cluster configuration and check whether it should still be deleted.")]
+    RevisionConflict {
+        cluster_id: ClusterId,
+        expected_revision: Revision,
+        actual_revision: Revision,
+    },
     #[error(
         "ClusterConfiguration '{cluster_name}' <{cluster_id}> cannot be deleted when cluster is in state '{actual_state}'! A ClusterConfiguration can be deleted when cluster is in state: {required_states}",
         actual_state = actual_state.short_name(),
@@ -83,7 +103,7 @@ mod tests {
         resource_manager.insert(cluster.id, ClusterDeployment { id: cluster.id }).await?;
 
         let result = resource_manager.resources_mut(async |resources| {
-            resources.delete_cluster_configuration(DeleteClusterConfigurationParams { cluster_id: cluster.id })
+            resources.delete_cluster_configuration(DeleteClusterConfigurationParams { cluster_id: cluster.id, expected_revision: None })
         }).await?;
 
         let Err(DeleteClusterConfigurationError::ClusterDeploymentFound { cluster_id }) = result
@@ -98,7 +118,7 @@ mod tests {
         let resource_manager = ResourceManager::new_in_memory();
         let cluster = ClusterFixture::create(resource_manager.clone()).await?;
         let result = resource_manager.resources_mut(async |resources|
-            resources.delete_cluster_configuration(DeleteClusterConfigurationParams { cluster_id: cluster.id })
+            resources.delete_cluster_configuration(DeleteClusterConfigurationParams { cluster_id: cluster.id, expected_revision: None })
         ).await??;
 
         assert_eq!(result, cluster.configuration);
@@ -1,5 +1,11 @@
+use std::sync::Arc;
 use opendut_types::ShortName;
 use opendut_types::cluster::ClusterDisplay;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use tracing::warn;
+use crate::manager::cluster_manager::{cluster_uses_peer, determine_member_interface_mapping};
+use crate::manager::peer_manager::{UnassignClusterError, UnassignClusterParams};
+use crate::manager::peer_messaging_broker::PeerMessagingBrokerRef;
 use crate::settings::vpn::Vpn;
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId, ClusterName};
 use opendut_types::cluster::state::ClusterState;
@@ -10,13 +16,14 @@ use crate::resource::storage::ResourcesStorageApi;
 pub struct DeleteClusterDeploymentParams {
     pub cluster_id: ClusterId,
     pub vpn: Vpn,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
 }
 
 impl Resources<'_> {
     #[tracing::instrument(skip_all, level="trace")]
     pub async fn delete_cluster_deployment(&mut self, params: DeleteClusterDeploymentParams) -> Result<ClusterDeployment, DeleteClusterDeploymentError> {
 
-        let DeleteClusterDeploymentParams { cluster_id, vpn } = params;
+        let DeleteClusterDeploymentParams { cluster_id, vpn, peer_messaging_broker } = params;
 
         let (deployment, cluster) =
             self.remove::<ClusterDeployment>(cluster_id)
@@ -34,11 +41,74 @@ impl Resources<'_> {
                     .map_err(|source| DeleteClusterDeploymentError::VpnClient { cluster_id, cluster_name: cluster.name.clone(), source })?;
             }
 
-            // TODO: unassign cluster for each peer
+            let all_peers = self.list::<PeerDescriptor>()
+                .map_err(|source| DeleteClusterDeploymentError::Persistence { cluster_id, cluster_name: Some(cluster.name.clone()), source })?
+                .into_values()
+                .collect::<Vec<_>>();
+
+            let member_ids = determine_member_interface_mapping(cluster.devices, all_peers, cluster.leader)
+                .map(|member_interface_mapping| member_interface_mapping.into_keys().collect::<Vec<_>>())
+                .unwrap_or_else(|_| vec![cluster.leader]);
+
+            for peer_id in member_ids {
+                self.unassign_cluster(UnassignClusterParams {
+                    peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+                    peer_id,
+                    cluster_id,
+                }).await
+                .map_err(|source| DeleteClusterDeploymentError::UnassignCluster { cluster_id, cluster_name: cluster.name.clone(), source })?;
+            }
         }
 
         Ok(deployment)
     }
+
+    /// Deletes the deployments of clusters, which use the peer, although it neither belongs to nor is shared with their project anymore,
+    /// e.g. after the peer stopped being shared with that project. Returns the IDs of the clusters, whose deployment was deleted.
+    #[tracing::instrument(skip_all, level="trace")]
+    pub async fn delete_cluster_deployments_outside_peer_project(&mut self, params: DeleteClusterDeploymentsOutsidePeerProjectParams) -> Result<Vec<ClusterId>, DeleteClusterDeploymentsOutsidePeerProjectError> {
+
+        let DeleteClusterDeploymentsOutsidePeerProjectParams { peer_id, vpn, peer_messaging_broker } = params;
+
+        let Some(peer) = self.get::<PeerDescriptor>(peer_id)
+            .map_err(|source| DeleteClusterDeploymentsOutsidePeerProjectError::Persistence { peer_id, source })?
+        else {
+            return Ok(Vec::new());
+        };
+
+        let clusters_outside_peer_project = self.list::<ClusterConfiguration>()
+            .map_err(|source| DeleteClusterDeploymentsOutsidePeerProjectError::Persistence { peer_id, source })?
+            .into_values()
+            .filter(|cluster| !peer.project.is_visible_to(&cluster.project) && cluster_uses_peer(cluster, &peer))
+            .collect::<Vec<_>>();
+
+        let mut undeployed_clusters = Vec::new();
+        for cluster in clusters_outside_peer_project {
+            let is_deployed = self.get::<ClusterDeployment>(cluster.id)
+                .map_err(|source| DeleteClusterDeploymentsOutsidePeerProjectError::Persistence { peer_id, source })?
+                .is_some();
+
+            if is_deployed {
+                warn!("Deleting deployment of cluster '{}' <{}>, because peer <{peer_id}> neither belongs to nor is shared with project '{}' anymore.", cluster.name, cluster.id, cluster.project);
+                self.delete_cluster_deployment(DeleteClusterDeploymentParams {
+                    cluster_id: cluster.id,
+                    vpn: Clone::clone(&vpn),
+                    peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+                }).await
+                .map_err(|source| DeleteClusterDeploymentsOutsidePeerProjectError::DeleteClusterDeployment { peer_id, source })?;
+
+                undeployed_clusters.push(cluster.id);
+            }
+        }
+
+        Ok(undeployed_clusters)
+    }
+}
+
+pub struct DeleteClusterDeploymentsOutsidePeerProjectParams {
+    pub peer_id: PeerId,
+    pub vpn: Vpn,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -69,5 +139,25 @@ pub enum DeleteClusterDeploymentError {
         cluster_id: ClusterId,
         cluster_name: ClusterName,
         #[source] source: opendut_vpn::DeleteClusterError,
-    }
+    },
+    #[error("Error when unassigning peers while deleting cluster deployment for cluster {cluster}", cluster=ClusterDisplay::new(&Some(cluster_name.to_owned()), cluster_id))]
+    UnassignCluster {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        #[source] source: UnassignClusterError,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteClusterDeploymentsOutsidePeerProjectError {
+    #[error("Error when accessing persistence while deleting cluster deployments, which peer <{peer_id}> is not visible to anymore")]
+    Persistence {
+        peer_id: PeerId,
+        #[source] source: PersistenceError,
+    },
+    #[error("Error when deleting cluster deployment, which peer <{peer_id}> is not visible to anymore")]
+    DeleteClusterDeployment {
+        peer_id: PeerId,
+        #[source] source: DeleteClusterDeploymentError,
+    },
 }
@@ -208,6 +208,9 @@ mod tests {
             name: ClusterName::try_from("BlockingCluster")?,
             leader: cluster.leader,
             devices: cluster.devices.clone(),
+            labels: Default::default(),
+            device_selector: None,
+            project: Default::default(),
         };
         // When another cluster is deployed
         {
@@ -313,6 +316,9 @@ mod tests {
                         .chain(peer_b.topology.devices.iter())
                         .map(|device| device.id)
                 ),
+                labels: Default::default(),
+                device_selector: None,
+                project: Default::default(),
             };
             resource_manager.insert(cluster.id, cluster.clone()).await?;
 
@@ -351,12 +357,15 @@ mod tests {
                         description: None,
                         interface: network_interface_id,
                         tags: vec![],
+                        labels: Default::default(),
                     }
                 ],
             },
             executors: ExecutorDescriptors {
                 executors: vec![],
             },
+            labels: Default::default(),
+            project: Default::default(),
         })
     }
 }
@@ -7,14 +7,18 @@ use futures::future::join_all;
 use futures::FutureExt;
 use tracing::{debug, error, trace, warn};
 
+use opendut_types::audit::AuditActor;
 use opendut_types::cluster::{ClusterAssignment, ClusterConfiguration, ClusterDeployment, ClusterId, ClusterName, PeerClusterAssignment};
 use opendut_types::peer::state::PeerConnectionState;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::project::ProjectId;
+use opendut_types::resources::Revision;
 use opendut_types::topology::{DeviceDescriptor, DeviceId};
 use opendut_types::util::net::{NetworkInterfaceDescriptor, NetworkInterfaceName};
 use opendut_types::util::Port;
 
 use crate::manager::peer_messaging_broker::PeerMessagingBrokerRef;
+use crate::resource::api::resources::Resources;
 use crate::resource::manager::ResourceManagerRef;
 use crate::resource::persistence::error::{MapErrToInner, PersistenceError, PersistenceResult};
 use crate::resource::storage::ResourcesStorageApi;
@@ -77,59 +81,103 @@ impl ClusterManager {
         self_ref
     }
 
+    pub fn peer_messaging_broker(&self) -> PeerMessagingBrokerRef {
+        Arc::clone(&self.peer_messaging_broker)
+    }
+
     #[tracing::instrument(skip(self), level="trace")]
-    pub async fn get_cluster_configuration(&self, cluster_id: ClusterId) -> Result<Option<ClusterConfiguration>, GetClusterConfigurationError> {
-        self.resource_manager.get::<ClusterConfiguration>(cluster_id).await
+    pub async fn get_cluster_configuration(&self, cluster_id: ClusterId) -> Result<Option<(ClusterConfiguration, Revision)>, GetClusterConfigurationError> {
+        self.resource_manager.resources(async |resources| -> PersistenceResult<Option<(ClusterConfiguration, Revision)>> {
+            let Some(configuration) = resources.get::<ClusterConfiguration>(cluster_id)? else {
+                return Ok(None);
+            };
+            let revision = resources.revision::<ClusterConfiguration>(cluster_id)?.unwrap_or_default();
+            Ok(Some((configuration, revision)))
+        }).await
+            .and_then(|result| result)
             .map_err(|source| GetClusterConfigurationError { cluster_id, source })
     }
 
     #[tracing::instrument(skip(self), level="trace")]
-    pub async fn list_cluster_configuration(&self) -> Result<Vec<ClusterConfiguration>, ListClusterConfigurationsError> {
-        self.resource_manager.list::<ClusterConfiguration>().await
-            .map(|clusters| clusters.into_values().collect::<Vec<_>>())
+    pub async fn list_cluster_configuration(&self) -> Result<Vec<(ClusterConfiguration, Revision)>, ListClusterConfigurationsError> {
+        self.resource_manager.resources(async |resources| -> PersistenceResult<Vec<(ClusterConfiguration, Revision)>> {
+            resources.list::<ClusterConfiguration>()?
+                .into_values()
+                .map(|configuration| {
+                    let revision = resources.revision::<ClusterConfiguration>(configuration.id)?.unwrap_or_default();
+                    Ok((configuration, revision))
+                })
+                .collect()
+        }).await
+            .and_then(|result| result)
             .map_err(|source| ListClusterConfigurationsError { source })
     }
 
 
-    #[tracing::instrument(skip(self), level="trace")]
-    pub async fn store_cluster_deployment(&mut self, deployment: ClusterDeployment) -> Result<ClusterId, StoreClusterDeploymentError> {
+    /// Stores the deployment, if `check_access` permits it. The access is checked in the same transaction, in which the deployment is stored.
+    /// A denied access is returned as `Ok(Err(denied))`, without modifying anything.
+    #[tracing::instrument(skip(self, check_access), level="trace")]
+    pub async fn store_cluster_deployment<E>(&mut self, deployment: ClusterDeployment, actor: AuditActor, check_access: impl FnOnce(&Resources) -> Result<(), E>) -> Result<Result<ClusterId, E>, StoreClusterDeploymentError> {
         let cluster_id = deployment.id;
 
-        let cluster_peers =
-            self.resource_manager.resources(async |resources| {
-                resources.list_cluster_peer_states(cluster_id).await
-            }).await
-            .map_err_to_inner(|source| ListClusterPeerStatesError::Persistence { cluster_id, source })
-            .map_err(|source| StoreClusterDeploymentError::ListClusterPeerStates { cluster_id, source })?;
+        let stored = self.resource_manager.resources_mut_as(actor, async |resources| {
+            if let Err(denied) = check_access(resources) {
+                return Ok(Err(denied));
+            }
 
-        let cluster_deployable = cluster_peers.check_all_peers_are_available_not_necessarily_online();
-        match cluster_deployable {
-            ClusterDeployable::AllPeersAvailable => {
-                self.resource_manager.resources_mut(async |resources| {
-                    let cluster_name = resources.get::<ClusterConfiguration>(cluster_id)
-                        .map_err(|source| StoreClusterDeploymentError::Persistence { cluster_id, cluster_name: None, source })?
+            let cluster_configuration = resources.get::<ClusterConfiguration>(cluster_id)
+                .map_err(|source| StoreClusterDeploymentError::Persistence { cluster_id, cluster_name: None, source })?;
+
+            if let Some(cluster_configuration) = &cluster_configuration {
+                let peers = resources.list::<PeerDescriptor>()
+                    .map_err(|source| StoreClusterDeploymentError::Persistence { cluster_id, cluster_name: Some(cluster_configuration.name.clone()), source })?
+                    .into_values()
+                    .collect::<Vec<_>>();
+
+                if let Some(peer) = find_peer_outside_project(cluster_configuration, &peers) {
+                    return Err(StoreClusterDeploymentError::PeerOutsideProject {
+                        cluster_id,
+                        cluster_name: cluster_configuration.name.clone(),
+                        peer_id: peer.id,
+                        project: Clone::clone(&cluster_configuration.project),
+                    });
+                }
+            }
+
+            let cluster_peers = resources.list_cluster_peer_states(cluster_id).await
+                .map_err(|source| StoreClusterDeploymentError::ListClusterPeerStates { cluster_id, source })?;
+
+            let cluster_deployable = cluster_peers.check_all_peers_are_available_not_necessarily_online();
+            match cluster_deployable {
+                ClusterDeployable::AllPeersAvailable => {
+                    let cluster_name = cluster_configuration
                         .map(|cluster| cluster.name)
                         .unwrap_or_else(|| ClusterName::try_from("unknown_cluster").unwrap());
 
                     resources.insert(cluster_id, deployment)
-                        .map_err(|source| StoreClusterDeploymentError::Persistence { cluster_id, cluster_name: Some(cluster_name.clone()), source })
-                }).await
-                    .map_err(|source| StoreClusterDeploymentError::Persistence { cluster_id, cluster_name: None, source })??;
-            }
-            ClusterDeployable::NotAllPeersAvailable { unavailable_peers } => {
-                let blocked_peers_by_id = unavailable_peers.into_iter().collect::<Vec<_>>();
-                warn!("Cannot store cluster deployment, because the following peers are blocked already: {blocked_peers_by_id:?}");
-                return Err(StoreClusterDeploymentError::IllegalPeerState { cluster_id: cluster_peers.cluster_id, cluster_name: None, invalid_peers: blocked_peers_by_id });
-            }
-            ClusterDeployable::AlreadyDeployed => {
-                trace!("Received instruction to store deployment for cluster <{cluster_id}>, which already exists. Ignoring.");
+                        .map_err(|source| StoreClusterDeploymentError::Persistence { cluster_id, cluster_name: Some(cluster_name.clone()), source })?;
+                }
+                ClusterDeployable::NotAllPeersAvailable { unavailable_peers } => {
+                    let blocked_peers_by_id = unavailable_peers.into_iter().collect::<Vec<_>>();
+                    warn!("Cannot store cluster deployment, because the following peers are blocked already: {blocked_peers_by_id:?}");
+                    return Err(StoreClusterDeploymentError::IllegalPeerState { cluster_id: cluster_peers.cluster_id, cluster_name: None, invalid_peers: blocked_peers_by_id });
+                }
+                ClusterDeployable::AlreadyDeployed => {
+                    trace!("Received instruction to store deployment for cluster <{cluster_id}>, which already exists. Ignoring.");
+                }
             }
+            Ok(Ok(()))
+        }).await
+            .map_err(|source| StoreClusterDeploymentError::Persistence { cluster_id, cluster_name: None, source })??;
+
+        if let Err(denied) = stored {
+            return Ok(Err(denied));
         }
 
         if let Err(error) = self.deploy_cluster_if_all_peers_available(cluster_id).await {
             error!("Failed to deploy cluster <{cluster_id}> after storing cluster deployment, despite all peers being available, due to:\n  {error}");
         }
-        Ok(cluster_id)
+        Ok(Ok(cluster_id))
     }
 
     #[tracing::instrument(skip(self), level="trace")]
@@ -224,13 +272,22 @@ impl ClusterManager {
             .map_err(|source| DeployClusterError::Persistence { cluster_id, source })?
             .ok_or(DeployClusterError::ClusterConfigurationNotFound(cluster_id))?;
 
-        let cluster_name = cluster_config.name;
+        let cluster_name = Clone::clone(&cluster_config.name);
 
         let all_peers = self.resource_manager.list::<PeerDescriptor>().await
             .map_err(|source| DeployClusterError::Persistence { cluster_id, source })?
             .into_values()
             .collect::<Vec<_>>();
 
+        // Peers may have stopped being shared with the project of the cluster since it was configured.
+        if let Some(peer) = find_peer_outside_project(&cluster_config, &all_peers) {
+            return Err(DeployClusterError::PeerOutsideProject {
+                cluster_id,
+                cluster_name,
+                peer_id: peer.id,
+                project: cluster_config.project,
+            });
+        }
 
         let member_interface_mapping = determine_member_interface_mapping(cluster_config.devices, all_peers, cluster_config.leader)
             .map_err(|cause| match cause {
@@ -239,6 +296,8 @@ impl ClusterManager {
 
         let member_ids = member_interface_mapping.keys().cloned().collect::<Vec<_>>();
 
+        let mut wireguard_peers = HashMap::new();
+
         if let Vpn::Enabled { vpn_client } = &self.vpn {
             vpn_client.create_cluster(cluster_id, &member_ids).await
                 .map_err(|cause| {
@@ -249,6 +308,16 @@ impl ClusterManager {
 
             let peers_string = member_ids.iter().map(|peer| peer.to_string()).collect::<Vec<_>>().join(",");
             debug!("Created group for cluster <{cluster_id}> in VPN service, using peers: {peers_string}");
+
+            for peer_id in &member_ids {
+                let peers = vpn_client.generate_wireguard_peers(cluster_id, *peer_id).await
+                    .map_err(|cause| {
+                        let message = format!("Failure while determining the WireGuard peers of peer <{peer_id}> in cluster <{cluster_id}>.");
+                        error!("{}\n  {cause}", message);
+                        DeployClusterError::Internal { cluster_id, cause: message }
+                    })?;
+                wireguard_peers.insert(*peer_id, peers);
+            }
         } else {
             debug!("VPN disabled. Not creating VPN group.")
         }
@@ -307,6 +376,7 @@ impl ClusterManager {
                         assignments: member_assignments.clone(),
                     },
                     device_interfaces,
+                    wireguard_peers: wireguard_peers.remove(&member_id).unwrap_or_default(),
                     options: assign_cluster_options.clone(),
                 }).await
                 .map_err(|cause| {
@@ -363,7 +433,7 @@ impl ClusterManager {
     }
 }
 
-fn determine_member_interface_mapping(
+pub(crate) fn determine_member_interface_mapping(
     cluster_devices: HashSet<DeviceId>,
     all_peers: Vec<PeerDescriptor>,
     leader: PeerId,
@@ -430,7 +500,7 @@ impl ClusterManagerOptions {
     }
 }
 #[derive(Debug, thiserror::Error)]
-enum DetermineMemberInterfaceMappingError {
+pub(crate) enum DetermineMemberInterfaceMappingError {
     #[error("Peer for device <{device_id}> not found.")]
     PeerForDeviceNotFound { device_id: DeviceId },
 }
@@ -461,6 +531,13 @@ pub mod error {
             cluster_name: Option<ClusterName>,
             invalid_peers: Vec<PeerId>,
         },
+        #[error("ClusterDeployment for cluster '{cluster_name}' <{cluster_id}> failed, because peer <{peer_id}> neither belongs to nor is shared with project '{project}' anymore.")]
+        PeerOutsideProject {
+            cluster_id: ClusterId,
+            cluster_name: ClusterName,
+            peer_id: PeerId,
+            project: ProjectId,
+        },
         ListClusterPeerStates { cluster_id: ClusterId, #[source] source: ListClusterPeerStatesError },
         Persistence { cluster_id: ClusterId, cluster_name: Option<ClusterName>, #[source] source: PersistenceError },
     }
@@ -490,6 +567,13 @@ pub mod error {
             cluster_id: ClusterId,
             cluster_name: ClusterName,
         },
+        #[error("Peer <{peer_id}> of cluster '{cluster_name}' <{cluster_id}> neither belongs to nor is shared with project '{project}' anymore.")]
+        PeerOutsideProject {
+            cluster_id: ClusterId,
+            cluster_name: ClusterName,
+            peer_id: PeerId,
+            project: ProjectId,
+        },
         #[error("Error when listing cluster peer states while rolling out cluster <{cluster_id}>")]
         ListClusterPeerStates {
             cluster_id: ClusterId,
@@ -516,7 +600,7 @@ pub mod error {
 
 #[cfg(test)]
 mod test {
-    use std::collections::HashSet;
+    use std::collections::{BTreeSet, HashSet};
     use std::net::IpAddr;
     use std::str::FromStr;
     use std::time::Duration;
@@ -530,6 +614,7 @@ mod test {
     use opendut_types::cluster::ClusterName;
     use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerName, Engine}, ExecutorDescriptor, ExecutorDescriptors, ExecutorId, ExecutorKind};
     use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::project::ProjectAssignment;
     use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, Topology};
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceId, NetworkInterfaceName};
 
@@ -561,17 +646,22 @@ mod test {
                 name: ClusterName::try_from("MyAwesomeCluster").unwrap(),
                 leader: leader_id,
                 devices: HashSet::from([peer_a.device, peer_b.device]),
+                labels: Default::default(),
+                device_selector: None,
+                project: Default::default(),
             };
 
             fixture.resource_manager.resources_mut::<_, (), anyhow::Error>(async |resources| {
                 resources.store_peer_descriptor(StorePeerDescriptorParams {
                     vpn: Vpn::Disabled,
                     peer_descriptor: Clone::clone(&peer_a.descriptor),
+                    expected_revision: None,
                 }).await?;
 
                 resources.store_peer_descriptor(StorePeerDescriptorParams {
                     vpn: Vpn::Disabled,
                     peer_descriptor: Clone::clone(&peer_b.descriptor),
+                    expected_revision: None,
                 }).await?;
 
                 Ok(())
@@ -585,6 +675,7 @@ mod test {
             fixture.resource_manager.resources_mut(async |resources| {
                 resources.create_cluster_configuration(CreateClusterConfigurationParams {
                     cluster_configuration,
+                    expected_revision: None,
                 })
             }).await??;
 
@@ -677,6 +768,55 @@ mod test {
         Ok(())
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn should_undeploy_and_not_deploy_clusters_using_peers_which_are_not_shared_with_their_project_anymore(
+        mut peer_a: PeerFixture,
+    ) -> anyhow::Result<()> {
+        let fixture = Fixture::create().await;
+        let chassis = ProjectId::try_from("chassis")?;
+        peer_a.descriptor.project = ProjectAssignment {
+            owner: ProjectId::try_from("powertrain")?,
+            shared_with: BTreeSet::from([chassis.clone()]),
+        };
+        fixture.resource_manager.insert(peer_a.id, Clone::clone(&peer_a.descriptor)).await?;
+
+        let cluster_id = ClusterId::random();
+        fixture.resource_manager.insert(cluster_id, ClusterConfiguration {
+            id: cluster_id,
+            name: ClusterName::try_from("ChassisCluster")?,
+            leader: peer_a.id,
+            devices: HashSet::from([peer_a.device]),
+            labels: Default::default(),
+            device_selector: None,
+            project: chassis,
+        }).await?;
+        fixture.resource_manager.insert(cluster_id, ClusterDeployment { id: cluster_id }).await?;
+
+        peer_a.descriptor.project.shared_with.clear();
+        fixture.resource_manager.insert(peer_a.id, Clone::clone(&peer_a.descriptor)).await?;
+
+        let result = fixture.testee.lock().await.deploy_cluster(cluster_id).await;
+        assert!(matches!(result, Err(DeployClusterError::PeerOutsideProject { peer_id, .. }) if peer_id == peer_a.id));
+
+        let undeployed_clusters = fixture.resource_manager.resources_mut(async |resources| {
+            resources.delete_cluster_deployments_outside_peer_project(DeleteClusterDeploymentsOutsidePeerProjectParams {
+                peer_id: peer_a.id,
+                vpn: Vpn::Disabled,
+                peer_messaging_broker: Arc::clone(&fixture.peer_messaging_broker),
+            }).await
+        }).await??;
+        assert_that!(undeployed_clusters, elements_are![eq(&cluster_id)]);
+        assert_that!(fixture.resource_manager.get::<ClusterDeployment>(cluster_id).await?, none());
+
+        let result = fixture.testee.lock().await
+            .store_cluster_deployment(ClusterDeployment { id: cluster_id }, AuditActor::System, |_| Ok::<_, ()>(())).await;
+        assert!(matches!(result, Err(StoreClusterDeploymentError::PeerOutsideProject { peer_id, .. }) if peer_id == peer_a.id));
+        assert_that!(fixture.resource_manager.get::<ClusterDeployment>(cluster_id).await?, none());
+
+        Ok(())
+    }
+
     #[rstest]
     fn should_determine_member_interface_mapping() -> anyhow::Result<()> {
 
@@ -692,6 +832,7 @@ mod test {
                 description: None,
                 interface: network_interface.id,
                 tags: Vec::new(),
+                labels: Default::default(),
             };
             (device, network_interface)
         }
@@ -715,6 +856,8 @@ mod test {
                     devices,
                 },
                 executors: ExecutorDescriptors { executors: vec![] },
+                labels: Default::default(),
+                project: Default::default(),
             }
         }
 
@@ -817,6 +960,7 @@ mod test {
                         description: DeviceDescription::try_from("Huii").ok(),
                         interface: network_interface_id,
                         tags: vec![],
+                        labels: Default::default(),
                     }
                 ]
             },
@@ -839,6 +983,8 @@ mod test {
                     }
                 ],
             },
+            labels: Default::default(),
+            project: Default::default(),
         };
         PeerFixture {
             id,
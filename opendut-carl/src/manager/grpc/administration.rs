@@ -0,0 +1,94 @@
+use std::pin::Pin;
+
+use futures::Stream;
+use opendut_carl_api::proto::services::administration::administration_server::{Administration as AdministrationService, AdministrationServer};
+use opendut_carl_api::proto::services::administration::*;
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use crate::manager::grpc::error::LogApiErr;
+use crate::manager::snapshot;
+use crate::manager::vpn_reconciler::VpnReconcilerRef;
+use crate::resource::manager::ResourceManagerRef;
+
+/// Size of the chunks, in which a snapshot archive is streamed to the client, to stay below the maximum message size.
+const SNAPSHOT_CHUNK_SIZE: usize = 64 * 1024;
+
+pub struct AdministrationFacade {
+    resource_manager: ResourceManagerRef,
+    vpn_reconciler: VpnReconcilerRef,
+}
+
+impl AdministrationFacade {
+
+    pub fn new(resource_manager: ResourceManagerRef, vpn_reconciler: VpnReconcilerRef) -> Self {
+        AdministrationFacade {
+            resource_manager,
+            vpn_reconciler,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<AdministrationServer<Self>> {
+        tonic_web::enable(AdministrationServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl AdministrationService for AdministrationFacade {
+    type ExportSnapshotStream = Pin<Box<dyn Stream<Item = Result<ExportSnapshotResponse, Status>> + Send>>;
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn export_snapshot(&self, _: Request<ExportSnapshotRequest>) -> Result<Response<Self::ExportSnapshotStream>, Status> {
+
+        trace!("Received request to export snapshot.");
+
+        let result = snapshot::export_archive(&self.resource_manager).await
+            .log_api_err()
+            .map_err(opendut_carl_api::carl::administration::ExportSnapshotError::from);
+
+        let responses = match result {
+            Ok(archive) => archive.chunks(SNAPSHOT_CHUNK_SIZE)
+                .map(|chunk| ExportSnapshotResponse {
+                    reply: Some(export_snapshot_response::Reply::Chunk(ExportSnapshotChunk { data: chunk.to_vec() })),
+                })
+                .collect::<Vec<_>>(),
+            Err(error) => vec![
+                ExportSnapshotResponse {
+                    reply: Some(export_snapshot_response::Reply::Failure(error.into())),
+                }
+            ],
+        };
+
+        Ok(Response::new(
+            Box::pin(tokio_stream::iter(responses.into_iter().map(Ok))) as Self::ExportSnapshotStream
+        ))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn get_vpn_reconciliation_report(&self, request: Request<GetVpnReconciliationReportRequest>) -> Result<Response<GetVpnReconciliationReportResponse>, Status> {
+        let request = request.into_inner();
+
+        trace!("Received request to get VPN reconciliation report: {request:?}");
+
+        let result = if request.reconcile {
+            self.vpn_reconciler.reconcile().await
+                .map(Some)
+                .log_api_err()
+                .map_err(opendut_carl_api::carl::administration::GetVpnReconciliationReportError::from)
+        } else {
+            Ok(self.vpn_reconciler.last_report().await)
+        };
+
+        let reply = match result {
+            Ok(report) => get_vpn_reconciliation_report_response::Reply::Success(GetVpnReconciliationReportSuccess {
+                report: report.map(Into::into),
+            }),
+            Err(error) => get_vpn_reconciliation_report_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(GetVpnReconciliationReportResponse {
+            reply: Some(reply),
+        }))
+    }
+}
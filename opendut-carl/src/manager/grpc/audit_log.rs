@@ -0,0 +1,68 @@
+use opendut_carl_api::proto::services::audit_log::audit_log_server::{AuditLog as AuditLogService, AuditLogServer};
+use opendut_carl_api::proto::services::audit_log::*;
+use opendut_types::audit::AuditLogFilter;
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use crate::auth::projects::ProjectScope;
+use crate::manager::audit_log::ListAuditEntriesError;
+use crate::manager::grpc::error::LogApiErr;
+use crate::resource::manager::ResourceManagerRef;
+use crate::resource::persistence::error::MapErrToInner;
+
+pub struct AuditLogFacade {
+    resource_manager: ResourceManagerRef,
+}
+
+impl AuditLogFacade {
+
+    pub fn new(resource_manager: ResourceManagerRef) -> Self {
+        AuditLogFacade {
+            resource_manager,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<AuditLogServer<Self>> {
+        tonic_web::enable(AuditLogServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl AuditLogService for AuditLogFacade {
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_audit_entries(&self, request: Request<ListAuditEntriesRequest>) -> Result<Response<ListAuditEntriesResponse>, Status> {
+
+        let scope = ProjectScope::of(&request)?;
+        let filter = AuditLogFilter::try_from(request.into_inner())
+            .map_err(|cause| Status::invalid_argument(format!("Audit log filter is not valid: {cause}")))?;
+
+        trace!("Received request to list audit entries matching: {filter:?}");
+
+        let result =
+            self.resource_manager.resources(async |resources|
+                resources.list_audit_entries_in_scope(&filter, &scope)
+            ).await
+            .map_err_to_inner(|source| ListAuditEntriesError::Persistence {
+                source: source.context("Persistence error in transaction for listing audit entries"),
+            })
+            .log_api_err()
+            .map_err(opendut_carl_api::carl::audit::ListAuditEntriesError::from);
+
+        let reply = match result {
+            Ok(entries) => list_audit_entries_response::Reply::Success(
+                ListAuditEntriesSuccess {
+                    entries: entries.into_iter()
+                        .map(Into::into)
+                        .collect(),
+                }
+            ),
+            Err(error) => list_audit_entries_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(ListAuditEntriesResponse {
+            reply: Some(reply),
+        }))
+    }
+}
@@ -1,17 +1,26 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use opendut_carl_api::proto::services::cluster_manager::cluster_manager_server::{ClusterManager as ClusterManagerService, ClusterManagerServer};
 use opendut_carl_api::proto::services::cluster_manager::*;
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::project::ProjectId;
+use opendut_types::resources::Revision;
 use tonic::{Request, Response, Status};
 use tonic_web::CorsGrpcWeb;
 use tracing::{error, trace};
 
+use crate::auth::CurrentUser;
+use crate::auth::ownership::OwnershipScope;
+use crate::auth::projects::ProjectScope;
 use crate::manager::cluster_manager::delete_cluster_deployment::DeleteClusterDeploymentParams;
 use crate::manager::cluster_manager::{ClusterManagerRef, ClusterPeerStates, CreateClusterConfigurationError, CreateClusterConfigurationParams, DeleteClusterConfigurationError, DeleteClusterConfigurationParams, DeleteClusterDeploymentError};
 use crate::manager::grpc::error::LogApiErr;
-use crate::manager::grpc::extract;
+use crate::manager::grpc::{check_owner, extract, DeniedAsStatus};
+use crate::manager::grpc::query;
+use crate::manager::grpc::query::ClusterConfigurationListEntry;
+use crate::resource::api::resources::Resources;
 use crate::resource::manager::ResourceManagerRef;
 use crate::resource::persistence::error::MapErrToInner;
+use crate::resource::storage::ResourcesStorageApi;
 
 pub struct ClusterManagerFacade {
     cluster_manager: ClusterManagerRef,
@@ -30,6 +39,34 @@ impl ClusterManagerFacade {
     pub fn into_grpc_service(self) -> CorsGrpcWeb<ClusterManagerServer<Self>> {
         tonic_web::enable(ClusterManagerServer::new(self))
     }
+
+    /// Project of the cluster configuration with the given ID, if it exists.
+    async fn cluster_project(&self, cluster_id: ClusterId) -> Result<Option<ProjectId>, Status> {
+        let configuration = self.resource_manager.get::<ClusterConfiguration>(cluster_id).await
+            .inspect_err(|error| error!("Error while determining the project of cluster <{cluster_id}>: {error}"))
+            .map_err(|_| Status::internal("Internal error when determining the project of a cluster"))?;
+
+        Ok(configuration.map(|configuration| configuration.project))
+    }
+
+    async fn is_cluster_visible(&self, scope: &ProjectScope, cluster_id: ClusterId) -> Result<bool, Status> {
+        Ok(self.cluster_project(cluster_id).await?
+            .is_none_or(|project| scope.is_member(&project)))
+    }
+}
+
+/// Clusters may only be modified and deployed by members of their project and, unless by administrators, only by the user who created their configuration.
+/// Called within the transaction modifying the cluster. Unknown clusters are left to the respective operation to report.
+fn check_cluster_write(resources: &Resources, scope: &ProjectScope, ownership: &OwnershipScope, cluster_id: ClusterId) -> Result<(), Status> {
+    let configuration = resources.get::<ClusterConfiguration>(cluster_id)
+        .inspect_err(|error| error!("Error while determining the project of cluster <{cluster_id}>: {error}"))
+        .map_err(|_| Status::internal("Internal error when determining the project of a cluster"))?;
+
+    if let Some(configuration) = configuration {
+        scope.check_write(&configuration.project)?;
+        check_owner::<ClusterConfiguration>(resources, ownership, cluster_id)?;
+    }
+    Ok(())
 }
 
 #[tonic::async_trait]
@@ -37,29 +74,49 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn create_cluster_configuration(&self, request: Request<CreateClusterConfigurationRequest>) -> Result<Response<CreateClusterConfigurationResponse>, Status> {
 
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let ownership = OwnershipScope::of(&request)?;
         let request = request.into_inner();
         let cluster: ClusterConfiguration = extract!(request.cluster_configuration)?;
+        let expected_revision = request.expected_revision.map(Revision::from);
 
         trace!("Received request to create cluster configuration: {cluster:?}");
 
+        scope.check_write(&cluster.project)?;
+
         let result =
-            self.resource_manager.resources_mut(async |resources|
-                resources.create_cluster_configuration(CreateClusterConfigurationParams {
+            self.resource_manager.resources_mut_as(actor, async |resources| {
+                if let Err(denied) = check_cluster_write(resources, &scope, &ownership, cluster.id) {
+                    return Ok(Err(denied));
+                }
+                let cluster_id = resources.create_cluster_configuration(CreateClusterConfigurationParams {
                     cluster_configuration: cluster.clone(),
-                })
-            ).await
+                    expected_revision,
+                })?;
+                let revision = resources.revision::<ClusterConfiguration>(cluster_id)
+                    .map_err(|source| CreateClusterConfigurationError::Persistence {
+                        cluster_id,
+                        cluster_name: cluster.name.clone(),
+                        source: source.context("Failed to determine revision of stored cluster configuration"),
+                    })?
+                    .unwrap_or_default();
+                Ok(Ok((cluster_id, revision)))
+            }).await
             .map_err_to_inner(|source| CreateClusterConfigurationError::Persistence {
                 cluster_id: cluster.id,
                 cluster_name: cluster.name,
                 source: source.context("Persistence error in transaction for creating cluster configuration"),
             })
+            .denied_as_status()?
             .log_api_err()
                 .map_err(opendut_carl_api::carl::cluster::CreateClusterConfigurationError::from);
 
         let reply = match result {
-            Ok(cluster_id) => create_cluster_configuration_response::Reply::Success(
+            Ok((cluster_id, revision)) => create_cluster_configuration_response::Reply::Success(
                 CreateClusterConfigurationSuccess {
-                    cluster_id: Some(cluster_id.into())
+                    cluster_id: Some(cluster_id.into()),
+                    revision: revision.into(),
                 }
             ),
             Err(error) => create_cluster_configuration_response::Reply::Failure(error.into())
@@ -72,22 +129,32 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn delete_cluster_configuration(&self, request: Request<DeleteClusterConfigurationRequest>) -> Result<Response<DeleteClusterConfigurationResponse>, Status> {
 
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let ownership = OwnershipScope::of(&request)?;
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.cluster_id)?;
+        let expected_revision = request.expected_revision.map(Revision::from);
 
         trace!("Received request to delete cluster configuration for cluster <{cluster_id}>.");
 
         let result =
-            self.resource_manager.resources_mut(async |resources|
+            self.resource_manager.resources_mut_as(actor, async |resources| {
+                if let Err(denied) = check_cluster_write(resources, &scope, &ownership, cluster_id) {
+                    return Ok(Err(denied));
+                }
                 resources.delete_cluster_configuration(DeleteClusterConfigurationParams {
                     cluster_id,
+                    expected_revision,
                 })
-            ).await
+                .map(Ok)
+            }).await
             .map_err_to_inner(|source| DeleteClusterConfigurationError::Persistence {
                 cluster_id,
                 cluster_name: None,
                 source: source.context("Persistence error in transaction for deleting cluster configuration"),
             })
+            .denied_as_status()?
             .log_api_err()
             .map_err(opendut_carl_api::carl::cluster::DeleteClusterConfigurationError::from);
 
@@ -107,6 +174,7 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn get_cluster_configuration(&self, request: Request<GetClusterConfigurationRequest>) -> Result<Response<GetClusterConfigurationResponse>, Status> {
 
+        let scope = ProjectScope::of(&request)?;
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.id)?;
 
@@ -114,12 +182,14 @@ impl ClusterManagerService for ClusterManagerFacade {
 
         let configuration = self.cluster_manager.lock().await.get_cluster_configuration(cluster_id).await
             .log_api_err()
-            .map_err(|cause| Status::internal(cause.to_string()))?;
+            .map_err(|cause| Status::internal(cause.to_string()))?
+            .filter(|(configuration, _)| scope.is_member(&configuration.project));
 
         let result = match configuration {
-            Some(configuration) => get_cluster_configuration_response::Result::Success(
+            Some((configuration, revision)) => get_cluster_configuration_response::Result::Success(
                 GetClusterConfigurationSuccess {
-                    configuration: Some(configuration.into())
+                    configuration: Some(configuration.into()),
+                    revision: revision.into(),
                 }
             ),
             None => get_cluster_configuration_response::Result::Failure(
@@ -132,17 +202,48 @@ impl ClusterManagerService for ClusterManagerFacade {
         }))
     }
     #[tracing::instrument(skip_all, level="trace")]
-    async fn list_cluster_configurations(&self, _: Request<ListClusterConfigurationsRequest>) -> Result<Response<ListClusterConfigurationsResponse>, Status> {
+    async fn list_cluster_configurations(&self, request: Request<ListClusterConfigurationsRequest>) -> Result<Response<ListClusterConfigurationsResponse>, Status> {
         trace!("Received request to list cluster configurations.");
 
-        let configurations = self.cluster_manager.lock().await.list_cluster_configuration().await
-            .log_api_err()
-            .map_err(|cause| Status::internal(cause.to_string()))?;
+        let scope = ProjectScope::of(&request)?;
+        let query = query::list_query(request.into_inner().query)?;
+
+        let (configurations, deployments) = {
+            let cluster_manager = self.cluster_manager.lock().await;
+
+            let configurations = cluster_manager.list_cluster_configuration().await
+                .log_api_err()
+                .map_err(|cause| Status::internal(cause.to_string()))?;
+            let deployments = cluster_manager.list_cluster_deployment().await
+                .log_api_err()
+                .map_err(|cause| Status::internal(cause.to_string()))?;
+            (configurations, deployments)
+        };
+
+        let deployed_clusters = deployments.into_iter()
+            .map(|deployment| deployment.id)
+            .collect::<HashSet<_>>();
+
+        let configurations = configurations.into_iter()
+            .filter(|(configuration, _)| scope.is_member(&configuration.project))
+            .map(|(configuration, revision)| {
+                let deployed = deployed_clusters.contains(&configuration.id);
+                ClusterConfigurationListEntry { configuration, revision, deployed }
+            });
+
+        let page = query.apply(configurations)
+            .map_err(query::invalid_query)?;
+
+        let (configurations, revisions) = page.items.into_iter()
+            .map(|entry| (opendut_types::proto::cluster::ClusterConfiguration::from(entry.configuration), u64::from(entry.revision)))
+            .unzip();
 
         Ok(Response::new(ListClusterConfigurationsResponse {
             result: Some(list_cluster_configurations_response::Result::Success(
                 ListClusterConfigurationsSuccess {
-                    configurations: configurations.into_iter().map(|configuration| configuration.into()).collect::<Vec<_>>()
+                    configurations,
+                    revisions,
+                    next_page_token: page.next_page_token.map(|token| token.to_string()),
                 }
             ))
         }))
@@ -151,12 +252,18 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn store_cluster_deployment(&self, request: Request<StoreClusterDeploymentRequest>) -> Result<Response<StoreClusterDeploymentResponse>, Status> {
 
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let ownership = OwnershipScope::of(&request)?;
         let request = request.into_inner();
         let cluster_deployment: ClusterDeployment = extract!(request.cluster_deployment)?;
 
         trace!("Received request to store cluster deployment: {cluster_deployment:?}");
 
-        let result = self.cluster_manager.lock().await.store_cluster_deployment(cluster_deployment).await
+        let cluster_id = cluster_deployment.id;
+        let result = self.cluster_manager.lock().await
+            .store_cluster_deployment(cluster_deployment, actor, |resources| check_cluster_write(resources, &scope, &ownership, cluster_id)).await
+            .denied_as_status()?
             .inspect_err(|cause| error!("{cause}"))
             .map_err(opendut_carl_api::carl::cluster::StoreClusterDeploymentError::from);
 
@@ -177,20 +284,31 @@ impl ClusterManagerService for ClusterManagerFacade {
     }
     #[tracing::instrument(skip_all, level="trace")]
     async fn delete_cluster_deployment(&self, request: Request<DeleteClusterDeploymentRequest>) -> Result<Response<DeleteClusterDeploymentResponse>, Status> {
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let ownership = OwnershipScope::of(&request)?;
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.cluster_id)?;
-        let vpn = self.cluster_manager.lock().await.vpn.clone();
+        let (vpn, peer_messaging_broker) = {
+            let cluster_manager = self.cluster_manager.lock().await;
+            (cluster_manager.vpn.clone(), cluster_manager.peer_messaging_broker())
+        };
 
         trace!("Received request to delete cluster deployment for cluster <{cluster_id}>.");
 
-        let result = self.resource_manager.resources_mut(async |resources|
-            resources.delete_cluster_deployment(DeleteClusterDeploymentParams { cluster_id, vpn }).await
-        ).await
+        let result = self.resource_manager.resources_mut_as(actor, async |resources| {
+            if let Err(denied) = check_cluster_write(resources, &scope, &ownership, cluster_id) {
+                return Ok(Err(denied));
+            }
+            resources.delete_cluster_deployment(DeleteClusterDeploymentParams { cluster_id, vpn, peer_messaging_broker }).await
+                .map(Ok)
+        }).await
             .map_err_to_inner(|source| DeleteClusterDeploymentError::Persistence {
                 cluster_id,
                 cluster_name: None,
                 source: source.context("Persistence error in transaction for deleting cluster deployment"),
             })
+            .denied_as_status()?
             .log_api_err()
             .map_err(opendut_carl_api::carl::cluster::DeleteClusterDeploymentError::from);
 
@@ -211,14 +329,19 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn get_cluster_deployment(&self, request: Request<GetClusterDeploymentRequest>) -> Result<Response<GetClusterDeploymentResponse>, Status> {
 
+        let scope = ProjectScope::of(&request)?;
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.id)?;
 
         trace!("Received request to get cluster deployment for cluster <{cluster_id}>.");
 
-        let deployment = self.cluster_manager.lock().await.get_cluster_deployment(cluster_id).await
-            .log_api_err()
-            .map_err(|cause| Status::internal(cause.to_string()))?;
+        let deployment = if self.is_cluster_visible(&scope, cluster_id).await? {
+            self.cluster_manager.lock().await.get_cluster_deployment(cluster_id).await
+                .log_api_err()
+                .map_err(|cause| Status::internal(cause.to_string()))?
+        } else {
+            None
+        };
 
         match deployment {
             Some(configuration) => Ok(Response::new(GetClusterDeploymentResponse {
@@ -237,26 +360,39 @@ impl ClusterManagerService for ClusterManagerFacade {
     }
 
     #[tracing::instrument(skip_all, level="trace")]
-    async fn list_cluster_deployments(&self, _: Request<ListClusterDeploymentsRequest>) -> Result<Response<ListClusterDeploymentsResponse>, Status> {
+    async fn list_cluster_deployments(&self, request: Request<ListClusterDeploymentsRequest>) -> Result<Response<ListClusterDeploymentsResponse>, Status> {
         trace!("Received request to list cluster deployments.");
 
+        let scope = ProjectScope::of(&request)?;
+
         let deployments = self.cluster_manager.lock().await.list_cluster_deployment().await
             .log_api_err()
             .map_err(|cause| Status::internal(cause.to_string()))?;
 
+        let mut visible_deployments = Vec::with_capacity(deployments.len());
+        for deployment in deployments {
+            if self.is_cluster_visible(&scope, deployment.id).await? {
+                visible_deployments.push(deployment);
+            }
+        }
+
         Ok(Response::new(ListClusterDeploymentsResponse {
             result: Some(list_cluster_deployments_response::Result::Success(
                 ListClusterDeploymentsSuccess {
-                    deployments: deployments.into_iter().map(|deployment| deployment.into()).collect::<Vec<_>>()
+                    deployments: visible_deployments.into_iter().map(|deployment| deployment.into()).collect::<Vec<_>>()
                 }
             ))
         }))
     }
 
     async fn list_cluster_peer_states(&self, request: Request<ListClusterPeerStatesRequest>) -> Result<Response<ListClusterPeerStatesResponse>, Status> {
+        let scope = ProjectScope::of(&request)?;
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.cluster_id)?;
         trace!("Received request to list cluster peers for cluster <{cluster_id}>.");
+        if !self.is_cluster_visible(&scope, cluster_id).await? {
+            return Err(Status::not_found(format!("Cluster <{cluster_id}> not found")));
+        }
         let result: ClusterPeerStates = self.resource_manager.resources_mut(async |resources| {
             resources.list_cluster_peer_states(cluster_id).await
         }).await
@@ -31,6 +31,10 @@ mod cluster_manager {
                         cause: String::from("Error when accessing persistence"),
                     }
                 }
+                cluster_manager::CreateClusterConfigurationError::RevisionConflict { cluster_id, cluster_name, expected_revision, actual_revision } =>
+                    Self::RevisionConflict { cluster_id, cluster_name, expected_revision, actual_revision },
+                cluster_manager::CreateClusterConfigurationError::PeerOutsideProject { cluster_id, cluster_name, peer_id, project } =>
+                    Self::PeerOutsideProject { cluster_id, cluster_name, peer_id, project },
             }
         }
     }
@@ -44,6 +48,8 @@ mod cluster_manager {
                     Self::ClusterConfigurationNotFound { cluster_id },
                 cluster_manager::DeleteClusterConfigurationError::IllegalClusterState { cluster_id, cluster_name, actual_state, required_states } =>
                     Self::IllegalClusterState { cluster_id, cluster_name, actual_state, required_states },
+                cluster_manager::DeleteClusterConfigurationError::RevisionConflict { cluster_id, expected_revision, actual_revision } =>
+                    Self::RevisionConflict { cluster_id, expected_revision, actual_revision },
                 cluster_manager::DeleteClusterConfigurationError::Persistence { cluster_id, cluster_name, source: _ } => {
                     Self::Internal {
                         cluster_id,
@@ -60,6 +66,8 @@ mod cluster_manager {
             match value {
                 cluster_manager::error::StoreClusterDeploymentError::IllegalPeerState { cluster_id, cluster_name, invalid_peers } =>
                     Self::IllegalPeerState { cluster_id, cluster_name, invalid_peers },
+                cluster_manager::error::StoreClusterDeploymentError::PeerOutsideProject { cluster_id, cluster_name, peer_id, project } =>
+                    Self::PeerOutsideProject { cluster_id, cluster_name, peer_id, project },
                 cluster_manager::error::StoreClusterDeploymentError::ListClusterPeerStates { cluster_id, source: _ } => {
                     Self::Internal {
                         cluster_id,
@@ -97,14 +105,20 @@ mod cluster_manager {
                         cluster_id,
                         cluster_name: Some(cluster_name),
                         cause: String::from("Error when tearing down VPN while deleting cluster deployment"),
-                    }
+                    },
+                cluster_manager::DeleteClusterDeploymentError::UnassignCluster { cluster_id, cluster_name, source: _ } =>
+                    Self::Internal {
+                        cluster_id,
+                        cluster_name: Some(cluster_name),
+                        cause: String::from("Error when unassigning peers while deleting cluster deployment"),
+                    },
             }
         }
     }
 }
 
 mod peer_manager {
-    use opendut_carl_api::carl::peer::{DeletePeerDescriptorError, GetPeerStateError, ListPeerStatesError, StorePeerDescriptorError};
+    use opendut_carl_api::carl::peer::{DecommissionPeerError, DeletePeerDescriptorError, GetPeerStateError, ListPeerStatesError, RedeemPeerSetupError, RevokePeerSetupError, RotatePeerCredentialsError, StorePeerDescriptorError};
     use crate::manager::peer_manager;
 
     impl From<peer_manager::store_peer_descriptor::StorePeerDescriptorError> for StorePeerDescriptorError {
@@ -112,6 +126,8 @@ mod peer_manager {
             match value {
                 peer_manager::store_peer_descriptor::StorePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states } =>
                     Self::IllegalPeerState { peer_id, peer_name, actual_state, required_states },
+                peer_manager::store_peer_descriptor::StorePeerDescriptorError::RevisionConflict { peer_id, peer_name, expected_revision, actual_revision } =>
+                    Self::RevisionConflict { peer_id, peer_name, expected_revision, actual_revision },
                 peer_manager::store_peer_descriptor::StorePeerDescriptorError::Persistence { peer_id, peer_name, source: _ } =>
                     Self::Internal {
                         peer_id,
@@ -123,7 +139,13 @@ mod peer_manager {
                         peer_id,
                         peer_name,
                         cause: String::from("Error when creating peer in VPN management while storing peer descriptor"),
-                    }
+                    },
+                peer_manager::store_peer_descriptor::StorePeerDescriptorError::DeleteClusterDeployments { peer_id, peer_name, source: _ } =>
+                    Self::Internal {
+                        peer_id,
+                        peer_name,
+                        cause: String::from("Error when deleting the deployments of clusters, which the peer is not shared with anymore"),
+                    },
             }
         }
     }
@@ -137,6 +159,8 @@ mod peer_manager {
                     Self::ClusterDeploymentExists { peer_id, cluster_id },
                 peer_manager::delete_peer_descriptor::DeletePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states } =>
                     Self::IllegalPeerState { peer_id, peer_name, actual_state, required_states },
+                peer_manager::delete_peer_descriptor::DeletePeerDescriptorError::RevisionConflict { peer_id, expected_revision, actual_revision } =>
+                    Self::RevisionConflict { peer_id, expected_revision, actual_revision },
                 peer_manager::delete_peer_descriptor::DeletePeerDescriptorError::Persistence { peer_id, peer_name, source: _ } =>
                     Self::Internal {
                         peer_id,
@@ -183,4 +207,273 @@ mod peer_manager {
             }
         }
     }
+
+    impl From<peer_manager::redeem_peer_setup::RedeemPeerSetupError> for RedeemPeerSetupError {
+        fn from(value: peer_manager::redeem_peer_setup::RedeemPeerSetupError) -> Self {
+            match value {
+                peer_manager::redeem_peer_setup::RedeemPeerSetupError::SetupNotFound { setup_id } =>
+                    Self::SetupNotFound { setup_id },
+                peer_manager::redeem_peer_setup::RedeemPeerSetupError::AlreadyUsed { setup_id } =>
+                    Self::AlreadyUsed { setup_id },
+                peer_manager::redeem_peer_setup::RedeemPeerSetupError::Expired { setup_id } =>
+                    Self::Expired { setup_id },
+                peer_manager::redeem_peer_setup::RedeemPeerSetupError::Revoked { setup_id } =>
+                    Self::Revoked { setup_id },
+                peer_manager::redeem_peer_setup::RedeemPeerSetupError::Persistence { setup_id, source: _ } =>
+                    Self::Internal {
+                        setup_id,
+                        cause: String::from("Error when accessing persistence while redeeming Setup-String"),
+                    },
+                peer_manager::redeem_peer_setup::RedeemPeerSetupError::CertificateRequestMissing { setup_id } =>
+                    Self::Internal {
+                        setup_id,
+                        cause: String::from("CARL requires a request for a client certificate when redeeming the Setup-String. Update EDGAR."),
+                    },
+                peer_manager::redeem_peer_setup::RedeemPeerSetupError::ClientCertificate { setup_id, source: _ } =>
+                    Self::Internal {
+                        setup_id,
+                        cause: String::from("Error when issuing the requested client certificate"),
+                    },
+                peer_manager::redeem_peer_setup::RedeemPeerSetupError::AuthRegistration { setup_id, source: _ } =>
+                    Self::Internal {
+                        setup_id,
+                        cause: String::from("Error when replacing the OIDC client contained in the Setup-String. Generate a new Setup-String for the peer."),
+                    },
+            }
+        }
+    }
+
+    impl From<peer_manager::revoke_peer_setup::RevokePeerSetupError> for RevokePeerSetupError {
+        fn from(value: peer_manager::revoke_peer_setup::RevokePeerSetupError) -> Self {
+            match value {
+                peer_manager::revoke_peer_setup::RevokePeerSetupError::SetupNotFound { setup_id } =>
+                    Self::SetupNotFound { setup_id },
+                peer_manager::revoke_peer_setup::RevokePeerSetupError::AlreadyUsed { setup_id } =>
+                    Self::AlreadyUsed { setup_id },
+                peer_manager::revoke_peer_setup::RevokePeerSetupError::AlreadyRevoked { setup_id } =>
+                    Self::AlreadyRevoked { setup_id },
+                peer_manager::revoke_peer_setup::RevokePeerSetupError::Persistence { setup_id, source: _ } =>
+                    Self::Internal {
+                        setup_id,
+                        cause: String::from("Error when accessing persistence while revoking Setup-String"),
+                    },
+                peer_manager::revoke_peer_setup::RevokePeerSetupError::AuthRegistration { setup_id, source: _ } =>
+                    Self::Internal {
+                        setup_id,
+                        cause: String::from("Error when removing registration while revoking Setup-String"),
+                    },
+                peer_manager::revoke_peer_setup::RevokePeerSetupError::VpnClient { setup_id, source: _ } =>
+                    Self::Internal {
+                        setup_id,
+                        cause: String::from("Error when revoking VPN configuration while revoking Setup-String"),
+                    },
+            }
+        }
+    }
+
+    impl From<peer_manager::decommission_peer::DecommissionPeerError> for DecommissionPeerError {
+        fn from(value: peer_manager::decommission_peer::DecommissionPeerError) -> Self {
+            match value {
+                peer_manager::decommission_peer::DecommissionPeerError::PeerNotFound { peer_id } =>
+                    Self::PeerNotFound { peer_id },
+                peer_manager::decommission_peer::DecommissionPeerError::Persistence { peer_id, source: _ } =>
+                    Self::Internal {
+                        peer_id,
+                        cause: String::from("Error when accessing persistence while decommissioning peer"),
+                    },
+            }
+        }
+    }
+
+    impl From<peer_manager::rotate_peer_credentials::RotatePeerCredentialsError> for RotatePeerCredentialsError {
+        fn from(value: peer_manager::rotate_peer_credentials::RotatePeerCredentialsError) -> Self {
+            match value {
+                peer_manager::rotate_peer_credentials::RotatePeerCredentialsError::PeerNotFound { peer_id } =>
+                    Self::PeerNotFound { peer_id },
+                peer_manager::rotate_peer_credentials::RotatePeerCredentialsError::PeerNotConnected { peer_id } =>
+                    Self::PeerNotConnected { peer_id },
+                peer_manager::rotate_peer_credentials::RotatePeerCredentialsError::AlreadyInProgress { peer_id } =>
+                    Self::AlreadyInProgress { peer_id },
+                peer_manager::rotate_peer_credentials::RotatePeerCredentialsError::Unconfirmed { peer_id, cause: _ } =>
+                    Self::Unconfirmed { peer_id },
+                peer_manager::rotate_peer_credentials::RotatePeerCredentialsError::NotSupported { peer_id, reason } =>
+                    Self::NotSupported { peer_id, reason },
+                peer_manager::rotate_peer_credentials::RotatePeerCredentialsError::IssueClientCertificate { peer_id, cause } =>
+                    Self::Internal { peer_id, cause },
+                peer_manager::rotate_peer_credentials::RotatePeerCredentialsError::Push { peer_id, cause } =>
+                    Self::Internal {
+                        peer_id,
+                        cause: cause.to_string(),
+                    },
+                peer_manager::rotate_peer_credentials::RotatePeerCredentialsError::AuthRegistration { peer_id, source: _ } =>
+                    Self::Internal {
+                        peer_id,
+                        cause: String::from("Error when registering or deleting OIDC clients while rotating credentials"),
+                    },
+                peer_manager::rotate_peer_credentials::RotatePeerCredentialsError::Persistence { peer_id, source: _ } =>
+                    Self::Internal {
+                        peer_id,
+                        cause: String::from("Error when accessing persistence while rotating credentials"),
+                    },
+            }
+        }
+    }
+}
+
+mod audit_log {
+    use opendut_carl_api::carl::audit::ListAuditEntriesError;
+    use crate::manager::audit_log;
+
+    impl From<audit_log::ListAuditEntriesError> for ListAuditEntriesError {
+        fn from(value: audit_log::ListAuditEntriesError) -> Self {
+            match value {
+                audit_log::ListAuditEntriesError::Persistence { source: _ } =>
+                    Self::Internal {
+                        cause: String::from("Error when accessing persistence while listing audit entries"),
+                    }
+            }
+        }
+    }
+}
+
+mod notification_manager {
+    use opendut_carl_api::carl::notification::{CreateNotificationSubscriptionError, DeleteNotificationSubscriptionError, ListNotificationSubscriptionsError};
+    use crate::manager::notification_manager;
+
+    impl From<notification_manager::CreateNotificationSubscriptionError> for CreateNotificationSubscriptionError {
+        fn from(value: notification_manager::CreateNotificationSubscriptionError) -> Self {
+            match value {
+                notification_manager::CreateNotificationSubscriptionError::NoEvents { id: _, name } =>
+                    Self::NoEvents { name },
+                notification_manager::CreateNotificationSubscriptionError::IllegalSecret { id: _, name, cause } =>
+                    Self::IllegalSecret { name, cause },
+                notification_manager::CreateNotificationSubscriptionError::Persistence { id: _, name, source: _ } =>
+                    Self::Internal {
+                        name,
+                        cause: String::from("Error when accessing persistence while creating notification subscription"),
+                    },
+            }
+        }
+    }
+
+    impl From<notification_manager::ListNotificationSubscriptionsError> for ListNotificationSubscriptionsError {
+        fn from(value: notification_manager::ListNotificationSubscriptionsError) -> Self {
+            match value {
+                notification_manager::ListNotificationSubscriptionsError::Persistence { source: _ } =>
+                    Self::Internal {
+                        cause: String::from("Error when accessing persistence while listing notification subscriptions"),
+                    },
+            }
+        }
+    }
+
+    impl From<notification_manager::DeleteNotificationSubscriptionError> for DeleteNotificationSubscriptionError {
+        fn from(value: notification_manager::DeleteNotificationSubscriptionError) -> Self {
+            match value {
+                notification_manager::DeleteNotificationSubscriptionError::NotFound { id } =>
+                    Self::NotFound { id },
+                notification_manager::DeleteNotificationSubscriptionError::Persistence { id, source: _ } =>
+                    Self::Internal {
+                        id,
+                        cause: String::from("Error when accessing persistence while deleting notification subscription"),
+                    },
+            }
+        }
+    }
+}
+
+mod snapshot {
+    use opendut_carl_api::carl::administration::ExportSnapshotError;
+    use crate::manager::snapshot;
+
+    impl From<snapshot::ExportSnapshotError> for ExportSnapshotError {
+        fn from(value: snapshot::ExportSnapshotError) -> Self {
+            match value {
+                snapshot::ExportSnapshotError::Persistence { source: _ } =>
+                    Self::Internal {
+                        cause: String::from("Error when accessing persistence while exporting snapshot"),
+                    },
+                snapshot::ExportSnapshotError::Archive { source } =>
+                    Self::Internal {
+                        cause: source.to_string(),
+                    },
+            }
+        }
+    }
+}
+
+mod token_manager {
+    use opendut_carl_api::carl::token::{CreatePersonalAccessTokenError, ListPersonalAccessTokensError, RevokePersonalAccessTokenError};
+    use crate::manager::token_manager;
+
+    impl From<token_manager::CreatePersonalAccessTokenError> for CreatePersonalAccessTokenError {
+        fn from(value: token_manager::CreatePersonalAccessTokenError) -> Self {
+            match value {
+                token_manager::CreatePersonalAccessTokenError::ScopeNotPermitted { scope } =>
+                    Self::ScopeNotPermitted { scope },
+                token_manager::CreatePersonalAccessTokenError::InvalidValidity { requested, maximum } =>
+                    Self::InvalidValidity { requested, maximum },
+                token_manager::CreatePersonalAccessTokenError::Generation { source: _ } =>
+                    Self::Internal {
+                        cause: String::from("Error when generating personal access token"),
+                    },
+                token_manager::CreatePersonalAccessTokenError::Persistence { source: _ } =>
+                    Self::Internal {
+                        cause: String::from("Error when accessing persistence while creating personal access token"),
+                    },
+            }
+        }
+    }
+
+    impl From<token_manager::ListPersonalAccessTokensError> for ListPersonalAccessTokensError {
+        fn from(value: token_manager::ListPersonalAccessTokensError) -> Self {
+            match value {
+                token_manager::ListPersonalAccessTokensError::Persistence { source: _ } =>
+                    Self::Internal {
+                        cause: String::from("Error when accessing persistence while listing personal access tokens"),
+                    },
+            }
+        }
+    }
+
+    impl From<token_manager::RevokePersonalAccessTokenError> for RevokePersonalAccessTokenError {
+        fn from(value: token_manager::RevokePersonalAccessTokenError) -> Self {
+            match value {
+                token_manager::RevokePersonalAccessTokenError::NotFound { id } =>
+                    Self::NotFound { id },
+                token_manager::RevokePersonalAccessTokenError::Persistence { id, source: _ } =>
+                    Self::Internal {
+                        id,
+                        cause: String::from("Error when accessing persistence while revoking personal access token"),
+                    },
+            }
+        }
+    }
+}
+
+mod vpn_reconciler {
+    use std::error::Error;
+
+    use opendut_carl_api::carl::administration::GetVpnReconciliationReportError;
+    use crate::manager::vpn_reconciler;
+
+    impl From<vpn_reconciler::ReconcileVpnError> for GetVpnReconciliationReportError {
+        fn from(value: vpn_reconciler::ReconcileVpnError) -> Self {
+            match value {
+                vpn_reconciler::ReconcileVpnError::VpnDisabled =>
+                    Self::VpnDisabled,
+                vpn_reconciler::ReconcileVpnError::Persistence(_) =>
+                    Self::Internal {
+                        cause: String::from("Error when accessing persistence while reconciling the VPN"),
+                    },
+                vpn_reconciler::ReconcileVpnError::Vpn(source) =>
+                    Self::Internal {
+                        cause: match source.source() {
+                            Some(cause) => format!("{source}:\n  {cause}"),
+                            None => source.to_string(),
+                        },
+                    },
+            }
+        }
+    }
 }
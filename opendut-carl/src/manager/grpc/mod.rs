@@ -1,17 +1,34 @@
 use std::fmt::Display;
 
+use tonic::Status;
+use tracing::error;
+
+use crate::auth::ownership::OwnershipScope;
+use crate::resource::api::resources::Resources;
+use crate::resource::api::Resource;
+use crate::resource::persistence::persistable::Persistable;
+
+pub use administration::AdministrationFacade;
+pub use audit_log::AuditLogFacade;
 pub use cluster_manager::ClusterManagerFacade;
 pub use metadata_provider::MetadataProviderFacade;
+pub use notification_manager::NotificationManagerFacade;
 pub use peer_manager::PeerManagerFacade;
 pub use peer_messaging_broker::PeerMessagingBrokerFacade;
 pub use observer_messaging_broker::ObserverMessagingBrokerFacade;
+pub use token_manager::TokenManagerFacade;
 
+mod administration;
+mod audit_log;
 mod cluster_manager;
 mod metadata_provider;
+mod notification_manager;
 mod peer_manager;
 mod peer_messaging_broker;
 mod observer_messaging_broker;
+mod token_manager;
 mod error;
+mod query;
 
 pub trait ExtractOrInvalidArgument<A, B>
 where
@@ -44,3 +61,44 @@ macro_rules! extract {
 }
 
 pub(crate) use extract;
+
+/// Checks whether the request may modify the resource, based on who created it.
+/// Called within the transaction modifying the resource, so that it cannot change hands in between.
+fn check_owner<R>(resources: &Resources, ownership: &OwnershipScope, id: R::Id) -> Result<(), Status>
+where R: Resource + Persistable {
+    if matches!(ownership, OwnershipScope::Unrestricted) {
+        return Ok(());
+    }
+
+    let creator = resources.creator_of::<R>(id)
+        .inspect_err(|error| error!("Error while determining the creator of a resource of kind '{}': {error}", R::TABLE))
+        .map_err(|_| Status::internal("Internal error when determining the creator of a resource"))?;
+
+    ownership.check_owner(creator.as_ref())?;
+    Ok(())
+}
+
+/// For transactions, which check whether the request may modify a resource, before modifying anything.
+/// A denied request yields `Ok(Err(status))`, so that the transaction does not need to roll back.
+trait DeniedAsStatus<T, E> {
+    /// Returns the denial as gRPC status, while errors of the modification are left to be reported in the response.
+    fn denied_as_status(self) -> Result<Result<T, E>, Status>;
+}
+impl<T, E> DeniedAsStatus<T, E> for Result<Result<T, Status>, E> {
+    fn denied_as_status(self) -> Result<Result<T, E>, Status> {
+        match self {
+            Ok(Ok(value)) => Ok(Ok(value)),
+            Ok(Err(denied)) => Err(denied),
+            Err(error) => Ok(Err(error)),
+        }
+    }
+}
+
+/// Annotates the request like the authorization middleware does, if authorization is disabled.
+#[cfg(test)]
+fn unrestricted<T>(message: T) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    request.extensions_mut().insert(crate::auth::projects::ProjectScope::Unrestricted);
+    request.extensions_mut().insert(OwnershipScope::Unrestricted);
+    request
+}
@@ -0,0 +1,166 @@
+use opendut_carl_api::proto::services::notification_manager::notification_manager_server::{NotificationManager as NotificationManagerService, NotificationManagerServer};
+use opendut_carl_api::proto::services::notification_manager::*;
+use opendut_types::notification::{NotificationSubscription, NotificationSubscriptionId};
+use opendut_types::project::ProjectId;
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::{error, trace};
+
+use crate::auth::CurrentUser;
+use crate::auth::projects::ProjectScope;
+use crate::manager::grpc::error::LogApiErr;
+use crate::manager::grpc::extract;
+use crate::manager::notification_manager::{CreateNotificationSubscriptionError, DeleteNotificationSubscriptionError, ListNotificationSubscriptionsError, TargetAddressPolicy};
+use crate::resource::manager::ResourceManagerRef;
+use crate::resource::persistence::error::MapErrToInner;
+
+pub struct NotificationManagerFacade {
+    resource_manager: ResourceManagerRef,
+    target_addresses: TargetAddressPolicy,
+}
+
+impl NotificationManagerFacade {
+
+    pub fn new(resource_manager: ResourceManagerRef, target_addresses: TargetAddressPolicy) -> Self {
+        NotificationManagerFacade {
+            resource_manager,
+            target_addresses,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<NotificationManagerServer<Self>> {
+        tonic_web::enable(NotificationManagerServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl NotificationManagerService for NotificationManagerFacade {
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn create_notification_subscription(&self, request: Request<CreateNotificationSubscriptionRequest>) -> Result<Response<CreateNotificationSubscriptionResponse>, Status> {
+
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let request = request.into_inner();
+        let subscription: NotificationSubscription = extract!(request.subscription)?;
+
+        trace!("Received request to create notification subscription: {:?}", Clone::clone(&subscription).redacted());
+
+        scope.check_write(&subscription.project)?;
+
+        self.target_addresses.check_target(&subscription.target) //domains are checked again, whenever they are resolved for a delivery
+            .map_err(|cause| Status::invalid_argument(format!("Field 'subscription.target' is not valid: {cause}")))?;
+
+        let id = subscription.id;
+        let name = Clone::clone(&subscription.name);
+
+        let result =
+            self.resource_manager.resources_mut_as(actor, async |resources|
+                resources.create_notification_subscription(subscription)
+            ).await
+            .map_err_to_inner(|source| CreateNotificationSubscriptionError::Persistence {
+                id,
+                name,
+                source: source.context("Persistence error in transaction for creating notification subscription"),
+            })
+            .log_api_err()
+            .map_err(opendut_carl_api::carl::notification::CreateNotificationSubscriptionError::from);
+
+        let reply = match result {
+            Ok(id) => create_notification_subscription_response::Reply::Success(
+                CreateNotificationSubscriptionSuccess {
+                    id: Some(id.into()),
+                }
+            ),
+            Err(error) => create_notification_subscription_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(CreateNotificationSubscriptionResponse {
+            reply: Some(reply),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_notification_subscriptions(&self, request: Request<ListNotificationSubscriptionsRequest>) -> Result<Response<ListNotificationSubscriptionsResponse>, Status> {
+
+        let scope = ProjectScope::of(&request)?;
+        let request = request.into_inner();
+        let project = request.project
+            .map(ProjectId::try_from)
+            .transpose()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'project' is not valid: {cause}")))?;
+
+        trace!("Received request to list notification subscriptions.");
+
+        let result =
+            self.resource_manager.resources(async |resources|
+                resources.list_notification_subscriptions()
+            ).await
+            .map_err_to_inner(|source| ListNotificationSubscriptionsError::Persistence {
+                source: source.context("Persistence error in transaction for listing notification subscriptions"),
+            })
+            .log_api_err()
+            .map_err(opendut_carl_api::carl::notification::ListNotificationSubscriptionsError::from);
+
+        let reply = match result {
+            Ok(subscriptions) => list_notification_subscriptions_response::Reply::Success(
+                ListNotificationSubscriptionsSuccess {
+                    subscriptions: subscriptions.into_iter()
+                        .filter(|subscription| scope.is_member(&subscription.project))
+                        .filter(|subscription| project.as_ref().is_none_or(|project| subscription.project == *project))
+                        .map(|subscription| subscription.redacted().into())
+                        .collect(),
+                }
+            ),
+            Err(error) => list_notification_subscriptions_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(ListNotificationSubscriptionsResponse {
+            reply: Some(reply),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn delete_notification_subscription(&self, request: Request<DeleteNotificationSubscriptionRequest>) -> Result<Response<DeleteNotificationSubscriptionResponse>, Status> {
+
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let request = request.into_inner();
+        let id: NotificationSubscriptionId = extract!(request.id)?;
+
+        trace!("Received request to delete notification subscription <{id}>.");
+
+        let existing = self.resource_manager.get::<NotificationSubscription>(id).await
+            .inspect_err(|error| error!("Error while determining the project of notification subscription <{id}>: {error}"))
+            .map_err(|_| Status::internal("Internal error when determining the project of a notification subscription"))?;
+
+        //subscriptions of other projects are reported as not found, so their existence is not revealed
+        let result = match existing.filter(|subscription| scope.is_member(&subscription.project)) {
+            None => Err(opendut_carl_api::carl::notification::DeleteNotificationSubscriptionError::NotFound { id }),
+            Some(_) => {
+                self.resource_manager.resources_mut_as(actor, async |resources|
+                    resources.delete_notification_subscription(id)
+                ).await
+                .map_err_to_inner(|source| DeleteNotificationSubscriptionError::Persistence {
+                    id,
+                    source: source.context("Persistence error in transaction for deleting notification subscription"),
+                })
+                .log_api_err()
+                .map_err(opendut_carl_api::carl::notification::DeleteNotificationSubscriptionError::from)
+            }
+        };
+
+        let reply = match result {
+            Ok(subscription) => delete_notification_subscription_response::Reply::Success(
+                DeleteNotificationSubscriptionSuccess {
+                    subscription: Some(subscription.redacted().into()),
+                }
+            ),
+            Err(error) => delete_notification_subscription_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(DeleteNotificationSubscriptionResponse {
+            reply: Some(reply),
+        }))
+    }
+}
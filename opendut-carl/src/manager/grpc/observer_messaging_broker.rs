@@ -9,8 +9,12 @@ use tonic_web::CorsGrpcWeb;
 use tracing::trace;
 use opendut_carl_api::proto::services::observer_messaging_broker::observer_messaging_broker_server::ObserverMessagingBrokerServer;
 use opendut_carl_api::proto::services::observer_messaging_broker::observer_messaging_broker_server::{ObserverMessagingBroker as ObserverMessagingBrokerService};
-use opendut_carl_api::proto::services::observer_messaging_broker::{WaitForPeersOnlineRequest, WaitForPeersOnlineResponse};
+use opendut_carl_api::proto::services::observer_messaging_broker::{WaitForPeersOnlineRequest, WaitForPeersOnlineResponse, WatchRequest, WatchResponse};
+use opendut_carl_api::carl::observer::{WatchEvent, WatchedResource, WatchedResourceKind};
+use opendut_types::cluster::{ClusterConfiguration, ClusterId};
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use uuid::Uuid;
+use crate::auth::projects::ProjectScope;
 use crate::manager::observer_messaging_broker::ObserverMessagingBrokerRef;
 use crate::resource::manager::ResourceManagerRef;
 
@@ -51,6 +55,49 @@ impl ObserverMessagingBrokerFacade {
     }
 }
 
+/// Removes resources from a watch event, which neither belong to nor are shared with the projects in scope.
+async fn scope_watch_event(resource_manager: &ResourceManagerRef, scope: &ProjectScope, kind: WatchedResourceKind, event: WatchEvent) -> Option<WatchEvent> {
+    if scope == &ProjectScope::Unrestricted {
+        return Some(event);
+    }
+
+    match event {
+        WatchEvent::Snapshot { entries, revision } => {
+            let mut visible_entries = Vec::with_capacity(entries.len());
+            for entry in entries {
+                if is_visible(resource_manager, scope, kind, entry.id, entry.resource.as_ref()).await {
+                    visible_entries.push(entry);
+                }
+            }
+            Some(WatchEvent::Snapshot { entries: visible_entries, revision })
+        }
+        WatchEvent::Inserted { resource, revision } => {
+            is_visible(resource_manager, scope, kind, resource.id(), Some(&resource)).await
+                .then_some(WatchEvent::Inserted { resource, revision })
+        }
+        WatchEvent::Removed { resource, revision } => {
+            is_visible(resource_manager, scope, kind, resource.id(), Some(&resource)).await
+                .then_some(WatchEvent::Removed { resource, revision })
+        }
+    }
+}
+
+/// Connection states and deployments are visible, if their peer or cluster configuration is.
+async fn is_visible(resource_manager: &ResourceManagerRef, scope: &ProjectScope, kind: WatchedResourceKind, id: Uuid, resource: Option<&WatchedResource>) -> bool {
+    match resource {
+        Some(WatchedResource::PeerDescriptor(peer)) => scope.can_read(&peer.project),
+        Some(WatchedResource::ClusterConfiguration(configuration)) => scope.is_member(&configuration.project),
+        _ => match kind {
+            WatchedResourceKind::PeerDescriptor | WatchedResourceKind::PeerConnectionState => {
+                matches!(resource_manager.get::<PeerDescriptor>(PeerId::from(id)).await, Ok(Some(peer)) if scope.can_read(&peer.project))
+            }
+            WatchedResourceKind::ClusterConfiguration | WatchedResourceKind::ClusterDeployment => {
+                matches!(resource_manager.get::<ClusterConfiguration>(ClusterId(id)).await, Ok(Some(configuration)) if scope.is_member(&configuration.project))
+            }
+        }
+    }
+}
+
 #[tonic::async_trait]
 impl ObserverMessagingBrokerService for ObserverMessagingBrokerFacade {
     type WaitForPeersOnlineStream = Pin<Box<dyn Stream<Item = Result<WaitForPeersOnlineResponse, Status>> + Send>>;
@@ -74,4 +121,30 @@ impl ObserverMessagingBrokerService for ObserverMessagingBrokerFacade {
         ))
     }
 
+    type WatchStream = Pin<Box<dyn Stream<Item = Result<WatchResponse, Status>> + Send>>;
+
+    async fn watch(&self, request: Request<WatchRequest>) -> Result<Response<Self::WatchStream>, Status> {
+        let scope = ProjectScope::of(&request)?;
+        let request = request.into_inner();
+        let request = opendut_carl_api::carl::observer::WatchRequest::try_from(request)
+            .map_err(|error| Status::invalid_argument(error.to_string()))?;
+
+        trace!("Received request to watch resources of kind <{:?}>.", request.kind);
+        let kind = request.kind;
+        let rx_outbound = self.observer_messaging_broker.watch(request).await
+            .map_err(|cause| Status::internal(cause.to_string()))?;
+        let resource_manager = self.resource_manager.clone();
+        let outbound_stream = ReceiverStream::new(rx_outbound)
+            .then(move |event| {
+                let resource_manager = resource_manager.clone();
+                let scope = scope.clone();
+                async move { scope_watch_event(&resource_manager, &scope, kind, event).await }
+            })
+            .filter_map(|event| event.map(|event| Ok(WatchResponse::from(event))));
+
+        Ok(Response::new(
+            Box::pin(outbound_stream) as Self::WatchStream
+        ))
+    }
+
 }
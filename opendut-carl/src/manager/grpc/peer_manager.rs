@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
 use pem::Pem;
 
 use opendut_auth::registration::client::RegistrationClientRef;
@@ -8,51 +12,103 @@ use opendut_carl_api::proto::services::peer_manager::peer_manager_server::{PeerM
 use opendut_carl_api::proto::services::peer_manager::*;
 use opendut_types::cleo::CleoId;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::setup::{PeerSetupId, PeerSetupRecord};
+use opendut_types::peer::update::UpdateVerificationKey;
+use opendut_types::peer::state::PeerConnectionState;
+use opendut_types::resources::Revision;
 use tonic::{Request, Response, Status};
 use tonic_web::CorsGrpcWeb;
 use tracing::{error, trace};
 use url::Url;
 
-use crate::manager::grpc::extract;
+use crate::auth::client_certificate::ClientCertificateAuthorityRef;
+use crate::auth::CurrentUser;
+use crate::auth::ownership::OwnershipScope;
+use crate::auth::projects::ProjectScope;
+use crate::manager::cluster_manager::DeleteClusterDeploymentsOutsidePeerProjectParams;
+use crate::manager::grpc::{check_owner, extract, DeniedAsStatus};
+use crate::manager::grpc::query;
+use crate::manager::grpc::query::PeerListEntry;
 use crate::manager::peer_manager;
-use crate::manager::peer_manager::{DeletePeerDescriptorError, DeletePeerDescriptorParams, GenerateCleoSetupParams, GeneratePeerSetupError, GeneratePeerSetupParams, StorePeerDescriptorError, StorePeerDescriptorParams};
+use crate::manager::peer_manager::{DecommissionPeerError, DecommissionPeerParams, DeletePeerDescriptorError, DeletePeerDescriptorParams, GenerateCleoSetupParams, GeneratePeerSetupError, GeneratePeerSetupParams, InvalidatePeerSetupCredentialsParams, RedeemedPeerSetup, RedeemPeerSetupError, RedeemPeerSetupParams, ReplaceSetupCredentialsParams, RevokedPeerSetup, RevokePeerSetupError, RevokePeerSetupParams, RevokeDecommissionedPeerAccessParams, RotatePeerCredentialsParams, StorePeerDescriptorError, StorePeerDescriptorParams};
+use crate::manager::peer_messaging_broker::PeerMessagingBrokerRef;
 use crate::manager::peer_manager::get_peer_state::GetPeerStateError;
 use crate::manager::peer_manager::list_devices::ListDevicesError;
 use crate::manager::peer_manager::list_peer_states::ListPeerStatesError;
+use crate::resource::api::resources::Resources;
 use crate::resource::manager::ResourceManagerRef;
-use crate::resource::persistence::error::{MapErrToInner, PersistenceError};
+use crate::resource::persistence::error::{MapErrToInner, PersistenceError, PersistenceResult};
+use crate::resource::storage::ResourcesStorageApi;
 use crate::settings::vpn::Vpn;
 use super::error::LogApiErr;
 
 pub struct PeerManagerFacade {
     resource_manager: ResourceManagerRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
     vpn: Vpn,
     carl_url: Url,
     ca: Pem,
+    update_verification_key: Option<UpdateVerificationKey>,
     oidc_registration_client: Option<RegistrationClientRef>,
+    client_certificate_authority: Option<ClientCertificateAuthorityRef>,
+    setup_expiration: Duration,
 }
 
 impl PeerManagerFacade {
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         resource_manager: ResourceManagerRef,
+        peer_messaging_broker: PeerMessagingBrokerRef,
         vpn: Vpn,
         carl_url: Url,
         ca: Pem,
+        update_verification_key: Option<UpdateVerificationKey>,
         oidc_registration_client: Option<RegistrationClientRef>,
+        client_certificate_authority: Option<ClientCertificateAuthorityRef>,
+        setup_expiration: Duration,
     ) -> Self {
         PeerManagerFacade {
             resource_manager,
+            peer_messaging_broker,
             vpn,
             carl_url,
             ca,
+            update_verification_key,
             oidc_registration_client,
+            client_certificate_authority,
+            setup_expiration,
         }
     }
 
     pub fn into_grpc_service(self) -> CorsGrpcWeb<PeerManagerServer<Self>> {
         tonic_web::enable(PeerManagerServer::new(self))
     }
+
+    /// Peers, which belong to or are shared with one of the projects in scope.
+    async fn visible_peers(&self, scope: &ProjectScope) -> Result<HashMap<PeerId, PeerDescriptor>, Status> {
+        let peers = self.resource_manager.list::<PeerDescriptor>().await
+            .inspect_err(|error| error!("Error while determining the peers visible to the projects of a request: {error}"))
+            .map_err(|_| Status::internal("Internal error when determining visible peers"))?;
+
+        Ok(peers.into_iter()
+            .filter(|(_, peer)| scope.can_read(&peer.project))
+            .collect())
+    }
+}
+
+/// Peers may only be modified by members of the project they belong to and, unless by administrators, only by the user who created them.
+/// Called within the transaction modifying the peer. Unknown peers are left to the respective operation to report.
+fn check_peer_write(resources: &Resources, scope: &ProjectScope, ownership: &OwnershipScope, peer_id: PeerId) -> Result<(), Status> {
+    let peer = resources.get::<PeerDescriptor>(peer_id)
+        .inspect_err(|error| error!("Error while determining the project of peer <{peer_id}>: {error}"))
+        .map_err(|_| Status::internal("Internal error when determining the project of a peer"))?;
+
+    if let Some(peer) = peer {
+        scope.check_write(&peer.project.owner)?;
+        check_owner::<PeerDescriptor>(resources, ownership, peer_id)?;
+    }
+    Ok(())
 }
 
 #[tonic::async_trait]
@@ -61,30 +117,61 @@ impl PeerManagerService for PeerManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn store_peer_descriptor(&self, request: Request<StorePeerDescriptorRequest>) -> Result<Response<StorePeerDescriptorResponse>, Status> {
 
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let ownership = OwnershipScope::of(&request)?;
         let request = request.into_inner();
         let peer: PeerDescriptor = extract!(request.peer)?;
+        let expected_revision = request.expected_revision.map(Revision::from);
 
         trace!("Received request to store peer descriptor: {peer:?}");
 
+        scope.check_write(&peer.project.owner)?;
+
         let result =
-            self.resource_manager.resources_mut(async |resources| {
-                resources.store_peer_descriptor(StorePeerDescriptorParams {
+            self.resource_manager.resources_mut_as(actor, async |resources| {
+                if let Err(denied) = check_peer_write(resources, &scope, &ownership, peer.id) {
+                    return Ok(Err(denied));
+                }
+                let peer_id = resources.store_peer_descriptor(StorePeerDescriptorParams {
                     vpn: Clone::clone(&self.vpn),
                     peer_descriptor: Clone::clone(&peer),
+                    expected_revision,
+                }).await?;
+                // Clusters of projects, which the peer is not shared with anymore, must not keep using it.
+                resources.delete_cluster_deployments_outside_peer_project(DeleteClusterDeploymentsOutsidePeerProjectParams {
+                    peer_id,
+                    vpn: Clone::clone(&self.vpn),
+                    peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
                 }).await
+                .map_err(|source| StorePeerDescriptorError::DeleteClusterDeployments {
+                    peer_id,
+                    peer_name: Clone::clone(&peer.name),
+                    source,
+                })?;
+                let revision = resources.revision::<PeerDescriptor>(peer_id)
+                    .map_err(|source| StorePeerDescriptorError::Persistence {
+                        peer_id,
+                        peer_name: Clone::clone(&peer.name),
+                        source: source.context("Failed to determine revision of stored peer descriptor"),
+                    })?
+                    .unwrap_or_default();
+                Ok(Ok((peer_id, revision)))
             }).await
             .map_err_to_inner(|source| StorePeerDescriptorError::Persistence {
                 peer_id: peer.id,
                 peer_name: peer.name,
                 source: source.context("Persistence error in transaction for storing peer descriptor"),
             })
+            .denied_as_status()?
             .log_api_err()
             .map_err(opendut_carl_api::carl::peer::StorePeerDescriptorError::from);
 
         let reply = match result {
-            Ok(peer_id) => store_peer_descriptor_response::Reply::Success(
+            Ok((peer_id, revision)) => store_peer_descriptor_response::Reply::Success(
                 StorePeerDescriptorSuccess {
-                    peer_id: Some(peer_id.into())
+                    peer_id: Some(peer_id.into()),
+                    revision: revision.into(),
                 }
             ),
             Err(error) => store_peer_descriptor_response::Reply::Failure(error.into()),
@@ -98,24 +185,34 @@ impl PeerManagerService for PeerManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn delete_peer_descriptor(&self, request: Request<DeletePeerDescriptorRequest>) -> Result<Response<DeletePeerDescriptorResponse>, Status> {
 
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let ownership = OwnershipScope::of(&request)?;
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer_id)?;
+        let expected_revision = request.expected_revision.map(Revision::from);
 
         trace!("Received request to delete peer descriptor for peer <{peer_id}>.");
 
         let result =
-            self.resource_manager.resources_mut(async |resources|
+            self.resource_manager.resources_mut_as(actor, async |resources| {
+                if let Err(denied) = check_peer_write(resources, &scope, &ownership, peer_id) {
+                    return Ok(Err(denied));
+                }
                 resources.delete_peer_descriptor(DeletePeerDescriptorParams {
                     vpn: Clone::clone(&self.vpn),
                     peer: peer_id,
                     oidc_registration_client: self.oidc_registration_client.clone(),
+                    expected_revision,
                 }).await
-            ).await
+                .map(Ok)
+            }).await
             .map_err_to_inner(|source| DeletePeerDescriptorError::Persistence {
                 peer_id,
                 peer_name: None,
                 source: source.context("Persistence error in transaction for deleting peer descriptor"),
             })
+            .denied_as_status()?
             .log_api_err()
             .map_err(opendut_carl_api::carl::peer::DeletePeerDescriptorError::from);
 
@@ -136,13 +233,22 @@ impl PeerManagerService for PeerManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn get_peer_descriptor(&self, request: Request<GetPeerDescriptorRequest>) -> Result<Response<GetPeerDescriptorResponse>, Status> {
 
+        let scope = ProjectScope::of(&request)?;
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer_id)?;
 
         trace!("Received request to get peer descriptor for peer <{peer_id}>.");
 
         let result =
-            self.resource_manager.get::<PeerDescriptor>(peer_id).await
+            self.resource_manager.resources(async |resources| -> PersistenceResult<Option<(PeerDescriptor, Revision)>> {
+                let Some(descriptor) = resources.get::<PeerDescriptor>(peer_id)?
+                    .filter(|descriptor| scope.can_read(&descriptor.project)) else {
+                    return Ok(None);
+                };
+                let revision = resources.revision::<PeerDescriptor>(peer_id)?.unwrap_or_default();
+                Ok(Some((descriptor, revision)))
+            }).await
+                .and_then(|result| result)
                 .inspect_err(|error| error!("Error while getting peer descriptor from gRPC API: {error}"))
                 .map_err(|_: PersistenceError| opendut_carl_api::carl::peer::GetPeerDescriptorError::Internal {
                     peer_id,
@@ -151,9 +257,10 @@ impl PeerManagerService for PeerManagerFacade {
 
         let response = match result {
             Ok(descriptor) => match descriptor {
-                Some(descriptor) => get_peer_descriptor_response::Reply::Success(
+                Some((descriptor, revision)) => get_peer_descriptor_response::Reply::Success(
                     GetPeerDescriptorSuccess {
-                        descriptor: Some(descriptor.into())
+                        descriptor: Some(descriptor.into()),
+                        revision: revision.into(),
                     }
                 ),
                 None => get_peer_descriptor_response::Reply::Failure(
@@ -169,11 +276,27 @@ impl PeerManagerService for PeerManagerFacade {
     }
 
     #[tracing::instrument(skip_all, level="trace")]
-    async fn list_peer_descriptors(&self, _: Request<ListPeerDescriptorsRequest>) -> Result<Response<ListPeerDescriptorsResponse>, Status> {
+    async fn list_peer_descriptors(&self, request: Request<ListPeerDescriptorsRequest>) -> Result<Response<ListPeerDescriptorsResponse>, Status> {
 
         trace!("Received request to list peer descriptors.");
 
-        let result = self.resource_manager.list::<PeerDescriptor>().await
+        let scope = ProjectScope::of(&request)?;
+        let query = query::list_query(request.into_inner().query)?;
+
+        let result =
+            self.resource_manager.resources(async |resources| -> PersistenceResult<Vec<PeerListEntry>> {
+                let connection_states = resources.list::<PeerConnectionState>()?;
+                resources.list::<PeerDescriptor>()?
+                    .into_values()
+                    .filter(|descriptor| scope.can_read(&descriptor.project))
+                    .map(|descriptor| {
+                        let revision = resources.revision::<PeerDescriptor>(descriptor.id)?.unwrap_or_default();
+                        let connection = connection_states.get(&descriptor.id).cloned().unwrap_or_default();
+                        Ok(PeerListEntry { descriptor, revision, connection })
+                    })
+                    .collect()
+            }).await
+            .and_then(|result| result)
             .inspect_err(|error| error!("Error while listing peer descriptors from gRPC API: {error}"))
             .map_err(|_: PersistenceError| opendut_carl_api::carl::peer::ListPeerDescriptorsError::Internal {
                 cause: String::from("Error when accessing persistence while listing peer descriptors"),
@@ -181,12 +304,19 @@ impl PeerManagerService for PeerManagerFacade {
 
         let response = match result {
             Ok(peers) => {
-                let peers = peers.into_values()
-                    .map(From::from)
-                    .collect::<Vec<_>>();
+                let page = query.apply(peers)
+                    .map_err(query::invalid_query)?;
+
+                let (peers, revisions) = page.items.into_iter()
+                    .map(|peer| (opendut_types::proto::peer::PeerDescriptor::from(peer.descriptor), u64::from(peer.revision)))
+                    .unzip();
 
                 list_peer_descriptors_response::Reply::Success(
-                    ListPeerDescriptorsSuccess { peers }
+                    ListPeerDescriptorsSuccess {
+                        peers,
+                        revisions,
+                        next_page_token: page.next_page_token.map(|token| token.to_string()),
+                    }
                 )
             }
             Err(error) => list_peer_descriptors_response::Reply::Failure(error.into())
@@ -200,11 +330,19 @@ impl PeerManagerService for PeerManagerFacade {
     #[tracing::instrument(skip_all, level="trace")]
     async fn get_peer_state(&self, request: Request<GetPeerStateRequest>) -> Result<Response<GetPeerStateResponse>, Status> {
 
+        let scope = ProjectScope::of(&request)?;
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer_id)?;
 
         trace!("Received request to get peer state for peer <{peer_id}>.");
 
+        if !self.visible_peers(&scope).await?.contains_key(&peer_id) {
+            let error = opendut_carl_api::carl::peer::GetPeerStateError::PeerNotFound { peer_id };
+            return Ok(Response::new(GetPeerStateResponse {
+                reply: Some(get_peer_state_response::Reply::Failure(error.into())),
+            }));
+        }
+
         let result =
             self.resource_manager.resources(async |resources| {
                 resources.get_peer_state(peer_id)
@@ -231,10 +369,12 @@ impl PeerManagerService for PeerManagerFacade {
     }
 
     #[tracing::instrument(skip_all, level="trace")]
-    async fn list_peer_states(&self, _: Request<ListPeerStatesRequest>) -> Result<Response<ListPeerStatesResponse>, Status> {
+    async fn list_peer_states(&self, request: Request<ListPeerStatesRequest>) -> Result<Response<ListPeerStatesResponse>, Status> {
 
         trace!("Received request to list peer states.");
 
+        let visible_peers = self.visible_peers(&ProjectScope::of(&request)?).await?;
+
         let result =
             self.resource_manager.resources(async |resources|
                 resources.list_peer_states()
@@ -249,6 +389,7 @@ impl PeerManagerService for PeerManagerFacade {
             Ok(peer_states) => list_peer_states_response::Reply::Success(
                 ListPeerStatesSuccess {
                     peer_state_entries: peer_states.into_iter()
+                        .filter(|(peer_id, _)| visible_peers.contains_key(peer_id))
                         .map(|(peer_id, peer_state)| ListPeerStatesEntry {
                             peer_id: Some(peer_id.into()),
                             peer_state: Some(peer_state.into()),
@@ -265,10 +406,13 @@ impl PeerManagerService for PeerManagerFacade {
     }
 
     #[tracing::instrument(skip_all, level="trace")]
-    async fn list_devices(&self, _: Request<ListDevicesRequest>) -> Result<Response<ListDevicesResponse>, Status> {
+    async fn list_devices(&self, request: Request<ListDevicesRequest>) -> Result<Response<ListDevicesResponse>, Status> {
 
         trace!("Received request to list devices.");
 
+        let visible_peers = self.visible_peers(&ProjectScope::of(&request)?).await?;
+        let query = query::list_query(request.into_inner().query)?;
+
         let devices = self.resource_manager.resources(async |resources|
             resources.list_devices()
         ).await
@@ -276,38 +420,58 @@ impl PeerManagerService for PeerManagerFacade {
                 source: source.context("Persistence error in transaction for listing peer states"),
             })
             .inspect_err(|error| error!("Error while listing devices: {error}"))
-            .map_err(|_| Status::internal("Internal error when listing devices"))?;
+            .map_err(|_| Status::internal("Internal error when listing devices"))?
+            .into_iter()
+            .filter(|device| visible_peers.values().any(|peer| peer.topology.devices.iter().any(|peer_device| peer_device.id == device.id)))
+            .collect::<Vec<_>>();
+
+        let page = query.apply(devices)
+            .map_err(query::invalid_query)?;
 
-        let devices = devices.into_iter()
+        let devices = page.items.into_iter()
             .map(From::from)
             .collect();
 
-        Ok(Response::new(ListDevicesResponse { devices }))
+        Ok(Response::new(ListDevicesResponse {
+            devices,
+            next_page_token: page.next_page_token.map(|token| token.to_string()),
+        }))
     }
 
     #[tracing::instrument(skip_all, level="trace")]
     async fn generate_peer_setup(&self, request: Request<GeneratePeerSetupRequest>) -> Result<Response<GeneratePeerSetupResponse>, Status> { // TODO: Refactor error types.
         trace!("Received request to generate peer setup.");
 
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let ownership = OwnershipScope::of(&request)?;
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer)?;
         let user_id = UserId { value: request.user_id };
 
         let setup =
-            self.resource_manager.resources(async |resources|
+            self.resource_manager.resources_mut_as(actor, async |resources| {
+                if let Err(denied) = check_peer_write(resources, &scope, &ownership, peer_id) {
+                    return Ok(Err(denied));
+                }
                 resources.generate_peer_setup(GeneratePeerSetupParams {
                     peer: peer_id,
                     carl_url: Clone::clone(&self.carl_url),
                     ca: Clone::clone(&self.ca),
                     vpn: Clone::clone(&self.vpn),
+                    update_verification_key: Clone::clone(&self.update_verification_key),
                     oidc_registration_client: self.oidc_registration_client.clone(),
+                    request_client_certificate: self.client_certificate_authority.is_some(),
                     user_id,
+                    expiration: self.setup_expiration,
                 }).await
-            ).await
+                .map(Ok)
+            }).await
             .map_err_to_inner(|source| GeneratePeerSetupError::Persistence {
                 peer_id,
-                source: source.context("Persistence error in transaction for getting peer state"),
+                source: source.context("Persistence error in transaction for generating peer setup"),
             })
+            .denied_as_status()?
             .log_api_err()
             .map_err(|_| Status::internal("Peer setup could not be created"))?;
 
@@ -344,6 +508,263 @@ impl PeerManagerService for PeerManagerFacade {
 
         Ok(Response::new(GenerateCleoSetupResponse { reply: Some(response) }))
     }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_peer_setups(&self, request: Request<ListPeerSetupsRequest>) -> Result<Response<ListPeerSetupsResponse>, Status> {
+
+        trace!("Received request to list peer setups.");
+
+        let visible_peers = self.visible_peers(&ProjectScope::of(&request)?).await?;
+
+        let result =
+            self.resource_manager.list::<PeerSetupRecord>().await
+                .inspect_err(|error| error!("Error while listing peer setups from gRPC API: {error}"))
+                .map_err(|_: PersistenceError| opendut_carl_api::carl::peer::ListPeerSetupsError::Internal {
+                    cause: String::from("Error when accessing persistence while listing peer setups"),
+                });
+
+        let reply = match result {
+            Ok(setups) => {
+                let mut setups = setups.into_values()
+                    .filter(|setup| visible_peers.contains_key(&setup.peer_id))
+                    .collect::<Vec<_>>();
+                setups.sort_by_key(|setup| setup.issued_at);
+
+                list_peer_setups_response::Reply::Success(
+                    ListPeerSetupsSuccess {
+                        setups: setups.into_iter().map(From::from).collect(),
+                    }
+                )
+            }
+            Err(error) => list_peer_setups_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(ListPeerSetupsResponse {
+            reply: Some(reply),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn redeem_peer_setup(&self, request: Request<RedeemPeerSetupRequest>) -> Result<Response<RedeemPeerSetupResponse>, Status> {
+
+        let actor = CurrentUser::audit_actor_of(&request);
+        let request = request.into_inner();
+        let setup_id: PeerSetupId = extract!(request.setup_id)?;
+        let peer_id: PeerId = extract!(request.peer_id)?;
+        let certificate_signing_request = request.certificate_signing_request;
+
+        trace!("Received request to redeem Setup-String <{setup_id}> for peer <{peer_id}>.");
+
+        let result =
+            self.resource_manager.resources_mut_as(actor, async |resources|
+                resources.redeem_peer_setup(RedeemPeerSetupParams {
+                    setup_id,
+                    peer_id,
+                    certificate_signing_request,
+                    client_certificate_authority: self.client_certificate_authority.clone(),
+                })
+            ).await
+            .map_err_to_inner(|source| RedeemPeerSetupError::Persistence {
+                setup_id,
+                source: source.context("Persistence error in transaction for redeeming peer setup"),
+            });
+
+        let result = match result {
+            Ok(RedeemedPeerSetup { setup, user_id, client_certificate }) =>
+                peer_manager::replace_setup_credentials(ReplaceSetupCredentialsParams {
+                    setup_id,
+                    peer_id,
+                    user_id,
+                    oidc_registration_client: self.oidc_registration_client.clone(),
+                }).await
+                .map(|credentials| (setup, credentials, client_certificate)),
+            Err(error) => Err(error),
+        }
+            .log_api_err()
+            .map_err(opendut_carl_api::carl::peer::RedeemPeerSetupError::from);
+
+        let reply = match result {
+            Ok((setup, credentials, client_certificate)) => {
+                let (client_id, client_secret) = credentials
+                    .map(|credentials| (credentials.client_id.into(), credentials.client_secret.into()))
+                    .unzip();
+                redeem_peer_setup_response::Reply::Success(
+                    RedeemPeerSetupSuccess {
+                        setup_id: Some(setup.id.into()),
+                        client_id,
+                        client_secret,
+                        client_certificate: client_certificate.map(Into::into),
+                    }
+                )
+            }
+            Err(error) => redeem_peer_setup_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(RedeemPeerSetupResponse {
+            reply: Some(reply),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn revoke_peer_setup(&self, request: Request<RevokePeerSetupRequest>) -> Result<Response<RevokePeerSetupResponse>, Status> {
+
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let ownership = OwnershipScope::of(&request)?;
+        let request = request.into_inner();
+        let setup_id: PeerSetupId = extract!(request.setup_id)?;
+
+        trace!("Received request to revoke Setup-String <{setup_id}>.");
+
+        let result =
+            self.resource_manager.resources_mut_as(actor, async |resources| {
+                let setup = resources.get::<PeerSetupRecord>(setup_id)
+                    .inspect_err(|error| error!("Error while determining the peer of Setup-String <{setup_id}>: {error}"))
+                    .map_err(|_| Status::internal("Internal error when determining the peer of a Setup-String"));
+                let access = setup.and_then(|setup| match setup {
+                    Some(setup) => check_peer_write(resources, &scope, &ownership, setup.peer_id),
+                    None => Ok(()),
+                });
+                if let Err(denied) = access {
+                    return Ok(Err(denied));
+                }
+                resources.revoke_peer_setup(RevokePeerSetupParams { setup_id })
+                    .map(Ok)
+            }).await
+            .map_err_to_inner(|source| RevokePeerSetupError::Persistence {
+                setup_id,
+                source: source.context("Persistence error in transaction for revoking peer setup"),
+            })
+            .denied_as_status()?;
+
+        let result = match result {
+            Ok(RevokedPeerSetup { setup, invalidate_credentials: true }) =>
+                peer_manager::invalidate_peer_setup_credentials(InvalidatePeerSetupCredentialsParams {
+                    setup_id,
+                    peer_id: setup.peer_id,
+                    vpn: Clone::clone(&self.vpn),
+                    oidc_registration_client: self.oidc_registration_client.clone(),
+                }).await
+                .map(|()| setup),
+            Ok(RevokedPeerSetup { setup, invalidate_credentials: false }) => Ok(setup),
+            Err(error) => Err(error),
+        }
+            .log_api_err()
+            .map_err(opendut_carl_api::carl::peer::RevokePeerSetupError::from);
+
+        let reply = match result {
+            Ok(setup) => revoke_peer_setup_response::Reply::Success(
+                RevokePeerSetupSuccess {
+                    setup_id: Some(setup.id.into()),
+                }
+            ),
+            Err(error) => revoke_peer_setup_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(RevokePeerSetupResponse {
+            reply: Some(reply),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn decommission_peer(&self, request: Request<DecommissionPeerRequest>) -> Result<Response<DecommissionPeerResponse>, Status> {
+
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let ownership = OwnershipScope::of(&request)?;
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+
+        trace!("Received request to decommission peer <{peer_id}>.");
+
+        let result =
+            self.resource_manager.resources_mut_as(actor, async |resources| {
+                if let Err(denied) = check_peer_write(resources, &scope, &ownership, peer_id) {
+                    return Ok(Err(denied));
+                }
+                resources.decommission_peer(DecommissionPeerParams { peer: peer_id })
+                .map(Ok)
+            }).await
+            .map_err_to_inner(|source| DecommissionPeerError::Persistence {
+                peer_id,
+                source: source.context("Persistence error in transaction for decommissioning peer"),
+            })
+            .denied_as_status()?
+            .log_api_err()
+            .map_err(opendut_carl_api::carl::peer::DecommissionPeerError::from);
+
+        //only after the decommissioning was committed, as these steps cannot be rolled back
+        let result = match result {
+            Ok(decommissioned) => Ok(
+                peer_manager::revoke_decommissioned_peer_access(RevokeDecommissionedPeerAccessParams {
+                    decommissioned,
+                    vpn: Clone::clone(&self.vpn),
+                    oidc_registration_client: self.oidc_registration_client.clone(),
+                    peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+                }).await
+            ),
+            Err(error) => Err(error),
+        };
+
+        let reply = match result {
+            Ok(report) => decommission_peer_response::Reply::Success(
+                DecommissionPeerSuccess {
+                    report: Some(report.into()),
+                }
+            ),
+            Err(error) => decommission_peer_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(DecommissionPeerResponse {
+            reply: Some(reply),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn rotate_peer_credentials(&self, request: Request<RotatePeerCredentialsRequest>) -> Result<Response<RotatePeerCredentialsResponse>, Status> {
+
+        let actor = CurrentUser::audit_actor_of(&request);
+        let scope = ProjectScope::of(&request)?;
+        let ownership = OwnershipScope::of(&request)?;
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+
+        trace!("Received request to rotate credentials of peer <{peer_id}>.");
+
+        // The rotation spans several transactions, as it awaits the confirmation of the peer in between, and does not change who may modify the peer.
+        // Each of these transactions is recorded in the audit log as done by the requesting user.
+        self.resource_manager.resources(async |resources| check_peer_write(resources, &scope, &ownership, peer_id)).await
+            .inspect_err(|error| error!("Error while checking whether peer <{peer_id}> may be modified: {error}"))
+            .map_err(|_| Status::internal("Internal error when determining the project of a peer"))??;
+
+        let result =
+            peer_manager::rotate_peer_credentials(RotatePeerCredentialsParams {
+                peer: peer_id,
+                actor,
+                resource_manager: Arc::clone(&self.resource_manager),
+                oidc_registration_client: self.oidc_registration_client.clone(),
+                client_certificate_authority: self.client_certificate_authority.clone(),
+                peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+                user_id: UserId { value: request.user_id },
+            }).await
+            .log_api_err()
+            .map_err(opendut_carl_api::carl::peer::RotatePeerCredentialsError::from);
+
+        let reply = match result {
+            Ok(rotated) => rotate_peer_credentials_response::Reply::Success(
+                RotatePeerCredentialsSuccess {
+                    peer_id: Some(peer_id.into()),
+                    client_id: rotated.client_id.map(Into::into),
+                    client_certificate_id: rotated.client_certificate_id.map(Into::into),
+                }
+            ),
+            Err(error) => rotate_peer_credentials_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(RotatePeerCredentialsResponse {
+            reply: Some(reply),
+        }))
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -354,22 +775,28 @@ pub struct PeerManagerFacadeOptionsLoadError {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
     use std::str::FromStr;
     use std::sync::Arc;
 
     use googletest::prelude::*;
     use url::Url;
 
+    use crate::manager::testing::PeerFixture;
+    use crate::manager::peer_messaging_broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
     use crate::resource::manager::ResourceManager;
     use crate::settings::vpn::Vpn;
     use opendut_carl_api::proto::services;
+    use opendut_types::audit::AuditActor;
     use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerName, Engine}, ExecutorDescriptor, ExecutorDescriptors, ExecutorId, ExecutorKind};
     use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::project::{ProjectAssignment, ProjectId};
     use opendut_types::proto;
     use opendut_types::topology::Topology;
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
 
     use super::*;
+    use crate::manager::grpc::unrestricted;
 
     pub fn get_cert() -> Pem {
         match Pem::from_str(CERTIFICATE_AUTHORITY_STRING) {
@@ -380,16 +807,27 @@ mod tests {
 
     const CERTIFICATE_AUTHORITY_STRING: &str = include_str!("../../../../resources/development/tls/insecure-development-ca.pem");
 
+    async fn create_peer_messaging_broker(resource_manager: &ResourceManagerRef) -> PeerMessagingBrokerRef {
+        PeerMessagingBroker::new(Arc::clone(resource_manager), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(1),
+            credentials_rotation_timeout: Duration::from_secs(1),
+        }).await
+    }
+
     #[tokio::test]
     async fn test_successful_create_delete() -> Result<()> {
 
         let resource_manager = ResourceManager::new_in_memory();
         let testee = PeerManagerFacade::new(
             Arc::clone(&resource_manager),
+            create_peer_messaging_broker(&resource_manager).await,
             Vpn::Disabled,
             Url::parse("https://example.com:1234")?,
             get_cert(),
             None,
+            None,
+            None,
+            Duration::from_secs(60),
         );
 
         let peer_id = PeerId::random();
@@ -427,11 +865,14 @@ mod tests {
                     }
                 ],
             },
+            labels: Default::default(),
+            project: Default::default(),
         };
 
-        let create_peer_reply = testee.store_peer_descriptor(Request::new(
+        let create_peer_reply = testee.store_peer_descriptor(unrestricted(
             StorePeerDescriptorRequest {
                 peer: Some(Clone::clone(&peer_descriptor).into()),
+                expected_revision: None,
             }
         )).await?;
         verify_that!(
@@ -439,40 +880,44 @@ mod tests {
             some(matches_pattern!(
                 store_peer_descriptor_response::Reply::Success(
                     matches_pattern!(services::peer_manager::StorePeerDescriptorSuccess {
-                        peer_id: some(eq(&proto::peer::PeerId::from(Clone::clone(&peer_id))))
+                        peer_id: some(eq(&proto::peer::PeerId::from(Clone::clone(&peer_id)))),
+                        ..
                     })
                 )
             ))
         )?;
 
-        let list_reply = testee.list_peer_descriptors(Request::new(
-            ListPeerDescriptorsRequest {}
+        let list_reply = testee.list_peer_descriptors(unrestricted(
+            ListPeerDescriptorsRequest { query: None }
         )).await?;
         verify_that!(
             list_reply.get_ref().reply,
             some(matches_pattern!(list_peer_descriptors_response::Reply::Success(
                 matches_pattern!(ListPeerDescriptorsSuccess {
-                    peers: container_eq([peer_descriptor.into()])
+                    peers: container_eq([peer_descriptor.into()]),
+                    ..
                 })
             )))
         )?;
 
-        let delete_reply = testee.delete_peer_descriptor(Request::new(
+        let delete_reply = testee.delete_peer_descriptor(unrestricted(
             services::peer_manager::DeletePeerDescriptorRequest {
                 peer_id: Some(peer_id.into()),
+                expected_revision: None,
             }
         )).await?;
         verify_that!(delete_reply.get_ref().reply,
             some(matches_pattern!(delete_peer_descriptor_response::Reply::Success(anything())))
         )?;
 
-        let list_reply = testee.list_peer_descriptors(Request::new(
-            services::peer_manager::ListPeerDescriptorsRequest {}
+        let list_reply = testee.list_peer_descriptors(unrestricted(
+            services::peer_manager::ListPeerDescriptorsRequest { query: None }
         )).await?;
         verify_that!(list_reply.get_ref().reply,
             some(matches_pattern!(list_peer_descriptors_response::Reply::Success(
                 matches_pattern!(ListPeerDescriptorsSuccess {
-                    peers: empty()
+                    peers: empty(),
+                    ..
                 })
             )))
         )?;
@@ -480,21 +925,206 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_filter_sort_and_paginate_listed_peers() -> Result<()> {
+
+        let resource_manager = ResourceManager::new_in_memory();
+        let testee = PeerManagerFacade::new(
+            Arc::clone(&resource_manager),
+            create_peer_messaging_broker(&resource_manager).await,
+            Vpn::Disabled,
+            Url::parse("https://example.com:1234")?,
+            get_cert(),
+            None,
+            None,
+            None,
+            Duration::from_secs(60),
+        );
+
+        for name in ["PeerA", "PeerB", "Other"] {
+            let mut peer = PeerFixture::new().descriptor;
+            peer.name = PeerName::try_from(name)?;
+            testee.store_peer_descriptor(unrestricted(
+                StorePeerDescriptorRequest {
+                    peer: Some(peer.into()),
+                    expected_revision: None,
+                }
+            )).await?;
+        }
+
+        let list_names = async |query: opendut_types::query::ListQuery| -> Result<(Vec<String>, Option<String>)> {
+            let reply = testee.list_peer_descriptors(unrestricted(
+                ListPeerDescriptorsRequest { query: Some(query.into()) }
+            )).await?.into_inner().reply;
+            let Some(list_peer_descriptors_response::Reply::Success(success)) = reply else {
+                panic!("Expected a successful reply when listing peers.")
+            };
+            let names = success.peers.into_iter()
+                .map(|peer| PeerDescriptor::try_from(peer).map(|peer| peer.name.value()))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            Ok((names, success.next_page_token))
+        };
+
+        let mut query = opendut_types::query::ListQuery {
+            filter: Some("name=peer?".parse()?),
+            sort: opendut_types::query::SortKey::parse_list("-name")?,
+            page_size: Some(1),
+            page_token: None,
+        };
+        let (names, next_page_token) = list_names(query.clone()).await?;
+        verify_that!(names, elements_are![eq("PeerB")])?;
+        verify_that!(next_page_token, some(anything()))?;
+
+        query.page_token = next_page_token.map(|token| token.parse()).transpose()?;
+        let (names, next_page_token) = list_names(query).await?;
+        verify_that!(names, elements_are![eq("PeerA")])?;
+        verify_that!(next_page_token, none())?;
+
+        let unsupported_field = testee.list_peer_descriptors(unrestricted(
+            ListPeerDescriptorsRequest {
+                query: Some(opendut_types::query::ListQuery {
+                    filter: Some("tag=front".parse()?),
+                    ..Default::default()
+                }.into()),
+            }
+        )).await;
+        verify_that!(unsupported_field.map_err(|status| status.code()), err(eq(tonic::Code::InvalidArgument)))?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn members_should_only_see_and_modify_peers_of_their_projects() -> Result<()> {
+
+        let resource_manager = ResourceManager::new_in_memory();
+        let testee = PeerManagerFacade::new(
+            Arc::clone(&resource_manager),
+            create_peer_messaging_broker(&resource_manager).await,
+            Vpn::Disabled,
+            Url::parse("https://example.com:1234")?,
+            get_cert(),
+            None,
+            None,
+            None,
+            Duration::from_secs(60),
+        );
+
+        let chassis = ProjectId::try_from("chassis")?;
+        let powertrain = ProjectId::try_from("powertrain")?;
+
+        let mut own_peer = PeerFixture::new().descriptor;
+        own_peer.project = ProjectAssignment::owned_by(chassis.clone());
+        let mut shared_peer = PeerFixture::new().descriptor;
+        shared_peer.project = ProjectAssignment::owned_by(powertrain.clone());
+        shared_peer.project.shared_with.insert(chassis.clone());
+        let mut foreign_peer = PeerFixture::new().descriptor;
+        foreign_peer.project = ProjectAssignment::owned_by(powertrain);
+        for peer in [&own_peer, &shared_peer, &foreign_peer] {
+            resource_manager.insert(peer.id, Clone::clone(peer)).await?;
+        }
+
+        let scope = ProjectScope::Members { projects: HashSet::from([ProjectId::default(), chassis.clone()]) };
+        fn scoped<T>(request: T, scope: &ProjectScope) -> Request<T> {
+            let mut request = unrestricted(request);
+            request.extensions_mut().insert(Clone::clone(scope));
+            request
+        }
+
+        let reply = testee.list_peer_descriptors(scoped(ListPeerDescriptorsRequest { query: None }, &scope)).await?.into_inner().reply;
+        let Some(list_peer_descriptors_response::Reply::Success(success)) = reply else {
+            panic!("Expected a successful reply when listing peers.")
+        };
+        let peer_ids = success.peers.into_iter()
+            .map(|peer| PeerDescriptor::try_from(peer).map(|peer| peer.id))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        verify_that!(peer_ids, unordered_elements_are![eq(&own_peer.id), eq(&shared_peer.id)])?;
+
+        let get_reply = testee.get_peer_descriptor(scoped(GetPeerDescriptorRequest { peer_id: Some(foreign_peer.id.into()) }, &scope)).await?;
+        verify_that!(get_reply.get_ref().reply, some(matches_pattern!(get_peer_descriptor_response::Reply::Failure(anything()))))?;
+
+        let delete_shared_peer = testee.delete_peer_descriptor(scoped(DeletePeerDescriptorRequest {
+            peer_id: Some(shared_peer.id.into()),
+            expected_revision: None,
+        }, &scope)).await;
+        verify_that!(delete_shared_peer.map_err(|status| status.code()), err(eq(tonic::Code::PermissionDenied)))?;
+
+        let delete_own_peer = testee.delete_peer_descriptor(scoped(DeletePeerDescriptorRequest {
+            peer_id: Some(own_peer.id.into()),
+            expected_revision: None,
+        }, &scope)).await?;
+        verify_that!(delete_own_peer.get_ref().reply, some(matches_pattern!(delete_peer_descriptor_response::Reply::Success(anything()))))?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn editors_should_only_modify_peers_they_created() -> Result<()> {
+
+        let resource_manager = ResourceManager::new_in_memory();
+        let testee = PeerManagerFacade::new(
+            Arc::clone(&resource_manager),
+            create_peer_messaging_broker(&resource_manager).await,
+            Vpn::Disabled,
+            Url::parse("https://example.com:1234")?,
+            get_cert(),
+            None,
+            None,
+            None,
+            Duration::from_secs(60),
+        );
+
+        let peer = PeerFixture::new().descriptor;
+        resource_manager.resources_mut_as(AuditActor::User { name: String::from("alice") }, async |resources|
+            resources.insert(peer.id, Clone::clone(&peer))
+        ).await??;
+
+        fn as_editor<T>(request: T, user: &str) -> Request<T> {
+            let mut request = unrestricted(request);
+            request.extensions_mut().insert(OwnershipScope::CreatedBy { user: String::from(user) });
+            request
+        }
+
+        let update_by_other_editor = testee.store_peer_descriptor(as_editor(StorePeerDescriptorRequest {
+            peer: Some(Clone::clone(&peer).into()),
+            expected_revision: None,
+        }, "bob")).await;
+        verify_that!(update_by_other_editor.map_err(|status| status.code()), err(eq(tonic::Code::PermissionDenied)))?;
+
+        let delete_by_other_editor = testee.delete_peer_descriptor(as_editor(DeletePeerDescriptorRequest {
+            peer_id: Some(peer.id.into()),
+            expected_revision: None,
+        }, "bob")).await;
+        verify_that!(delete_by_other_editor.map_err(|status| status.code()), err(eq(tonic::Code::PermissionDenied)))?;
+
+        let delete_by_creator = testee.delete_peer_descriptor(as_editor(DeletePeerDescriptorRequest {
+            peer_id: Some(peer.id.into()),
+            expected_revision: None,
+        }, "alice")).await?;
+        verify_that!(delete_by_creator.get_ref().reply, some(matches_pattern!(delete_peer_descriptor_response::Reply::Success(anything()))))?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn register_fails_when_no_id_specified() -> Result<()> {
 
         let resource_manager = ResourceManager::new_in_memory();
         let testee = PeerManagerFacade::new(
             Arc::clone(&resource_manager),
+            create_peer_messaging_broker(&resource_manager).await,
             Vpn::Disabled,
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
             None,
+            None,
+            None,
+            Duration::from_secs(60),
         );
 
-        let create_peer_reply = testee.store_peer_descriptor(Request::new(
+        let create_peer_reply = testee.store_peer_descriptor(unrestricted(
             StorePeerDescriptorRequest {
-                peer: None
+                peer: None,
+                expected_revision: None,
             }
         )).await;
 
@@ -503,15 +1133,16 @@ mod tests {
             err(anything())
         )?;
 
-        let list_reply = testee.list_peer_descriptors(Request::new(
-            services::peer_manager::ListPeerDescriptorsRequest {}
+        let list_reply = testee.list_peer_descriptors(unrestricted(
+            services::peer_manager::ListPeerDescriptorsRequest { query: None }
         )).await?;
 
         verify_that!(
             list_reply.get_ref().reply,
             some(matches_pattern!(list_peer_descriptors_response::Reply::Success(
                 matches_pattern!(ListPeerDescriptorsSuccess {
-                    peers: empty()
+                    peers: empty(),
+                    ..
                 })
             )))
         )?;
@@ -525,15 +1156,20 @@ mod tests {
         let resource_manager = ResourceManager::new_in_memory();
         let testee = PeerManagerFacade::new(
             Arc::clone(&resource_manager),
+            create_peer_messaging_broker(&resource_manager).await,
             Vpn::Disabled,
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
             None,
+            None,
+            None,
+            Duration::from_secs(60),
         );
 
-        let delete_peer_reply = testee.delete_peer_descriptor(Request::new(
+        let delete_peer_reply = testee.delete_peer_descriptor(unrestricted(
             services::peer_manager::DeletePeerDescriptorRequest {
                 peer_id: None,
+                expected_revision: None,
             }
         )).await;
 
@@ -542,15 +1178,16 @@ mod tests {
             err(anything())
         )?;
 
-        let list_reply = testee.list_peer_descriptors(Request::new(
-            services::peer_manager::ListPeerDescriptorsRequest {}
+        let list_reply = testee.list_peer_descriptors(unrestricted(
+            services::peer_manager::ListPeerDescriptorsRequest { query: None }
         )).await?;
 
         verify_that!(
             list_reply.get_ref().reply,
             some(matches_pattern!(list_peer_descriptors_response::Reply::Success(
                 matches_pattern!(ListPeerDescriptorsSuccess {
-                    peers: empty()
+                    peers: empty(),
+                    ..
                 })
             )))
         )?;
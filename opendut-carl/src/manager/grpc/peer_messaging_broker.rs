@@ -2,9 +2,16 @@ use std::net::IpAddr;
 use std::ops::Not;
 use std::pin::Pin;
 use std::str::FromStr;
+use std::sync::Arc;
 
+use crate::auth::CurrentUser;
+use crate::manager::peer_manager::{resolve_unconfirmed_credentials_rotation, PresentedCredentials, ResolveUnconfirmedCredentialsRotationParams};
 use crate::manager::peer_messaging_broker::{OpenError, PeerMessagingBrokerRef};
+use crate::resource::manager::ResourceManagerRef;
+use crate::settings::vpn::Vpn;
+use crate::startup::tls::CertifiedPeer;
 use futures::StreamExt;
+use opendut_auth::registration::client::RegistrationClientRef;
 use opendut_carl_api::carl::broker::stream_header;
 use opendut_carl_api::carl::broker::stream_header::PeerVersion;
 use opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_broker_server::PeerMessagingBrokerServer;
@@ -21,11 +28,14 @@ use uuid::Uuid;
 
 pub struct PeerMessagingBrokerFacade {
     peer_messaging_broker: PeerMessagingBrokerRef,
+    vpn: Vpn,
+    resource_manager: ResourceManagerRef,
+    oidc_registration_client: Option<RegistrationClientRef>,
 }
 
 impl PeerMessagingBrokerFacade {
-    pub fn new(peer_messaging_broker: PeerMessagingBrokerRef) -> Self {
-        Self { peer_messaging_broker }
+    pub fn new(peer_messaging_broker: PeerMessagingBrokerRef, vpn: Vpn, resource_manager: ResourceManagerRef, oidc_registration_client: Option<RegistrationClientRef>) -> Self {
+        Self { peer_messaging_broker, vpn, resource_manager, oidc_registration_client }
     }
     pub fn into_grpc_service(self) -> CorsGrpcWeb<PeerMessagingBrokerServer<Self>> {
         tonic_web::enable(PeerMessagingBrokerServer::new(self))
@@ -58,6 +68,16 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
                 Status::invalid_argument(message)
             })?;
 
+        let vpn_endpoint_host = extra_headers.vpn_endpoint_host;
+
+        let presented = PresentedCredentials {
+            client_id: request.extensions().get::<CurrentUser>()
+                .and_then(|user| user.claims.authorized_party())
+                .map(|client_id| client_id.as_str().to_owned()),
+            client_certificate_id: request.extensions().get::<CertifiedPeer>()
+                .and_then(|peer| peer.0)
+                .map(|certificate| certificate.certificate_id),
+        };
 
         let (tx_inbound, rx_outbound) = self.peer_messaging_broker.open(peer_id, remote_host, extra_headers).await
             .map_err(|cause| {
@@ -67,9 +87,34 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
                     OpenError::SendApplyPeerConfiguration { .. } => Status::unavailable(cause.to_string()),
                     OpenError::Persistence { .. } => Status::internal(cause.to_string()),
                     OpenError::PeerNotFound(_) => Status::internal(cause.to_string()),
+                    OpenError::PeerDecommissioned { .. } => Status::permission_denied(cause.to_string()),
                 }
             })?;
 
+        //only after opening succeeded, so that a rejected connection cannot redirect the VPN traffic of the peer
+        if let (Vpn::Enabled { vpn_client }, Some(vpn_endpoint_host)) = (&self.vpn, vpn_endpoint_host) {
+            if let Err(cause) = vpn_client.update_peer_endpoint(peer_id, vpn_endpoint_host).await {
+                warn!("Failed to record VPN endpoint host <{vpn_endpoint_host}> of peer <{peer_id}>:\n  {cause}");
+            }
+        }
+
+        if let Some(rotation) = self.peer_messaging_broker.take_unconfirmed_credentials_rotation(peer_id).await {
+            let params = ResolveUnconfirmedCredentialsRotationParams {
+                peer: peer_id,
+                rotation: Clone::clone(&rotation),
+                presented,
+                resource_manager: Arc::clone(&self.resource_manager),
+                oidc_registration_client: self.oidc_registration_client.clone(),
+            };
+            let peer_messaging_broker = Arc::clone(&self.peer_messaging_broker);
+            tokio::spawn(async move {
+                if let Err(cause) = resolve_unconfirmed_credentials_rotation(params).await {
+                    error!("Failed to invalidate the unused credentials of peer <{peer_id}> after an unconfirmed rotation. Retrying on its next connect:\n  {cause}");
+                    peer_messaging_broker.remember_unconfirmed_credentials_rotation(peer_id, rotation).await;
+                }
+            });
+        }
+
         let mut inbound = request.into_inner();
         tokio::spawn(async move {
             while let Some(result) = inbound.next().await {
@@ -142,8 +187,19 @@ fn extract_extra_headers(metadata: &MetadataMap) -> Result<stream_header::ExtraH
         })
         .transpose()?;
 
+    let vpn_endpoint_host = metadata
+        .get(stream_header::VPN_ENDPOINT_HOST)
+        .map(|host| {
+            let host = host.to_str()
+                .map_err(|_| "VPN endpoint host should be a valid string")?;
+            IpAddr::from_str(host)
+                .map_err(|_| UserError::from("VPN endpoint host should be a valid IP address"))
+        })
+        .transpose()?;
+
     let extra_headers = stream_header::ExtraHeaders {
         client_version,
+        vpn_endpoint_host,
     };
 
     Ok(extra_headers)
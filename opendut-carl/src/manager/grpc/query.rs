@@ -0,0 +1,70 @@
+use opendut_types::cluster::ClusterConfiguration;
+use opendut_types::labels::Labels;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::peer::state::PeerConnectionState;
+use opendut_types::query::{ListQuery, QueryError, QueryField, Queryable};
+use opendut_types::resources::Revision;
+use tonic::Status;
+
+/// Converts the query of a list request. Lists all resources, if no query is set.
+pub fn list_query(query: Option<opendut_types::proto::query::ListQuery>) -> Result<ListQuery, Status> {
+    query
+        .map(ListQuery::try_from)
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(|cause| Status::invalid_argument(format!("Invalid query: {cause}")))
+}
+
+pub fn invalid_query(error: QueryError) -> Status {
+    Status::invalid_argument(format!("Invalid query: {error}"))
+}
+
+/// Peer with its revision and connection state, which can additionally be filtered and sorted by `state=online` or `state=offline`.
+pub struct PeerListEntry {
+    pub descriptor: PeerDescriptor,
+    pub revision: Revision,
+    pub connection: PeerConnectionState,
+}
+
+impl Queryable for PeerListEntry {
+    const KIND: &'static str = PeerDescriptor::KIND;
+    const FIELDS: &'static [QueryField] = &[QueryField::Id, QueryField::Name, QueryField::Location, QueryField::Project, QueryField::State];
+
+    fn values(&self, field: QueryField) -> Vec<String> {
+        match field {
+            QueryField::State => match self.connection {
+                PeerConnectionState::Online { .. } => vec![String::from("online")],
+                PeerConnectionState::Offline => vec![String::from("offline")],
+            },
+            _ => self.descriptor.values(field),
+        }
+    }
+
+    fn labels(&self) -> &Labels {
+        &self.descriptor.labels
+    }
+}
+
+/// Cluster configuration with its revision, which can additionally be filtered and sorted by `state=deployed` or `state=undeployed`.
+pub struct ClusterConfigurationListEntry {
+    pub configuration: ClusterConfiguration,
+    pub revision: Revision,
+    pub deployed: bool,
+}
+
+impl Queryable for ClusterConfigurationListEntry {
+    const KIND: &'static str = ClusterConfiguration::KIND;
+    const FIELDS: &'static [QueryField] = &[QueryField::Id, QueryField::Name, QueryField::Project, QueryField::State];
+
+    fn values(&self, field: QueryField) -> Vec<String> {
+        match field {
+            QueryField::State if self.deployed => vec![String::from("deployed")],
+            QueryField::State => vec![String::from("undeployed")],
+            _ => self.configuration.values(field),
+        }
+    }
+
+    fn labels(&self) -> &Labels {
+        &self.configuration.labels
+    }
+}
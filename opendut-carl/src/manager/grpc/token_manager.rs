@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use opendut_carl_api::proto::services::token_manager::token_manager_server::{TokenManager as TokenManagerService, TokenManagerServer};
+use opendut_carl_api::proto::services::token_manager::*;
+use opendut_types::audit::AuditActor;
+use opendut_types::user::token::{PersonalAccessTokenDescriptor, PersonalAccessTokenId, PersonalAccessTokenName, PersonalAccessTokenScope};
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use crate::auth::authorization::{AuthorizationPolicy, Role};
+use crate::auth::CurrentUser;
+use crate::manager::grpc::error::LogApiErr;
+use crate::manager::grpc::extract;
+use crate::manager::token_manager::{CreatePersonalAccessTokenError, CreatePersonalAccessTokenParams, ListPersonalAccessTokensError, RevokePersonalAccessTokenError};
+use crate::resource::manager::ResourceManagerRef;
+use crate::resource::persistence::error::MapErrToInner;
+
+pub struct TokenManagerFacade {
+    resource_manager: ResourceManagerRef,
+    policy: AuthorizationPolicy,
+    maximum_validity: Duration,
+}
+
+impl TokenManagerFacade {
+
+    pub fn new(resource_manager: ResourceManagerRef, policy: AuthorizationPolicy, maximum_validity: Duration) -> Self {
+        TokenManagerFacade {
+            resource_manager,
+            policy,
+            maximum_validity,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<TokenManagerServer<Self>> {
+        tonic_web::enable(TokenManagerServer::new(self))
+    }
+
+    /// Owners of personal access tokens are only known, if users authenticate.
+    fn current_user<T>(request: &Request<T>) -> Result<CurrentUser, Status> {
+        request.extensions().get::<CurrentUser>()
+            .cloned()
+            .ok_or_else(|| Status::failed_precondition("Personal access tokens require authentication to be enabled in CARL."))
+    }
+
+    /// Administrators may list and revoke the tokens of all users.
+    fn owner_filter(&self, user: &CurrentUser) -> Option<String> {
+        let is_administrator = self.policy.roles_of(user).contains(&Role::Administrator);
+        (!is_administrator).then(|| Clone::clone(&user.name))
+    }
+
+    async fn list_personal_access_tokens(&self, owner: Option<&str>) -> Result<Vec<PersonalAccessTokenDescriptor>, opendut_carl_api::carl::token::ListPersonalAccessTokensError> {
+        self.resource_manager.resources(async |resources|
+            resources.list_personal_access_tokens(owner)
+        ).await
+        .map_err_to_inner(|source| ListPersonalAccessTokensError::Persistence {
+            source: source.context("Persistence error in transaction for listing personal access tokens"),
+        })
+        .log_api_err()
+        .map_err(opendut_carl_api::carl::token::ListPersonalAccessTokensError::from)
+    }
+}
+
+#[tonic::async_trait]
+impl TokenManagerService for TokenManagerFacade {
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn create_personal_access_token(&self, request: Request<CreatePersonalAccessTokenRequest>) -> Result<Response<CreatePersonalAccessTokenResponse>, Status> {
+
+        let user = Self::current_user(&request)?;
+        let request = request.into_inner();
+        let name: PersonalAccessTokenName = extract!(request.name)?;
+        let scopes = request.scopes.into_iter()
+            .map(PersonalAccessTokenScope::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'scopes' is not valid: {cause}")))?;
+
+        trace!("Received request from user '{}' to create personal access token '{name}' with scopes: {scopes:?}", user.name);
+
+        let params = CreatePersonalAccessTokenParams {
+            name,
+            owner: Clone::clone(&user.name),
+            owner_roles: self.policy.roles_of(&user),
+            owner_role_claims: Clone::clone(&user.claims.additional_claims().roles),
+            owner_groups: Clone::clone(&user.claims.additional_claims().groups),
+            scopes,
+            validity: Duration::from_millis(request.validity_ms),
+            maximum_validity: self.maximum_validity,
+        };
+
+        let result =
+            self.resource_manager.resources_mut_as(AuditActor::User { name: user.name }, async |resources|
+                resources.create_personal_access_token(params)
+            ).await
+            .map_err_to_inner(|source| CreatePersonalAccessTokenError::Persistence {
+                source: source.context("Persistence error in transaction for creating personal access token"),
+            })
+            .log_api_err()
+            .map_err(opendut_carl_api::carl::token::CreatePersonalAccessTokenError::from);
+
+        let reply = match result {
+            Ok((descriptor, token)) => create_personal_access_token_response::Reply::Success(
+                CreatePersonalAccessTokenSuccess {
+                    descriptor: Some(descriptor.into()),
+                    token,
+                }
+            ),
+            Err(error) => create_personal_access_token_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(CreatePersonalAccessTokenResponse {
+            reply: Some(reply),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_personal_access_tokens(&self, request: Request<ListPersonalAccessTokensRequest>) -> Result<Response<ListPersonalAccessTokensResponse>, Status> {
+
+        let user = Self::current_user(&request)?;
+        let request = request.into_inner();
+
+        trace!("Received request from user '{}' to list personal access tokens.", user.name);
+
+        let result = match (request.all_users, self.owner_filter(&user)) {
+            (true, Some(_)) => Err(opendut_carl_api::carl::token::ListPersonalAccessTokensError::NotPermitted),
+            (true, None) => self.list_personal_access_tokens(None).await,
+            (false, _) => self.list_personal_access_tokens(Some(&user.name)).await,
+        };
+
+        let reply = match result {
+            Ok(tokens) => list_personal_access_tokens_response::Reply::Success(
+                ListPersonalAccessTokensSuccess {
+                    tokens: tokens.into_iter()
+                        .map(Into::into)
+                        .collect(),
+                }
+            ),
+            Err(error) => list_personal_access_tokens_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(ListPersonalAccessTokensResponse {
+            reply: Some(reply),
+        }))
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn revoke_personal_access_token(&self, request: Request<RevokePersonalAccessTokenRequest>) -> Result<Response<RevokePersonalAccessTokenResponse>, Status> {
+
+        let user = Self::current_user(&request)?;
+        let request = request.into_inner();
+        let id: PersonalAccessTokenId = extract!(request.id)?;
+        let owner = self.owner_filter(&user);
+
+        trace!("Received request from user '{}' to revoke personal access token <{id}>.", user.name);
+
+        let result =
+            self.resource_manager.resources_mut_as(AuditActor::User { name: user.name }, async |resources|
+                resources.revoke_personal_access_token(id, owner.as_deref())
+            ).await
+            .map_err_to_inner(|source| RevokePersonalAccessTokenError::Persistence {
+                id,
+                source: source.context("Persistence error in transaction for revoking personal access token"),
+            })
+            .log_api_err()
+            .map_err(opendut_carl_api::carl::token::RevokePersonalAccessTokenError::from);
+
+        let reply = match result {
+            Ok(descriptor) => revoke_personal_access_token_response::Reply::Success(
+                RevokePersonalAccessTokenSuccess {
+                    descriptor: Some(descriptor.into()),
+                }
+            ),
+            Err(error) => revoke_personal_access_token_response::Reply::Failure(error.into()),
+        };
+
+        Ok(Response::new(RevokePersonalAccessTokenResponse {
+            reply: Some(reply),
+        }))
+    }
+}
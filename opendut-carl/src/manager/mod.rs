@@ -1,8 +1,13 @@
+pub mod audit_log;
 pub mod peer_messaging_broker;
 pub mod cluster_manager;
 pub mod grpc;
+pub mod notification_manager;
 pub mod peer_manager;
 pub mod observer_messaging_broker;
+pub mod snapshot;
+pub mod token_manager;
+pub mod vpn_reconciler;
 
 #[cfg(test)]
 mod testing {
@@ -55,6 +60,7 @@ mod testing {
                             description: DeviceDescription::try_from("Huii").ok(),
                             interface: network_interface_1,
                             tags: vec![],
+                            labels: Default::default(),
                         },
                         DeviceDescriptor {
                             id: device_2,
@@ -62,12 +68,15 @@ mod testing {
                             description: DeviceDescription::try_from("Huii").ok(),
                             interface: network_interface_2,
                             tags: vec![],
+                            labels: Default::default(),
                         }
                     ]
                 },
                 executors: ExecutorDescriptors {
                     executors: vec![],
-                }
+                },
+                labels: Default::default(),
+                project: Default::default(),
             };
             PeerFixture {
                 id,
@@ -101,6 +110,9 @@ mod testing {
                 name: ClusterName::try_from(format!("Cluster-{cluster_id}"))?,
                 leader: peer_a.id,
                 devices: HashSet::from([peer_a.device_1, peer_a.device_2, peer_b.device_1]),
+                labels: Default::default(),
+                device_selector: None,
+                project: Default::default(),
             };
             resource_manager.insert(cluster_id, cluster_configuration.clone()).await?;
 
@@ -0,0 +1,253 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, warn};
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::notification::{NotificationEventKind, NotificationSubscription, NotificationTarget};
+use opendut_types::peer::state::PeerConnectionState;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+
+use crate::manager::notification_manager::mqtt::MqttPublisher;
+use crate::manager::notification_manager::webhook::WebhookSender;
+use crate::manager::notification_manager::{NotificationEvent, NotificationOptions};
+use crate::manager::peer_messaging_broker::{ExecutorReport, PeerMessagingBrokerRef};
+use crate::resource::manager::{ResourceManagerRef, SubscriptionEvent};
+use crate::resource::subscription::ReceiveError;
+
+pub type NotificationDispatcherRef = Arc<NotificationDispatcher>;
+
+/// Turns changes of clusters and peers into [`NotificationEvent`]s and delivers them to the matching subscriptions.
+pub struct NotificationDispatcher {
+    resource_manager: ResourceManagerRef,
+    webhooks: WebhookSender,
+    mqtt: MqttPublisher,
+}
+
+impl NotificationDispatcher {
+    pub async fn create(
+        resource_manager: ResourceManagerRef,
+        peer_messaging_broker: &PeerMessagingBrokerRef,
+        options: NotificationOptions,
+    ) -> anyhow::Result<NotificationDispatcherRef> {
+        let self_ref = Arc::new(Self {
+            resource_manager: Arc::clone(&resource_manager),
+            webhooks: WebhookSender::new(Clone::clone(&options))
+                .context("Failed to create HTTP client for sending notifications to webhooks")?,
+            mqtt: MqttPublisher::new(options)
+                .context("Failed to create TLS configuration for publishing notifications to MQTT brokers")?,
+        });
+
+        //subscribe before returning, so no event is missed after startup
+        let mut cluster_deployments = resource_manager.subscribe::<ClusterDeployment>().await;
+        let mut peer_connection_states = resource_manager.subscribe::<PeerConnectionState>().await;
+        let mut executor_reports = peer_messaging_broker.subscribe_executor_reports();
+
+        let dispatcher = Arc::clone(&self_ref);
+        tokio::spawn(async move {
+            loop {
+                match cluster_deployments.receive().await {
+                    Ok(event) => {
+                        if let Some(event) = dispatcher.cluster_event(event).await {
+                            dispatcher.dispatch(event).await;
+                        }
+                    }
+                    Err(ReceiveError::Broadcast(RecvError::Lagged(skipped))) => warn!("Missed {skipped} changes of cluster deployments. No notifications are sent for them."),
+                    Err(ReceiveError::Broadcast(RecvError::Closed)) => break,
+                }
+            }
+        });
+
+        let dispatcher = Arc::clone(&self_ref);
+        tokio::spawn(async move {
+            loop {
+                match peer_connection_states.receive().await {
+                    Ok(event) => {
+                        if let Some(event) = dispatcher.peer_event(event).await {
+                            dispatcher.dispatch(event).await;
+                        }
+                    }
+                    Err(ReceiveError::Broadcast(RecvError::Lagged(skipped))) => warn!("Missed {skipped} changes of peer connection states. No notifications are sent for them."),
+                    Err(ReceiveError::Broadcast(RecvError::Closed)) => break,
+                }
+            }
+        });
+
+        let dispatcher = Arc::clone(&self_ref);
+        tokio::spawn(async move {
+            loop {
+                match executor_reports.recv().await {
+                    Ok(report) => {
+                        if let Some(event) = dispatcher.executor_event(report).await {
+                            dispatcher.dispatch(event).await;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => warn!("Missed {skipped} reports of finished executors. No notifications are sent for them."),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+
+        Ok(self_ref)
+    }
+
+    async fn cluster_event(&self, event: SubscriptionEvent<ClusterDeployment>) -> Option<NotificationEvent> {
+        let (kind, cluster_id) = match event {
+            SubscriptionEvent::Inserted { id, .. } => (NotificationEventKind::ClusterDeployed, id),
+            SubscriptionEvent::Removed { id, .. } => (NotificationEventKind::ClusterUndeployed, id),
+        };
+        let cluster = self.find_cluster(cluster_id, kind).await?;
+        Some(NotificationEvent::cluster(kind, &cluster))
+    }
+
+    async fn peer_event(&self, event: SubscriptionEvent<PeerConnectionState>) -> Option<NotificationEvent> {
+        let (kind, peer_id) = match event {
+            SubscriptionEvent::Inserted { id, value: PeerConnectionState::Online { .. } } => (NotificationEventKind::PeerOnline, id),
+            SubscriptionEvent::Inserted { id, value: PeerConnectionState::Offline } => (NotificationEventKind::PeerOffline, id),
+            SubscriptionEvent::Removed { .. } => return None, //only happens when the peer itself is deleted
+        };
+        let peer = self.find_peer(peer_id, kind).await?;
+        Some(NotificationEvent::peer(kind, &peer))
+    }
+
+    async fn executor_event(&self, report: ExecutorReport) -> Option<NotificationEvent> {
+        let peer = self.find_peer(report.peer_id, NotificationEventKind::ExecutorFinished).await?;
+        Some(NotificationEvent::executor_finished(&peer, report.executor_id, report.outcome))
+    }
+
+    async fn find_cluster(&self, cluster_id: ClusterId, kind: NotificationEventKind) -> Option<ClusterConfiguration> {
+        match self.resource_manager.get::<ClusterConfiguration>(cluster_id).await {
+            Ok(Some(cluster)) => Some(cluster),
+            Ok(None) => {
+                warn!("Cluster <{cluster_id}> not found. Not sending notifications about event '{kind}'.");
+                None
+            }
+            Err(cause) => {
+                error!("Failed to load cluster <{cluster_id}>. Not sending notifications about event '{kind}':\n  {cause}");
+                None
+            }
+        }
+    }
+
+    async fn find_peer(&self, peer_id: PeerId, kind: NotificationEventKind) -> Option<PeerDescriptor> {
+        match self.resource_manager.get::<PeerDescriptor>(peer_id).await {
+            Ok(Some(peer)) => Some(peer),
+            Ok(None) => {
+                warn!("Peer <{peer_id}> not found. Not sending notifications about event '{kind}'.");
+                None
+            }
+            Err(cause) => {
+                error!("Failed to load peer <{peer_id}>. Not sending notifications about event '{kind}':\n  {cause}");
+                None
+            }
+        }
+    }
+
+    /// Delivers the event to each matching subscription in a separate task, so that slow targets do not delay the others.
+    async fn dispatch(&self, event: NotificationEvent) {
+        let subscriptions = match self.resource_manager.list::<NotificationSubscription>().await {
+            Ok(subscriptions) => subscriptions,
+            Err(cause) => {
+                error!("Failed to load notification subscriptions. Not sending notifications about event '{}':\n  {cause}", event.event);
+                return;
+            }
+        };
+
+        let subscriptions = subscriptions.into_values()
+            .filter(|subscription| event.matches(subscription))
+            .collect::<Vec<_>>();
+
+        if subscriptions.is_empty() {
+            return;
+        }
+
+        let payload = match event.to_json() {
+            Ok(payload) => payload,
+            Err(cause) => {
+                error!("Failed to serialize event '{}' <{}>. Not sending notifications:\n  {cause}", event.event, event.id);
+                return;
+            }
+        };
+
+        for subscription in subscriptions {
+            let webhooks = Clone::clone(&self.webhooks);
+            let mqtt = Clone::clone(&self.mqtt);
+            let payload = Clone::clone(&payload);
+            let kind = event.event;
+            let event_id = event.id;
+
+            tokio::spawn(async move {
+                let result = match &subscription.target {
+                    NotificationTarget::Webhook { url, secret } => {
+                        webhooks.send(url, secret, kind, &payload).await
+                            .map_err(anyhow::Error::from)
+                    }
+                    NotificationTarget::Mqtt { broker_url, topic } => {
+                        mqtt.publish(broker_url, topic, &payload).await
+                            .map_err(anyhow::Error::from)
+                    }
+                };
+
+                match result {
+                    Ok(()) => debug!("Delivered event '{kind}' <{event_id}> to notification subscription '{}' <{}>.", subscription.name, subscription.id),
+                    Err(cause) => error!("Failed to deliver event '{kind}' <{event_id}> to notification subscription '{}' <{}>:\n  {cause:#}", subscription.name, subscription.id),
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+    use reqwest::StatusCode;
+
+    use opendut_types::notification::{NotificationSecret, NotificationSubscriptionId, NotificationSubscriptionName};
+
+    use super::*;
+    use crate::manager::notification_manager::testing::{options, WebhookStandIn};
+    use crate::manager::notification_manager::webhook::EVENT_HEADER;
+    use crate::manager::peer_manager::tests::create_peer_descriptor;
+    use crate::manager::peer_messaging_broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resource::manager::ResourceManager;
+
+    #[tokio::test]
+    async fn should_notify_webhook_when_peer_comes_online() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(&resource_manager), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_millis(200),
+            credentials_rotation_timeout: Duration::from_millis(200),
+        }).await;
+        let _testee = NotificationDispatcher::create(Arc::clone(&resource_manager), &peer_messaging_broker, options()).await?;
+
+        let mut stand_in = WebhookStandIn::spawn(vec![StatusCode::OK]).await?;
+        let peer = create_peer_descriptor();
+        resource_manager.insert(peer.id, Clone::clone(&peer)).await?;
+
+        let subscription = NotificationSubscription {
+            id: NotificationSubscriptionId::random(),
+            name: NotificationSubscriptionName::try_from("peers-online")?,
+            project: Clone::clone(&peer.project.owner),
+            events: vec![NotificationEventKind::PeerOnline],
+            selector: None,
+            target: NotificationTarget::webhook(Clone::clone(&stand_in.url), NotificationSecret::from_str("0123456789abcdef")?)?,
+        };
+        resource_manager.insert(subscription.id, subscription).await?;
+
+        resource_manager.insert(peer.id, PeerConnectionState::Online { remote_host: IpAddr::V4(Ipv4Addr::LOCALHOST) }).await?;
+
+        let request = stand_in.receive().await?;
+        assert_that!(request.headers.get(EVENT_HEADER).and_then(|value| value.to_str().ok()), some(eq("peer-online")));
+        let payload: serde_json::Value = serde_json::from_slice(&request.body)?;
+        assert_that!(payload["peer_id"].as_str(), some(eq(&peer.id.to_string())));
+
+        resource_manager.insert(peer.id, PeerConnectionState::Offline).await?;
+        stand_in.expect_no_request().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+use opendut_types::labels::Labels;
+use opendut_types::notification::{NotificationEventKind, NotificationSubscription};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::{PeerDescriptor, PeerId, PeerName};
+use opendut_types::project::ProjectAssignment;
+
+use crate::manager::peer_messaging_broker::ExecutorOutcome;
+
+/// Event, which CARL sends notifications about. Serialized as JSON, it forms the payload of webhook requests and MQTT messages.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NotificationEvent {
+    /// Unique per event, so that receivers can detect duplicate deliveries.
+    pub id: Uuid,
+    pub event: NotificationEventKind,
+    /// Time of the event in RFC 3339 format.
+    pub occurred_at: String,
+    /// Labels of the cluster or peer, which the event is about.
+    pub labels: Labels,
+    /// Projects, whose subscriptions receive the event.
+    #[serde(skip)]
+    pub projects: ProjectAssignment,
+    #[serde(flatten)]
+    pub resource: NotificationResource,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "resource", rename_all = "kebab-case")]
+pub enum NotificationResource {
+    Cluster { cluster_id: ClusterId, cluster_name: ClusterName },
+    Peer { peer_id: PeerId, peer_name: PeerName },
+    Executor { peer_id: PeerId, peer_name: PeerName, executor_id: ExecutorId, outcome: ExecutorOutcome },
+}
+
+impl NotificationEvent {
+    fn new(event: NotificationEventKind, labels: Labels, projects: ProjectAssignment, resource: NotificationResource) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event,
+            occurred_at: chrono::Utc::now().to_rfc3339(),
+            labels,
+            projects,
+            resource,
+        }
+    }
+
+    pub fn cluster(event: NotificationEventKind, cluster: &ClusterConfiguration) -> Self {
+        Self::new(
+            event,
+            Clone::clone(&cluster.labels),
+            ProjectAssignment::owned_by(Clone::clone(&cluster.project)),
+            NotificationResource::Cluster { cluster_id: cluster.id, cluster_name: Clone::clone(&cluster.name) },
+        )
+    }
+
+    pub fn peer(event: NotificationEventKind, peer: &PeerDescriptor) -> Self {
+        Self::new(
+            event,
+            Clone::clone(&peer.labels),
+            Clone::clone(&peer.project),
+            NotificationResource::Peer { peer_id: peer.id, peer_name: Clone::clone(&peer.name) },
+        )
+    }
+
+    pub fn executor_finished(peer: &PeerDescriptor, executor_id: ExecutorId, outcome: ExecutorOutcome) -> Self {
+        Self::new(
+            NotificationEventKind::ExecutorFinished,
+            Clone::clone(&peer.labels),
+            Clone::clone(&peer.project),
+            NotificationResource::Executor { peer_id: peer.id, peer_name: Clone::clone(&peer.name), executor_id, outcome },
+        )
+    }
+
+    /// Whether the subscription asks for this kind of event about a resource of its project with matching labels.
+    pub fn matches(&self, subscription: &NotificationSubscription) -> bool {
+        subscription.events.contains(&self.event)
+            && self.projects.is_visible_to(&subscription.project)
+            && subscription.selector.as_ref().is_none_or(|selector| selector.matches(&self.labels))
+    }
+
+    pub fn to_json(&self) -> Result<Vec<u8>, serde_json::Error> {
+        serde_json::to_vec(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use googletest::prelude::*;
+    use url::Url;
+
+    use opendut_types::labels::LabelSelector;
+    use opendut_types::notification::{NotificationSecret, NotificationSubscriptionId, NotificationSubscriptionName, NotificationTarget};
+    use opendut_types::project::ProjectId;
+
+    use super::*;
+    use crate::manager::peer_manager::tests::create_peer_descriptor;
+
+    fn subscription(project: &str, events: Vec<NotificationEventKind>, selector: Option<&str>) -> anyhow::Result<NotificationSubscription> {
+        Ok(NotificationSubscription {
+            id: NotificationSubscriptionId::random(),
+            name: NotificationSubscriptionName::try_from("test")?,
+            project: ProjectId::try_from(project)?,
+            events,
+            selector: selector.map(LabelSelector::from_str).transpose()?,
+            target: NotificationTarget::webhook(Url::parse("http://localhost:8080")?, NotificationSecret::from_str("0123456789abcdef")?)?,
+        })
+    }
+
+    #[test]
+    fn should_match_subscriptions_by_event_project_and_labels() -> anyhow::Result<()> {
+        let mut peer = create_peer_descriptor();
+        peer.labels.insert("bench", "hil")?;
+        peer.project = ProjectAssignment::owned_by(ProjectId::try_from("chassis")?);
+        peer.project.shared_with.insert(ProjectId::try_from("powertrain")?);
+
+        let event = NotificationEvent::peer(NotificationEventKind::PeerOffline, &peer);
+
+        assert!(event.matches(&subscription("chassis", vec![NotificationEventKind::PeerOffline], None)?));
+        assert!(event.matches(&subscription("powertrain", vec![NotificationEventKind::PeerOnline, NotificationEventKind::PeerOffline], Some("bench=hil"))?));
+        assert!(!event.matches(&subscription("chassis", vec![NotificationEventKind::PeerOnline], None)?), "Expected no match for other event kinds.");
+        assert!(!event.matches(&subscription("chassis", vec![NotificationEventKind::PeerOffline], Some("bench=sil"))?), "Expected no match for other labels.");
+        assert!(!event.matches(&subscription("infotainment", vec![NotificationEventKind::PeerOffline], None)?), "Expected no match for other projects.");
+        Ok(())
+    }
+
+    #[test]
+    fn should_serialize_event_as_json_payload() -> anyhow::Result<()> {
+        let peer = create_peer_descriptor();
+        let executor_id = ExecutorId::random();
+        let event = NotificationEvent::executor_finished(&peer, executor_id, ExecutorOutcome::Exited { exit_code: 0 });
+
+        let payload: serde_json::Value = serde_json::from_slice(&event.to_json()?)?;
+
+        assert_that!(payload["event"].as_str(), some(eq("executor-finished")));
+        assert_that!(payload["resource"].as_str(), some(eq("executor")));
+        assert_that!(payload["peer_id"].as_str(), some(eq(&peer.id.to_string())));
+        assert_that!(payload["executor_id"].as_str(), some(eq(&executor_id.uuid.to_string())));
+        assert_that!(payload["outcome"]["status"].as_str(), some(eq("exited")));
+        assert_that!(payload["outcome"]["exit_code"].as_i64(), some(eq(0)));
+        assert!(payload.get("projects").is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use std::time::Duration;
+
+use tracing::{debug, info};
+
+use opendut_types::notification::{NotificationSecret, NotificationSubscription, NotificationSubscriptionId, NotificationSubscriptionName, NotificationTarget};
+
+use crate::resource::api::resources::Resources;
+use crate::resource::persistence::error::PersistenceError;
+use crate::resource::storage::ResourcesStorageApi;
+
+pub use dispatcher::{NotificationDispatcher, NotificationDispatcherRef};
+pub use event::NotificationEvent;
+pub use target_address::{TargetAddressError, TargetAddressPolicy};
+
+mod dispatcher;
+mod event;
+mod mqtt;
+mod target_address;
+mod webhook;
+#[cfg(test)]
+mod testing;
+
+#[derive(Clone, Debug)]
+pub struct NotificationOptions {
+    pub delivery_timeout: Duration,
+    pub max_retries: usize,
+    pub min_retry_delay: Duration,
+    pub max_retry_delay: Duration,
+    pub target_addresses: TargetAddressPolicy,
+}
+
+impl NotificationOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let delivery_timeout = Duration::from_millis(config.get::<u64>("notification.delivery.timeout.ms")?);
+        let max_retries = config.get::<usize>("notification.delivery.retries.max")?;
+        let min_retry_delay = Duration::from_millis(config.get::<u64>("notification.delivery.retries.delay.min.ms")?);
+        let max_retry_delay = Duration::from_millis(config.get::<u64>("notification.delivery.retries.delay.max.ms")?);
+        let target_addresses = TargetAddressPolicy::load(config)?;
+
+        Ok(NotificationOptions {
+            delivery_timeout,
+            max_retries,
+            min_retry_delay,
+            max_retry_delay,
+            target_addresses,
+        })
+    }
+
+    fn backoff(&self) -> backon::ExponentialBuilder {
+        backon::ExponentialBuilder::default()
+            .with_min_delay(self.min_retry_delay)
+            .with_max_delay(self.max_retry_delay)
+            .with_max_times(self.max_retries)
+    }
+}
+
+impl Resources<'_> {
+    #[tracing::instrument(skip_all, level="trace")]
+    pub fn create_notification_subscription(&mut self, subscription: NotificationSubscription) -> Result<NotificationSubscriptionId, CreateNotificationSubscriptionError> {
+        let id = subscription.id;
+        let name = Clone::clone(&subscription.name);
+
+        if subscription.events.is_empty() {
+            return Err(CreateNotificationSubscriptionError::NoEvents { id, name });
+        }
+        if let NotificationTarget::Webhook { secret, .. } = &subscription.target {
+            NotificationSecret::try_from(Clone::clone(&secret.0))
+                .map_err(|cause| CreateNotificationSubscriptionError::IllegalSecret { id, name: Clone::clone(&name), cause: cause.to_string() })?;
+        }
+
+        debug!("Creating notification subscription '{name}' <{id}> for project '{}'.", subscription.project);
+        self.insert(id, subscription)
+            .map_err(|source| CreateNotificationSubscriptionError::Persistence { id, name: Clone::clone(&name), source })?;
+
+        info!("Successfully created notification subscription '{name}' <{id}>.");
+        Ok(id)
+    }
+
+    /// Lists all notification subscriptions, ordered by their name.
+    #[tracing::instrument(skip_all, level="trace")]
+    pub fn list_notification_subscriptions(&self) -> Result<Vec<NotificationSubscription>, ListNotificationSubscriptionsError> {
+        let mut subscriptions = self.list::<NotificationSubscription>()
+            .map_err(|source| ListNotificationSubscriptionsError::Persistence { source })?
+            .into_values()
+            .collect::<Vec<_>>();
+
+        subscriptions.sort_by(|left, right| left.name.to_string().cmp(&right.name.to_string()));
+
+        Ok(subscriptions)
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    pub fn delete_notification_subscription(&mut self, id: NotificationSubscriptionId) -> Result<NotificationSubscription, DeleteNotificationSubscriptionError> {
+        let subscription = self.remove::<NotificationSubscription>(id)
+            .map_err(|source| DeleteNotificationSubscriptionError::Persistence { id, source })?
+            .ok_or(DeleteNotificationSubscriptionError::NotFound { id })?;
+
+        info!("Successfully deleted notification subscription '{}' <{id}>.", subscription.name);
+        Ok(subscription)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CreateNotificationSubscriptionError {
+    #[error("Notification subscription '{name}' <{id}> does not subscribe to any events")]
+    NoEvents {
+        id: NotificationSubscriptionId,
+        name: NotificationSubscriptionName,
+    },
+    #[error("Notification subscription '{name}' <{id}> has an illegal webhook secret: {cause}")]
+    IllegalSecret {
+        id: NotificationSubscriptionId,
+        name: NotificationSubscriptionName,
+        cause: String,
+    },
+    #[error("Error when accessing persistence while creating notification subscription '{name}' <{id}>")]
+    Persistence {
+        id: NotificationSubscriptionId,
+        name: NotificationSubscriptionName,
+        #[source] source: PersistenceError,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListNotificationSubscriptionsError {
+    #[error("Error when accessing persistence while listing notification subscriptions")]
+    Persistence {
+        #[source] source: PersistenceError,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteNotificationSubscriptionError {
+    #[error("Notification subscription <{id}> not found")]
+    NotFound { id: NotificationSubscriptionId },
+    #[error("Error when accessing persistence while deleting notification subscription <{id}>")]
+    Persistence {
+        id: NotificationSubscriptionId,
+        #[source] source: PersistenceError,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use url::Url;
+
+    use opendut_types::notification::NotificationEventKind;
+    use opendut_types::project::ProjectId;
+
+    use crate::resource::manager::ResourceManager;
+
+    use super::*;
+
+    fn subscription(name: &str, events: Vec<NotificationEventKind>, secret: &str) -> NotificationSubscription {
+        NotificationSubscription {
+            id: NotificationSubscriptionId::random(),
+            name: NotificationSubscriptionName::try_from(name).unwrap(),
+            project: ProjectId::default(),
+            events,
+            selector: None,
+            target: NotificationTarget::Webhook {
+                url: Url::parse("https://ci.example.com/hooks/opendut").unwrap(),
+                secret: NotificationSecret(String::from(secret)),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn should_create_list_and_delete_notification_subscriptions() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let offline = subscription("peers-offline", vec![NotificationEventKind::PeerOffline], "0123456789abcdef");
+        let deployed = subscription("clusters-deployed", vec![NotificationEventKind::ClusterDeployed], "0123456789abcdef");
+
+        for subscription in [Clone::clone(&offline), Clone::clone(&deployed)] {
+            resource_manager.resources_mut(async |resources| resources.create_notification_subscription(subscription)).await??;
+        }
+
+        let subscriptions = resource_manager.resources(async |resources| resources.list_notification_subscriptions()).await??;
+        assert_that!(subscriptions, elements_are![eq(&deployed), eq(&offline)]);
+
+        let deleted = resource_manager.resources_mut(async |resources| resources.delete_notification_subscription(offline.id)).await??;
+        assert_that!(deleted, eq(&offline));
+
+        let result = resource_manager.resources_mut(async |resources| resources.delete_notification_subscription(offline.id)).await?;
+        assert_that!(result, err(matches_pattern!(DeleteNotificationSubscriptionError::NotFound { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_subscriptions_without_events_or_with_short_secret() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+
+        let result = resource_manager.resources_mut(async |resources|
+            resources.create_notification_subscription(subscription("nothing", vec![], "0123456789abcdef"))
+        ).await?;
+        assert_that!(result, err(matches_pattern!(CreateNotificationSubscriptionError::NoEvents { .. })));
+
+        let result = resource_manager.resources_mut(async |resources|
+            resources.create_notification_subscription(subscription("weak", vec![NotificationEventKind::PeerOnline], "secret"))
+        ).await?;
+        assert_that!(result, err(matches_pattern!(CreateNotificationSubscriptionError::IllegalSecret { .. })));
+
+        let subscriptions = resource_manager.list::<NotificationSubscription>().await?;
+        assert_that!(subscriptions, empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use backon::Retryable;
+use bytes::BytesMut;
+use percent_encoding::percent_decode_str;
+use rumqttc::mqttbytes::v4::{read, ConnAck, Connect, ConnectReturnCode, Disconnect, Packet, Publish};
+use rumqttc::mqttbytes::QoS;
+use rustls::pki_types::ServerName;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use tracing::{debug, warn};
+use url::{Host, Url};
+use uuid::Uuid;
+
+use opendut_types::notification::redact_userinfo;
+
+use crate::manager::notification_manager::{NotificationOptions, TargetAddressError};
+
+const DEFAULT_PORT: u16 = 1883;
+const DEFAULT_TLS_PORT: u16 = 8883;
+const KEEP_ALIVE_SECONDS: u16 = 60;
+const MAX_PACKET_SIZE: usize = 1024;
+
+/// Publishes events to MQTT brokers.
+///
+/// Publishes a single message with QoS 0 per connection, which avoids keeping connections to the brokers of all subscriptions open.
+/// Brokers with an `mqtts` URL are connected to via TLS, verifying their certificate against the root certificates of the system.
+#[derive(Clone)]
+pub struct MqttPublisher {
+    options: NotificationOptions,
+    tls_connector: TlsConnector,
+}
+
+impl MqttPublisher {
+    pub fn new(options: NotificationOptions) -> Result<Self, MqttError> {
+        let native_certs = rustls_native_certs::load_native_certs();
+        for cause in &native_certs.errors {
+            warn!("Failed to load root certificate for connecting to MQTT brokers: {cause}");
+        }
+        let mut root_certificates = rustls::RootCertStore::empty();
+        root_certificates.add_parsable_certificates(native_certs.certs);
+
+        let tls_config = rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|cause| MqttError::Tls { cause })?
+            .with_root_certificates(root_certificates)
+            .with_no_client_auth();
+
+        Ok(Self {
+            options,
+            tls_connector: TlsConnector::from(Arc::new(tls_config)),
+        })
+    }
+
+    /// Retries with exponential backoff, unless the broker refuses the connection.
+    pub async fn publish(&self, broker_url: &Url, topic: &str, payload: &[u8]) -> Result<(), MqttError> {
+        (|| async {
+            tokio::time::timeout(self.options.delivery_timeout, self.publish_once(broker_url, topic, payload)).await
+                .map_err(|_| MqttError::Timeout { url: redact_userinfo(broker_url) })?
+        })
+            .retry(self.options.backoff())
+            .when(MqttError::is_transient)
+            .notify(|cause, delay| debug!("Publishing notification to MQTT broker failed. Retrying in {} ms:\n  {cause}", delay.as_millis()))
+            .await
+    }
+
+    async fn publish_once(&self, broker_url: &Url, topic: &str, payload: &[u8]) -> Result<(), MqttError> {
+        let io_error = |cause| MqttError::Io { url: redact_userinfo(broker_url), cause };
+
+        let is_tls = broker_url.scheme() == "mqtts";
+        let default_port = if is_tls { DEFAULT_TLS_PORT } else { DEFAULT_PORT };
+
+        let addresses = self.options.target_addresses.resolve(broker_url, default_port).await
+            .map_err(|cause| MqttError::Target { url: redact_userinfo(broker_url), cause })?;
+
+        let stream = TcpStream::connect(addresses.as_slice()).await.map_err(io_error)?;
+
+        if is_tls {
+            let server_name = match broker_url.host() {
+                Some(Host::Domain(domain)) => ServerName::try_from(domain.to_owned())
+                    .map_err(|cause| MqttError::Protocol { url: redact_userinfo(broker_url), cause: cause.to_string() })?,
+                Some(Host::Ipv4(address)) => ServerName::from(IpAddr::V4(address)),
+                Some(Host::Ipv6(address)) => ServerName::from(IpAddr::V6(address)),
+                None => unreachable!("Resolving the broker URL succeeded, so it has a host."),
+            };
+            let stream = self.tls_connector.connect(server_name, stream).await.map_err(io_error)?;
+            exchange(stream, broker_url, topic, payload).await
+        } else {
+            exchange(stream, broker_url, topic, payload).await
+        }
+    }
+}
+
+async fn exchange<S>(mut stream: S, broker_url: &Url, topic: &str, payload: &[u8]) -> Result<(), MqttError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let io_error = |cause| MqttError::Io { url: redact_userinfo(broker_url), cause };
+    let protocol_error = |cause: rumqttc::mqttbytes::Error| MqttError::Protocol { url: redact_userinfo(broker_url), cause: cause.to_string() };
+
+    let mut buffer = BytesMut::new();
+    connect_packet(broker_url)?.write(&mut buffer).map_err(protocol_error)?;
+    stream.write_all(&buffer).await.map_err(io_error)?;
+
+    let mut received = BytesMut::new();
+    let connack = loop {
+        match read(&mut received, MAX_PACKET_SIZE) {
+            Ok(packet) => break packet,
+            Err(rumqttc::mqttbytes::Error::InsufficientBytes(_)) => {
+                let read_bytes = stream.read_buf(&mut received).await.map_err(io_error)?;
+                if read_bytes == 0 {
+                    return Err(MqttError::Protocol { url: redact_userinfo(broker_url), cause: String::from("Connection closed before receiving CONNACK packet.") });
+                }
+            }
+            Err(cause) => return Err(protocol_error(cause)),
+        }
+    };
+    match connack {
+        Packet::ConnAck(ConnAck { code: ConnectReturnCode::Success, .. }) => {}
+        Packet::ConnAck(ConnAck { code, .. }) => return Err(MqttError::Rejected { url: redact_userinfo(broker_url), code }),
+        other => return Err(MqttError::Protocol { url: redact_userinfo(broker_url), cause: format!("Expected CONNACK packet, received {other:?}.") }),
+    }
+
+    let mut buffer = BytesMut::new();
+    Publish::new(topic, QoS::AtMostOnce, payload.to_vec()).write(&mut buffer).map_err(protocol_error)?;
+    Disconnect.write(&mut buffer).map_err(protocol_error)?;
+    stream.write_all(&buffer).await.map_err(io_error)?;
+    stream.shutdown().await.map_err(io_error)?;
+
+    Ok(())
+}
+
+fn connect_packet(broker_url: &Url) -> Result<Connect, MqttError> {
+    let client_id = format!("opendut-carl-{}", &Uuid::new_v4().simple().to_string()[..8]);
+
+    let mut connect = Connect::new(client_id);
+    connect.keep_alive = KEEP_ALIVE_SECONDS;
+    connect.clean_session = true;
+
+    if let Some((username, password)) = credentials(broker_url)? {
+        connect.set_login(username, password);
+    }
+    Ok(connect)
+}
+
+/// Decodes the credentials of the broker URL, which are percent-encoded in the userinfo of the URL.
+fn credentials(broker_url: &Url) -> Result<Option<(String, String)>, MqttError> {
+    let decode = |value: &str| percent_decode_str(value).decode_utf8()
+        .map(|value| value.into_owned())
+        .map_err(|cause| MqttError::Credentials { url: redact_userinfo(broker_url), cause });
+
+    let username = decode(broker_url.username())?;
+    let password = broker_url.password().map(decode).transpose()?;
+
+    if username.is_empty() && password.is_none() {
+        Ok(None)
+    } else {
+        Ok(Some((username, password.unwrap_or_default())))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum MqttError {
+    #[error("Failed to communicate with MQTT broker '{url}'")]
+    Io {
+        url: Url,
+        #[source] cause: std::io::Error,
+    },
+    #[error("MQTT broker '{url}' did not respond in time")]
+    Timeout {
+        url: Url,
+    },
+    #[error("Unexpected response from MQTT broker '{url}': {cause}")]
+    Protocol {
+        url: Url,
+        cause: String,
+    },
+    #[error("MQTT broker '{url}' refused the connection: {code:?}")]
+    Rejected {
+        url: Url,
+        code: ConnectReturnCode,
+    },
+    #[error("Refusing to publish to MQTT broker '{url}'")]
+    Target {
+        url: Url,
+        #[source] cause: TargetAddressError,
+    },
+    #[error("Credentials of MQTT broker '{url}' are not valid UTF-8 after percent-decoding")]
+    Credentials {
+        url: Url,
+        #[source] cause: std::str::Utf8Error,
+    },
+    #[error("Failed to configure TLS for connecting to MQTT brokers")]
+    Tls {
+        #[source] cause: rustls::Error,
+    },
+}
+
+impl MqttError {
+    fn is_transient(&self) -> bool {
+        match self {
+            MqttError::Io { .. } | MqttError::Timeout { .. } => true,
+            MqttError::Target { cause, .. } => matches!(cause, TargetAddressError::Resolve { .. }),
+            MqttError::Protocol { .. } | MqttError::Rejected { .. } | MqttError::Credentials { .. } | MqttError::Tls { .. } => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+    use crate::manager::notification_manager::testing::{options, MqttStandIn};
+    use crate::manager::notification_manager::TargetAddressPolicy;
+
+    #[tokio::test]
+    async fn should_publish_payload_to_mqtt_broker() -> anyhow::Result<()> {
+        let mut stand_in = MqttStandIn::spawn().await?;
+        let testee = MqttPublisher::new(options())?;
+
+        testee.publish(&stand_in.url, "opendut/events", br#"{"event":"cluster-deployed"}"#).await?;
+
+        let message = stand_in.receive().await?;
+        assert_that!(message.client_id, starts_with("opendut-carl-"));
+        assert_that!(message.username, none());
+        assert_that!(message.password, none());
+        assert_that!(message.topic, eq("opendut/events"));
+        assert_that!(message.payload, eq(&br#"{"event":"cluster-deployed"}"#.to_vec()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_send_percent_decoded_credentials_to_mqtt_broker() -> anyhow::Result<()> {
+        let mut stand_in = MqttStandIn::spawn().await?;
+        let testee = MqttPublisher::new(options())?;
+
+        let mut broker_url = Clone::clone(&stand_in.url);
+        broker_url.set_username("carl@opendut").expect("URL should have a host");
+        broker_url.set_password(Some("p@ss:word/1")).expect("URL should have a host");
+        assert_that!(broker_url.as_str(), contains_substring("carl%40opendut:p%40ss%3Aword%2F1@"));
+
+        testee.publish(&broker_url, "opendut/events", b"{}").await?;
+
+        let message = stand_in.receive().await?;
+        assert_that!(message.username, some(eq("carl@opendut")));
+        assert_that!(message.password, some(eq("p@ss:word/1")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refuse_mqtt_brokers_outside_the_allowed_networks() -> anyhow::Result<()> {
+        let stand_in = MqttStandIn::spawn().await?;
+        let testee = MqttPublisher::new(NotificationOptions {
+            target_addresses: TargetAddressPolicy::default(),
+            ..options()
+        })?;
+
+        let result = testee.publish(&stand_in.url, "opendut/events", b"{}").await;
+
+        assert_that!(result, err(matches_pattern!(MqttError::Target { .. })));
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+
+use ipnet::IpNet;
+use url::{Host, Url};
+
+use opendut_types::notification::NotificationTarget;
+
+/// Decides, which addresses CARL may deliver notifications to, so that subscriptions cannot reach into the network of CARL.
+///
+/// Addresses, which are not publicly routable, are refused, unless they belong to one of the allowed networks.
+/// This covers private, loopback and link-local addresses, including those of cloud metadata services, e.g. `169.254.169.254`.
+#[derive(Clone, Debug, Default)]
+pub struct TargetAddressPolicy {
+    allowed_networks: Vec<IpNet>,
+}
+
+impl TargetAddressPolicy {
+    pub fn new(allowed_networks: Vec<IpNet>) -> Self {
+        Self { allowed_networks }
+    }
+
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        use opendut_util::settings::LoadError;
+
+        let field = "notification.targets.allowed.networks";
+        let value = config.get_string(field)
+            .map_err(|cause| LoadError::ReadField { field, source: Box::new(cause) })?;
+
+        let allowed_networks = value.split(',')
+            .map(str::trim)
+            .filter(|network| !network.is_empty())
+            .map(|network| {
+                let with_prefix = match network {
+                    network if network.contains('/') => network.to_owned(),
+                    network if network.contains(':') => format!("{network}/128"),
+                    network => format!("{network}/32"),
+                };
+                IpNet::from_str(&with_prefix)
+                    .map_err(|cause| LoadError::ParseValue { field, value: network.to_owned(), source: Box::new(cause) })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { allowed_networks })
+    }
+
+    pub fn permits(&self, address: IpAddr) -> bool {
+        is_publicly_routable(address)
+            || self.allowed_networks.iter().any(|network| network.contains(&address))
+    }
+
+    /// Checks the host of the target, if it is given as an IP address.
+    /// Domains are checked, when they are resolved for delivering a notification.
+    pub fn check_target(&self, target: &NotificationTarget) -> Result<(), TargetAddressError> {
+        match target {
+            NotificationTarget::Webhook { url, secret: _ } => self.check_url(url),
+            NotificationTarget::Mqtt { broker_url, topic: _ } => self.check_url(broker_url),
+        }
+    }
+
+    /// Checks the host of the URL, if it is given as an IP address.
+    pub fn check_url(&self, url: &Url) -> Result<(), TargetAddressError> {
+        match url.host() {
+            Some(Host::Ipv4(address)) => self.check(url, IpAddr::V4(address)),
+            Some(Host::Ipv6(address)) => self.check(url, IpAddr::V6(address)),
+            Some(Host::Domain(_)) => Ok(()),
+            None => Err(TargetAddressError::NoHost { url: Clone::clone(url) }),
+        }
+    }
+
+    /// Resolves the host of the URL and checks all of its addresses, so that a domain cannot point CARL to a refused address.
+    pub async fn resolve(&self, url: &Url, default_port: u16) -> Result<Vec<SocketAddr>, TargetAddressError> {
+        let port = url.port().unwrap_or(default_port);
+
+        let addresses = match url.host() {
+            Some(Host::Ipv4(address)) => vec![SocketAddr::new(IpAddr::V4(address), port)],
+            Some(Host::Ipv6(address)) => vec![SocketAddr::new(IpAddr::V6(address), port)],
+            Some(Host::Domain(domain)) => self.resolve_domain(domain, port).await?,
+            None => return Err(TargetAddressError::NoHost { url: Clone::clone(url) }),
+        };
+
+        for address in &addresses {
+            self.check(url, address.ip())?;
+        }
+        Ok(addresses)
+    }
+
+    /// Resolves the domain and checks all of its addresses.
+    pub async fn resolve_domain(&self, domain: &str, port: u16) -> Result<Vec<SocketAddr>, TargetAddressError> {
+        let addresses = tokio::net::lookup_host((domain, port)).await
+            .map_err(|cause| TargetAddressError::Resolve { host: domain.to_owned(), cause })?
+            .collect::<Vec<_>>();
+
+        if let Some(address) = addresses.iter().find(|address| !self.permits(address.ip())) {
+            return Err(TargetAddressError::Refused { host: domain.to_owned(), address: address.ip() });
+        }
+        Ok(addresses)
+    }
+
+    fn check(&self, url: &Url, address: IpAddr) -> Result<(), TargetAddressError> {
+        if self.permits(address) {
+            Ok(())
+        } else {
+            Err(TargetAddressError::Refused { host: url.host_str().unwrap_or_default().to_owned(), address })
+        }
+    }
+}
+
+fn is_publicly_routable(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => is_publicly_routable_v4(address),
+        IpAddr::V6(address) => match address.to_ipv4_mapped() {
+            Some(mapped) => is_publicly_routable_v4(mapped),
+            None => is_publicly_routable_v6(address),
+        },
+    }
+}
+
+fn is_publicly_routable_v4(address: Ipv4Addr) -> bool {
+    let [first, second, third, _] = address.octets();
+
+    let is_this_network = first == 0;
+    let is_shared = first == 100 && (second & 0b1100_0000) == 64; //100.64.0.0/10, e.g. carrier-grade NAT or cloud metadata services
+    let is_protocol_assignment = first == 192 && second == 0 && third == 0;
+    let is_benchmarking = first == 198 && (second & 0b1111_1110) == 18;
+    let is_reserved = first >= 240;
+
+    !(address.is_private()
+        || address.is_loopback()
+        || address.is_link_local()
+        || address.is_multicast()
+        || address.is_broadcast()
+        || address.is_documentation()
+        || is_this_network
+        || is_shared
+        || is_protocol_assignment
+        || is_benchmarking
+        || is_reserved)
+}
+
+fn is_publicly_routable_v6(address: Ipv6Addr) -> bool {
+    let segments = address.segments();
+
+    let is_documentation = segments[0] == 0x2001 && segments[1] == 0x0db8;
+    let is_ipv4_compatible = segments[..6] == [0; 6]; //includes the loopback and unspecified addresses
+
+    !(address.is_loopback()
+        || address.is_unspecified()
+        || address.is_multicast()
+        || address.is_unique_local() //e.g. fd00:ec2::254 of cloud metadata services
+        || address.is_unicast_link_local()
+        || is_documentation
+        || is_ipv4_compatible)
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TargetAddressError {
+    #[error("Address {address} of host '{host}' is not publicly routable and not within the networks configured in 'notification.targets.allowed.networks'.")]
+    Refused {
+        host: String,
+        address: IpAddr,
+    },
+    #[error("Failed to resolve host '{host}'")]
+    Resolve {
+        host: String,
+        #[source] cause: std::io::Error,
+    },
+    #[error("URL '{url}' has no host.")]
+    NoHost {
+        url: Url,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("127.0.0.1")]
+    #[case("10.1.2.3")]
+    #[case("172.16.0.1")]
+    #[case("192.168.0.1")]
+    #[case("169.254.169.254")]
+    #[case("100.100.100.200")]
+    #[case("0.0.0.0")]
+    #[case("::1")]
+    #[case("::ffff:127.0.0.1")]
+    #[case("fd00:ec2::254")]
+    #[case("fe80::1")]
+    fn should_refuse_addresses_which_are_not_publicly_routable(#[case] address: &str) -> anyhow::Result<()> {
+        let address = IpAddr::from_str(address)?;
+
+        assert_that!(TargetAddressPolicy::default().permits(address), eq(false));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("93.184.215.14")]
+    #[case("2606:2800:21f:cb07:6820:80da:af6b:8b2c")]
+    fn should_permit_publicly_routable_addresses(#[case] address: &str) -> anyhow::Result<()> {
+        let address = IpAddr::from_str(address)?;
+
+        assert_that!(TargetAddressPolicy::default().permits(address), eq(true));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_permit_addresses_of_allowed_networks() -> anyhow::Result<()> {
+        let testee = TargetAddressPolicy::new(vec![IpNet::from_str("10.0.0.0/8")?]);
+
+        assert_that!(testee.permits(IpAddr::from_str("10.1.2.3")?), eq(true));
+        assert_that!(testee.permits(IpAddr::from_str("192.168.0.1")?), eq(false));
+
+        let url = Url::parse("http://192.168.0.1:8080/hook")?;
+        assert_that!(testee.resolve(&url, 80).await, err(matches_pattern!(TargetAddressError::Refused { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_check_the_resolved_addresses_of_domains() -> anyhow::Result<()> {
+        let url = Url::parse("http://localhost:8080/hook")?;
+
+        let result = TargetAddressPolicy::default().resolve(&url, 80).await;
+        assert_that!(result, err(matches_pattern!(TargetAddressError::Refused { .. })));
+
+        let loopback = TargetAddressPolicy::new(vec![IpNet::from_str("127.0.0.0/8")?, IpNet::from_str("::1/128")?]);
+        assert_that!(loopback.resolve(&url, 80).await, ok(not(empty())));
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::VecDeque;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use ipnet::IpNet;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use url::Url;
+
+use crate::manager::notification_manager::{NotificationOptions, TargetAddressPolicy};
+
+/// Permits the loopback network, which the stand-ins listen on.
+pub fn options() -> NotificationOptions {
+    NotificationOptions {
+        delivery_timeout: Duration::from_secs(5),
+        max_retries: 3,
+        min_retry_delay: Duration::from_millis(1),
+        max_retry_delay: Duration::from_millis(10),
+        target_addresses: TargetAddressPolicy::new(vec![
+            IpNet::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8).expect("Prefix length of loopback network should be valid."),
+        ]),
+    }
+}
+
+pub struct ReceivedRequest {
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+/// HTTP server in place of a webhook, which records the received requests and responds with the given status codes in order.
+pub struct WebhookStandIn {
+    pub url: Url,
+    requests: mpsc::Receiver<ReceivedRequest>,
+}
+
+#[derive(Clone)]
+struct WebhookState {
+    responses: Arc<Mutex<VecDeque<StatusCode>>>,
+    requests: mpsc::Sender<ReceivedRequest>,
+}
+
+impl WebhookStandIn {
+    pub async fn spawn(responses: Vec<StatusCode>) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = Url::parse(&format!("http://{}/hook", listener.local_addr()?))?;
+        let (tx, rx) = mpsc::channel(10);
+
+        let state = WebhookState {
+            responses: Arc::new(Mutex::new(VecDeque::from(responses))),
+            requests: tx,
+        };
+        let router = axum::Router::new()
+            .route("/hook", axum::routing::post(receive_webhook))
+            .with_state(state);
+
+        tokio::spawn(async move {
+            axum::serve(listener, router).await
+        });
+
+        Ok(Self { url, requests: rx })
+    }
+
+    pub async fn receive(&mut self) -> anyhow::Result<ReceivedRequest> {
+        tokio::time::timeout(Duration::from_secs(5), self.requests.recv()).await?
+            .ok_or_else(|| anyhow::anyhow!("Webhook stand-in stopped."))
+    }
+
+    pub async fn expect_no_request(&mut self) {
+        let result = tokio::time::timeout(Duration::from_millis(100), self.requests.recv()).await;
+        assert!(result.is_err(), "Expected no further request to webhook.");
+    }
+}
+
+async fn receive_webhook(State(state): State<WebhookState>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    let status = state.responses.lock().unwrap()
+        .pop_front()
+        .unwrap_or(StatusCode::OK);
+    state.requests.send(ReceivedRequest { headers, body }).await
+        .expect("Test should still receive requests.");
+    status
+}
+
+pub struct PublishedMessage {
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub topic: String,
+    pub payload: Vec<u8>,
+}
+
+/// MQTT broker in place of a real one, which accepts every connection and records published messages.
+pub struct MqttStandIn {
+    pub url: Url,
+    messages: mpsc::Receiver<PublishedMessage>,
+}
+
+impl MqttStandIn {
+    pub async fn spawn() -> anyhow::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = Url::parse(&format!("mqtt://{}", listener.local_addr()?))?;
+        let (tx, rx) = mpsc::channel(10);
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let tx = Clone::clone(&tx);
+                tokio::spawn(async move {
+                    if let Ok(message) = receive_publish(stream).await {
+                        let _ = tx.send(message).await;
+                    }
+                });
+            }
+        });
+
+        Ok(Self { url, messages: rx })
+    }
+
+    pub async fn receive(&mut self) -> anyhow::Result<PublishedMessage> {
+        tokio::time::timeout(Duration::from_secs(5), self.messages.recv()).await?
+            .ok_or_else(|| anyhow::anyhow!("MQTT stand-in stopped."))
+    }
+}
+
+async fn receive_publish(mut stream: TcpStream) -> anyhow::Result<PublishedMessage> {
+    let (packet_type, connect) = read_packet(&mut stream).await?;
+    anyhow::ensure!(packet_type == 0x10, "Expected CONNECT packet, received {packet_type:#04x}.");
+    let (_, rest) = read_string(&connect)?; //protocol name
+    let flags = rest[1];
+    let (client_id, rest) = read_string(&rest[4..])?; //skip protocol level, connect flags and keep alive
+    let (username, rest) = if flags & 0x80 != 0 {
+        let (username, rest) = read_string(rest)?;
+        (Some(username), rest)
+    } else {
+        (None, rest)
+    };
+    let password = if flags & 0x40 != 0 {
+        Some(read_string(rest)?.0)
+    } else {
+        None
+    };
+
+    stream.write_all(&[0x20, 0x02, 0x00, 0x00]).await?;
+
+    let (packet_type, publish) = read_packet(&mut stream).await?;
+    anyhow::ensure!(packet_type & 0xF0 == 0x30, "Expected PUBLISH packet, received {packet_type:#04x}.");
+    let (topic, payload) = read_string(&publish)?;
+
+    Ok(PublishedMessage {
+        client_id,
+        username,
+        password,
+        topic,
+        payload: payload.to_vec(),
+    })
+}
+
+async fn read_packet(stream: &mut TcpStream) -> anyhow::Result<(u8, Vec<u8>)> {
+    let packet_type = stream.read_u8().await?;
+
+    let mut remaining_length = 0usize;
+    let mut multiplier = 1usize;
+    loop {
+        let byte = stream.read_u8().await?;
+        remaining_length += usize::from(byte & 0x7F) * multiplier;
+        multiplier *= 128;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+
+    let mut body = vec![0; remaining_length];
+    stream.read_exact(&mut body).await?;
+    Ok((packet_type, body))
+}
+
+fn read_string(bytes: &[u8]) -> anyhow::Result<(String, &[u8])> {
+    anyhow::ensure!(bytes.len() >= 2, "Expected length prefix of string.");
+    let length = usize::from(u16::from_be_bytes([bytes[0], bytes[1]]));
+    anyhow::ensure!(bytes.len() >= 2 + length, "Expected string of {length} bytes.");
+    let string = String::from_utf8(bytes[2..2 + length].to_vec())?;
+    Ok((string, &bytes[2 + length..]))
+}
@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use backon::Retryable;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::StatusCode;
+use ring::hmac;
+use tracing::debug;
+use url::Url;
+
+use opendut_types::notification::{NotificationEventKind, NotificationSecret};
+
+use crate::manager::notification_manager::{NotificationOptions, TargetAddressError, TargetAddressPolicy};
+
+/// HMAC-SHA256 signature of the request body, keyed with the secret of the subscription, in the format `sha256=<hex>`.
+pub const SIGNATURE_HEADER: &str = "X-OpenDuT-Signature-256";
+pub const EVENT_HEADER: &str = "X-OpenDuT-Event";
+
+/// Sends events as signed HTTP POST requests to webhooks.
+#[derive(Clone)]
+pub struct WebhookSender {
+    client: reqwest::Client,
+    options: NotificationOptions,
+}
+
+impl WebhookSender {
+    /// Redirects are not followed, as they could lead to addresses refused by the [`TargetAddressPolicy`].
+    pub fn new(options: NotificationOptions) -> Result<Self, reqwest::Error> {
+        let client = reqwest::Client::builder()
+            .timeout(options.delivery_timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(CheckingResolver { policy: Clone::clone(&options.target_addresses) }))
+            .build()?;
+        Ok(Self { client, options })
+    }
+
+    /// Retries with exponential backoff, unless the webhook rejects the request.
+    pub async fn send(&self, url: &Url, secret: &NotificationSecret, event: NotificationEventKind, payload: &[u8]) -> Result<(), WebhookError> {
+        let signature = sign(secret, payload);
+
+        (|| async {
+            self.send_once(url, &signature, event, payload).await
+        })
+            .retry(self.options.backoff())
+            .when(WebhookError::is_transient)
+            .notify(|cause, delay| debug!("Delivery of notification to webhook failed. Retrying in {} ms:\n  {cause}", delay.as_millis()))
+            .await
+    }
+
+    async fn send_once(&self, url: &Url, signature: &str, event: NotificationEventKind, payload: &[u8]) -> Result<(), WebhookError> {
+        //hosts given as IP address are not passed to the resolver
+        self.options.target_addresses.check_url(url)
+            .map_err(|cause| WebhookError::Target { url: Clone::clone(url), cause })?;
+
+        let response = self.client.post(Clone::clone(url))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(EVENT_HEADER, event.name())
+            .body(payload.to_vec())
+            .send().await
+            .map_err(|cause| WebhookError::Request { url: Clone::clone(url), cause })?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(WebhookError::Status { url: Clone::clone(url), status })
+        }
+    }
+}
+
+/// Resolves the hosts of webhooks and refuses them, if any of their addresses is refused by the policy,
+/// so that the checked addresses are the ones connected to.
+struct CheckingResolver {
+    policy: TargetAddressPolicy,
+}
+
+impl Resolve for CheckingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let policy = Clone::clone(&self.policy);
+        Box::pin(async move {
+            let addresses = policy.resolve_domain(name.as_str(), 0).await
+                .map_err(|cause| Box::new(cause) as Box<dyn std::error::Error + Send + Sync>)?;
+            let addresses: Addrs = Box::new(addresses.into_iter());
+            Ok(addresses)
+        })
+    }
+}
+
+fn is_refused_by_resolver(error: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        if let Some(TargetAddressError::Refused { .. }) = cause.downcast_ref::<TargetAddressError>() {
+            return true;
+        }
+        source = cause.source();
+    }
+    false
+}
+
+pub fn sign(secret: &NotificationSecret, payload: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.0.as_bytes());
+    let tag = hmac::sign(&key, payload);
+    let hex = tag.as_ref().iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("sha256={hex}")
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum WebhookError {
+    #[error("Refusing to send request to webhook '{url}'")]
+    Target {
+        url: Url,
+        #[source] cause: TargetAddressError,
+    },
+    #[error("Failed to send request to webhook '{url}'")]
+    Request {
+        url: Url,
+        #[source] cause: reqwest::Error,
+    },
+    #[error("Webhook '{url}' responded with HTTP status {status}")]
+    Status {
+        url: Url,
+        status: StatusCode,
+    },
+}
+
+impl WebhookError {
+    /// Other client errors are not retried, as repeating the request would not change the response.
+    fn is_transient(&self) -> bool {
+        match self {
+            WebhookError::Target { cause, .. } => matches!(cause, TargetAddressError::Resolve { .. }),
+            WebhookError::Request { cause, .. } => !is_refused_by_resolver(cause),
+            WebhookError::Status { status, .. } => status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use googletest::prelude::*;
+
+    use super::*;
+    use crate::manager::notification_manager::testing::{options, WebhookStandIn};
+
+    #[test]
+    fn should_sign_payload_with_hmac_sha256() -> anyhow::Result<()> {
+        let secret = NotificationSecret::from_str("It's a Secret to Everybody")?;
+
+        let signature = sign(&secret, b"Hello, World!");
+
+        // Example of GitHub's documentation for validating webhook deliveries.
+        assert_that!(signature, eq("sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_retry_webhook_after_server_error() -> anyhow::Result<()> {
+        let mut stand_in = WebhookStandIn::spawn(vec![StatusCode::SERVICE_UNAVAILABLE, StatusCode::OK]).await?;
+        let secret = NotificationSecret::from_str("0123456789abcdef")?;
+        let testee = WebhookSender::new(options())?;
+
+        testee.send(&stand_in.url, &secret, NotificationEventKind::PeerOnline, br#"{"event":"peer-online"}"#).await?;
+
+        let first = stand_in.receive().await?;
+        let second = stand_in.receive().await?;
+        assert_that!(first.body, eq(&second.body));
+        assert_that!(second.headers.get(EVENT_HEADER).and_then(|value| value.to_str().ok()), some(eq("peer-online")));
+        assert_that!(
+            second.headers.get(SIGNATURE_HEADER).and_then(|value| value.to_str().ok()),
+            some(eq(&sign(&secret, br#"{"event":"peer-online"}"#)))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refuse_webhooks_outside_the_allowed_networks() -> anyhow::Result<()> {
+        let mut stand_in = WebhookStandIn::spawn(vec![StatusCode::OK]).await?;
+        let testee = WebhookSender::new(NotificationOptions { target_addresses: TargetAddressPolicy::default(), ..options() })?;
+
+        let result = testee.send(&stand_in.url, &NotificationSecret::from_str("0123456789abcdef")?, NotificationEventKind::PeerOnline, b"{}").await;
+        assert_that!(result, err(matches_pattern!(WebhookError::Target { cause: matches_pattern!(TargetAddressError::Refused { .. }), .. })));
+
+        let mut via_domain = Clone::clone(&stand_in.url);
+        via_domain.set_host(Some("localhost"))?;
+        let result = testee.send(&via_domain, &NotificationSecret::from_str("0123456789abcdef")?, NotificationEventKind::PeerOnline, b"{}").await;
+        assert_that!(result, err(matches_pattern!(WebhookError::Request { .. })));
+
+        stand_in.expect_no_request().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_webhook_after_client_error() -> anyhow::Result<()> {
+        let mut stand_in = WebhookStandIn::spawn(vec![StatusCode::NOT_FOUND, StatusCode::OK]).await?;
+        let testee = WebhookSender::new(options())?;
+
+        let result = testee.send(&stand_in.url, &NotificationSecret::from_str("0123456789abcdef")?, NotificationEventKind::PeerOnline, b"{}").await;
+
+        assert_that!(result, err(matches_pattern!(WebhookError::Status { status: eq(&StatusCode::NOT_FOUND), .. })));
+        stand_in.receive().await?;
+        stand_in.expect_no_request().await;
+        Ok(())
+    }
+}
@@ -1,11 +1,16 @@
 mod observe_peers_online;
+mod watch;
 
 use crate::manager::cluster_manager::ClusterManagerRef;
 use crate::manager::observer_messaging_broker::observe_peers_online::ObservePeersOnline;
+use crate::manager::observer_messaging_broker::watch::{ResourceWatch, Watchable};
 use crate::resource::manager::ResourceManagerRef;
 use crate::resource::persistence::error::PersistenceError;
-use opendut_carl_api::carl::observer::{WaitForPeersOnlineResponseStatus};
-use opendut_types::peer::PeerId;
+use opendut_carl_api::carl::observer::{WaitForPeersOnlineResponseStatus, WatchEvent, WatchRequest, WatchedResourceKind};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::peer::state::PeerConnectionState;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::resources::Revision;
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
@@ -73,4 +78,50 @@ impl ObserverMessagingBroker {
 
         Ok(rx_outbound)
     }
+
+    pub async fn watch(&self, request: WatchRequest) -> Result<mpsc::Receiver<WatchEvent>, ObserverError> {
+        match request.kind {
+            WatchedResourceKind::PeerDescriptor => self.spawn_watch::<PeerDescriptor>(request.resume_revision).await,
+            WatchedResourceKind::PeerConnectionState => self.spawn_watch::<PeerConnectionState>(request.resume_revision).await,
+            WatchedResourceKind::ClusterConfiguration => self.spawn_watch::<ClusterConfiguration>(request.resume_revision).await,
+            WatchedResourceKind::ClusterDeployment => self.spawn_watch::<ClusterDeployment>(request.resume_revision).await,
+        }
+    }
+
+    async fn spawn_watch<R: Watchable>(&self, resume_revision: Option<Revision>) -> Result<mpsc::Receiver<WatchEvent>, ObserverError> {
+        let (tx_outbound, rx_outbound) = mpsc::channel::<WatchEvent>(1024);
+        let (mut watch, snapshot) = ResourceWatch::<R>::create(self.resource_manager.clone(), resume_revision).await?;
+
+        tokio::spawn(async move {
+            if tx_outbound.send(snapshot).await.is_err() {
+                return;
+            }
+
+            loop {
+                let event = tokio::select! {
+                    event = watch.next_event() => event,
+                    () = tx_outbound.closed() => {
+                        debug!("Watcher of resources has closed the connection.");
+                        break;
+                    }
+                };
+
+                match event {
+                    Ok(Some(event)) => {
+                        if tx_outbound.send(event).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(cause) => {
+                        error!("Failed to determine modifications for watcher of resources:\n  {cause}");
+                        break;
+                    }
+                }
+            }
+            trace!("Leaving watch thread.");
+        });
+
+        Ok(rx_outbound)
+    }
 }
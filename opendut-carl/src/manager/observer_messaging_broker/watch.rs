@@ -0,0 +1,214 @@
+use std::ops::Not;
+use opendut_carl_api::carl::observer::{WatchEvent, WatchSnapshotEntry, WatchedResource};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::peer::state::PeerConnectionState;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::resources::Revision;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::debug;
+use crate::resource::api::id::ResourceId;
+use crate::resource::api::Resource;
+use crate::resource::manager::{ResourceManagerRef, SubscriptionEvent};
+use crate::resource::persistence::error::{PersistenceError, PersistenceResult};
+use crate::resource::persistence::persistable::Persistable;
+use crate::resource::storage::ResourcesStorageApi;
+use crate::resource::subscription::{ReceiveError, RevisionedSubscriptionEvent, Subscribable, Subscription};
+
+/// Resources, whose modifications can be watched by clients.
+pub trait Watchable: Resource<Id: Send + Sync> + Persistable + Subscribable {
+    fn into_watched(id: Self::Id, value: Self) -> WatchedResource;
+}
+impl Watchable for PeerDescriptor {
+    fn into_watched(_: PeerId, value: Self) -> WatchedResource {
+        WatchedResource::PeerDescriptor(value)
+    }
+}
+impl Watchable for PeerConnectionState {
+    fn into_watched(peer_id: PeerId, state: Self) -> WatchedResource {
+        WatchedResource::PeerConnectionState { peer_id, state }
+    }
+}
+impl Watchable for ClusterConfiguration {
+    fn into_watched(_: ClusterId, value: Self) -> WatchedResource {
+        WatchedResource::ClusterConfiguration(value)
+    }
+}
+impl Watchable for ClusterDeployment {
+    fn into_watched(_: ClusterId, value: Self) -> WatchedResource {
+        WatchedResource::ClusterDeployment(value)
+    }
+}
+
+pub struct ResourceWatch<R: Watchable> {
+    resource_manager: ResourceManagerRef,
+    subscription: Subscription<R>,
+    /// Revision up to which the watcher knows all modifications.
+    delivered_revision: Revision,
+}
+
+impl<R: Watchable> ResourceWatch<R> {
+    /// Starts watching and returns the initial snapshot, which has to be sent before any further events.
+    pub async fn create(resource_manager: ResourceManagerRef, resume_revision: Option<Revision>) -> Result<(Self, WatchEvent), PersistenceError> {
+        let subscription = resource_manager.subscribe::<R>().await; //subscribe before taking the snapshot, so no modification is missed in between
+        let (snapshot, revision) = snapshot::<R>(&resource_manager, resume_revision).await?;
+
+        let watch = Self {
+            resource_manager,
+            subscription,
+            delivered_revision: revision,
+        };
+        Ok((watch, snapshot))
+    }
+
+    /// Waits for the next modification. Returns `None`, when no further modifications can be received.
+    pub async fn next_event(&mut self) -> Result<Option<WatchEvent>, PersistenceError> {
+        loop {
+            match self.subscription.receive_with_revision().await {
+                Ok(RevisionedSubscriptionEvent { event, revision }) => {
+                    if revision <= self.delivered_revision {
+                        continue; //already contained in the snapshot
+                    }
+                    self.delivered_revision = revision;
+
+                    let event = match event {
+                        SubscriptionEvent::Inserted { id, value } => WatchEvent::Inserted { resource: R::into_watched(id, value), revision },
+                        SubscriptionEvent::Removed { id, value } => WatchEvent::Removed { resource: R::into_watched(id, value), revision },
+                    };
+                    return Ok(Some(event));
+                }
+                Err(ReceiveError::Broadcast(RecvError::Lagged(skipped))) => {
+                    debug!("Watcher of {} fell behind by {skipped} modifications. Sending a new snapshot.", R::TABLE);
+                    let (snapshot, revision) = snapshot::<R>(&self.resource_manager, Some(self.delivered_revision)).await?;
+                    self.delivered_revision = revision;
+                    return Ok(Some(snapshot));
+                }
+                Err(ReceiveError::Broadcast(RecvError::Closed)) => return Ok(None),
+            }
+        }
+    }
+}
+
+async fn snapshot<R: Watchable>(resource_manager: &ResourceManagerRef, resume_revision: Option<Revision>) -> PersistenceResult<(WatchEvent, Revision)> {
+    resource_manager.resources(async |resources| -> PersistenceResult<(WatchEvent, Revision)> {
+        let revision = resources.latest_revision_of::<R>()?;
+
+        //revisions of volatile resources start over when CARL restarts, so a newer revision stems from before the restart and nothing is known to be unmodified
+        let resume_revision = resume_revision.filter(|resume_revision| *resume_revision <= revision);
+
+        let entries = resources.list::<R>()?
+            .into_iter()
+            .map(|(id, value)| {
+                let entry_revision = resources.revision::<R>(id.clone())?.unwrap_or_default();
+                let unmodified = resume_revision.is_some_and(|resume_revision| entry_revision <= resume_revision);
+
+                Ok(WatchSnapshotEntry {
+                    id: ResourceId::<R>::into_id(id.clone()).value(),
+                    revision: entry_revision,
+                    resource: unmodified.not().then(|| R::into_watched(id, value)),
+                })
+            })
+            .collect::<PersistenceResult<Vec<_>>>()?;
+
+        Ok((WatchEvent::Snapshot { entries, revision }, revision))
+    }).await
+    .and_then(|result| result)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::peer_manager::tests::create_peer_descriptor;
+    use crate::resource::manager::ResourceManager;
+    use googletest::prelude::*;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    #[tokio::test]
+    async fn should_send_snapshot_followed_by_modifications() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let existing_peer = create_peer_descriptor();
+        resource_manager.insert(existing_peer.id, existing_peer.clone()).await?;
+
+        let (mut watch, snapshot) = ResourceWatch::<PeerDescriptor>::create(resource_manager.clone(), None).await?;
+
+        let WatchEvent::Snapshot { entries, revision: snapshot_revision } = snapshot else {
+            panic!("Expected the first event to be a snapshot, but got: {snapshot:?}");
+        };
+        assert_that!(entries, elements_are![
+            matches_pattern!(WatchSnapshotEntry {
+                id: eq(&existing_peer.id.uuid),
+                resource: some(eq(&WatchedResource::PeerDescriptor(existing_peer.clone()))),
+                ..
+            })
+        ]);
+
+        resource_manager.remove::<PeerDescriptor>(existing_peer.id).await?;
+
+        let event = timeout(Duration::from_secs(10), watch.next_event()).await??;
+        let Some(WatchEvent::Removed { resource, revision }) = event else {
+            panic!("Expected a removal event, but got: {event:?}");
+        };
+        assert_eq!(resource, WatchedResource::PeerDescriptor(existing_peer));
+        assert!(revision > snapshot_revision, "Expected the removal to have a newer revision than the snapshot.");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_omit_unmodified_resources_from_snapshot_when_resuming() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let unmodified_peer = create_peer_descriptor();
+        resource_manager.insert(unmodified_peer.id, unmodified_peer.clone()).await?;
+
+        let (_, snapshot) = ResourceWatch::<PeerDescriptor>::create(resource_manager.clone(), None).await?;
+        let WatchEvent::Snapshot { revision: resume_revision, .. } = snapshot else {
+            panic!("Expected the first event to be a snapshot, but got: {snapshot:?}");
+        };
+
+        let modified_peer = create_peer_descriptor();
+        resource_manager.insert(modified_peer.id, modified_peer.clone()).await?;
+
+        let (_, snapshot) = ResourceWatch::<PeerDescriptor>::create(resource_manager.clone(), Some(resume_revision)).await?;
+        let WatchEvent::Snapshot { entries, .. } = snapshot else {
+            panic!("Expected the first event to be a snapshot, but got: {snapshot:?}");
+        };
+        assert_that!(entries, unordered_elements_are![
+            matches_pattern!(WatchSnapshotEntry {
+                id: eq(&unmodified_peer.id.uuid),
+                resource: none(),
+                ..
+            }),
+            matches_pattern!(WatchSnapshotEntry {
+                id: eq(&modified_peer.id.uuid),
+                resource: some(eq(&WatchedResource::PeerDescriptor(modified_peer.clone()))),
+                ..
+            }),
+        ]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_send_full_snapshot_when_resuming_from_revision_before_restart() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer_id = PeerId::random();
+        resource_manager.insert(peer_id, PeerConnectionState::Offline).await?;
+
+        let resume_revision = Revision::from(1000); //handed out by the volatile storage before CARL restarted
+
+        let (_, snapshot) = ResourceWatch::<PeerConnectionState>::create(resource_manager.clone(), Some(resume_revision)).await?;
+        let WatchEvent::Snapshot { entries, .. } = snapshot else {
+            panic!("Expected the first event to be a snapshot, but got: {snapshot:?}");
+        };
+        assert_that!(entries, elements_are![
+            matches_pattern!(WatchSnapshotEntry {
+                id: eq(&peer_id.uuid),
+                resource: some(eq(&WatchedResource::PeerConnectionState { peer_id, state: PeerConnectionState::Offline })),
+                ..
+            })
+        ]);
+
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@ use opendut_types::peer::configuration::parameter;
 use opendut_types::peer::configuration::{OldPeerConfiguration, ParameterTarget, PeerConfiguration};
 use opendut_types::peer::{PeerDescriptor, PeerId};
 use opendut_types::util::net::{NetworkInterfaceDescriptor, NetworkInterfaceName};
+use opendut_types::vpn::wireguard::WireGuardPeerDescriptor;
 use tracing::debug;
 use crate::resource::api::resources::Resources;
 
@@ -15,6 +16,8 @@ pub struct AssignClusterParams {
     pub peer_id: PeerId,
     pub device_interfaces: Vec<NetworkInterfaceDescriptor>,
     pub cluster_assignment: ClusterAssignment,
+    /// Other peers of the cluster, if the VPN requires the peer to configure them itself.
+    pub wireguard_peers: Vec<WireGuardPeerDescriptor>,
     pub options: AssignClusterOptions,
 }
 
@@ -35,7 +38,7 @@ pub enum AssignClusterError {
 
 impl Resources<'_> {
     pub async fn assign_cluster(&mut self, params: AssignClusterParams) -> Result<(), AssignClusterError> {
-        let AssignClusterParams { peer_messaging_broker, peer_id, cluster_assignment, device_interfaces, options } = params;
+        let AssignClusterParams { peer_messaging_broker, peer_id, cluster_assignment, device_interfaces, wireguard_peers, options } = params;
 
         debug!("Assigning cluster to peer <{peer_id}>.");
 
@@ -73,6 +76,20 @@ impl Resources<'_> {
                     peer_configuration.set(executor, ParameterTarget::Present); //TODO not always Present
                 }
 
+                {
+                    let removed_wireguard_peers = peer_configuration.wireguard_peers.iter()
+                        .filter(|parameter| wireguard_peers.iter().all(|peer| peer.peer_id != parameter.value.descriptor.peer_id))
+                        .map(|parameter| Clone::clone(&parameter.value))
+                        .collect::<Vec<_>>();
+
+                    for wireguard_peer in removed_wireguard_peers {
+                        peer_configuration.set(wireguard_peer, ParameterTarget::Absent);
+                    }
+                    for descriptor in wireguard_peers {
+                        peer_configuration.set(parameter::WireGuardPeer { descriptor }, ParameterTarget::Present);
+                    }
+                }
+
                 peer_configuration
             };
             self.insert(peer_id, Clone::clone(&peer_configuration))
@@ -108,6 +125,8 @@ mod tests {
     use std::str::FromStr;
     use std::sync::Arc;
     use opendut_carl_api::carl::broker::stream_header;
+    use opendut_types::peer::configuration::Parameter;
+    use opendut_types::vpn::wireguard::PublicKey;
     use crate::manager::peer_manager::tests::create_peer_descriptor;
     use crate::manager::testing::PeerFixture;
 
@@ -159,6 +178,7 @@ mod tests {
                 peer_id,
                 cluster_assignment: Clone::clone(&cluster_assignment),
                 device_interfaces: vec![],
+                wireguard_peers: vec![],
                 options: AssignClusterOptions {
                     bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
                 }
@@ -193,4 +213,68 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_mark_wireguard_peers_absent_which_left_the_cluster() -> anyhow::Result<()> {
+        let peer_id = PeerFixture::new().id;
+
+        let settings = crate::settings::load_defaults()?;
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resource_manager),
+            PeerMessagingBrokerOptions::load(&settings.config).unwrap(),
+        ).await;
+
+        resource_manager.resources_mut(async |resources| {
+            resources.insert(peer_id, create_peer_descriptor())
+        }).await??;
+        let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?, stream_header::ExtraHeaders::default()).await?;
+        let _ = receiver.recv().await;
+
+        let wireguard_peer = |address: &str| -> anyhow::Result<WireGuardPeerDescriptor> {
+            Ok(WireGuardPeerDescriptor {
+                peer_id: PeerId::random(),
+                public_key: PublicKey::from_bytes([1; 32]),
+                address: IpAddr::from_str(address)?,
+                endpoint: None,
+            })
+        };
+        let leaving_peer = wireguard_peer("10.100.0.2")?;
+        let remaining_peer = wireguard_peer("10.100.0.3")?;
+
+        for wireguard_peers in [vec![Clone::clone(&leaving_peer), Clone::clone(&remaining_peer)], vec![Clone::clone(&remaining_peer)]] {
+            resource_manager.resources_mut(async |resources|
+                resources.assign_cluster(AssignClusterParams {
+                    peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+                    peer_id,
+                    cluster_assignment: ClusterAssignment {
+                        id: ClusterId::random(),
+                        leader: peer_id,
+                        assignments: vec![],
+                    },
+                    device_interfaces: vec![],
+                    wireguard_peers,
+                    options: AssignClusterOptions {
+                        bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
+                    }
+                }).await
+            ).await??;
+        }
+
+        let peer_configuration = resource_manager.get::<PeerConfiguration>(peer_id).await?.unwrap();
+        assert_that!(peer_configuration.wireguard_peers, unordered_elements_are![
+            matches_pattern!(Parameter {
+                target: eq(&ParameterTarget::Absent),
+                value: eq(&parameter::WireGuardPeer { descriptor: leaving_peer }),
+                ..
+            }),
+            matches_pattern!(Parameter {
+                target: eq(&ParameterTarget::Present),
+                value: eq(&parameter::WireGuardPeer { descriptor: remaining_peer }),
+                ..
+            }),
+        ]);
+
+        Ok(())
+    }
+
 }
@@ -0,0 +1,258 @@
+use std::time::SystemTime;
+
+use tracing::{debug, info, warn};
+
+use opendut_auth::registration::client::RegistrationClientRef;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::certificate::{ClientCertificateId, ClientCertificateRecord};
+use opendut_types::peer::decommission::{PeerDecommissionAction, PeerDecommissionOutcome, PeerDecommissionRecord, PeerDecommissionReport, PeerDecommissionStep};
+use opendut_types::peer::setup::{PeerSetupRecord, PeerSetupStatus};
+
+use crate::manager::peer_messaging_broker::PeerMessagingBrokerRef;
+use crate::resource::api::resources::Resources;
+use crate::resource::persistence::error::PersistenceError;
+use crate::resource::storage::ResourcesStorageApi;
+use crate::settings::vpn::Vpn;
+
+pub struct DecommissionPeerParams {
+    pub peer: PeerId,
+}
+
+/// A peer, whose decommissioning was persisted, but whose access outside of CARL was not revoked yet.
+/// See [`revoke_decommissioned_peer_access`].
+#[must_use]
+#[derive(Debug)]
+pub struct DecommissionedPeer {
+    pub peer_id: PeerId,
+    steps: Vec<PeerDecommissionStep>,
+}
+
+impl Resources<'_> {
+    /// Revokes the Setup-Strings and client certificates of a peer and records it as decommissioned, while keeping its descriptor.
+    /// The record makes CARL reject the peer's stream, even if it presents credentials, which remain valid, like OIDC tokens, which were issued before.
+    #[tracing::instrument(skip_all, level="trace")]
+    pub fn decommission_peer(&mut self, params: DecommissionPeerParams) -> Result<DecommissionedPeer, DecommissionPeerError> {
+        let peer_id = params.peer;
+
+        self.get::<PeerDescriptor>(peer_id)
+            .map_err(|source| DecommissionPeerError::Persistence { peer_id, source })?
+            .ok_or(DecommissionPeerError::PeerNotFound { peer_id })?;
+
+        debug!("Decommissioning peer <{peer_id}>.");
+
+        let mut steps = Vec::new();
+
+        let revoked_setups = self.revoke_pending_peer_setups(peer_id)
+            .map_err(|source| DecommissionPeerError::Persistence { peer_id, source })?;
+        steps.push(PeerDecommissionStep {
+            action: PeerDecommissionAction::RevokeSetupStrings,
+            outcome: if revoked_setups > 0 {
+                PeerDecommissionOutcome::Done
+            } else {
+                PeerDecommissionOutcome::Skipped { reason: String::from("No pending Setup-Strings") }
+            },
+        });
+
+        let revoked_certificates = self.revoke_client_certificates(peer_id, None)
+            .map_err(|source| DecommissionPeerError::Persistence { peer_id, source })?;
+        steps.push(PeerDecommissionStep {
+            action: PeerDecommissionAction::RevokeClientCertificates,
+            outcome: if revoked_certificates > 0 {
+                PeerDecommissionOutcome::Done
+            } else {
+                PeerDecommissionOutcome::Skipped { reason: String::from("No valid client certificates") }
+            },
+        });
+
+        self.insert(peer_id, PeerDecommissionRecord { peer_id, decommissioned_at: SystemTime::now() })
+            .map_err(|source| DecommissionPeerError::Persistence { peer_id, source })?;
+
+        Ok(DecommissionedPeer { peer_id, steps })
+    }
+}
+
+pub struct RevokeDecommissionedPeerAccessParams {
+    pub decommissioned: DecommissionedPeer,
+    pub vpn: Vpn,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+}
+
+/// Revokes the access of a decommissioned peer outside of CARL's persistence, so it is called after the decommissioning was committed.
+/// The steps are performed independently of each other and their outcome is reported, so that failed steps can be retried by decommissioning the peer again.
+#[tracing::instrument(skip_all, level="trace")]
+pub async fn revoke_decommissioned_peer_access(params: RevokeDecommissionedPeerAccessParams) -> PeerDecommissionReport {
+    let DecommissionedPeer { peer_id, mut steps } = params.decommissioned;
+
+    //the OIDC clients are deleted, so that EDGAR cannot obtain new tokens, while tokens issued before are rejected due to the decommission record
+    let outcome = match params.oidc_registration_client {
+        None => PeerDecommissionOutcome::Skipped { reason: String::from("Authentication is disabled") },
+        Some(registration_client) if registration_client.config.peer_credentials.is_some() =>
+            PeerDecommissionOutcome::Skipped { reason: String::from("Peers share statically configured credentials") },
+        Some(registration_client) => {
+            match registration_client.delete_client_by_resource_id(peer_id.into()).await {
+                Ok(_) => PeerDecommissionOutcome::Done,
+                Err(cause) => PeerDecommissionOutcome::Failed { cause: cause.to_string() },
+            }
+        }
+    };
+    steps.push(PeerDecommissionStep { action: PeerDecommissionAction::DeleteOidcClients, outcome });
+
+    let outcome = match params.vpn {
+        Vpn::Disabled => PeerDecommissionOutcome::Skipped { reason: String::from("VPN is disabled") },
+        Vpn::Enabled { vpn_client } => {
+            match vpn_client.delete_peer(peer_id).await {
+                Ok(()) => PeerDecommissionOutcome::Done,
+                Err(cause) => PeerDecommissionOutcome::Failed { cause: cause.to_string() },
+            }
+        }
+    };
+    steps.push(PeerDecommissionStep { action: PeerDecommissionAction::DeleteVpnPeer, outcome });
+
+    let outcome = match params.peer_messaging_broker.disconnect_peer(peer_id).await {
+        Ok(true) => PeerDecommissionOutcome::Done,
+        Ok(false) => PeerDecommissionOutcome::Skipped { reason: String::from("Peer is not connected") },
+        Err(cause) => PeerDecommissionOutcome::Failed { cause: cause.to_string() },
+    };
+    steps.push(PeerDecommissionStep { action: PeerDecommissionAction::DisconnectStream, outcome });
+
+    let report = PeerDecommissionReport { peer_id, steps };
+    if report.is_complete() {
+        info!("Successfully decommissioned peer <{peer_id}>.");
+    } else {
+        warn!("Decommissioning of peer <{peer_id}> is incomplete:\n{report:#?}");
+    }
+    report
+}
+
+impl Resources<'_> {
+    fn revoke_pending_peer_setups(&mut self, peer_id: PeerId) -> Result<usize, PersistenceError> {
+        let now = SystemTime::now();
+
+        let pending_setups = self.list::<PeerSetupRecord>()?
+            .into_values()
+            .filter(|setup| setup.peer_id == peer_id && setup.status(now) == PeerSetupStatus::Pending)
+            .collect::<Vec<_>>();
+        let count = pending_setups.len();
+
+        for setup in pending_setups {
+            debug!("Revoking Setup-String <{}> of decommissioned peer <{peer_id}>.", setup.id);
+            self.insert(setup.id, PeerSetupRecord { revoked_at: Some(now), ..setup })?;
+        }
+        Ok(count)
+    }
+
+    /// Revokes the valid client certificates of a peer, except for the given one, which e.g. was just issued.
+    pub(super) fn revoke_client_certificates(&mut self, peer_id: PeerId, except: Option<ClientCertificateId>) -> Result<usize, PersistenceError> {
+        let now = SystemTime::now();
+
+        let valid_certificates = self.list::<ClientCertificateRecord>()?
+            .into_values()
+            .filter(|certificate| certificate.peer_id == peer_id && certificate.is_valid(now) && Some(certificate.id) != except)
+            .collect::<Vec<_>>();
+        let count = valid_certificates.len();
+
+        for certificate in valid_certificates {
+            debug!("Revoking client certificate <{}> of peer <{peer_id}>.", certificate.id);
+            self.insert(certificate.id, ClientCertificateRecord { revoked_at: Some(now), ..certificate })?;
+        }
+        Ok(count)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DecommissionPeerError {
+    #[error("Peer <{peer_id}> could not be decommissioned, because a peer with that id does not exist!")]
+    PeerNotFound { peer_id: PeerId },
+    #[error("Error when accessing persistence while decommissioning peer <{peer_id}>")]
+    Persistence {
+        peer_id: PeerId,
+        #[source] source: PersistenceError,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use opendut_types::peer::setup::PeerSetupId;
+
+    use crate::manager::peer_manager::tests::create_peer_descriptor;
+    use crate::manager::peer_messaging_broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resource::manager::ResourceManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_revoke_pending_setup_strings_and_report_skipped_steps() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(&resource_manager), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_millis(200),
+            credentials_rotation_timeout: Duration::from_millis(200),
+        }).await;
+
+        let peer = create_peer_descriptor();
+        let peer_id = peer.id;
+        resource_manager.insert(peer_id, peer).await?;
+
+        let now = SystemTime::now();
+        let pending = PeerSetupRecord {
+            id: PeerSetupId::random(),
+            peer_id,
+            issued_at: now,
+            expires_at: now + Duration::from_secs(60),
+            used_at: None,
+            revoked_at: None,
+        };
+        resource_manager.insert(pending.id, Clone::clone(&pending)).await?;
+        let certificate = ClientCertificateRecord {
+            id: ClientCertificateId::random(),
+            peer_id,
+            issued_at: now,
+            expires_at: now + Duration::from_secs(60),
+            revoked_at: None,
+        };
+        resource_manager.insert(certificate.id, Clone::clone(&certificate)).await?;
+
+        let decommission = async |peer| -> anyhow::Result<PeerDecommissionReport> {
+            let decommissioned = resource_manager.resources_mut(async |resources| resources.decommission_peer(DecommissionPeerParams { peer })).await??;
+            let report = revoke_decommissioned_peer_access(RevokeDecommissionedPeerAccessParams {
+                decommissioned,
+                vpn: Vpn::Disabled,
+                oidc_registration_client: None,
+                peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            }).await;
+            Ok(report)
+        };
+
+        let report = decommission(peer_id).await?;
+
+        assert!(report.is_complete());
+        assert_that!(report.steps, elements_are![
+            matches_pattern!(PeerDecommissionStep { action: eq(&PeerDecommissionAction::RevokeSetupStrings), outcome: eq(&PeerDecommissionOutcome::Done) }),
+            matches_pattern!(PeerDecommissionStep { action: eq(&PeerDecommissionAction::RevokeClientCertificates), outcome: eq(&PeerDecommissionOutcome::Done) }),
+            matches_pattern!(PeerDecommissionStep { action: eq(&PeerDecommissionAction::DeleteOidcClients), outcome: matches_pattern!(PeerDecommissionOutcome::Skipped { .. }) }),
+            matches_pattern!(PeerDecommissionStep { action: eq(&PeerDecommissionAction::DeleteVpnPeer), outcome: matches_pattern!(PeerDecommissionOutcome::Skipped { .. }) }),
+            matches_pattern!(PeerDecommissionStep { action: eq(&PeerDecommissionAction::DisconnectStream), outcome: matches_pattern!(PeerDecommissionOutcome::Skipped { .. }) }),
+        ]);
+        let revoked = resource_manager.get::<PeerSetupRecord>(pending.id).await?.unwrap();
+        assert_that!(revoked.status(SystemTime::now()), eq(PeerSetupStatus::Revoked));
+        let revoked_certificate = resource_manager.get::<ClientCertificateRecord>(certificate.id).await?.unwrap();
+        assert_that!(revoked_certificate.revoked_at, some(anything()));
+        assert!(resource_manager.get::<PeerDescriptor>(peer_id).await?.is_some(), "Expected peer descriptor to be kept.");
+        assert!(resource_manager.get::<PeerDecommissionRecord>(peer_id).await?.is_some(), "Expected peer to be recorded as decommissioned.");
+
+        let report = decommission(peer_id).await?;
+        assert_that!(report.steps[0].outcome, matches_pattern!(PeerDecommissionOutcome::Skipped { .. }));
+        assert_that!(report.steps[1].outcome, matches_pattern!(PeerDecommissionOutcome::Skipped { .. }));
+
+        let result = resource_manager.resources_mut(async |resources|
+            resources.decommission_peer(DecommissionPeerParams { peer: PeerId::random() })
+        ).await?;
+        assert_that!(result, err(matches_pattern!(DecommissionPeerError::PeerNotFound { .. })));
+        Ok(())
+    }
+}
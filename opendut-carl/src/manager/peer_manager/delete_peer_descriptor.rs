@@ -4,16 +4,22 @@ use crate::resource::storage::ResourcesStorageApi;
 use crate::settings::vpn::Vpn;
 use opendut_auth::registration::client::RegistrationClientRef;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerName};
+use opendut_types::peer::certificate::ClientCertificateRecord;
+use opendut_types::peer::decommission::PeerDecommissionRecord;
+use opendut_types::peer::setup::PeerSetupRecord;
 use tracing::{debug, info, warn};
 use opendut_types::cluster::ClusterId;
+use opendut_types::resources::Revision;
 use opendut_types::peer::state::{PeerMemberState, PeerState};
-use crate::resource::api::resources::Resources;
+use crate::resource::api::resources::{Resources, RevisionConflict};
 use crate::resource::persistence::error::PersistenceError;
 
 pub struct DeletePeerDescriptorParams {
     pub vpn: Vpn,
     pub peer: PeerId,
     pub oidc_registration_client: Option<RegistrationClientRef>,
+    /// Only delete the peer descriptor, if it is still at this revision.
+    pub expected_revision: Option<Revision>,
 }
 
 impl Resources<'_> {
@@ -22,6 +28,17 @@ impl Resources<'_> {
 
         let peer_id = params.peer;
 
+        self.check_revision::<PeerDescriptor>(peer_id, params.expected_revision)
+            .map_err(|source| DeletePeerDescriptorError::Persistence { peer_id, peer_name: None, source })?
+            .map_err(|conflict| match conflict {
+                RevisionConflict { actual: None, .. } => DeletePeerDescriptorError::PeerNotFound { peer_id },
+                RevisionConflict { expected, actual: Some(actual) } => DeletePeerDescriptorError::RevisionConflict {
+                    peer_id,
+                    expected_revision: expected,
+                    actual_revision: actual,
+                },
+            })?;
+
         let peer_member_states = self.list_peer_member_states()
             .map_err(|source| DeletePeerDescriptorError::Persistence { peer_id, peer_name: None, source })?;
         let peer_member_state = peer_member_states.get(&peer_id);
@@ -37,6 +54,30 @@ impl Resources<'_> {
 
             let peer_name = &peer_descriptor.name;
 
+            let setup_ids = self.list::<PeerSetupRecord>()
+                .map_err(|source| DeletePeerDescriptorError::Persistence { peer_id, peer_name: Some(peer_name.clone()), source })?
+                .into_values()
+                .filter(|setup| setup.peer_id == peer_id)
+                .map(|setup| setup.id)
+                .collect::<Vec<_>>();
+            for setup_id in setup_ids {
+                self.remove::<PeerSetupRecord>(setup_id)
+                    .map_err(|source| DeletePeerDescriptorError::Persistence { peer_id, peer_name: Some(peer_name.clone()), source })?;
+            }
+
+            let certificate_ids = self.list::<ClientCertificateRecord>()
+                .map_err(|source| DeletePeerDescriptorError::Persistence { peer_id, peer_name: Some(peer_name.clone()), source })?
+                .into_values()
+                .filter(|certificate| certificate.peer_id == peer_id)
+                .map(|certificate| certificate.id)
+                .collect::<Vec<_>>();
+            for certificate_id in certificate_ids {
+                self.remove::<ClientCertificateRecord>(certificate_id)
+                    .map_err(|source| DeletePeerDescriptorError::Persistence { peer_id, peer_name: Some(peer_name.clone()), source })?;
+            }
+            self.remove::<PeerDecommissionRecord>(peer_id)
+                .map_err(|source| DeletePeerDescriptorError::Persistence { peer_id, peer_name: Some(peer_name.clone()), source })?;
+
             if let Some(registration_client) = params.oidc_registration_client {
                 let resource_id = peer_id.into();
                 debug!("Deleting OIDC client for peer '{peer_name}' <{peer_id}>.");
@@ -79,6 +120,12 @@ pub enum DeletePeerDescriptorError {
         peer_id: PeerId,
         cluster_id: ClusterId,
     },
+    #[error("Peer <{peer_id}> was modified since revision {expected_revision} and is not deleted! Reload the peer and check whether it should still be deleted.")]
+    RevisionConflict {
+        peer_id: PeerId,
+        expected_revision: Revision,
+        actual_revision: Revision,
+    },
     #[error(
         "Peer {peer} cannot be deleted in state '{actual_state}'! A peer can be deleted when: {required_states}",
         peer = PeerDisplay::new(&Some(peer_name.to_owned()), peer_id),
@@ -126,7 +173,7 @@ mod tests {
         resource_manager.insert(cluster.id, ClusterDeployment { id: cluster.id }).await?;
 
         // Act
-        let delete_peer_descriptor_params = DeletePeerDescriptorParams { vpn: Vpn::Disabled, peer: cluster.peer_a.id, oidc_registration_client: None };
+        let delete_peer_descriptor_params = DeletePeerDescriptorParams { vpn: Vpn::Disabled, peer: cluster.peer_a.id, oidc_registration_client: None, expected_revision: None };
         let result = resource_manager.resources_mut(async |resources|
             resources.delete_peer_descriptor(delete_peer_descriptor_params).await
         ).await?;
@@ -148,7 +195,7 @@ mod tests {
         let cluster = ClusterFixture::create(resource_manager.clone()).await?;
 
         // Act
-        let delete_peer_descriptor_params = DeletePeerDescriptorParams { vpn: Vpn::Disabled, peer: cluster.peer_a.id, oidc_registration_client: None };
+        let delete_peer_descriptor_params = DeletePeerDescriptorParams { vpn: Vpn::Disabled, peer: cluster.peer_a.id, oidc_registration_client: None, expected_revision: None };
         let result = resource_manager.resources_mut(async |resources|
             resources.delete_peer_descriptor(delete_peer_descriptor_params).await
         ).await??;
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, error, info};
+
+use opendut_auth::registration::client::RegistrationClientRef;
+use opendut_types::peer::setup::{PeerSetupId, PeerSetupRecord, PeerSetupStatus};
+
+use crate::manager::peer_manager::{invalidate_peer_setup_credentials, InvalidatePeerSetupCredentialsParams, RevokePeerSetupError, RevokePeerSetupParams};
+use crate::resource::manager::ResourceManagerRef;
+use crate::resource::persistence::error::{MapErrToInner, PersistenceError};
+use crate::resource::storage::ResourcesStorageApi;
+use crate::settings::vpn::Vpn;
+
+pub struct ExpirePeerSetupsParams {
+    pub resource_manager: ResourceManagerRef,
+    pub vpn: Vpn,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+}
+
+/// Revokes the Setup-Strings, which expired without being used, and invalidates the credentials contained in them.
+/// Otherwise, whoever obtained such a Setup-String could still connect to CARL and the VPN with its credentials.
+/// Returns the IDs of the revoked Setup-Strings.
+#[tracing::instrument(skip_all, level="trace")]
+pub async fn expire_peer_setups(params: ExpirePeerSetupsParams) -> Result<Vec<PeerSetupId>, ExpirePeerSetupsError> {
+    let ExpirePeerSetupsParams { resource_manager, vpn, oidc_registration_client } = params;
+
+    let revoked = resource_manager.resources_mut(async |resources| {
+        let now = SystemTime::now();
+        let expired = resources.list::<PeerSetupRecord>()
+            .map_err(|source| ExpirePeerSetupsError::Persistence { source })?
+            .into_values()
+            .filter(|setup| setup.status(now) == PeerSetupStatus::Expired)
+            .collect::<Vec<_>>();
+
+        let mut revoked = Vec::new();
+        for setup in expired {
+            debug!("Setup-String <{}> of peer <{}> expired without being used.", setup.id, setup.peer_id);
+            revoked.push(resources.revoke_peer_setup(RevokePeerSetupParams { setup_id: setup.id })?);
+        }
+        Ok(revoked)
+    }).await
+        .map_err_to_inner(|source| ExpirePeerSetupsError::Persistence { source })?;
+
+    let mut revoked_setup_ids = Vec::new();
+    for revoked in revoked {
+        let setup_id = revoked.setup.id;
+        if revoked.invalidate_credentials {
+            invalidate_peer_setup_credentials(InvalidatePeerSetupCredentialsParams {
+                setup_id,
+                peer_id: revoked.setup.peer_id,
+                vpn: Clone::clone(&vpn),
+                oidc_registration_client: oidc_registration_client.clone(),
+            }).await?;
+        }
+        revoked_setup_ids.push(setup_id);
+    }
+    Ok(revoked_setup_ids)
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ExpirePeerSetupsError {
+    #[error("Error when accessing persistence while revoking expired Setup-Strings")]
+    Persistence {
+        #[source] source: PersistenceError,
+    },
+    #[error(transparent)]
+    Revoke(#[from] RevokePeerSetupError),
+}
+
+/// Starts revoking expired Setup-Strings in the configured interval.
+pub fn spawn_peer_setup_expiry(resource_manager: ResourceManagerRef, vpn: Vpn, oidc_registration_client: Option<RegistrationClientRef>, options: PeerSetupExpiryOptions) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(options.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            let result = expire_peer_setups(ExpirePeerSetupsParams {
+                resource_manager: Arc::clone(&resource_manager),
+                vpn: Clone::clone(&vpn),
+                oidc_registration_client: oidc_registration_client.clone(),
+            }).await;
+            match result {
+                Ok(revoked) if revoked.is_empty() => {}
+                Ok(revoked) => info!("Revoked {} expired Setup-String(s).", revoked.len()),
+                Err(cause) => error!("Failed to revoke expired Setup-Strings:\n  {cause}"),
+            }
+        }
+    });
+}
+
+pub struct PeerSetupExpiryOptions {
+    pub interval: Duration,
+}
+
+impl PeerSetupExpiryOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let interval_ms = config.get::<u64>("peer.setup.expiry.check.interval.ms")?;
+
+        Ok(PeerSetupExpiryOptions {
+            interval: Duration::from_millis(interval_ms),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_types::peer::PeerId;
+
+    use crate::resource::manager::ResourceManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_revoke_expired_setup_strings_only() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let now = SystemTime::now();
+        let pending = PeerSetupRecord {
+            id: PeerSetupId::random(),
+            peer_id: PeerId::random(),
+            issued_at: now,
+            expires_at: now + Duration::from_secs(60),
+            used_at: None,
+            revoked_at: None,
+        };
+        let expired = PeerSetupRecord { id: PeerSetupId::random(), peer_id: PeerId::random(), expires_at: now - Duration::from_secs(1), ..Clone::clone(&pending) };
+        let used = PeerSetupRecord { id: PeerSetupId::random(), used_at: Some(now), ..Clone::clone(&expired) };
+        for setup in [&pending, &expired, &used] {
+            resource_manager.insert(setup.id, Clone::clone(setup)).await?;
+        }
+
+        let revoked = expire_peer_setups(ExpirePeerSetupsParams {
+            resource_manager: Arc::clone(&resource_manager),
+            vpn: Vpn::Disabled,
+            oidc_registration_client: None,
+        }).await?;
+
+        assert_that!(revoked, elements_are![eq(&expired.id)]);
+        let status = async |setup_id| resource_manager.get::<PeerSetupRecord>(setup_id).await.unwrap().unwrap().status(SystemTime::now());
+        assert_that!(status(expired.id).await, eq(PeerSetupStatus::Revoked));
+        assert_that!(status(pending.id).await, eq(PeerSetupStatus::Pending));
+        assert_that!(status(used.id).await, eq(PeerSetupStatus::Used));
+        Ok(())
+    }
+}
@@ -3,9 +3,12 @@ use crate::settings::vpn::Vpn;
 use opendut_auth::registration::client::RegistrationClientRef;
 use opendut_auth::registration::resources::UserId;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerName, PeerSetup};
+use opendut_types::peer::setup::{PeerSetupId, PeerSetupRecord, PeerSetupStatus};
+use opendut_types::peer::update::UpdateVerificationKey;
 use opendut_types::util::net::{AuthConfig, Certificate};
 use opendut_types::vpn::VpnPeerConfiguration;
 use pem::Pem;
+use std::time::{Duration, SystemTime};
 use tracing::{debug, error, info, warn};
 use url::Url;
 use crate::resource::api::resources::Resources;
@@ -16,13 +19,18 @@ pub struct GeneratePeerSetupParams {
     pub carl_url: Url,
     pub ca: Pem,
     pub vpn: Vpn,
+    pub update_verification_key: Option<UpdateVerificationKey>,
     pub oidc_registration_client: Option<RegistrationClientRef>,
+    /// Whether the peer requests a client certificate for authenticating via mutual TLS, when redeeming the Setup-String.
+    pub request_client_certificate: bool,
     pub user_id: UserId,
+    /// How long the Setup-String may be used for setting up EDGAR.
+    pub expiration: Duration,
 }
 
 impl Resources<'_> {
     #[tracing::instrument(skip_all, level="trace")]
-    pub async fn generate_peer_setup(&self, params: GeneratePeerSetupParams) -> Result<PeerSetup, GeneratePeerSetupError> {
+    pub async fn generate_peer_setup(&mut self, params: GeneratePeerSetupParams) -> Result<PeerSetup, GeneratePeerSetupError> {
 
         let peer_id = params.peer;
 
@@ -62,14 +70,55 @@ impl Resources<'_> {
             }
         };
 
+        let setup_record = self.record_peer_setup(peer_id, params.expiration)
+            .map_err(|source| GeneratePeerSetupError::Persistence { peer_id, source })?;
+
+        // The peer is set up anew, so whoever holds a client certificate of its previous setup must not connect as it anymore.
+        let revoked_certificates = self.revoke_client_certificates(peer_id, None)
+            .map_err(|source| GeneratePeerSetupError::Persistence { peer_id, source })?;
+        if revoked_certificates > 0 {
+            debug!("Revoked {revoked_certificates} client certificate(s) of peer <{peer_id}>, as a new Setup-String was generated for it.");
+        }
+
         Ok(PeerSetup {
             id: peer_id,
             carl: params.carl_url,
             ca: Certificate(params.ca),
             auth_config,
             vpn: vpn_config,
+            update_verification_key: params.update_verification_key,
+            setup_id: Some(setup_record.id),
+            request_client_certificate: params.request_client_certificate,
         })
     }
+
+    /// Records a newly issued Setup-String. Pending Setup-Strings of the same peer are revoked,
+    /// as generating a new Setup-String replaces the credentials contained in them.
+    fn record_peer_setup(&mut self, peer_id: PeerId, expiration: Duration) -> Result<PeerSetupRecord, PersistenceError> {
+        let now = SystemTime::now();
+
+        let superseded_setups = self.list::<PeerSetupRecord>()?
+            .into_values()
+            .filter(|setup| setup.peer_id == peer_id && setup.status(now) == PeerSetupStatus::Pending)
+            .collect::<Vec<_>>();
+
+        for setup in superseded_setups {
+            debug!("Revoking Setup-String <{}> of peer <{peer_id}>, as it is superseded by a new one.", setup.id);
+            self.insert(setup.id, PeerSetupRecord { revoked_at: Some(now), ..setup })?;
+        }
+
+        let setup_record = PeerSetupRecord {
+            id: PeerSetupId::random(),
+            peer_id,
+            issued_at: now,
+            expires_at: now + expiration,
+            used_at: None,
+            revoked_at: None,
+        };
+        self.insert(setup_record.id, Clone::clone(&setup_record))?;
+
+        Ok(setup_record)
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
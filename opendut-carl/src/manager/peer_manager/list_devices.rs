@@ -60,6 +60,7 @@ mod tests {
             resources.store_peer_descriptor(StorePeerDescriptorParams {
                 vpn: Vpn::Disabled,
                 peer_descriptor: peer.descriptor,
+                expected_revision: None,
             }).await
         }).await??;
 
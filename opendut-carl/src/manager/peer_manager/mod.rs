@@ -13,12 +13,30 @@ pub mod list_devices;
 pub mod generate_peer_setup;
 pub use generate_peer_setup::*;
 
+pub mod redeem_peer_setup;
+pub use redeem_peer_setup::*;
+
+pub mod revoke_peer_setup;
+pub use revoke_peer_setup::*;
+
+pub mod expire_peer_setups;
+pub use expire_peer_setups::*;
+
+pub mod decommission_peer;
+pub use decommission_peer::*;
+
+pub mod rotate_peer_credentials;
+pub use rotate_peer_credentials::*;
+
 pub mod generate_cleo_setup;
 pub use generate_cleo_setup::*;
 
 pub mod assign_cluster;
 pub use assign_cluster::*;
 
+pub mod unassign_cluster;
+pub use unassign_cluster::*;
+
 pub mod list_peer_member_states;
 
 pub mod get_peer_member_state;
@@ -0,0 +1,288 @@
+use std::time::SystemTime;
+
+use tracing::{debug, error, info};
+
+use opendut_auth::registration::client::{RegistrationClientError, RegistrationClientRef};
+use opendut_auth::registration::resources::UserId;
+use opendut_types::audit::AuditActor;
+use opendut_types::peer::PeerId;
+use opendut_types::peer::decommission::PeerDecommissionRecord;
+use opendut_types::peer::setup::{PeerSetupId, PeerSetupRecord, PeerSetupStatus};
+use opendut_types::util::net::{Certificate, ClientCredentials};
+
+use crate::auth::client_certificate::{ClientCertificateAuthorityRef, IssueClientCertificateError, SignedClientCertificate};
+use crate::resource::api::resources::Resources;
+use crate::resource::persistence::error::PersistenceError;
+use crate::resource::storage::ResourcesStorageApi;
+
+pub struct RedeemPeerSetupParams {
+    pub setup_id: PeerSetupId,
+    pub peer_id: PeerId,
+    /// DER-encoded request for a client certificate, signed with the key EDGAR generated.
+    pub certificate_signing_request: Option<Vec<u8>>,
+    /// If present, the peer has to request a client certificate for authenticating via mutual TLS.
+    pub client_certificate_authority: Option<ClientCertificateAuthorityRef>,
+}
+
+pub struct RedeemedPeerSetup {
+    pub setup: PeerSetupRecord,
+    /// The user, who generated the Setup-String and for whom the replacing OIDC client is registered.
+    pub user_id: UserId,
+    /// The client certificate issued for the key of the peer. Only present, if CARL issues client certificates.
+    pub client_certificate: Option<Certificate>,
+}
+
+pub struct ReplaceSetupCredentialsParams {
+    pub setup_id: PeerSetupId,
+    pub peer_id: PeerId,
+    pub user_id: UserId,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+}
+
+impl Resources<'_> {
+    /// Marks a Setup-String as used, so that it cannot be used for setting up another EDGAR.
+    /// A decommissioned peer is thereby commissioned again, as its Setup-String was issued after the decommissioning.
+    /// If CARL issues client certificates, the requested one is issued and all previous ones of the peer are revoked.
+    /// The OIDC client contained in it is replaced via [`replace_setup_credentials`], once this is committed.
+    #[tracing::instrument(skip_all, level="trace")]
+    pub fn redeem_peer_setup(&mut self, params: RedeemPeerSetupParams) -> Result<RedeemedPeerSetup, RedeemPeerSetupError> {
+        let RedeemPeerSetupParams { setup_id, peer_id, certificate_signing_request, client_certificate_authority } = params;
+
+        let setup = self.get::<PeerSetupRecord>(setup_id)
+            .map_err(|source| RedeemPeerSetupError::Persistence { setup_id, source })?
+            .filter(|setup| setup.peer_id == peer_id) //do not reveal Setup-Strings of other peers
+            .ok_or(RedeemPeerSetupError::SetupNotFound { setup_id })?;
+
+        let now = SystemTime::now();
+        match setup.status(now) {
+            PeerSetupStatus::Pending => {}
+            PeerSetupStatus::Used => return Err(RedeemPeerSetupError::AlreadyUsed { setup_id }),
+            PeerSetupStatus::Expired => return Err(RedeemPeerSetupError::Expired { setup_id }),
+            PeerSetupStatus::Revoked => return Err(RedeemPeerSetupError::Revoked { setup_id }),
+        }
+
+        let user_id = match self.creator_of::<PeerSetupRecord>(setup_id)
+            .map_err(|source| RedeemPeerSetupError::Persistence { setup_id, source })? {
+            Some(AuditActor::User { name }) => name,
+            Some(creator) => creator.to_string(),
+            None => AuditActor::System.to_string(),
+        };
+
+        let client_certificate = match client_certificate_authority {
+            None => None,
+            Some(authority) => {
+                let certificate_signing_request = certificate_signing_request
+                    .ok_or(RedeemPeerSetupError::CertificateRequestMissing { setup_id })?;
+                let SignedClientCertificate { record, certificate } = authority.issue_for_request(peer_id, &certificate_signing_request)
+                    .map_err(|source| RedeemPeerSetupError::ClientCertificate { setup_id, source })?;
+
+                self.revoke_client_certificates(peer_id, None)
+                    .map_err(|source| RedeemPeerSetupError::Persistence { setup_id, source })?;
+                self.insert(record.id, record)
+                    .map_err(|source| RedeemPeerSetupError::Persistence { setup_id, source })?;
+                Some(certificate)
+            }
+        };
+
+        debug!("Marking Setup-String <{setup_id}> of peer <{peer_id}> as used.");
+        let setup = PeerSetupRecord { used_at: Some(now), ..setup };
+        self.insert(setup_id, Clone::clone(&setup))
+            .map_err(|source| RedeemPeerSetupError::Persistence { setup_id, source })?;
+
+        let decommission = self.remove::<PeerDecommissionRecord>(peer_id)
+            .map_err(|source| RedeemPeerSetupError::Persistence { setup_id, source })?;
+        if decommission.is_some() {
+            info!("Peer <{peer_id}> was decommissioned and is commissioned again with Setup-String <{setup_id}>.");
+        }
+
+        info!("Peer <{peer_id}> was set up with Setup-String <{setup_id}>.");
+        Ok(RedeemedPeerSetup { setup, user_id: UserId { value: user_id }, client_certificate })
+    }
+}
+
+/// Replaces the OIDC client contained in a redeemed Setup-String with a new one, which only the redeeming EDGAR receives.
+/// Whoever else obtained the Setup-String can thereby not connect to CARL with its credentials.
+/// Returns `None`, if peers do not get individual OIDC clients.
+///
+/// If registering the new client fails, the previous one is deleted nonetheless, so that the Setup-String's credentials
+/// never outlive its redemption. The peer then needs a new Setup-String.
+#[tracing::instrument(skip_all, level="trace")]
+pub async fn replace_setup_credentials(params: ReplaceSetupCredentialsParams) -> Result<Option<ClientCredentials>, RedeemPeerSetupError> {
+    let ReplaceSetupCredentialsParams { setup_id, peer_id, user_id, oidc_registration_client } = params;
+
+    let Some(registration_client) = oidc_registration_client else {
+        return Ok(None);
+    };
+    if registration_client.config.peer_credentials.is_some() { //statically configured credentials are shared between peers
+        return Ok(None);
+    }
+
+    debug!("Registering OIDC client, which replaces the one in Setup-String <{setup_id}> of peer <{peer_id}>.");
+    let credentials = match registration_client.register_additional_client_for_user(peer_id.into(), user_id).await {
+        Ok(credentials) => credentials,
+        Err(source) => {
+            let _ignore_result = registration_client.delete_client_by_resource_id(peer_id.into()).await
+                .inspect_err(|cause| error!("Failed to delete OIDC client contained in Setup-String <{setup_id}> of peer <{peer_id}>:\n  {cause}"));
+            return Err(RedeemPeerSetupError::AuthRegistration { setup_id, source });
+        }
+    };
+
+    debug!("Deleting OIDC client contained in Setup-String <{setup_id}> of peer <{peer_id}>.");
+    if let Err(source) = registration_client.delete_other_clients_by_resource_id(peer_id.into(), &credentials.client_id).await {
+        let _ignore_result = registration_client.delete_client(&credentials.client_id.0).await
+            .inspect_err(|cause| error!("Failed to delete replacing OIDC client <{}> of peer <{peer_id}>:\n  {cause}", credentials.client_id.0));
+        return Err(RedeemPeerSetupError::AuthRegistration { setup_id, source });
+    }
+
+    Ok(Some(credentials))
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RedeemPeerSetupError {
+    #[error("Setup-String <{setup_id}> not found")]
+    SetupNotFound { setup_id: PeerSetupId },
+    #[error("Setup-String <{setup_id}> was already used")]
+    AlreadyUsed { setup_id: PeerSetupId },
+    #[error("Setup-String <{setup_id}> is expired")]
+    Expired { setup_id: PeerSetupId },
+    #[error("Setup-String <{setup_id}> was revoked")]
+    Revoked { setup_id: PeerSetupId },
+    #[error("Error when accessing persistence while redeeming Setup-String <{setup_id}>")]
+    Persistence {
+        setup_id: PeerSetupId,
+        #[source] source: PersistenceError,
+    },
+    #[error("Setup-String <{setup_id}> was redeemed without requesting a client certificate, which CARL requires")]
+    CertificateRequestMissing { setup_id: PeerSetupId },
+    #[error("Error when issuing the client certificate while redeeming Setup-String <{setup_id}>")]
+    ClientCertificate {
+        setup_id: PeerSetupId,
+        #[source] source: IssueClientCertificateError,
+    },
+    #[error("Error when replacing the OIDC client while redeeming Setup-String <{setup_id}>")]
+    AuthRegistration {
+        setup_id: PeerSetupId,
+        #[source] source: RegistrationClientError,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+    use opendut_types::peer::certificate::ClientCertificateRecord;
+    use rcgen::{CertificateParams, KeyPair};
+
+    use crate::auth::client_certificate;
+    use crate::auth::client_certificate::tests::create_client_certificate_authority;
+    use crate::resource::manager::ResourceManager;
+
+    use super::*;
+
+    fn pending_setup(peer_id: PeerId) -> PeerSetupRecord {
+        let now = SystemTime::now();
+        PeerSetupRecord {
+            id: PeerSetupId::random(),
+            peer_id,
+            issued_at: now,
+            expires_at: now + Duration::from_secs(60),
+            used_at: None,
+            revoked_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_redeem_a_setup_string_only_once() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer_id = PeerId::random();
+        let setup = pending_setup(peer_id);
+        resource_manager.insert(setup.id, Clone::clone(&setup)).await?;
+
+        let params = || RedeemPeerSetupParams { setup_id: setup.id, peer_id, certificate_signing_request: None, client_certificate_authority: None };
+
+        let result = resource_manager.resources_mut(async |resources| resources.redeem_peer_setup(params())).await??;
+        assert_that!(result.setup.used_at, some(anything()));
+
+        let result = resource_manager.resources_mut(async |resources| resources.redeem_peer_setup(params())).await?;
+        assert_that!(result, err(matches_pattern!(RedeemPeerSetupError::AlreadyUsed { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_redeem_an_expired_or_foreign_setup_string() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer_id = PeerId::random();
+        let expired = PeerSetupRecord { expires_at: SystemTime::now() - Duration::from_secs(1), ..pending_setup(peer_id) };
+        let foreign = pending_setup(PeerId::random());
+        resource_manager.insert(expired.id, Clone::clone(&expired)).await?;
+        resource_manager.insert(foreign.id, Clone::clone(&foreign)).await?;
+
+        let result = resource_manager.resources_mut(async |resources|
+            resources.redeem_peer_setup(RedeemPeerSetupParams { setup_id: expired.id, peer_id, certificate_signing_request: None, client_certificate_authority: None })
+        ).await?;
+        assert_that!(result, err(matches_pattern!(RedeemPeerSetupError::Expired { .. })));
+
+        let result = resource_manager.resources_mut(async |resources|
+            resources.redeem_peer_setup(RedeemPeerSetupParams { setup_id: foreign.id, peer_id, certificate_signing_request: None, client_certificate_authority: None })
+        ).await?;
+        assert_that!(result, err(matches_pattern!(RedeemPeerSetupError::SetupNotFound { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_issue_requested_client_certificate_and_revoke_previous_ones() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let authority = Arc::new(create_client_certificate_authority()?);
+        let peer_id = PeerId::random();
+        let setup = pending_setup(peer_id);
+        resource_manager.insert(setup.id, Clone::clone(&setup)).await?;
+        let previous = authority.issue(peer_id)?.record;
+        resource_manager.insert(previous.id, Clone::clone(&previous)).await?;
+
+        let request = CertificateParams::default().serialize_request(&KeyPair::generate()?)?;
+        let redeemed = resource_manager.resources_mut(async |resources|
+            resources.redeem_peer_setup(RedeemPeerSetupParams {
+                setup_id: setup.id,
+                peer_id,
+                certificate_signing_request: Some(request.der().to_vec()),
+                client_certificate_authority: Some(Arc::clone(&authority)),
+            })
+        ).await??;
+
+        let certificate = redeemed.client_certificate.expect("client certificate should have been issued");
+        let (certified_peer, certificate_id) = client_certificate::peer_of_certificate(certificate.0.contents()).unwrap();
+        assert_that!(certified_peer, eq(peer_id));
+
+        let issued = resource_manager.get::<ClientCertificateRecord>(certificate_id).await?;
+        assert_that!(issued, some(field!(ClientCertificateRecord.revoked_at, none())));
+        let previous = resource_manager.get::<ClientCertificateRecord>(previous.id).await?;
+        assert_that!(previous, some(field!(ClientCertificateRecord.revoked_at, some(anything()))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_redeem_a_setup_string_without_requesting_a_required_client_certificate() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let authority = Arc::new(create_client_certificate_authority()?);
+        let peer_id = PeerId::random();
+        let setup = pending_setup(peer_id);
+        resource_manager.insert(setup.id, Clone::clone(&setup)).await?;
+
+        let result = resource_manager.resources_mut(async |resources|
+            resources.redeem_peer_setup(RedeemPeerSetupParams {
+                setup_id: setup.id,
+                peer_id,
+                certificate_signing_request: None,
+                client_certificate_authority: Some(Arc::clone(&authority)),
+            })
+        ).await?;
+        assert_that!(result, err(matches_pattern!(RedeemPeerSetupError::CertificateRequestMissing { .. })));
+
+        let setup = resource_manager.get::<PeerSetupRecord>(setup.id).await?;
+        assert_that!(setup, some(field!(PeerSetupRecord.used_at, none())));
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use std::ops::Not;
+use std::time::SystemTime;
+
+use tracing::{debug, info};
+
+use opendut_auth::registration::client::RegistrationClientRef;
+use opendut_types::peer::PeerId;
+use opendut_types::peer::setup::{PeerSetupId, PeerSetupRecord, PeerSetupStatus};
+
+use crate::resource::api::resources::Resources;
+use crate::resource::persistence::error::PersistenceError;
+use crate::resource::storage::ResourcesStorageApi;
+use crate::settings::vpn::Vpn;
+
+pub struct RevokePeerSetupParams {
+    pub setup_id: PeerSetupId,
+}
+
+pub struct RevokedPeerSetup {
+    pub setup: PeerSetupRecord,
+    /// Whether the credentials contained in the Setup-String are still in use by the peer and need to be invalidated
+    /// via [`invalidate_peer_setup_credentials`], once the revocation is committed.
+    pub invalidate_credentials: bool,
+}
+
+pub struct InvalidatePeerSetupCredentialsParams {
+    pub setup_id: PeerSetupId,
+    pub peer_id: PeerId,
+    pub vpn: Vpn,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+}
+
+impl Resources<'_> {
+    /// Revokes a Setup-String, which was not used yet.
+    /// The credentials contained in it are not invalidated here, since that cannot be rolled back together with the transaction.
+    #[tracing::instrument(skip_all, level="trace")]
+    pub fn revoke_peer_setup(&mut self, params: RevokePeerSetupParams) -> Result<RevokedPeerSetup, RevokePeerSetupError> {
+        let setup_id = params.setup_id;
+
+        let setup = self.get::<PeerSetupRecord>(setup_id)
+            .map_err(|source| RevokePeerSetupError::Persistence { setup_id, source })?
+            .ok_or(RevokePeerSetupError::SetupNotFound { setup_id })?;
+        let peer_id = setup.peer_id;
+
+        let now = SystemTime::now();
+        match setup.status(now) {
+            PeerSetupStatus::Pending | PeerSetupStatus::Expired => {}
+            PeerSetupStatus::Used => return Err(RevokePeerSetupError::AlreadyUsed { setup_id }),
+            PeerSetupStatus::Revoked => return Err(RevokePeerSetupError::AlreadyRevoked { setup_id }),
+        }
+
+        let is_latest_setup_of_peer = self.list::<PeerSetupRecord>()
+            .map_err(|source| RevokePeerSetupError::Persistence { setup_id, source })?
+            .into_values()
+            .filter(|other| other.peer_id == peer_id)
+            .all(|other| other.issued_at <= setup.issued_at);
+
+        debug!("Revoking Setup-String <{setup_id}> of peer <{peer_id}>.");
+        let setup = PeerSetupRecord { revoked_at: Some(now), ..setup };
+        self.insert(setup_id, Clone::clone(&setup))
+            .map_err(|source| RevokePeerSetupError::Persistence { setup_id, source })?;
+
+        if is_latest_setup_of_peer.not() {
+            debug!("Setup-String <{setup_id}> of peer <{peer_id}> was superseded by a newer one. Not invalidating the credentials of the newer one.");
+        }
+
+        info!("Successfully revoked Setup-String <{setup_id}> of peer <{peer_id}>.");
+        Ok(RevokedPeerSetup { setup, invalidate_credentials: is_latest_setup_of_peer })
+    }
+}
+
+/// Deletes the OIDC client and revokes the VPN configuration, which were issued with a revoked Setup-String.
+/// Runs after the revocation was committed. If it fails, the Setup-String stays revoked and invalidating can be retried
+/// by revoking the peer's credentials otherwise, e.g. by generating a new Setup-String.
+#[tracing::instrument(skip_all, level="trace")]
+pub async fn invalidate_peer_setup_credentials(params: InvalidatePeerSetupCredentialsParams) -> Result<(), RevokePeerSetupError> {
+    let InvalidatePeerSetupCredentialsParams { setup_id, peer_id, vpn, oidc_registration_client } = params;
+
+    if let Some(registration_client) = oidc_registration_client {
+        if registration_client.config.peer_credentials.is_none() { //statically configured credentials are shared between peers
+            debug!("Deleting OIDC client issued with Setup-String <{setup_id}> of peer <{peer_id}>.");
+            registration_client.delete_client_by_resource_id(peer_id.into())
+                .await
+                .map_err(|source| RevokePeerSetupError::AuthRegistration { setup_id, source })?;
+        }
+    }
+
+    if let Vpn::Enabled { vpn_client } = vpn {
+        debug!("Revoking VPN configuration issued with Setup-String <{setup_id}> of peer <{peer_id}>.");
+        vpn_client.revoke_vpn_peer_configuration(peer_id)
+            .await
+            .map_err(|source| RevokePeerSetupError::VpnClient { setup_id, source })?;
+    }
+
+    info!("Invalidated the credentials issued with Setup-String <{setup_id}> of peer <{peer_id}>.");
+    Ok(())
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RevokePeerSetupError {
+    #[error("Setup-String <{setup_id}> not found")]
+    SetupNotFound { setup_id: PeerSetupId },
+    #[error("Setup-String <{setup_id}> was already used")]
+    AlreadyUsed { setup_id: PeerSetupId },
+    #[error("Setup-String <{setup_id}> is already revoked")]
+    AlreadyRevoked { setup_id: PeerSetupId },
+    #[error("Error when accessing persistence while revoking Setup-String <{setup_id}>")]
+    Persistence {
+        setup_id: PeerSetupId,
+        #[source] source: PersistenceError,
+    },
+    #[error("Error when removing registration in authentication provider while revoking Setup-String <{setup_id}>")]
+    AuthRegistration {
+        setup_id: PeerSetupId,
+        #[source] source: opendut_auth::registration::client::RegistrationClientError,
+    },
+    #[error("Error when revoking VPN configuration while revoking Setup-String <{setup_id}>")]
+    VpnClient {
+        setup_id: PeerSetupId,
+        #[source] source: opendut_vpn::RevokeVpnPeerConfigurationError,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use opendut_types::peer::PeerId;
+
+    use crate::resource::manager::ResourceManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_revoke_a_pending_setup_string_but_not_a_used_one() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let now = SystemTime::now();
+        let pending = PeerSetupRecord {
+            id: PeerSetupId::random(),
+            peer_id: PeerId::random(),
+            issued_at: now,
+            expires_at: now + Duration::from_secs(60),
+            used_at: None,
+            revoked_at: None,
+        };
+        let used = PeerSetupRecord { id: PeerSetupId::random(), used_at: Some(now), ..Clone::clone(&pending) };
+        resource_manager.insert(pending.id, Clone::clone(&pending)).await?;
+        resource_manager.insert(used.id, Clone::clone(&used)).await?;
+
+        let params = |setup_id| RevokePeerSetupParams { setup_id };
+
+        let result = resource_manager.resources_mut(async |resources| resources.revoke_peer_setup(params(pending.id))).await??;
+        assert_that!(result.setup.status(now), eq(PeerSetupStatus::Revoked));
+
+        let result = resource_manager.resources_mut(async |resources| resources.revoke_peer_setup(params(pending.id))).await?;
+        assert_that!(result, err(matches_pattern!(RevokePeerSetupError::AlreadyRevoked { .. })));
+
+        let result = resource_manager.resources_mut(async |resources| resources.revoke_peer_setup(params(used.id))).await?;
+        assert_that!(result, err(matches_pattern!(RevokePeerSetupError::AlreadyUsed { .. })));
+        Ok(())
+    }
+}
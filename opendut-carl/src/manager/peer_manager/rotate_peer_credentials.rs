@@ -0,0 +1,389 @@
+use std::time::SystemTime;
+
+use tracing::{debug, error, info};
+
+use opendut_auth::registration::client::RegistrationClientRef;
+use opendut_auth::registration::resources::UserId;
+use opendut_types::audit::AuditActor;
+use opendut_types::peer::certificate::{ClientCertificateId, ClientCertificateRecord, RotatedPeerCredentials};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+
+use crate::auth::client_certificate::{ClientCertificateAuthorityRef, IssuedClientCertificate};
+use crate::manager::peer_messaging_broker::{PeerMessagingBrokerRef, RotateCredentialsError, RotatedCredentials, UnconfirmedCredentialsRotation};
+use crate::resource::manager::ResourceManagerRef;
+use crate::resource::persistence::error::{MapErrToInner, PersistenceError};
+use crate::resource::storage::ResourcesStorageApi;
+
+pub struct RotatePeerCredentialsParams {
+    pub peer: PeerId,
+    /// Recorded in the audit log as the one who issued and revoked the client certificates.
+    pub actor: AuditActor,
+    pub resource_manager: ResourceManagerRef,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+    /// If present, a new client certificate is issued to the peer.
+    pub client_certificate_authority: Option<ClientCertificateAuthorityRef>,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub user_id: UserId,
+}
+
+/// Issues new OIDC client credentials and a new client certificate for a connected peer and sends them to its EDGAR.
+/// The previous credentials are only deleted, respectively revoked, after EDGAR confirmed that it stored the new ones,
+/// so that the peer keeps working, if the rotation fails.
+///
+/// If EDGAR received the new credentials, but it is unknown whether it stored them, both stay valid,
+/// until EDGAR reconnects with one of them. See [`resolve_unconfirmed_credentials_rotation`].
+#[tracing::instrument(skip_all, level="trace")]
+pub async fn rotate_peer_credentials(params: RotatePeerCredentialsParams) -> Result<RotatedPeerCredentials, RotatePeerCredentialsError> {
+    let peer_id = params.peer;
+
+    params.resource_manager.get::<PeerDescriptor>(peer_id).await
+        .map_err(|source| RotatePeerCredentialsError::Persistence { peer_id, source })?
+        .ok_or(RotatePeerCredentialsError::PeerNotFound { peer_id })?;
+
+    let registration_client = match params.oidc_registration_client {
+        Some(registration_client) if registration_client.config.peer_credentials.is_none() => Some(registration_client),
+        Some(_) if params.client_certificate_authority.is_none() =>
+            return Err(RotatePeerCredentialsError::NotSupported { peer_id, reason: String::from("Peers share statically configured credentials.") }),
+        None if params.client_certificate_authority.is_none() =>
+            return Err(RotatePeerCredentialsError::NotSupported { peer_id, reason: String::from("Authentication is disabled.") }),
+        _ => None,
+    };
+
+    let oidc = match &registration_client {
+        None => None,
+        Some(registration_client) => {
+            debug!("Registering new OIDC client for peer <{peer_id}>.");
+            let credentials = registration_client.register_additional_client_for_user(peer_id.into(), params.user_id).await
+                .map_err(|source| RotatePeerCredentialsError::AuthRegistration { peer_id, source })?;
+            Some(credentials)
+        }
+    };
+    let client_id = oidc.as_ref().map(|credentials| Clone::clone(&credentials.client_id));
+
+    let issued_certificate = match &params.client_certificate_authority {
+        None => None,
+        Some(authority) => {
+            let IssuedClientCertificate { record, client_certificate } = authority.issue(peer_id)
+                .map_err(|cause| RotatePeerCredentialsError::IssueClientCertificate { peer_id, cause: cause.to_string() })?;
+            params.resource_manager.resources_mut_as(Clone::clone(&params.actor), async |resources| resources.insert(record.id, Clone::clone(&record))).await
+                .map_err_to_inner(std::convert::identity)
+                .map_err(|source| RotatePeerCredentialsError::Persistence { peer_id, source })?;
+            Some((record, client_certificate))
+        }
+    };
+    let (certificate_record, client_certificate) = issued_certificate.unzip();
+
+    let credentials = RotatedCredentials { oidc, client_certificate };
+    if let Err(cause) = params.peer_messaging_broker.rotate_credentials(peer_id, credentials).await {
+        if let RotateCredentialsError::Timeout { .. } | RotateCredentialsError::Unconfirmed { .. } = cause {
+            debug!("Keeping the previous and new credentials of peer <{peer_id}> until it reconnects with one of them.");
+            params.peer_messaging_broker.remember_unconfirmed_credentials_rotation(peer_id, UnconfirmedCredentialsRotation {
+                client_id: Clone::clone(&client_id),
+                client_certificate_id: certificate_record.as_ref().map(|record| record.id),
+            }).await;
+            return Err(RotatePeerCredentialsError::Unconfirmed { peer_id, cause });
+        }
+
+        if let (Some(registration_client), Some(client_id)) = (&registration_client, &client_id) {
+            debug!("Deleting OIDC client <{}>, since rotating the credentials of peer <{peer_id}> failed.", client_id.0);
+            let _ignore_result = registration_client.delete_client(&client_id.0).await
+                .inspect_err(|cause| error!("Failed to delete unused OIDC client <{}> of peer <{peer_id}>:\n  {cause}", client_id.0));
+        }
+        if let Some(record) = certificate_record {
+            debug!("Revoking client certificate <{}>, since rotating the credentials of peer <{peer_id}> failed.", record.id);
+            let _ignore_result = params.resource_manager.resources_mut_as(Clone::clone(&params.actor), async |resources|
+                resources.insert(record.id, ClientCertificateRecord { revoked_at: Some(SystemTime::now()), ..record })
+            ).await
+                .map_err_to_inner(std::convert::identity)
+                .inspect_err(|cause| error!("Failed to revoke unused client certificate of peer <{peer_id}>:\n  {cause}"));
+        }
+
+        return Err(match cause {
+            RotateCredentialsError::PeerNotConnected { .. } => RotatePeerCredentialsError::PeerNotConnected { peer_id },
+            RotateCredentialsError::AlreadyPending { .. } => RotatePeerCredentialsError::AlreadyInProgress { peer_id },
+            cause => RotatePeerCredentialsError::Push { peer_id, cause },
+        });
+    }
+
+    if let (Some(registration_client), Some(client_id)) = (&registration_client, &client_id) {
+        debug!("Deleting previous OIDC clients of peer <{peer_id}>.");
+        registration_client.delete_other_clients_by_resource_id(peer_id.into(), client_id).await
+            .map_err(|source| RotatePeerCredentialsError::AuthRegistration { peer_id, source })?;
+    }
+
+    let client_certificate_id = certificate_record.map(|record| record.id);
+    if let Some(certificate_id) = client_certificate_id {
+        debug!("Revoking previous client certificates of peer <{peer_id}>.");
+        params.resource_manager.resources_mut_as(params.actor, async |resources| resources.revoke_client_certificates(peer_id, Some(certificate_id))).await
+            .map_err_to_inner(std::convert::identity)
+            .map_err(|source| RotatePeerCredentialsError::Persistence { peer_id, source })?;
+    }
+
+    info!("Successfully rotated credentials of peer <{peer_id}>.");
+    Ok(RotatedPeerCredentials { client_id, client_certificate_id })
+}
+
+/// The credentials, with which a peer authenticated when opening its stream.
+pub struct PresentedCredentials {
+    pub client_id: Option<String>,
+    pub client_certificate_id: Option<ClientCertificateId>,
+}
+
+pub struct ResolveUnconfirmedCredentialsRotationParams {
+    pub peer: PeerId,
+    pub rotation: UnconfirmedCredentialsRotation,
+    pub presented: PresentedCredentials,
+    pub resource_manager: ResourceManagerRef,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+}
+
+/// Invalidates either the previous or the new credentials of a peer, which did not confirm a rotation,
+/// depending on which ones it presented when reconnecting.
+#[tracing::instrument(skip_all, level="trace")]
+pub async fn resolve_unconfirmed_credentials_rotation(params: ResolveUnconfirmedCredentialsRotationParams) -> Result<(), RotatePeerCredentialsError> {
+    let ResolveUnconfirmedCredentialsRotationParams { peer: peer_id, rotation, presented, resource_manager, oidc_registration_client } = params;
+
+    let presented_new_client = presented.client_id.as_ref()
+        .zip(rotation.client_id.as_ref())
+        .map(|(presented, new)| *presented == new.0);
+    let presented_new_certificate = presented.client_certificate_id
+        .zip(rotation.client_certificate_id)
+        .map(|(presented, new)| presented == new);
+
+    let uses_new_credentials = match presented_new_client.or(presented_new_certificate) {
+        Some(uses_new_credentials) => uses_new_credentials,
+        None => {
+            debug!("Peer <{peer_id}> presented none of the credentials of the unconfirmed rotation. Not invalidating any of them.");
+            return Ok(());
+        }
+    };
+
+    let registration_client = oidc_registration_client
+        .filter(|registration_client| registration_client.config.peer_credentials.is_none());
+
+    if uses_new_credentials {
+        info!("Peer <{peer_id}> reconnected with its rotated credentials. Invalidating its previous credentials.");
+        if let (Some(registration_client), Some(client_id)) = (&registration_client, &rotation.client_id) {
+            registration_client.delete_other_clients_by_resource_id(peer_id.into(), client_id).await
+                .map_err(|source| RotatePeerCredentialsError::AuthRegistration { peer_id, source })?;
+        }
+        if let Some(certificate_id) = rotation.client_certificate_id {
+            resource_manager.resources_mut(async |resources| resources.revoke_client_certificates(peer_id, Some(certificate_id))).await
+                .map_err_to_inner(std::convert::identity)
+                .map_err(|source| RotatePeerCredentialsError::Persistence { peer_id, source })?;
+        }
+    } else {
+        info!("Peer <{peer_id}> reconnected with its previous credentials. Invalidating its rotated credentials.");
+        if let (Some(registration_client), Some(client_id)) = (&registration_client, &rotation.client_id) {
+            registration_client.delete_client(&client_id.0).await
+                .map_err(|source| RotatePeerCredentialsError::AuthRegistration { peer_id, source })?;
+        }
+        if let Some(certificate_id) = rotation.client_certificate_id {
+            resource_manager.resources_mut(async |resources| {
+                match resources.get::<ClientCertificateRecord>(certificate_id)? {
+                    Some(record) => resources.insert(certificate_id, ClientCertificateRecord { revoked_at: Some(SystemTime::now()), ..record }),
+                    None => Ok(()),
+                }
+            }).await
+                .map_err_to_inner(std::convert::identity)
+                .map_err(|source| RotatePeerCredentialsError::Persistence { peer_id, source })?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RotatePeerCredentialsError {
+    #[error("Credentials of peer <{peer_id}> could not be rotated, because a peer with that id does not exist!")]
+    PeerNotFound { peer_id: PeerId },
+    #[error("Credentials of peer <{peer_id}> could not be rotated, because the peer is not connected")]
+    PeerNotConnected { peer_id: PeerId },
+    #[error("Credentials of peer <{peer_id}> are already being rotated")]
+    AlreadyInProgress { peer_id: PeerId },
+    #[error("Peer <{peer_id}> did not confirm its rotated credentials. Its previous and new credentials stay valid, until it reconnects with one of them")]
+    Unconfirmed {
+        peer_id: PeerId,
+        #[source] cause: RotateCredentialsError,
+    },
+    #[error("Credentials of peer <{peer_id}> cannot be rotated: {reason}")]
+    NotSupported { peer_id: PeerId, reason: String },
+    #[error("Failed to issue client certificate while rotating credentials of peer <{peer_id}>: {cause}")]
+    IssueClientCertificate { peer_id: PeerId, cause: String },
+    #[error("Error when sending rotated credentials to peer <{peer_id}>")]
+    Push {
+        peer_id: PeerId,
+        #[source] cause: RotateCredentialsError,
+    },
+    #[error("Error when registering or deleting OIDC clients while rotating credentials of peer <{peer_id}>")]
+    AuthRegistration {
+        peer_id: PeerId,
+        #[source] source: opendut_auth::registration::client::RegistrationClientError,
+    },
+    #[error("Error when accessing persistence while rotating credentials of peer <{peer_id}>")]
+    Persistence {
+        peer_id: PeerId,
+        #[source] source: PersistenceError,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use std::net::IpAddr;
+    use std::ops::Not;
+    use std::str::FromStr;
+
+    use googletest::prelude::*;
+    use opendut_carl_api::carl::broker::stream_header;
+    use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, rotate_credentials_result, upstream, RotateCredentials, RotateCredentialsResult, RotateCredentialsSuccess};
+
+    use crate::auth::client_certificate::tests::create_client_certificate_authority;
+    use crate::manager::peer_manager::tests::create_peer_descriptor;
+    use crate::manager::peer_messaging_broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resource::manager::ResourceManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_rotate_client_certificate_and_revoke_previous_one_once_confirmed() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(&resource_manager), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_millis(200),
+            credentials_rotation_timeout: Duration::from_millis(200),
+        }).await;
+        let authority = Arc::new(create_client_certificate_authority()?);
+
+        let peer = create_peer_descriptor();
+        let peer_id = peer.id;
+        resource_manager.insert(peer_id, peer).await?;
+
+        let IssuedClientCertificate { record: previous, .. } = authority.issue(peer_id)?;
+        resource_manager.insert(previous.id, Clone::clone(&previous)).await?;
+
+        let (sender, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?, stream_header::ExtraHeaders::default()).await?;
+        let _initial_configuration = receiver.recv().await;
+
+        let edgar = tokio::spawn(async move {
+            let received = receiver.recv().await.unwrap().message.unwrap();
+            let downstream::Message::RotateCredentials(RotateCredentials { client_id, client_certificate, .. }) = received else {
+                panic!("Expected RotateCredentials message, but received: {received:?}");
+            };
+            assert!(client_id.is_none());
+            assert!(client_certificate.is_some());
+            sender.send(upstream::Message::RotateCredentialsResult(RotateCredentialsResult {
+                result: Some(rotate_credentials_result::Result::Success(RotateCredentialsSuccess { client_id })),
+            })).await.unwrap();
+        });
+
+        let rotated = rotate_peer_credentials(RotatePeerCredentialsParams {
+            peer: peer_id,
+            actor: AuditActor::System,
+            resource_manager: Arc::clone(&resource_manager),
+            oidc_registration_client: None,
+            client_certificate_authority: Some(authority),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            user_id: UserId { value: String::from("testUser") },
+        }).await?;
+        edgar.await?;
+
+        assert_that!(rotated.client_id, none());
+        let new_certificate_id = rotated.client_certificate_id.expect("Expected a new client certificate to be issued");
+
+        let previous = resource_manager.get::<ClientCertificateRecord>(previous.id).await?.unwrap();
+        assert_that!(previous.revoked_at, some(anything()));
+        let new_certificate = resource_manager.get::<ClientCertificateRecord>(new_certificate_id).await?.unwrap();
+        assert!(new_certificate.is_valid(SystemTime::now()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_rotate_credentials_when_authentication_is_disabled() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(&resource_manager), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_millis(200),
+            credentials_rotation_timeout: Duration::from_millis(200),
+        }).await;
+
+        let peer = create_peer_descriptor();
+        let peer_id = peer.id;
+        resource_manager.insert(peer_id, peer).await?;
+
+        let params = |peer| RotatePeerCredentialsParams {
+            peer,
+            actor: AuditActor::System,
+            resource_manager: Arc::clone(&resource_manager),
+            oidc_registration_client: None,
+            client_certificate_authority: None,
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            user_id: UserId { value: String::from("testUser") },
+        };
+
+        let result = rotate_peer_credentials(params(peer_id)).await;
+        assert_that!(result, err(matches_pattern!(RotatePeerCredentialsError::NotSupported { .. })));
+
+        let result = rotate_peer_credentials(params(PeerId::random())).await;
+        assert_that!(result, err(matches_pattern!(RotatePeerCredentialsError::PeerNotFound { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_keep_previous_and_new_client_certificate_until_peer_reconnects_when_rotation_is_unconfirmed() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(Arc::clone(&resource_manager), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(10),
+            credentials_rotation_timeout: Duration::from_millis(200),
+        }).await;
+        let authority = Arc::new(create_client_certificate_authority()?);
+
+        let peer = create_peer_descriptor();
+        let peer_id = peer.id;
+        resource_manager.insert(peer_id, peer).await?;
+
+        let IssuedClientCertificate { record: previous, .. } = authority.issue(peer_id)?;
+        resource_manager.insert(previous.id, Clone::clone(&previous)).await?;
+
+        let (_sender, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?, stream_header::ExtraHeaders::default()).await?;
+        let _initial_configuration = receiver.recv().await;
+
+        let params = || RotatePeerCredentialsParams {
+            peer: peer_id,
+            actor: AuditActor::System,
+            resource_manager: Arc::clone(&resource_manager),
+            oidc_registration_client: None,
+            client_certificate_authority: Some(Arc::clone(&authority)),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            user_id: UserId { value: String::from("testUser") },
+        };
+
+        let (first, second) = tokio::join!(
+            rotate_peer_credentials(params()),
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                rotate_peer_credentials(params()).await
+            },
+        );
+        assert_that!(first, err(matches_pattern!(RotatePeerCredentialsError::Unconfirmed { .. })));
+        assert_that!(second, err(matches_pattern!(RotatePeerCredentialsError::AlreadyInProgress { .. })));
+
+        let is_valid = async |certificate_id| resource_manager.get::<ClientCertificateRecord>(certificate_id).await.unwrap().unwrap().is_valid(SystemTime::now());
+        let rotation = peer_messaging_broker.take_unconfirmed_credentials_rotation(peer_id).await
+            .expect("Expected the unconfirmed rotation to be remembered");
+        let new_certificate_id = rotation.client_certificate_id.expect("Expected a new client certificate to be issued");
+        assert!(is_valid(previous.id).await);
+        assert!(is_valid(new_certificate_id).await);
+
+        resolve_unconfirmed_credentials_rotation(ResolveUnconfirmedCredentialsRotationParams {
+            peer: peer_id,
+            rotation,
+            presented: PresentedCredentials { client_id: None, client_certificate_id: Some(previous.id) },
+            resource_manager: Arc::clone(&resource_manager),
+            oidc_registration_client: None,
+        }).await?;
+
+        assert!(is_valid(previous.id).await);
+        assert!(is_valid(new_certificate_id).await.not());
+        Ok(())
+    }
+}
@@ -1,15 +1,19 @@
-use crate::resource::api::resources::Resources;
+use crate::manager::cluster_manager::DeleteClusterDeploymentsOutsidePeerProjectError;
+use crate::resource::api::resources::{Resources, RevisionConflict};
 use crate::resource::persistence::error::PersistenceError;
 use crate::resource::storage::ResourcesStorageApi;
 use crate::settings::vpn::Vpn;
 use opendut_types::peer::state::PeerState;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerName};
+use opendut_types::resources::Revision;
 use opendut_types::ShortName;
 use tracing::{debug, error, info, warn};
 
 pub struct StorePeerDescriptorParams {
     pub vpn: Vpn,
     pub peer_descriptor: PeerDescriptor,
+    /// Only store the peer descriptor, if it is still at this revision.
+    pub expected_revision: Option<Revision>,
 }
 
 impl Resources<'_> {
@@ -20,6 +24,15 @@ impl Resources<'_> {
         let peer_name = Clone::clone(&params.peer_descriptor.name);
         let peer_descriptor = params.peer_descriptor;
 
+        self.check_revision::<PeerDescriptor>(peer_id, params.expected_revision)
+            .map_err(|source| StorePeerDescriptorError::Persistence { peer_id, peer_name: peer_name.clone(), source })?
+            .map_err(|RevisionConflict { expected, actual }| StorePeerDescriptorError::RevisionConflict {
+                peer_id,
+                peer_name: peer_name.clone(),
+                expected_revision: expected,
+                actual_revision: actual,
+            })?;
+
         let is_new_peer = self.get::<PeerDescriptor>(peer_id)
             .map_err(|source| StorePeerDescriptorError::Persistence { peer_id, peer_name: peer_name.clone(), source })?
             .is_none();
@@ -69,6 +82,13 @@ pub enum StorePeerDescriptorError {
         actual_state: PeerState,
         required_states: Vec<PeerState>,
     },
+    #[error("Peer '{peer_name}' <{peer_id}> was modified since revision {expected_revision} and cannot be updated! Reload the peer and apply the changes again.")]
+    RevisionConflict {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        expected_revision: Revision,
+        actual_revision: Option<Revision>,
+    },
     #[error("Error when accessing persistence while creating peer '{peer_name}' <{peer_id}>")]
     Persistence {
         peer_id: PeerId,
@@ -81,6 +101,12 @@ pub enum StorePeerDescriptorError {
         peer_name: PeerName,
         #[source] source: opendut_vpn::CreatePeerError,
     },
+    #[error("Error when deleting the deployments of clusters, which peer '{peer_name}' <{peer_id}> is not shared with anymore")]
+    DeleteClusterDeployments {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        #[source] source: DeleteClusterDeploymentsOutsidePeerProjectError,
+    },
 }
 
 #[cfg(test)]
@@ -114,6 +140,7 @@ mod tests {
             resources.store_peer_descriptor(StorePeerDescriptorParams {
                 vpn: Vpn::Disabled,
                 peer_descriptor: Clone::clone(&peer.descriptor),
+                expected_revision: None,
             }).await
         ).await??;
 
@@ -132,6 +159,7 @@ mod tests {
             description: DeviceDescription::try_from("Additional device for peerA").ok(),
             interface: additional_network_interface.id,
             tags: vec![],
+            labels: Default::default(),
         };
 
         let changed_descriptor = PeerDescriptor {
@@ -155,6 +183,7 @@ mod tests {
             resources.store_peer_descriptor(StorePeerDescriptorParams {
                 vpn: Vpn::Disabled,
                 peer_descriptor: Clone::clone(&changed_descriptor),
+                expected_revision: None,
             }).await
         ).await??;
 
@@ -163,4 +192,37 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn should_reject_update_of_peer_descriptor_modified_since_expected_revision() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer = PeerFixture::new();
+
+        let store = async |expected_revision: Option<Revision>| {
+            resource_manager.resources_mut(async |resources|
+                resources.store_peer_descriptor(StorePeerDescriptorParams {
+                    vpn: Vpn::Disabled,
+                    peer_descriptor: Clone::clone(&peer.descriptor),
+                    expected_revision,
+                }).await
+            ).await
+        };
+
+        store(None).await??;
+        let first_revision = resource_manager.resources(async |resources| resources.revision::<PeerDescriptor>(peer.id)).await??;
+        let first_revision = first_revision.expect("Revision of stored peer descriptor should be available.");
+
+        store(Some(first_revision)).await??;
+        let second_revision = resource_manager.resources(async |resources| resources.revision::<PeerDescriptor>(peer.id)).await??;
+        assert!(second_revision > Some(first_revision));
+
+        let result = store(Some(first_revision)).await?;
+        assert_that!(result, err(matches_pattern!(StorePeerDescriptorError::RevisionConflict {
+            expected_revision: eq(&first_revision),
+            actual_revision: eq(&second_revision),
+            ..
+        })));
+
+        Ok(())
+    }
 }
@@ -17,6 +17,8 @@ pub fn create_peer_descriptor() -> PeerDescriptor {
         },
         executors: ExecutorDescriptors {
             executors: vec![],
-        }
+        },
+        labels: Default::default(),
+        project: Default::default(),
     }
 }
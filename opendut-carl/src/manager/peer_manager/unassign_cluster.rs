@@ -0,0 +1,159 @@
+use crate::manager::peer_messaging_broker::PeerMessagingBrokerRef;
+use crate::resource::persistence::error::PersistenceError;
+use crate::resource::storage::ResourcesStorageApi;
+use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, ApplyPeerConfiguration};
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::configuration::{OldPeerConfiguration, ParameterTarget, PeerConfiguration};
+use opendut_types::peer::PeerId;
+use tracing::debug;
+use crate::resource::api::resources::Resources;
+
+pub struct UnassignClusterParams {
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub peer_id: PeerId,
+    pub cluster_id: ClusterId,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum UnassignClusterError {
+    #[error("Error while persisting removed ClusterAssignment for peer <{peer_id}>.")]
+    Persistence { peer_id: PeerId, #[source] source: PersistenceError },
+}
+
+impl Resources<'_> {
+    /// Removes the assignment of the cluster from the peer and marks the WireGuard peers of the cluster as absent,
+    /// so that the peer stops accepting traffic from the other members.
+    /// The configuration is sent to the peer, if it is connected. Otherwise, it receives it when connecting.
+    pub async fn unassign_cluster(&mut self, params: UnassignClusterParams) -> Result<(), UnassignClusterError> {
+        let UnassignClusterParams { peer_messaging_broker, peer_id, cluster_id } = params;
+
+        let old_peer_configuration = self.get::<OldPeerConfiguration>(peer_id)
+            .map_err(|source| UnassignClusterError::Persistence { peer_id, source })?
+            .unwrap_or_default();
+
+        let assigned_cluster = old_peer_configuration.cluster_assignment.as_ref().map(|assignment| assignment.id);
+        if assigned_cluster != Some(cluster_id) {
+            debug!("Peer <{peer_id}> is not assigned to cluster <{cluster_id}>. Not unassigning it.");
+            return Ok(());
+        }
+        debug!("Unassigning cluster <{cluster_id}> from peer <{peer_id}>.");
+
+        let old_peer_configuration = OldPeerConfiguration { cluster_assignment: None };
+        self.insert(peer_id, Clone::clone(&old_peer_configuration))
+            .map_err(|source| UnassignClusterError::Persistence { peer_id, source })?;
+
+        let mut peer_configuration = self.get::<PeerConfiguration>(peer_id)
+            .map_err(|source| UnassignClusterError::Persistence { peer_id, source })?
+            .unwrap_or_default();
+
+        let wireguard_peers = peer_configuration.wireguard_peers.iter()
+            .map(|parameter| Clone::clone(&parameter.value))
+            .collect::<Vec<_>>();
+        for wireguard_peer in wireguard_peers {
+            peer_configuration.set(wireguard_peer, ParameterTarget::Absent);
+        }
+        self.insert(peer_id, Clone::clone(&peer_configuration))
+            .map_err(|source| UnassignClusterError::Persistence { peer_id, source })?;
+
+        let sent = peer_messaging_broker.send_to_peer(
+            peer_id,
+            downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration {
+                old_configuration: Some(old_peer_configuration.into()),
+                configuration: Some(peer_configuration.into()),
+            }),
+        ).await;
+        if let Err(cause) = sent {
+            debug!("Could not send configuration without cluster <{cluster_id}> to peer <{peer_id}>. It is applied, when the peer connects again: {cause}");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::peer_manager::{AssignClusterOptions, AssignClusterParams};
+    use crate::manager::peer_manager::tests::create_peer_descriptor;
+    use crate::manager::peer_messaging_broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::manager::testing::PeerFixture;
+    use crate::resource::manager::ResourceManager;
+    use googletest::prelude::*;
+    use opendut_carl_api::carl::broker::stream_header;
+    use opendut_types::cluster::ClusterAssignment;
+    use opendut_types::peer::configuration::{parameter, Parameter};
+    use opendut_types::util::net::NetworkInterfaceName;
+    use opendut_types::vpn::wireguard::{PublicKey, WireGuardPeerDescriptor};
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn should_remove_the_cluster_assignment_and_mark_wireguard_peers_absent() -> anyhow::Result<()> {
+        let peer_id = PeerFixture::new().id;
+        let cluster_id = ClusterId::random();
+
+        let settings = crate::settings::load_defaults()?;
+        let resource_manager = ResourceManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resource_manager),
+            PeerMessagingBrokerOptions::load(&settings.config).unwrap(),
+        ).await;
+
+        resource_manager.resources_mut(async |resources| {
+            resources.insert(peer_id, create_peer_descriptor())
+        }).await??;
+        let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?, stream_header::ExtraHeaders::default()).await?;
+        let _ = receiver.recv().await;
+
+        let wireguard_peer = WireGuardPeerDescriptor {
+            peer_id: PeerId::random(),
+            public_key: PublicKey::from_bytes([1; 32]),
+            address: IpAddr::from_str("10.100.0.2")?,
+            endpoint: None,
+        };
+
+        resource_manager.resources_mut(async |resources| {
+            resources.assign_cluster(AssignClusterParams {
+                peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+                peer_id,
+                cluster_assignment: ClusterAssignment {
+                    id: cluster_id,
+                    leader: peer_id,
+                    assignments: vec![],
+                },
+                device_interfaces: vec![],
+                wireguard_peers: vec![Clone::clone(&wireguard_peer)],
+                options: AssignClusterOptions {
+                    bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
+                }
+            }).await
+        }).await??;
+        let _ = receiver.recv().await;
+
+        resource_manager.resources_mut(async |resources| {
+            resources.unassign_cluster(UnassignClusterParams {
+                peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+                peer_id,
+                cluster_id,
+            }).await
+        }).await??;
+
+        let received = receiver.recv().await.unwrap()
+            .message.unwrap();
+        let downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration {
+            old_configuration, configuration
+        }) = received else { panic!() };
+
+        assert_that!(OldPeerConfiguration::try_from(old_configuration.unwrap())?.cluster_assignment, none());
+        assert_that!(PeerConfiguration::try_from(configuration.unwrap())?.wireguard_peers, elements_are![
+            matches_pattern!(Parameter {
+                target: eq(&ParameterTarget::Absent),
+                value: eq(&parameter::WireGuardPeer { descriptor: wireguard_peer }),
+                ..
+            }),
+        ]);
+
+        Ok(())
+    }
+}
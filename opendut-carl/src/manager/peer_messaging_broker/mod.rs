@@ -1,19 +1,25 @@
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::net::IpAddr;
+use std::ops::Not;
 use std::sync::Arc;
 use std::time::Duration;
 
 use opendut_carl_api::carl::broker::stream_header;
-use opendut_carl_api::proto::services::peer_messaging_broker::{upstream, DisconnectNotice};
+use opendut_carl_api::proto::services::peer_messaging_broker::{executor_finished, rotate_credentials_result, upstream, DisconnectNotice, ExecutorFinished, RotateCredentials, RotateCredentialsResult};
 use opendut_carl_api::proto::services::peer_messaging_broker::Pong;
 use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, ApplyPeerConfiguration, Downstream, TracingContext};
+use opendut_types::peer::certificate::ClientCertificateId;
+use opendut_types::peer::decommission::PeerDecommissionRecord;
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::executor::ExecutorId;
 use opendut_types::peer::state::{PeerConnectionState};
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::util::net::{ClientCertificate, ClientCredentials, ClientId};
 use opentelemetry::propagation::TextMapPropagator;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use tokio::sync::mpsc::error::SendError;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, oneshot, RwLock};
 use tracing::{debug, error, info, trace, warn, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use crate::resource::persistence::error::PersistenceError;
@@ -28,18 +34,43 @@ pub type PeerMessagingBrokerRef = Arc<PeerMessagingBroker>;
 pub struct PeerMessagingBroker {
     resource_manager: ResourceManagerRef,
     peers: Arc<RwLock<HashMap<PeerId, PeerMessagingRef>>>,
+    pending_credential_rotations: PendingCredentialRotations,
+    /// Peers, which received rotated credentials, but did not confirm storing them. They keep their previous and new credentials until they reconnect with one of them.
+    unconfirmed_credential_rotations: Arc<RwLock<HashMap<PeerId, UnconfirmedCredentialsRotation>>>,
+    executor_reports: broadcast::Sender<ExecutorReport>,
     options: PeerMessagingBrokerOptions,
 }
+type PendingCredentialRotations = Arc<RwLock<HashMap<PeerId, oneshot::Sender<Result<Option<ClientId>, String>>>>>;
 struct PeerMessagingRef {
     downstream: mpsc::Sender<Downstream>,
     disconnected: bool,
 }
 
+/// Reported by a peer, when one of its executors stopped running by itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutorReport {
+    pub peer_id: PeerId,
+    pub executor_id: ExecutorId,
+    pub outcome: ExecutorOutcome,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case", tag = "status")]
+pub enum ExecutorOutcome {
+    /// The executor ran to completion. A non-zero exit code usually means that the tests failed.
+    Exited { exit_code: i32 },
+    /// EDGAR could not run the executor as intended.
+    Failed { cause: String },
+}
+
 impl PeerMessagingBroker {
     pub async fn new(resource_manager: ResourceManagerRef, options: PeerMessagingBrokerOptions) -> PeerMessagingBrokerRef {
         let self_ref = Arc::new(Self {
             resource_manager: resource_manager.clone(),
             peers: Default::default(),
+            pending_credential_rotations: Default::default(),
+            unconfirmed_credential_rotations: Default::default(),
+            executor_reports: broadcast::channel(100).0,
             options,
         });
         effects::register(resource_manager, self_ref.clone()).await;
@@ -47,7 +78,12 @@ impl PeerMessagingBroker {
         self_ref
     }
 
-    #[tracing::instrument(skip(self), level="trace")]
+    /// Receives the reports of executors, which finished on any of the connected peers.
+    pub fn subscribe_executor_reports(&self) -> broadcast::Receiver<ExecutorReport> {
+        self.executor_reports.subscribe()
+    }
+
+    #[tracing::instrument(skip(self, message), level="trace")] //message may contain credentials
     pub async fn send_to_peer(&self, peer_id: PeerId, message: downstream::Message) -> Result<(), Error> {
         let downstream = {
             let peers = self.peers.read().await;
@@ -97,6 +133,79 @@ impl PeerMessagingBroker {
         Ok(())
     }
 
+    /// Closes the stream of a connected peer. Returns `false`, if the peer was not connected.
+    pub async fn disconnect_peer(&self, peer_id: PeerId) -> Result<bool, Error> {
+        let connected = self.peers.read().await
+            .get(&peer_id)
+            .is_some_and(|peer| peer.disconnected.not());
+
+        if connected {
+            self.disconnect(peer_id).await?;
+        }
+        Ok(connected)
+    }
+
+    /// Sends new credentials to a connected peer and waits until the peer confirms that it stored them.
+    /// Only one rotation may be pending per peer, as the confirmation could not be attributed otherwise.
+    /// Neither may a peer's credentials be rotated again, while a previous rotation is unconfirmed, as its credentials would never be invalidated otherwise.
+    ///
+    /// If the peer does not confirm in time or disconnects meanwhile, it is unknown whether it stored the new credentials.
+    /// See [`Self::remember_unconfirmed_credentials_rotation`].
+    pub async fn rotate_credentials(&self, peer_id: PeerId, credentials: RotatedCredentials) -> Result<(), RotateCredentialsError> {
+        if self.unconfirmed_credential_rotations.read().await.contains_key(&peer_id) {
+            return Err(RotateCredentialsError::AlreadyPending { peer_id });
+        }
+
+        let (tx_confirmation, rx_confirmation) = oneshot::channel();
+        match self.pending_credential_rotations.write().await.entry(peer_id) {
+            Entry::Occupied(_) => return Err(RotateCredentialsError::AlreadyPending { peer_id }),
+            Entry::Vacant(entry) => { entry.insert(tx_confirmation); }
+        }
+
+        let result = async {
+            let RotatedCredentials { oidc, client_certificate } = credentials;
+            let expected_client_id = oidc.as_ref().map(|oidc| Clone::clone(&oidc.client_id));
+
+            let (client_id, client_secret) = match oidc {
+                Some(ClientCredentials { client_id, client_secret }) => (Some(client_id.into()), Some(client_secret.into())),
+                None => (None, None),
+            };
+            self.send_to_peer(peer_id, downstream::Message::RotateCredentials(RotateCredentials {
+                client_id,
+                client_secret,
+                client_certificate: client_certificate.map(Into::into),
+            })).await
+                .map_err(|cause| match cause {
+                    Error::PeerNotFound(peer_id) => RotateCredentialsError::PeerNotConnected { peer_id },
+                    Error::DownstreamSend(cause) => RotateCredentialsError::Send { peer_id, cause: cause.to_string() },
+                })?;
+
+            let confirmation = tokio::time::timeout(self.options.credentials_rotation_timeout, rx_confirmation).await
+                .map_err(|_| RotateCredentialsError::Timeout { peer_id })?
+                .map_err(|_| RotateCredentialsError::Unconfirmed { peer_id })?;
+
+            match confirmation {
+                Ok(client_id) if client_id == expected_client_id => Ok(()),
+                Ok(client_id) => Err(RotateCredentialsError::Rejected { peer_id, cause: format!("Peer confirmed unexpected client <{}>", client_id.map(|client_id| client_id.0).unwrap_or_default()) }),
+                Err(cause) => Err(RotateCredentialsError::Rejected { peer_id, cause }),
+            }
+        }.await;
+
+        self.pending_credential_rotations.write().await.remove(&peer_id);
+        result
+    }
+
+    /// Remembers credentials, which a peer received, but did not confirm storing, so that either its previous or its new credentials
+    /// can be invalidated, once it reconnects with the other ones.
+    pub async fn remember_unconfirmed_credentials_rotation(&self, peer_id: PeerId, rotation: UnconfirmedCredentialsRotation) {
+        self.unconfirmed_credential_rotations.write().await.insert(peer_id, rotation);
+    }
+
+    /// Returns the credentials, which the peer did not confirm since it last connected, and forgets them.
+    pub async fn take_unconfirmed_credentials_rotation(&self, peer_id: PeerId) -> Option<UnconfirmedCredentialsRotation> {
+        self.unconfirmed_credential_rotations.write().await.remove(&peer_id)
+    }
+
     pub async fn remove_peer(&self, peer_id: PeerId) -> Result<(), RemovePeerError> {
         let peer_connection_state = self.resource_manager.get::<PeerConnectionState>(peer_id).await;
         if let Ok(Some(peer_connection_state)) = peer_connection_state {
@@ -140,6 +249,7 @@ impl PeerMessagingBroker {
         };
 
         self.expect_known_peer_descriptor(peer_id).await?.ok_or(OpenError::PeerNotFound(peer_id))?;
+        self.expect_not_decommissioned(peer_id).await?;
         self.update_peer_connection_state(peer_id, remote_host).await?;
         self.peers.write().await.insert(peer_id, peer_messaging_ref);
         self.send_initial_peer_configuration(peer_id).await?;
@@ -148,13 +258,15 @@ impl PeerMessagingBroker {
 
         {
             let peers = Arc::clone(&self.peers);
+            let pending_credential_rotations = Arc::clone(&self.pending_credential_rotations);
+            let executor_reports = Clone::clone(&self.executor_reports);
             let resource_manager = Arc::clone(&self.resource_manager);
 
             tokio::spawn(async move {
                 loop {
                     let received = tokio::time::timeout(timeout_duration, rx_inbound.recv()).await;
                     match received {
-                        Ok(Some(message)) => handle_stream_message(message, peer_id, &tx_outbound).await,
+                        Ok(Some(message)) => handle_stream_message(message, peer_id, &tx_outbound, &pending_credential_rotations, &executor_reports).await,
                         Ok(None) => {
                             info!("Peer <{peer_id}> disconnected! Closing inbound channel.");
                             break;
@@ -238,6 +350,15 @@ impl PeerMessagingBroker {
             .map_err(|source| OpenError::Persistence { peer_id, source })?
     }
 
+    async fn expect_not_decommissioned(&self, peer_id: PeerId) -> Result<(), OpenError> {
+        let decommission = self.resource_manager.get::<PeerDecommissionRecord>(peer_id).await
+            .map_err(|source| OpenError::Persistence { peer_id, source })?;
+        match decommission {
+            Some(_) => Err(OpenError::PeerDecommissioned { peer_id }),
+            None => Ok(()),
+        }
+    }
+
     async fn update_peer_connection_state(&self, peer_id: PeerId, remote_host: IpAddr) -> Result<(), OpenError> {
         self.resource_manager.resources_mut(async |resources| {
             let maybe_peer_state = resources.get::<PeerConnectionState>(peer_id)
@@ -305,6 +426,8 @@ async fn handle_stream_message(
     message: upstream::Message,
     peer_id: PeerId,
     tx_outbound: &mpsc::Sender<Downstream>,
+    pending_credential_rotations: &PendingCredentialRotations,
+    executor_reports: &broadcast::Sender<ExecutorReport>,
 ) {
     match message {
         upstream::Message::Ping(_) => {
@@ -314,6 +437,44 @@ async fn handle_stream_message(
                 tx_outbound.send(Downstream { message: Some(message), context }).await
                     .inspect_err(|cause| warn!("Failed to send ping to peer <{peer_id}>:\n  {cause}"));
         },
+        upstream::Message::RotateCredentialsResult(RotateCredentialsResult { result }) => {
+            let result = match result {
+                Some(rotate_credentials_result::Result::Success(success)) => success.client_id
+                    .map(ClientId::try_from)
+                    .transpose()
+                    .map_err(|cause| format!("Peer confirmed illegal client ID: {cause}")),
+                Some(rotate_credentials_result::Result::Failure(failure)) => Err(failure.cause),
+                None => Err(String::from("Peer sent empty credentials rotation result")),
+            };
+
+            match pending_credential_rotations.write().await.remove(&peer_id) {
+                Some(tx_confirmation) => {
+                    let _ignore_result = tx_confirmation.send(result);
+                }
+                None => warn!("Peer <{peer_id}> sent a credentials rotation result, but no rotation was pending. Ignoring."),
+            }
+        },
+        upstream::Message::ExecutorFinished(ExecutorFinished { executor_id, outcome }) => {
+            let executor_id = match executor_id.map(ExecutorId::try_from) {
+                Some(Ok(executor_id)) => executor_id,
+                Some(Err(cause)) => {
+                    warn!("Peer <{peer_id}> reported a finished executor with illegal ID: {cause}");
+                    return;
+                }
+                None => {
+                    warn!("Peer <{peer_id}> reported a finished executor without ID. Ignoring.");
+                    return;
+                }
+            };
+            let outcome = match outcome {
+                Some(executor_finished::Outcome::Exited(exited)) => ExecutorOutcome::Exited { exit_code: exited.exit_code },
+                Some(executor_finished::Outcome::Failure(failure)) => ExecutorOutcome::Failed { cause: failure.cause },
+                None => ExecutorOutcome::Failed { cause: String::from("Peer reported no outcome.") },
+            };
+            debug!("Peer <{peer_id}> reported that executor <{}> finished: {outcome:?}", executor_id.uuid);
+
+            let _ignore_no_receivers = executor_reports.send(ExecutorReport { peer_id, executor_id, outcome });
+        },
     }
 }
 
@@ -325,6 +486,35 @@ pub enum Error {
     PeerNotFound(PeerId),
 }
 
+/// New credentials for a peer. Either part is absent, if CARL does not use it for authenticating peers.
+pub struct RotatedCredentials {
+    pub oidc: Option<ClientCredentials>,
+    pub client_certificate: Option<ClientCertificate>,
+}
+
+/// Identifies the credentials sent to a peer, which it did not confirm storing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnconfirmedCredentialsRotation {
+    pub client_id: Option<ClientId>,
+    pub client_certificate_id: Option<ClientCertificateId>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RotateCredentialsError {
+    #[error("Peer <{peer_id}> is not connected")]
+    PeerNotConnected { peer_id: PeerId },
+    #[error("Failed to send new credentials to peer <{peer_id}>:\n  {cause}")]
+    Send { peer_id: PeerId, cause: String },
+    #[error("Peer <{peer_id}> did not confirm the new credentials in time")]
+    Timeout { peer_id: PeerId },
+    #[error("Peer <{peer_id}> disconnected before confirming the new credentials")]
+    Unconfirmed { peer_id: PeerId },
+    #[error("Credentials of peer <{peer_id}> are already being rotated or a previous rotation is unconfirmed")]
+    AlreadyPending { peer_id: PeerId },
+    #[error("Peer <{peer_id}> could not store the new credentials:\n  {cause}")]
+    Rejected { peer_id: PeerId, cause: String },
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum OpenError {
     #[error(
@@ -335,6 +525,8 @@ pub enum OpenError {
     PeerAlreadyConnected { peer_id: PeerId },
     #[error("Peer not found. Unknown peer id: <{0}>")]
     PeerNotFound(PeerId),
+    #[error("Peer <{peer_id}> was decommissioned. It has to be set up again with a new Setup-String.")]
+    PeerDecommissioned { peer_id: PeerId },
 
     #[error("Error while sending peer configuration to peer:\n  {cause}")]
     SendApplyPeerConfiguration { peer_id: PeerId, cause: String },
@@ -354,6 +546,8 @@ pub enum RemovePeerError {
 #[derive(Clone)]
 pub struct PeerMessagingBrokerOptions {
     pub peer_disconnect_timeout: Duration,
+    /// How long to wait for a peer to confirm that it stored rotated credentials.
+    pub credentials_rotation_timeout: Duration,
 }
 impl PeerMessagingBrokerOptions {
     pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
@@ -361,8 +555,13 @@ impl PeerMessagingBrokerOptions {
             config.get::<u64>("peer.disconnect.timeout.ms")?
         );
 
+        let credentials_rotation_timeout = Duration::from_millis(
+            config.get::<u64>("peer.credentials.rotation.timeout.ms")?
+        );
+
         Ok(PeerMessagingBrokerOptions {
             peer_disconnect_timeout,
+            credentials_rotation_timeout,
         })
     }
 }
@@ -371,6 +570,7 @@ impl PeerMessagingBrokerOptions {
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
+    use std::time::SystemTime;
 
     use googletest::prelude::*;
     use tokio::sync::mpsc;
@@ -388,6 +588,7 @@ mod tests {
 
         let options = PeerMessagingBrokerOptions {
             peer_disconnect_timeout: Duration::from_millis(200),
+            credentials_rotation_timeout: Duration::from_millis(200),
         };
         let testee = PeerMessagingBroker::new(Arc::clone(&resource_manager), options.clone()).await;
 
@@ -464,6 +665,7 @@ mod tests {
         let Fixture { resource_manager, peer_id } = fixture().await?;
         let options = PeerMessagingBrokerOptions {
             peer_disconnect_timeout: Duration::from_millis(200),
+            credentials_rotation_timeout: Duration::from_millis(200),
         };
         let testee = PeerMessagingBroker::new(Arc::clone(&resource_manager), options.clone()).await;
         let remote_host = IpAddr::from_str("1.2.3.4")?;
@@ -484,11 +686,29 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_reject_stream_of_decommissioned_peer() -> anyhow::Result<()> {
+        let Fixture { resource_manager, peer_id } = fixture().await?;
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_millis(200),
+            credentials_rotation_timeout: Duration::from_millis(200),
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resource_manager), options).await;
+        resource_manager.insert(peer_id, PeerDecommissionRecord { peer_id, decommissioned_at: SystemTime::now() }).await?;
+
+        let result = testee.open(peer_id, IpAddr::from_str("1.2.3.4")?, stream_header::ExtraHeaders::default()).await;
+
+        assert_that!(result, err(matches_pattern!(OpenError::PeerDecommissioned { peer_id: eq(&peer_id) })));
+        assert!(resource_manager.get::<PeerConnectionState>(peer_id).await?.is_none());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn should_memorize_peer_connection_state_is_offline_when_peer_disconnects() -> anyhow::Result<()> {
         let Fixture { resource_manager, peer_id } = fixture().await?;
         let options = PeerMessagingBrokerOptions {
             peer_disconnect_timeout: Duration::from_millis(200),
+            credentials_rotation_timeout: Duration::from_millis(200),
         };
         let testee = PeerMessagingBroker::new(Arc::clone(&resource_manager), options.clone()).await;
         let remote_host = IpAddr::from_str("1.2.3.4")?;
@@ -517,6 +737,7 @@ mod tests {
 
         let options = PeerMessagingBrokerOptions {
             peer_disconnect_timeout: Duration::from_millis(200),
+            credentials_rotation_timeout: Duration::from_millis(200),
         };
         let testee = PeerMessagingBroker::new(Arc::clone(&resource_manager), options.clone()).await;
 
@@ -534,6 +755,71 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_relay_reports_of_finished_executors() -> anyhow::Result<()> {
+        let Fixture { resource_manager, peer_id } = fixture().await?;
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_millis(200),
+            credentials_rotation_timeout: Duration::from_millis(200),
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resource_manager), options.clone()).await;
+        let mut executor_reports = testee.subscribe_executor_reports();
+
+        let (sender, _receiver) = testee.open(peer_id, IpAddr::from_str("1.2.3.4")?, stream_header::ExtraHeaders::default()).await?;
+
+        let executor_id = ExecutorId::random();
+        sender.send(upstream::Message::ExecutorFinished(ExecutorFinished {
+            executor_id: Some(executor_id.into()),
+            outcome: Some(executor_finished::Outcome::Exited(opendut_carl_api::proto::services::peer_messaging_broker::ExecutorExited { exit_code: 1 })),
+        })).await?;
+
+        let report = tokio::time::timeout(Duration::from_secs(5), executor_reports.recv()).await??;
+        assert_that!(report, eq(&ExecutorReport { peer_id, executor_id, outcome: ExecutorOutcome::Exited { exit_code: 1 } }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_rotate_credentials_of_connected_peer_once_confirmed() -> anyhow::Result<()> {
+        let Fixture { resource_manager, peer_id } = fixture().await?;
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_millis(200),
+            credentials_rotation_timeout: Duration::from_millis(200),
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resource_manager), options.clone()).await;
+        let remote_host = IpAddr::from_str("1.2.3.4")?;
+        let credentials = || RotatedCredentials {
+            oidc: Some(ClientCredentials {
+                client_id: ClientId(String::from("rotated-client")),
+                client_secret: opendut_types::util::net::ClientSecret(String::from("rotated-secret")),
+            }),
+            client_certificate: None,
+        };
+
+        let result = testee.rotate_credentials(peer_id, credentials()).await;
+        assert_that!(result, err(matches_pattern!(RotateCredentialsError::PeerNotConnected { .. })));
+
+        let (sender, mut receiver) = testee.open(peer_id, remote_host, stream_header::ExtraHeaders::default()).await?;
+        let _initial_configuration = receiver.recv().await;
+
+        let peer = tokio::spawn(async move {
+            let received = receiver.recv().await.unwrap().message.unwrap();
+            let downstream::Message::RotateCredentials(RotateCredentials { client_id, .. }) = received else {
+                panic!("Expected RotateCredentials message, but received: {received:?}");
+            };
+            sender.send(upstream::Message::RotateCredentialsResult(RotateCredentialsResult {
+                result: Some(rotate_credentials_result::Result::Success(
+                    opendut_carl_api::proto::services::peer_messaging_broker::RotateCredentialsSuccess { client_id }
+                )),
+            })).await.unwrap();
+        });
+
+        testee.rotate_credentials(peer_id, credentials()).await?;
+        peer.await?;
+        assert!(testee.pending_credential_rotations.read().await.is_empty());
+
+        Ok(())
+    }
+
     async fn do_ping(sender: &mpsc::Sender<upstream::Message>, receiver: &mut Receiver<Downstream>) {
         sender.send(upstream::Message::Ping(Ping {})).await
             .unwrap();
@@ -0,0 +1,374 @@
+//! Archive format of snapshots, which is a `.tar.gz` file with the following contents:
+//!
+//! * `manifest.yaml`: Format version, CARL version and number of contained resources. Always the first file.
+//! * `specs.yaml`: Peers and clusters as specifications, which can be read by `cleo apply`.
+//! * `protobuf/<table>/<id>.binpb`: Each persistent resource encoded as Protobuf, as stored in the database.
+//!
+//! Format version 2 added the notification subscriptions, format version 3 the records of issued Setup-Strings and client certificates.
+//! Archives of older versions are still read, they simply contain none of these.
+//!
+//! Only the Protobuf files are read when importing. The specifications are meant for humans and CLEO.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Write};
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use opendut_types::audit::AuditEntry;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::notification::NotificationSubscription;
+use opendut_types::peer::certificate::ClientCertificateRecord;
+use opendut_types::peer::decommission::PeerDecommissionRecord;
+use opendut_types::peer::setup::PeerSetupRecord;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::resources::{Id, Revision};
+use opendut_types::specs::parse::yaml::{YamlSpecificationDocument, YamlSpecificationFile};
+use opendut_types::specs::SpecificationDocument;
+
+use crate::manager::snapshot::Snapshot;
+use crate::resource::api::id::ResourceId;
+use crate::resource::api::Resource;
+use crate::resource::persistence::error::PersistenceError;
+use crate::resource::persistence::persistable::Persistable;
+
+/// Version of the archive format. Must be incremented for incompatible changes.
+pub const FORMAT_VERSION: u32 = 3;
+/// Oldest version of the archive format, which can still be read.
+pub const OLDEST_SUPPORTED_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.yaml";
+const SPECIFICATIONS_FILE: &str = "specs.yaml";
+const PROTOBUF_DIRECTORY: &str = "protobuf";
+const PROTOBUF_FILE_EXTENSION: &str = "binpb";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotManifest {
+    pub format_version: u32,
+    pub carl_version: String,
+    pub created_at: String,
+    pub revision: Revision,
+    /// Number of contained resources per table, to detect incomplete archives.
+    pub resources: BTreeMap<String, usize>,
+}
+
+pub fn write_archive(snapshot: &Snapshot, writer: impl Write) -> Result<(), SnapshotArchiveError> {
+    let mut tar = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+
+    let manifest = SnapshotManifest {
+        format_version: FORMAT_VERSION,
+        carl_version: String::from(crate::app_info::PKG_VERSION),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        revision: snapshot.revision,
+        resources: snapshot.resource_counts(),
+    };
+    let manifest = serde_yaml::to_string(&manifest)
+        .map_err(|source| SnapshotArchiveError::Manifest { source })?;
+    append_file(&mut tar, MANIFEST_FILE, manifest.as_bytes())?;
+
+    let specifications = specifications(snapshot)
+        .map_err(|source| SnapshotArchiveError::Specifications { source })?;
+    append_file(&mut tar, SPECIFICATIONS_FILE, specifications.as_bytes())?;
+
+    append_resources(&mut tar, &snapshot.peer_descriptors)?;
+    append_resources(&mut tar, &snapshot.peer_setups)?;
+    append_resources(&mut tar, &snapshot.client_certificates)?;
+    append_resources(&mut tar, &snapshot.peer_decommissions)?;
+    append_resources(&mut tar, &snapshot.cluster_configurations)?;
+    append_resources(&mut tar, &snapshot.cluster_deployments)?;
+    append_resources(&mut tar, &snapshot.audit_entries)?;
+    append_resources(&mut tar, &snapshot.notification_subscriptions)?;
+
+    tar.into_inner()?.finish()?;
+    Ok(())
+}
+
+pub fn read_archive(reader: impl Read) -> Result<Snapshot, SnapshotArchiveError> {
+    let mut archive = tar::Archive::new(GzDecoder::new(reader));
+
+    let mut manifest: Option<SnapshotManifest> = None;
+    let mut snapshot = Snapshot::default();
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+
+        if manifest.is_none() {
+            if path != Path::new(MANIFEST_FILE) {
+                return Err(SnapshotArchiveError::MissingManifest);
+            }
+            let parsed = serde_yaml::from_slice::<SnapshotManifest>(&bytes)
+                .map_err(|source| SnapshotArchiveError::Manifest { source })?;
+
+            if (OLDEST_SUPPORTED_FORMAT_VERSION..=FORMAT_VERSION).contains(&parsed.format_version).not() {
+                return Err(SnapshotArchiveError::UnsupportedFormatVersion { actual: parsed.format_version, oldest_supported: OLDEST_SUPPORTED_FORMAT_VERSION, supported: FORMAT_VERSION });
+            }
+            manifest = Some(parsed);
+        }
+        else if path == Path::new(SPECIFICATIONS_FILE) {
+            continue; //only meant for humans and CLEO, the resources are read from the Protobuf files
+        }
+        else {
+            read_resource(&mut snapshot, path, bytes)?;
+        }
+    }
+
+    let manifest = manifest.ok_or(SnapshotArchiveError::MissingManifest)?;
+    snapshot.revision = manifest.revision;
+
+    let actual_counts = snapshot.resource_counts();
+    for table in manifest.resources.keys().chain(actual_counts.keys()) {
+        let expected = manifest.resources.get(table).copied().unwrap_or_default();
+        let actual = actual_counts.get(table).copied().unwrap_or_default();
+        if expected != actual {
+            return Err(SnapshotArchiveError::IncompleteArchive { table: table.clone(), expected, actual });
+        }
+    }
+
+    Ok(snapshot)
+}
+
+fn specifications(snapshot: &Snapshot) -> Result<String, serde_yaml::Error> {
+    let mut peers = snapshot.peer_descriptors.values().cloned().collect::<Vec<_>>();
+    peers.sort_by_key(|peer| (peer.name.to_string(), peer.id.uuid));
+
+    let mut clusters = snapshot.cluster_configurations.values().cloned().collect::<Vec<_>>();
+    clusters.sort_by_key(|cluster| (cluster.name.to_string(), cluster.id.0));
+
+    let documents = peers.into_iter().map(SpecificationDocument::from)
+        .chain(clusters.into_iter().map(SpecificationDocument::from))
+        .map(YamlSpecificationDocument::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    YamlSpecificationFile { documents }.try_to_yaml_string()
+}
+
+fn append_resources<R, W>(tar: &mut tar::Builder<W>, resources: &HashMap<R::Id, R>) -> std::io::Result<()>
+where
+    R: Resource + Persistable,
+    W: Write,
+{
+    let resources = resources.iter()
+        .map(|(id, resource)| (ResourceId::<R>::into_id(id.clone()).value(), resource))
+        .collect::<BTreeMap<_, _>>(); //sorted, so that exports of the same state are identical
+
+    for (id, resource) in resources {
+        let bytes = R::Proto::from(resource.clone()).encode_to_vec();
+        append_file(tar, resource_path::<R>(id), &bytes)?;
+    }
+    Ok(())
+}
+
+fn append_file<W: Write>(tar: &mut tar::Builder<W>, path: impl AsRef<Path>, data: &[u8]) -> std::io::Result<()> {
+    let modification_time = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(modification_time.as_secs());
+    header.set_cksum();
+    tar.append_data(&mut header, path, data)
+}
+
+fn resource_path<R: Persistable>(id: Uuid) -> PathBuf {
+    PathBuf::from(PROTOBUF_DIRECTORY)
+        .join(R::TABLE)
+        .join(format!("{id}.{PROTOBUF_FILE_EXTENSION}"))
+}
+
+fn read_resource(snapshot: &mut Snapshot, path: PathBuf, bytes: Vec<u8>) -> Result<(), SnapshotArchiveError> {
+    let Some((table, id)) = parse_resource_path(&path) else {
+        return Err(SnapshotArchiveError::UnexpectedFile { path });
+    };
+
+    if table == PeerDescriptor::TABLE {
+        decode_into(&mut snapshot.peer_descriptors, id, path, bytes)
+    } else if table == PeerSetupRecord::TABLE {
+        decode_into(&mut snapshot.peer_setups, id, path, bytes)
+    } else if table == ClientCertificateRecord::TABLE {
+        decode_into(&mut snapshot.client_certificates, id, path, bytes)
+    } else if table == PeerDecommissionRecord::TABLE {
+        decode_into(&mut snapshot.peer_decommissions, id, path, bytes)
+    } else if table == ClusterConfiguration::TABLE {
+        decode_into(&mut snapshot.cluster_configurations, id, path, bytes)
+    } else if table == ClusterDeployment::TABLE {
+        decode_into(&mut snapshot.cluster_deployments, id, path, bytes)
+    } else if table == AuditEntry::TABLE {
+        decode_into(&mut snapshot.audit_entries, id, path, bytes)
+    } else if table == NotificationSubscription::TABLE {
+        decode_into(&mut snapshot.notification_subscriptions, id, path, bytes)
+    } else {
+        Err(SnapshotArchiveError::UnexpectedFile { path })
+    }
+}
+
+/// Extracts table and ID from a path like `protobuf/<table>/<id>.binpb`.
+fn parse_resource_path(path: &Path) -> Option<(&str, Uuid)> {
+    let id = path.file_name()?.to_str()?
+        .strip_suffix(&format!(".{PROTOBUF_FILE_EXTENSION}"))?;
+    let id = Uuid::parse_str(id).ok()?;
+
+    let table_directory = path.parent()?;
+    if table_directory.parent()? != Path::new(PROTOBUF_DIRECTORY) {
+        return None;
+    }
+    let table = table_directory.file_name()?.to_str()?;
+
+    Some((table, id))
+}
+
+fn decode_into<R>(resources: &mut HashMap<R::Id, R>, id: Uuid, path: PathBuf, bytes: Vec<u8>) -> Result<(), SnapshotArchiveError>
+where R: Resource + Persistable {
+    let resource = R::try_from_bytes(bytes)
+        .map_err(|source| SnapshotArchiveError::Decode { path: path.clone(), source })?;
+
+    match resources.insert(ResourceId::<R>::from_id(Id::from(id)), resource) {
+        None => Ok(()),
+        Some(_) => Err(SnapshotArchiveError::UnexpectedFile { path }), //same resource contained twice
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SnapshotArchiveError {
+    #[error("Failed to read or write snapshot archive")]
+    Io(#[from] std::io::Error),
+    #[error("Snapshot archive does not start with a 'manifest.yaml'")]
+    MissingManifest,
+    #[error("Failed to read or write manifest of snapshot archive")]
+    Manifest {
+        #[source] source: serde_yaml::Error,
+    },
+    #[error("Failed to write specifications into snapshot archive")]
+    Specifications {
+        #[source] source: serde_yaml::Error,
+    },
+    #[error("Snapshot archive has format version {actual}, but only versions {oldest_supported} to {supported} are supported")]
+    UnsupportedFormatVersion {
+        actual: u32,
+        oldest_supported: u32,
+        supported: u32,
+    },
+    #[error("Unexpected file '{}' in snapshot archive", .path.display())]
+    UnexpectedFile {
+        path: PathBuf,
+    },
+    #[error("Failed to decode '{}' in snapshot archive", .path.display())]
+    Decode {
+        path: PathBuf,
+        #[source] source: PersistenceError,
+    },
+    #[error("Manifest of snapshot archive lists {expected} resource(s) of kind '{table}', but the archive contains {actual}")]
+    IncompleteArchive {
+        table: String,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manager::testing::ClusterFixture;
+    use crate::resource::manager::ResourceManager;
+    use googletest::prelude::*;
+
+    #[tokio::test]
+    async fn should_read_written_archive() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let cluster = ClusterFixture::create(resource_manager.clone()).await?;
+        let snapshot = resource_manager.resources(async |resources| resources.export_snapshot()).await??;
+
+        let mut archive = Vec::new();
+        write_archive(&snapshot, &mut archive)?;
+        let result = read_archive(archive.as_slice())?;
+
+        assert_that!(result, eq(&snapshot));
+        assert_that!(result.cluster_configurations.get(&cluster.id), some(eq(&cluster.configuration)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_contain_specifications_readable_by_cleo() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let cluster = ClusterFixture::create(resource_manager.clone()).await?;
+        let snapshot = resource_manager.resources(async |resources| resources.export_snapshot()).await??;
+
+        let mut archive = Vec::new();
+        write_archive(&snapshot, &mut archive)?;
+
+        let mut archive = tar::Archive::new(GzDecoder::new(archive.as_slice()));
+        let mut specifications = String::new();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if entry.path()? == Path::new(SPECIFICATIONS_FILE) {
+                entry.read_to_string(&mut specifications)?;
+            }
+        }
+
+        let documents = YamlSpecificationFile::try_from_yaml_str(&specifications)?.documents
+            .into_iter()
+            .map(SpecificationDocument::try_from)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let ids = documents.iter().map(|document| document.metadata.id).collect::<Vec<_>>();
+        assert_that!(ids, unordered_elements_are![eq(&Some(cluster.peer_a.id.uuid)), eq(&Some(cluster.peer_b.id.uuid)), eq(&Some(cluster.id.0))]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_archive_of_unknown_format_version() -> anyhow::Result<()> {
+        let manifest = SnapshotManifest {
+            format_version: FORMAT_VERSION + 1,
+            carl_version: String::from("0.0.0"),
+            created_at: String::from("2025-01-01T00:00:00+00:00"),
+            revision: Revision::default(),
+            resources: BTreeMap::new(),
+        };
+
+        let mut archive = Vec::new();
+        {
+            let mut tar = tar::Builder::new(GzEncoder::new(&mut archive, Compression::default()));
+            append_file(&mut tar, MANIFEST_FILE, serde_yaml::to_string(&manifest)?.as_bytes())?;
+            tar.into_inner()?.finish()?;
+        }
+
+        let result = read_archive(archive.as_slice());
+        assert_that!(result, err(matches_pattern!(SnapshotArchiveError::UnsupportedFormatVersion { actual: eq(&(FORMAT_VERSION + 1)), .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn should_read_archive_of_previous_format_version_without_notification_subscriptions() -> anyhow::Result<()> {
+        let manifest = SnapshotManifest {
+            format_version: OLDEST_SUPPORTED_FORMAT_VERSION,
+            carl_version: String::from("0.0.0"),
+            created_at: String::from("2025-01-01T00:00:00+00:00"),
+            revision: Revision::from(7),
+            resources: BTreeMap::from([(String::from(PeerDescriptor::TABLE), 0)]),
+        };
+
+        let mut archive = Vec::new();
+        {
+            let mut tar = tar::Builder::new(GzEncoder::new(&mut archive, Compression::default()));
+            append_file(&mut tar, MANIFEST_FILE, serde_yaml::to_string(&manifest)?.as_bytes())?;
+            tar.into_inner()?.finish()?;
+        }
+
+        let result = read_archive(archive.as_slice())?;
+        assert_that!(result.revision, eq(Revision::from(7)));
+        assert!(result.notification_subscriptions.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime};
+
+use tracing::{debug, info};
+
+use opendut_types::user::token::{PersonalAccessToken, PersonalAccessTokenDescriptor, PersonalAccessTokenId, PersonalAccessTokenName, PersonalAccessTokenOwner, PersonalAccessTokenOwnerId, PersonalAccessTokenScope};
+
+use crate::auth::authorization::Role;
+use crate::auth::personal_access_tokens;
+use crate::resource::api::resources::Resources;
+use crate::resource::persistence::error::PersistenceError;
+use crate::resource::storage::ResourcesStorageApi;
+
+pub struct CreatePersonalAccessTokenParams {
+    pub name: PersonalAccessTokenName,
+    pub owner: String,
+    /// Roles of the owner, which the scopes of the token must not exceed.
+    pub owner_roles: HashSet<Role>,
+    /// OIDC roles of the owner, which are recorded for all their tokens to limit the roles these grant.
+    pub owner_role_claims: Vec<String>,
+    /// OIDC groups of the owner, which determine the projects the token grants access to.
+    pub owner_groups: Vec<String>,
+    pub scopes: Vec<PersonalAccessTokenScope>,
+    pub validity: Duration,
+    pub maximum_validity: Duration,
+}
+
+impl Resources<'_> {
+    /// Creates a personal access token and returns it together with its descriptor. Only the hash of the token's secret is stored.
+    #[tracing::instrument(skip_all, level="trace")]
+    pub fn create_personal_access_token(&mut self, params: CreatePersonalAccessTokenParams) -> Result<(PersonalAccessTokenDescriptor, String), CreatePersonalAccessTokenError> {
+        let CreatePersonalAccessTokenParams { name, owner, owner_roles, owner_role_claims, owner_groups, scopes, validity, maximum_validity } = params;
+
+        if validity.is_zero() || validity > maximum_validity {
+            return Err(CreatePersonalAccessTokenError::InvalidValidity { requested: validity, maximum: maximum_validity });
+        }
+        if let Some(scope) = scopes.iter().find(|scope| !owner_roles.contains(&Role::from(**scope))) {
+            return Err(CreatePersonalAccessTokenError::ScopeNotPermitted { scope: *scope });
+        }
+
+        let id = PersonalAccessTokenId::random();
+        let (token, secret_hash) = personal_access_tokens::generate_token(id)
+            .map_err(|source| CreatePersonalAccessTokenError::Generation { source })?;
+
+        let created_at = SystemTime::now();
+        let descriptor = PersonalAccessTokenDescriptor {
+            id,
+            name,
+            owner,
+            scopes,
+            created_at,
+            expires_at: created_at + validity,
+            revoked_at: None,
+        };
+
+        debug!("Creating personal access token '{}' <{id}> for user '{}'.", descriptor.name, descriptor.owner);
+        self.insert(id, PersonalAccessToken { descriptor: Clone::clone(&descriptor), secret_hash })
+            .map_err(|source| CreatePersonalAccessTokenError::Persistence { source })?;
+
+        let owner = PersonalAccessTokenOwner {
+            owner: Clone::clone(&descriptor.owner),
+            roles: owner_role_claims,
+            groups: owner_groups,
+            recorded_at: created_at,
+        };
+        self.insert(PersonalAccessTokenOwnerId::of(&owner.owner), owner)
+            .map_err(|source| CreatePersonalAccessTokenError::Persistence { source })?;
+
+        info!("Successfully created personal access token '{}' <{id}> for user '{}'.", descriptor.name, descriptor.owner);
+        Ok((descriptor, token))
+    }
+
+    /// Lists the personal access tokens of the given owner or, if `None`, of all users, ordered from oldest to newest.
+    #[tracing::instrument(skip_all, level="trace")]
+    pub fn list_personal_access_tokens(&self, owner: Option<&str>) -> Result<Vec<PersonalAccessTokenDescriptor>, ListPersonalAccessTokensError> {
+        let mut tokens = self.list::<PersonalAccessToken>()
+            .map_err(|source| ListPersonalAccessTokensError::Persistence { source })?
+            .into_values()
+            .map(|token| token.descriptor)
+            .filter(|descriptor| owner.is_none_or(|owner| descriptor.owner == owner))
+            .collect::<Vec<_>>();
+
+        tokens.sort_by_key(|descriptor| descriptor.created_at);
+
+        Ok(tokens)
+    }
+
+    /// Revokes a personal access token of the given owner or, if `None`, of any user. Revoking an already revoked token has no effect.
+    #[tracing::instrument(skip_all, level="trace")]
+    pub fn revoke_personal_access_token(&mut self, id: PersonalAccessTokenId, owner: Option<&str>) -> Result<PersonalAccessTokenDescriptor, RevokePersonalAccessTokenError> {
+        let token = self.get::<PersonalAccessToken>(id)
+            .map_err(|source| RevokePersonalAccessTokenError::Persistence { id, source })?
+            .filter(|token| owner.is_none_or(|owner| token.descriptor.owner == owner))
+            .ok_or(RevokePersonalAccessTokenError::NotFound { id })?;
+
+        if token.descriptor.revoked_at.is_some() {
+            debug!("Personal access token <{id}> is already revoked.");
+            return Ok(token.descriptor);
+        }
+
+        let token = PersonalAccessToken {
+            descriptor: PersonalAccessTokenDescriptor { revoked_at: Some(SystemTime::now()), ..token.descriptor },
+            ..token
+        };
+        self.insert(id, Clone::clone(&token))
+            .map_err(|source| RevokePersonalAccessTokenError::Persistence { id, source })?;
+
+        info!("Successfully revoked personal access token '{}' <{id}> of user '{}'.", token.descriptor.name, token.descriptor.owner);
+        Ok(token.descriptor)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CreatePersonalAccessTokenError {
+    #[error("Personal access token may not be scoped to '{scope}', which is not a role of its owner")]
+    ScopeNotPermitted { scope: PersonalAccessTokenScope },
+    #[error("Validity of personal access token must be greater than zero and at most {maximum:?}, but was {requested:?}")]
+    InvalidValidity { requested: Duration, maximum: Duration },
+    #[error("Error when generating personal access token")]
+    Generation {
+        #[source] source: anyhow::Error,
+    },
+    #[error("Error when accessing persistence while creating personal access token")]
+    Persistence {
+        #[source] source: PersistenceError,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListPersonalAccessTokensError {
+    #[error("Error when accessing persistence while listing personal access tokens")]
+    Persistence {
+        #[source] source: PersistenceError,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RevokePersonalAccessTokenError {
+    #[error("Personal access token <{id}> not found")]
+    NotFound { id: PersonalAccessTokenId },
+    #[error("Error when accessing persistence while revoking personal access token <{id}>")]
+    Persistence {
+        id: PersonalAccessTokenId,
+        #[source] source: PersistenceError,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use crate::resource::manager::ResourceManager;
+
+    use super::*;
+
+    fn params(owner: &str, scopes: Vec<PersonalAccessTokenScope>) -> CreatePersonalAccessTokenParams {
+        CreatePersonalAccessTokenParams {
+            name: PersonalAccessTokenName::try_from("nightly-build").unwrap(),
+            owner: owner.to_owned(),
+            owner_roles: HashSet::from([Role::Editor, Role::Viewer]),
+            owner_role_claims: vec![],
+            owner_groups: vec![],
+            scopes,
+            validity: Duration::from_secs(3600),
+            maximum_validity: Duration::from_secs(86400),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_only_create_tokens_within_the_roles_and_validity_permitted() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+
+        let (descriptor, token) = resource_manager.resources_mut(async |resources| {
+            resources.create_personal_access_token(params("alice", vec![PersonalAccessTokenScope::Viewer]))
+        }).await??;
+        assert_that!(descriptor.owner, eq("alice"));
+        assert!(token.starts_with(personal_access_tokens::TOKEN_PREFIX));
+
+        let result = resource_manager.resources_mut(async |resources| {
+            resources.create_personal_access_token(params("alice", vec![PersonalAccessTokenScope::Administrator]))
+        }).await?;
+        assert_that!(result, err(matches_pattern!(CreatePersonalAccessTokenError::ScopeNotPermitted { scope: eq(&PersonalAccessTokenScope::Administrator) })));
+
+        let result = resource_manager.resources_mut(async |resources| {
+            resources.create_personal_access_token(CreatePersonalAccessTokenParams { validity: Duration::from_secs(2 * 86400), ..params("alice", vec![]) })
+        }).await?;
+        assert_that!(result, err(matches_pattern!(CreatePersonalAccessTokenError::InvalidValidity { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_list_and_revoke_only_own_tokens() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+
+        let (alice_token, _) = resource_manager.resources_mut(async |resources| {
+            resources.create_personal_access_token(params("alice", vec![PersonalAccessTokenScope::Editor]))
+        }).await??;
+        resource_manager.resources_mut(async |resources| {
+            resources.create_personal_access_token(params("bob", vec![PersonalAccessTokenScope::Viewer]))
+        }).await??;
+
+        let tokens = resource_manager.resources(async |resources| resources.list_personal_access_tokens(Some("alice"))).await??;
+        assert_that!(tokens, elements_are![eq(&alice_token)]);
+        let tokens = resource_manager.resources(async |resources| resources.list_personal_access_tokens(None)).await??;
+        assert_that!(tokens.len(), eq(2));
+
+        let result = resource_manager.resources_mut(async |resources| {
+            resources.revoke_personal_access_token(alice_token.id, Some("bob"))
+        }).await?;
+        assert_that!(result, err(matches_pattern!(RevokePersonalAccessTokenError::NotFound { .. })));
+
+        let revoked = resource_manager.resources_mut(async |resources| {
+            resources.revoke_personal_access_token(alice_token.id, Some("alice"))
+        }).await??;
+        assert!(revoked.revoked_at.is_some());
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use opentelemetry::metrics::Counter;
+use opentelemetry::{global, KeyValue};
+use tokio::sync::RwLock;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, error, info, warn};
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::vpn::reconciliation::{VpnReconciliationOutcome, VpnReconciliationReport};
+use opendut_vpn::{ExpectedVpnState, ReconcileError};
+
+use crate::manager::cluster_manager::{determine_member_interface_mapping, ClusterManagerRef};
+use crate::resource::api::resources::Resources;
+use crate::resource::manager::ResourceManagerRef;
+use crate::resource::persistence::error::{PersistenceError, PersistenceResult};
+use crate::resource::storage::ResourcesStorageApi;
+use crate::settings::vpn::Vpn;
+
+pub type VpnReconcilerRef = Arc<VpnReconciler>;
+
+/// Compares the state of the VPN with the registered peers and deployed clusters, to repair drift,
+/// e.g. groups left behind after a crash of CARL or peers deleted directly in the VPN.
+pub struct VpnReconciler {
+    resource_manager: ResourceManagerRef,
+    /// Locked while reconciling, so that no cluster is deployed meanwhile. Its VPN group would otherwise appear missing and be created twice.
+    /// `None`, if CARL does not deploy clusters, e.g. while importing a snapshot.
+    cluster_manager: Option<ClusterManagerRef>,
+    vpn: Vpn,
+    last_report: RwLock<Option<VpnReconciliationReport>>,
+    runs: Counter<u64>,
+    findings: Counter<u64>,
+}
+
+impl VpnReconciler {
+    /// Creates the reconciler and, if the VPN is enabled, starts reconciling in the configured interval.
+    pub fn create(resource_manager: ResourceManagerRef, cluster_manager: Option<ClusterManagerRef>, vpn: Vpn, options: VpnReconcilerOptions) -> VpnReconcilerRef {
+        let meter = global::meter(opendut_util::telemetry::DEFAULT_METER_NAME);
+
+        let self_ref = Arc::new(Self {
+            resource_manager,
+            cluster_manager,
+            vpn,
+            last_report: RwLock::new(None),
+            runs: meter.u64_counter("vpn_reconciliation_runs").build(),
+            findings: meter.u64_counter("vpn_reconciliation_findings").build(),
+        });
+
+        match (&self_ref.vpn, options.interval) {
+            (Vpn::Enabled { .. }, Some(interval)) => {
+                let reconciler = Arc::clone(&self_ref);
+                tokio::spawn(async move {
+                    let mut interval = tokio::time::interval(interval);
+                    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                    loop {
+                        interval.tick().await;
+                        if let Err(cause) = reconciler.reconcile().await {
+                            error!("Failed to reconcile the state of the VPN:\n  {cause}");
+                        }
+                    }
+                });
+            }
+            (Vpn::Enabled { .. }, None) => debug!("Periodic reconciliation of the VPN state is disabled."),
+            (Vpn::Disabled, _) => {}
+        }
+
+        self_ref
+    }
+
+    /// Reconciles the state of the VPN now and keeps the report, so it can be queried via [`VpnReconciler::last_report`].
+    ///
+    /// The resources stay locked until the VPN is reconciled, so that no peers or clusters are stored or deleted meanwhile,
+    /// which the reconciliation would otherwise consider orphaned or missing, based on the state read before.
+    #[tracing::instrument(skip(self), level="debug")]
+    pub async fn reconcile(&self) -> Result<VpnReconciliationReport, ReconcileVpnError> {
+        let Vpn::Enabled { vpn_client } = &self.vpn else {
+            return Err(ReconcileVpnError::VpnDisabled);
+        };
+
+        let _cluster_manager = match &self.cluster_manager {
+            Some(cluster_manager) => Some(cluster_manager.lock().await),
+            None => None,
+        };
+
+        let result = self.resource_manager.resources(async |resources| {
+            let expected = determine_expected_state(resources)?;
+            vpn_client.reconcile(&expected).await
+                .map_err(ReconcileVpnError::from)
+        }).await
+            .map_err(ReconcileVpnError::from)
+            .and_then(|result| result);
+
+        let findings = match result {
+            Ok(findings) => findings,
+            Err(cause) => {
+                self.runs.add(1, &[KeyValue::new("result", "failure")]);
+                return Err(cause);
+            }
+        };
+        self.runs.add(1, &[KeyValue::new("result", "success")]);
+
+        for finding in &findings {
+            self.findings.add(1, &[
+                KeyValue::new("mismatch", finding.mismatch.kind()),
+                KeyValue::new("outcome", finding.outcome.kind()),
+            ]);
+            match finding.outcome {
+                VpnReconciliationOutcome::Repaired => info!("{}: {}.", finding.mismatch, finding.outcome),
+                VpnReconciliationOutcome::Unrepaired | VpnReconciliationOutcome::Failed { .. } => warn!("{}: {}.", finding.mismatch, finding.outcome),
+            }
+        }
+
+        let report = VpnReconciliationReport {
+            timestamp: SystemTime::now(),
+            findings,
+        };
+        *self.last_report.write().await = Some(Clone::clone(&report));
+
+        Ok(report)
+    }
+
+    pub async fn last_report(&self) -> Option<VpnReconciliationReport> {
+        Clone::clone(&*self.last_report.read().await)
+    }
+
+}
+
+fn determine_expected_state(resources: &Resources) -> PersistenceResult<ExpectedVpnState> {
+    let all_peers = resources.list::<PeerDescriptor>()?
+        .into_values()
+        .collect::<Vec<_>>();
+    let configurations = resources.list::<ClusterConfiguration>()?;
+    let deployments = resources.list::<ClusterDeployment>()?;
+
+    let mut expected = ExpectedVpnState {
+        peers: all_peers.iter().map(|peer| peer.id).collect(),
+        ..Default::default()
+    };
+
+    for cluster_id in deployments.into_keys() {
+        let Some(configuration) = configurations.get(&cluster_id) else {
+            warn!("Cluster <{cluster_id}> is deployed, but has no configuration. Not reconciling it in the VPN.");
+            continue;
+        };
+        match determine_member_interface_mapping(Clone::clone(&configuration.devices), Clone::clone(&all_peers), configuration.leader) {
+            Ok(member_interface_mapping) => {
+                expected.clusters.insert(cluster_id, member_interface_mapping.into_keys().collect());
+            }
+            Err(cause) => warn!("Could not determine members of deployed cluster <{cluster_id}>. Not reconciling it in the VPN.\n  {cause}"),
+        }
+    }
+
+    Ok(expected)
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ReconcileVpnError {
+    #[error("The VPN is disabled, so there is nothing to reconcile")]
+    VpnDisabled,
+    #[error("The registered peers and deployed clusters could not be loaded")]
+    Persistence(#[from] PersistenceError),
+    #[error("The state of the VPN could not be reconciled")]
+    Vpn(#[from] ReconcileError),
+}
+
+pub struct VpnReconcilerOptions {
+    /// `None`, if the state should only be reconciled on request.
+    pub interval: Option<Duration>,
+}
+impl VpnReconcilerOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let interval_ms = config.get::<u64>("vpn.reconciliation.interval.ms")?;
+
+        Ok(VpnReconcilerOptions {
+            interval: (interval_ms > 0).then(|| Duration::from_millis(interval_ms)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::ClusterId;
+    use opendut_types::peer::PeerId;
+    use opendut_types::vpn::VpnPeerConfiguration;
+    use opendut_types::vpn::reconciliation::{VpnMismatch, VpnReconciliationFinding};
+    use opendut_vpn::{CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, VpnManagementClient};
+
+    use crate::manager::testing::ClusterFixture;
+    use crate::resource::manager::ResourceManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_reconcile_the_registered_peers_and_deployed_clusters_and_keep_the_report() -> anyhow::Result<()> {
+        let resource_manager = ResourceManager::new_in_memory();
+        let cluster = ClusterFixture::create(Arc::clone(&resource_manager)).await?;
+        resource_manager.insert(cluster.id, ClusterDeployment { id: cluster.id }).await?;
+
+        let finding = VpnReconciliationFinding {
+            mismatch: VpnMismatch::OrphanedCluster { cluster_id: ClusterId::random() },
+            outcome: VpnReconciliationOutcome::Repaired,
+        };
+        let vpn_client = Arc::new(RecordingVpnClient::returning(vec![Clone::clone(&finding)]));
+        let vpn = Vpn::Enabled { vpn_client: Arc::clone(&vpn_client) as Arc<dyn VpnManagementClient + Send + Sync> };
+        let testee = VpnReconciler::create(resource_manager, None, vpn, VpnReconcilerOptions { interval: None });
+
+        assert_that!(testee.last_report().await, none());
+
+        let report = testee.reconcile().await?;
+
+        assert_that!(report.findings, elements_are![eq(&finding)]);
+        assert_that!(testee.last_report().await, some(eq(&report)));
+
+        let expected = vpn_client.expected.lock().unwrap().clone().expect("VPN should have been reconciled.");
+        assert_that!(expected.peers, unordered_elements_are![eq(&cluster.peer_a.id), eq(&cluster.peer_b.id)]);
+        assert_that!(expected.clusters.get(&cluster.id).cloned(), some(unordered_elements_are![eq(&cluster.peer_a.id), eq(&cluster.peer_b.id)]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_reconcile_a_disabled_vpn() -> anyhow::Result<()> {
+        let testee = VpnReconciler::create(ResourceManager::new_in_memory(), None, Vpn::Disabled, VpnReconcilerOptions { interval: None });
+
+        assert_that!(testee.reconcile().await, err(matches_pattern!(ReconcileVpnError::VpnDisabled)));
+        Ok(())
+    }
+
+    /// Records the expected state passed for reconciliation and returns the given findings.
+    struct RecordingVpnClient {
+        expected: Mutex<Option<ExpectedVpnState>>,
+        findings: Vec<VpnReconciliationFinding>,
+    }
+    impl RecordingVpnClient {
+        fn returning(findings: Vec<VpnReconciliationFinding>) -> Self {
+            Self { expected: Mutex::default(), findings }
+        }
+    }
+
+    #[async_trait]
+    impl VpnManagementClient for RecordingVpnClient {
+        async fn create_cluster(&self, _cluster_id: ClusterId, _peers: &[PeerId]) -> std::result::Result<(), CreateClusterError> { Ok(()) }
+        async fn delete_cluster(&self, _cluster_id: ClusterId) -> std::result::Result<(), DeleteClusterError> { Ok(()) }
+        async fn create_peer(&self, _peer_id: PeerId) -> std::result::Result<(), CreatePeerError> { Ok(()) }
+        async fn delete_peer(&self, _peer_id: PeerId) -> std::result::Result<(), DeletePeerError> { Ok(()) }
+        async fn generate_vpn_peer_configuration(&self, _peer_id: PeerId) -> std::result::Result<VpnPeerConfiguration, CreateVpnPeerConfigurationError> {
+            Ok(VpnPeerConfiguration::Disabled)
+        }
+
+        async fn reconcile(&self, expected: &ExpectedVpnState) -> std::result::Result<Vec<VpnReconciliationFinding>, ReconcileError> {
+            *self.expected.lock().unwrap() = Some(Clone::clone(expected));
+            Ok(Clone::clone(&self.findings))
+        }
+    }
+}
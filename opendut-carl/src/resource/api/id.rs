@@ -1,9 +1,16 @@
 use crate::resource::api::Resource;
+use opendut_types::audit::{AuditEntry, AuditEntryId};
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::notification::{NotificationSubscription, NotificationSubscriptionId};
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::certificate::{ClientCertificateId, ClientCertificateRecord};
+use opendut_types::peer::decommission::PeerDecommissionRecord;
+use opendut_types::peer::setup::{PeerSetupId, PeerSetupRecord};
 use opendut_types::peer::state::PeerConnectionState;
 use opendut_types::peer::{PeerDescriptor, PeerId};
 use opendut_types::resources::Id;
+use opendut_types::user::token::{PersonalAccessToken, PersonalAccessTokenId, PersonalAccessTokenOwner, PersonalAccessTokenOwnerId};
+use opendut_types::user::{LocalUser, LocalUserId};
 
 
 pub trait ResourceId<R: Resource> {
@@ -11,6 +18,14 @@ pub trait ResourceId<R: Resource> {
     fn from_id(id: Id) -> Self;
 }
 
+impl ResourceId<AuditEntry> for AuditEntryId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+    fn from_id(id: Id) -> Self {
+        AuditEntryId::from(id.value())
+    }
+}
 impl ResourceId<ClusterConfiguration> for ClusterId {
     fn into_id(self) -> Id {
         Id::from(self.0)
@@ -58,3 +73,59 @@ impl ResourceId<PeerConnectionState> for PeerId {
         PeerId::from(id.value())
     }
 }
+impl ResourceId<PeerSetupRecord> for PeerSetupId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+    fn from_id(id: Id) -> Self {
+        PeerSetupId::from(id.value())
+    }
+}
+impl ResourceId<ClientCertificateRecord> for ClientCertificateId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+    fn from_id(id: Id) -> Self {
+        ClientCertificateId::from(id.value())
+    }
+}
+impl ResourceId<PeerDecommissionRecord> for PeerId {
+    fn into_id(self) -> Id {
+        Id::from(self.uuid)
+    }
+    fn from_id(id: Id) -> Self {
+        PeerId::from(id.value())
+    }
+}
+impl ResourceId<LocalUser> for LocalUserId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+    fn from_id(id: Id) -> Self {
+        LocalUserId::from(id.value())
+    }
+}
+impl ResourceId<PersonalAccessToken> for PersonalAccessTokenId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+    fn from_id(id: Id) -> Self {
+        PersonalAccessTokenId::from(id.value())
+    }
+}
+impl ResourceId<PersonalAccessTokenOwner> for PersonalAccessTokenOwnerId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+    fn from_id(id: Id) -> Self {
+        PersonalAccessTokenOwnerId::from(id.value())
+    }
+}
+impl ResourceId<NotificationSubscription> for NotificationSubscriptionId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+    fn from_id(id: Id) -> Self {
+        NotificationSubscriptionId::from(id.value())
+    }
+}
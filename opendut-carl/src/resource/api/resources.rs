@@ -1,56 +1,172 @@
+use crate::resource::api::id::ResourceId;
 use crate::resource::api::Resource;
 use crate::resource::persistence::error::PersistenceResult;
-use crate::resource::persistence::persistable::Persistable;
+use crate::resource::persistence::persistable::{Persistable, StorageKind};
 use crate::resource::storage::{ResourceTransaction, ResourcesStorageApi};
-use crate::resource::subscription::{ResourceSubscriptionChannels, Subscribable, SubscriptionEvent};
+use crate::resource::subscription::{ResourceSubscriptionChannels, RevisionedSubscriptionEvent, Subscribable, SubscriptionEvent};
+use opendut_types::audit::{AuditAction, AuditActor, AuditEntry, AuditEntryId, AuditedResource};
+use opendut_types::resources::Revision;
 use std::collections::HashMap;
+use std::time::SystemTime;
 
 pub type RelayedSubscriptionEvents = ResourceSubscriptionChannels;
 
 pub struct Resources<'transaction> {
     transaction: ResourceTransaction<'transaction>,
+    actor: AuditActor,
 }
 impl<'transaction> Resources<'transaction> {
-    pub fn new(transaction: ResourceTransaction<'transaction>) -> Self {
-        Resources { transaction }
+    pub fn new(transaction: ResourceTransaction<'transaction>, actor: AuditActor) -> Self {
+        Resources { transaction, actor }
     }
+
+    /// The user or component on whose behalf modifications are made in this transaction.
+    pub fn actor(&self) -> &AuditActor {
+        &self.actor
+    }
+
+    /// Appends an entry to the audit log for modifications of persistent resources and keeps track of their creators.
+    /// Volatile resources, like the connection state of peers, change too frequently to be audited.
+    fn record_audit_entry<R>(&mut self, id: R::Id, action: AuditAction) -> PersistenceResult<()>
+    where R: Resource + Persistable {
+        if matches!(R::STORAGE, StorageKind::Volatile) || R::TABLE == AuditEntry::TABLE {
+            return Ok(());
+        }
+
+        match action {
+            AuditAction::Inserted => self.transaction.record_creator::<R>(id.clone(), &self.actor)?,
+            AuditAction::Removed => self.transaction.forget_creator::<R>(id.clone())?,
+        }
+
+        let entry = AuditEntry {
+            id: AuditEntryId::random(),
+            timestamp: SystemTime::now(),
+            actor: self.actor.clone(),
+            action,
+            resource: AuditedResource {
+                kind: String::from(R::TABLE),
+                id: ResourceId::<R>::into_id(id).value(),
+            },
+        };
+        self.transaction.append(entry.id, entry)
+    }
+
+    /// Inserts an entry into the audit log as-is, e.g. when importing a snapshot.
+    pub fn restore_audit_entry(&mut self, entry: AuditEntry) -> PersistenceResult<()> {
+        self.transaction.append(entry.id, entry)
+    }
+
+    /// Determines who created the resource, i.e. who first inserted it after it was last removed.
+    /// Returns `None`, if no creator is recorded, e.g. because the resource was stored before the audit log was introduced.
+    pub fn creator_of<R>(&self, id: R::Id) -> PersistenceResult<Option<AuditActor>>
+    where R: Resource + Persistable {
+        self.transaction.creator_of::<R>(id)
+    }
+
+    /// Records the creator of a resource, which was stored without one, e.g. when importing a snapshot.
+    pub fn restore_creator<R>(&mut self, id: R::Id, creator: &AuditActor) -> PersistenceResult<()>
+    where R: Resource + Persistable {
+        self.transaction.record_creator::<R>(id, creator)
+    }
+}
+
+impl Resources<'_> {
+    /// Checks that a resource is still at the revision a client expects, i.e. that nobody modified it since the client read it.
+    /// Passes, if the client does not expect any revision.
+    pub fn check_revision<R>(&self, id: R::Id, expected_revision: Option<Revision>) -> PersistenceResult<Result<(), RevisionConflict>>
+    where R: Resource + Persistable + Clone {
+        let Some(expected) = expected_revision else {
+            return Ok(Ok(()));
+        };
+
+        let actual = self.revision::<R>(id)?;
+
+        if actual == Some(expected) {
+            Ok(Ok(()))
+        } else {
+            Ok(Err(RevisionConflict { expected, actual }))
+        }
+    }
+}
+
+/// The revision of a resource differs from the revision a client expected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RevisionConflict {
+    pub expected: Revision,
+    /// `None`, if the resource was deleted in the meantime.
+    pub actual: Option<Revision>,
 }
 
-fn notify_insertion_on_success<R>(event: SubscriptionEvent<R>, result: &PersistenceResult<()>, relayed_subscription_events: &mut RelayedSubscriptionEvents)
+fn notify_insertion_on_success<R>(event: SubscriptionEvent<R>, result: &PersistenceResult<Revision>, relayed_subscription_events: &mut RelayedSubscriptionEvents)
 where R: Resource + Persistable + Subscribable {
-    if result.is_ok() {
+    if let Ok(revision) = result {
         let event_name = event.display_name().to_owned();
         relayed_subscription_events
-            .notify(event)
+            .notify(RevisionedSubscriptionEvent { event, revision: *revision })
             .unwrap_or_else(|_| panic!("should successfully queue notification about {event_name} resource during transaction"));
     }
 }
 
-fn notify_removal_on_success<R>(id: R::Id, result: &PersistenceResult<Option<R>>, relayed_subscription_events: &mut RelayedSubscriptionEvents)
+fn notify_removal_on_success<R>(id: R::Id, result: &PersistenceResult<Option<(R, Revision)>>, relayed_subscription_events: &mut RelayedSubscriptionEvents)
 where R: Resource + Persistable + Subscribable {
-    if let Ok(Some(resource)) = result {
+    if let Ok(Some((resource, revision))) = result {
         let event = SubscriptionEvent::Removed { id, value: resource.clone() };
         let event_name = event.display_name().to_owned();
         relayed_subscription_events
-            .notify(event)
+            .notify(RevisionedSubscriptionEvent { event, revision: *revision })
             .unwrap_or_else(|_| panic!("should successfully queue notification about {event_name} resource during transaction"));
 
     }
 }
 
+impl Resources<'_> {
+    /// Returns the revision of the most recent modification of any persistent resource.
+    pub fn latest_revision(&self) -> PersistenceResult<Revision> {
+        self.transaction.latest_revision()
+    }
+
+    /// Returns the revision of the most recent modification of any resource stored like `R`, i.e. persistent or volatile.
+    pub fn latest_revision_of<R: Persistable>(&self) -> PersistenceResult<Revision> {
+        self.transaction.latest_revision_of::<R>()
+    }
+
+    /// Like [`ResourcesStorageApi::insert`], but without recording an entry in the audit log,
+    /// e.g. when importing a snapshot, which brings along the audit entries of its resources.
+    pub fn restore<R>(&mut self, id: R::Id, resource: R) -> PersistenceResult<()>
+    where R: Resource + Persistable + Subscribable {
+        let result = self.transaction.insert(id.clone(), resource.clone())
+            .and_then(|()| self.transaction.latest_revision_of::<R>());
+        notify_insertion_on_success(SubscriptionEvent::Inserted { id, value: resource }, &result, self.transaction.relayed_subscription_events);
+        result.map(|_| ())
+    }
+}
+
 impl ResourcesStorageApi for Resources<'_> {
     fn insert<R>(&mut self, id: R::Id, resource: R) -> PersistenceResult<()>
     where R: Resource + Persistable + Subscribable {
-        let result = self.transaction.insert(id.clone(), resource.clone());
+        let result = self.transaction.insert(id.clone(), resource.clone())
+            .and_then(|()| self.transaction.latest_revision_of::<R>())
+            .and_then(|revision| {
+                self.record_audit_entry::<R>(id.clone(), AuditAction::Inserted)?;
+                Ok(revision)
+            });
         notify_insertion_on_success(SubscriptionEvent::Inserted { id, value: resource }, &result, self.transaction.relayed_subscription_events);
-        result
+        result.map(|_| ())
     }
 
     fn remove<R>(&mut self, id: R::Id) -> PersistenceResult<Option<R>>
     where R: Resource + Persistable + Subscribable {
-        let result = self.transaction.remove(id.clone());
+        let result = self.transaction.remove::<R>(id.clone())
+            .and_then(|removed| match removed {
+                Some(removed) => {
+                    let revision = self.transaction.latest_revision_of::<R>()?;
+                    self.record_audit_entry::<R>(id.clone(), AuditAction::Removed)?;
+                    Ok(Some((removed, revision)))
+                }
+                None => Ok(None),
+            });
         notify_removal_on_success(id, &result, self.transaction.relayed_subscription_events);
-        result
+        result.map(|removed| removed.map(|(removed, _)| removed))
     }
 
     fn get<R>(&self, id: R::Id) -> PersistenceResult<Option<R>>
@@ -62,4 +178,9 @@ impl ResourcesStorageApi for Resources<'_> {
     where R: Resource + Persistable + Clone {
         self.transaction.list()
     }
+
+    fn revision<R>(&self, id: R::Id) -> PersistenceResult<Option<Revision>>
+    where R: Resource + Persistable + Clone {
+        self.transaction.revision(id)
+    }
 }
@@ -1,8 +1,15 @@
 use crate::resource::api::id::ResourceId;
+use opendut_types::audit::{AuditEntry, AuditEntryId};
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::notification::{NotificationSubscription, NotificationSubscriptionId};
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::certificate::{ClientCertificateId, ClientCertificateRecord};
+use opendut_types::peer::decommission::PeerDecommissionRecord;
+use opendut_types::peer::setup::{PeerSetupId, PeerSetupRecord};
 use opendut_types::peer::state::PeerConnectionState;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::user::token::{PersonalAccessToken, PersonalAccessTokenId, PersonalAccessTokenOwner, PersonalAccessTokenOwnerId};
+use opendut_types::user::{LocalUser, LocalUserId};
 use std::any::Any;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -11,6 +18,9 @@ pub trait Resource: Any + Send + Sync + Debug + Clone {
     type Id: ResourceId<Self> + Clone + Hash + PartialEq + Eq + Debug;
 }
 
+impl Resource for AuditEntry {
+    type Id = AuditEntryId;
+}
 impl Resource for ClusterConfiguration {
     type Id = ClusterId;
 }
@@ -29,3 +39,24 @@ impl Resource for PeerDescriptor {
 impl Resource for PeerConnectionState {
     type Id = PeerId;
 }
+impl Resource for PeerSetupRecord {
+    type Id = PeerSetupId;
+}
+impl Resource for ClientCertificateRecord {
+    type Id = ClientCertificateId;
+}
+impl Resource for PeerDecommissionRecord {
+    type Id = PeerId;
+}
+impl Resource for LocalUser {
+    type Id = LocalUserId;
+}
+impl Resource for PersonalAccessToken {
+    type Id = PersonalAccessTokenId;
+}
+impl Resource for PersonalAccessTokenOwner {
+    type Id = PersonalAccessTokenOwnerId;
+}
+impl Resource for NotificationSubscription {
+    type Id = NotificationSubscriptionId;
+}
@@ -4,11 +4,14 @@ use crate::resource::persistence::error::{MapErrToInner, PersistenceResult};
 use crate::resource::persistence::persistable::Persistable;
 use crate::resource::storage::{PersistenceOptions, ResourceStorage, ResourcesStorageApi};
 pub use crate::resource::subscription::SubscriptionEvent;
-use crate::resource::subscription::{ResourceSubscriptionChannels, Subscribable, Subscription};
+use crate::resource::subscription::{ResourceSubscriptionChannel, ResourceSubscriptionChannels, Subscribable, Subscription};
 use std::collections::HashMap;
+use opendut_types::audit::AuditActor;
 use std::fmt::Display;
 use std::sync::Arc;
-use tokio::sync::{RwLock, RwLockWriteGuard};
+use tokio::sync::broadcast::error::TryRecvError;
+use tokio::sync::RwLock;
+use tracing::error;
 use crate::resource::ConnectError;
 
 pub type ResourceManagerRef = Arc<ResourceManager>;
@@ -67,7 +70,7 @@ impl ResourceManager {
     {
         let state = self.state.read().await;
         state.storage.resources(async move |transaction| {
-            let transaction = Resources::new(transaction);
+            let transaction = Resources::new(transaction, AuditActor::System);
             closure(&transaction).await
         }).await
     }
@@ -76,18 +79,32 @@ impl ResourceManager {
     /// - Opens a database transaction and then either commits it, or rolls it back when you return an `Err` out of the closure.
     /// - Acquires the lock for the database mutex and keeps it until the end of the closure.
     /// - Groups the async calls, so we only have to await at the end.
+    ///
+    /// Modifications are recorded in the audit log as done by CARL itself. Use [`Self::resources_mut_as`] for modifications requested by a user.
     pub async fn resources_mut<F, T, E>(&self, closure: F) -> PersistenceResult<Result<T, E>>
+    where
+        F: AsyncFnOnce(&mut Resources) -> Result<T, E>,
+        E: Display,
+    {
+        self.resources_mut_as(AuditActor::System, closure).await
+    }
+
+    /// Like [`Self::resources_mut`], but records the given actor in the audit log.
+    pub async fn resources_mut_as<F, T, E>(&self, actor: AuditActor, closure: F) -> PersistenceResult<Result<T, E>>
     where
         F: AsyncFnOnce(&mut Resources) -> Result<T, E>,
         E: Display,
     {
         let mut state = self.state.write().await;
         let (result, relayed_subscription_events) = state.storage.resources_mut(async move |transaction| {
-            let mut transaction = Resources::new(transaction);
+            let mut transaction = Resources::new(transaction, actor);
             closure(&mut transaction).await
         }).await?;
         if result.is_ok() {
-            Self::send_relayed_subscription_events(relayed_subscription_events, &mut state).await;
+            //the transaction is committed at this point, so the modification is not reported as failed
+            if let Err(cause) = Self::send_relayed_subscription_events(relayed_subscription_events, &mut state.subscribers) {
+                error!("Committed transaction, but could not notify all subscribers about its modifications: {cause}");
+            }
         }
         Ok(result)
     }
@@ -98,56 +115,73 @@ impl ResourceManager {
         state.subscribers.subscribe()
     }
 
-    async fn send_relayed_subscription_events(
+    /// Passes the events, which were queued during a transaction, on to the subscribers.
+    /// Relays the events of all resources, even if relaying the events of one resource fails.
+    fn send_relayed_subscription_events(
         relayed_subscription_events: RelayedSubscriptionEvents,
-        state: &mut RwLockWriteGuard<'_, State>,
-    ) {
-        let ResourceSubscriptionChannels {
+        subscribers: &mut ResourceSubscriptionChannels,
+    ) -> Result<(), RelaySubscriptionEventsError> {
+        let ResourceSubscriptionChannels { //destructured, so that newly added resources cannot be forgotten
             mut cluster_configuration,
             mut cluster_deployment,
             mut old_peer_configuration,
             mut peer_configuration,
             mut peer_descriptor,
             mut peer_connection_state,
+            mut peer_setup,
+            mut client_certificate,
+            mut peer_decommission,
+            mut local_user,
+            mut personal_access_token,
+            mut personal_access_token_owner,
+            mut notification_subscription,
         } = relayed_subscription_events;
 
+        [
+            relay(&mut cluster_configuration, subscribers),
+            relay(&mut cluster_deployment, subscribers),
+            relay(&mut old_peer_configuration, subscribers),
+            relay(&mut peer_configuration, subscribers),
+            relay(&mut peer_descriptor, subscribers),
+            relay(&mut peer_connection_state, subscribers),
+            relay(&mut peer_setup, subscribers),
+            relay(&mut client_certificate, subscribers),
+            relay(&mut peer_decommission, subscribers),
+            relay(&mut local_user, subscribers),
+            relay(&mut personal_access_token, subscribers),
+            relay(&mut personal_access_token_owner, subscribers),
+            relay(&mut notification_subscription, subscribers),
+        ]
+        .into_iter()
+        .collect()
+    }
+}
 
-        while let Ok(event) = cluster_configuration.1.try_recv() {
-            state.subscribers
-                .notify(event)
-                .expect("should successfully send notification about event during resource transaction");
-        }
-
-        while let Ok(event) = cluster_deployment.1.try_recv() {
-            state.subscribers
-                .notify(event)
-                .expect("should successfully send notification about event during resource transaction");
-        }
-
-        while let Ok(event) = old_peer_configuration.1.try_recv() {
-            state.subscribers
-                .notify(event)
-                .expect("should successfully send notification about event during resource transaction");
-        }
-
-        while let Ok(event) = peer_configuration.1.try_recv() {
-            state.subscribers
-                .notify(event)
-                .expect("should successfully send notification about event during resource transaction");
-        }
-
-        while let Ok(event) = peer_descriptor.1.try_recv() {
-            state.subscribers
-                .notify(event)
-                .expect("should successfully send notification about event during resource transaction");
-        }
-
-        while let Ok(event) = peer_connection_state.1.try_recv() {
-            state.subscribers
-                .notify(event)
-                .expect("should successfully send notification about event during resource transaction");
+fn relay<R>(relayed: &mut ResourceSubscriptionChannel<R>, subscribers: &mut ResourceSubscriptionChannels) -> Result<(), RelaySubscriptionEventsError>
+where R: Resource + Subscribable {
+    let resource = std::any::type_name::<R>();
+    let mut skipped = 0;
+    loop {
+        match relayed.1.try_recv() {
+            Ok(event) => subscribers.notify(event)
+                .map_err(|_| RelaySubscriptionEventsError::Send { resource })?,
+            Err(TryRecvError::Lagged(count)) => skipped += count,
+            Err(TryRecvError::Empty | TryRecvError::Closed) => break,
         }
     }
+    if skipped > 0 {
+        Err(RelaySubscriptionEventsError::Lagged { resource, skipped })
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RelaySubscriptionEventsError {
+    #[error("Subscribers of '{resource}' could not be notified, because the channel to them is closed.")]
+    Send { resource: &'static str },
+    #[error("{skipped} modifications of '{resource}' exceeded the capacity of the transaction's queue. Subscribers were not notified about them.")]
+    Lagged { resource: &'static str, skipped: u64 },
 }
 
 
@@ -220,7 +254,9 @@ mod test {
                         results_url: None,
                     }
                 ],
-            }
+            },
+            labels: Default::default(),
+            project: Default::default(),
         };
 
         let cluster_resource_id = ClusterId::random();
@@ -229,6 +265,9 @@ mod test {
             name: ClusterName::try_from("ClusterX032")?,
             leader: peer.id,
             devices: HashSet::new(),
+            labels: Default::default(),
+            device_selector: None,
+            project: Default::default(),
         };
 
 
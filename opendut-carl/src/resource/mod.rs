@@ -1,6 +1,8 @@
 use std::io;
 use std::path::PathBuf;
 
+use crate::resource::persistence::error::PersistenceError;
+
 pub(crate) mod api;
 pub mod manager;
 pub(crate) mod storage;
@@ -15,6 +17,8 @@ pub enum ConnectError {
     DatabaseDirCreate { dir: PathBuf, #[source] source: io::Error },
     #[error("Failed to create database in-memory")]
     DatabaseInMemoryCreate(#[source] redb::DatabaseError),
+    #[error("Failed to clean up database")]
+    DatabaseCleanup(#[source] PersistenceError),
 
 
     #[cfg(feature="postgres")]
@@ -17,6 +17,7 @@ pub enum PersistenceErrorKind {
     ProtobufDecode(#[source] prost::DecodeError),
     ProtobufConversion(#[source] opendut_types::proto::ConversionError),
     KeyValueStore(#[source] redb::Error),
+    ReadOnlyTransaction { operation: &'static str },
 
     #[cfg(feature="postgres")]
     DieselInternal(#[source] diesel::result::Error),
@@ -47,6 +48,14 @@ impl PersistenceError {
         }
     }
 
+    /// Modifications have to happen in a read-write transaction, i.e. via `resources_mut()`.
+    pub fn read_only_transaction(operation: &'static str) -> Self {
+        Self {
+            context_messages: Vec::new(),
+            source: PersistenceErrorKind::ReadOnlyTransaction { operation },
+        }
+    }
+
     pub fn context(mut self, message: impl Into<String>) -> Self {
         self.context_messages.push(message.into());
         self
@@ -84,6 +93,7 @@ impl Display for PersistenceErrorKind {
             Self::ProtobufDecode(source) => writeln!(f, "Error while converting stored bytes to ProtoBuf model while loading from persistence: {source}")?,
             Self::ProtobufConversion(source) => writeln!(f, "Error while converting from ProtoBuf model to internal model while loading from persistence: {source}")?,
             Self::KeyValueStore(source) => writeln!(f, "Error occurred in the key-value store: {source}")?,
+            Self::ReadOnlyTransaction { operation } => writeln!(f, "Attempted to {operation} in a read-only transaction.")?,
 
             #[cfg(feature="postgres")]
             Self::DieselInternal(source) => writeln!(f, "Error internal to Diesel, likely from transaction: {source}")?,
@@ -1,6 +1,6 @@
 use std::cmp::Ordering;
 
-use crate::resource::persistence::error::PersistenceResult;
+use crate::resource::persistence::error::{PersistenceError, PersistenceResult};
 use opendut_types::resources::Id;
 use redb::{AccessGuard, ReadableTable, TableError, TypeName};
 use uuid::Uuid;
@@ -35,12 +35,139 @@ impl Db<'_> {
 
     pub(crate) fn read_write_table(&self, table: TableDefinition) -> PersistenceResult<ReadWriteTable> {
         match self {
-            Db::Read(_) => unimplemented!("Called `.read_write_table()` on a Db::Read() variant."),
+            Db::Read(_) => Err(PersistenceError::read_only_transaction("open a table for writing")),
             Db::ReadWrite(transaction) => Ok(transaction.open_table(table)?),
         }
     }
 }
 
+/// Revision of each stored resource, keyed by the table of the resource and its ID.
+const REVISION_TABLE: redb::TableDefinition<(&str, u128), u64> = redb::TableDefinition::new("revision");
+/// Latest revision handed out, so that revisions keep increasing across all resources of a database.
+/// Volatile resources count their revisions in the in-memory database, so their frequent modifications do not cause writes to the persistent database.
+const REVISION_COUNTER_TABLE: redb::TableDefinition<&str, u64> = redb::TableDefinition::new("revision_counter");
+const REVISION_COUNTER_KEY: &str = "latest";
+/// Who created each persistent resource, keyed like the revisions, so that ownership can be checked without scanning the audit log.
+/// The value is the encoded ProtoBuf of the [`opendut_types::audit::AuditActor`].
+const CREATOR_TABLE: redb::TableDefinition<(&str, u128), &[u8]> = redb::TableDefinition::new("creator");
+
+impl Db<'_> {
+    pub(super) fn read_revision(&self, table: &str, id: Id) -> PersistenceResult<Option<u64>> {
+        let key = (table, id.value().as_u128());
+
+        let revision = match self {
+            Db::Read(transaction) => match transaction.open_table(REVISION_TABLE) {
+                Ok(revisions) => revisions.get(key)?.map(|revision| revision.value()),
+                Err(TableError::TableDoesNotExist(_)) => None,
+                Err(cause) => Err(redb::Error::from(cause))?,
+            },
+            Db::ReadWrite(transaction) => transaction.open_table(REVISION_TABLE)?
+                .get(key)?
+                .map(|revision| revision.value()),
+        };
+        Ok(revision)
+    }
+
+    pub(super) fn write_revision(&self, table: &str, id: Id, revision: u64) -> PersistenceResult<()> {
+        let mut revisions = self.read_write_revision_table()?;
+        revisions.insert((table, id.value().as_u128()), revision)?;
+        Ok(())
+    }
+
+    pub(super) fn remove_revision(&self, table: &str, id: Id) -> PersistenceResult<()> {
+        let mut revisions = self.read_write_revision_table()?;
+        revisions.remove((table, id.value().as_u128()))?;
+        Ok(())
+    }
+
+    /// Removes the revisions of all resources in the given table and returns how many were removed.
+    pub(crate) fn remove_revisions_of_table(&self, table: &str) -> PersistenceResult<usize> {
+        let mut revisions = self.read_write_revision_table()?;
+        let mut removed = 0;
+        revisions.retain_in((table, u128::MIN)..=(table, u128::MAX), |_, _| {
+            removed += 1;
+            false
+        })?;
+        Ok(removed)
+    }
+
+    /// Increments the revision counter and returns the new revision.
+    pub(super) fn next_revision(&self) -> PersistenceResult<u64> {
+        match self {
+            Db::Read(_) => Err(PersistenceError::read_only_transaction("increment the revision counter")),
+            Db::ReadWrite(transaction) => {
+                let mut counter = transaction.open_table(REVISION_COUNTER_TABLE)?;
+                let latest = counter.get(REVISION_COUNTER_KEY)?
+                    .map(|revision| revision.value())
+                    .unwrap_or_default();
+                let next = latest + 1;
+                counter.insert(REVISION_COUNTER_KEY, next)?;
+                Ok(next)
+            }
+        }
+    }
+
+    /// Returns the latest revision handed out, i.e. the revision of the most recent modification.
+    pub(super) fn latest_revision(&self) -> PersistenceResult<u64> {
+        let latest = match self {
+            Db::Read(transaction) => match transaction.open_table(REVISION_COUNTER_TABLE) {
+                Ok(counter) => counter.get(REVISION_COUNTER_KEY)?.map(|revision| revision.value()),
+                Err(TableError::TableDoesNotExist(_)) => None,
+                Err(cause) => Err(redb::Error::from(cause))?,
+            },
+            Db::ReadWrite(transaction) => transaction.open_table(REVISION_COUNTER_TABLE)?
+                .get(REVISION_COUNTER_KEY)?
+                .map(|revision| revision.value()),
+        };
+        Ok(latest.unwrap_or_default())
+    }
+
+    pub(super) fn read_creator(&self, table: &str, id: Id) -> PersistenceResult<Option<Vec<u8>>> {
+        let key = (table, id.value().as_u128());
+
+        let creator = match self {
+            Db::Read(transaction) => match transaction.open_table(CREATOR_TABLE) {
+                Ok(creators) => creators.get(key)?.map(|creator| creator.value().to_vec()),
+                Err(TableError::TableDoesNotExist(_)) => None,
+                Err(cause) => Err(redb::Error::from(cause))?,
+            },
+            Db::ReadWrite(transaction) => transaction.open_table(CREATOR_TABLE)?
+                .get(key)?
+                .map(|creator| creator.value().to_vec()),
+        };
+        Ok(creator)
+    }
+
+    pub(super) fn write_creator(&self, table: &str, id: Id, creator: &[u8]) -> PersistenceResult<()> {
+        match self {
+            Db::Read(_) => Err(PersistenceError::read_only_transaction("record the creator of a resource")),
+            Db::ReadWrite(transaction) => {
+                transaction.open_table(CREATOR_TABLE)?
+                    .insert((table, id.value().as_u128()), creator)?;
+                Ok(())
+            }
+        }
+    }
+
+    pub(super) fn remove_creator(&self, table: &str, id: Id) -> PersistenceResult<()> {
+        match self {
+            Db::Read(_) => Err(PersistenceError::read_only_transaction("forget the creator of a resource")),
+            Db::ReadWrite(transaction) => {
+                transaction.open_table(CREATOR_TABLE)?
+                    .remove((table, id.value().as_u128()))?;
+                Ok(())
+            }
+        }
+    }
+
+    fn read_write_revision_table(&self) -> PersistenceResult<redb::Table<'_, (&'static str, u128), u64>> {
+        match self {
+            Db::Read(_) => Err(PersistenceError::read_only_transaction("modify the revision of a resource")),
+            Db::ReadWrite(transaction) => Ok(transaction.open_table(REVISION_TABLE)?),
+        }
+    }
+}
+
 pub(super) enum ReadTable<'transaction> {
     Read(redb::ReadOnlyTable<Key, Value>),
     ReadWrite(redb::Table<'transaction, Key, Value>),
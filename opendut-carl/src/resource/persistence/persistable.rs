@@ -1,15 +1,28 @@
 use crate::resource::api::Resource;
 use crate::resource::persistence::error::PersistenceResult;
 use crate::resource::persistence::TableDefinition;
+use opendut_types::audit::AuditEntry;
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::notification::NotificationSubscription;
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::certificate::ClientCertificateRecord;
+use opendut_types::peer::decommission::PeerDecommissionRecord;
+use opendut_types::peer::setup::PeerSetupRecord;
 use opendut_types::peer::state::PeerConnectionState;
 use opendut_types::peer::PeerDescriptor;
 use opendut_types::proto::ConversionError;
+use opendut_types::user::token::{PersonalAccessToken, PersonalAccessTokenOwner};
+use opendut_types::user::LocalUser;
 use prost::Message;
 use std::fmt::Debug;
 
 
+impl Persistable for AuditEntry {
+    type Proto = opendut_types::proto::audit::AuditEntry;
+    const TABLE: &'static str = "audit_log";
+    const STORAGE: StorageKind = StorageKind::Persistent;
+}
+
 impl Persistable for ClusterConfiguration {
     type Proto = opendut_types::proto::cluster::ClusterConfiguration;
     const TABLE: &'static str = "cluster_configuration";
@@ -46,6 +59,48 @@ impl Persistable for PeerDescriptor {
     const STORAGE: StorageKind = StorageKind::Persistent;
 }
 
+impl Persistable for PeerSetupRecord {
+    type Proto = opendut_types::proto::peer::PeerSetupRecord;
+    const TABLE: &'static str = "peer_setup";
+    const STORAGE: StorageKind = StorageKind::Persistent;
+}
+
+impl Persistable for ClientCertificateRecord {
+    type Proto = opendut_types::proto::peer::ClientCertificateRecord;
+    const TABLE: &'static str = "client_certificate";
+    const STORAGE: StorageKind = StorageKind::Persistent;
+}
+
+impl Persistable for PeerDecommissionRecord {
+    type Proto = opendut_types::proto::peer::PeerDecommissionRecord;
+    const TABLE: &'static str = "peer_decommission";
+    const STORAGE: StorageKind = StorageKind::Persistent;
+}
+
+impl Persistable for LocalUser {
+    type Proto = opendut_types::proto::user::LocalUser;
+    const TABLE: &'static str = "local_user";
+    const STORAGE: StorageKind = StorageKind::Persistent;
+}
+
+impl Persistable for PersonalAccessToken {
+    type Proto = opendut_types::proto::user::PersonalAccessToken;
+    const TABLE: &'static str = "personal_access_token";
+    const STORAGE: StorageKind = StorageKind::Persistent;
+}
+
+impl Persistable for PersonalAccessTokenOwner {
+    type Proto = opendut_types::proto::user::PersonalAccessTokenOwner;
+    const TABLE: &'static str = "personal_access_token_owner";
+    const STORAGE: StorageKind = StorageKind::Persistent;
+}
+
+impl Persistable for NotificationSubscription {
+    type Proto = opendut_types::proto::notification::NotificationSubscription;
+    const TABLE: &'static str = "notification_subscription";
+    const STORAGE: StorageKind = StorageKind::Persistent;
+}
+
 
 pub trait Persistable: Send + Sync + Sized + Debug + Resource {
     type Proto: Message + Default + From<Self> + TryInto<Self, Error=ConversionError>;
@@ -12,7 +12,7 @@ use std::ops::Not;
 use uuid::Uuid;
 
 pub fn insert(cluster_configuration: ClusterConfiguration, connection: &mut PgConnection) -> PersistenceResult<()> {
-    let ClusterConfiguration { id, name, leader, devices } = cluster_configuration;
+    let ClusterConfiguration { id, name, leader, devices, labels: _, device_selector: _, project: _ } = cluster_configuration;
 
     insert_persistable(PersistableClusterConfiguration {
         cluster_id: id.0,
@@ -110,6 +110,9 @@ pub fn list(filter_by_cluster_id: Filter<ClusterId>, connection: &mut PgConnecti
                 name,
                 leader: leader_id,
                 devices,
+                labels: Default::default(),
+                device_selector: None,
+                project: Default::default(),
             }
        ))
     })
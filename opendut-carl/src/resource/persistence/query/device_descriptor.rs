@@ -9,7 +9,7 @@ use opendut_types::util::net::NetworkInterfaceId;
 use uuid::Uuid;
 
 pub fn insert(device_descriptor: DeviceDescriptor, connection: &mut PgConnection) -> PersistenceResult<()> {
-    let DeviceDescriptor { id, name, description, interface, tags } = device_descriptor;
+    let DeviceDescriptor { id, name, description, interface, tags, labels: _ } = device_descriptor;
 
     let name = name.value().to_owned();
     let description = description.map(|description| description.value().to_owned());
@@ -92,6 +92,7 @@ fn device_descriptor_from_persistable(
         interface: network_interface_id.map(NetworkInterfaceId::from)
             .expect("We should always have a NetworkInterfaceId persisted for now."), //TODO DeviceDescriptor should use an Option<NetworkInterfaceId>
         tags,
+        labels: Default::default(),
     };
     Ok(result)
 }
@@ -13,7 +13,7 @@ use opendut_types::topology::Topology;
 use opendut_types::util::net::NetworkInterfaceName;
 
 pub fn insert(peer_descriptor: PeerDescriptor, connection: &mut PgConnection) -> PersistenceResult<()> {
-    let PeerDescriptor { id: peer_id, name, location, network, topology, executors } = peer_descriptor;
+    let PeerDescriptor { id: peer_id, name, location, network, topology, executors, labels: _, project: _ } = peer_descriptor;
     let PeerNetworkDescriptor { interfaces, bridge_name } = network;
 
     insert_persistable(PersistablePeerDescriptor {
@@ -153,6 +153,8 @@ pub fn list(filter_by_peer_id: Filter<PeerId>, connection: &mut PgConnection) ->
                     devices,
                 },
                 executors: ExecutorDescriptors { executors },
+                labels: Default::default(),
+                project: Default::default(),
             }
         ))
     })
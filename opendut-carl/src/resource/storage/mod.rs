@@ -7,6 +7,8 @@ use crate::resource::persistence::{Db, Memory};
 use crate::resource::subscription::Subscribable;
 use crate::resource::{persistence, ConnectError};
 use anyhow::anyhow;
+use opendut_types::audit::{AuditActor, AuditEntry};
+use opendut_types::resources::Revision;
 use prost::Message;
 use redb::backends::InMemoryBackend;
 use std::collections::HashMap;
@@ -57,6 +59,9 @@ impl ResourceStorage {
             .create_with_backend(InMemoryBackend::new())
             .map_err(ConnectError::DatabaseInMemoryCreate)?;
 
+        remove_revisions_of_audit_entries(&db)
+            .map_err(ConnectError::DatabaseCleanup)?;
+
         Ok(Self { db, memory })
     }
 
@@ -115,6 +120,18 @@ impl ResourceStorage {
     }
 }
 
+/// Entries of the audit log used to be stored with a revision, which nothing reads, so these are removed.
+fn remove_revisions_of_audit_entries(db: &redb::Database) -> PersistenceResult<()> {
+    let mut transaction = db.begin_write()?;
+    let removed = Db::ReadWrite(&mut transaction).remove_revisions_of_table(AuditEntry::TABLE)?;
+    transaction.commit()?;
+
+    if removed > 0 {
+        info!("Removed {removed} obsolete revisions of audit entries from the database.");
+    }
+    Ok(())
+}
+
 pub struct ResourceTransaction<'transaction> {
     db: Db<'transaction>,
     memory: Memory<'transaction>,
@@ -130,7 +147,10 @@ impl ResourcesStorageApi for ResourceTransaction<'_> {
         let value = R::Proto::from(resource).encode_to_vec();
 
         let mut table = db.read_write_table(R::TABLE_DEFINITION)?;
-        table.insert(key, value)?;
+        table.insert(&key, value)?;
+
+        let revision = db.next_revision()?;
+        db.write_revision(R::TABLE, key.id, revision)?;
 
         Ok(())
     }
@@ -143,10 +163,16 @@ impl ResourcesStorageApi for ResourceTransaction<'_> {
 
         let mut table = db.read_write_table(R::TABLE_DEFINITION)?;
 
-        let value = table.remove(key)?
+        let value = table.remove(&key)?
             .map(|value| R::try_from_bytes(value.value()))
             .transpose()?;
 
+        db.remove_revision(R::TABLE, key.id)?;
+
+        if value.is_some() {
+            db.next_revision()?; //removals get a revision, too, so that watchers can order them
+        }
+
         Ok(value)
     }
 
@@ -188,8 +214,80 @@ impl ResourcesStorageApi for ResourceTransaction<'_> {
             Ok(HashMap::default())
         }
     }
+
+    fn revision<R>(&self, id: R::Id) -> PersistenceResult<Option<Revision>>
+    where
+        R: Resource + Persistable + Clone
+    {
+        let db = self.get_db_for_resource::<R>();
+
+        let id = ResourceId::<R>::into_id(id);
+
+        match db.read_revision(R::TABLE, id)? {
+            Some(revision) => Ok(Some(Revision(revision))),
+            None => {
+                let exists = self.get::<R>(ResourceId::<R>::from_id(id))?.is_some();
+                Ok(exists.then_some(Revision::default())) //resources stored before revisions were introduced
+            }
+        }
+    }
 }
 impl ResourceTransaction<'_> {
+    /// Returns the revision of the most recent modification of any persistent resource.
+    pub fn latest_revision(&self) -> PersistenceResult<Revision> {
+        self.db.latest_revision().map(Revision)
+    }
+
+    /// Returns the revision of the most recent modification of any resource stored like `R`.
+    /// Volatile resources have revisions separate from persistent resources, which start over when CARL restarts.
+    pub fn latest_revision_of<R: Persistable>(&self) -> PersistenceResult<Revision> {
+        self.get_db_for_resource::<R>().latest_revision().map(Revision)
+    }
+
+    /// Like [`ResourcesStorageApi::insert`], but without a revision, for resources which are only ever appended and never watched, like the entries of the audit log.
+    pub fn append<R>(&mut self, id: R::Id, resource: R) -> PersistenceResult<()>
+    where R: Resource + Persistable {
+        let db = self.get_db_for_resource::<R>();
+
+        let key = persistence::Key::from(ResourceId::<R>::into_id(id));
+
+        let value = R::Proto::from(resource).encode_to_vec();
+
+        let mut table = db.read_write_table(R::TABLE_DEFINITION)?;
+        table.insert(&key, value)?;
+
+        Ok(())
+    }
+
+    /// Returns who created the resource, i.e. who first inserted it after it was last removed.
+    /// Returns `None`, if no creator was recorded, e.g. for resources stored before creators were recorded.
+    pub fn creator_of<R>(&self, id: R::Id) -> PersistenceResult<Option<AuditActor>>
+    where R: Resource + Persistable {
+        let db = self.get_db_for_resource::<R>();
+
+        db.read_creator(R::TABLE, ResourceId::<R>::into_id(id))?
+            .map(|bytes| {
+                let creator = opendut_types::proto::audit::AuditActor::decode(bytes.as_slice())?;
+                Ok(AuditActor::try_from(creator)?)
+            })
+            .transpose()
+    }
+
+    /// Records the creator of the resource, unless a creator is recorded already, i.e. when the resource is updated.
+    pub fn record_creator<R>(&mut self, id: R::Id, creator: &AuditActor) -> PersistenceResult<()>
+    where R: Resource + Persistable {
+        if self.creator_of::<R>(id.clone())?.is_some() {
+            return Ok(());
+        }
+        let creator = opendut_types::proto::audit::AuditActor::from(Clone::clone(creator)).encode_to_vec();
+        self.get_db_for_resource::<R>().write_creator(R::TABLE, ResourceId::<R>::into_id(id), &creator)
+    }
+
+    pub fn forget_creator<R>(&mut self, id: R::Id) -> PersistenceResult<()>
+    where R: Resource + Persistable {
+        self.get_db_for_resource::<R>().remove_creator(R::TABLE, ResourceId::<R>::into_id(id))
+    }
+
     fn get_db_for_resource<R: Persistable>(&self) -> &Db<'_> {
         match R::STORAGE {
             StorageKind::Persistent => &self.db,
@@ -315,4 +413,8 @@ pub trait ResourcesStorageApi {
 
     fn list<R>(&self) -> PersistenceResult<HashMap<R::Id, R>>
     where R: Resource + Persistable + Clone;
+
+    /// Returns the current revision of the resource, or `None` if it does not exist.
+    fn revision<R>(&self, id: R::Id) -> PersistenceResult<Option<Revision>>
+    where R: Resource + Persistable + Clone;
 }
@@ -72,5 +72,8 @@ pub fn cluster_configuration(leader_id: PeerId, devices: Vec<DeviceId>) -> anyho
         name: ClusterName::try_from("cluster-name")?,
         leader: leader_id,
         devices: HashSet::from_iter(devices),
+        labels: Default::default(),
+        device_selector: None,
+        project: Default::default(),
     })
 }
@@ -101,6 +101,7 @@ pub fn peer_descriptor() -> anyhow::Result<PeerDescriptor> {
                         DeviceTag::try_from("tag1")?,
                         DeviceTag::try_from("tag2")?,
                     ],
+                    labels: Default::default(),
                 },
                 DeviceDescriptor {
                     id: DeviceId::random(),
@@ -111,6 +112,7 @@ pub fn peer_descriptor() -> anyhow::Result<PeerDescriptor> {
                         DeviceTag::try_from("tag2")?,
                         DeviceTag::try_from("tag3")?,
                     ],
+                    labels: Default::default(),
                 },
             ],
         },
@@ -148,5 +150,7 @@ pub fn peer_descriptor() -> anyhow::Result<PeerDescriptor> {
                 },
             ]
         },
+        labels: Default::default(),
+        project: Default::default(),
     })
 }
@@ -1,16 +1,28 @@
 use crate::resource::api::Resource;
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::notification::NotificationSubscription;
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::certificate::ClientCertificateRecord;
+use opendut_types::peer::decommission::PeerDecommissionRecord;
+use opendut_types::peer::setup::PeerSetupRecord;
 use opendut_types::peer::state::PeerConnectionState;
 use opendut_types::peer::PeerDescriptor;
+use opendut_types::resources::Revision;
+use opendut_types::user::token::{PersonalAccessToken, PersonalAccessTokenOwner};
+use opendut_types::user::LocalUser;
 use tokio::sync::broadcast;
 
 
 pub struct Subscription<R: Resource> {
-    receiver: broadcast::Receiver<SubscriptionEvent<R>>,
+    receiver: broadcast::Receiver<RevisionedSubscriptionEvent<R>>,
 }
 impl<R: Resource> Subscription<R> {
     pub async fn receive(&mut self) -> Result<SubscriptionEvent<R>, ReceiveError> {
+        self.receive_with_revision().await
+            .map(|revisioned| revisioned.event)
+    }
+
+    pub async fn receive_with_revision(&mut self) -> Result<RevisionedSubscriptionEvent<R>, ReceiveError> {
         self.receiver.recv().await
             .map_err(ReceiveError::Broadcast)
     }
@@ -30,6 +42,13 @@ impl<R: Resource> SubscriptionEvent<R> {
     }
 }
 
+/// A [`SubscriptionEvent`] together with the revision of the modification, which triggered it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevisionedSubscriptionEvent<R: Resource> {
+    pub event: SubscriptionEvent<R>,
+    pub revision: Revision,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ReceiveError {
     #[error("Error while receiving Broadcast")]
@@ -37,13 +56,13 @@ pub enum ReceiveError {
 }
 
 pub trait Subscribable: Resource {
-    fn resource_subscribers_field(resource_subscribers: &mut ResourceSubscriptionChannels) -> &broadcast::Sender<SubscriptionEvent<Self>>
+    fn resource_subscribers_field(resource_subscribers: &mut ResourceSubscriptionChannels) -> &broadcast::Sender<RevisionedSubscriptionEvent<Self>>
     where Self: Sized;
 }
 macro_rules! impl_subscribable {
     ($resource:ty, $field:ident) => {
         impl Subscribable for $resource {
-            fn resource_subscribers_field(resource_subscribers: &mut ResourceSubscriptionChannels) -> &broadcast::Sender<SubscriptionEvent<Self>>
+            fn resource_subscribers_field(resource_subscribers: &mut ResourceSubscriptionChannels) -> &broadcast::Sender<RevisionedSubscriptionEvent<Self>>
             where Self: Sized {
                 let (sender, _) = &resource_subscribers.$field;
                 sender
@@ -57,9 +76,16 @@ impl_subscribable!(OldPeerConfiguration, old_peer_configuration);
 impl_subscribable!(PeerConfiguration, peer_configuration);
 impl_subscribable!(PeerDescriptor, peer_descriptor);
 impl_subscribable!(PeerConnectionState, peer_connection_state);
+impl_subscribable!(PeerSetupRecord, peer_setup);
+impl_subscribable!(ClientCertificateRecord, client_certificate);
+impl_subscribable!(PeerDecommissionRecord, peer_decommission);
+impl_subscribable!(LocalUser, local_user);
+impl_subscribable!(PersonalAccessToken, personal_access_token);
+impl_subscribable!(PersonalAccessTokenOwner, personal_access_token_owner);
+impl_subscribable!(NotificationSubscription, notification_subscription);
 
 
-pub type ResourceSubscriptionChannel<R> = (broadcast::Sender<SubscriptionEvent<R>>, broadcast::Receiver<SubscriptionEvent<R>>); //store both the sender and initial receiver, to prevent channel from closing
+pub type ResourceSubscriptionChannel<R> = (broadcast::Sender<RevisionedSubscriptionEvent<R>>, broadcast::Receiver<RevisionedSubscriptionEvent<R>>); //store both the sender and initial receiver, to prevent channel from closing
 
 pub struct ResourceSubscriptionChannels {
     pub cluster_configuration: ResourceSubscriptionChannel<ClusterConfiguration>,
@@ -68,6 +94,13 @@ pub struct ResourceSubscriptionChannels {
     pub peer_configuration: ResourceSubscriptionChannel<PeerConfiguration>,
     pub peer_descriptor: ResourceSubscriptionChannel<PeerDescriptor>,
     pub peer_connection_state: ResourceSubscriptionChannel<PeerConnectionState>,
+    pub peer_setup: ResourceSubscriptionChannel<PeerSetupRecord>,
+    pub client_certificate: ResourceSubscriptionChannel<ClientCertificateRecord>,
+    pub peer_decommission: ResourceSubscriptionChannel<PeerDecommissionRecord>,
+    pub local_user: ResourceSubscriptionChannel<LocalUser>,
+    pub personal_access_token: ResourceSubscriptionChannel<PersonalAccessToken>,
+    pub personal_access_token_owner: ResourceSubscriptionChannel<PersonalAccessTokenOwner>,
+    pub notification_subscription: ResourceSubscriptionChannel<NotificationSubscription>,
 }
 impl ResourceSubscriptionChannels {
     pub fn subscribe<R: Resource + Subscribable>(&mut self) -> Subscription<R> {
@@ -76,14 +109,14 @@ impl ResourceSubscriptionChannels {
         Subscription { receiver }
     }
 
-    pub fn notify<R: Resource + Subscribable + Sized>(&mut self, event: SubscriptionEvent<R>) -> Result<(), broadcast::error::SendError<SubscriptionEvent<R>>> {
+    pub fn notify<R: Resource + Subscribable + Sized>(&mut self, event: RevisionedSubscriptionEvent<R>) -> Result<(), broadcast::error::SendError<RevisionedSubscriptionEvent<R>>> {
         R::resource_subscribers_field(self)
             .send(event)?;
         Ok(())
     }
 
     pub fn is_empty(&self) -> bool {
-        let ResourceSubscriptionChannels { cluster_configuration, cluster_deployment, old_peer_configuration, peer_configuration, peer_descriptor, peer_connection_state } = self;
+        let ResourceSubscriptionChannels { cluster_configuration, cluster_deployment, old_peer_configuration, peer_configuration, peer_descriptor, peer_connection_state, peer_setup, client_certificate, peer_decommission, local_user, personal_access_token, personal_access_token_owner, notification_subscription } = self;
 
         cluster_configuration.0.is_empty()
         && cluster_deployment.0.is_empty()
@@ -91,6 +124,13 @@ impl ResourceSubscriptionChannels {
         && peer_configuration.0.is_empty()
         && peer_descriptor.0.is_empty()
         && peer_connection_state.0.is_empty()
+        && peer_setup.0.is_empty()
+        && client_certificate.0.is_empty()
+        && peer_decommission.0.is_empty()
+        && local_user.0.is_empty()
+        && personal_access_token.0.is_empty()
+        && personal_access_token_owner.0.is_empty()
+        && notification_subscription.0.is_empty()
     }
 }
 impl Default for ResourceSubscriptionChannels {
@@ -103,6 +143,13 @@ impl Default for ResourceSubscriptionChannels {
         let peer_configuration = broadcast::channel(capacity);
         let peer_descriptor = broadcast::channel(capacity);
         let peer_connection_state = broadcast::channel(capacity);
+        let peer_setup = broadcast::channel(capacity);
+        let client_certificate = broadcast::channel(capacity);
+        let peer_decommission = broadcast::channel(capacity);
+        let local_user = broadcast::channel(capacity);
+        let personal_access_token = broadcast::channel(capacity);
+        let personal_access_token_owner = broadcast::channel(capacity);
+        let notification_subscription = broadcast::channel(capacity);
 
         Self {
             cluster_configuration,
@@ -111,6 +158,13 @@ impl Default for ResourceSubscriptionChannels {
             peer_configuration,
             peer_descriptor,
             peer_connection_state,
+            peer_setup,
+            client_certificate,
+            peer_decommission,
+            local_user,
+            personal_access_token,
+            personal_access_token_owner,
+            notification_subscription,
         }
     }
 }
@@ -180,6 +234,37 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_provide_increasing_revisions_of_modifications() -> anyhow::Result<()> {
+        let fixture = SubscriptionFixture::new();
+        let mut subscription = fixture.resource_manager.subscribe::<PeerConnectionState>().await;
+
+        fixture.resource_manager.insert(fixture.id, PeerConnectionState::Offline).await?;
+        let inserted = timeout(fixture.timeout_duration, subscription.receive_with_revision()).await??;
+
+        fixture.resource_manager.remove::<PeerConnectionState>(fixture.id).await?;
+        let removed = timeout(fixture.timeout_duration, subscription.receive_with_revision()).await??;
+
+        assert!(removed.revision > inserted.revision, "Expected the removal to have a newer revision than the insertion.");
+        let latest_revision = fixture.resource_manager.resources(async |resources| resources.latest_revision_of::<PeerConnectionState>()).await??;
+        assert_eq!(removed.revision, latest_revision);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_count_revisions_of_volatile_resources_separately() -> anyhow::Result<()> {
+        let fixture = SubscriptionFixture::new();
+        let persistent_revision = fixture.resource_manager.resources(async |resources| resources.latest_revision()).await??;
+
+        fixture.resource_manager.insert(fixture.id, PeerConnectionState::Offline).await?;
+
+        let latest_revision = fixture.resource_manager.resources(async |resources| resources.latest_revision()).await??;
+        assert_eq!(latest_revision, persistent_revision, "Expected modifications of volatile resources not to advance the revision of the persistent database.");
+        let volatile_revision = fixture.resource_manager.resources(async |resources| resources.latest_revision_of::<PeerConnectionState>()).await??;
+        assert!(volatile_revision > Revision::default());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn should_not_notify_if_nothing_was_removed() -> anyhow::Result<()> {
         let fixture = SubscriptionFixture::new();
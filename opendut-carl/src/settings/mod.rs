@@ -5,6 +5,7 @@ pub mod vpn;
 pub fn load_with_overrides(overrides: config::Config) -> Result<LoadedConfig, LoadError> {
     let carl_config_hide_secrets_override = config::Config::builder()
         .set_override("vpn.netbird.auth.secret", "redacted")?
+        .set_override("vpn.netbird.auth.client.secret", "redacted")?
         .set_override("network.oidc.client.secret", "redacted")?
         .set_override("persistence.database.password", "redacted")?
         .build()?;
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -7,8 +7,15 @@ use config::Config;
 use tracing::debug;
 use url::Url;
 
+use opendut_auth::confidential::client::{ConfidentialClient, ConfidentialClientRef};
+use opendut_auth::confidential::config::ConfidentialClientConfigData;
+use opendut_auth::confidential::pem::read_pem_from_file_path;
+use opendut_auth::confidential::reqwest_client::OidcReqwestClient;
 use opendut_vpn::VpnManagementClient;
-use opendut_vpn_netbird::{NetbirdManagementClient, NetbirdManagementClientConfiguration, NetbirdToken};
+use opendut_vpn_netbird::{NetbirdManagementClient, NetbirdManagementClientConfiguration, NetbirdToken, TokenProviderRef};
+use opendut_vpn_wireguard::{OverlayNetwork, WireGuardManagementClient, WireGuardManagementClientConfiguration};
+
+const NETBIRD_AUTH_CLIENT_CONFIG_PREFIX: &str = "vpn.netbird.auth.client";
 
 #[derive(Clone)]
 pub enum Vpn {
@@ -32,16 +39,17 @@ pub async fn create(settings: &Config) -> anyhow::Result<Vpn> {
                 let ca = settings.get::<Option<PathBuf>>("vpn.netbird.ca")?
                     .ok_or_else(|| anyhow!("No configuration found for: vpn.netbird.ca"))?;
 
-                let auth_secret = settings.get::<Option<String>>("vpn.netbird.auth.secret")?
-                    .ok_or_else(|| anyhow!("No configuration found for: vpn.netbird.auth.secret"))?;
-
                 let vpn_netbird_auth_type = "vpn.netbird.auth.type";
                 let auth_type = settings.get::<Option<String>>(vpn_netbird_auth_type)?;
 
-                let auth_token = match auth_type {
+                let authentication: TokenProviderRef = match auth_type {
                     Some(auth_type) => match auth_type.as_ref() {
-                        "personal-access-token" => NetbirdToken::new_personal_access(auth_secret),
-                        "bearer-token" => unimplemented!("Using a bearer token is not yet supported."),
+                        "personal-access-token" => {
+                            let auth_secret = settings.get::<Option<String>>("vpn.netbird.auth.secret")?
+                                .ok_or_else(|| anyhow!("No configuration found for: vpn.netbird.auth.secret"))?;
+                            Arc::new(NetbirdToken::new_personal_access(auth_secret))
+                        }
+                        "bearer-token" => create_netbird_confidential_client(settings, &ca).await?,
                         _ => return Err(anyhow!("Invalid configuration parameter for '{vpn_netbird_auth_type}', allowed values are 'bearer-token' and 'personal-access-token'.")),
                     }
                     None => return unknown_enum_variant(settings, vpn_netbird_auth_type)
@@ -60,7 +68,7 @@ pub async fn create(settings: &Config) -> anyhow::Result<Vpn> {
                 let vpn_client = NetbirdManagementClient::create_client_and_delete_default_policy(
                     NetbirdManagementClientConfiguration {
                         management_url: base_url,
-                        authentication_token: Some(auth_token),
+                        authentication: Some(authentication),
                         ca: Some(ca),
                         timeout: Duration::from_millis(timeout_ms),
                         retries,
@@ -69,14 +77,44 @@ pub async fn create(settings: &Config) -> anyhow::Result<Vpn> {
                 ).await?;
                 Ok(Vpn::Enabled { vpn_client: Arc::new(vpn_client) })
             }
+            "wireguard" => {
+                let network = settings.get_string("vpn.wireguard.network")?
+                    .parse::<OverlayNetwork>()?;
+
+                let listen_port = settings.get::<u16>("vpn.wireguard.listen.port")?;
+
+                let state_file = settings.get::<Option<PathBuf>>("vpn.wireguard.state.file")?
+                    .ok_or_else(|| anyhow!("No configuration found for: vpn.wireguard.state.file"))?;
+
+                let vpn_client = WireGuardManagementClient::create(
+                    WireGuardManagementClientConfiguration {
+                        network,
+                        listen_port,
+                        state_file,
+                    }
+                )?;
+                Ok(Vpn::Enabled { vpn_client: Arc::new(vpn_client) })
+            }
             "" => unknown_enum_variant(settings, vpn_kind_key),
-            other => Err(anyhow!("Invalid configuration parameter '{other}' for key '{vpn_kind_key}', allowed value is 'netbird'.")),
+            other => Err(anyhow!("Invalid configuration parameter '{other}' for key '{vpn_kind_key}', allowed values are 'netbird' and 'wireguard'.")),
         }
     } else {
         Ok(Vpn::Disabled)
     }
 }
 
+/// Client for obtaining bearer tokens from the identity provider of NetBird via the client-credentials flow.
+/// The identity provider is expected to be reachable with the same CA as NetBird.
+async fn create_netbird_confidential_client(settings: &Config, ca: &Path) -> anyhow::Result<ConfidentialClientRef> {
+    let client_config = ConfidentialClientConfigData::from_settings_with_prefix(settings, NETBIRD_AUTH_CLIENT_CONFIG_PREFIX)?;
+
+    let ca = read_pem_from_file_path(&ca.to_path_buf())?;
+    let reqwest_client = OidcReqwestClient::from_pem(ca)?;
+
+    let client = ConfidentialClient::from_client_config(client_config, reqwest_client).await?;
+    Ok(client)
+}
+
 fn unknown_enum_variant(settings: &Config, key: &str) -> anyhow::Result<Vpn> {
     let value = settings.get_string(key)?;
     if value.trim().is_empty() {
@@ -1,23 +1,36 @@
+use std::ops::Not;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use anyhow::Context;
 use config::Config;
 use pem::Pem;
 use opendut_auth::registration::client::RegistrationClientRef;
 use opendut_auth::registration::resources::ResourceHomeUrl;
-use crate::manager::grpc::{ClusterManagerFacade, MetadataProviderFacade, ObserverMessagingBrokerFacade, PeerManagerFacade, PeerMessagingBrokerFacade};
+use opendut_types::peer::update::UpdateVerificationKey;
+use crate::auth::authorization::AuthorizationPolicy;
+use crate::auth::client_certificate::ClientCertificateAuthorityRef;
+use crate::manager::grpc::{AdministrationFacade, AuditLogFacade, ClusterManagerFacade, MetadataProviderFacade, NotificationManagerFacade, ObserverMessagingBrokerFacade, PeerManagerFacade, PeerMessagingBrokerFacade, TokenManagerFacade};
 use crate::resource::manager::ResourceManagerRef;
 use crate::startup;
 use crate::manager::cluster_manager::{ClusterManager, ClusterManagerOptions};
+use crate::manager::notification_manager::{NotificationDispatcher, NotificationOptions};
 use crate::manager::observer_messaging_broker::ObserverMessagingBroker;
+use crate::manager::peer_manager::{spawn_peer_setup_expiry, PeerSetupExpiryOptions};
 use crate::manager::peer_messaging_broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+use crate::manager::vpn_reconciler::{VpnReconciler, VpnReconcilerOptions};
 use crate::settings::vpn;
 
 pub struct GrpcFacades {
+    pub administration_facade: AdministrationFacade,
+    pub audit_log_facade: AuditLogFacade,
     pub cluster_manager_facade: ClusterManagerFacade,
     pub metadata_provider_facade: MetadataProviderFacade,
+    pub notification_manager_facade: NotificationManagerFacade,
     pub peer_manager_facade: PeerManagerFacade,
     pub peer_messaging_broker_facade: PeerMessagingBrokerFacade,
     pub observer_messaging_broker_facade: ObserverMessagingBrokerFacade,
+    pub token_manager_facade: TokenManagerFacade,
 }
 
 impl GrpcFacades {
@@ -26,6 +39,8 @@ impl GrpcFacades {
         carl_url: &ResourceHomeUrl,
         ca_certificate: Pem,
         oidc_registration_client: Option<RegistrationClientRef>,
+        client_certificate_authority: Option<ClientCertificateAuthorityRef>,
+        authorization_policy: AuthorizationPolicy,
         settings: &Config,
     ) -> anyhow::Result<Self> {
 
@@ -46,28 +61,74 @@ impl GrpcFacades {
         ).await;
 
 
+        let vpn_reconciler = VpnReconciler::create(
+            Arc::clone(&resource_manager),
+            Some(Arc::clone(&cluster_manager)),
+            Clone::clone(&vpn),
+            VpnReconcilerOptions::load(settings)?,
+        );
+
+        let administration_facade = AdministrationFacade::new(Arc::clone(&resource_manager), vpn_reconciler);
+
+        let audit_log_facade = AuditLogFacade::new(Arc::clone(&resource_manager));
+
         let cluster_manager_facade = ClusterManagerFacade::new(Arc::clone(&cluster_manager), Arc::clone(&resource_manager));
 
         let metadata_provider_facade = MetadataProviderFacade::new();
 
+        let notification_options = NotificationOptions::load(settings)?;
+        NotificationDispatcher::create(
+            Arc::clone(&resource_manager),
+            &peer_messaging_broker,
+            Clone::clone(&notification_options),
+        ).await?;
+        let notification_manager_facade = NotificationManagerFacade::new(Arc::clone(&resource_manager), notification_options.target_addresses);
+
+        let update_verification_key = {
+            let key = settings.get_string("peer.update.verification.key")?;
+            key.is_empty().not().then(|| UpdateVerificationKey::from_str(&key))
+                .transpose()
+                .context("Error while parsing the key for verifying updates of EDGAR.")?
+        };
+
+        let setup_expiration = Duration::from_millis(settings.get::<u64>("peer.setup.expiration.ms")?);
+
+        spawn_peer_setup_expiry(
+            Arc::clone(&resource_manager),
+            Clone::clone(&vpn),
+            oidc_registration_client.clone(),
+            PeerSetupExpiryOptions::load(settings)?,
+        );
+
         let peer_manager_facade = PeerManagerFacade::new(
             Arc::clone(&resource_manager),
-            vpn,
+            Arc::clone(&peer_messaging_broker),
+            Clone::clone(&vpn),
             Clone::clone(&carl_url.value()),
             ca_certificate,
-            oidc_registration_client,
+            update_verification_key,
+            oidc_registration_client.clone(),
+            client_certificate_authority,
+            setup_expiration,
         );
-        let peer_messaging_broker_facade = PeerMessagingBrokerFacade::new(Arc::clone(&peer_messaging_broker));
+        let peer_messaging_broker_facade = PeerMessagingBrokerFacade::new(Arc::clone(&peer_messaging_broker), vpn, Arc::clone(&resource_manager), oidc_registration_client);
         
         let observer_messaging_broker = ObserverMessagingBroker::new(Arc::clone(&resource_manager), Arc::clone(&cluster_manager));
         let observer_messaging_broker_facade = ObserverMessagingBrokerFacade::new(Arc::clone(&resource_manager), Arc::clone(&observer_messaging_broker));
 
+        let token_maximum_validity = Duration::from_millis(settings.get::<u64>("network.personal.access.tokens.validity.max.ms")?);
+        let token_manager_facade = TokenManagerFacade::new(Arc::clone(&resource_manager), authorization_policy, token_maximum_validity);
+
         Ok(GrpcFacades {
+            administration_facade,
+            audit_log_facade,
             cluster_manager_facade,
             metadata_provider_facade,
+            notification_manager_facade,
             peer_manager_facade,
             peer_messaging_broker_facade,
             observer_messaging_broker_facade,
+            token_manager_facade,
         })
     }
 }
@@ -1,10 +1,11 @@
-use axum::routing::get;
+use axum::routing::{get, post};
 use config::Config;
 use tower_http::services::{ServeDir, ServeFile};
 use tracing::info;
 use opendut_auth::registration::resources::ResourceHomeUrl;
-use opendut_types::lea::LeaConfig;
+use opendut_types::lea::{LeaConfig, LeaLocalUsersConfig};
 use opendut_util::project;
+use crate::auth::local_users::{LocalUserStoreRef, LOCAL_ISSUER_PATH, TOKEN_ENDPOINT_PATH};
 use crate::http::router;
 use crate::http::state::{CarlInstallDirectory, HttpState, LoadableLeaIdentityProviderConfig};
 
@@ -40,7 +41,10 @@ pub fn create_http_service(settings: &Config) -> anyhow::Result<axum::Router<Htt
         )
         .route("/api/cleo/:architecture/download", get(router::cleo::download_cleo))
         .route("/api/edgar/:architecture/download", get(router::edgar::download_edgar))
+        .route("/api/edgar/:architecture/manifest", get(router::edgar::download_edgar_manifest))
+        .route("/api/edgar/:architecture/signature", get(router::edgar::download_edgar_signature))
         .route("/api/lea/config", get(router::lea_config))
+        .route(&format!("/{LOCAL_ISSUER_PATH}{TOKEN_ENDPOINT_PATH}"), post(router::token::issue_token))
         .nest_service(
             "/",
             ServeDir::new(&lea_dir)
@@ -53,6 +57,7 @@ pub fn create_http_service(settings: &Config) -> anyhow::Result<axum::Router<Htt
 pub fn create_http_state(
     carl_url: &ResourceHomeUrl,
     carl_installation_directory: CarlInstallDirectory,
+    local_user_store: Option<LocalUserStoreRef>,
     settings: &Config,
 ) -> anyhow::Result<HttpState> {
 
@@ -67,12 +72,19 @@ pub fn create_http_state(
         None
     };
 
+    let local_users = match &local_user_store {
+        Some(local_user_store) => Some(LeaLocalUsersConfig { token_url: local_user_store.token_url()? }),
+        None => None,
+    };
+
     let http_state = HttpState {
         lea_config: LeaConfig {
             carl_url: carl_url.value(),
             idp_config: lea_idp_config.map(|LoadableLeaIdentityProviderConfig(config)| config),
+            local_users,
         },
-        carl_installation_directory
+        carl_installation_directory,
+        local_user_store,
     };
 
     Ok(http_state)
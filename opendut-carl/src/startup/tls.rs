@@ -1,17 +1,36 @@
+use std::io;
+use std::sync::Arc;
+
 use anyhow::{anyhow, Context};
-use axum_server::tls_rustls::RustlsConfig;
+use axum::Extension;
+use axum::middleware::AddExtension;
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
 use config::Config;
+use futures::future::BoxFuture;
+use opendut_types::peer::certificate::ClientCertificateId;
+use opendut_types::peer::PeerId;
 use opendut_util::project;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::pki_types::pem::PemObject;
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
 use std::fs;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower::Layer;
 use tracing::debug;
 
+use crate::auth::client_certificate;
+use crate::auth::client_certificate::ClientCertificateAuthorityRef;
+
 pub enum TlsConfig {
     Enabled(RustlsConfig),
     Disabled
 }
 
 impl TlsConfig {
-    pub async fn load(settings: &Config) -> anyhow::Result<Self> {
+    pub async fn load(settings: &Config, client_certificate_authority: Option<&ClientCertificateAuthorityRef>) -> anyhow::Result<Self> {
         let tls_enabled: bool = settings.get_bool("network.tls.enabled")
             .map_err(|cause| anyhow!("Expected configuration flag 'network.tls.enabled' to be parseable as boolean! {}", cause))?;
 
@@ -32,13 +51,101 @@ impl TlsConfig {
                     .context(format!("Error while reading TLS key at {}", key_path.display()))?
             };
 
-            let tls_config = RustlsConfig::from_pem(cert, key).await?;
+            let tls_config = match client_certificate_authority {
+                None => RustlsConfig::from_pem(cert, key).await?,
+                Some(client_certificate_authority) => {
+                    debug!("Requesting client certificates, so that EDGAR can authenticate via mutual TLS.");
+                    let server_config = server_config_with_client_certificates(&cert, &key, client_certificate_authority)?;
+                    RustlsConfig::from_config(Arc::new(server_config))
+                }
+            };
 
             TlsConfig::Enabled(tls_config)
         } else {
+            if client_certificate_authority.is_some() {
+                return Err(anyhow!("Client certificates are enabled via 'network.tls.client.enabled', which requires TLS to be enabled via 'network.tls.enabled'."));
+            }
             TlsConfig::Disabled
         };
 
         Ok(tls_config)
     }
 }
+
+fn server_config_with_client_certificates(cert: &[u8], key: &[u8], client_certificate_authority: &ClientCertificateAuthorityRef) -> anyhow::Result<ServerConfig> {
+    let cert_chain = CertificateDer::pem_slice_iter(cert)
+        .collect::<Result<Vec<_>, _>>()
+        .context("Error while parsing TLS certificate.")?;
+    let key = PrivateKeyDer::from_pem_slice(key)
+        .context("Error while parsing TLS key.")?;
+
+    let mut client_roots = RootCertStore::empty();
+    client_roots.add(client_certificate_authority.ca_certificate())
+        .context("Error while adding CA certificate for client certificates to root store.")?;
+
+    let client_verifier = WebPkiClientVerifier::builder(Arc::new(client_roots))
+        .allow_unauthenticated() //users authenticate via OIDC instead
+        .build()
+        .context("Error while creating verifier for client certificates.")?;
+
+    let mut server_config = ServerConfig::builder()
+        .with_client_cert_verifier(client_verifier)
+        .with_single_cert(cert_chain, key)
+        .context("Error while creating TLS configuration.")?;
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(server_config)
+}
+
+/// Attached to each request received via TLS. Holds the client certificate of the connection,
+/// or `None`, if the client did not present a certificate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CertifiedPeer(pub Option<PeerCertificate>);
+
+/// Identifies a client certificate, which was verified during the TLS handshake.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PeerCertificate {
+    /// The peer, for which the certificate was issued.
+    pub peer_id: PeerId,
+    pub certificate_id: ClientCertificateId,
+}
+
+/// Accepts TLS connections like the [`RustlsAcceptor`] and additionally attaches the [`CertifiedPeer`] to each request of the connection.
+/// Client certificates are verified during the TLS handshake, so the peer ID can be taken from the subject of the certificate.
+/// Whether the certificate was revoked in the meantime, is checked by the authorization middleware.
+#[derive(Clone)]
+pub struct ClientCertificateAcceptor {
+    inner: RustlsAcceptor,
+}
+
+impl ClientCertificateAcceptor {
+    pub fn new(tls_config: RustlsConfig) -> Self {
+        Self { inner: RustlsAcceptor::new(tls_config) }
+    }
+}
+
+impl<I, S> Accept<I, S> for ClientCertificateAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = TlsStream<I>;
+    type Service = AddExtension<S, CertifiedPeer>;
+    type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let acceptor = Clone::clone(&self.inner);
+
+        Box::pin(async move {
+            let (stream, service) = acceptor.accept(stream, service).await?;
+
+            let (_, connection) = stream.get_ref();
+            let peer_certificate = connection.peer_certificates()
+                .and_then(|certificates| certificates.first())
+                .and_then(|certificate| client_certificate::peer_of_certificate(certificate.as_ref()))
+                .map(|(peer_id, certificate_id)| PeerCertificate { peer_id, certificate_id });
+
+            Ok((stream, Extension(CertifiedPeer(peer_certificate)).layer(service)))
+        })
+    }
+}
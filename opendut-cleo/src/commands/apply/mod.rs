@@ -0,0 +1,434 @@
+use crate::commands::apply::plan::{Change, Plan};
+use crate::commands::cluster_configuration::apply::convert_document_to_cluster_configuration;
+use crate::commands::peer::apply::convert_document_to_peer_descriptor;
+use crate::CreateOutputFormat;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::ClusterConfiguration;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::specs::parse::json::JsonSpecificationDocument;
+use opendut_types::specs::parse::yaml::YamlSpecificationFile;
+use opendut_types::specs::{Specification, SpecificationDocument};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+
+pub mod plan;
+
+#[derive(clap::Parser)]
+///Create, update or delete openDuT resources, so that they match the specification files
+pub struct ApplyCli {
+    #[command(flatten)]
+    sources: SourceArgs,
+    ///Delete peers and cluster configurations, which are not contained in the specification files
+    #[arg(long)]
+    prune: bool,
+    ///Text, JSON or prettified JSON as output format
+    #[arg(global=true, value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
+    output: CreateOutputFormat,
+}
+
+/// Specification files or directories, or inline JSON documents.
+#[derive(clap::Args)]
+#[group(required=true, multiple=true)]
+pub struct SourceArgs {
+    ///YAML file, directory containing YAML files, or inline JSON document
+    #[arg(value_parser=parse_source)]
+    from: Vec<Source>,
+    ///YAML file or directory containing YAML files. Can be specified multiple times.
+    #[arg(short='f', long="filename", value_parser=parse_source)]
+    filenames: Vec<Source>,
+}
+
+impl SourceArgs {
+    pub fn read_documents(&self) -> crate::Result<Vec<SpecificationDocument>> {
+        let mut documents = Vec::new();
+        for source in self.from.iter().chain(self.filenames.iter()) {
+            documents.extend(read_documents_from_source(source)?);
+        }
+        Ok(documents)
+    }
+}
+
+impl ApplyCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let documents = self.sources.read_documents()?;
+        let plan = compute_plan(documents, self.prune, carl).await?;
+
+        match self.output {
+            CreateOutputFormat::Text => print!("{}", plan.render_text()),
+            CreateOutputFormat::Json => println!("{}", serde_json::to_string(&plan).unwrap()),
+            CreateOutputFormat::PrettyJson => println!("{}", serde_json::to_string_pretty(&plan).unwrap()),
+        }
+
+        apply_plan(&plan, carl).await?;
+
+        if let (CreateOutputFormat::Text, true) = (&self.output, plan.has_changes()) {
+            println!("Applied all changes successfully.");
+        }
+        Ok(())
+    }
+}
+
+/// Determines the changes needed to make the resources in CARL match the given specification documents.
+pub async fn compute_plan(documents: Vec<SpecificationDocument>, prune: bool, carl: &mut CarlClient) -> crate::Result<Plan> {
+    let current_peers = carl.peers.list_peer_descriptors_with_revisions().await
+        .map_err(|error| format!("Could not list peers.\n  {error}"))?;
+    let current_cluster_configurations = carl.cluster.list_cluster_configurations_with_revisions().await
+        .map_err(|error| format!("Could not list cluster configurations.\n  {error}"))?;
+
+    let known = KnownResources {
+        peers: current_peers.iter().map(|(peer, _)| peer.clone()).collect(),
+        cluster_configurations: current_cluster_configurations.iter().map(|(configuration, _)| configuration.clone()).collect(),
+    };
+    let models = convert_documents_to_models(documents, &known)?;
+
+    Plan::compute(models, current_peers, current_cluster_configurations, prune)
+}
+
+/// Peers are created and updated before the cluster configurations referencing their devices,
+/// and deleted after the cluster configurations, which might still reference them.
+async fn apply_plan(plan: &Plan, carl: &mut CarlClient) -> crate::Result<()> {
+    for change in &plan.peers {
+        match change {
+            Change::Create { desired } => {
+                carl.peers.store_peer_descriptor(desired.clone()).await
+                    .map_err(|error| format!("Failed to create peer '{}' <{}>.\n  {error}", desired.name, desired.id))?;
+            }
+            Change::Update { desired, revision, .. } => {
+                carl.peers.store_peer_descriptor_if_unmodified(desired.clone(), *revision).await
+                    .map_err(|error| format!("Failed to update peer '{}' <{}>.\n  {error}", desired.name, desired.id))?;
+            }
+            Change::Unchanged { .. } | Change::Delete { .. } => continue,
+        }
+    }
+
+    for change in &plan.cluster_configurations {
+        match change {
+            Change::Create { desired } => {
+                carl.cluster.store_cluster_configuration(desired.clone()).await
+                    .map_err(|error| format!("Failed to create cluster configuration '{}' <{}>.\n  {error}", desired.name, desired.id))?;
+            }
+            Change::Update { desired, revision, .. } => {
+                carl.cluster.store_cluster_configuration_if_unmodified(desired.clone(), *revision).await
+                    .map_err(|error| format!("Failed to update cluster configuration '{}' <{}>.\n  {error}", desired.name, desired.id))?;
+            }
+            Change::Delete { current, revision } => {
+                carl.cluster.delete_cluster_configuration_if_unmodified(current.id, *revision).await
+                    .map_err(|error| format!("Failed to delete cluster configuration '{}' <{}>.\n  {error}", current.name, current.id))?;
+            }
+            Change::Unchanged { .. } => continue,
+        }
+    }
+
+    for change in &plan.peers {
+        if let Change::Delete { current, revision } = change {
+            carl.peers.delete_peer_descriptor_if_unmodified(current.id, *revision).await
+                .map_err(|error| format!("Failed to delete peer '{}' <{}>.\n  {error}", current.name, current.id))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_documents_from_source(source: &Source) -> crate::Result<Vec<SpecificationDocument>> {
+    match source {
+        Source::File(path) => {
+            let mut documents = Vec::new();
+            for file in collect_specification_files(path)? {
+                let content = fs::read_to_string(&file)
+                    .map_err(|cause| format!("Failed to read specification file '{}': {cause}", file.display()))?;
+
+                let file_documents = parse_yaml_documents(&content)
+                    .map_err(|cause| format!("Failed to load specification file '{}':\n  {cause}", file.display()))?;
+                documents.extend(file_documents);
+            }
+            Ok(documents)
+        }
+        Source::Inline(InlineSource::Json(json)) => {
+            Ok(vec![parse_json_document(json)?])
+        }
+    }
+}
+
+/// Parses all specification documents contained in a string of YAML text.
+pub fn parse_yaml_documents(content: &str) -> crate::Result<Vec<SpecificationDocument>> {
+    YamlSpecificationFile::try_from_yaml_str(content)
+        .map_err(|cause| format!("Failed to parse specification: {cause}"))?
+        .documents
+        .into_iter()
+        .map(|yaml_specification| {
+            SpecificationDocument::try_from(yaml_specification)
+                .map_err(|error| error.to_string())
+        }).collect()
+}
+
+/// Parses a single specification document from a string of JSON text.
+pub fn parse_json_document(json: &str) -> crate::Result<SpecificationDocument> {
+    let json_document = JsonSpecificationDocument::try_from_json_str(json)
+        .map_err(|cause| format!("Error while parsing JSON:\n  {cause}"))?;
+
+    SpecificationDocument::try_from(json_document)
+        .map_err(|cause| format!("Error while converting JSON document to specification model:\n  {cause}"))
+}
+
+/// Resources, which already exist in CARL.
+/// They are used to resolve references by name and to keep the IDs of resources, which are specified without ID.
+#[derive(Default)]
+pub struct KnownResources {
+    pub peers: Vec<PeerDescriptor>,
+    pub cluster_configurations: Vec<ClusterConfiguration>,
+}
+
+/// Converts the documents into models, upgrading older specification versions on the way.
+/// Peers are converted first, so that clusters can reference the leader and devices by the names of specified peers.
+/// Names are also resolved against the known peers, unless a peer with the same ID is specified.
+pub fn convert_documents_to_models(documents: Vec<SpecificationDocument>, known: &KnownResources) -> crate::Result<Vec<ResourceModel>> {
+    let (peer_documents, cluster_documents): (Vec<_>, Vec<_>) = documents.into_iter()
+        .partition(|document| matches!(document.spec, Specification::PeerDescriptorSpecification(_)));
+
+    let specified_peers = peer_documents.into_iter()
+        .map(|document| convert_document_to_model(document, &[], known))
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    let mut peers = specified_peers.iter()
+        .filter_map(|model| match model {
+            ResourceModel::PeerDescriptor(peer) => Some(peer.clone()),
+            ResourceModel::ClusterConfiguration(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let unspecified_peers = known.peers.iter()
+        .filter(|known| peers.iter().all(|peer| peer.id != known.id))
+        .cloned()
+        .collect::<Vec<_>>();
+    peers.extend(unspecified_peers);
+
+    let cluster_configurations = cluster_documents.into_iter()
+        .map(|document| convert_document_to_model(document, &peers, known))
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    Ok(specified_peers.into_iter().chain(cluster_configurations).collect())
+}
+
+/// Returns the path itself, if it is a file, or the YAML files directly contained in the directory, sorted by name.
+fn collect_specification_files(path: &Path) -> crate::Result<Vec<PathBuf>> {
+    if path.is_dir().not() {
+        return Ok(vec![path.to_owned()]);
+    }
+
+    let entries = fs::read_dir(path)
+        .map_err(|cause| format!("Failed to read directory '{}': {cause}", path.display()))?;
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry
+            .map_err(|cause| format!("Failed to read directory '{}': {cause}", path.display()))?;
+        let file = entry.path();
+
+        let is_yaml = file.extension()
+            .is_some_and(|extension| extension == "yaml" || extension == "yml");
+
+        if file.is_file() && is_yaml {
+            files.push(file);
+        }
+    }
+    files.sort();
+
+    if files.is_empty() {
+        Err(format!("The directory '{}' does not contain any YAML files.", path.display()))?;
+    }
+    Ok(files)
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub enum Source {
+    File(PathBuf),
+    Inline(InlineSource)
+}
+
+#[derive(Clone, Debug)]
+pub enum InlineSource {
+    Json(String)
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("'{arg}' cannot be used as source. {details}")]
+struct SourceParsingError {
+    arg: String,
+    details: String,
+}
+
+fn parse_source(arg: &str) -> Result<Source, SourceParsingError> {
+
+    if arg.starts_with('{') && arg.ends_with('}') {
+        Ok(Source::Inline(InlineSource::Json(arg.to_owned())))
+    }
+    else {
+        let path = PathBuf::from(arg);
+        if !path.exists() {
+            Err(SourceParsingError { arg: String::from(arg), details: String::from("Path does not exist.") })?;
+        }
+        if path.is_file() || path.is_dir() {
+            Ok(Source::File(path))
+        }
+        else {
+            Err(SourceParsingError { arg: String::from(arg), details: String::from("Path designates neither a file nor a directory.") })
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ResourceModel {
+    PeerDescriptor(PeerDescriptor),
+    ClusterConfiguration(ClusterConfiguration),
+}
+
+/// Converts a single document. Clusters reference the leader and devices among the given `peers`.
+fn convert_document_to_model(specification_document: SpecificationDocument, peers: &[PeerDescriptor], known: &KnownResources) -> crate::Result<ResourceModel> {
+    let result = match specification_document.spec {
+        Specification::PeerDescriptorSpecification(peer) => {
+            let peer_name = specification_document.metadata.name.clone();
+            let peer = peer.into_latest()
+                .map_err(|error| format!("Could not upgrade the provided specification for peer '{}'.\n  {}", peer_name, error))?;
+            let peer_descriptor = convert_document_to_peer_descriptor(specification_document.metadata, peer, &known.peers)
+                .map_err(|error| format!("Could not parse the provided specification for peer '{}'.\n  {}", peer_name, error))?;
+            ResourceModel::PeerDescriptor(peer_descriptor)
+        }
+        Specification::ClusterConfigurationSpecification(cluster_configuration) => {
+            let cluster_configuration_name = specification_document.metadata.name.clone();
+            let cluster_configuration = convert_document_to_cluster_configuration(specification_document.metadata, cluster_configuration.into_latest(), peers, &known.cluster_configurations)
+                .map_err(|error| format!("Could not parse the provided specification for cluster configuration '{}'.\n {}", cluster_configuration_name, error))?;
+            ResourceModel::ClusterConfiguration(cluster_configuration)
+        }        
+    };
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::peer::{PeerDescriptor, PeerId, PeerName, PeerNetworkDescriptor};
+    use opendut_types::specs::peer::{NetworkDescriptorSpecificationV1, NetworkInterfaceDescriptorSpecificationV1, NetworkInterfaceKind, PeerDescriptorSpecification, PeerDescriptorSpecificationV1, TopologySpecificationV1};
+    use opendut_types::specs::{Specification, SpecificationDocument, SpecificationMetadata};
+    use opendut_types::topology::Topology;
+    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
+
+    #[test]
+    fn should_convert_document_to_model() -> anyhow::Result<()> {
+        let peer = generate_peer_descriptor()?;
+        
+        let interface_kind = match peer.network.interfaces[0].configuration {
+            NetworkInterfaceConfiguration::Ethernet => {
+                NetworkInterfaceKind::Ethernet
+            }
+            NetworkInterfaceConfiguration::Can { .. } => {
+                NetworkInterfaceKind::Can
+            }
+        };
+        
+        let network = get_interface_specification(peer.clone(), interface_kind)?;
+        
+        let document = SpecificationDocument {
+            version: String::from("v1"),
+            metadata: SpecificationMetadata {
+                id: Some(peer.id.uuid),
+                name: peer.name.clone().value(),
+            },
+            spec: Specification::PeerDescriptorSpecification(PeerDescriptorSpecification::V1(PeerDescriptorSpecificationV1 {
+                location: None,
+                network,
+                topology: TopologySpecificationV1 { devices: vec![] },
+                executors: vec![],
+            }))
+        };
+        
+        let model = convert_document_to_model(document, &[], &KnownResources::default()).unwrap();
+        let result =
+            if let ResourceModel::PeerDescriptor(model) = model {
+                model
+            }
+            else {
+                panic!("Specification is not a peer.")
+            };
+        
+        assert_that!(result, eq(&peer));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_resolve_cluster_leader_by_name_of_specified_peer() -> anyhow::Result<()> {
+        let known_peer = generate_peer_descriptor()?;
+        let specified_peer_id = PeerId::random();
+
+        let documents = parse_yaml_documents(&format!(r#"
+            kind: ClusterConfiguration
+            version: v2
+            metadata:
+              id: 7a0c4f35-4a7f-4e53-8d5b-d4d1d3c70ec8
+              name: MyCluster
+            spec:
+              leader: MyLeader
+            ---
+            kind: PeerDescriptor
+            version: v2
+            metadata:
+              id: {specified_peer_id}
+              name: MyLeader
+            spec: {{}}
+        "#).replace("\n            ", "\n")).unwrap();
+
+        let known = KnownResources {
+            peers: vec![known_peer],
+            cluster_configurations: vec![],
+        };
+        let models = convert_documents_to_models(documents, &known).unwrap();
+
+        let [ResourceModel::PeerDescriptor(peer), ResourceModel::ClusterConfiguration(cluster)] = models.as_slice() else {
+            panic!("Expected the peer followed by the cluster configuration, but got: {models:?}");
+        };
+        assert_that!(peer.id, eq(specified_peer_id));
+        assert_that!(cluster.leader, eq(specified_peer_id));
+
+        Ok(())
+    }
+    
+    fn generate_peer_descriptor() -> anyhow::Result<PeerDescriptor> {
+        Ok(PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from("peer1")?,
+            location: None,
+            network: PeerNetworkDescriptor {
+                interfaces: vec![
+                    NetworkInterfaceDescriptor {
+                        id: NetworkInterfaceId::random(),
+                        name: NetworkInterfaceName::try_from("eth0")?,
+                        configuration: NetworkInterfaceConfiguration::Ethernet,
+                    }
+                ],
+                bridge_name: Some(NetworkInterfaceName::try_from("br-opendut")?),
+            },
+            topology: Topology { devices: vec![] },
+            executors: ExecutorDescriptors { executors: vec![] },
+            labels: Default::default(),
+            project: Default::default(),
+        })
+    }
+    
+    fn get_interface_specification(peer: PeerDescriptor, interface_kind: NetworkInterfaceKind) -> anyhow::Result<NetworkDescriptorSpecificationV1> {
+        Ok(NetworkDescriptorSpecificationV1 {
+            interfaces: vec![
+                NetworkInterfaceDescriptorSpecificationV1 {
+                    id: peer.network.interfaces[0].id.uuid,
+                    name: peer.network.interfaces[0].name.to_string(),
+                    kind: interface_kind,
+                    parameters: None,
+                }
+            ],
+            bridge_name: peer.network.bridge_name.map(|name| name.name()),
+        })
+    }
+}
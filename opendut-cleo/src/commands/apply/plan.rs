@@ -0,0 +1,365 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use console::Style;
+use serde::Serialize;
+use uuid::Uuid;
+
+use opendut_types::cluster::ClusterConfiguration;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::resources::Revision;
+
+use crate::commands::apply::ResourceModel;
+
+/// Changes needed to bring the resources in CARL to the state described by the specification files.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Plan {
+    pub peers: Vec<Change<PeerDescriptor>>,
+    pub cluster_configurations: Vec<Change<ClusterConfiguration>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum Change<R> {
+    Create { desired: R },
+    Update { current: R, desired: R, revision: Revision },
+    Unchanged { current: R },
+    Delete { current: R, revision: Revision },
+}
+
+impl Plan {
+    /// Matches the desired resources with the current resources by their ID.
+    /// Current resources, which are not specified, are only deleted when `prune` is set.
+    pub fn compute(
+        desired: Vec<ResourceModel>,
+        current_peers: Vec<(PeerDescriptor, Revision)>,
+        current_cluster_configurations: Vec<(ClusterConfiguration, Revision)>,
+        prune: bool,
+    ) -> crate::Result<Self> {
+        let mut desired_peers = Vec::new();
+        let mut desired_cluster_configurations = Vec::new();
+
+        for model in desired {
+            match model {
+                ResourceModel::PeerDescriptor(peer) => desired_peers.push(peer),
+                ResourceModel::ClusterConfiguration(cluster) => desired_cluster_configurations.push(cluster),
+            }
+        }
+
+        Ok(Plan {
+            peers: compute_changes(desired_peers, current_peers, prune)?,
+            cluster_configurations: compute_changes(desired_cluster_configurations, current_cluster_configurations, prune)?,
+        })
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.peers.iter().any(Change::is_change)
+            || self.cluster_configurations.iter().any(Change::is_change)
+    }
+
+    pub fn render_text(&self) -> String {
+        let mut text = String::new();
+
+        for change in &self.peers {
+            render_change(&mut text, change);
+        }
+        for change in &self.cluster_configurations {
+            render_change(&mut text, change);
+        }
+
+        let count = |action: &str| {
+            self.peers.iter().filter(|change| change.action() == action).count()
+                + self.cluster_configurations.iter().filter(|change| change.action() == action).count()
+        };
+        let create = count("create");
+        let update = count("update");
+        let delete = count("delete");
+        let unchanged = count("unchanged");
+
+        if self.has_changes() {
+            let _ = writeln!(text, "Plan: {create} to create, {update} to update, {delete} to delete, {unchanged} unchanged.");
+        } else {
+            let _ = writeln!(text, "No changes. The resources in CARL match the specification ({unchanged} unchanged).");
+        }
+        text
+    }
+}
+
+impl<R> Change<R> {
+    fn action(&self) -> &'static str {
+        match self {
+            Change::Create { .. } => "create",
+            Change::Update { .. } => "update",
+            Change::Unchanged { .. } => "unchanged",
+            Change::Delete { .. } => "delete",
+        }
+    }
+
+    fn is_change(&self) -> bool {
+        !matches!(self, Change::Unchanged { .. })
+    }
+}
+
+/// A resource, which can be managed declaratively via specification files.
+pub trait PlannedResource: Clone + PartialEq {
+    const KIND: &'static str;
+
+    fn uuid(&self) -> Uuid;
+
+    fn display_name(&self) -> String;
+
+    /// Names of the top-level fields, which differ from `other`.
+    fn changed_fields(&self, other: &Self) -> Vec<&'static str>;
+}
+
+impl PlannedResource for PeerDescriptor {
+    const KIND: &'static str = "peer";
+
+    fn uuid(&self) -> Uuid {
+        self.id.uuid
+    }
+
+    fn display_name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn changed_fields(&self, other: &Self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if self.name != other.name { fields.push("name"); }
+        if self.location != other.location { fields.push("location"); }
+        if self.network != other.network { fields.push("network"); }
+        if self.topology != other.topology { fields.push("topology"); }
+        if self.executors != other.executors { fields.push("executors"); }
+        if self.labels != other.labels { fields.push("labels"); }
+        if self.project != other.project { fields.push("project"); }
+        fields
+    }
+}
+
+impl PlannedResource for ClusterConfiguration {
+    const KIND: &'static str = "cluster configuration";
+
+    fn uuid(&self) -> Uuid {
+        self.id.0
+    }
+
+    fn display_name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn changed_fields(&self, other: &Self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if self.name != other.name { fields.push("name"); }
+        if self.leader != other.leader { fields.push("leader"); }
+        if self.devices != other.devices { fields.push("devices"); }
+        if self.device_selector != other.device_selector { fields.push("device-selector"); }
+        if self.labels != other.labels { fields.push("labels"); }
+        if self.project != other.project { fields.push("project"); }
+        fields
+    }
+}
+
+fn compute_changes<R: PlannedResource>(
+    desired: Vec<R>,
+    current: Vec<(R, Revision)>,
+    prune: bool,
+) -> crate::Result<Vec<Change<R>>> {
+    let mut current = current.into_iter()
+        .map(|(resource, revision)| (resource.uuid(), (resource, revision)))
+        .collect::<HashMap<_, _>>();
+
+    let mut specified = HashSet::new();
+    let mut changes = Vec::new();
+
+    for desired in desired {
+        let id = desired.uuid();
+        if !specified.insert(id) {
+            return Err(format!("The {} <{}> is specified more than once.", R::KIND, id));
+        }
+
+        let change = match current.remove(&id) {
+            None => Change::Create { desired },
+            Some((current, _)) if current == desired => Change::Unchanged { current },
+            Some((current, revision)) => Change::Update { current, desired, revision },
+        };
+        changes.push(change);
+    }
+
+    if prune {
+        let mut unspecified = current.into_values().collect::<Vec<_>>();
+        unspecified.sort_by_key(|(resource, _)| resource.uuid());
+
+        changes.extend(unspecified.into_iter()
+            .map(|(current, revision)| Change::Delete { current, revision })
+        );
+    }
+
+    Ok(changes)
+}
+
+fn render_change<R: PlannedResource>(text: &mut String, change: &Change<R>) {
+    let describe = |resource: &R| format!("{} '{}' <{}>", R::KIND, resource.display_name(), resource.uuid());
+
+    let _ = match change {
+        Change::Create { desired } => {
+            writeln!(text, "{}", Style::new().green().apply_to(format!("  + create {}", describe(desired))))
+        }
+        Change::Update { current, desired, .. } => {
+            let fields = current.changed_fields(desired).join(", ");
+            writeln!(text, "{}", Style::new().yellow().apply_to(format!("  ~ update {} (changed: {fields})", describe(desired))))
+        }
+        Change::Unchanged { .. } => Ok(()),
+        Change::Delete { current, .. } => {
+            writeln!(text, "{}", Style::new().red().apply_to(format!("  - delete {}", describe(current))))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use opendut_types::cluster::{ClusterId, ClusterName};
+    use opendut_types::peer::{PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::project::ProjectId;
+    use opendut_types::topology::Topology;
+
+    #[test]
+    fn should_plan_create_update_and_unchanged_without_deleting_unspecified_resources() -> anyhow::Result<()> {
+        let unchanged = peer("unchanged")?;
+        let updated = peer("updated")?;
+        let created = peer("created")?;
+        let unspecified = peer("unspecified")?;
+
+        let desired_update = PeerDescriptor {
+            location: Some(PeerLocation::try_from("Ulm")?),
+            ..updated.clone()
+        };
+
+        let plan = Plan::compute(
+            vec![
+                ResourceModel::PeerDescriptor(unchanged.clone()),
+                ResourceModel::PeerDescriptor(desired_update.clone()),
+                ResourceModel::PeerDescriptor(created.clone()),
+            ],
+            vec![
+                (unchanged.clone(), Revision(1)),
+                (updated.clone(), Revision(2)),
+                (unspecified.clone(), Revision(3)),
+            ],
+            vec![],
+            false,
+        )?;
+
+        assert_that!(plan.peers, elements_are![
+            eq(&Change::Unchanged { current: unchanged }),
+            eq(&Change::Update { current: updated.clone(), desired: desired_update.clone(), revision: Revision(2) }),
+            eq(&Change::Create { desired: created }),
+        ]);
+        assert_that!(updated.changed_fields(&desired_update), elements_are![eq(&"location")]);
+        assert_that!(plan.has_changes(), eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn should_plan_deletion_of_unspecified_resources_when_pruning() -> anyhow::Result<()> {
+        let leader = peer("leader")?;
+        let unspecified_peer = peer("unspecified")?;
+        let unspecified_cluster = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("cluster")?,
+            leader: leader.id,
+            devices: HashSet::new(),
+            labels: Default::default(),
+            device_selector: None,
+            project: Default::default(),
+        };
+
+        let plan = Plan::compute(
+            vec![ResourceModel::PeerDescriptor(leader.clone())],
+            vec![(leader.clone(), Revision(1)), (unspecified_peer.clone(), Revision(2))],
+            vec![(unspecified_cluster.clone(), Revision(3))],
+            true,
+        )?;
+
+        assert_that!(plan.peers, elements_are![
+            eq(&Change::Unchanged { current: leader }),
+            eq(&Change::Delete { current: unspecified_peer, revision: Revision(2) }),
+        ]);
+        assert_that!(plan.cluster_configurations, elements_are![
+            eq(&Change::Delete { current: unspecified_cluster, revision: Revision(3) }),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_report_changed_projects() -> anyhow::Result<()> {
+        let current = peer("peer")?;
+        let mut desired = current.clone();
+        desired.project.shared_with.insert(ProjectId::try_from("chassis")?);
+
+        let plan = Plan::compute(
+            vec![ResourceModel::PeerDescriptor(desired.clone())],
+            vec![(current.clone(), Revision(1))],
+            vec![],
+            false,
+        )?;
+
+        assert_that!(plan.peers, elements_are![
+            eq(&Change::Update { current: current.clone(), desired: desired.clone(), revision: Revision(1) }),
+        ]);
+        assert_that!(plan.render_text(), contains_substring("(changed: project)"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_report_no_changes_if_specification_matches() -> anyhow::Result<()> {
+        let peer = peer("peer")?;
+
+        let plan = Plan::compute(
+            vec![ResourceModel::PeerDescriptor(peer.clone())],
+            vec![(peer, Revision(1))],
+            vec![],
+            true,
+        )?;
+
+        assert_that!(plan.has_changes(), eq(false));
+        assert_that!(plan.render_text(), contains_substring("No changes."));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_resources_specified_more_than_once() -> anyhow::Result<()> {
+        let peer = peer("peer")?;
+
+        let error = Plan::compute(
+            vec![
+                ResourceModel::PeerDescriptor(peer.clone()),
+                ResourceModel::PeerDescriptor(peer.clone()),
+            ],
+            vec![],
+            vec![],
+            false,
+        ).unwrap_err();
+
+        assert_that!(error, contains_substring(peer.id.to_string()));
+        Ok(())
+    }
+
+    fn peer(name: &str) -> anyhow::Result<PeerDescriptor> {
+        Ok(PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from(name)?,
+            location: None,
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Topology { devices: vec![] },
+            executors: ExecutorDescriptors { executors: vec![] },
+            labels: Default::default(),
+            project: Default::default(),
+        })
+    }
+}
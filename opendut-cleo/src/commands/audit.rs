@@ -0,0 +1,137 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use cli_table::{print_stdout, Table, WithTitle};
+use serde::Serialize;
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::audit::{AuditActor, AuditEntry, AuditLogFilter};
+
+use crate::ListOutputFormat;
+
+/// Show the modifications made to openDuT resources and who made them
+#[derive(clap::Parser)]
+pub struct AuditCli {
+    /// Only show modifications of the resource with this ID
+    #[arg(long)]
+    resource: Option<Uuid>,
+    /// Only show modifications of this kind of resource, e.g. 'peer_descriptor' or 'cluster_configuration'
+    #[arg(long)]
+    kind: Option<String>,
+    /// Only show modifications made by this user
+    #[arg(long)]
+    user: Option<String>,
+    ///JSON, prettified JSON or table as output format
+    #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
+    output: ListOutputFormat,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct SerializableAuditEntry {
+    timestamp: String,
+    user: String,
+    action: String,
+    kind: String,
+    resource: Uuid,
+}
+
+#[derive(Table)]
+struct AuditEntryTable {
+    #[table(title = "Timestamp")]
+    timestamp: String,
+    #[table(title = "User")]
+    user: String,
+    #[table(title = "Action")]
+    action: String,
+    #[table(title = "Kind")]
+    kind: String,
+    #[table(title = "ResourceID")]
+    resource: Uuid,
+}
+
+impl From<AuditEntry> for SerializableAuditEntry {
+    fn from(entry: AuditEntry) -> Self {
+        SerializableAuditEntry {
+            timestamp: DateTime::<Utc>::from(entry.timestamp).to_rfc3339_opts(SecondsFormat::Millis, true),
+            user: entry.actor.to_string(),
+            action: entry.action.to_string(),
+            kind: entry.resource.kind,
+            resource: entry.resource.id,
+        }
+    }
+}
+
+impl From<SerializableAuditEntry> for AuditEntryTable {
+    fn from(entry: SerializableAuditEntry) -> Self {
+        let SerializableAuditEntry { timestamp, user, action, kind, resource } = entry;
+        AuditEntryTable { timestamp, user, action, kind, resource }
+    }
+}
+
+impl AuditCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let filter = AuditLogFilter {
+            resource_id: self.resource,
+            resource_kind: self.kind,
+            actor: self.user.map(|name| AuditActor::User { name }),
+        };
+
+        let entries = carl.audit.list_audit_entries(filter).await
+            .map_err(|error| format!("Could not list audit entries.\n  {error}"))?
+            .into_iter()
+            .map(SerializableAuditEntry::from)
+            .collect::<Vec<_>>();
+
+        match self.output {
+            ListOutputFormat::Table => {
+                let table = entries.into_iter()
+                    .map(AuditEntryTable::from)
+                    .collect::<Vec<_>>();
+                print_stdout(table.with_title())
+                    .expect("List of audit entries should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&entries).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&entries).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use googletest::prelude::*;
+
+    use opendut_types::audit::{AuditAction, AuditActor, AuditEntryId, AuditedResource};
+
+    use super::*;
+
+    #[test]
+    fn should_convert_audit_entry_for_output() {
+        let resource = Uuid::new_v4();
+        let entry = AuditEntry {
+            id: AuditEntryId::random(),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+            actor: AuditActor::User { name: String::from("alice") },
+            action: AuditAction::Removed,
+            resource: AuditedResource { kind: String::from("peer_descriptor"), id: resource },
+        };
+
+        assert_that!(
+            SerializableAuditEntry::from(entry),
+            eq(&SerializableAuditEntry {
+                timestamp: String::from("2023-11-14T22:13:20.123Z"),
+                user: String::from("alice"),
+                action: String::from("removed"),
+                kind: String::from("peer_descriptor"),
+                resource,
+            })
+        );
+    }
+}
@@ -1,30 +1,83 @@
 use std::collections::HashSet;
+use std::str::FromStr;
 use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
-use opendut_types::peer::PeerId;
-use opendut_types::specs::cluster::ClusterConfigurationSpecificationV1;
-use opendut_types::specs::SpecificationMetadata;
+use opendut_types::labels::{LabelSelector, Labels};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::project::ProjectId;
+use opendut_types::specs::cluster::ClusterConfigurationSpecificationV2;
+use opendut_types::specs::{ReferenceSpecification, SpecificationMetadata};
 use opendut_types::topology::{DeviceId};
 
-pub fn convert_document_to_cluster_configuration(specification_metadata: SpecificationMetadata, cluster: ClusterConfigurationSpecificationV1) -> crate::Result<ClusterConfiguration>  {
+use crate::resolve::{find_by_name, resolve_by_name};
+
+/// Converts the specification into a cluster configuration.
+/// The leader and devices referenced by name are looked up in the given `peers`.
+/// Devices of the `peers` matching the device selector are added to the devices, like CARL does when storing the configuration.
+/// If no ID is specified, the ID of the known cluster configuration with the same name is used, or a new one is generated.
+pub fn convert_document_to_cluster_configuration(specification_metadata: SpecificationMetadata, cluster: ClusterConfigurationSpecificationV2, peers: &[PeerDescriptor], known_cluster_configurations: &[ClusterConfiguration]) -> crate::Result<ClusterConfiguration>  {
     let SpecificationMetadata { id, name } = specification_metadata;
-    
-    let id = ClusterId::from(id);
+
+    let id = match id {
+        Some(id) => ClusterId::from(id),
+        None => {
+            let known = known_cluster_configurations.iter()
+                .map(|configuration| (configuration.id, configuration.name.to_string()));
+            find_by_name("cluster", &name, known)?
+                .unwrap_or_else(ClusterId::random)
+        }
+    };
 
     let name = ClusterName::try_from(name)
         .map_err(|error| error.to_string())?;
-    
-    let leader = cluster.leader_id;
+
+    let leader = match cluster.leader {
+        ReferenceSpecification::Id(id) => PeerId::from(id),
+        ReferenceSpecification::Name(leader_name) => {
+            let peers = peers.iter()
+                .map(|peer| (peer.id, peer.name.to_string()));
+            resolve_by_name("peer", &leader_name, peers)
+                .map_err(|error| format!("Could not resolve the leader of cluster '{name}': {error}"))?
+        }
+    };
+
     let devices = cluster.devices.into_iter()
-        .map(DeviceId::from).
-        collect::<HashSet<_>>();
+        .map(|device| match device {
+            ReferenceSpecification::Id(id) => Ok(DeviceId::from(id)),
+            ReferenceSpecification::Name(device_name) => {
+                let devices = peers.iter()
+                    .flat_map(|peer| &peer.topology.devices)
+                    .map(|device| (device.id, device.name.to_string()));
+                resolve_by_name("device", &device_name, devices)
+                    .map_err(|error| format!("Could not resolve a device of cluster '{name}': {error}"))
+            }
+        })
+        .collect::<crate::Result<HashSet<_>>>()?;
+
+    let labels = Labels::try_from(cluster.labels)
+        .map_err(|error| format!("Invalid labels of cluster '{name}': {error}"))?;
+
+    let device_selector = cluster.device_selector
+        .map(|selector| LabelSelector::from_str(&selector))
+        .transpose()
+        .map_err(|error| format!("Invalid device selector of cluster '{name}': {error}"))?;
 
-    let configuration = ClusterConfiguration {
+    let project = cluster.project
+        .map(ProjectId::try_from)
+        .transpose()
+        .map_err(|error| format!("Invalid project of cluster '{name}': {error}"))?
+        .unwrap_or_default();
+
+    let mut configuration = ClusterConfiguration {
         id,
         name,
-        leader: PeerId::from(leader),
+        leader,
         devices,
+        labels,
+        device_selector,
+        project,
     };
-    
+    configuration.select_devices(peers.iter().flat_map(|peer| &peer.topology.devices));
+
     Ok(configuration)
 }
 
@@ -32,7 +85,12 @@ pub fn convert_document_to_cluster_configuration(specification_metadata: Specifi
 mod tests {
     use super::*;
     use googletest::prelude::*;
-    
+    use opendut_types::peer::{PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::specs::cluster::ClusterConfigurationSpecificationV1;
+    use opendut_types::topology::{DeviceDescriptor, DeviceName, Topology};
+    use opendut_types::util::net::NetworkInterfaceId;
+
     #[test]
     fn should_convert_document_to_cluster_configuration() -> anyhow::Result<()> {
         let cluster_configuration = ClusterConfiguration {
@@ -40,23 +98,180 @@ mod tests {
             name: ClusterName::try_from("FirstCluster")?,
             leader: PeerId::random(),
             devices: Default::default(),
+            labels: Default::default(),
+            device_selector: None,
+            project: Default::default(),
         };
-        
+
         let specification_meta_data = SpecificationMetadata {
-            id: cluster_configuration.id.0,
+            id: Some(cluster_configuration.id.0),
             name: cluster_configuration.name.clone().value(),
         };
-        
+
         let document = ClusterConfigurationSpecificationV1 {
             leader_id: cluster_configuration.leader.uuid,
             devices: vec![],
         };
-        
-        let result = convert_document_to_cluster_configuration(specification_meta_data, document).unwrap();
+
+        let result = convert_document_to_cluster_configuration(specification_meta_data, document.into(), &[], &[]).unwrap();
 
         assert_that!(result, eq(&cluster_configuration));
 
 
-        Ok(())      
+        Ok(())
+    }
+
+    #[test]
+    fn should_resolve_leader_referenced_by_name() -> anyhow::Result<()> {
+        let leader = peer("leader")?;
+        let other = peer("other")?;
+
+        let document = ClusterConfigurationSpecificationV2 {
+            leader: ReferenceSpecification::Name(String::from("leader")),
+            devices: vec![],
+            device_selector: None,
+            labels: Default::default(),
+            project: None,
+        };
+
+        let result = convert_document_to_cluster_configuration(metadata(), document, &[other, leader.clone()], &[]).unwrap();
+
+        assert_that!(result.leader, eq(leader.id));
+        Ok(())
+    }
+
+    #[test]
+    fn should_fail_to_resolve_leader_with_ambiguous_or_unknown_name() -> anyhow::Result<()> {
+        let first = peer("leader")?;
+        let second = peer("leader")?;
+
+        let ambiguous = ClusterConfigurationSpecificationV2 {
+            leader: ReferenceSpecification::Name(String::from("leader")),
+            devices: vec![],
+            device_selector: None,
+            labels: Default::default(),
+            project: None,
+        };
+        let error = convert_document_to_cluster_configuration(metadata(), ambiguous, &[first.clone(), second.clone()], &[]).unwrap_err();
+
+        assert_that!(error, contains_substring("ambiguous"));
+        assert_that!(error, contains_substring(first.id.to_string()));
+        assert_that!(error, contains_substring(second.id.to_string()));
+
+        let unknown = ClusterConfigurationSpecificationV2 {
+            leader: ReferenceSpecification::Name(String::from("unknown")),
+            devices: vec![],
+            device_selector: None,
+            labels: Default::default(),
+            project: None,
+        };
+        let error = convert_document_to_cluster_configuration(metadata(), unknown, &[first], &[]).unwrap_err();
+
+        assert_that!(error, contains_substring("Could not find a peer with the name 'unknown'."));
+        Ok(())
+    }
+
+    #[test]
+    fn should_resolve_devices_by_name_and_take_id_of_known_cluster_by_name() -> anyhow::Result<()> {
+        let mut leader = peer("leader")?;
+        let device = DeviceDescriptor {
+            id: DeviceId::random(),
+            name: DeviceName::try_from("device")?,
+            description: None,
+            interface: NetworkInterfaceId::random(),
+            tags: vec![],
+            labels: Default::default(),
+        };
+        leader.topology.devices.push(device.clone());
+
+        let known_cluster_configuration = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("cluster")?,
+            leader: leader.id,
+            devices: HashSet::new(),
+            labels: Default::default(),
+            device_selector: None,
+            project: Default::default(),
+        };
+
+        let document = ClusterConfigurationSpecificationV2 {
+            leader: ReferenceSpecification::Name(String::from("leader")),
+            devices: vec![ReferenceSpecification::Name(String::from("device"))],
+            device_selector: None,
+            labels: Default::default(),
+            project: None,
+        };
+        let metadata = SpecificationMetadata {
+            id: None,
+            name: String::from("cluster"),
+        };
+
+        let result = convert_document_to_cluster_configuration(metadata, document, &[leader], &[known_cluster_configuration.clone()]).unwrap();
+
+        assert_that!(result.id, eq(known_cluster_configuration.id));
+        assert_that!(result.devices, unordered_elements_are![eq(&device.id)]);
+        Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn should_convert_labels_and_select_devices() -> anyhow::Result<()> {
+        let mut leader = peer("leader")?;
+        let device = DeviceDescriptor {
+            id: DeviceId::random(),
+            name: DeviceName::try_from("device")?,
+            description: None,
+            interface: NetworkInterfaceId::random(),
+            tags: vec![],
+            labels: Labels::from_str("team=chassis")?,
+        };
+        leader.topology.devices.push(device.clone());
+
+        let document = ClusterConfigurationSpecificationV2 {
+            leader: ReferenceSpecification::Name(String::from("leader")),
+            devices: vec![],
+            device_selector: Some(String::from("team=chassis,!deprecated")),
+            labels: [(String::from("site"), String::from("munich"))].into(),
+            project: None,
+        };
+        let result = convert_document_to_cluster_configuration(metadata(), document, &[leader.clone()], &[]).unwrap();
+
+        assert_that!(result.labels.get("site"), some(eq("munich")));
+        assert_that!(result.device_selector.map(|selector| selector.to_string()), some(eq("team=chassis,!deprecated")));
+        assert_that!(result.devices, unordered_elements_are![eq(&device.id)]);
+
+        let invalid = ClusterConfigurationSpecificationV2 {
+            leader: ReferenceSpecification::Name(String::from("leader")),
+            devices: vec![],
+            device_selector: Some(String::from("=chassis")),
+            labels: Default::default(),
+            project: None,
+        };
+        let error = convert_document_to_cluster_configuration(metadata(), invalid, &[leader], &[]).unwrap_err();
+
+        assert_that!(error, contains_substring("Invalid device selector of cluster 'cluster'"));
+        Ok(())
+    }
+
+    fn metadata() -> SpecificationMetadata {
+        SpecificationMetadata {
+            id: Some(ClusterId::random().0),
+            name: String::from("cluster"),
+        }
+    }
+
+    fn peer(name: &str) -> anyhow::Result<PeerDescriptor> {
+        Ok(PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from(name)?,
+            location: None,
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Topology { devices: vec![] },
+            executors: ExecutorDescriptors { executors: vec![] },
+            labels: Default::default(),
+            project: Default::default(),
+        })
+    }
+}
@@ -3,10 +3,12 @@ use std::ops::Not;
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::cluster::{ClusterConfiguration, ClusterId};
-use opendut_types::peer::PeerId;
+use opendut_types::project::ProjectId;
 use opendut_types::topology::{DeviceDescriptor, DeviceId, DeviceName};
 
 use crate::parse::cluster::{ParseableClusterId, ParseableClusterName};
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
 use crate::{ClusterConfigurationDevices, CreateOutputFormat};
 
 /// Create a cluster configuration
@@ -18,9 +20,12 @@ pub struct CreateClusterConfigurationCli {
     ///ClusterID
     #[arg(short, long)]
     cluster_id: Option<ParseableClusterId>,
-    ///PeerID of the leader
-    #[arg(short, long)]
-    leader_id: PeerId,
+    ///ID or name of the leader peer
+    #[arg(short, long, alias="leader-id")]
+    leader: PeerReference,
+    ///Project the cluster belongs to. Its peers have to belong to or be shared with this project.
+    #[arg(long, default_value_t)]
+    project: ProjectId,
     ///List of devices in cluster
     #[clap(flatten)]
     devices: ClusterConfigurationDevices,
@@ -44,7 +49,7 @@ impl CreateClusterConfigurationCli {
             Err(format!("Cluster <{}> can not be updated while it is deployed.", cluster_id))?
         };
 
-        let leader = self.leader_id; //TODO: check if peer exists
+        let leader = resolve_peer(carl, self.leader).await?;
 
         let all_devices = carl.peers.list_devices().await
             .map_err(|error| format!("Error while listing devices.\n  {}", error))?;
@@ -99,7 +104,10 @@ impl CreateClusterConfigurationCli {
             id: cluster_id, 
             name: Clone::clone(&cluster_name), 
             leader, 
-            devices: device_ids 
+            devices: device_ids,
+            labels: Default::default(),
+            device_selector: None,
+            project: self.project,
         };
         
         create_cluster_configuration(configuration, carl, &output).await?;
@@ -294,6 +302,7 @@ mod test {
                 description: DeviceDescription::try_from("").ok(),
                 interface: NetworkInterfaceId::random(),
                 tags: vec![],
+                labels: Default::default(),
             },
             DeviceDescriptor {
                 id: DeviceId::random(),
@@ -301,6 +310,7 @@ mod test {
                 description: DeviceDescription::try_from("").ok(),
                 interface: NetworkInterfaceId::random(),
                 tags: vec![],
+                labels: Default::default(),
             },
             DeviceDescriptor {
                 id: DeviceId::random(),
@@ -308,6 +318,7 @@ mod test {
                 description: DeviceDescription::try_from("").ok(),
                 interface: NetworkInterfaceId::random(),
                 tags: vec![],
+                labels: Default::default(),
             }
         ]
     }
@@ -1,17 +1,18 @@
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::cluster::ClusterId;
+use crate::parse::reference::ClusterReference;
+use crate::resolve::resolve_cluster;
 
 /// Delete a cluster configuration
 #[derive(clap::Parser)]
 pub struct DeleteClusterConfigurationCli {
-    /// ID of the cluster
+    /// ID or name of the cluster
     #[arg()]
-    id: ClusterId,
+    cluster: ClusterReference,
 }
 
 impl DeleteClusterConfigurationCli {
     pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
-        let id = self.id;
+        let id = resolve_cluster(carl, self.cluster).await?;
 
         let cluster_deployments = carl.cluster.list_cluster_deployments().await
             .map_err(|_| String::from("Failed to get list of cluster deployments!"))?;
@@ -7,14 +7,16 @@ use opendut_types::topology::DeviceName;
 use serde::Serialize;
 
 use crate::DescribeOutputFormat;
+use crate::parse::reference::ClusterReference;
+use crate::resolve::resolve_cluster;
 
 
 /// Describe a cluster configuration
 #[derive(clap::Parser)]
 pub struct DescribeClusterConfigurationCli {
-    /// ID of the cluster
+    /// ID or name of the cluster
     #[arg()]
-    id: ClusterId,
+    cluster: ClusterReference,
 }
 
 #[derive(Debug, Serialize)]
@@ -28,7 +30,7 @@ struct ClusterTable {
 
 impl DescribeClusterConfigurationCli {
     pub async fn execute(self, carl: &mut CarlClient, output: DescribeOutputFormat) -> crate::Result<()> {
-        let cluster_id = self.id;
+        let cluster_id = resolve_cluster(carl, self.cluster).await?;
 
         let clusters_configuration = carl.cluster.list_cluster_configurations().await
             .map_err(|_| String::from("Failed to get list of cluster configurations!"))?;
@@ -2,12 +2,19 @@ use cli_table::{print_stdout, Table, WithTitle};
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::cluster::{ClusterId, ClusterName};
+use opendut_types::project::ProjectId;
 
+use crate::commands::query::{ListQueryArgs, ProjectFilterArgs};
 use crate::ListOutputFormat;
 
 /// List all cluster configurations
 #[derive(clap::Parser)]
-pub struct ListClusterConfigurationsCli;
+pub struct ListClusterConfigurationsCli {
+    #[command(flatten)]
+    query: ListQueryArgs,
+    #[command(flatten)]
+    project: ProjectFilterArgs,
+}
 
 #[derive(Table)]
 struct ClusterTable {
@@ -15,12 +22,18 @@ struct ClusterTable {
     name: ClusterName,
     #[table(title = "ClusterID")]
     id: ClusterId,
+    #[table(title = "Project")]
+    project: ProjectId,
 }
 
 impl ListClusterConfigurationsCli {
     pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
-        let clusters = carl.cluster.list_cluster_configurations().await
-            .map_err(|error| format!("Could not list any cluster configurations.\n  {error}"))?;
+        let clusters = carl.cluster.query_cluster_configurations(self.project.apply(self.query.into_query())).await
+            .map_err(|error| format!("Could not list any cluster configurations.\n  {error}"))?
+            .items
+            .into_iter()
+            .map(|(configuration, _)| configuration)
+            .collect::<Vec<_>>();
 
         match output {
             ListOutputFormat::Table => {
@@ -29,6 +42,7 @@ impl ListClusterConfigurationsCli {
                         ClusterTable {
                             name: cluster.name,
                             id: cluster.id,
+                            project: cluster.project,
                         }
                     })
                     .collect::<Vec<_>>();
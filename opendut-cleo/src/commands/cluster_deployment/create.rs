@@ -1,18 +1,20 @@
 use crate::CreateOutputFormat;
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::cluster::{ClusterDeployment, ClusterId};
+use opendut_types::cluster::ClusterDeployment;
+use crate::parse::reference::ClusterReference;
+use crate::resolve::resolve_cluster;
 
 /// Create a cluster deployment
 #[derive(clap::Parser)]
 pub struct CreateClusterDeploymentCli {
-    /// ID of the cluster
+    /// ID or name of the cluster
     #[arg()]
-    id: ClusterId,
+    cluster: ClusterReference,
 }
 
 impl CreateClusterDeploymentCli {
     pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
-        let id = self.id;
+        let id = resolve_cluster(carl, self.cluster).await?;
 
         let deployment = ClusterDeployment { id };
         carl.cluster.store_cluster_deployment(deployment).await
@@ -1,17 +1,18 @@
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::cluster::ClusterId;
+use crate::parse::reference::ClusterReference;
+use crate::resolve::resolve_cluster;
 
 /// Delete a cluster deployment
 #[derive(clap::Parser)]
 pub struct DeleteClusterDeploymentCli {
-    /// ID of the cluster
+    /// ID or name of the cluster
     #[arg()]
-    id: ClusterId,
+    cluster: ClusterReference,
 }
 
 impl DeleteClusterDeploymentCli {
     pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
-        let id = self.id;
+        let id = resolve_cluster(carl, self.cluster).await?;
         carl.cluster.delete_cluster_deployment(id).await
             .map_err(|error| {
                 format!("Could not delete cluster deployment for ClusterID '{}'.\n  {}", id, error)
@@ -1,15 +1,16 @@
 use crate::{CreateOutputFormat, DescribeOutputFormat};
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::peer::PeerId;
 use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, DeviceTag};
 use opendut_types::util::net::NetworkInterfaceName;
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
 
 /// Create a device
 #[derive(clap::Parser)]
 pub struct CreateDeviceCli {
-    ///ID of the peer to add the device to
-    #[arg(long)]
-    peer_id: PeerId,
+    ///ID or name of the peer to add the device to
+    #[arg(long, alias="peer-id")]
+    peer: PeerReference,
     ///ID of the device to be added or updated
     #[arg(long)]
     device_id: Option<DeviceId>,
@@ -30,10 +31,10 @@ pub struct CreateDeviceCli {
 impl CreateDeviceCli {
     #[allow(clippy::too_many_arguments)]
     pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
-        let peer_id = self.peer_id;
+        let peer_id = resolve_peer(carl, self.peer).await?;
         let device_id = self.device_id.unwrap_or(DeviceId::random());
 
-        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+        let (mut peer_descriptor, revision) = carl.peers.get_peer_descriptor_with_revision(peer_id).await
             .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
         let peer_network_interface_names = peer_descriptor.network.interfaces.iter().map(|peer_interface| {
             peer_interface.name.clone()
@@ -58,6 +59,7 @@ impl CreateDeviceCli {
                     description: self.description,
                     interface: interface.id,
                     tags: self.tags.unwrap_or_default(),
+                    labels: Default::default(),
                 };
                 peer_descriptor.topology.devices.push(new_device);
             }
@@ -82,7 +84,7 @@ impl CreateDeviceCli {
                 }
             }
         }
-        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+        carl.peers.store_peer_descriptor_if_unmodified(Clone::clone(&peer_descriptor), revision).await
             .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
         let output_format = DescribeOutputFormat::from(output);
         crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
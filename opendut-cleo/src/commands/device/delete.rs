@@ -1,23 +1,24 @@
 use std::ops::Not;
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::topology::DeviceId;
+use crate::parse::reference::DeviceReference;
+use crate::resolve::resolve_device;
 
 /// Delete a device
 #[derive(clap::Parser)]
 pub struct DeleteDeviceCli {
-    /// ID of the device
+    /// ID or name of the device
     #[arg()]
-    id: DeviceId,
+    device: DeviceReference,
 }
 
 impl DeleteDeviceCli {
     pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
-        let device_to_delete = self.id;
+        let device_to_delete = resolve_device(carl, self.device).await?;
 
-        let mut peers = carl.peers.list_peer_descriptors().await
+        let mut peers = carl.peers.list_peer_descriptors_with_revisions().await
             .map_err(|error| format!("Could not list peers.\n  {}", error))?;
 
-        let peer = peers.iter_mut().find(|peer| {
+        let (peer, revision) = peers.iter_mut().find(|(peer, _)| {
             peer.topology.devices
                 .iter()
                 .any(|device| device.id == device_to_delete)
@@ -44,7 +45,7 @@ impl DeleteDeviceCli {
 
         peer.topology.devices.retain(|device| device.id != device_to_delete);
 
-        carl.peers.store_peer_descriptor(Clone::clone(peer)).await
+        carl.peers.store_peer_descriptor_if_unmodified(Clone::clone(peer), *revision).await
             .map_err(|error| format!("Failed to delete peer.\n  {}", error))?;
 
         Ok(())
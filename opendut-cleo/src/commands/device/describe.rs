@@ -1,21 +1,22 @@
 use indoc::indoc;
 
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::topology::DeviceId;
 
 use crate::DescribeOutputFormat;
+use crate::parse::reference::DeviceReference;
+use crate::resolve::resolve_device;
 
 /// Describe a device
 #[derive(clap::Parser)]
 pub struct DescribeDeviceCli {
-    /// ID of the device
+    /// ID or name of the device
     #[arg()]
-    id: DeviceId,
+    device: DeviceReference,
 }
 
 impl DescribeDeviceCli {
     pub async fn execute(self, carl: &mut CarlClient, output: DescribeOutputFormat) -> crate::Result<()> {
-        let device_id = self.id;
+        let device_id = resolve_device(carl, self.device).await?;
 
         let devices = carl.peers.list_devices().await
             .map_err(|_| String::from("Failed to fetch list of devices."))?;
@@ -1,4 +1,8 @@
+use std::collections::BTreeMap;
+
 use opendut_carl_api::carl::CarlClient;
+use opendut_types::query::{Filter, ListQuery, Predicate, Queryable};
+use opendut_types::topology::DeviceDescriptor;
 
 use crate::commands::device::{render_devices, SerializableDevice};
 use crate::ListOutputFormat;
@@ -13,20 +17,22 @@ pub struct FindDeviceCli {
 
 impl FindDeviceCli {
     pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let patterns = self.criteria.iter()
+            .map(|criterion| glob::Pattern::new(criterion)
+                .map_err(|error| format!("Invalid search criterion '{criterion}': {error}"))
+            )
+            .collect::<crate::Result<Vec<_>>>()?;
+
         let devices = {
-            let devices = carl.peers.list_devices().await
-                .map_err(|_| String::from("Failed to find devices."))?;
-
-            devices.into_iter()
-                .filter(|device| {
-                    self.criteria.iter().any(|criterion| {
-                        let pattern = glob::Pattern::new(criterion).expect("Failed to read glob pattern");
-                        pattern.matches(&device.name.value().to_lowercase())
-                            || pattern.matches(&device.id.to_string().to_lowercase())
-                            || pattern.matches(&device.description.clone().unwrap().value().to_lowercase())
-                            || device.tags.iter().any(|tag| pattern.matches(&tag.value().to_lowercase()))
-                    })
-                })
+            let mut devices = BTreeMap::new(); //sorted by ID, like CARL sorts query results by default
+            for query in queries(&patterns) {
+                let page = carl.peers.query_devices(query).await
+                    .map_err(|error| format!("Failed to find devices.\n  {error}"))?;
+                for device in page.items {
+                    devices.insert(device.id.to_string(), device);
+                }
+            }
+            devices.into_values()
                 .map(SerializableDevice::from)
                 .collect::<Vec<_>>()
         };
@@ -35,3 +41,61 @@ impl FindDeviceCli {
         Ok(())
     }
 }
+
+/// Queries for the devices, of which any searchable field matches any of the patterns.
+/// CARL only lists resources matching all predicates of a filter, so each field and pattern is queried separately.
+fn queries(patterns: &[glob::Pattern]) -> Vec<ListQuery> {
+    patterns.iter()
+        .flat_map(|pattern| DeviceDescriptor::FIELDS.iter().map(|field| ListQuery {
+            filter: Some(Filter {
+                predicates: vec![Predicate { field: *field, negated: false, pattern: pattern.clone() }],
+            }),
+            ..Default::default()
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use opendut_types::topology::{DeviceId, DeviceName, DeviceTag};
+    use opendut_types::util::net::NetworkInterfaceId;
+
+    fn matches_any(device: &DeviceDescriptor, patterns: &[glob::Pattern]) -> bool {
+        queries(patterns).iter()
+            .any(|query| query.filter.as_ref().is_some_and(|filter| filter.matches(device)))
+    }
+
+    #[test]
+    fn should_match_devices_without_description() -> anyhow::Result<()> {
+        let device = DeviceDescriptor {
+            id: DeviceId::random(),
+            name: DeviceName::try_from("front-camera")?,
+            description: None,
+            interface: NetworkInterfaceId::random(),
+            tags: vec![DeviceTag::try_from("video")?],
+            labels: Default::default(),
+        };
+
+        assert_that!(matches_any(&device, &[glob::Pattern::new("front-*")?]), eq(true));
+        assert_that!(matches_any(&device, &[glob::Pattern::new("vid*")?]), eq(true));
+        assert_that!(matches_any(&device, &[glob::Pattern::new("rear-*")?]), eq(false));
+        Ok(())
+    }
+
+    #[test]
+    fn should_match_case_insensitively() -> anyhow::Result<()> {
+        let device = DeviceDescriptor {
+            id: DeviceId::random(),
+            name: DeviceName::try_from("Front-Camera")?,
+            description: None,
+            interface: NetworkInterfaceId::random(),
+            tags: vec![],
+            labels: Default::default(),
+        };
+
+        assert_that!(matches_any(&device, &[glob::Pattern::new("front-*")?]), eq(true));
+        Ok(())
+    }
+}
@@ -1,15 +1,20 @@
 use opendut_carl_api::carl::CarlClient;
 use crate::commands::device::{render_devices, SerializableDevice};
+use crate::commands::query::ListQueryArgs;
 use crate::ListOutputFormat;
 
 /// List all devices
 #[derive(clap::Parser)]
-pub struct ListDevicesCli;
+pub struct ListDevicesCli {
+    #[command(flatten)]
+    query: ListQueryArgs,
+}
 
 impl ListDevicesCli {
     pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
-        let devices = carl.peers.list_devices().await
-            .map_err(|_| String::from("Devices could not be listed"))?
+        let devices = carl.peers.query_devices(self.query.into_query()).await
+            .map_err(|error| format!("Devices could not be listed.\n  {error}"))?
+            .items
             .into_iter()
             .map(SerializableDevice::from)
             .collect::<Vec<_>>();
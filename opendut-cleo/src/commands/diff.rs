@@ -0,0 +1,31 @@
+use opendut_carl_api::carl::CarlClient;
+
+use crate::commands::apply::{compute_plan, SourceArgs};
+use crate::CreateOutputFormat;
+
+/// Show the changes `apply` would make, without applying them
+#[derive(clap::Parser)]
+pub struct DiffCli {
+    #[command(flatten)]
+    sources: SourceArgs,
+    ///Include the deletion of peers and cluster configurations, which are not contained in the specification files
+    #[arg(long)]
+    prune: bool,
+    ///Text, JSON or prettified JSON as output format
+    #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
+    output: CreateOutputFormat,
+}
+
+impl DiffCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let documents = self.sources.read_documents()?;
+        let plan = compute_plan(documents, self.prune, carl).await?;
+
+        match self.output {
+            CreateOutputFormat::Text => print!("{}", plan.render_text()),
+            CreateOutputFormat::Json => println!("{}", serde_json::to_string(&plan).unwrap()),
+            CreateOutputFormat::PrettyJson => println!("{}", serde_json::to_string_pretty(&plan).unwrap()),
+        }
+        Ok(())
+    }
+}
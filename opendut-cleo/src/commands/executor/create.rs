@@ -1,17 +1,18 @@
 use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorId};
 
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::peer::PeerId;
 use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ExecutorKind, ResultsUrl};
 
 use crate::{CreateOutputFormat, DescribeOutputFormat, EngineVariants};
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
 
 /// Create a container executor using command-line arguments
 #[derive(clap::Parser)]
 pub struct CreateContainerExecutorCli {
-    ///ID of the peer to add the container executor to
-    #[arg(long)]
-    peer_id: PeerId,
+    ///ID or name of the peer to add the container executor to
+    #[arg(long, alias="peer-id")]
+    peer: PeerReference,
     ///Engine
     #[arg(short, long)]
     engine: EngineVariants,
@@ -86,15 +87,15 @@ impl CreateContainerExecutorCli {
             results_url: self.results_url,
         };
 
-        let peer_id = self.peer_id;
+        let peer_id = resolve_peer(carl, self.peer).await?;
         
 
-        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+        let (mut peer_descriptor, revision) = carl.peers.get_peer_descriptor_with_revision(peer_id).await
             .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
 
         peer_descriptor.executors.executors.push(executor_descriptor);
 
-        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+        carl.peers.store_peer_descriptor_if_unmodified(Clone::clone(&peer_descriptor), revision).await
             .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
         let output_format = DescribeOutputFormat::from(output);
         crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
@@ -1,13 +1,14 @@
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::executor::{container::ContainerImage, ExecutorKind};
-use opendut_types::peer::PeerId;
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
 
 /// Delete a container executor
 #[derive(clap::Parser)]
 pub struct DeleteContainerExecutorCli {
-    ///ID of the peer to delete the container executor from
+    ///ID or name of the peer to delete the container executor from
     #[arg()]
-    peer_id: PeerId,
+    peer: PeerReference,
     ///Container images to delete
     #[arg(short, long, num_args = 1.., required = true)]
     images: Vec<ContainerImage>,
@@ -15,11 +16,12 @@ pub struct DeleteContainerExecutorCli {
 
 impl DeleteContainerExecutorCli {
     pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let peer_id = resolve_peer(carl, self.peer).await?;
 
-        let mut peer = carl.peers
-            .get_peer_descriptor(self.peer_id)
+        let (mut peer, revision) = carl.peers
+            .get_peer_descriptor_with_revision(peer_id)
             .await
-            .map_err(|error| format!("Failed to get peer with the id '{}'.\n  {}", self.peer_id, error))?;
+            .map_err(|error| format!("Failed to get peer with the id '{}'.\n  {}", peer_id, error))?;
 
         let container_images = self.images.into_iter()
             .map(ContainerImage::try_from)
@@ -35,7 +37,7 @@ impl DeleteContainerExecutorCli {
             })
         };
 
-        carl.peers.store_peer_descriptor(peer).await
+        carl.peers.store_peer_descriptor_if_unmodified(peer, revision).await
             .map_err(|error| format!("Failed to delete container executor for peer.\n  {}", error))?;
 
         Ok(())
@@ -0,0 +1,23 @@
+use std::path::PathBuf;
+
+use opendut_carl_api::carl::CarlClient;
+
+/// Download a snapshot archive of all persistent resources of CARL, which can be restored via `opendut-carl import`
+#[derive(clap::Parser)]
+pub struct ExportSnapshotCli {
+    /// Path of the archive to create, e.g. 'carl-snapshot.tar.gz'
+    file: PathBuf,
+}
+
+impl ExportSnapshotCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let archive = carl.administration.export_snapshot().await
+            .map_err(|error| format!("Could not export snapshot.\n  {error}"))?;
+
+        std::fs::write(&self.file, archive)
+            .map_err(|error| format!("Could not write snapshot to '{}'.\n  {error}", self.file.display()))?;
+
+        println!("Exported snapshot to '{}'.", self.file.display());
+        Ok(())
+    }
+}
@@ -1,18 +1,19 @@
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::peer::PeerId;
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
 
 /// Generate a Setup-String to setup a peer
 #[derive(clap::Parser)]
 pub struct GenerateSetupStringCli {
-    /// ID of the peer to generate a Setup-String for
+    /// ID or name of the peer to generate a Setup-String for
     #[arg()]
-    id: PeerId,
+    peer: PeerReference,
 }
 
 impl GenerateSetupStringCli {
 
     pub async fn execute(self, carl: &mut CarlClient, cleo_oidc_client_id: String,) -> crate::Result<()> {
-        let peer_id = self.id;
+        let peer_id = resolve_peer(carl, self.peer).await?;
 
         let created_setup = carl
             .peers
@@ -24,6 +25,9 @@ impl GenerateSetupStringCli {
             Ok(setup_string) => {
                 println!("{}", setup_string);
                 eprintln!("Setup-Strings may only be used to set up one host. For setting up multiple hosts, you should create a peer for each host.");
+                if let Some(setup_id) = created_setup.setup_id {
+                    eprintln!("The Setup-String has the ID <{setup_id}>. It can be revoked until it is used via: opendut-cleo revoke-setup-string {setup_id}");
+                }
             }
             Err(_) => {
                 println!("Could not encode setup string...")
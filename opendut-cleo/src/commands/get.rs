@@ -0,0 +1,320 @@
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::specs::parse::json::JsonSpecificationDocument;
+use opendut_types::specs::parse::yaml::{YamlSpecificationDocument, YamlSpecificationFile};
+use opendut_types::specs::SpecificationDocument;
+
+use crate::parse::reference::{ClusterReference, PeerReference};
+use crate::resolve::{resolve_cluster, resolve_peer};
+
+/// Print openDuT resources as specification documents, which can be passed to `apply` unchanged
+#[derive(clap::Parser)]
+pub struct GetCli {
+    #[command(subcommand)]
+    resource: GetResource,
+    ///YAML or JSON as output format. With JSON, each document is printed on a separate line.
+    #[arg(global=true, value_enum, short, long, default_value_t=SpecificationOutputFormat::Yaml)]
+    output: SpecificationOutputFormat,
+}
+
+#[derive(clap::Subcommand)]
+enum GetResource {
+    /// The peer with the given ID or name, or all peers
+    Peer {
+        /// ID or name of the peer
+        peer: Option<PeerReference>,
+    },
+    /// The cluster configuration with the given ID or name, or all cluster configurations
+    ClusterConfiguration {
+        /// ID or name of the cluster configuration
+        cluster: Option<ClusterReference>,
+    },
+    /// All peers and cluster configurations
+    All,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum SpecificationOutputFormat {
+    Yaml,
+    Json,
+}
+
+impl GetCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let documents = match self.resource {
+            GetResource::Peer { peer: Some(peer) } => {
+                let id = resolve_peer(carl, peer).await?;
+                let peer = carl.peers.get_peer_descriptor(id).await
+                    .map_err(|error| format!("Failed to get peer <{id}>.\n  {error}"))?;
+                vec![SpecificationDocument::from(peer)]
+            }
+            GetResource::Peer { peer: None } => {
+                get_all_peers(carl).await?
+            }
+            GetResource::ClusterConfiguration { cluster: Some(cluster) } => {
+                let id = resolve_cluster(carl, cluster).await?;
+                let configuration = carl.cluster.get_cluster_configuration(id).await
+                    .map_err(|error| format!("Failed to get cluster configuration <{id}>.\n  {error}"))?;
+                vec![SpecificationDocument::from(configuration)]
+            }
+            GetResource::ClusterConfiguration { cluster: None } => {
+                get_all_cluster_configurations(carl).await?
+            }
+            GetResource::All => {
+                let mut documents = get_all_peers(carl).await?;
+                documents.extend(get_all_cluster_configurations(carl).await?);
+                documents
+            }
+        };
+
+        print!("{}", render_documents(documents, self.output)?);
+        Ok(())
+    }
+}
+
+/// Sorted by ID, so that the output remains stable, e.g. when it is kept under version control.
+async fn get_all_peers(carl: &mut CarlClient) -> crate::Result<Vec<SpecificationDocument>> {
+    let mut peers = carl.peers.list_peer_descriptors().await
+        .map_err(|error| format!("Could not list peers.\n  {error}"))?;
+    peers.sort_by_key(|peer| peer.id.uuid);
+
+    Ok(peers.into_iter().map(SpecificationDocument::from).collect())
+}
+
+async fn get_all_cluster_configurations(carl: &mut CarlClient) -> crate::Result<Vec<SpecificationDocument>> {
+    let mut configurations = carl.cluster.list_cluster_configurations().await
+        .map_err(|error| format!("Could not list cluster configurations.\n  {error}"))?;
+    configurations.sort_by_key(|configuration| configuration.id.0);
+
+    Ok(configurations.into_iter().map(SpecificationDocument::from).collect())
+}
+
+pub(crate) fn render_documents(documents: Vec<SpecificationDocument>, format: SpecificationOutputFormat) -> crate::Result<String> {
+    match format {
+        SpecificationOutputFormat::Yaml => {
+            let documents = documents.into_iter()
+                .map(YamlSpecificationDocument::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|cause| format!("Failed to serialize specification as YAML: {cause}"))?;
+
+            YamlSpecificationFile { documents }.try_to_yaml_string()
+                .map_err(|cause| format!("Failed to serialize specification as YAML: {cause}"))
+        }
+        SpecificationOutputFormat::Json => {
+            let mut json = String::new();
+            for document in documents {
+                let document = JsonSpecificationDocument::try_from(document)
+                    .and_then(|document| serde_json::to_string(&document))
+                    .map_err(|cause| format!("Failed to serialize specification as JSON: {cause}"))?;
+                json.push_str(&document);
+                json.push('\n');
+            }
+            Ok(json)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+    use crate::commands::apply::{convert_documents_to_models, parse_json_document, parse_yaml_documents, KnownResources, ResourceModel};
+    use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+    use opendut_types::labels::{LabelSelector, Labels};
+    use opendut_types::peer::executor::container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine};
+    use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorDescriptors, ExecutorId, ExecutorKind, ResultsUrl};
+    use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, DeviceTag, Topology};
+    use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
+    use proptest::collection::{btree_map, hash_set, vec};
+    use proptest::option;
+    use proptest::prelude::*;
+    use uuid::Uuid;
+
+    proptest! {
+        #[test]
+        fn peer_descriptors_should_survive_a_round_trip_through_yaml(peer in peer_descriptor()) {
+            let yaml = render_documents(vec![SpecificationDocument::from(peer.clone())], SpecificationOutputFormat::Yaml)
+                .map_err(TestCaseError::fail)?;
+
+            let models = parse_yaml_models(&yaml)?;
+            prop_assert_eq!(models, vec![ResourceModel::PeerDescriptor(peer)]);
+        }
+
+        #[test]
+        fn cluster_configurations_should_survive_a_round_trip_through_yaml(configuration in cluster_configuration()) {
+            let yaml = render_documents(vec![SpecificationDocument::from(configuration.clone())], SpecificationOutputFormat::Yaml)
+                .map_err(TestCaseError::fail)?;
+
+            let models = parse_yaml_models(&yaml)?;
+            prop_assert_eq!(models, vec![ResourceModel::ClusterConfiguration(configuration)]);
+        }
+
+        #[test]
+        fn multiple_resources_should_survive_a_round_trip_through_one_yaml_file(
+            peers in vec(peer_descriptor(), 1..3),
+            configurations in vec(cluster_configuration(), 0..3),
+        ) {
+            let documents = peers.iter().cloned().map(SpecificationDocument::from)
+                .chain(configurations.iter().cloned().map(SpecificationDocument::from))
+                .collect::<Vec<_>>();
+            let yaml = render_documents(documents, SpecificationOutputFormat::Yaml)
+                .map_err(TestCaseError::fail)?;
+
+            let expected = peers.into_iter().map(ResourceModel::PeerDescriptor)
+                .chain(configurations.into_iter().map(ResourceModel::ClusterConfiguration))
+                .collect::<Vec<_>>();
+
+            let models = parse_yaml_models(&yaml)?;
+            prop_assert_eq!(models, expected);
+        }
+
+        #[test]
+        fn peer_descriptors_should_survive_a_round_trip_through_json(peer in peer_descriptor()) {
+            let json = render_documents(vec![SpecificationDocument::from(peer.clone())], SpecificationOutputFormat::Json)
+                .map_err(TestCaseError::fail)?;
+
+            let document = parse_json_document(json.trim_end()).map_err(TestCaseError::fail)?;
+            let models = convert_documents_to_models(vec![document], &KnownResources::default()).map_err(TestCaseError::fail)?;
+            prop_assert_eq!(models, vec![ResourceModel::PeerDescriptor(peer)]);
+        }
+    }
+
+    fn parse_yaml_models(yaml: &str) -> Result<Vec<ResourceModel>, TestCaseError> {
+        let documents = parse_yaml_documents(yaml).map_err(TestCaseError::fail)?;
+        convert_documents_to_models(documents, &KnownResources::default()).map_err(TestCaseError::fail)
+    }
+
+    fn uuid() -> impl Strategy<Value = Uuid> {
+        any::<u128>().prop_map(Uuid::from_u128)
+    }
+
+    fn name() -> impl Strategy<Value = String> {
+        "[a-z][a-z0-9]{3,15}"
+    }
+
+    fn text() -> impl Strategy<Value = String> {
+        "[a-zA-Z0-9 .,:-]{0,40}"
+    }
+
+    fn labels() -> impl Strategy<Value = Labels> {
+        btree_map(name(), "[a-z0-9._/-]{0,10}", 0..3)
+            .prop_map(|labels| Labels::try_from(labels).unwrap())
+    }
+
+    fn label_selector() -> impl Strategy<Value = LabelSelector> {
+        (name(), name())
+            .prop_map(|(key, value)| LabelSelector::from_str(&format!("{key}={value},!{value}")).unwrap())
+    }
+
+    fn network_interface() -> impl Strategy<Value = NetworkInterfaceDescriptor> {
+        let sample_point = (0..1000u32).prop_map(|sample_point| CanSamplePoint::try_from(sample_point).unwrap());
+
+        let configuration = prop_oneof![
+            Just(NetworkInterfaceConfiguration::Ethernet),
+            (1..=1_000_000u32, sample_point.clone(), any::<bool>(), 1..=8_000_000u32, sample_point)
+                .prop_map(|(bitrate, sample_point, fd, data_bitrate, data_sample_point)| {
+                    NetworkInterfaceConfiguration::Can {
+                        bitrate,
+                        sample_point,
+                        fd,
+                        data_bitrate,
+                        data_sample_point,
+                    }
+                }),
+        ];
+
+        (uuid(), "[a-z][a-z0-9]{0,14}", configuration)
+            .prop_map(|(id, name, configuration)| NetworkInterfaceDescriptor {
+                id: NetworkInterfaceId::from(id),
+                name: NetworkInterfaceName::try_from(name).unwrap(),
+                configuration,
+            })
+    }
+
+    fn device() -> impl Strategy<Value = DeviceDescriptor> {
+        (uuid(), name(), option::of(text()), uuid(), vec(name(), 0..3), labels())
+            .prop_map(|(id, name, description, interface, tags, labels)| DeviceDescriptor {
+                id: DeviceId::from(id),
+                name: DeviceName::try_from(name).unwrap(),
+                description: description.map(|description| DeviceDescription::try_from(description).unwrap()),
+                interface: NetworkInterfaceId::from(interface),
+                tags: tags.into_iter().map(|tag| DeviceTag::try_from(tag).unwrap()).collect(),
+                labels,
+            })
+    }
+
+    fn executor() -> impl Strategy<Value = ExecutorDescriptor> {
+        let container = (
+            prop_oneof![Just(Engine::Docker), Just(Engine::Podman)],
+            option::of(name()),
+            name(),
+            vec(name(), 0..3),
+            vec(name(), 0..3),
+            vec((name(), text()), 0..3),
+            vec("[0-9]{2,5}:[0-9]{2,5}", 0..3),
+            option::of(name()),
+            vec(name(), 0..3),
+        ).prop_map(|(engine, name, image, volumes, devices, envs, ports, command, args)| ExecutorKind::Container {
+            engine,
+            name: name.map(|name| ContainerName::try_from(name).unwrap()).unwrap_or(ContainerName::Empty),
+            image: ContainerImage::try_from(image).unwrap(),
+            volumes: volumes.into_iter().map(|volume| ContainerVolume::try_from(volume).unwrap()).collect(),
+            devices: devices.into_iter().map(|device| ContainerDevice::try_from(device).unwrap()).collect(),
+            envs: envs.into_iter().map(|(name, value)| ContainerEnvironmentVariable::new(name, value).unwrap()).collect(),
+            ports: ports.into_iter().map(|port| ContainerPortSpec::try_from(port).unwrap()).collect(),
+            command: command.map(|command| ContainerCommand::try_from(command).unwrap()).unwrap_or(ContainerCommand::Default),
+            args: args.into_iter().map(|arg| ContainerCommandArgument::try_from(arg).unwrap()).collect(),
+        });
+
+        let kind = prop_oneof![
+            Just(ExecutorKind::Executable),
+            container,
+        ];
+        let results_url = option::of(name().prop_map(|path| ResultsUrl::try_from(format!("https://example.com/{path}/")).unwrap()));
+
+        (uuid(), kind, results_url)
+            .prop_map(|(id, kind, results_url)| ExecutorDescriptor {
+                id: ExecutorId::from(id),
+                kind,
+                results_url,
+            })
+    }
+
+    fn peer_descriptor() -> impl Strategy<Value = PeerDescriptor> {
+        (
+            uuid(),
+            name(),
+            option::of(text()),
+            vec(network_interface(), 0..3),
+            option::of("[a-z][a-z0-9]{0,14}"),
+            vec(device(), 0..3),
+            vec(executor(), 0..3),
+            labels(),
+        ).prop_map(|(id, name, location, interfaces, bridge_name, devices, executors, labels)| PeerDescriptor {
+            id: PeerId::from(id),
+            name: PeerName::try_from(name).unwrap(),
+            location: location.map(|location| PeerLocation::try_from(location).unwrap()),
+            network: PeerNetworkDescriptor {
+                interfaces,
+                bridge_name: bridge_name.map(|bridge_name| NetworkInterfaceName::try_from(bridge_name).unwrap()),
+            },
+            topology: Topology { devices },
+            executors: ExecutorDescriptors { executors },
+            labels,
+            project: Default::default(),
+        })
+    }
+
+    fn cluster_configuration() -> impl Strategy<Value = ClusterConfiguration> {
+        (uuid(), name(), uuid(), hash_set(uuid(), 0..4), labels(), option::of(label_selector()))
+            .prop_map(|(id, name, leader, devices, labels, device_selector)| ClusterConfiguration {
+                id: ClusterId::from(id),
+                name: ClusterName::try_from(name).unwrap(),
+                leader: PeerId::from(leader),
+                devices: devices.into_iter().map(DeviceId::from).collect(),
+                labels,
+                device_selector,
+                project: Default::default(),
+            })
+    }
+}
@@ -1,12 +1,34 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
 pub mod apply;
+pub mod audit;
 pub mod cluster_configuration;
 pub mod cluster_deployment;
 pub mod device;
+pub mod diff;
 pub mod peer;
 pub mod network_interface;
+pub mod notification;
 pub mod executor;
+pub mod export_snapshot;
+pub mod get;
+pub mod query;
+pub mod reconcile_vpn;
+pub mod schema;
 pub mod decode_setup_string;
 pub mod generate_setup_string;
+pub mod revoke_setup_string;
+pub mod setup_string;
 pub mod completions;
 pub mod setup;
+pub mod token;
+pub mod tui;
 pub mod wait;
+pub mod watch;
+
+/// Formats a point in time as RFC 3339 timestamp in UTC, to the second.
+pub fn format_time(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
@@ -1,15 +1,16 @@
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::peer::PeerId;
 use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
 
 use crate::{CreateOutputFormat, DescribeOutputFormat, NetworkInterfaceType};
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
 
 /// Create a network interface
 #[derive(clap::Parser)]
 pub struct CreateNetworkInterfaceCli {
-    ///ID of the peer to add the network interface to
-    #[arg(long)]
-    peer_id: PeerId,
+    ///ID or name of the peer to add the network interface to
+    #[arg(long, alias="peer-id")]
+    peer: PeerReference,
     ///Type of the network interface
     #[arg(long("type"))]
     interface_type: NetworkInterfaceType,
@@ -19,9 +20,9 @@ pub struct CreateNetworkInterfaceCli {
 }
 impl CreateNetworkInterfaceCli {
     pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
-        let peer_id = self.peer_id;
+        let peer_id = resolve_peer(carl, self.peer).await?;
 
-        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+        let (mut peer_descriptor, revision) = carl.peers.get_peer_descriptor_with_revision(peer_id).await
             .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
 
         let peer_interface_names = peer_descriptor.network.interfaces
@@ -55,7 +56,7 @@ impl CreateNetworkInterfaceCli {
             );
         }
 
-        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+        carl.peers.store_peer_descriptor_if_unmodified(Clone::clone(&peer_descriptor), revision).await
             .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
         let output_format = DescribeOutputFormat::from(output);
         crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
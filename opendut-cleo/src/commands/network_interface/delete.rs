@@ -1,15 +1,16 @@
 use std::collections::HashMap;
 
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::peer::PeerId;
 use opendut_types::util::net::{NetworkInterfaceId, NetworkInterfaceName};
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
 
 /// Delete a network interface from a peer
 #[derive(clap::Parser)]
 pub struct DeleteNetworkInterfaceCli {
-    ///ID of the peer to delete the network configuration from
+    ///ID or name of the peer to delete the network configuration from
     #[arg()]
-    peer_id: PeerId,
+    peer: PeerReference,
     ///NetworkConfiguration Interface (at least one)
     #[arg(long("interface"), num_args = 1.., required = true)]
     interfaces: Vec<String>,
@@ -17,10 +18,10 @@ pub struct DeleteNetworkInterfaceCli {
 
 impl DeleteNetworkInterfaceCli {
     pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
-        let peer_id = self.peer_id;
+        let peer_id = resolve_peer(carl, self.peer).await?;
 
-        let mut peer = carl.peers
-            .get_peer_descriptor(peer_id)
+        let (mut peer, revision) = carl.peers
+            .get_peer_descriptor_with_revision(peer_id)
             .await
             .map_err(|error| format!("Failed to get peer with the id '{}'.\n  {}", peer_id, error))?;
 
@@ -53,7 +54,7 @@ impl DeleteNetworkInterfaceCli {
             peer.network.interfaces.retain(|interface| interface.id != interface_to_remove.id)
         };
 
-        carl.peers.store_peer_descriptor(peer).await
+        carl.peers.store_peer_descriptor_if_unmodified(peer, revision).await
             .map_err(|error| format!("Failed to delete network interfaces for peer.\n  {}", error))?;
 
         Ok(())
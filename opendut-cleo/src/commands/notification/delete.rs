@@ -0,0 +1,20 @@
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::notification::NotificationSubscriptionId;
+
+/// Delete a notification subscription, so that its target is not notified anymore
+#[derive(clap::Parser)]
+pub struct DeleteNotificationSubscriptionCli {
+    /// ID of the subscription, as printed when creating or listing it
+    #[arg()]
+    id: NotificationSubscriptionId,
+}
+
+impl DeleteNotificationSubscriptionCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let subscription = carl.notifications.delete_notification_subscription(self.id).await
+            .map_err(|error| format!("Could not delete notification subscription <{}>.\n  {error}", self.id))?;
+
+        println!("Deleted notification subscription '{}' <{}> of project '{}'.", subscription.name, subscription.id, subscription.project);
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use serde::Serialize;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::notification::{NotificationEventKind, NotificationSubscription, NotificationSubscriptionId};
+use opendut_types::project::ProjectId;
+
+use crate::ListOutputFormat;
+
+/// List the notification subscriptions of your projects
+#[derive(clap::Parser)]
+pub struct ListNotificationSubscriptionsCli {
+    /// Only list the subscriptions of this project
+    #[arg(long)]
+    project: Option<ProjectId>,
+    ///JSON, prettified JSON or table as output format
+    #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
+    output: ListOutputFormat,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub(super) struct SerializableNotificationSubscription {
+    id: NotificationSubscriptionId,
+    name: String,
+    project: ProjectId,
+    events: Vec<NotificationEventKind>,
+    selector: Option<String>,
+    target: String,
+}
+
+#[derive(Table)]
+struct NotificationSubscriptionTable {
+    #[table(title = "Name")]
+    name: String,
+    #[table(title = "SubscriptionID")]
+    id: NotificationSubscriptionId,
+    #[table(title = "Project")]
+    project: ProjectId,
+    #[table(title = "Events")]
+    events: String,
+    #[table(title = "Selector")]
+    selector: String,
+    #[table(title = "Target")]
+    target: String,
+}
+
+impl From<NotificationSubscription> for SerializableNotificationSubscription {
+    fn from(subscription: NotificationSubscription) -> Self {
+        SerializableNotificationSubscription {
+            id: subscription.id,
+            name: subscription.name.value(),
+            project: subscription.project,
+            events: subscription.events,
+            selector: subscription.selector.map(|selector| selector.to_string()),
+            target: subscription.target.to_string(),
+        }
+    }
+}
+
+impl From<SerializableNotificationSubscription> for NotificationSubscriptionTable {
+    fn from(subscription: SerializableNotificationSubscription) -> Self {
+        let SerializableNotificationSubscription { id, name, project, events, selector, target } = subscription;
+        let events = events.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        NotificationSubscriptionTable { name, id, project, events, selector: selector.unwrap_or_default(), target }
+    }
+}
+
+impl ListNotificationSubscriptionsCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let subscriptions = carl.notifications.list_notification_subscriptions(self.project).await
+            .map_err(|error| format!("Could not list notification subscriptions.\n  {error}"))?
+            .into_iter()
+            .map(SerializableNotificationSubscription::from)
+            .collect::<Vec<_>>();
+
+        match self.output {
+            ListOutputFormat::Table => {
+                let table = subscriptions.into_iter()
+                    .map(NotificationSubscriptionTable::from)
+                    .collect::<Vec<_>>();
+                print_stdout(table.with_title())
+                    .expect("List of notification subscriptions should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&subscriptions).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&subscriptions).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use googletest::prelude::*;
+    use url::Url;
+
+    use opendut_types::labels::LabelSelector;
+    use opendut_types::notification::{NotificationSubscriptionName, NotificationTarget};
+
+    use super::*;
+
+    #[test]
+    fn should_convert_subscription_for_output() -> anyhow::Result<()> {
+        let subscription = NotificationSubscription {
+            id: NotificationSubscriptionId::try_from("8d4e0c1a-3b7f-4f52-a0d9-1e6c5b2f9a37")?,
+            name: NotificationSubscriptionName::try_from("hil-bench")?,
+            project: ProjectId::try_from("chassis")?,
+            events: vec![NotificationEventKind::PeerOnline, NotificationEventKind::PeerOffline],
+            selector: Some(LabelSelector::from_str("bench=hil")?),
+            target: NotificationTarget::mqtt(Url::parse("mqtt://broker.example.com")?, String::from("opendut/chassis"))?,
+        };
+
+        let result = SerializableNotificationSubscription::from(subscription);
+        assert_that!(result.selector, some(eq("bench=hil")));
+        assert_that!(result.target, eq("mqtt://broker.example.com (opendut/chassis)"));
+
+        let table = NotificationSubscriptionTable::from(result);
+        assert_that!(table.events, eq("peer-online, peer-offline"));
+        Ok(())
+    }
+}
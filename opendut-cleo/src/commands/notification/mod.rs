@@ -0,0 +1,29 @@
+use opendut_carl_api::carl::CarlClient;
+
+pub mod create;
+pub mod delete;
+pub mod list;
+
+/// Manage subscriptions, which notify webhooks or MQTT brokers about events of your projects
+#[derive(clap::Parser)]
+pub struct NotificationCli {
+    #[command(subcommand)]
+    command: NotificationCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum NotificationCommand {
+    Create(create::CreateNotificationSubscriptionCli),
+    List(list::ListNotificationSubscriptionsCli),
+    Delete(delete::DeleteNotificationSubscriptionCli),
+}
+
+impl NotificationCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        match self.command {
+            NotificationCommand::Create(implementation) => implementation.execute(carl).await,
+            NotificationCommand::List(implementation) => implementation.execute(carl).await,
+            NotificationCommand::Delete(implementation) => implementation.execute(carl).await,
+        }
+    }
+}
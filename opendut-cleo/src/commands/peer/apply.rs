@@ -1,15 +1,26 @@
+use opendut_types::labels::Labels;
+use opendut_types::project::{ProjectAssignment, ProjectId};
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
 use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorDescriptors, ExecutorId, ExecutorKind, ResultsUrl};
 use opendut_types::peer::executor::container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine};
-use opendut_types::specs::peer::{DeviceSpecificationV1, NetworkInterfaceDescriptorSpecificationV1, NetworkInterfaceKind, PeerDescriptorSpecificationV1, ExecutorSpecificationV1, SpecificationEngineKind, SpecificationExecutorKind};
-use opendut_types::specs::SpecificationMetadata;
+use opendut_types::specs::peer::{DeviceSpecificationV2, ExecutorKindSpecificationV2, ExecutorSpecificationV2, NetworkInterfaceConfigurationSpecificationV2, NetworkInterfaceDescriptorSpecificationV2, PeerDescriptorSpecificationV2, SpecificationEngineKind};
+use opendut_types::specs::{ReferenceSpecification, SpecificationMetadata};
 use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, DeviceTag, Topology};
 use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
 
-pub fn convert_document_to_peer_descriptor(specification_metadata: SpecificationMetadata, peer: PeerDescriptorSpecificationV1) -> crate::Result<PeerDescriptor> {
+use crate::resolve::{find_by_name, resolve_by_name};
+
+/// Converts the specification into a peer descriptor.
+/// IDs, which are not specified, are taken from the `known_peers` by name, or generated, if no peer, interface or device with that name exists.
+pub fn convert_document_to_peer_descriptor(specification_metadata: SpecificationMetadata, peer: PeerDescriptorSpecificationV2, known_peers: &[PeerDescriptor]) -> crate::Result<PeerDescriptor> {
     let SpecificationMetadata { id, name } = specification_metadata;
 
-    let id = PeerId::from(id);
+    let id = match id {
+        Some(id) => PeerId::from(id),
+        None => find_by_name("peer", &name, known_peers.iter().map(|peer| (peer.id, peer.name.to_string())))?
+            .unwrap_or_else(PeerId::random),
+    };
+    let known_peer = known_peers.iter().find(|known_peer| known_peer.id == id);
 
     let name = PeerName::try_from(name)
         .map_err(|error| error.to_string())?;
@@ -18,9 +29,24 @@ pub fn convert_document_to_peer_descriptor(specification_metadata: Specification
         .map(PeerLocation::try_from)
         .transpose()
         .map_err(|error| error.to_string())?;
+
+    let labels = Labels::try_from(peer.labels)
+        .map_err(|error| format!("Invalid labels of peer '{name}': {error}"))?;
+
+    let project = ProjectAssignment {
+        owner: peer.project
+            .map(ProjectId::try_from)
+            .transpose()
+            .map_err(|error| format!("Invalid project of peer '{name}': {error}"))?
+            .unwrap_or_default(),
+        shared_with: peer.shared_with_projects.into_iter()
+            .map(ProjectId::try_from)
+            .collect::<Result<_, _>>()
+            .map_err(|error| format!("Invalid project of peer '{name}': {error}"))?,
+    };
     
     let network_interfaces = peer.network.interfaces.into_iter()
-        .map(convert_network_specification_to_descriptor)
+        .map(|interface| convert_network_specification_to_descriptor(interface, known_peer))
         .collect::<Result<Vec<_>, _>>()?;
     let bridge_name = peer.network.bridge_name
         .map(NetworkInterfaceName::try_from)
@@ -28,7 +54,7 @@ pub fn convert_document_to_peer_descriptor(specification_metadata: Specification
         .map_err(|error| error.to_string())?;
     
     let topology = peer.topology.devices.into_iter()
-        .map(convert_device_specification_to_descriptor)
+        .map(|device| convert_device_specification_to_descriptor(device, &network_interfaces, known_peer))
         .collect::<Result<Vec<_>, _>>()?;
 
     let executors = peer.executors.into_iter()
@@ -49,37 +75,45 @@ pub fn convert_document_to_peer_descriptor(specification_metadata: Specification
         executors: ExecutorDescriptors {
             executors,
         },
+        labels,
+        project,
     };
     Ok(descriptor)
 }
 
-fn convert_network_specification_to_descriptor(specification: NetworkInterfaceDescriptorSpecificationV1) -> crate::Result<NetworkInterfaceDescriptor> {
+fn convert_network_specification_to_descriptor(specification: NetworkInterfaceDescriptorSpecificationV2, known_peer: Option<&PeerDescriptor>) -> crate::Result<NetworkInterfaceDescriptor> {
     
     let name = NetworkInterfaceName::try_from(specification.name.clone())
         .map_err(|error| error.to_string())?;
+
+    let id = match specification.id {
+        Some(id) => NetworkInterfaceId::from(id),
+        None => {
+            let known_interfaces = known_peer.into_iter()
+                .flat_map(|peer| &peer.network.interfaces)
+                .map(|interface| (interface.id, interface.name.name()));
+            find_by_name("network interface", &specification.name, known_interfaces)?
+                .unwrap_or_else(NetworkInterfaceId::random)
+        }
+    };
     
-    let configuration = match specification.kind {
-        NetworkInterfaceKind::Ethernet => NetworkInterfaceConfiguration::Ethernet,
-        NetworkInterfaceKind::Can => {
-            match specification.parameters {
-                Some(parameters) => {
-                    NetworkInterfaceConfiguration::Can {
-                        bitrate: parameters.bitrate_kbps * 1000,
-                        sample_point: CanSamplePoint::try_from(parameters.sample_point)
-                            .map_err(|error| format!("Could not use the provided sample point parameter for network interface <{}>:  {}", specification.id, error))?,
-                        fd: parameters.fd,
-                        data_bitrate: parameters.data_bitrate_kbps * 1000,
-                        data_sample_point: CanSamplePoint::try_from(parameters.data_sample_point)
-                            .map_err(|error| format!("Could not use the provided data sample point parameter for network interface <{}>:  {}", specification.id, error))?,
-                    }
-                }
-                None => Err(String::from("Parameters for the can interface were not provided."))?,
+    let configuration = match specification.configuration {
+        NetworkInterfaceConfigurationSpecificationV2::Ethernet {} => NetworkInterfaceConfiguration::Ethernet,
+        NetworkInterfaceConfigurationSpecificationV2::Can(parameters) => {
+            NetworkInterfaceConfiguration::Can {
+                bitrate: parameters.bitrate,
+                sample_point: CanSamplePoint::try_from(parameters.sample_point)
+                    .map_err(|error| format!("Could not use the provided sample point parameter for network interface <{}>:  {}", id, error))?,
+                fd: parameters.fd,
+                data_bitrate: parameters.data_bitrate,
+                data_sample_point: CanSamplePoint::try_from(parameters.data_sample_point)
+                    .map_err(|error| format!("Could not use the provided data sample point parameter for network interface <{}>:  {}", id, error))?,
             }
         }
     };
 
     let network_descriptor = NetworkInterfaceDescriptor {
-        id: NetworkInterfaceId::from(specification.id),
+        id,
         name,
         configuration,
     };
@@ -87,90 +121,110 @@ fn convert_network_specification_to_descriptor(specification: NetworkInterfaceDe
     Ok(network_descriptor)
 }
 
-fn convert_device_specification_to_descriptor(specification: DeviceSpecificationV1) -> crate::Result<DeviceDescriptor> {
+fn convert_device_specification_to_descriptor(specification: DeviceSpecificationV2, interfaces: &[NetworkInterfaceDescriptor], known_peer: Option<&PeerDescriptor>) -> crate::Result<DeviceDescriptor> {
+    let id = match specification.id {
+        Some(id) => DeviceId::from(id),
+        None => {
+            let known_devices = known_peer.into_iter()
+                .flat_map(|peer| &peer.topology.devices)
+                .map(|device| (device.id, device.name.to_string()));
+            find_by_name("device", &specification.name, known_devices)?
+                .unwrap_or_else(DeviceId::random)
+        }
+    };
+
+    let interface = match specification.interface {
+        ReferenceSpecification::Id(id) => NetworkInterfaceId::from(id),
+        ReferenceSpecification::Name(interface_name) => {
+            let interfaces = interfaces.iter()
+                .map(|interface| (interface.id, interface.name.name()));
+            resolve_by_name("network interface", &interface_name, interfaces)
+                .map_err(|error| format!("Could not resolve the network interface of device <{}>: {}", id, error))?
+        }
+    };
+
     let tags = specification.tags.into_iter().map(|tag| 
         DeviceTag::try_from(tag)
-            .map_err(|error| format!("Could not apply the provided device tags for device: <{}>:  {}", specification.id, error))
+            .map_err(|error| format!("Could not apply the provided device tags for device: <{}>:  {}", id, error))
     ).collect::<Result<Vec<_>, _>>()?;
     
     let description = specification.description
         .map(DeviceDescription::try_from)
         .transpose()
         .map_err(|error| error.to_string())?;
+
+    let labels = Labels::try_from(specification.labels)
+        .map_err(|error| format!("Invalid labels of device <{}>: {}", id, error))?;
     
     let device_descriptor = DeviceDescriptor {
-        id: DeviceId::from(specification.id),
+        id,
         name: DeviceName::try_from(specification.name)
-            .map_err(|error| format!("Could not apply the provided device name for device: <{}>:  {}", specification.id, error))?,
+            .map_err(|error| format!("Could not apply the provided device name for device: <{}>:  {}", id, error))?,
         description,
-        interface: NetworkInterfaceId::from(specification.interface_id),
+        interface,
         tags,
+        labels,
     };
     
     Ok(device_descriptor)
 }
 
-fn convert_executor_specification_to_descriptor(specification: ExecutorSpecificationV1) -> crate::Result<ExecutorDescriptor> {
+fn convert_executor_specification_to_descriptor(specification: ExecutorSpecificationV2) -> crate::Result<ExecutorDescriptor> {
     let results_url = specification.results_url
         .map(ResultsUrl::try_from)
         .transpose()
         .map_err(| error | format!("Could not apply the provided results url for the executor <{}>: {}", specification.id, error))?;
 
     let kind = match specification.kind {
-        SpecificationExecutorKind::Executable => ExecutorKind::Executable,
-        SpecificationExecutorKind::Container => {
-            match specification.parameters {
-                Some(parameters) => {
-                    let engine = match parameters.engine {
-                        SpecificationEngineKind::Docker => Engine::Docker,
-                        SpecificationEngineKind::Podman => Engine::Podman,
-                    };
-                    let name = parameters.name
-                        .map(ContainerName::try_from)
-                        .transpose()
-                        .map_err(| error | format!("Could not apply the provided container name for the executor <{}>: {}", specification.id, error))?
-                        .unwrap_or(ContainerName::Empty);
-                    let image = ContainerImage::try_from(parameters.image)
-                            .map_err(|error| format!("Could not use the provided container image parameter for container executor <{}>:  {}", specification.id, error))?;
-                    let volumes = parameters.volumes.into_iter().map(|volume| 
-                        ContainerVolume::try_from(volume)
-                            .map_err(|error| format!("Could not apply the provided container volumes for container executor <{}>:  {}", specification.id, error))
-                    ).collect::<Result<Vec<_>, _>>()?;
-                    let devices = parameters.devices.into_iter().map(|device|
-                        ContainerDevice::try_from(device)
-                            .map_err(|error| format!("Could not apply the provided container devices for container executor <{}>: {}", specification.id, error))
-                    ).collect::<Result<Vec<_>, _>>()?;
-                    let envs = parameters.envs.into_iter().map(|envs|
-                        ContainerEnvironmentVariable::new(envs.name, envs.value)
-                            .map_err(|error| format!("Could not apply the provided container environment variables for container executor <{}>: {}", specification.id, error))
-                    ).collect::<Result<Vec<_>, _>>()?;
-                    let ports = parameters.ports.into_iter().map(|port|
-                        ContainerPortSpec::try_from(port)  
-                            .map_err(|error| format!("Could not use the provided container port parameter for container executor <{}>:  {}", specification.id, error))
-                    ).collect::<Result<Vec<_>, _>>()?;
-                    let command =  parameters.command
-                        .map(ContainerCommand::try_from)
-                        .transpose()
-                        .map_err(| error | format!("Could not apply the provided container command for the executor <{}>: {}", specification.id, error))?
-                        .unwrap_or(ContainerCommand::Default);
-                    let args = parameters.command_args.into_iter().map(|arg|
-                    ContainerCommandArgument::try_from(arg)
-                        .map_err(|error| format!("Could not use the provided container command arguments parameter for container executor <{}>: {}", specification.id, error))
-                    ).collect::<Result<Vec<_>, _>>()?;
-                    
-                    ExecutorKind::Container {
-                        engine,
-                        name,
-                        image,
-                        volumes,
-                        devices,
-                        envs,
-                        ports,
-                        command,
-                        args,
-                    }
-                }
-                None => Err(String::from("Parameters for the container executor were not provided."))?,
+        ExecutorKindSpecificationV2::Executable {} => ExecutorKind::Executable,
+        ExecutorKindSpecificationV2::Container(parameters) => {
+            let engine = match parameters.engine {
+                SpecificationEngineKind::Docker => Engine::Docker,
+                SpecificationEngineKind::Podman => Engine::Podman,
+            };
+            let name = parameters.name
+                .map(ContainerName::try_from)
+                .transpose()
+                .map_err(| error | format!("Could not apply the provided container name for the executor <{}>: {}", specification.id, error))?
+                .unwrap_or(ContainerName::Empty);
+            let image = ContainerImage::try_from(parameters.image)
+                    .map_err(|error| format!("Could not use the provided container image parameter for container executor <{}>:  {}", specification.id, error))?;
+            let volumes = parameters.volumes.into_iter().map(|volume| 
+                ContainerVolume::try_from(volume)
+                    .map_err(|error| format!("Could not apply the provided container volumes for container executor <{}>:  {}", specification.id, error))
+            ).collect::<Result<Vec<_>, _>>()?;
+            let devices = parameters.devices.into_iter().map(|device|
+                ContainerDevice::try_from(device)
+                    .map_err(|error| format!("Could not apply the provided container devices for container executor <{}>: {}", specification.id, error))
+            ).collect::<Result<Vec<_>, _>>()?;
+            let envs = parameters.envs.into_iter().map(|envs|
+                ContainerEnvironmentVariable::new(envs.name, envs.value)
+                    .map_err(|error| format!("Could not apply the provided container environment variables for container executor <{}>: {}", specification.id, error))
+            ).collect::<Result<Vec<_>, _>>()?;
+            let ports = parameters.ports.into_iter().map(|port|
+                ContainerPortSpec::try_from(port)  
+                    .map_err(|error| format!("Could not use the provided container port parameter for container executor <{}>:  {}", specification.id, error))
+            ).collect::<Result<Vec<_>, _>>()?;
+            let command =  parameters.command
+                .map(ContainerCommand::try_from)
+                .transpose()
+                .map_err(| error | format!("Could not apply the provided container command for the executor <{}>: {}", specification.id, error))?
+                .unwrap_or(ContainerCommand::Default);
+            let args = parameters.command_args.into_iter().map(|arg|
+            ContainerCommandArgument::try_from(arg)
+                .map_err(|error| format!("Could not use the provided container command arguments parameter for container executor <{}>: {}", specification.id, error))
+            ).collect::<Result<Vec<_>, _>>()?;
+            
+            ExecutorKind::Container {
+                engine,
+                name,
+                image,
+                volumes,
+                devices,
+                envs,
+                ports,
+                command,
+                args,
             }
         }
     };
@@ -191,7 +245,7 @@ mod tests {
     use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorId, ExecutorKind, ResultsUrl};
     use opendut_types::peer::executor::container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine};
     use opendut_types::peer::executor::container::ContainerName::Empty;
-    use opendut_types::specs::peer::{DeviceSpecificationV1, ExecutorConfigurationSpecification, NetworkInterfaceConfigurationSpecification, NetworkInterfaceDescriptorSpecificationV1, NetworkInterfaceKind, TopologySpecificationV1, ExecutorSpecificationV1, NetworkDescriptorSpecificationV1, SpecificationEngineKind, SpecificationExecutorKind, SpecificationEnvVariable};
+    use opendut_types::specs::peer::{CanInterfaceSpecificationV2, NetworkDescriptorSpecificationV2, TopologySpecificationV2, DeviceSpecificationV1, ExecutorConfigurationSpecification, NetworkInterfaceDescriptorSpecificationV1, NetworkInterfaceKind, PeerDescriptorSpecificationV1, TopologySpecificationV1, ExecutorSpecificationV1, NetworkDescriptorSpecificationV1, SpecificationEngineKind, SpecificationExecutorKind, SpecificationEnvVariable};
     use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, DeviceTag};
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
 
@@ -200,7 +254,7 @@ mod tests {
         let peer = generate_peer_descriptor()?;
         
         let specification_metadata = SpecificationMetadata {
-            id: peer.id.uuid,
+            id: Some(peer.id.uuid),
             name: peer.name.clone().value(),
         };
         
@@ -227,7 +281,7 @@ mod tests {
             executors,
         };
 
-        let result = convert_document_to_peer_descriptor(specification_metadata, specification_peer).unwrap();
+        let result = convert_document_to_peer_descriptor(specification_metadata, PeerDescriptorSpecificationV2::try_from(specification_peer)?, &[]).unwrap();
 
         assert_that!(result, eq(&peer));
 
@@ -236,31 +290,20 @@ mod tests {
     
     #[test]
     fn should_convert_ethernet_network_specification_to_descriptor() -> anyhow::Result<()> {
-        let specification =  NetworkInterfaceDescriptorSpecificationV1 {
-            id: NetworkInterfaceId::random().uuid,
+        let id = NetworkInterfaceId::random();
+        let specification =  NetworkInterfaceDescriptorSpecificationV2 {
+            id: Some(id.uuid),
             name: "eth0".to_string(),
-            kind: NetworkInterfaceKind::Ethernet,
-            parameters: None,
-        };
-        
-        let configuration = match specification.kind {
-            NetworkInterfaceKind::Ethernet => NetworkInterfaceConfiguration::Ethernet,
-            NetworkInterfaceKind::Can => NetworkInterfaceConfiguration::Can {
-                bitrate: 0,
-                sample_point: CanSamplePoint::try_from(0.7)?,
-                fd: false,
-                data_bitrate: 0,
-                data_sample_point: CanSamplePoint::try_from(0.7)?,
-            }
+            configuration: NetworkInterfaceConfigurationSpecificationV2::Ethernet {},
         };
         
         let descriptor = NetworkInterfaceDescriptor {
-            id: NetworkInterfaceId::from(specification.id),
+            id,
             name: NetworkInterfaceName::try_from(specification.name.clone())?,
-            configuration,
+            configuration: NetworkInterfaceConfiguration::Ethernet,
         };
         
-        let result = convert_network_specification_to_descriptor(specification).unwrap();
+        let result = convert_network_specification_to_descriptor(specification, None).unwrap();
         
         assert_that!(result, eq(&descriptor));
         Ok(())
@@ -268,23 +311,23 @@ mod tests {
    
     #[test]
     fn should_convert_can_network_specification_to_descriptor() -> anyhow::Result<()> {
-        let specification = NetworkInterfaceDescriptorSpecificationV1 {
-            id: NetworkInterfaceId::random().uuid,
+        let id = NetworkInterfaceId::random();
+        let specification = NetworkInterfaceDescriptorSpecificationV2 {
+            id: Some(id.uuid),
             name: "can0".to_string(),
-            kind: NetworkInterfaceKind::Can,
-            parameters: Some(
-                NetworkInterfaceConfigurationSpecification {
-                    bitrate_kbps: 500,
+            configuration: NetworkInterfaceConfigurationSpecificationV2::Can(
+                CanInterfaceSpecificationV2 {
+                    bitrate: 500000,
                     sample_point: 0.7,
                     fd: true,
-                    data_bitrate_kbps: 2000,
+                    data_bitrate: 2000000,
                     data_sample_point: 0.7,
                 }
             ),
         };
 
         let descriptor = NetworkInterfaceDescriptor {
-            id: NetworkInterfaceId::from(specification.id),
+            id,
             name: NetworkInterfaceName::try_from(specification.name.clone())?,
             configuration: NetworkInterfaceConfiguration::Can {
                 bitrate: 500000,
@@ -295,7 +338,7 @@ mod tests {
             },
         };
 
-        let result = convert_network_specification_to_descriptor(specification).unwrap();
+        let result = convert_network_specification_to_descriptor(specification, None).unwrap();
         
         assert_that!(result, eq(&descriptor));
         Ok(())
@@ -303,14 +346,17 @@ mod tests {
 
     #[test]
     fn should_convert_device_specification_to_descriptor() -> anyhow::Result<()> {
-        let specification = DeviceSpecificationV1 {
-            id: DeviceId::random().0,
+        let id = DeviceId::random();
+        let interface = NetworkInterfaceId::random();
+        let specification = DeviceSpecificationV2 {
+            id: Some(id.0),
             name: "device".to_string(),
             description: None,
-            interface_id: NetworkInterfaceId::random().uuid,
+            interface: ReferenceSpecification::Id(interface.uuid),
             tags: vec![
                 String::from("new")
             ],
+            labels: [(String::from("team"), String::from("chassis"))].into(),
         };
 
         let tags = specification.tags.iter()
@@ -318,18 +364,95 @@ mod tests {
             .collect();
         
         let descriptor = DeviceDescriptor {
-            id: DeviceId::from(specification.id),
+            id,
             name: DeviceName::try_from(specification.name.clone())?,
             description: specification.description.clone().map(|d| DeviceDescription::try_from(d).unwrap()),
-            interface: NetworkInterfaceId::from(specification.interface_id),
+            interface,
             tags,
+            labels: Labels::try_from(specification.labels.clone())?,
         };
         
-        let result = convert_device_specification_to_descriptor(specification).unwrap();
+        let result = convert_device_specification_to_descriptor(specification, &[], None).unwrap();
         
         assert_that!(result, eq(&descriptor));
         Ok(())
     }
+
+    #[test]
+    fn should_take_ids_of_known_peer_by_name_and_resolve_interface_by_name() -> anyhow::Result<()> {
+        let known_peer = generate_peer_descriptor()?;
+        let known_interface = &known_peer.network.interfaces[0];
+        let known_device = &known_peer.topology.devices[0];
+
+        let specification = PeerDescriptorSpecificationV2 {
+            location: None,
+            labels: Default::default(),
+            network: NetworkDescriptorSpecificationV2 {
+                interfaces: vec![
+                    NetworkInterfaceDescriptorSpecificationV2 {
+                        id: None,
+                        name: known_interface.name.name(),
+                        configuration: NetworkInterfaceConfigurationSpecificationV2::Ethernet {},
+                    }
+                ],
+                bridge_name: None,
+            },
+            topology: TopologySpecificationV2 {
+                devices: vec![
+                    DeviceSpecificationV2 {
+                        id: None,
+                        name: known_device.name.to_string(),
+                        description: None,
+                        interface: ReferenceSpecification::Name(known_interface.name.name()),
+                        tags: vec![],
+                        labels: Default::default(),
+                    },
+                    DeviceSpecificationV2 {
+                        id: None,
+                        name: String::from("new-device"),
+                        description: None,
+                        interface: ReferenceSpecification::Name(known_interface.name.name()),
+                        tags: vec![],
+                        labels: Default::default(),
+                    },
+                ],
+            },
+            executors: vec![],
+            project: None,
+            shared_with_projects: Vec::new(),
+        };
+        let metadata = SpecificationMetadata {
+            id: None,
+            name: known_peer.name.to_string(),
+        };
+
+        let result = convert_document_to_peer_descriptor(metadata, specification, &[known_peer.clone()]).unwrap();
+
+        assert_that!(result.id, eq(known_peer.id));
+        assert_that!(result.network.interfaces[0].id, eq(known_interface.id));
+        assert_that!(result.topology.devices[0].id, eq(known_device.id));
+        assert_that!(result.topology.devices[0].interface, eq(known_interface.id));
+        assert_that!(result.topology.devices[1].id, not(eq(known_device.id)));
+        assert_that!(result.topology.devices[1].interface, eq(known_interface.id));
+        Ok(())
+    }
+
+    #[test]
+    fn should_fail_to_resolve_unknown_interface_name() -> anyhow::Result<()> {
+        let specification = DeviceSpecificationV2 {
+            id: None,
+            name: String::from("device"),
+            description: None,
+            interface: ReferenceSpecification::Name(String::from("can0")),
+            tags: vec![],
+            labels: Default::default(),
+        };
+
+        let error = convert_device_specification_to_descriptor(specification, &[], None).unwrap_err();
+
+        assert_that!(error, contains_substring("Could not find a network interface with the name 'can0'."));
+        Ok(())
+    }
     
     fn generate_peer_descriptor() -> anyhow::Result<PeerDescriptor> {
         Ok(PeerDescriptor {
@@ -356,6 +479,7 @@ mod tests {
                         tags: vec![
                             DeviceTag::try_from("first-device")?
                         ],
+                        labels: Default::default(),
                     }
                 ],
             },
@@ -390,6 +514,8 @@ mod tests {
                     results_url: Some(ResultsUrl::try_from("https://example.com/webdav/results/")?),
                 }
             ] },
+            labels: Default::default(),
+            project: Default::default(),
         })
     }
     
@@ -4,6 +4,7 @@ use crate::{CreateOutputFormat};
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
 use opendut_types::peer::executor::{ExecutorDescriptors};
+use opendut_types::project::{ProjectAssignment, ProjectId};
 use opendut_types::util::net::NetworkInterfaceName;
 
 /// Create a peer
@@ -23,6 +24,12 @@ pub struct CreatePeerCli {
     /// Not removing the bridge could lead to network traffic being misdirected!
     #[arg(long)]
     bridge_name: Option<NetworkInterfaceName>,
+    ///Project the peer belongs to
+    #[arg(long, default_value_t)]
+    project: ProjectId,
+    ///Further projects, which may use the peer in their clusters, but not modify it
+    #[arg(long, value_delimiter = ',')]
+    shared_with_projects: Vec<ProjectId>,
 }
 
 impl CreatePeerCli {
@@ -44,7 +51,12 @@ impl CreatePeerCli {
             topology: Default::default(),
             executors: ExecutorDescriptors {
                 executors: vec![],
-            }
+            },
+            labels: Default::default(),
+            project: ProjectAssignment {
+                owner: self.project,
+                shared_with: self.shared_with_projects.into_iter().collect(),
+            },
         };
 
         create_peer(descriptor, carl, &output).await?;
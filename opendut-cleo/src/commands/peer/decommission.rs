@@ -0,0 +1,140 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use serde::Serialize;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::decommission::{PeerDecommissionReport, PeerDecommissionStep};
+
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
+use crate::ListOutputFormat;
+
+/// Revoke the Setup-Strings, OIDC clients, VPN access and stream of a peer, e.g. when its host is retired or compromised.
+/// The peer itself is kept and can be set up again with a new Setup-String.
+#[derive(clap::Parser)]
+pub struct DecommissionPeerCli {
+    /// ID or name of the peer
+    #[arg()]
+    peer: PeerReference,
+    ///JSON, prettified JSON or table as output format
+    #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
+    output: ListOutputFormat,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct SerializableReport {
+    peer_id: String,
+    complete: bool,
+    steps: Vec<SerializableStep>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct SerializableStep {
+    action: String,
+    outcome: String,
+}
+
+#[derive(Table)]
+struct StepTable {
+    #[table(title = "Action")]
+    action: String,
+    #[table(title = "Outcome")]
+    outcome: String,
+}
+
+impl From<PeerDecommissionReport> for SerializableReport {
+    fn from(report: PeerDecommissionReport) -> Self {
+        SerializableReport {
+            peer_id: report.peer_id.to_string(),
+            complete: report.is_complete(),
+            steps: report.steps.into_iter().map(SerializableStep::from).collect(),
+        }
+    }
+}
+
+impl From<PeerDecommissionStep> for SerializableStep {
+    fn from(step: PeerDecommissionStep) -> Self {
+        SerializableStep {
+            action: step.action.to_string(),
+            outcome: step.outcome.to_string(),
+        }
+    }
+}
+
+impl From<SerializableStep> for StepTable {
+    fn from(step: SerializableStep) -> Self {
+        let SerializableStep { action, outcome } = step;
+        StepTable { action, outcome }
+    }
+}
+
+impl DecommissionPeerCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = resolve_peer(carl, self.peer).await?;
+
+        let report = carl.peers.decommission_peer(id).await
+            .map_err(|error| format!("Failed to decommission peer <{id}>.\n  {error}"))?;
+
+        let report = SerializableReport::from(report);
+        let complete = report.complete;
+
+        match self.output {
+            ListOutputFormat::Table => {
+                let table = report.steps.into_iter()
+                    .map(StepTable::from)
+                    .collect::<Vec<_>>();
+                print_stdout(table.with_title())
+                    .expect("List of decommissioning steps should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&report).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&report).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        if complete {
+            eprintln!("Decommissioned peer <{id}>.");
+            Ok(())
+        } else {
+            Err(format!("Decommissioning of peer <{id}> is incomplete. Run the command again to retry the failed steps."))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_types::peer::PeerId;
+    use opendut_types::peer::decommission::{PeerDecommissionAction, PeerDecommissionOutcome};
+
+    use super::*;
+
+    #[test]
+    fn should_convert_decommission_report_for_output() -> anyhow::Result<()> {
+        let peer_id = PeerId::try_from("5c806e1c-448e-4dda-854a-20a33cfe1cfe")?;
+        let report = PeerDecommissionReport {
+            peer_id,
+            steps: vec![
+                PeerDecommissionStep { action: PeerDecommissionAction::DeleteOidcClients, outcome: PeerDecommissionOutcome::Done },
+                PeerDecommissionStep { action: PeerDecommissionAction::DeleteVpnPeer, outcome: PeerDecommissionOutcome::Failed { cause: String::from("NetBird unreachable") } },
+            ],
+        };
+
+        assert_that!(
+            SerializableReport::from(report),
+            eq(&SerializableReport {
+                peer_id: String::from("5c806e1c-448e-4dda-854a-20a33cfe1cfe"),
+                complete: false,
+                steps: vec![
+                    SerializableStep { action: String::from("Delete OIDC clients"), outcome: String::from("done") },
+                    SerializableStep { action: String::from("Delete VPN peer"), outcome: String::from("failed: NetBird unreachable") },
+                ],
+            })
+        );
+        Ok(())
+    }
+}
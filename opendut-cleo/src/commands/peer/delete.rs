@@ -3,24 +3,25 @@ use std::ops::Not;
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::cluster::ClusterId;
-use opendut_types::peer::PeerId;
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
 
 /// Delete a peer
 #[derive(clap::Parser)]
 pub struct DeletePeerCli {
-    /// ID of the peer
+    /// ID or name of the peer
     #[arg()]
-    id: PeerId,
+    peer: PeerReference,
 }
 
 impl DeletePeerCli {
     pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
-        let id = self.id;
+        let id = resolve_peer(carl, self.peer).await?;
 
-        { //block deleting, if device is used in cluster
-            let peer_descriptor = carl.peers.get_peer_descriptor(id).await
-                .map_err(|error| format!("Failed to get peer descriptor for peer: {}.\n {}", id, error))?;
+        let (peer_descriptor, revision) = carl.peers.get_peer_descriptor_with_revision(id).await
+            .map_err(|error| format!("Failed to get peer descriptor for peer: {}.\n {}", id, error))?;
 
+        { //block deleting, if device is used in cluster
             let peer_device_ids = peer_descriptor.topology.devices.into_iter().map(|descriptor| descriptor.id).collect::<Vec<_>>();
 
             let clusters = carl.cluster
@@ -43,7 +44,7 @@ impl DeletePeerCli {
         }
         
         carl.peers
-            .delete_peer_descriptor(id)
+            .delete_peer_descriptor_if_unmodified(id, revision)
             .await
             .map_err(|error| format!("Failed to delete peer with the id '{}'.\n  {}", id, error))?;
         println!("Deleted peer with the PeerID: {}", id);
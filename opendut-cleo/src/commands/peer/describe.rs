@@ -1,20 +1,22 @@
 use indoc::indoc;
 
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::PeerDescriptor;
 use crate::DescribeOutputFormat;
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
 
 /// Describe a peer
 #[derive(clap::Parser)]
 pub struct DescribePeerCli {
-    /// ID of the peer
+    /// ID or name of the peer
     #[arg()]
-    id: PeerId,
+    peer: PeerReference,
 }
 
 impl DescribePeerCli {
     pub async fn execute(self, carl: &mut CarlClient, output: DescribeOutputFormat) -> crate::Result<()> {
-        let peer_id = self.id;
+        let peer_id = resolve_peer(carl, self.peer).await?;
         
         let peer_descriptor =
             carl.peers.get_peer_descriptor(peer_id).await.map_err(|_| {
@@ -6,11 +6,18 @@ use serde::Serialize;
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName};
 use opendut_types::peer::state::{PeerConnectionState, PeerState};
+use opendut_types::project::ProjectId;
+use crate::commands::query::{ListQueryArgs, ProjectFilterArgs};
 use crate::ListOutputFormat;
 
 /// List all peers
 #[derive(clap::Parser)]
-pub struct ListPeersCli;
+pub struct ListPeersCli {
+    #[command(flatten)]
+    query: ListQueryArgs,
+    #[command(flatten)]
+    project: ProjectFilterArgs,
+}
 
 #[derive(Serialize, Debug)]
 struct SerializablePeer {
@@ -18,6 +25,7 @@ struct SerializablePeer {
     id: PeerId,
     status: PeerStatus,
     location: PeerLocation,
+    project: ProjectId,
     network_interfaces: Vec<String>,
 }
 
@@ -31,18 +39,21 @@ struct PeerTable {
     status: PeerStatus,
     #[table(title = "Location")]
     location: PeerLocation,
+    #[table(title = "Project")]
+    project: ProjectId,
     #[table(title = "NetworkInterfaces")]
     network_interfaces: String,
 }
 impl From<SerializablePeer> for PeerTable {
     fn from(peer: SerializablePeer) -> Self {
-        let SerializablePeer { name, id, status, location, network_interfaces } = peer;
+        let SerializablePeer { name, id, status, location, project, network_interfaces } = peer;
 
         PeerTable {
             name,
             id,
             status,
             location,
+            project,
             network_interfaces: network_interfaces.join(", "),
         }
     }
@@ -77,15 +88,16 @@ impl ListPeersCli {
     pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
         let all_peer_descriptors = carl
             .peers
-            .list_peer_descriptors()
+            .query_peer_descriptors(self.project.apply(self.query.into_query()))
             .await
-            .map_err(|error| format!("Could not list peers.\n  {}", error))?;
+            .map_err(|error| format!("Could not list peers.\n  {}", error))?
+            .items;
         let all_peer_states = carl.peers.list_peer_states().await
             .map_err(|_| "Failed to list peer states!")?;
         
         let serializable_peers = all_peer_descriptors
             .into_iter()
-            .map(|peer| {
+            .map(|(peer, _)| {
                 let peer_state = all_peer_states.get(&peer.id).cloned().unwrap_or_default();
                 add_peer_status(peer, peer_state)
             })
@@ -125,6 +137,7 @@ fn add_peer_status(
         name: Clone::clone(&peer.name),
         id: peer.id,
         location: Clone::clone(&peer.location.clone().unwrap_or_default()),
+        project: peer.project.owner,
         network_interfaces,
         status
     }
@@ -157,7 +170,9 @@ mod test {
             topology: Default::default(),
             executors: ExecutorDescriptors {
                 executors: vec![]
-            }
+            },
+            labels: Default::default(),
+            project: Default::default(),
         };
         assert_that!(
             add_peer_status(peer.clone(), PeerState::default()),
@@ -3,3 +3,5 @@ pub mod describe;
 pub mod create;
 pub mod delete;
 pub mod apply;
+pub mod decommission;
+pub mod rotate_credentials;
@@ -0,0 +1,29 @@
+use opendut_carl_api::carl::CarlClient;
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
+
+/// Issue new OIDC client credentials and a new client certificate for a connected peer and send them to its EDGAR, without setting it up again
+#[derive(clap::Parser)]
+pub struct RotatePeerCredentialsCli {
+    /// ID or name of the peer
+    #[arg()]
+    peer: PeerReference,
+}
+
+impl RotatePeerCredentialsCli {
+    pub async fn execute(self, carl: &mut CarlClient, cleo_oidc_client_id: String) -> crate::Result<()> {
+        let id = resolve_peer(carl, self.peer).await?;
+
+        let rotated = carl.peers.rotate_peer_credentials(id, cleo_oidc_client_id).await
+            .map_err(|error| format!("Failed to rotate credentials of peer <{id}>.\n  {error}"))?;
+
+        println!("Rotated credentials of peer <{id}>.");
+        if let Some(client_id) = rotated.client_id {
+            println!("EDGAR uses the OIDC client <{}> the next time it connects.", client_id.value());
+        }
+        if let Some(client_certificate_id) = rotated.client_certificate_id {
+            println!("EDGAR uses the client certificate <{client_certificate_id}> the next time it connects. Previous client certificates were revoked.");
+        }
+        Ok(())
+    }
+}
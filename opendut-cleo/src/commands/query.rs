@@ -0,0 +1,55 @@
+use std::str::FromStr;
+
+use opendut_types::labels::LabelSelector;
+use opendut_types::project::ProjectId;
+use opendut_types::query::{Filter, ListQuery, Predicate, SortKey};
+
+/// Arguments for filtering, sorting and limiting the listed resources. These are evaluated in CARL.
+#[derive(clap::Args)]
+pub struct ListQueryArgs {
+    ///Only list resources matching all comma-separated predicates, e.g. 'name=edgar-*,state=online'. Use '!=' to negate a predicate.
+    #[arg(long)]
+    filter: Option<Filter>,
+    ///Only list resources with labels matching all comma-separated requirements, e.g. 'team=chassis,site!=munich'. Use 'key' or '!key' to require a label to exist or not exist.
+    #[arg(long, short = 'l')]
+    selector: Option<LabelSelector>,
+    ///Sort by the comma-separated fields, each optionally prefixed with '-' for descending order, e.g. 'location,-name'
+    #[arg(long, value_delimiter = ',')]
+    sort: Vec<SortKey>,
+    ///Maximum number of resources to list
+    #[arg(long)]
+    limit: Option<u32>,
+}
+
+impl ListQueryArgs {
+    pub fn into_query(self) -> ListQuery {
+        ListQuery {
+            filter: self.filter,
+            selector: self.selector,
+            sort: self.sort,
+            page_size: self.limit,
+            page_token: None,
+        }
+    }
+}
+
+/// Argument for listing only the resources of a project.
+#[derive(clap::Args)]
+pub struct ProjectFilterArgs {
+    ///Only list resources, which belong to or are shared with this project. Shorthand for '--filter project=<PROJECT>'
+    #[arg(long)]
+    project: Option<ProjectId>,
+}
+
+impl ProjectFilterArgs {
+    pub fn apply(self, mut query: ListQuery) -> ListQuery {
+        if let Some(project) = self.project {
+            let predicate = Predicate::from_str(&format!("project={project}"))
+                .expect("Project ID should be a valid pattern.");
+            let mut filter = query.filter.unwrap_or(Filter { predicates: Vec::new() });
+            filter.predicates.push(predicate);
+            query.filter = Some(filter);
+        }
+        query
+    }
+}
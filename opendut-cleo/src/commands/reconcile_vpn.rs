@@ -0,0 +1,140 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use cli_table::{print_stdout, Table, WithTitle};
+use serde::Serialize;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::vpn::reconciliation::{VpnMismatch, VpnReconciliationFinding, VpnReconciliationReport};
+
+use crate::ListOutputFormat;
+
+/// Compare the VPN with the registered peers and deployed clusters, and repair mismatches
+#[derive(clap::Parser)]
+pub struct ReconcileVpnCli {
+    /// Only show the report of the last periodic reconciliation, without reconciling now
+    #[arg(long)]
+    last: bool,
+    ///JSON, prettified JSON or table as output format
+    #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
+    output: ListOutputFormat,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct SerializableReport {
+    timestamp: String,
+    findings: Vec<SerializableFinding>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct SerializableFinding {
+    mismatch: String,
+    resource: String,
+    outcome: String,
+}
+
+#[derive(Table)]
+struct FindingTable {
+    #[table(title = "Mismatch")]
+    mismatch: String,
+    #[table(title = "Resource")]
+    resource: String,
+    #[table(title = "Outcome")]
+    outcome: String,
+}
+
+impl From<VpnReconciliationReport> for SerializableReport {
+    fn from(report: VpnReconciliationReport) -> Self {
+        SerializableReport {
+            timestamp: DateTime::<Utc>::from(report.timestamp).to_rfc3339_opts(SecondsFormat::Millis, true),
+            findings: report.findings.into_iter().map(SerializableFinding::from).collect(),
+        }
+    }
+}
+
+impl From<VpnReconciliationFinding> for SerializableFinding {
+    fn from(finding: VpnReconciliationFinding) -> Self {
+        let resource = match &finding.mismatch {
+            VpnMismatch::MissingPeer { peer_id }
+            | VpnMismatch::OrphanedPeer { peer_id }
+            | VpnMismatch::PeerWithoutDevice { peer_id } => peer_id.to_string(),
+            VpnMismatch::MissingCluster { cluster_id }
+            | VpnMismatch::OutdatedCluster { cluster_id }
+            | VpnMismatch::OrphanedCluster { cluster_id } => cluster_id.to_string(),
+            VpnMismatch::StaleSetupKey { name } => Clone::clone(name),
+        };
+        SerializableFinding {
+            mismatch: finding.mismatch.kind().to_owned(),
+            resource,
+            outcome: finding.outcome.to_string(),
+        }
+    }
+}
+
+impl From<SerializableFinding> for FindingTable {
+    fn from(finding: SerializableFinding) -> Self {
+        let SerializableFinding { mismatch, resource, outcome } = finding;
+        FindingTable { mismatch, resource, outcome }
+    }
+}
+
+impl ReconcileVpnCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let report = carl.administration.get_vpn_reconciliation_report(!self.last).await
+            .map_err(|error| format!("Could not reconcile the VPN.\n  {error}"))?
+            .ok_or("The VPN was not reconciled yet. Run the command without '--last' to reconcile it now.")?;
+
+        let report = SerializableReport::from(report);
+
+        match self.output {
+            ListOutputFormat::Table => {
+                println!("Reconciled the VPN at {}.", report.timestamp);
+                if report.findings.is_empty() {
+                    println!("No mismatches found.");
+                } else {
+                    let table = report.findings.into_iter()
+                        .map(FindingTable::from)
+                        .collect::<Vec<_>>();
+                    print_stdout(table.with_title())
+                        .expect("List of reconciliation findings should be printable as table.");
+                }
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&report).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&report).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::ClusterId;
+    use opendut_types::vpn::reconciliation::VpnReconciliationOutcome;
+
+    use super::*;
+
+    #[test]
+    fn should_convert_reconciliation_finding_for_output() -> anyhow::Result<()> {
+        let cluster_id = ClusterId::try_from("5c806e1c-448e-4dda-854a-20a33cfe1cfe")?;
+        let finding = VpnReconciliationFinding {
+            mismatch: VpnMismatch::OrphanedCluster { cluster_id },
+            outcome: VpnReconciliationOutcome::Failed { cause: String::from("NetBird unreachable") },
+        };
+
+        assert_that!(
+            SerializableFinding::from(finding),
+            eq(&SerializableFinding {
+                mismatch: String::from("orphaned-cluster"),
+                resource: String::from("5c806e1c-448e-4dda-854a-20a33cfe1cfe"),
+                outcome: String::from("repair failed: NetBird unreachable"),
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::setup::PeerSetupId;
+
+/// Revoke a Setup-String, which was not used yet, so that it cannot be used for setting up a peer anymore
+#[derive(clap::Parser)]
+pub struct RevokeSetupStringCli {
+    /// ID of the Setup-String, as printed when generating it
+    #[arg()]
+    id: PeerSetupId,
+}
+
+impl RevokeSetupStringCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let setup_id = carl.peers.revoke_peer_setup(self.id).await
+            .map_err(|error| format!("Could not revoke Setup-String <{}>.\n  {error}", self.id))?;
+
+        println!("Revoked Setup-String <{setup_id}>.");
+        Ok(())
+    }
+}
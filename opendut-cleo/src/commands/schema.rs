@@ -0,0 +1,44 @@
+use opendut_types::specs::parse::ResourceKind;
+use opendut_types::specs::schema::json_schema;
+use opendut_types::specs::SpecificationVersion;
+
+/// Print the JSON Schema of a specification document, e.g. for validation and completion in editors
+#[derive(clap::Parser)]
+pub struct SchemaCli {
+    ///Kind of resource described by the document
+    #[arg(value_enum)]
+    kind: SchemaKind,
+    ///Version of the specification format
+    #[arg(value_enum, long, default_value_t=SchemaVersion::V2)]
+    version: SchemaVersion,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum SchemaKind {
+    PeerDescriptor,
+    ClusterConfiguration,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum SchemaVersion {
+    V1,
+    V2,
+}
+
+impl SchemaCli {
+    pub fn execute(self) -> crate::Result<()> {
+        let kind = match self.kind {
+            SchemaKind::PeerDescriptor => ResourceKind::PeerDescriptor,
+            SchemaKind::ClusterConfiguration => ResourceKind::ClusterConfiguration,
+        };
+        let version = match self.version {
+            SchemaVersion::V1 => SpecificationVersion::V1,
+            SchemaVersion::V2 => SpecificationVersion::V2,
+        };
+
+        let schema = serde_json::to_string_pretty(&json_schema(kind, version))
+            .map_err(|error| format!("Failed to serialize the JSON Schema.\n  {error}"))?;
+        println!("{schema}");
+        Ok(())
+    }
+}
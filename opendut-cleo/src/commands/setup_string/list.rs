@@ -0,0 +1,139 @@
+use std::time::SystemTime;
+
+use cli_table::{print_stdout, Table, WithTitle};
+use serde::Serialize;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerId;
+use opendut_types::peer::setup::{PeerSetupId, PeerSetupRecord, PeerSetupStatus};
+
+use crate::commands::format_time;
+use crate::parse::reference::PeerReference;
+use crate::resolve::resolve_peer;
+use crate::ListOutputFormat;
+
+/// List the Setup-Strings issued for peers
+#[derive(clap::Parser)]
+pub struct ListSetupStringsCli {
+    /// Only list the Setup-Strings of this peer (ID or name)
+    #[arg(long)]
+    peer: Option<PeerReference>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct SerializableSetupString {
+    id: PeerSetupId,
+    peer_id: PeerId,
+    status: PeerSetupStatus,
+    issued_at: String,
+    expires_at: String,
+    used_at: Option<String>,
+    revoked_at: Option<String>,
+}
+
+#[derive(Table)]
+struct SetupStringTable {
+    #[table(title = "SetupID")]
+    id: PeerSetupId,
+    #[table(title = "PeerID")]
+    peer_id: PeerId,
+    #[table(title = "Status")]
+    status: PeerSetupStatus,
+    #[table(title = "Issued")]
+    issued_at: String,
+    #[table(title = "Expires")]
+    expires_at: String,
+}
+
+impl SerializableSetupString {
+    fn new(setup: PeerSetupRecord, now: SystemTime) -> Self {
+        SerializableSetupString {
+            id: setup.id,
+            peer_id: setup.peer_id,
+            status: setup.status(now),
+            issued_at: format_time(setup.issued_at),
+            expires_at: format_time(setup.expires_at),
+            used_at: setup.used_at.map(format_time),
+            revoked_at: setup.revoked_at.map(format_time),
+        }
+    }
+}
+
+impl From<SerializableSetupString> for SetupStringTable {
+    fn from(setup: SerializableSetupString) -> Self {
+        let SerializableSetupString { id, peer_id, status, issued_at, expires_at, .. } = setup;
+        SetupStringTable { id, peer_id, status, issued_at, expires_at }
+    }
+}
+
+impl ListSetupStringsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let peer_id = match self.peer {
+            Some(peer) => Some(resolve_peer(carl, peer).await?),
+            None => None,
+        };
+
+        let setups = carl.peers.list_peer_setups().await
+            .map_err(|error| format!("Could not list Setup-Strings.\n  {error}"))?;
+
+        let now = SystemTime::now();
+        let setups = setups.into_iter()
+            .filter(|setup| peer_id.is_none_or(|peer_id| setup.peer_id == peer_id))
+            .map(|setup| SerializableSetupString::new(setup, now))
+            .collect::<Vec<_>>();
+
+        match output {
+            ListOutputFormat::Table => {
+                let table = setups.into_iter()
+                    .map(SetupStringTable::from)
+                    .collect::<Vec<_>>();
+                print_stdout(table.with_title())
+                    .expect("List of Setup-Strings should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&setups).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&setups).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_convert_setup_record_for_output() -> anyhow::Result<()> {
+        let issued_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let setup = PeerSetupRecord {
+            id: PeerSetupId::try_from("0c5ee4a7-6e2f-4b4c-9d2e-7b8a8fd3c2a1")?,
+            peer_id: PeerId::try_from("5c806e1c-448e-4dda-854a-20a33cfe1cfe")?,
+            issued_at,
+            expires_at: issued_at + Duration::from_secs(60),
+            used_at: Some(issued_at + Duration::from_secs(30)),
+            revoked_at: None,
+        };
+
+        let result = SerializableSetupString::new(Clone::clone(&setup), issued_at + Duration::from_secs(90));
+
+        assert_that!(result, eq(&SerializableSetupString {
+            id: setup.id,
+            peer_id: setup.peer_id,
+            status: PeerSetupStatus::Used,
+            issued_at: String::from("2023-11-14T22:13:20Z"),
+            expires_at: String::from("2023-11-14T22:14:20Z"),
+            used_at: Some(String::from("2023-11-14T22:13:50Z")),
+            revoked_at: None,
+        }));
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::user::token::{PersonalAccessTokenDescriptor, PersonalAccessTokenId, PersonalAccessTokenName, PersonalAccessTokenScope};
+
+use crate::commands::format_time;
+use crate::CreateOutputFormat;
+
+/// Create a personal access token, which acts on your behalf, limited to the given scopes
+#[derive(clap::Parser)]
+pub struct CreateTokenCli {
+    /// Name of the token, e.g. the CI pipeline using it
+    #[arg()]
+    name: PersonalAccessTokenName,
+    /// Role the token is scoped to: administrator, editor or viewer. Can be specified multiple times.
+    #[arg(long="scope", required=true)]
+    scopes: Vec<PersonalAccessTokenScope>,
+    /// Number of days after which the token expires
+    #[arg(long, default_value_t=30)]
+    expires_in_days: u64,
+    ///Text, JSON or prettified JSON as output format
+    #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
+    output: CreateOutputFormat,
+}
+
+#[derive(Serialize)]
+struct SerializableCreatedToken {
+    id: PersonalAccessTokenId,
+    name: String,
+    expires_at: String,
+    token: String,
+}
+
+impl SerializableCreatedToken {
+    fn new(descriptor: PersonalAccessTokenDescriptor, expires_at: String, token: String) -> Self {
+        SerializableCreatedToken {
+            id: descriptor.id,
+            name: descriptor.name.value(),
+            expires_at,
+            token,
+        }
+    }
+}
+
+impl CreateTokenCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let validity = Duration::from_secs(self.expires_in_days * 24 * 60 * 60);
+
+        let created = carl.tokens.create_personal_access_token(Clone::clone(&self.name), self.scopes, validity).await
+            .map_err(|error| format!("Could not create personal access token '{}'.\n  {error}", self.name))?;
+
+        let descriptor = created.descriptor;
+        let expires_at = format_time(descriptor.expires_at);
+
+        match self.output {
+            CreateOutputFormat::Text => {
+                println!("Created personal access token '{}' <{}>, which expires at {expires_at}.", descriptor.name, descriptor.id);
+                println!("Store the token now, as it cannot be shown again:\n{}", created.token);
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&SerializableCreatedToken::new(descriptor, expires_at, created.token)).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&SerializableCreatedToken::new(descriptor, expires_at, created.token)).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use std::time::SystemTime;
+
+use cli_table::{print_stdout, Table, WithTitle};
+use serde::Serialize;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::user::token::{PersonalAccessTokenDescriptor, PersonalAccessTokenId, PersonalAccessTokenScope, PersonalAccessTokenStatus};
+
+use crate::commands::format_time;
+use crate::ListOutputFormat;
+
+/// List your personal access tokens
+#[derive(clap::Parser)]
+pub struct ListTokensCli {
+    /// List the tokens of all users. Only permitted for administrators.
+    #[arg(long)]
+    all_users: bool,
+    ///JSON, prettified JSON or table as output format
+    #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
+    output: ListOutputFormat,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct SerializableToken {
+    id: PersonalAccessTokenId,
+    name: String,
+    owner: String,
+    scopes: Vec<PersonalAccessTokenScope>,
+    status: PersonalAccessTokenStatus,
+    created_at: String,
+    expires_at: String,
+    revoked_at: Option<String>,
+}
+
+#[derive(Table)]
+struct TokenTable {
+    #[table(title = "TokenID")]
+    id: PersonalAccessTokenId,
+    #[table(title = "Name")]
+    name: String,
+    #[table(title = "Owner")]
+    owner: String,
+    #[table(title = "Scopes")]
+    scopes: String,
+    #[table(title = "Status")]
+    status: PersonalAccessTokenStatus,
+    #[table(title = "Expires")]
+    expires_at: String,
+}
+
+impl SerializableToken {
+    fn new(token: PersonalAccessTokenDescriptor, now: SystemTime) -> Self {
+        SerializableToken {
+            id: token.id,
+            status: token.status(now),
+            name: token.name.value(),
+            owner: token.owner,
+            scopes: token.scopes,
+            created_at: format_time(token.created_at),
+            expires_at: format_time(token.expires_at),
+            revoked_at: token.revoked_at.map(format_time),
+        }
+    }
+}
+
+impl From<SerializableToken> for TokenTable {
+    fn from(token: SerializableToken) -> Self {
+        let SerializableToken { id, name, owner, scopes, status, expires_at, .. } = token;
+        let scopes = scopes.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        TokenTable { id, name, owner, scopes, status, expires_at }
+    }
+}
+
+impl ListTokensCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let tokens = carl.tokens.list_personal_access_tokens(self.all_users).await
+            .map_err(|error| format!("Could not list personal access tokens.\n  {error}"))?;
+
+        let now = SystemTime::now();
+        let tokens = tokens.into_iter()
+            .map(|token| SerializableToken::new(token, now))
+            .collect::<Vec<_>>();
+
+        match self.output {
+            ListOutputFormat::Table => {
+                let table = tokens.into_iter()
+                    .map(TokenTable::from)
+                    .collect::<Vec<_>>();
+                print_stdout(table.with_title())
+                    .expect("List of personal access tokens should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&tokens).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&tokens).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use opendut_types::user::token::PersonalAccessTokenName;
+
+    use super::*;
+
+    #[test]
+    fn should_convert_token_for_output() -> anyhow::Result<()> {
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let token = PersonalAccessTokenDescriptor {
+            id: PersonalAccessTokenId::try_from("0c5ee4a7-6e2f-4b4c-9d2e-7b8a8fd3c2a1")?,
+            name: PersonalAccessTokenName::try_from("nightly-build")?,
+            owner: String::from("alice"),
+            scopes: vec![PersonalAccessTokenScope::Editor, PersonalAccessTokenScope::Viewer],
+            created_at,
+            expires_at: created_at + Duration::from_secs(60),
+            revoked_at: None,
+        };
+
+        let result = SerializableToken::new(Clone::clone(&token), created_at + Duration::from_secs(90));
+        assert_that!(result.status, eq(PersonalAccessTokenStatus::Expired));
+        assert_that!(result.expires_at, eq("2023-11-14T22:14:20Z"));
+
+        let table = TokenTable::from(result);
+        assert_that!(table.scopes, eq("editor, viewer"));
+        Ok(())
+    }
+}
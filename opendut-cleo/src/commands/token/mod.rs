@@ -0,0 +1,29 @@
+use opendut_carl_api::carl::CarlClient;
+
+pub mod create;
+pub mod list;
+pub mod revoke;
+
+/// Manage your personal access tokens, e.g. for authenticating CI pipelines
+#[derive(clap::Parser)]
+pub struct TokenCli {
+    #[command(subcommand)]
+    command: TokenCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum TokenCommand {
+    Create(create::CreateTokenCli),
+    List(list::ListTokensCli),
+    Revoke(revoke::RevokeTokenCli),
+}
+
+impl TokenCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        match self.command {
+            TokenCommand::Create(implementation) => implementation.execute(carl).await,
+            TokenCommand::List(implementation) => implementation.execute(carl).await,
+            TokenCommand::Revoke(implementation) => implementation.execute(carl).await,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::user::token::PersonalAccessTokenId;
+
+/// Revoke a personal access token, so that CARL does not accept it anymore
+#[derive(clap::Parser)]
+pub struct RevokeTokenCli {
+    /// ID of the token, as printed when creating or listing it
+    #[arg()]
+    id: PersonalAccessTokenId,
+}
+
+impl RevokeTokenCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let descriptor = carl.tokens.revoke_personal_access_token(self.id).await
+            .map_err(|error| format!("Could not revoke personal access token <{}>.\n  {error}", self.id))?;
+
+        println!("Revoked personal access token '{}' <{}> of user '{}'.", descriptor.name, descriptor.id, descriptor.owner);
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::peer::state::PeerState;
+use opendut_types::peer::{PeerDescriptor, PeerId, PeerName};
+use opendut_types::topology::DeviceDescriptor;
+
+use crate::commands::tui::state::Action;
+
+pub struct PeerRow {
+    pub descriptor: PeerDescriptor,
+    pub state: PeerState,
+}
+
+pub struct ClusterRow {
+    pub configuration: ClusterConfiguration,
+    pub deployed: bool,
+}
+
+pub struct DeviceRow {
+    pub descriptor: DeviceDescriptor,
+    pub peer: PeerName,
+}
+
+/// Resources shown in the terminal UI, sorted by name, so that the rows do not jump around between refreshes.
+#[derive(Default)]
+pub struct Snapshot {
+    pub peers: Vec<PeerRow>,
+    pub clusters: Vec<ClusterRow>,
+    pub devices: Vec<DeviceRow>,
+}
+
+impl Snapshot {
+    pub async fn fetch(carl: &mut CarlClient) -> crate::Result<Self> {
+        let descriptors = carl.peers.list_peer_descriptors().await
+            .map_err(|error| format!("Could not list peers.\n  {error}"))?;
+        let mut states = carl.peers.list_peer_states().await
+            .map_err(|error| format!("Could not list peer states.\n  {error}"))?;
+        let configurations = carl.cluster.list_cluster_configurations().await
+            .map_err(|error| format!("Could not list cluster configurations.\n  {error}"))?;
+        let deployments = carl.cluster.list_cluster_deployments().await
+            .map_err(|error| format!("Could not list cluster deployments.\n  {error}"))?
+            .into_iter()
+            .map(|deployment| deployment.id)
+            .collect::<HashSet<_>>();
+
+        let mut devices = descriptors.iter()
+            .flat_map(|peer| peer.topology.devices.iter()
+                .map(|device| DeviceRow { descriptor: device.clone(), peer: peer.name.clone() })
+            )
+            .collect::<Vec<_>>();
+        devices.sort_by(|a, b| a.descriptor.name.value().cmp(b.descriptor.name.value()));
+
+        let mut peers = descriptors.into_iter()
+            .map(|descriptor| {
+                let state = states.remove(&descriptor.id).unwrap_or_default();
+                PeerRow { descriptor, state }
+            })
+            .collect::<Vec<_>>();
+        peers.sort_by_key(|peer| peer.descriptor.name.to_string());
+
+        let mut clusters = configurations.into_iter()
+            .map(|configuration| {
+                let deployed = deployments.contains(&configuration.id);
+                ClusterRow { configuration, deployed }
+            })
+            .collect::<Vec<_>>();
+        clusters.sort_by_key(|cluster| cluster.configuration.name.to_string());
+
+        Ok(Snapshot { peers, clusters, devices })
+    }
+
+    pub fn peer_name(&self, id: PeerId) -> String {
+        self.peers.iter()
+            .find(|peer| peer.descriptor.id == id)
+            .map(|peer| peer.descriptor.name.to_string())
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    pub fn cluster_name(&self, id: ClusterId) -> String {
+        self.clusters.iter()
+            .find(|cluster| cluster.configuration.id == id)
+            .map(|cluster| cluster.configuration.name.to_string())
+            .unwrap_or_else(|| id.to_string())
+    }
+}
+
+/// Sends the confirmed action to CARL and returns a message for the status line.
+pub async fn execute(carl: &mut CarlClient, action: Action) -> crate::Result<String> {
+    match action {
+        Action::Deploy(id) => {
+            carl.cluster.store_cluster_deployment(ClusterDeployment { id }).await
+                .map_err(|error| format!("Could not deploy cluster <{id}>.\n  {error}"))?;
+            Ok(format!("Deployed cluster <{id}>."))
+        }
+        Action::Undeploy(id) => {
+            carl.cluster.delete_cluster_deployment(id).await
+                .map_err(|error| format!("Could not undeploy cluster <{id}>.\n  {error}"))?;
+            Ok(format!("Undeployed cluster <{id}>."))
+        }
+        Action::DeletePeer(id) => {
+            carl.peers.delete_peer_descriptor(id).await
+                .map_err(|error| format!("Could not delete peer <{id}>.\n  {error}"))?;
+            Ok(format!("Deleted peer <{id}>."))
+        }
+        Action::DeleteCluster(id) => {
+            carl.cluster.delete_cluster_configuration(id).await
+                .map_err(|error| format!("Could not delete cluster configuration <{id}>.\n  {error}"))?;
+            Ok(format!("Deleted cluster configuration <{id}>."))
+        }
+    }
+}
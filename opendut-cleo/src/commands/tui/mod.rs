@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::DefaultTerminal;
+use tokio::sync::mpsc;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::observer::WatchedResourceKind;
+
+use crate::commands::tui::data::Snapshot;
+use crate::commands::tui::state::{App, Command};
+
+mod data;
+mod render;
+mod state;
+
+/// Interactive terminal UI with live-updating lists of peers, clusters and devices
+#[derive(clap::Parser)]
+pub struct TuiCli {
+    /// Seconds after which all resources are listed again, in addition to the modifications pushed by CARL
+    #[arg(long, default_value_t = 10)]
+    refresh_interval: u64,
+}
+
+impl TuiCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let mut app = App::default();
+        app.update(Snapshot::fetch(carl).await?);
+
+        let mut terminal = ratatui::init();
+        let result = run(&mut terminal, carl, &mut app, Duration::from_secs(self.refresh_interval.max(1))).await;
+        ratatui::restore();
+        result
+    }
+}
+
+async fn run(terminal: &mut DefaultTerminal, carl: &mut CarlClient, app: &mut App, refresh_interval: Duration) -> crate::Result<()> {
+    let mut keys = spawn_key_reader();
+
+    let mut watches = Vec::new();
+    for kind in [
+        WatchedResourceKind::PeerDescriptor,
+        WatchedResourceKind::PeerConnectionState,
+        WatchedResourceKind::ClusterConfiguration,
+        WatchedResourceKind::ClusterDeployment,
+    ] {
+        match carl.observer.watch(kind, None).await {
+            Ok(stream) => watches.push(stream),
+            Err(error) => app.status = Some(format!("Could not watch resources, only refreshing periodically. {error}")),
+        }
+    }
+    let mut modifications = futures::stream::select_all(watches);
+
+    let mut refresh_timer = tokio::time::interval(refresh_interval);
+
+    loop {
+        terminal.draw(|frame| render::render(frame, app))
+            .map_err(|cause| format!("Failed to draw terminal UI.\n  {cause}"))?;
+
+        let command = tokio::select! {
+            key = keys.recv() => match key {
+                Some(KeyEvent { code: KeyCode::Char('c'), modifiers, .. }) if modifiers.contains(KeyModifiers::CONTROL) => Some(Command::Quit),
+                Some(key) => {
+                    app.status = None;
+                    app.handle_key(key.code)
+                }
+                None => Some(Command::Quit),
+            },
+            Some(_) = modifications.next() => Some(Command::Refresh),
+            _ = refresh_timer.tick() => Some(Command::Refresh),
+        };
+
+        match command {
+            None => {}
+            Some(Command::Quit) => return Ok(()),
+            Some(Command::Refresh) => refresh(carl, app).await,
+            Some(Command::Execute(action)) => {
+                let message = data::execute(carl, action).await
+                    .unwrap_or_else(|error| error);
+                app.status = Some(message);
+                refresh(carl, app).await;
+            }
+        }
+    }
+}
+
+async fn refresh(carl: &mut CarlClient, app: &mut App) {
+    match Snapshot::fetch(carl).await {
+        Ok(snapshot) => app.update(snapshot),
+        Err(error) => app.status = Some(error),
+    }
+}
+
+/// Reads key presses on a separate thread, since crossterm only offers blocking reads.
+/// The thread ends shortly after the receiver is dropped.
+fn spawn_key_reader() -> mpsc::UnboundedReceiver<KeyEvent> {
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    tokio::task::spawn_blocking(move || {
+        while !sender.is_closed() {
+            match event::poll(Duration::from_millis(100)) {
+                Ok(true) => {
+                    if let Ok(Event::Key(key)) = event::read() {
+                        if key.kind == KeyEventKind::Press {
+                            let _ = sender.send(key);
+                        }
+                    }
+                }
+                Ok(false) => {}
+                Err(_) => break,
+            }
+        }
+    });
+
+    receiver
+}
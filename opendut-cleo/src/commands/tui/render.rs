@@ -0,0 +1,153 @@
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph, Row, Table, TableState, Tabs, Wrap};
+use ratatui::Frame;
+
+use opendut_types::peer::state::{PeerConnectionState, PeerMemberState};
+use opendut_types::specs::SpecificationDocument;
+
+use crate::commands::get::{render_documents, SpecificationOutputFormat};
+use crate::commands::tui::state::{App, Mode, Pane};
+
+const HELP: &str = "Tab: switch pane  ↑↓: select  Enter: details  d: deploy  u: undeploy  x: delete  r: refresh  q: quit";
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let [tabs_area, main_area, status_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ]).areas(frame.area());
+
+    let tabs = Tabs::new(Pane::ALL.map(|pane| pane.title()))
+        .select(Pane::ALL.iter().position(|pane| *pane == app.pane))
+        .highlight_style(Style::new().add_modifier(Modifier::BOLD | Modifier::REVERSED));
+    frame.render_widget(tabs, tabs_area);
+
+    match app.mode {
+        Mode::Detail => render_detail(frame, app, main_area),
+        Mode::Browse | Mode::Confirm(_) => render_table(frame, app, main_area),
+    }
+
+    let status = app.status.as_deref()
+        .and_then(|status| status.lines().next())
+        .unwrap_or(HELP);
+    frame.render_widget(Paragraph::new(status).dark_gray(), status_area);
+
+    if let Mode::Confirm(action) = &app.mode {
+        render_confirmation(frame, &action.describe(&app.snapshot));
+    }
+}
+
+fn render_table(frame: &mut Frame, app: &App, area: Rect) {
+    let (header, widths, rows): (Row, Vec<Constraint>, Vec<Row>) = match app.pane {
+        Pane::Peers => (
+            Row::new(["Name", "PeerID", "Connection", "Member"]),
+            vec![Constraint::Fill(1), Constraint::Length(36), Constraint::Fill(1), Constraint::Fill(1)],
+            app.snapshot.peers.iter().map(|peer| {
+                let connection = match &peer.state.connection {
+                    PeerConnectionState::Online { remote_host } => format!("Online ({remote_host})"),
+                    PeerConnectionState::Offline => String::from("Offline"),
+                };
+                let member = match &peer.state.member {
+                    PeerMemberState::Available => String::from("Available"),
+                    PeerMemberState::Blocked { by_cluster } => format!("Blocked by {}", app.snapshot.cluster_name(*by_cluster)),
+                };
+                let color = match (&peer.state.connection, &peer.state.member) {
+                    (PeerConnectionState::Offline, _) => Color::Reset,
+                    (PeerConnectionState::Online { .. }, PeerMemberState::Available) => Color::Green,
+                    (PeerConnectionState::Online { .. }, PeerMemberState::Blocked { .. }) => Color::Yellow,
+                };
+                Row::new([peer.descriptor.name.to_string(), peer.descriptor.id.to_string(), connection, member])
+                    .style(Style::new().fg(color))
+            }).collect(),
+        ),
+        Pane::Clusters => (
+            Row::new(["Name", "ClusterID", "Leader", "Devices", "Deployment"]),
+            vec![Constraint::Fill(1), Constraint::Length(36), Constraint::Fill(1), Constraint::Length(7), Constraint::Length(12)],
+            app.snapshot.clusters.iter().map(|cluster| {
+                let configuration = &cluster.configuration;
+                let (deployment, color) = if cluster.deployed {
+                    ("Deployed", Color::Green)
+                } else {
+                    ("Undeployed", Color::Reset)
+                };
+                Row::new([
+                    configuration.name.to_string(),
+                    configuration.id.to_string(),
+                    app.snapshot.peer_name(configuration.leader),
+                    configuration.devices.len().to_string(),
+                    String::from(deployment),
+                ]).style(Style::new().fg(color))
+            }).collect(),
+        ),
+        Pane::Devices => (
+            Row::new(["Name", "DeviceID", "Peer", "Interface", "Tags"]),
+            vec![Constraint::Fill(1), Constraint::Length(36), Constraint::Fill(1), Constraint::Length(36), Constraint::Fill(1)],
+            app.snapshot.devices.iter().map(|device| {
+                let descriptor = &device.descriptor;
+                Row::new([
+                    descriptor.name.to_string(),
+                    descriptor.id.to_string(),
+                    device.peer.to_string(),
+                    descriptor.interface.to_string(),
+                    descriptor.tags.iter().map(|tag| tag.value()).collect::<Vec<_>>().join(", "),
+                ])
+            }).collect(),
+        ),
+    };
+
+    let table = Table::new(rows, widths)
+        .header(header.bold())
+        .block(Block::bordered().title(app.pane.title()))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+    let mut state = TableState::default().with_selected(Some(app.selected()));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn render_detail(frame: &mut Frame, app: &App, area: Rect) {
+    let (title, text) = match app.pane {
+        Pane::Peers => app.snapshot.peers.get(app.selected())
+            .map(|peer| {
+                let specification = render_specification(SpecificationDocument::from(peer.descriptor.clone()));
+                let state = format!("# State: {:?}\n", peer.state);
+                (peer.descriptor.name.to_string(), state + &specification)
+            }),
+        Pane::Clusters => app.snapshot.clusters.get(app.selected())
+            .map(|cluster| {
+                let specification = render_specification(SpecificationDocument::from(cluster.configuration.clone()));
+                let deployment = format!("# Deployed: {}\n", cluster.deployed);
+                (cluster.configuration.name.to_string(), deployment + &specification)
+            }),
+        Pane::Devices => app.snapshot.devices.get(app.selected())
+            .map(|device| {
+                let yaml = serde_yaml::to_string(&device.descriptor)
+                    .unwrap_or_else(|cause| format!("Failed to serialize device as YAML: {cause}"));
+                (device.descriptor.name.to_string(), format!("# Peer: {}\n{yaml}", device.peer))
+            }),
+    }.unwrap_or_default();
+
+    let paragraph = Paragraph::new(text)
+        .wrap(Wrap { trim: false })
+        .block(Block::bordered().title(title).title_bottom(Line::from("Esc: back").right_aligned()));
+    frame.render_widget(paragraph, area);
+}
+
+fn render_specification(document: SpecificationDocument) -> String {
+    render_documents(vec![document], SpecificationOutputFormat::Yaml)
+        .unwrap_or_else(|cause| cause)
+}
+
+fn render_confirmation(frame: &mut Frame, question: &str) {
+    let text = format!("{question} [y/n]");
+    let width = u16::try_from(text.chars().count() + 2).unwrap_or(u16::MAX);
+
+    let [area] = Layout::vertical([Constraint::Length(3)]).flex(Flex::Center).areas(frame.area());
+    let [area] = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(area);
+
+    let paragraph = Paragraph::new(text)
+        .block(Block::bordered().title("Confirm").yellow());
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
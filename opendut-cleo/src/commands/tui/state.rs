@@ -0,0 +1,296 @@
+use ratatui::crossterm::event::KeyCode;
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+
+use crate::commands::tui::data::Snapshot;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Pane {
+    #[default]
+    Peers,
+    Clusters,
+    Devices,
+}
+impl Pane {
+    pub const ALL: [Pane; 3] = [Pane::Peers, Pane::Clusters, Pane::Devices];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Pane::Peers => "Peers",
+            Pane::Clusters => "Clusters",
+            Pane::Devices => "Devices",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Pane::Peers => 0,
+            Pane::Clusters => 1,
+            Pane::Devices => 2,
+        }
+    }
+
+    fn next(&self) -> Pane {
+        Pane::ALL[(self.index() + 1) % Pane::ALL.len()]
+    }
+
+    fn previous(&self) -> Pane {
+        Pane::ALL[(self.index() + Pane::ALL.len() - 1) % Pane::ALL.len()]
+    }
+}
+
+/// Modifications, which the user requested via the keyboard and which are sent to CARL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    Deploy(ClusterId),
+    Undeploy(ClusterId),
+    DeletePeer(PeerId),
+    DeleteCluster(ClusterId),
+}
+impl Action {
+    pub fn describe(&self, snapshot: &Snapshot) -> String {
+        match self {
+            Action::Deploy(id) => format!("Deploy cluster '{}'?", snapshot.cluster_name(*id)),
+            Action::Undeploy(id) => format!("Undeploy cluster '{}'?", snapshot.cluster_name(*id)),
+            Action::DeletePeer(id) => format!("Delete peer '{}'?", snapshot.peer_name(*id)),
+            Action::DeleteCluster(id) => format!("Delete cluster configuration '{}'?", snapshot.cluster_name(*id)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Quit,
+    Refresh,
+    Execute(Action),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Browse,
+    Detail,
+    Confirm(Action),
+}
+
+#[derive(Default)]
+pub struct App {
+    pub snapshot: Snapshot,
+    pub pane: Pane,
+    pub mode: Mode,
+    pub status: Option<String>,
+    selected: [usize; 3],
+}
+
+impl App {
+    pub fn selected(&self) -> usize {
+        self.selected[self.pane.index()]
+    }
+
+    fn row_count(&self) -> usize {
+        match self.pane {
+            Pane::Peers => self.snapshot.peers.len(),
+            Pane::Clusters => self.snapshot.clusters.len(),
+            Pane::Devices => self.snapshot.devices.len(),
+        }
+    }
+
+    /// Replaces the shown resources, keeping the selection within bounds.
+    pub fn update(&mut self, snapshot: Snapshot) {
+        self.snapshot = snapshot;
+        let current = self.pane;
+        for pane in Pane::ALL {
+            self.pane = pane;
+            let last = self.row_count().saturating_sub(1);
+            self.selected[pane.index()] = self.selected[pane.index()].min(last);
+        }
+        self.pane = current;
+    }
+
+    fn select(&mut self, offset: isize) {
+        let count = self.row_count();
+        if count == 0 {
+            return;
+        }
+        let index = self.selected().saturating_add_signed(offset).min(count - 1);
+        self.selected[self.pane.index()] = index;
+    }
+
+    fn selected_action(&self, key: KeyCode) -> Option<Action> {
+        match self.pane {
+            Pane::Peers => {
+                let peer = self.snapshot.peers.get(self.selected())?;
+                match key {
+                    KeyCode::Char('x') => Some(Action::DeletePeer(peer.descriptor.id)),
+                    _ => None,
+                }
+            }
+            Pane::Clusters => {
+                let cluster = self.snapshot.clusters.get(self.selected())?;
+                let id = cluster.configuration.id;
+                match key {
+                    KeyCode::Char('d') if !cluster.deployed => Some(Action::Deploy(id)),
+                    KeyCode::Char('u') if cluster.deployed => Some(Action::Undeploy(id)),
+                    KeyCode::Char('x') => Some(Action::DeleteCluster(id)),
+                    _ => None,
+                }
+            }
+            Pane::Devices => None,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) -> Option<Command> {
+        match &self.mode {
+            Mode::Confirm(action) => {
+                let action = action.clone();
+                self.mode = Mode::Browse;
+                match key {
+                    KeyCode::Char('y') => Some(Command::Execute(action)),
+                    _ => {
+                        self.status = Some(String::from("Cancelled."));
+                        None
+                    }
+                }
+            }
+            Mode::Detail => {
+                match key {
+                    KeyCode::Esc | KeyCode::Enter | KeyCode::Backspace => self.mode = Mode::Browse,
+                    KeyCode::Char('q') => return Some(Command::Quit),
+                    _ => {}
+                }
+                None
+            }
+            Mode::Browse => {
+                match key {
+                    KeyCode::Char('q') | KeyCode::Esc => return Some(Command::Quit),
+                    KeyCode::Char('r') => return Some(Command::Refresh),
+                    KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => self.pane = self.pane.next(),
+                    KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => self.pane = self.pane.previous(),
+                    KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                    KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                    KeyCode::Enter => {
+                        if self.row_count() > 0 {
+                            self.mode = Mode::Detail;
+                        }
+                    }
+                    KeyCode::Char('d' | 'u' | 'x') => {
+                        match self.selected_action(key) {
+                            Some(action) => self.mode = Mode::Confirm(action),
+                            None => self.status = Some(String::from("Not available for the selected entry.")),
+                        }
+                    }
+                    _ => {}
+                }
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::{ClusterConfiguration, ClusterName};
+
+    use crate::commands::tui::data::ClusterRow;
+
+    use super::*;
+
+    fn app_with_clusters(deployed: &[bool]) -> App {
+        let clusters = deployed.iter()
+            .enumerate()
+            .map(|(index, deployed)| ClusterRow {
+                configuration: ClusterConfiguration {
+                    id: ClusterId::random(),
+                    name: ClusterName::try_from(format!("cluster{index}")).unwrap(),
+                    leader: PeerId::random(),
+                    devices: Default::default(),
+                    labels: Default::default(),
+                    device_selector: None,
+                    project: Default::default(),
+                },
+                deployed: *deployed,
+            })
+            .collect();
+
+        let mut app = App::default();
+        app.update(Snapshot { clusters, ..Default::default() });
+        app.pane = Pane::Clusters;
+        app
+    }
+
+    #[test]
+    fn should_deploy_the_selected_cluster_only_after_confirmation() {
+        let mut app = app_with_clusters(&[false, false]);
+        let id = app.snapshot.clusters[1].configuration.id;
+
+        assert_that!(app.handle_key(KeyCode::Down), none());
+        assert_that!(app.handle_key(KeyCode::Char('d')), none());
+        assert_that!(app.mode, eq(&Mode::Confirm(Action::Deploy(id))));
+
+        assert_that!(app.handle_key(KeyCode::Char('y')), some(eq(&Command::Execute(Action::Deploy(id)))));
+        assert_that!(app.mode, eq(&Mode::Browse));
+    }
+
+    #[test]
+    fn should_cancel_confirmation_with_any_other_key() {
+        let mut app = app_with_clusters(&[true]);
+
+        app.handle_key(KeyCode::Char('x'));
+        assert_that!(app.handle_key(KeyCode::Esc), none());
+
+        assert_that!(app.mode, eq(&Mode::Browse));
+        assert_that!(app.status, some(eq("Cancelled.")));
+    }
+
+    #[test]
+    fn should_only_offer_undeploy_for_deployed_clusters() {
+        let mut app = app_with_clusters(&[true]);
+        let id = app.snapshot.clusters[0].configuration.id;
+
+        app.handle_key(KeyCode::Char('d'));
+        assert_that!(app.mode, eq(&Mode::Browse));
+
+        app.handle_key(KeyCode::Char('u'));
+        assert_that!(app.mode, eq(&Mode::Confirm(Action::Undeploy(id))));
+    }
+
+    #[test]
+    fn should_keep_selection_within_bounds() {
+        let mut app = app_with_clusters(&[false, false, false]);
+        app.handle_key(KeyCode::Up);
+        assert_that!(app.selected(), eq(0));
+
+        for _ in 0..5 {
+            app.handle_key(KeyCode::Char('j'));
+        }
+        assert_that!(app.selected(), eq(2));
+
+        app.update(Snapshot::default());
+        app.pane = Pane::Clusters;
+        assert_that!(app.selected(), eq(0));
+    }
+
+    #[test]
+    fn should_close_detail_view_before_quitting() {
+        let mut app = app_with_clusters(&[false]);
+
+        app.handle_key(KeyCode::Enter);
+        assert_that!(app.mode, eq(&Mode::Detail));
+
+        assert_that!(app.handle_key(KeyCode::Esc), none());
+        assert_that!(app.handle_key(KeyCode::Esc), some(eq(&Command::Quit)));
+    }
+
+    #[test]
+    fn should_cycle_through_panes() {
+        let mut app = App::default();
+
+        app.handle_key(KeyCode::BackTab);
+        assert_that!(app.pane, eq(Pane::Devices));
+        app.handle_key(KeyCode::Tab);
+        assert_that!(app.pane, eq(Pane::Peers));
+    }
+}
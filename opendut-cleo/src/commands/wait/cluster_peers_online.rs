@@ -2,15 +2,16 @@ use std::collections::HashSet;
 use std::time::Duration;
 use opendut_carl_api::carl::CarlClient;
 use opendut_carl_api::carl::cluster::{ListClusterPeerStatesResponse};
-use opendut_types::cluster::ClusterId;
 use crate::commands::wait::await_peers_online;
+use crate::parse::reference::ClusterReference;
+use crate::resolve::resolve_cluster;
 
 /// Wait for all peers in a cluster to come online
 #[derive(clap::Parser)]
 pub struct WaitPeersInClusterOnline {
-    /// ID of the cluster
+    /// ID or name of the cluster
     #[arg()]
-    pub id: ClusterId,
+    pub cluster: ClusterReference,
     /// Maximum observation duration in seconds
     #[arg(long, default_value_t = 600)]
     pub timeout: u64,
@@ -21,7 +22,8 @@ pub struct WaitPeersInClusterOnline {
 
 impl WaitPeersInClusterOnline {
     pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
-        let response = carl.cluster.list_cluster_peer_states(self.id).await
+        let cluster_id = resolve_cluster(carl, self.cluster).await?;
+        let response = carl.cluster.list_cluster_peer_states(cluster_id).await
             .map_err(|cause| cause.to_string())?;
 
         let max_observation_duration = Duration::from_secs(self.timeout);
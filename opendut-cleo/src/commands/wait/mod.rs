@@ -1,58 +1,82 @@
 use std::collections::HashSet;
+use std::ops::Not;
 use std::time::Duration;
+use futures::StreamExt;
 use opendut_carl_api::carl::CarlClient;
-use opendut_carl_api::carl::observer::{WaitForPeersOnlineResponse, WaitForPeersOnlineResponseStatus};
+use opendut_carl_api::carl::observer::{WatchEvent, WatchedResource, WatchedResourceKind};
 use opendut_types::peer::PeerId;
-use opendut_types::proto::ConversionError;
+use opendut_types::peer::state::PeerConnectionState;
 
 pub mod peer_online;
 pub mod cluster_peers_online;
 
 async fn await_peers_online(carl: &mut CarlClient, peer_ids: HashSet<PeerId>, max_observation_duration: Duration, peers_may_not_yet_exist: bool) -> crate::Result<()> {
-    let mut response_stream = carl.observer.wait_peers_online(peer_ids.clone(), max_observation_duration, peers_may_not_yet_exist).await
+    if peers_may_not_yet_exist.not() {
+        check_peers_exist(carl, &peer_ids).await?;
+    }
+
+    let mut events = carl.observer.watch(WatchedResourceKind::PeerConnectionState, None).await
         .map_err(|cause| format!("Failed to get stream: {}", cause.message))?;
 
-    let request_timeout_duration = Duration::from_secs(5);
-
-    loop {
-        let received = tokio::time::timeout(request_timeout_duration, response_stream.message()).await;
-
-        match received {
-            Ok(response_result) => {
-                match response_result {
-                    Ok(Some(response)) => {
-                        let conversion_result: Result<WaitForPeersOnlineResponse, ConversionError> = response.try_into();
-                        match conversion_result {
-                            Ok(response) => {
-                                println!("Response: {:?}", response);
-                                match response.status {
-                                    WaitForPeersOnlineResponseStatus::WaitForPeersOnlineSuccess | WaitForPeersOnlineResponseStatus::WaitForPeersOnlineFailure { .. } => {
-                                        break;
-                                    }
-                                    WaitForPeersOnlineResponseStatus::WaitForPeersOnlinePending => {}
-                                }
-                            }
-                            Err(error) => {
-                                println!("Failed to decode message from server: {:?}", error);
-                            }
+    let mut offline_peers = peer_ids.clone();
+
+    let observation = async {
+        while let Some(event) = events.next().await {
+            let event = event.map_err(|cause| cause.to_string())?;
+
+            match event {
+                WatchEvent::Snapshot { entries, .. } => {
+                    offline_peers = peer_ids.clone();
+                    for entry in entries {
+                        if let Some(WatchedResource::PeerConnectionState { peer_id, state: PeerConnectionState::Online { .. } }) = entry.resource {
+                            offline_peers.remove(&peer_id);
                         }
                     }
-                    Ok(None) => {
-                        println!("CARL disconnected!");
-                        return Err(format!("CARL disconnected while waiting for peers <{:?}> to come online!", peer_ids));
+                }
+                WatchEvent::Inserted { resource: WatchedResource::PeerConnectionState { peer_id, state }, .. } => {
+                    match state {
+                        PeerConnectionState::Online { .. } => {
+                            offline_peers.remove(&peer_id);
+                        }
+                        PeerConnectionState::Offline => {
+                            if peer_ids.contains(&peer_id) {
+                                offline_peers.insert(peer_id);
+                            }
+                        }
                     }
-                    Err(error) => {
-                        println!("GRPC error status: {:?}", error);
-                        tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+                WatchEvent::Removed { resource: WatchedResource::PeerConnectionState { peer_id, .. }, .. } => {
+                    if peer_ids.contains(&peer_id) {
+                        offline_peers.insert(peer_id);
                     }
                 }
+                WatchEvent::Inserted { .. } | WatchEvent::Removed { .. } => {}
             }
-            Err(_elapsed_timeout_error) => {
-                println!("No message for {} ms.", request_timeout_duration.as_millis());
-                tokio::time::sleep(Duration::from_secs(1)).await;
+
+            if offline_peers.is_empty() {
+                println!("All peers <{peer_ids:?}> are online.");
+                return Ok(());
             }
+            println!("Waiting for peers <{offline_peers:?}> to come online.");
         }
+        Err(format!("CARL disconnected while waiting for peers <{:?}> to come online!", peer_ids))
+    };
+
+    tokio::time::timeout(max_observation_duration, observation).await
+        .map_err(|_elapsed| format!("Peers <{offline_peers:?}> did not come online within {} seconds.", max_observation_duration.as_secs()))?
+}
+
+async fn check_peers_exist(carl: &mut CarlClient, peer_ids: &HashSet<PeerId>) -> crate::Result<()> {
+    let existing_peer_ids = carl.peers.list_peer_descriptors().await
+        .map_err(|cause| format!("Failed to list peers.\n  {cause}"))?
+        .into_iter()
+        .map(|peer| peer.id)
+        .collect::<HashSet<_>>();
+
+    let unknown_peers = peer_ids.difference(&existing_peer_ids).collect::<Vec<_>>();
+    if unknown_peers.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Unknown peer IDs provided: <{unknown_peers:?}>."))
     }
-    
-    Ok(())
 }
@@ -0,0 +1,191 @@
+use futures::StreamExt;
+use serde::Serialize;
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::observer::{WatchEvent, WatchedResource, WatchedResourceKind};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
+use opendut_types::peer::state::PeerConnectionState;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::resources::Revision;
+
+/// Print modifications of openDuT resources as they happen
+#[derive(clap::Parser)]
+pub struct WatchCli {
+    /// Kind of resource to watch
+    #[arg(value_enum)]
+    resource: WatchResource,
+    /// Revision printed last by a previous watch. Unmodified resources are then only listed by their ID.
+    #[arg(long)]
+    resume_revision: Option<u64>,
+    ///Text or JSON as output format, with one event per line
+    #[arg(value_enum, short, long, default_value_t=WatchOutputFormat::Text)]
+    output: WatchOutputFormat,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum WatchResource {
+    Peers,
+    PeerStates,
+    ClusterConfigurations,
+    ClusterDeployments,
+}
+impl From<WatchResource> for WatchedResourceKind {
+    fn from(value: WatchResource) -> Self {
+        match value {
+            WatchResource::Peers => WatchedResourceKind::PeerDescriptor,
+            WatchResource::PeerStates => WatchedResourceKind::PeerConnectionState,
+            WatchResource::ClusterConfigurations => WatchedResourceKind::ClusterConfiguration,
+            WatchResource::ClusterDeployments => WatchedResourceKind::ClusterDeployment,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone)]
+enum WatchOutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum SerializableWatchEvent {
+    Snapshot { revision: Revision, entries: Vec<SerializableSnapshotEntry> },
+    Inserted { revision: Revision, id: Uuid, resource: SerializableWatchedResource },
+    Removed { revision: Revision, id: Uuid, resource: SerializableWatchedResource },
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct SerializableSnapshotEntry {
+    id: Uuid,
+    revision: Revision,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resource: Option<SerializableWatchedResource>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum SerializableWatchedResource {
+    Peer(PeerDescriptor),
+    PeerState { peer_id: PeerId, state: PeerConnectionState },
+    ClusterConfiguration(ClusterConfiguration),
+    ClusterDeployment(ClusterDeployment),
+}
+
+impl From<WatchedResource> for SerializableWatchedResource {
+    fn from(resource: WatchedResource) -> Self {
+        match resource {
+            WatchedResource::PeerDescriptor(peer) => SerializableWatchedResource::Peer(peer),
+            WatchedResource::PeerConnectionState { peer_id, state } => SerializableWatchedResource::PeerState { peer_id, state },
+            WatchedResource::ClusterConfiguration(configuration) => SerializableWatchedResource::ClusterConfiguration(configuration),
+            WatchedResource::ClusterDeployment(deployment) => SerializableWatchedResource::ClusterDeployment(deployment),
+        }
+    }
+}
+
+impl From<WatchEvent> for SerializableWatchEvent {
+    fn from(event: WatchEvent) -> Self {
+        match event {
+            WatchEvent::Snapshot { entries, revision } => SerializableWatchEvent::Snapshot {
+                revision,
+                entries: entries.into_iter()
+                    .map(|entry| SerializableSnapshotEntry {
+                        id: entry.id,
+                        revision: entry.revision,
+                        resource: entry.resource.map(SerializableWatchedResource::from),
+                    })
+                    .collect(),
+            },
+            WatchEvent::Inserted { resource, revision } => SerializableWatchEvent::Inserted {
+                revision,
+                id: resource.id(),
+                resource: resource.into(),
+            },
+            WatchEvent::Removed { resource, revision } => SerializableWatchEvent::Removed {
+                revision,
+                id: resource.id(),
+                resource: resource.into(),
+            },
+        }
+    }
+}
+
+fn summarize(resource: &WatchedResource) -> String {
+    match resource {
+        WatchedResource::PeerDescriptor(peer) => peer.name.to_string(),
+        WatchedResource::PeerConnectionState { state, .. } => match state {
+            PeerConnectionState::Online { remote_host } => format!("online ({remote_host})"),
+            PeerConnectionState::Offline => String::from("offline"),
+        },
+        WatchedResource::ClusterConfiguration(configuration) => configuration.name.to_string(),
+        WatchedResource::ClusterDeployment(_) => String::from("deployed"),
+    }
+}
+
+fn format_text(event: &WatchEvent) -> String {
+    match event {
+        WatchEvent::Snapshot { entries, revision } => {
+            let mut text = format!("Snapshot at revision {revision} with {} resource(s):", entries.len());
+            for entry in entries {
+                let summary = entry.resource.as_ref()
+                    .map(summarize)
+                    .unwrap_or_else(|| String::from("unchanged"));
+                text.push_str(&format!("\n  {}  {}  {summary}", entry.revision, entry.id));
+            }
+            text
+        }
+        WatchEvent::Inserted { resource, revision } => format!("Revision {revision}: inserted  {}  {}", resource.id(), summarize(resource)),
+        WatchEvent::Removed { resource, revision } => format!("Revision {revision}: removed  {}  {}", resource.id(), summarize(resource)),
+    }
+}
+
+impl WatchCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let mut events = carl.observer.watch(self.resource.into(), self.resume_revision.map(Revision::from)).await
+            .map_err(|error| format!("Could not watch resources.\n  {error}"))?;
+
+        while let Some(event) = events.next().await {
+            let event = event.map_err(|error| format!("Could not receive modifications of resources.\n  {error}"))?;
+
+            match self.output {
+                WatchOutputFormat::Text => {
+                    println!("{}", format_text(&event));
+                }
+                WatchOutputFormat::Json => {
+                    let json = serde_json::to_string(&SerializableWatchEvent::from(event)).unwrap();
+                    println!("{}", json);
+                }
+            }
+        }
+        Err(String::from("CARL closed the connection."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_carl_api::carl::observer::WatchSnapshotEntry;
+
+    use super::*;
+
+    #[test]
+    fn should_format_snapshot_as_text() {
+        let peer_id = PeerId::random();
+        let event = WatchEvent::Snapshot {
+            entries: vec![
+                WatchSnapshotEntry {
+                    id: peer_id.uuid,
+                    revision: Revision(3),
+                    resource: Some(WatchedResource::PeerConnectionState { peer_id, state: PeerConnectionState::Offline }),
+                },
+            ],
+            revision: Revision(5),
+        };
+
+        assert_that!(
+            format_text(&event),
+            eq(&format!("Snapshot at revision 5 with 1 resource(s):\n  3  {}  offline", peer_id.uuid))
+        );
+    }
+}
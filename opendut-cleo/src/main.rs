@@ -11,6 +11,7 @@ use opendut_util::settings::{load_config, FileFormat, LoadedConfig};
 
 mod commands;
 pub mod parse;
+mod resolve;
 
 type Error = String;
 type Result<T> = std::result::Result<T, Error>;
@@ -39,8 +40,12 @@ enum Commands {
         #[arg(global = true, value_enum, short, long, default_value_t=ListOutputFormat::Table)]
         output: ListOutputFormat,
     },
-    /// Create openDuT resources from specifications files
+    /// Create, update or delete openDuT resources, so that they match the specification files
     Apply(commands::apply::ApplyCli),
+    /// Show the changes, which `apply` would make for the specification files
+    Diff(commands::diff::DiffCli),
+    Get(commands::get::GetCli),
+    Schema(commands::schema::SchemaCli),
     ///Create openDuT resource
     Create {
         #[command(subcommand)]
@@ -51,6 +56,7 @@ enum Commands {
     },
     GenerateSetupString(commands::generate_setup_string::GenerateSetupStringCli),
     DecodeSetupString(commands::decode_setup_string::DecodeSetupStringCli),
+    RevokeSetupString(commands::revoke_setup_string::RevokeSetupStringCli),
     ///Describe openDuT resource
     Describe {
         ///Name of openDuT resource
@@ -75,11 +81,31 @@ enum Commands {
         #[command(subcommand)]
         resource: DeleteResource,
     },
+    ///Revoke all access of an openDuT resource
+    Decommission {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: DecommissionResource,
+    },
+    ///Replace the credentials of an openDuT resource
+    Rotate {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: RotateResource,
+    },
     /// Wait for a resource
     Await {
         #[command(subcommand)]
         resource: AwaitResource,
     },
+    Watch(commands::watch::WatchCli),
+    ///Show the audit log of modifications to openDuT resources
+    Audit(commands::audit::AuditCli),
+    ExportSnapshot(commands::export_snapshot::ExportSnapshotCli),
+    ReconcileVpn(commands::reconcile_vpn::ReconcileVpnCli),
+    Notification(commands::notification::NotificationCli),
+    Token(commands::token::TokenCli),
+    Tui(commands::tui::TuiCli),
     ///Show the configuration that CLEO currently uses
     Config,
     /// Generates shell completion
@@ -97,6 +123,7 @@ enum ListResource {
     Peers(commands::peer::list::ListPeersCli),
     Devices(commands::device::list::ListDevicesCli),
     ContainerExecutor(commands::executor::list::ListContainerExecutorCli),
+    SetupStrings(commands::setup_string::list::ListSetupStringsCli),
 }
 
 #[derive(clap::Args)]
@@ -154,6 +181,16 @@ enum DeleteResource {
     Device(commands::device::delete::DeleteDeviceCli),
 }
 
+#[derive(Subcommand)]
+enum DecommissionResource {
+    Peer(commands::peer::decommission::DecommissionPeerCli),
+}
+
+#[derive(Subcommand)]
+enum RotateResource {
+    PeerCredentials(commands::peer::rotate_credentials::RotatePeerCredentialsCli),
+}
+
 #[derive(Subcommand)]
 enum AwaitResource {
     PeerOnline(commands::wait::peer_online::WaitPeerOnlineCli),
@@ -250,12 +287,26 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 ListResource::Devices(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                ListResource::SetupStrings(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
             }
         }
         Commands::Apply(implementation) => {
             let mut carl = create_carl_client(&settings.config).await;
             implementation.execute(&mut carl).await?;
         }
+        Commands::Diff(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::Get(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::Schema(implementation) => {
+            implementation.execute()?;
+        }
         Commands::Create { resource, output } => {
             match *resource {
                 CreateResource::ClusterConfiguration(implementation) => {
@@ -295,6 +346,10 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
         Commands::DecodeSetupString(implementation) => {
             implementation.execute().await?;
         }
+        Commands::RevokeSetupString(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
         Commands::Describe { resource, output } => {
             let mut carl = create_carl_client(&settings.config).await;
             match resource {
@@ -332,6 +387,23 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 }
             }
         }
+        Commands::Decommission { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                DecommissionResource::Peer(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+            }
+        }
+        Commands::Rotate { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                RotateResource::PeerCredentials(implementation) => {
+                    let cleo_oidc_client_id = get_cleo_oidc_client_id(&settings.config).await;
+                    implementation.execute(&mut carl, cleo_oidc_client_id).await?;
+                }
+            }
+        }
         Commands::Find { resource, output } => {
             let mut carl = create_carl_client(&settings.config).await;
             match resource {
@@ -340,6 +412,34 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 }
             }
         }
+        Commands::Audit(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::Watch(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::ExportSnapshot(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::ReconcileVpn(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::Notification(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::Token(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
+        Commands::Tui(implementation) => {
+            let mut carl = create_carl_client(&settings.config).await;
+            implementation.execute(&mut carl).await?;
+        }
         Commands::Config => {
             println!("Active CLEO configuration: {:?}", settings);
         }
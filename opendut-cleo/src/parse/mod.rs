@@ -1,4 +1,5 @@
 pub mod cluster;
+pub mod reference;
 
 use std::str::FromStr;
 
@@ -0,0 +1,60 @@
+use std::fmt;
+
+use uuid::Uuid;
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::topology::DeviceId;
+
+use super::*;
+
+/// Reference to a resource, either by its ID or by its name.
+/// A value which can be parsed as UUID is treated as ID.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reference<Id> {
+    Id(Id),
+    Name(String),
+}
+
+pub type PeerReference = Reference<PeerId>;
+pub type ClusterReference = Reference<ClusterId>;
+pub type DeviceReference = Reference<DeviceId>;
+
+impl<Id: From<Uuid>> FromStr for Reference<Id> {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.trim().is_empty() {
+            return Err(ParseError::new::<Self>(value, "Expected an ID or a name, but got an empty value."));
+        }
+        match Uuid::parse_str(value) {
+            Ok(uuid) => Ok(Reference::Id(Id::from(uuid))),
+            Err(_) => Ok(Reference::Name(value.to_owned())),
+        }
+    }
+}
+
+impl<Id: fmt::Display> fmt::Display for Reference<Id> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reference::Id(id) => write!(f, "<{id}>"),
+            Reference::Name(name) => write!(f, "'{name}'"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[test]
+    fn should_parse_uuid_as_id_and_anything_else_as_name() -> Result<()> {
+        let id = PeerId::random();
+
+        verify_that!(PeerReference::from_str(&id.to_string()), ok(eq(&Reference::Id(id))))?;
+        verify_that!(PeerReference::from_str("my-peer"), ok(eq(&Reference::Name(String::from("my-peer")))))?;
+        verify_that!(PeerReference::from_str(" "), err(anything()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::topology::DeviceId;
+
+use crate::parse::reference::{ClusterReference, DeviceReference, PeerReference, Reference};
+
+/// Looks up the ID of the resource with the given name.
+/// Returns `None`, if no resource has this name, and an error, if multiple resources have it.
+pub fn find_by_name<Id: Display>(kind: &str, name: &str, candidates: impl IntoIterator<Item=(Id, String)>) -> crate::Result<Option<Id>> {
+    let mut matches = candidates.into_iter()
+        .filter(|(_, candidate)| candidate == name)
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+
+    match matches.len() {
+        0 => Ok(None),
+        1 => Ok(matches.pop()),
+        _ => {
+            let ids = matches.iter()
+                .map(|id| format!("<{id}>"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(format!("The {kind} name '{name}' is ambiguous, as multiple {kind}s have this name: {ids}. Reference the {kind} by its ID instead."))
+        }
+    }
+}
+
+/// Like [`find_by_name`], but fails if no resource has the given name.
+pub fn resolve_by_name<Id: Display>(kind: &str, name: &str, candidates: impl IntoIterator<Item=(Id, String)>) -> crate::Result<Id> {
+    find_by_name(kind, name, candidates)?
+        .ok_or_else(|| format!("Could not find a {kind} with the name '{name}'."))
+}
+
+pub async fn resolve_peer(carl: &mut CarlClient, reference: PeerReference) -> crate::Result<PeerId> {
+    match reference {
+        Reference::Id(id) => Ok(id),
+        Reference::Name(name) => {
+            let peers = carl.peers.list_peer_descriptors().await
+                .map_err(|error| format!("Could not list peers.\n  {error}"))?;
+
+            resolve_by_name("peer", &name, peers.into_iter().map(|peer| (peer.id, peer.name.value())))
+        }
+    }
+}
+
+pub async fn resolve_cluster(carl: &mut CarlClient, reference: ClusterReference) -> crate::Result<ClusterId> {
+    match reference {
+        Reference::Id(id) => Ok(id),
+        Reference::Name(name) => {
+            let configurations = carl.cluster.list_cluster_configurations().await
+                .map_err(|error| format!("Could not list cluster configurations.\n  {error}"))?;
+
+            resolve_by_name("cluster", &name, configurations.into_iter().map(|configuration| (configuration.id, configuration.name.value())))
+        }
+    }
+}
+
+pub async fn resolve_device(carl: &mut CarlClient, reference: DeviceReference) -> crate::Result<DeviceId> {
+    match reference {
+        Reference::Id(id) => Ok(id),
+        Reference::Name(name) => {
+            let devices = carl.peers.list_devices().await
+                .map_err(|error| format!("Could not list devices.\n  {error}"))?;
+
+            resolve_by_name("device", &name, devices.into_iter().map(|device| (device.id, device.name.to_string())))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[test]
+    fn should_find_the_single_resource_with_the_given_name() -> Result<()> {
+        let candidates = vec![(1, String::from("first")), (2, String::from("second"))];
+
+        verify_that!(find_by_name("peer", "second", candidates.clone()), ok(some(eq(2))))?;
+        verify_that!(find_by_name("peer", "third", candidates.clone()), ok(none()))?;
+        verify_that!(resolve_by_name("peer", "third", candidates).unwrap_err(), contains_substring("Could not find a peer"))?;
+        Ok(())
+    }
+
+    #[test]
+    fn should_fail_if_the_name_is_ambiguous() -> Result<()> {
+        let candidates = vec![(1, String::from("same")), (2, String::from("same")), (3, String::from("other"))];
+
+        let error = find_by_name("device", "same", candidates).unwrap_err();
+
+        verify_that!(error, contains_substring("ambiguous"))?;
+        verify_that!(error, contains_substring("<1>, <2>"))?;
+        Ok(())
+    }
+}
@@ -47,6 +47,18 @@ enum Commands {
         #[arg(long, global=true, default_value="1538")]
         mtu: u16,
     },
+    /// Show the status of the running EDGAR Service
+    Status {
+        /// Print the status as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Detect problems with the EDGAR Service and suggest how to fix them
+    Doctor {
+        /// Print the problems as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -79,6 +91,8 @@ enum SetupMode {
         #[arg(long)]
         bridge: Option<NetworkInterfaceName>,
     },
+    /// Remove EDGAR from your system, reversing a managed setup
+    Uninstall,
 }
 
 pub async fn cli() -> anyhow::Result<()> {
@@ -125,10 +139,19 @@ pub async fn cli() -> anyhow::Result<()> {
                     let device_interfaces = HashSet::from_iter(device_interfaces);
                     setup::start::unmanaged(dry_run, no_confirm, management_url, setup_key, bridge, device_interfaces, leader, mtu).await?;
                 }
+                SetupMode::Uninstall => {
+                    setup::start::uninstall(dry_run, no_confirm).await?;
+                }
             };
             info!("EDGAR Setup finished!\n");
             Ok(())
         }
+        Commands::Status { json } => {
+            crate::diagnostics::status(json).await
+        }
+        Commands::Doctor { json } => {
+            crate::diagnostics::doctor(json).await
+        }
     }
 }
 
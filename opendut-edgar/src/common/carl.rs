@@ -48,6 +48,7 @@ pub async fn connect(settings: &Config) -> anyhow::Result<CarlClient> {
 pub async fn open_stream(
     self_id: PeerId,
     remote_address: &IpAddr,
+    vpn_endpoint_host: Option<IpAddr>,
     carl: &mut CarlClient,
 ) -> anyhow::Result<(broker::Downstream, broker::Upstream), broker::error::OpenStream> {
     debug!("Opening peer messaging stream...");
@@ -56,6 +57,7 @@ pub async fn open_stream(
         client_version: Some(stream_header::PeerVersion {
             value: crate::app_info::PKG_VERSION.to_owned()
         }),
+        vpn_endpoint_host,
     };
     let (rx_inbound, tx_outbound) = carl.broker.open_stream(self_id, remote_address, extra_headers).await?;
 
@@ -4,6 +4,7 @@ use opendut_types::util::net::NetworkInterfaceName;
 
 pub mod carl;
 pub mod settings;
+pub mod status;
 pub mod task;
 
 pub fn default_bridge_name() -> NetworkInterfaceName {
@@ -7,14 +7,57 @@ pub mod key {
     pub mod peer {
         pub const id: &str = "peer.id";
     }
+    pub mod network {
+        pub mod tls {
+            pub mod client {
+                pub const certificate: &str = "network.tls.client.certificate";
+                pub const key: &str = "network.tls.client.key";
+            }
+        }
+    }
+    pub mod status {
+        pub mod socket {
+            pub const path: &str = "status.socket.path";
+        }
+    }
+    pub mod update {
+        pub const policy: &str = "update.policy";
+
+        pub mod verification {
+            pub const key: &str = "update.verification.key";
+        }
+        pub mod reconnect {
+            pub mod timeout {
+                pub const ms: &str = "update.reconnect.timeout.ms";
+            }
+        }
+    }
     pub mod vpn {
         pub const table: &str = "vpn";
+        pub const kind: &str = "vpn.kind";
 
         pub mod disabled {
             pub mod remote {
                 pub const host: &str = "vpn.disabled.remote.host";
             }
         }
+        pub mod wireguard {
+            pub const table: &str = "vpn.wireguard";
+            pub const address: &str = "vpn.wireguard.address";
+
+            pub mod private {
+                pub const key: &str = "vpn.wireguard.private.key";
+            }
+            pub mod prefix {
+                pub const length: &str = "vpn.wireguard.prefix.length";
+            }
+            pub mod listen {
+                pub const port: &str = "vpn.wireguard.listen.port";
+            }
+            pub mod endpoint {
+                pub const host: &str = "vpn.wireguard.endpoint.host";
+            }
+        }
     }
 }
 
@@ -23,7 +66,9 @@ pub fn default_config_file_path() -> PathBuf {
 }
 
 pub fn load_with_overrides(overrides: config::Config) -> anyhow::Result<LoadedConfig> {
-    let edgar_config_hide_secrets_override = opendut_util::settings::Config::default();
+    let edgar_config_hide_secrets_override = opendut_util::settings::Config::builder()
+        .set_override(key::vpn::wireguard::private::key, "redacted")?
+        .build()?;
 
     Ok(opendut_util::settings::load_config("edgar", include_str!("../../edgar.toml"), config::FileFormat::Toml, overrides, edgar_config_hide_secrets_override)?)
 }
@@ -0,0 +1,273 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use opendut_netbird_client_api::extension::LocalPeerStateExtension;
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_util::settings::LoadedConfig;
+
+use crate::common::settings;
+use crate::fs;
+use crate::service::vpn::{wireguard, VpnConfig, VpnKind};
+
+/// Status of an EDGAR, as reported by the running service via its status socket.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StatusReport {
+    pub version: String,
+    pub peer_id: Option<PeerId>,
+    pub service: ServiceReachability,
+    pub carl: CarlConnectionState,
+    pub vpn: VpnState,
+    pub cluster: Option<ClusterState>,
+    pub interface_management_enabled: bool,
+    pub interfaces: Vec<InterfaceState>,
+    pub processes: Vec<ProcessState>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "kebab-case")]
+pub enum ServiceReachability {
+    Running,
+    Unreachable { socket: PathBuf, cause: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "kebab-case")]
+pub enum CarlConnectionState {
+    /// The service is not reachable, so the connection state is not known.
+    Unknown,
+    Connecting,
+    Connected,
+    Disconnected { reason: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "kebab-case")]
+pub enum VpnState {
+    Disabled,
+    Unavailable { cause: String },
+    Running {
+        local_ip: Option<String>,
+        management_connected: bool,
+        signal_connected: bool,
+        peers_connected: usize,
+        peers_total: usize,
+    },
+    WireGuard {
+        local_ip: Option<String>,
+        interface_present: bool,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClusterState {
+    pub id: ClusterId,
+    pub leader: PeerId,
+    pub peers: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceState {
+    pub name: String,
+    pub kind: InterfaceKind,
+    /// Operational state as reported by the kernel, e.g. `up`, `down` or `unknown`.
+    pub operational_state: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum InterfaceKind {
+    EthernetBridge,
+    CanBridge,
+    Gre,
+}
+
+impl std::fmt::Display for InterfaceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InterfaceKind::EthernetBridge => f.pad("ethernet-bridge"),
+            InterfaceKind::CanBridge => f.pad("can-bridge"),
+            InterfaceKind::Gre => f.pad("gre"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProcessState {
+    pub pid: u32,
+    pub name: String,
+    pub command_line: String,
+}
+
+pub fn socket_path(settings: &LoadedConfig) -> anyhow::Result<PathBuf> {
+    let path = settings.config.get::<PathBuf>(settings::key::status::socket::path)?;
+    Ok(path)
+}
+
+pub fn interface_management_enabled(settings: &LoadedConfig) -> bool {
+    settings.config.get::<bool>("network.interface.management.enabled")
+        .unwrap_or(false)
+}
+
+pub async fn determine_vpn_state(settings: &LoadedConfig) -> VpnState {
+    let vpn_config = VpnConfig::load(settings)
+        .unwrap_or(VpnConfig { enabled: true, kind: VpnKind::default() });
+
+    if !vpn_config.enabled {
+        return VpnState::Disabled;
+    }
+
+    if vpn_config.is_wireguard() {
+        return VpnState::WireGuard {
+            local_ip: wireguard::WireGuardConfig::load(settings).ok()
+                .map(|config| config.address.to_string()),
+            interface_present: Path::new("/sys/class/net").join(wireguard::INTERFACE_NAME).exists(),
+        };
+    }
+
+    let status = async {
+        let mut client = opendut_netbird_client_api::client::Client::connect().await?;
+        client.full_status().await
+    }.await;
+
+    match status {
+        Ok(status) => VpnState::Running {
+            local_ip: status.local_peer_state.as_ref()
+                .and_then(|state| state.local_ip().ok())
+                .map(|ip| ip.to_string()),
+            management_connected: status.management_state.as_ref().is_some_and(|state| state.connected),
+            signal_connected: status.signal_state.as_ref().is_some_and(|state| state.connected),
+            peers_connected: status.peers.iter().filter(|peer| peer.conn_status == "Connected").count(),
+            peers_total: status.peers.len(),
+        },
+        Err(cause) => VpnState::Unavailable { cause: cause.to_string() },
+    }
+}
+
+/// Lists the network interfaces, which EDGAR creates, by their name prefix.
+pub fn list_managed_interfaces() -> Vec<InterfaceState> {
+    list_managed_interfaces_in(Path::new("/sys/class/net"))
+}
+
+fn list_managed_interfaces_in(sys_class_net: &Path) -> Vec<InterfaceState> {
+    let Ok(entries) = fs::read_dir(sys_class_net) else {
+        debug!("Could not read network interfaces from '{}'.", sys_class_net.display());
+        return Vec::new();
+    };
+
+    let can_bridge_name = crate::common::default_can_bridge_name().name();
+    let bridge_name = crate::common::default_bridge_name().name();
+
+    let mut interfaces = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+
+            let kind = if name == can_bridge_name {
+                InterfaceKind::CanBridge
+            } else if name == bridge_name {
+                InterfaceKind::EthernetBridge
+            } else if name.starts_with(crate::service::network_interface::gre::GRE_INTERFACE_NAME_PREFIX) {
+                InterfaceKind::Gre
+            } else {
+                return None;
+            };
+
+            let operational_state = fs::read_to_string(entry.path().join("operstate"))
+                .map(|state| state.trim().to_owned())
+                .unwrap_or_else(|_| String::from("unknown"));
+
+            Some(InterfaceState { name, kind, operational_state })
+        })
+        .collect::<Vec<_>>();
+
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+/// Processes, which EDGAR spawns for routing CAN frames or running executors.
+const MONITORED_PROCESSES: [&str; 3] = ["cannelloni", "podman", "docker"];
+
+pub fn list_processes() -> Vec<ProcessState> {
+    list_processes_in(Path::new("/proc"))
+}
+
+fn list_processes_in(proc: &Path) -> Vec<ProcessState> {
+    let Ok(entries) = fs::read_dir(proc) else {
+        debug!("Could not read processes from '{}'.", proc.display());
+        return Vec::new();
+    };
+
+    let mut processes = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_string_lossy().parse::<u32>().ok()?;
+            let name = fs::read_to_string(entry.path().join("comm")).ok()?.trim().to_owned();
+
+            if !MONITORED_PROCESSES.contains(&name.as_str()) {
+                return None;
+            }
+
+            let command_line = fs::read(entry.path().join("cmdline"))
+                .map(|command_line| {
+                    command_line.split(|byte| *byte == 0)
+                        .filter(|argument| !argument.is_empty())
+                        .map(String::from_utf8_lossy)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+
+            Some(ProcessState { pid, name, command_line })
+        })
+        .collect::<Vec<_>>();
+
+    processes.sort_by_key(|process| process.pid);
+    processes
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_list_only_interfaces_managed_by_edgar() -> anyhow::Result<()> {
+        let sys_class_net = tempfile::tempdir()?;
+        for (name, state) in [("br-opendut", "up\n"), ("gre-opendut0", "down\n"), ("eth0", "up\n")] {
+            let interface = sys_class_net.path().join(name);
+            fs::create_dir(&interface)?;
+            fs::write(interface.join("operstate"), state)?;
+        }
+
+        let interfaces = list_managed_interfaces_in(sys_class_net.path());
+
+        assert_that!(interfaces, elements_are![
+            eq(&InterfaceState { name: String::from("br-opendut"), kind: InterfaceKind::EthernetBridge, operational_state: String::from("up") }),
+            eq(&InterfaceState { name: String::from("gre-opendut0"), kind: InterfaceKind::Gre, operational_state: String::from("down") }),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_list_monitored_processes_with_their_command_line() -> anyhow::Result<()> {
+        let proc = tempfile::tempdir()?;
+        for (pid, name, command_line) in [("42", "cannelloni\n", "cannelloni\0-I\0br-vcan-opendut\0"), ("7", "bash\n", "bash\0")] {
+            let process = proc.path().join(pid);
+            fs::create_dir(&process)?;
+            fs::write(process.join("comm"), name)?;
+            fs::write(process.join("cmdline"), command_line)?;
+        }
+        fs::create_dir(proc.path().join("self"))?;
+
+        let processes = list_processes_in(proc.path());
+
+        assert_that!(processes, elements_are![
+            eq(&ProcessState { pid: 42, name: String::from("cannelloni"), command_line: String::from("cannelloni -I br-vcan-opendut") }),
+        ]);
+        Ok(())
+    }
+}
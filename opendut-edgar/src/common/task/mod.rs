@@ -13,6 +13,12 @@ pub trait Task: Send + Sync {
 
     /// Make changes to the host system.
     async fn execute(&self) -> anyhow::Result<Success>;
+
+    /// Task which reverses the changes made by this task, when EDGAR is uninstalled.
+    /// Tasks, which do not leave changes on the host system, do not provide one.
+    fn undo(&self) -> Option<Box<dyn Task>> {
+        None
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -0,0 +1,276 @@
+use crate::common::status::{CarlConnectionState, InterfaceKind, ServiceReachability, StatusReport, VpnState};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    pub summary: String,
+    pub remediation: String,
+}
+impl Problem {
+    fn new(summary: impl Into<String>, remediation: impl Into<String>) -> Self {
+        Self { summary: summary.into(), remediation: remediation.into() }
+    }
+}
+
+pub fn diagnose(report: &StatusReport) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    if report.peer_id.is_none() {
+        problems.push(Problem::new(
+            "EDGAR has not been set up, as no peer ID is configured.",
+            "Run `opendut-edgar setup managed <SETUP-STRING>` with a setup string from LEA or CLEO.",
+        ));
+    }
+
+    if let ServiceReachability::Unreachable { socket, cause } = &report.service {
+        problems.push(Problem::new(
+            format!("The EDGAR service could not be reached via '{}': {cause}", socket.display()),
+            "Check whether the service is running with `systemctl status opendut-edgar` and inspect its log with `journalctl -u opendut-edgar`. \
+            When permission was denied, run this command with `sudo`.",
+        ));
+    }
+
+    match &report.carl {
+        CarlConnectionState::Connecting => problems.push(Problem::new(
+            "EDGAR is not connected to CARL yet.",
+            "Check that the host configured under `network.carl` in `/etc/opendut/edgar.toml` is reachable, \
+            and that the CA certificate matches the one of CARL.",
+        )),
+        CarlConnectionState::Disconnected { reason } => problems.push(Problem::new(
+            format!("EDGAR lost its connection to CARL: {reason}"),
+            "The service restarts automatically and reconnects. If this persists, check the network connection to CARL.",
+        )),
+        CarlConnectionState::Unknown | CarlConnectionState::Connected => {}
+    }
+
+    match &report.vpn {
+        VpnState::Disabled => {}
+        VpnState::Unavailable { cause } => problems.push(Problem::new(
+            format!("The NetBird client could not be queried: {cause}"),
+            "Check whether the NetBird client is running with `systemctl status netbird`. Re-run `opendut-edgar setup` to reinstall it.",
+        )),
+        VpnState::WireGuard { local_ip, interface_present } => {
+            if local_ip.is_none() {
+                problems.push(Problem::new(
+                    "No WireGuard address is configured for this peer.",
+                    "Re-run `opendut-edgar setup managed <SETUP-STRING>` with a setup string from LEA or CLEO.",
+                ));
+            }
+            if !interface_present {
+                problems.push(Problem::new(
+                    format!("The WireGuard interface '{}' does not exist.", crate::service::vpn::wireguard::INTERFACE_NAME),
+                    "The service creates it on startup. Inspect its log with `journalctl -u opendut-edgar`.",
+                ));
+            }
+        }
+        VpnState::Running { local_ip, management_connected, signal_connected, peers_connected, .. } => {
+            if local_ip.is_none() {
+                problems.push(Problem::new(
+                    "The NetBird client has no address in the VPN and may not be logged in.",
+                    "Re-run `opendut-edgar setup managed <SETUP-STRING>` to log into the VPN again.",
+                ));
+            }
+            if !management_connected {
+                problems.push(Problem::new(
+                    "The NetBird client is not connected to the NetBird management service.",
+                    "Check that the management URL shown by `netbird status` is reachable from this host.",
+                ));
+            }
+            if !signal_connected {
+                problems.push(Problem::new(
+                    "The NetBird client is not connected to the NetBird signal service.",
+                    "Check that the signal URL shown by `netbird status` is reachable from this host.",
+                ));
+            }
+            if let Some(cluster) = &report.cluster {
+                let remote_peers = cluster.peers.saturating_sub(1);
+                if *peers_connected < remote_peers {
+                    problems.push(Problem::new(
+                        format!("Only {peers_connected} of the {remote_peers} other peers in the cluster are connected via the VPN."),
+                        "Check that the other peers are online. `netbird status --detail` shows the connection state of each VPN peer.",
+                    ));
+                }
+            }
+        }
+    }
+
+    if report.interface_management_enabled {
+        diagnose_interfaces(report, &mut problems);
+    }
+
+    problems
+}
+
+fn diagnose_interfaces(report: &StatusReport, problems: &mut Vec<Problem>) {
+    let count = |kind: InterfaceKind| report.interfaces.iter().filter(|interface| interface.kind == kind).count();
+
+    for interface in &report.interfaces {
+        if interface.operational_state == "down" {
+            problems.push(Problem::new(
+                format!("The network interface '{}' is down.", interface.name),
+                format!("Bring it up with `ip link set {} up` or redeploy the cluster.", interface.name),
+            ));
+        }
+    }
+
+    match &report.cluster {
+        Some(cluster) => {
+            if count(InterfaceKind::EthernetBridge) == 0 {
+                problems.push(Problem::new(
+                    format!("EDGAR is assigned to cluster <{}>, but the Ethernet bridge does not exist.", cluster.id),
+                    "Restart the service with `systemctl restart opendut-edgar` or redeploy the cluster. Check the service log for errors.",
+                ));
+            }
+
+            let expected_gre_interfaces = if report.peer_id == Some(cluster.leader) {
+                cluster.peers.saturating_sub(1)
+            } else {
+                usize::from(cluster.peers > 1)
+            };
+            let gre_interfaces = count(InterfaceKind::Gre);
+            if gre_interfaces < expected_gre_interfaces {
+                problems.push(Problem::new(
+                    format!("Expected {expected_gre_interfaces} GRE interface(s) for cluster <{}>, but found {gre_interfaces}.", cluster.id),
+                    "Check that the VPN is connected, then redeploy the cluster. Check the service log for errors.",
+                ));
+            }
+
+            let cannelloni_processes = report.processes.iter().filter(|process| process.name == "cannelloni").count();
+            if count(InterfaceKind::CanBridge) > 0 && cluster.peers > 1 && cannelloni_processes == 0 {
+                problems.push(Problem::new(
+                    "The CAN bridge exists, but no cannelloni process forwards CAN frames to the other peers.",
+                    "Check that cannelloni is installed with `which cannelloni`. Check the service log for errors.",
+                ));
+            }
+        }
+        None => {
+            if count(InterfaceKind::Gre) > 0 {
+                problems.push(Problem::new(
+                    "GRE interfaces exist, although EDGAR is not assigned to a cluster.",
+                    "They are left over from a previous deployment. Restart the service with `systemctl restart opendut-edgar` to clean them up.",
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::ClusterId;
+    use opendut_types::peer::PeerId;
+
+    use crate::common::status::{ClusterState, InterfaceState};
+
+    use super::*;
+
+    fn healthy_report() -> StatusReport {
+        StatusReport {
+            version: String::from("0.0.0"),
+            peer_id: Some(PeerId::random()),
+            service: ServiceReachability::Running,
+            carl: CarlConnectionState::Connected,
+            vpn: VpnState::Running {
+                local_ip: Some(String::from("100.64.0.1")),
+                management_connected: true,
+                signal_connected: true,
+                peers_connected: 1,
+                peers_total: 1,
+            },
+            cluster: None,
+            interface_management_enabled: true,
+            interfaces: vec![],
+            processes: vec![],
+        }
+    }
+
+    fn interface(name: &str, kind: InterfaceKind, operational_state: &str) -> InterfaceState {
+        InterfaceState { name: name.to_owned(), kind, operational_state: operational_state.to_owned() }
+    }
+
+    fn summaries(problems: Vec<Problem>) -> Vec<String> {
+        problems.into_iter().map(|problem| problem.summary).collect()
+    }
+
+    #[test]
+    fn should_detect_no_problems_in_a_healthy_report() {
+        assert_that!(diagnose(&healthy_report()), empty());
+    }
+
+    #[test]
+    fn should_detect_unreachable_service_and_missing_setup() {
+        let report = StatusReport {
+            peer_id: None,
+            service: ServiceReachability::Unreachable { socket: "/run/opendut-edgar/status.sock".into(), cause: String::from("No such file or directory") },
+            carl: CarlConnectionState::Unknown,
+            vpn: VpnState::Disabled,
+            ..healthy_report()
+        };
+
+        let problems = summaries(diagnose(&report));
+
+        assert_that!(problems, elements_are![
+            contains_substring("not been set up"),
+            contains_substring("could not be reached"),
+        ]);
+    }
+
+    #[test]
+    fn should_detect_missing_interfaces_of_a_cluster_member() {
+        let report = healthy_report();
+        let report = StatusReport {
+            cluster: Some(ClusterState { id: ClusterId::random(), leader: PeerId::random(), peers: 2 }),
+            interfaces: vec![interface("br-opendut", InterfaceKind::EthernetBridge, "down")],
+            ..report
+        };
+
+        let problems = summaries(diagnose(&report));
+
+        assert_that!(problems, elements_are![
+            contains_substring("'br-opendut' is down"),
+            contains_substring("Expected 1 GRE interface(s)"),
+        ]);
+    }
+
+    #[test]
+    fn should_expect_a_gre_interface_per_remote_peer_on_the_leader() {
+        let report = healthy_report();
+        let leader = report.peer_id.unwrap();
+        let report = StatusReport {
+            cluster: Some(ClusterState { id: ClusterId::random(), leader, peers: 3 }),
+            vpn: VpnState::Running {
+                local_ip: Some(String::from("100.64.0.1")),
+                management_connected: true,
+                signal_connected: true,
+                peers_connected: 2,
+                peers_total: 2,
+            },
+            interfaces: vec![
+                interface("br-opendut", InterfaceKind::EthernetBridge, "up"),
+                interface("gre-opendut0", InterfaceKind::Gre, "unknown"),
+            ],
+            ..report
+        };
+
+        let problems = summaries(diagnose(&report));
+
+        assert_that!(problems, elements_are![
+            contains_substring("Expected 2 GRE interface(s)"),
+        ]);
+    }
+
+    #[test]
+    fn should_detect_disconnected_vpn_peers_of_the_cluster() {
+        let report = StatusReport {
+            cluster: Some(ClusterState { id: ClusterId::random(), leader: PeerId::random(), peers: 3 }),
+            interface_management_enabled: false,
+            ..healthy_report()
+        };
+
+        let problems = summaries(diagnose(&report));
+
+        assert_that!(problems, elements_are![
+            eq("Only 1 of the 2 other peers in the cluster are connected via the VPN."),
+        ]);
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::anyhow;
+use tokio::io::AsyncReadExt;
+use tokio::net::UnixStream;
+
+use opendut_types::peer::PeerId;
+use opendut_util::settings::LoadedConfig;
+
+use crate::common::settings;
+use crate::common::status::{self, CarlConnectionState, ServiceReachability, StatusReport, VpnState};
+
+mod doctor;
+
+/// Prints the status of the running EDGAR service.
+pub async fn status(json: bool) -> anyhow::Result<()> {
+    let report = request_report().await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{}", format_status(&report));
+    }
+    Ok(())
+}
+
+/// Prints the problems detected in the status of the EDGAR service together with hints how to fix them.
+/// Fails when problems were detected, so that it can be used in scripts.
+pub async fn doctor(json: bool) -> anyhow::Result<()> {
+    let report = request_report().await?;
+    let problems = doctor::diagnose(&report);
+
+    if json {
+        let problems = problems.iter()
+            .map(|problem| serde_json::json!({ "summary": problem.summary, "remediation": problem.remediation }))
+            .collect::<Vec<_>>();
+        println!("{}", serde_json::to_string_pretty(&problems)?);
+    } else if problems.is_empty() {
+        println!("No problems detected.");
+    } else {
+        for problem in &problems {
+            println!("✗ {}\n  → {}\n", problem.summary, problem.remediation);
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("Detected {} problem(s).", problems.len()))
+    }
+}
+
+/// Requests the report from the service. If it is not reachable,
+/// the information available without the service is collected instead, to still allow diagnosing problems.
+async fn request_report() -> anyhow::Result<StatusReport> {
+    let settings = settings::load_with_overrides(config::Config::default())?;
+    let socket_path = status::socket_path(&settings)?;
+
+    match request_report_via_socket(&socket_path).await {
+        Ok(report) => Ok(report),
+        Err(cause) => Ok(collect_report_without_service(&settings, &socket_path, cause).await),
+    }
+}
+
+async fn request_report_via_socket(socket_path: &Path) -> anyhow::Result<StatusReport> {
+    let mut stream = UnixStream::connect(socket_path).await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+
+    let report = serde_json::from_str(&response)
+        .map_err(|cause| anyhow!("Failed to parse status report of EDGAR service: {cause}"))?;
+    Ok(report)
+}
+
+async fn collect_report_without_service(settings: &LoadedConfig, socket_path: &Path, cause: anyhow::Error) -> StatusReport {
+    let peer_id = settings.config.get::<PeerId>(settings::key::peer::id).ok();
+
+    StatusReport {
+        version: crate::app_info::PKG_VERSION.to_owned(),
+        peer_id,
+        service: ServiceReachability::Unreachable { socket: socket_path.to_owned(), cause: cause.to_string() },
+        carl: CarlConnectionState::Unknown,
+        vpn: status::determine_vpn_state(settings).await,
+        cluster: None,
+        interface_management_enabled: status::interface_management_enabled(settings),
+        interfaces: status::list_managed_interfaces(),
+        processes: status::list_processes(),
+    }
+}
+
+fn format_status(report: &StatusReport) -> String {
+    let mut text = String::new();
+
+    let peer_id = report.peer_id.map(|id| id.to_string()).unwrap_or_else(|| String::from("not configured"));
+    let service = match &report.service {
+        ServiceReachability::Running => String::from("running"),
+        ServiceReachability::Unreachable { socket, cause } => format!("not reachable via '{}' ({cause})", socket.display()),
+    };
+    let carl = match &report.carl {
+        CarlConnectionState::Unknown => String::from("unknown"),
+        CarlConnectionState::Connecting => String::from("connecting"),
+        CarlConnectionState::Connected => String::from("connected"),
+        CarlConnectionState::Disconnected { reason } => format!("disconnected ({reason})"),
+    };
+    let vpn = match &report.vpn {
+        VpnState::Disabled => String::from("disabled"),
+        VpnState::Unavailable { cause } => format!("unavailable ({cause})"),
+        VpnState::WireGuard { local_ip, interface_present } => format!(
+            "WireGuard, address {}, interface {}",
+            local_ip.as_deref().unwrap_or("none"),
+            if *interface_present { "present" } else { "missing" },
+        ),
+        VpnState::Running { local_ip, management_connected, signal_connected, peers_connected, peers_total } => {
+            let connected = |connected: &bool| if *connected { "connected" } else { "disconnected" };
+            format!(
+                "address {}, management {}, signal {}, {peers_connected}/{peers_total} peers connected",
+                local_ip.as_deref().unwrap_or("none"),
+                connected(management_connected),
+                connected(signal_connected),
+            )
+        }
+    };
+    let cluster = match &report.cluster {
+        Some(cluster) => {
+            let role = if report.peer_id == Some(cluster.leader) { "leader" } else { "member" };
+            format!("<{}> as {role}, with {} peer(s)", cluster.id, cluster.peers)
+        }
+        None => String::from("none"),
+    };
+
+    let _ = writeln!(text, "EDGAR {}", report.version);
+    let _ = writeln!(text, "  Peer ID:     {peer_id}");
+    let _ = writeln!(text, "  Service:     {service}");
+    let _ = writeln!(text, "  CARL:        {carl}");
+    let _ = writeln!(text, "  VPN:         {vpn}");
+    let _ = writeln!(text, "  Cluster:     {cluster}");
+
+    if report.interface_management_enabled {
+        let _ = writeln!(text, "  Interfaces:");
+        if report.interfaces.is_empty() {
+            let _ = writeln!(text, "    none");
+        }
+        for interface in &report.interfaces {
+            let _ = writeln!(text, "    {:<16} {:<16} {}", interface.name, interface.kind, interface.operational_state);
+        }
+    } else {
+        let _ = writeln!(text, "  Interfaces:  not managed by EDGAR");
+    }
+
+    let _ = writeln!(text, "  Processes:");
+    if report.processes.is_empty() {
+        let _ = writeln!(text, "    none");
+    }
+    for process in &report.processes {
+        let _ = writeln!(text, "    {:<8} {}", process.pid, process.command_line);
+    }
+
+    text
+}
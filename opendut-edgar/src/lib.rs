@@ -5,6 +5,7 @@ mod cli;
 pub use cli::cli;
 
 mod common;
+mod diagnostics;
 mod setup;
 mod service;
 
@@ -18,6 +19,7 @@ pub mod testing {
     pub mod service {
         pub use crate::service::start;
         pub use crate::service::peer_configuration;
+        pub use crate::service::status;
     }
     pub mod carl {
         pub use crate::common::carl::connect;
@@ -0,0 +1,271 @@
+use std::io::Write;
+use std::os::unix::fs::{MetadataExt, OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+use opendut_types::util::net::{Certificate, ClientCertificate, ClientCredentials};
+
+use crate::fs;
+
+/// Replaces the OIDC client credentials in the configuration file, keeping all other settings as they are.
+/// EDGAR uses the new credentials the next time it connects to CARL.
+pub fn store_rotated_credentials(config_file: &Path, credentials: &ClientCredentials) -> anyhow::Result<()> {
+    let current_settings = fs::read_to_string(config_file)?;
+    let mut settings = toml_edit::DocumentMut::from_str(&current_settings)
+        .context(format!("Failed to parse configuration file at '{}' as TOML.", config_file.display()))?;
+
+    let client = settings.get_mut("network")
+        .and_then(|network| network.get_mut("oidc"))
+        .and_then(|oidc| oidc.get_mut("client"))
+        .and_then(|client| client.as_table_like_mut())
+        .ok_or(anyhow!("No OIDC client configured in '{}'. Not storing rotated credentials.", config_file.display()))?;
+
+    client.insert("id", toml_edit::value(Clone::clone(&credentials.client_id).value()));
+    client.insert("secret", toml_edit::value(Clone::clone(&credentials.client_secret).value()));
+
+    replace_file(config_file, settings.to_string().as_bytes(), Secrecy::Secret)
+        .context(format!("Error while writing rotated credentials to configuration file at '{}'.", config_file.display()))?;
+    Ok(())
+}
+
+/// Replaces the client certificate at the paths given in the configuration file.
+/// EDGAR uses the new certificate the next time it connects to CARL.
+pub fn store_rotated_client_certificate(config_file: &Path, client_certificate: &ClientCertificate) -> anyhow::Result<()> {
+    let (certificate_path, key_path) = client_certificate_paths(config_file)?;
+
+    replace_file(&certificate_path, client_certificate.certificate.encode_as_string().as_bytes(), Secrecy::Public)
+        .context(format!("Error while writing rotated client certificate to '{}'.", certificate_path.display()))?;
+    replace_file(&key_path, client_certificate.key.encode_as_string().as_bytes(), Secrecy::Secret)
+        .context(format!("Error while writing rotated client certificate key to '{}'.", key_path.display()))?;
+    Ok(())
+}
+
+/// Writes the client certificate, which CARL issued for the key generated by EDGAR, to the path given in the configuration file.
+pub fn store_issued_client_certificate(config_file: &Path, certificate: &Certificate) -> anyhow::Result<()> {
+    let (certificate_path, _) = client_certificate_paths(config_file)?;
+
+    replace_file(&certificate_path, certificate.encode_as_string().as_bytes(), Secrecy::Public)
+        .context(format!("Error while writing issued client certificate to '{}'.", certificate_path.display()))?;
+    Ok(())
+}
+
+/// Determines the paths of the client certificate and its key from the configuration file.
+pub fn client_certificate_paths(config_file: &Path) -> anyhow::Result<(PathBuf, PathBuf)> {
+    let current_settings = fs::read_to_string(config_file)?;
+    let settings = toml_edit::DocumentMut::from_str(&current_settings)
+        .context(format!("Failed to parse configuration file at '{}' as TOML.", config_file.display()))?;
+
+    let configured_path = |key: &str| settings.get("network")
+        .and_then(|network| network.get("tls"))
+        .and_then(|tls| tls.get("client"))
+        .and_then(|client| client.get(key))
+        .and_then(|path| path.as_str())
+        .map(PathBuf::from)
+        .ok_or(anyhow!("No client certificate configured in '{}'.", config_file.display()));
+
+    Ok((configured_path("certificate")?, configured_path("key")?))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Secrecy {
+    /// Only the owner may read or modify the file.
+    Secret,
+    Public,
+}
+
+/// Writes the content to a temporary file next to the target and renames it afterwards,
+/// so that a crash while writing cannot leave EDGAR with truncated credentials.
+/// The ownership and permissions of the target are kept, as EDGAR may run as a different user than the one which owns its files.
+/// A target, which does not exist yet, is owned by the owner of its directory.
+fn replace_file(target: &Path, content: &[u8], secrecy: Secrecy) -> anyhow::Result<()> {
+    let temporary_path = {
+        let mut temporary_path = target.as_os_str().to_owned();
+        temporary_path.push(".tmp");
+        PathBuf::from(temporary_path)
+    };
+
+    let existing = fs::metadata(target).ok();
+    let owner = existing.as_ref()
+        .map(|existing| (existing.uid(), existing.gid()))
+        .or_else(|| target.parent()
+            .and_then(|dir| fs::metadata(dir).ok())
+            .map(|dir| (dir.uid(), dir.gid()))
+        );
+    let mode = match (&existing, secrecy) {
+        (_, Secrecy::Secret) => 0o600,
+        (Some(existing), Secrecy::Public) => existing.permissions().mode() & 0o7777,
+        (None, Secrecy::Public) => 0o644,
+    };
+
+    if temporary_path.exists() {
+        fs::remove_file(&temporary_path)?; //left behind by a previous attempt
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600) //only widened after writing, so that secrets are never readable by others
+        .open(&temporary_path)
+        .context(format!("Failed to create temporary file '{}'.", temporary_path.display()))?;
+
+    let result = (|| {
+        if let Some((uid, gid)) = owner {
+            std::os::unix::fs::fchown(&file, Some(uid), Some(gid))
+                .context(format!("Failed to keep the ownership of '{}'.", target.display()))?;
+        }
+        file.write_all(content)?;
+        file.set_permissions(std::fs::Permissions::from_mode(mode))?;
+        file.sync_all()?;
+        fs::rename(&temporary_path, target)?;
+        anyhow::Ok(())
+    })();
+
+    if result.is_err() {
+        let _ignore_result = fs::remove_file(&temporary_path);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    use opendut_types::util::net::{ClientId, ClientSecret};
+
+    use super::*;
+
+    fn credentials() -> ClientCredentials {
+        ClientCredentials {
+            client_id: ClientId(String::from("NewClientId")),
+            client_secret: ClientSecret(String::from("NewClientSecret")),
+        }
+    }
+
+    #[test]
+    fn should_replace_only_the_client_credentials() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let config_file = temp.child("edgar.toml");
+        config_file.write_str(indoc!(r#"
+            [peer]
+            id = "dc72f6d9-d700-455f-8c31-9f15438e7503"
+
+            [network.oidc]
+            enabled = true
+
+            [network.oidc.client]
+            issuer.url = "https://test.com:1234/"
+            id = "OldClientId"
+            secret = "OldClientSecret"
+            scopes = "test"
+        "#))?;
+
+        store_rotated_credentials(config_file.path(), &credentials())?;
+
+        assert_that!(fs::read_to_string(&config_file)?, eq(indoc!(r#"
+            [peer]
+            id = "dc72f6d9-d700-455f-8c31-9f15438e7503"
+
+            [network.oidc]
+            enabled = true
+
+            [network.oidc.client]
+            issuer.url = "https://test.com:1234/"
+            id = "NewClientId"
+            secret = "NewClientSecret"
+            scopes = "test"
+        "#)));
+        Ok(())
+    }
+
+    #[test]
+    fn should_replace_the_client_certificate_at_the_configured_paths() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let certificate_file = temp.child("client.pem");
+        certificate_file.write_str("OldCertificate")?;
+        let key_file = temp.child("client.key");
+        key_file.write_str("OldKey")?;
+        let config_file = temp.child("edgar.toml");
+        config_file.write_str(&format!(indoc!(r#"
+            [network.tls.client]
+            certificate = "{}"
+            key = "{}"
+        "#), certificate_file.path().display(), key_file.path().display()))?;
+
+        let client_certificate = ClientCertificate {
+            certificate: opendut_types::util::net::Certificate(pem::Pem::new("CERTIFICATE", vec![1, 2, 3])),
+            key: opendut_types::util::net::ClientCertificateKey(pem::Pem::new("PRIVATE KEY", vec![4, 5, 6])),
+        };
+
+        store_rotated_client_certificate(config_file.path(), &client_certificate)?;
+
+        assert_that!(fs::read_to_string(&certificate_file)?, eq(&client_certificate.certificate.encode_as_string()));
+        assert_that!(fs::read_to_string(&key_file)?, eq(&client_certificate.key.encode_as_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_store_the_issued_client_certificate_at_the_configured_path() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let certificate_file = temp.child("client.pem");
+        let key_file = temp.child("client.key");
+        key_file.write_str("GeneratedKey")?;
+        let config_file = temp.child("edgar.toml");
+        config_file.write_str(&format!(indoc!(r#"
+            [network.tls.client]
+            certificate = "{}"
+            key = "{}"
+        "#), certificate_file.path().display(), key_file.path().display()))?;
+
+        let certificate = Certificate(pem::Pem::new("CERTIFICATE", vec![1, 2, 3]));
+
+        store_issued_client_certificate(config_file.path(), &certificate)?;
+
+        assert_that!(fs::read_to_string(&certificate_file)?, eq(&certificate.encode_as_string()));
+        assert_that!(fs::metadata(&certificate_file)?.permissions().mode() & 0o777, eq(0o644));
+        assert_that!(fs::read_to_string(&key_file)?, eq("GeneratedKey"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_replace_files_atomically_keeping_their_permissions() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let certificate_file = temp.child("client.pem");
+        certificate_file.write_str("OldCertificate")?;
+        fs::set_permissions(&certificate_file, std::fs::Permissions::from_mode(0o640))?;
+        let key_file = temp.child("client.key");
+        key_file.write_str("OldKey")?;
+        fs::set_permissions(&key_file, std::fs::Permissions::from_mode(0o644))?;
+
+        replace_file(certificate_file.path(), b"NewCertificate", Secrecy::Public)?;
+        replace_file(key_file.path(), b"NewKey", Secrecy::Secret)?;
+
+        assert_that!(fs::read_to_string(&certificate_file)?, eq("NewCertificate"));
+        assert_that!(fs::metadata(&certificate_file)?.permissions().mode() & 0o777, eq(0o640));
+        assert_that!(fs::read_to_string(&key_file)?, eq("NewKey"));
+        assert_that!(fs::metadata(&key_file)?.permissions().mode() & 0o777, eq(0o600));
+        assert_that!(temp.child("client.pem.tmp").exists(), eq(false));
+        assert_that!(temp.child("client.key.tmp").exists(), eq(false));
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_store_credentials_when_no_oidc_client_is_configured() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let config_file = temp.child("edgar.toml");
+        let original_settings = indoc!(r#"
+            [network.oidc]
+            enabled = false
+        "#);
+        config_file.write_str(original_settings)?;
+
+        let result = store_rotated_credentials(config_file.path(), &credentials());
+
+        assert!(result.is_err());
+        assert_that!(fs::read_to_string(&config_file)?, eq(original_settings));
+        Ok(())
+    }
+}
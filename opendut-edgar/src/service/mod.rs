@@ -1,11 +1,14 @@
 pub mod start;
 pub mod network_interface;
 pub mod peer_configuration;
+pub mod status;
 
 mod cluster_assignment;
+pub(crate) mod credentials;
 mod cannelloni_manager;
 mod can_manager;
-mod vpn;
+pub(crate) mod vpn;
 mod test_execution;
 mod network_metrics;
 mod tasks;
+pub(crate) mod update;
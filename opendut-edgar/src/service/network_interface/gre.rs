@@ -7,7 +7,7 @@ use opendut_types::util::net::NetworkInterfaceName;
 use crate::service::network_interface;
 use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
 
-const GRE_INTERFACE_NAME_PREFIX: &str = "gre-opendut";
+pub(crate) const GRE_INTERFACE_NAME_PREFIX: &str = "gre-opendut";
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
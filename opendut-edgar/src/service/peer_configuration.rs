@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::fmt::Formatter;
+use std::ops::Not;
 use std::net::IpAddr;
 use opendut_types::cluster::{ClusterAssignment, PeerClusterAssignment};
 use opendut_types::util::net::NetworkInterfaceName;
@@ -12,6 +13,7 @@ use crate::common::task::{runner, Task};
 use crate::service::{cluster_assignment, tasks};
 use crate::service::can_manager::CanManagerRef;
 use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
+use crate::service::status::ServiceStatusRef;
 use crate::service::test_execution::executor_manager::ExecutorManagerRef;
 use crate::setup::RunMode;
 
@@ -25,6 +27,7 @@ pub struct ApplyPeerConfigurationParams {
     pub network_interface_management: NetworkInterfaceManagement,
     pub executor_manager: ExecutorManagerRef,
     pub metrics_manager: NetworkMetricsManagerRef,
+    pub service_status: ServiceStatusRef,
 }
 #[derive(Clone)]
 pub enum NetworkInterfaceManagement {
@@ -52,7 +55,21 @@ pub async fn spawn_peer_configurations_handler(mut rx_peer_configuration: mpsc::
 
 #[tracing::instrument(skip_all)]
 async fn apply_peer_configuration(params: ApplyPeerConfigurationParams) -> anyhow::Result<()> {
-    let ApplyPeerConfigurationParams { self_id, peer_configuration, old_peer_configuration, network_interface_management, executor_manager, metrics_manager } = params;
+    let ApplyPeerConfigurationParams { self_id, peer_configuration, old_peer_configuration, network_interface_management, executor_manager, metrics_manager, service_status } = params;
+
+    service_status.write().await
+        .set_cluster_assignment(old_peer_configuration.cluster_assignment.as_ref());
+
+    if peer_configuration.wireguard_peers.is_empty().not() { //only sent by CARL when using the WireGuard VPN
+        let wireguard_peers = peer_configuration.wireguard_peers.iter()
+            .filter(|peer| peer.target == ParameterTarget::Present)
+            .map(|peer| peer.value.descriptor.clone())
+            .collect::<Vec<_>>();
+
+        if let Err(error) = crate::service::vpn::wireguard::configure_peers(&wireguard_peers).await {
+            error!("Failed to configure WireGuard peers: {error:#}");
+        }
+    }
 
     {
         let mut tasks: Vec<Box<dyn Task>> = vec![];
@@ -7,9 +7,10 @@ use std::time::Duration;
 use anyhow::{anyhow, Context};
 use opendut_carl_api::proto::services::peer_messaging_broker;
 use opendut_carl_api::proto::services::peer_messaging_broker::downstream::Message;
-use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, TracingContext};
+use opendut_carl_api::proto::services::peer_messaging_broker::{executor_finished, rotate_credentials_result, ApplyPeerConfiguration, ExecutorExited, ExecutorFailure, ExecutorFinished, RotateCredentials, RotateCredentialsFailure, RotateCredentialsResult, RotateCredentialsSuccess, TracingContext};
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
 use opendut_types::peer::PeerId;
+use opendut_types::util::net::{ClientCertificate, ClientCredentials};
 use opendut_util::settings::LoadedConfig;
 use opendut_util::telemetry;
 use opendut_util::telemetry::logging::LoggingConfig;
@@ -24,11 +25,15 @@ use tracing_opentelemetry::OpenTelemetrySpanExt;
 use opendut_util::telemetry::opentelemetry_types;
 use crate::app_info;
 use crate::common::{carl, settings};
+use crate::common::status::CarlConnectionState;
 use crate::service::can_manager::{CanManager, CanManagerRef};
+use crate::service::credentials;
 use crate::service::network_interface::manager::{NetworkInterfaceManager, NetworkInterfaceManagerRef};
 use crate::service::network_metrics::manager::NetworkMetricsManager;
 use crate::service::peer_configuration::{ApplyPeerConfigurationParams, NetworkInterfaceManagement};
-use crate::service::test_execution::executor_manager::{ExecutorManager, ExecutorManagerRef};
+use crate::service::status::{self, ServiceStatus, ServiceStatusRef};
+use crate::service::test_execution::executor_manager::{ExecutorManager, ExecutorManagerRef, ExecutorOutcome, ExecutorReport};
+use crate::service::update::{self, UpdateOutcome};
 use crate::service::vpn;
 
 use super::network_metrics::manager::NetworkMetricsManagerRef;
@@ -74,10 +79,15 @@ pub async fn create_with_telemetry(settings_override: config::Config) -> anyhow:
         telemetry::initialize_with_config(logging_config, opentelemetry).await?
     };
 
+    update::watch_pending_update(&settings)?;
+
+    let service_status = ServiceStatus::create(self_id);
+    status::spawn_server(&settings, Arc::clone(&service_status));
+
     let (tx_peer_configuration, rx_peer_configuration) = mpsc::channel(100);
     crate::service::peer_configuration::spawn_peer_configurations_handler(rx_peer_configuration).await?;
 
-    run_stream_receiver(self_id, settings, tx_peer_configuration).await?;
+    run_stream_receiver(self_id, settings, tx_peer_configuration, service_status).await?;
 
     metrics_shutdown_handle.shutdown();
 
@@ -88,12 +98,15 @@ pub async fn run_stream_receiver(
     self_id: PeerId,
     settings: LoadedConfig,
     tx_peer_configuration: mpsc::Sender<ApplyPeerConfigurationParams>,
+    service_status: ServiceStatusRef,
 ) -> anyhow::Result<()> {
 
     info!("Started with ID <{self_id}> and configuration: {settings:?}");
 
+    let (tx_executor_reports, rx_executor_reports) = mpsc::channel(100);
+
     let handle_stream_info = {
-        let executor_manager: ExecutorManagerRef = ExecutorManager::create();
+        let executor_manager: ExecutorManagerRef = ExecutorManager::create(tx_executor_reports);
 
         let network_interface_management = {
             let network_interface_management_enabled = settings.config.get::<bool>("network.interface.management.enabled")?;
@@ -115,18 +128,42 @@ pub async fn run_stream_receiver(
             network_interface_management,
             executor_manager,
             metrics_manager,
+            service_status: Arc::clone(&service_status),
         }
     };
 
+    if vpn::VpnConfig::load(&settings)?.is_wireguard() {
+        let wireguard_config = vpn::wireguard::WireGuardConfig::load(&settings)?;
+        let network_interface_manager: NetworkInterfaceManagerRef = NetworkInterfaceManager::create()?;
+        vpn::wireguard::create_interface(&wireguard_config, &network_interface_manager).await?;
+    }
+
     let remote_address = vpn::retrieve_remote_host(&settings).await?;
-    
+    let vpn_endpoint_host = vpn::endpoint_host(&settings)?;
+
     let timeout_duration = Duration::from_millis(settings.config.get::<u64>("carl.disconnect.timeout.ms")?);
 
     let mut carl = carl::connect(&settings.config).await?;
 
-    let (mut rx_inbound, tx_outbound) = carl::open_stream(self_id, &remote_address, &mut carl).await?;
+    let (mut rx_inbound, tx_outbound) = carl::open_stream(self_id, &remote_address, vpn_endpoint_host, &mut carl).await?;
+
+    service_status.write().await.carl = CarlConnectionState::Connected;
+
+    tokio::spawn(forward_executor_reports(rx_executor_reports, tx_outbound.clone()));
 
-    loop {
+    update::confirm_pending_update();
+
+    match update::check_for_update(&settings, &mut carl).await {
+        Ok(UpdateOutcome::Installed { version }) => {
+            info!("Installed EDGAR {version}. Exiting to be restarted with the new version.");
+            service_status.write().await.carl = CarlConnectionState::Disconnected { reason: format!("Restarting to run EDGAR {version}.") };
+            return Ok(());
+        }
+        Ok(UpdateOutcome::UpToDate | UpdateOutcome::Skipped) => {}
+        Err(cause) => warn!("Failed to update EDGAR: {cause:#}"),
+    }
+
+    let disconnect_reason = loop {
         let received = tokio::time::timeout(timeout_duration, rx_inbound.message()).await;
 
         match received {
@@ -168,15 +205,17 @@ pub async fn run_stream_receiver(
                 }
                 Ok(None) => {
                     info!("CARL disconnected!");
-                    break;
+                    break String::from("CARL closed the connection.");
                 }
             }
             Err(_) => {
                 error!("No message from CARL within {} ms.", timeout_duration.as_millis());
-                break;
+                break format!("No message from CARL within {} ms.", timeout_duration.as_millis());
             }
         }
-    }
+    };
+
+    service_status.write().await.carl = CarlConnectionState::Disconnected { reason: disconnect_reason };
 
     Ok(())
 }
@@ -187,6 +226,7 @@ struct HandleStreamInfo {
     pub network_interface_management: NetworkInterfaceManagement,
     pub executor_manager: ExecutorManagerRef,
     pub metrics_manager: NetworkMetricsManagerRef,
+    pub service_status: ServiceStatusRef,
 }
 
 async fn handle_stream_message(
@@ -197,7 +237,7 @@ async fn handle_stream_message(
 ) -> anyhow::Result<()> {
 
     if let peer_messaging_broker::Downstream { message: Some(message), context } = message {
-        if matches!(message, Message::Pong(_)).not() {
+        if matches!(message, Message::Pong(_) | Message::RotateCredentials(_)).not() { //do not log credentials
             trace!("Received message: {:?}", message);
         }
 
@@ -216,6 +256,7 @@ async fn handle_stream_message(
             Message::DisconnectNotice(_) => {
                 return Err(anyhow!("CARL sent a disconnect notice. Shutting down now."))
             }
+            Message::RotateCredentials(message) => rotate_credentials(message, tx_outbound).await,
         }
     } else {
         ignore(message)
@@ -255,6 +296,7 @@ async fn apply_peer_configuration_raw(
                                 network_interface_management: handle_stream_info.network_interface_management.clone(),
                                 executor_manager: Arc::clone(&handle_stream_info.executor_manager),
                                 metrics_manager: Arc::clone(&handle_stream_info.metrics_manager),
+                                service_status: Arc::clone(&handle_stream_info.service_status),
                             };
                             peer_configuration_sender.send(apply_config_params).await?
                         }
@@ -269,6 +311,90 @@ async fn apply_peer_configuration_raw(
     Ok(())
 }
 
+async fn rotate_credentials(
+    message: RotateCredentials,
+    tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
+) {
+    info!("Received rotated credentials from CARL.");
+
+    let result = extract_rotated_credentials(message)
+        .and_then(|(oidc, client_certificate)| {
+            let config_file = settings::default_config_file_path();
+            if let Some(client_certificate) = client_certificate {
+                credentials::store_rotated_client_certificate(&config_file, &client_certificate)?;
+            }
+            match oidc {
+                Some(credentials) => {
+                    credentials::store_rotated_credentials(&config_file, &credentials)?;
+                    Ok(Some(credentials.client_id))
+                }
+                None => Ok(None),
+            }
+        });
+
+    let result = match result {
+        Ok(client_id) => {
+            info!("Stored rotated credentials. They are used the next time EDGAR connects to CARL.");
+            rotate_credentials_result::Result::Success(RotateCredentialsSuccess { client_id: client_id.map(Into::into) })
+        }
+        Err(cause) => {
+            error!("Failed to store rotated credentials:\n  {cause:#}");
+            rotate_credentials_result::Result::Failure(RotateCredentialsFailure { cause: format!("{cause:#}") })
+        }
+    };
+
+    let message = peer_messaging_broker::Upstream {
+        message: Some(peer_messaging_broker::upstream::Message::RotateCredentialsResult(RotateCredentialsResult { result: Some(result) })),
+        context: None,
+    };
+    let _ignore_error =
+        tx_outbound.send(message).await
+            .inspect_err(|cause| warn!("Failed to confirm rotated credentials to CARL: {cause}"));
+}
+
+async fn forward_executor_reports(
+    mut rx_executor_reports: mpsc::Receiver<ExecutorReport>,
+    tx_outbound: mpsc::Sender<peer_messaging_broker::Upstream>,
+) {
+    while let Some(ExecutorReport { executor_id, outcome }) = rx_executor_reports.recv().await {
+        debug!("Reporting to CARL that executor <{}> finished: {outcome:?}", executor_id.uuid);
+
+        let outcome = match outcome {
+            ExecutorOutcome::Exited { exit_code } => executor_finished::Outcome::Exited(ExecutorExited { exit_code }),
+            ExecutorOutcome::Failed { cause } => executor_finished::Outcome::Failure(ExecutorFailure { cause }),
+        };
+        let message = peer_messaging_broker::Upstream {
+            message: Some(peer_messaging_broker::upstream::Message::ExecutorFinished(ExecutorFinished {
+                executor_id: Some(executor_id.into()),
+                outcome: Some(outcome),
+            })),
+            context: None,
+        };
+
+        if let Err(cause) = tx_outbound.send(message).await {
+            warn!("Failed to report finished executor <{}> to CARL: {cause}", executor_id.uuid);
+            break; //stream to CARL is closed
+        }
+    }
+}
+
+/// Either part is absent, if CARL does not use it for authenticating peers.
+fn extract_rotated_credentials(message: RotateCredentials) -> anyhow::Result<(Option<ClientCredentials>, Option<ClientCertificate>)> {
+    let oidc = match (message.client_id, message.client_secret) {
+        (Some(client_id), Some(client_secret)) => Some(ClientCredentials {
+            client_id: client_id.try_into()?,
+            client_secret: client_secret.try_into()?,
+        }),
+        (None, None) => None,
+        (Some(_), None) => return Err(anyhow!("CARL sent rotated credentials without client secret.")),
+        (None, Some(_)) => return Err(anyhow!("CARL sent rotated credentials without client ID.")),
+    };
+    let client_certificate = message.client_certificate
+        .map(ClientCertificate::try_from)
+        .transpose()?;
+    Ok((oidc, client_certificate))
+}
+
 fn set_parent_context(span: &Span, context: Option<TracingContext>) {
     if let Some(context) = context {
         let propagator = TraceContextPropagator::new();
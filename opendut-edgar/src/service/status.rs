@@ -0,0 +1,110 @@
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::sync::Arc;
+
+use tokio::io::AsyncWriteExt;
+use tokio::net::UnixListener;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use opendut_types::cluster::ClusterAssignment;
+use opendut_types::peer::PeerId;
+use opendut_util::settings::LoadedConfig;
+
+use crate::common::status::{self, CarlConnectionState, ClusterState, ServiceReachability, StatusReport};
+use crate::fs;
+
+pub type ServiceStatusRef = Arc<RwLock<ServiceStatus>>;
+
+/// State of the running service, which cannot be determined from the outside.
+#[derive(Debug)]
+pub struct ServiceStatus {
+    pub peer_id: PeerId,
+    pub carl: CarlConnectionState,
+    pub cluster: Option<ClusterState>,
+}
+impl ServiceStatus {
+    pub fn create(peer_id: PeerId) -> ServiceStatusRef {
+        Arc::new(RwLock::new(Self {
+            peer_id,
+            carl: CarlConnectionState::Connecting,
+            cluster: None,
+        }))
+    }
+
+    pub fn set_cluster_assignment(&mut self, cluster_assignment: Option<&ClusterAssignment>) {
+        self.cluster = cluster_assignment.map(|assignment| ClusterState {
+            id: assignment.id,
+            leader: assignment.leader,
+            peers: assignment.assignments.len(),
+        });
+    }
+}
+
+/// Answers each connection on the status socket with a [StatusReport] as JSON and closes it afterwards.
+/// Failing to open the socket is not fatal, as EDGAR can do its job without it.
+pub fn spawn_server(settings: &LoadedConfig, service_status: ServiceStatusRef) {
+    let socket_path = match status::socket_path(settings) {
+        Ok(socket_path) => socket_path,
+        Err(cause) => {
+            warn!("Not providing status socket, as its path could not be determined: {cause}");
+            return;
+        }
+    };
+
+    let listener = match bind(&socket_path) {
+        Ok(listener) => listener,
+        Err(cause) => {
+            warn!("Not providing status socket, as it could not be opened at '{}': {cause}", socket_path.display());
+            return;
+        }
+    };
+    debug!("Providing status socket at '{}'.", socket_path.display());
+
+    let settings = settings.clone();
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((mut stream, _)) => {
+                    let report = collect_report(&settings, &service_status).await;
+                    let mut json = serde_json::to_vec(&report)
+                        .expect("Status report should always be serializable as JSON.");
+                    json.push(b'\n');
+
+                    if let Err(cause) = stream.write_all(&json).await {
+                        debug!("Failed to send status report: {cause}");
+                    }
+                }
+                Err(cause) => warn!("Failed to accept connection on status socket: {cause}"),
+            }
+        }
+    });
+}
+
+fn bind(socket_path: &Path) -> anyhow::Result<UnixListener> {
+    if socket_path.exists() {
+        fs::remove_file(socket_path)?; //left over from a previous run
+    }
+    let listener = UnixListener::bind(socket_path)?;
+    fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o660))?;
+    Ok(listener)
+}
+
+async fn collect_report(settings: &LoadedConfig, service_status: &ServiceStatusRef) -> StatusReport {
+    let (peer_id, carl, cluster) = {
+        let service_status = service_status.read().await;
+        (service_status.peer_id, service_status.carl.clone(), service_status.cluster.clone())
+    };
+
+    StatusReport {
+        version: crate::app_info::PKG_VERSION.to_owned(),
+        peer_id: Some(peer_id),
+        service: ServiceReachability::Running,
+        carl,
+        vpn: status::determine_vpn_state(settings).await,
+        cluster,
+        interface_management_enabled: status::interface_management_enabled(settings),
+        interfaces: status::list_managed_interfaces(),
+        processes: status::list_processes(),
+    }
+}
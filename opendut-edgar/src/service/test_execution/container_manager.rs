@@ -1,4 +1,4 @@
-use std::{env, io::{Cursor, ErrorKind, Write}, path::PathBuf, process::Stdio};
+use std::{env, io::{Cursor, ErrorKind, Write}, ops::Not, path::PathBuf, process::Stdio};
 
 use anyhow::Result;
 use tokio::{fs::{self, File}, io::{AsyncBufReadExt, AsyncReadExt, BufReader}, process::{Child, Command}, sync::{mpsc, watch}};
@@ -10,6 +10,7 @@ use zip::{CompressionMethod, write::{FileOptionExtension, FileOptions, SimpleFil
 
 use opendut_types::peer::executor::{container::{CommandName, ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ResultsUrl};
 
+use crate::service::test_execution::executor_manager::ExecutorOutcome;
 use crate::service::test_execution::webdav_client::{self, WebdavClient};
 
 #[derive(Debug)]
@@ -57,14 +58,24 @@ impl ContainerManager {
         }
     }
 
-    pub async fn start(&mut self) {
+    /// Runs the container until it exits. Returns its outcome, unless it was stopped, because the executor was terminated.
+    pub async fn start(&mut self) -> Option<ExecutorOutcome> {
         match self.run().await {
-            Ok(_) => (),
-            Err(cause) => error!("{}", cause.to_string()),
+            Ok(Some(exit_code)) => Some(ExecutorOutcome::Exited { exit_code }),
+            Ok(None) => None,
+            Err(cause) => {
+                error!("{}", cause.to_string());
+                self.is_termination_requested().not().then(|| ExecutorOutcome::Failed { cause: cause.to_string() })
+            }
         }
     }
 
-    async fn run(&mut self) -> Result<(), Error> {
+    fn is_termination_requested(&self) -> bool {
+        // If the value in the channel has changed or the channel has been closed, we terminate
+        self.termination_channel_rx.has_changed().unwrap_or(true)
+    }
+
+    async fn run(&mut self) -> Result<Option<i32>, Error> {
         let mut results_uploaded = false;
 
         self.create_results_dir().await?;
@@ -75,11 +86,10 @@ impl ContainerManager {
                 container_name.clone()
             )?;
 
-        loop {
+        let exit_code = loop {
             log_reader.read().await;
 
-            // If the value in the channel has changed or the channel has been closed, we terminate
-            if self.termination_channel_rx.has_changed().unwrap_or(true) {
+            if self.is_termination_requested() {
                 self.stop_container(&container_name).await?;
             }
 
@@ -96,7 +106,7 @@ impl ContainerManager {
                         self.remove_result_ready_indicator().await?;
                         self.upload_results().await?;
                     }
-                    break
+                    break self.get_container_exit_code(&container_name).await?
                 },
                 state => {
                     warn!("Unexpected container state of '{}': {:?}", self.config.name, state)
@@ -104,11 +114,11 @@ impl ContainerManager {
             }
 
             tokio::time::sleep(std::time::Duration::from_millis(MONITOR_INTERVAL_MS)).await;
-        }
+        };
 
         self.cleanup_results_dir().await?;
 
-        Ok(())
+        Ok(self.is_termination_requested().not().then_some(exit_code))
     }
 
     async fn get_container_state(&self, container_name: &str) -> Result<ContainerState, Error> {
@@ -130,6 +140,18 @@ impl ContainerManager {
         
     }
 
+    async fn get_container_exit_code(&self, container_name: &str) -> Result<i32, Error> {
+        let output = Command::new(self.config.engine.command_name())
+            .args(["inspect", "-f", "'{{.State.ExitCode}}'", container_name])
+            .output()
+            .await
+            .map_err(|cause| Error::CommandLineProgramExecution { command: format!("{} inspect", &self.config.engine.command_name()), cause })?;
+
+        let exit_code = String::from_utf8_lossy(&output.stdout).replace('\'', "");
+        exit_code.trim().parse::<i32>()
+            .map_err(|cause| Error::Other { message: format!("Unknown exit code returned by {} inspect: '{}' ({cause})", &self.config.engine.command_name(), exit_code.trim()) })
+    }
+
     async fn start_container(&mut self) -> Result<String, Error>{
 
         let mut cmd = Command::new(self.config.engine.command_name());
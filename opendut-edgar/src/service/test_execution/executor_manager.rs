@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
-use opendut_types::peer::{self, executor::{ExecutorDescriptor, ExecutorKind}};
-use tokio::sync::{watch::{self, Sender}, Mutex};
+use opendut_types::peer::{self, executor::{ExecutorDescriptor, ExecutorId, ExecutorKind}};
+use tokio::sync::{mpsc, watch::{self, Sender}, Mutex};
 use tracing::{debug, warn};
 use opendut_types::peer::configuration::parameter;
 use crate::service::test_execution::container_manager::{ContainerManager, ContainerConfiguration};
@@ -11,12 +11,27 @@ pub type ExecutorManagerRef = Arc<Mutex<ExecutorManager>>;
 #[derive(Debug)]
 pub struct ExecutorManager {
     tx_termination_channels: Vec<Sender<bool>>,
+    tx_executor_reports: mpsc::Sender<ExecutorReport>,
+}
+
+/// Sent to CARL, when an executor stopped running by itself.
+#[derive(Debug)]
+pub struct ExecutorReport {
+    pub executor_id: ExecutorId,
+    pub outcome: ExecutorOutcome,
+}
+
+#[derive(Debug)]
+pub enum ExecutorOutcome {
+    Exited { exit_code: i32 },
+    Failed { cause: String },
 }
 
 impl ExecutorManager {
-    pub fn create() -> ExecutorManagerRef {
+    pub fn create(tx_executor_reports: mpsc::Sender<ExecutorReport>) -> ExecutorManagerRef {
         Arc::new(Mutex::new(Self {
             tx_termination_channels: Vec::new(),
+            tx_executor_reports,
         }))
     }
 
@@ -37,7 +52,7 @@ impl ExecutorManager {
             let (tx, rx) = watch::channel(false);
 
             let parameter::Executor { descriptor } = executor;
-            let ExecutorDescriptor { id: _, kind, results_url } = descriptor;
+            let ExecutorDescriptor { id: executor_id, kind, results_url } = descriptor;
 
             match kind {
                 ExecutorKind::Executable => warn!("Executing Executable not yet implemented."),
@@ -64,8 +79,14 @@ impl ExecutorManager {
                         devices,
                         volumes,
                     };
+                    let tx_executor_reports = self.tx_executor_reports.clone();
                     tokio::spawn(async move {
-                        ContainerManager::new(container_config, rx).start().await;
+                        if let Some(outcome) = ContainerManager::new(container_config, rx).start().await {
+                            let report = ExecutorReport { executor_id, outcome };
+                            if let Err(cause) = tx_executor_reports.send(report).await {
+                                warn!("Failed to report finished executor <{}>: {cause}", executor_id.uuid);
+                            }
+                        }
                     });
                 }
             }
@@ -0,0 +1,436 @@
+use std::io::Read;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context};
+use flate2::read::GzDecoder;
+use serde::Deserialize;
+use tracing::{debug, error, info, warn};
+use url::Url;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::update::UpdateVerificationKey;
+use opendut_util::project;
+use opendut_util::settings::LoadedConfig;
+
+use crate::app_info;
+use crate::common::settings;
+use crate::fs;
+
+mod pending;
+use pending::{PendingUpdate, StartupCheck};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdatePolicy {
+    /// Install newer versions advertised by CARL.
+    Automatic,
+    /// Only log when CARL advertises a newer version.
+    Notify,
+    Disabled,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpdateOutcome {
+    UpToDate,
+    Skipped,
+    /// The new executable is in place. The service needs to restart to run it.
+    Installed { version: semver::Version },
+}
+
+/// Path of the executable inside the EDGAR distribution archive.
+const EXECUTABLE_IN_DISTRIBUTION: &str = "opendut-edgar/opendut-edgar";
+
+/// Shell script, which the service runs before each start of EDGAR with the path of the executable as argument.
+/// Restores the previous executable, if an update fails to start before EDGAR can roll it back by itself.
+pub const STARTUP_GUARD_SCRIPT: &str = include_str!("startup-guard.sh");
+
+/// Describes an EDGAR distribution served by CARL. The signature covers the manifest,
+/// so that a validly signed distribution of another version or target cannot be passed off as the advertised one.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DistributionManifest {
+    version: String,
+    target: String,
+    /// Hex-encoded SHA-256 digest of the distribution archive.
+    sha256: String,
+}
+
+/// Compares the own version with the one of CARL and installs the distribution served by CARL,
+/// if it is newer, did not fail before, the policy allows it and its signed manifest matches.
+pub async fn check_for_update(settings: &LoadedConfig, carl: &mut CarlClient) -> anyhow::Result<UpdateOutcome> {
+    let policy = settings.config.get::<UpdatePolicy>(settings::key::update::policy)?;
+    if policy == UpdatePolicy::Disabled {
+        return Ok(UpdateOutcome::Skipped);
+    }
+
+    let carl_version = carl.metadata.version().await?;
+    let carl_version = semver::Version::parse(&carl_version.name)
+        .context(format!("CARL reported an invalid version: {}", carl_version.name))?;
+    let own_version = semver::Version::parse(app_info::PKG_VERSION)?;
+
+    if carl_version <= own_version {
+        debug!("EDGAR {own_version} is up-to-date with CARL {carl_version}.");
+        return Ok(UpdateOutcome::UpToDate);
+    }
+
+    if policy == UpdatePolicy::Notify {
+        info!("CARL offers EDGAR {carl_version}, but the update policy only allows notifying about it. Running EDGAR {own_version}.");
+        return Ok(UpdateOutcome::Skipped);
+    }
+
+    let verification_key = settings.config.get_string(settings::key::update::verification::key)?;
+    if verification_key.is_empty() {
+        warn!("CARL offers EDGAR {carl_version}, but no key for verifying updates is configured under '{}'. Running EDGAR {own_version}.", settings::key::update::verification::key);
+        return Ok(UpdateOutcome::Skipped);
+    }
+    let verification_key = UpdateVerificationKey::from_str(&verification_key)?;
+
+    let paths = UpdatePaths::of_current_executable()?;
+    if pending::has_failed(&paths, &carl_version.to_string())? {
+        warn!("CARL offers EDGAR {carl_version}, but it failed to start on this peer before. Running EDGAR {own_version}.");
+        return Ok(UpdateOutcome::Skipped);
+    }
+
+    info!("Updating EDGAR from {own_version} to {carl_version}.");
+
+    let downloader = DistributionDownloader::new(settings).await?;
+    let manifest = downloader.download("manifest").await?;
+    let signature = downloader.download("signature").await?;
+    let manifest = verify_manifest(&verification_key, &manifest, &signature)?;
+    manifest.check(&carl_version, &own_version, distribution_target())?;
+
+    let distribution = downloader.download("download").await?;
+    manifest.check_digest(&distribution)?;
+    let executable = extract_executable(&distribution)?;
+
+    install_executable(&paths, &executable, &PendingUpdate {
+        previous_version: own_version.to_string(),
+        version: carl_version.to_string(),
+        attempts: 0,
+    })?;
+
+    Ok(UpdateOutcome::Installed { version: carl_version })
+}
+
+/// Checks on startup, whether the running executable was just installed by an update.
+/// If so, the previous version is restored, when this version does not reconnect to CARL in time or keeps failing to start.
+pub fn watch_pending_update(settings: &LoadedConfig) -> anyhow::Result<()> {
+    let paths = UpdatePaths::of_current_executable()?;
+
+    match pending::check_on_startup(&paths, app_info::PKG_VERSION)? {
+        StartupCheck::NoUpdatePending => Ok(()),
+        StartupCheck::RolledBack => {
+            bail!("EDGAR {} failed to start repeatedly after an update, so the previous version was restored. Exiting to be restarted with it.", app_info::PKG_VERSION)
+        }
+        StartupCheck::AwaitingReconnect => {
+            let timeout = Duration::from_millis(settings.config.get::<u64>(settings::key::update::reconnect::timeout::ms)?);
+            info!("EDGAR {} was just installed by an update. Restoring the previous version, if it does not reconnect to CARL within {} ms.", app_info::PKG_VERSION, timeout.as_millis());
+
+            tokio::spawn(async move {
+                tokio::time::sleep(timeout).await;
+
+                if paths.pending_update_file.exists() {
+                    error!("EDGAR did not reconnect to CARL within {} ms after the update. Restoring the previous version.", timeout.as_millis());
+                    if let Err(cause) = pending::rollback(&paths) {
+                        error!("Failed to restore the previous version of EDGAR: {cause:#}");
+                    }
+                    std::process::exit(1); //SystemD restarts the service with the previous version
+                }
+            });
+            Ok(())
+        }
+    }
+}
+
+/// Marks a pending update as successful, after the new version connected to CARL.
+pub fn confirm_pending_update() {
+    let confirmed = UpdatePaths::of_current_executable()
+        .and_then(|paths| pending::confirm(&paths));
+
+    match confirmed {
+        Ok(Some(update)) => info!("Successfully updated EDGAR from {} to {}.", update.previous_version, update.version),
+        Ok(None) => {}
+        Err(cause) => warn!("Failed to confirm the update of EDGAR: {cause:#}"),
+    }
+}
+
+#[derive(Debug)]
+struct UpdatePaths {
+    executable: PathBuf,
+    new_executable: PathBuf,
+    previous_executable: PathBuf,
+    pending_update_file: PathBuf,
+    /// Counted by the startup guard of the service. Named as in the [STARTUP_GUARD_SCRIPT].
+    start_attempts_file: PathBuf,
+    failed_updates_file: PathBuf,
+}
+impl UpdatePaths {
+    fn of_current_executable() -> anyhow::Result<Self> {
+        let executable = std::env::current_exe()?;
+        Ok(Self::of_executable(executable))
+    }
+
+    fn of_executable(executable: PathBuf) -> Self {
+        let with_suffix = |suffix: &str| {
+            let mut path = executable.clone().into_os_string();
+            path.push(suffix);
+            PathBuf::from(path)
+        };
+
+        Self {
+            new_executable: with_suffix(".new"),
+            previous_executable: with_suffix(".previous"),
+            pending_update_file: executable.with_file_name("update-pending.json"),
+            start_attempts_file: executable.with_file_name("update-start-attempts"),
+            failed_updates_file: executable.with_file_name("update-failed.json"),
+            executable,
+        }
+    }
+}
+
+struct DistributionDownloader {
+    client: reqwest::Client,
+    base_url: Url,
+}
+impl DistributionDownloader {
+    async fn new(settings: &LoadedConfig) -> anyhow::Result<Self> {
+        let host = settings.config.get_string("network.carl.host")?;
+        let port = u16::try_from(settings.config.get_int("network.carl.port")?)?;
+        let ca = fs::read(project::make_path_absolute(settings.config.get_string("network.tls.ca")?)?)?;
+        let domain_name_override = settings.config.get_string("network.tls.domain.name.override")?;
+
+        let mut client = reqwest::Client::builder()
+            .add_root_certificate(reqwest::Certificate::from_pem(&ca)?);
+
+        let url_host = if domain_name_override.is_empty() {
+            host
+        } else {
+            let address = tokio::net::lookup_host((host.as_str(), port)).await?
+                .next()
+                .ok_or_else(|| anyhow!("Could not resolve address of CARL host '{host}'."))?;
+            client = client.resolve(&domain_name_override, address);
+            domain_name_override
+        };
+        let client = client.build()?;
+
+        let base_url = Url::parse(&format!("https://{url_host}:{port}/api/edgar/{}/", distribution_target()))?;
+
+        Ok(Self { client, base_url })
+    }
+
+    async fn download(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+        let url = self.base_url.join(path)?;
+        let response = self.client.get(url.clone()).send().await?
+            .error_for_status()
+            .context(format!("Failed to download '{url}'."))?;
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+fn distribution_target() -> &'static str {
+    if cfg!(target_arch = "x86_64") {
+        "x86_64-unknown-linux-gnu"
+    } else if cfg!(target_arch = "aarch64") {
+        "aarch64-unknown-linux-gnu"
+    } else {
+        "armv7-unknown-linux-gnueabihf"
+    }
+}
+
+fn verify_manifest(key: &UpdateVerificationKey, manifest: &[u8], signature: &[u8]) -> anyhow::Result<DistributionManifest> {
+    ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, key.as_bytes())
+        .verify(manifest, signature)
+        .map_err(|_| anyhow!("The signature of the EDGAR distribution manifest could not be verified with the configured key. Not installing it."))?;
+
+    serde_json::from_slice(manifest)
+        .context("The EDGAR distribution manifest is invalid. Not installing it.")
+}
+
+impl DistributionManifest {
+    /// Rejects distributions of another version than the one CARL advertises, of an older version or for another target.
+    fn check(&self, expected_version: &semver::Version, own_version: &semver::Version, target: &str) -> anyhow::Result<()> {
+        let version = semver::Version::parse(&self.version)
+            .context(format!("The EDGAR distribution manifest contains an invalid version: {}", self.version))?;
+
+        if &version != expected_version {
+            bail!("The EDGAR distribution is of version {version}, but CARL advertises {expected_version}. Not installing it.");
+        }
+        if &version <= own_version {
+            bail!("The EDGAR distribution is of version {version}, which is not newer than the running {own_version}. Not installing it.");
+        }
+        if self.target != target {
+            bail!("The EDGAR distribution is built for '{}', but this peer requires '{target}'. Not installing it.", self.target);
+        }
+        Ok(())
+    }
+
+    fn check_digest(&self, distribution: &[u8]) -> anyhow::Result<()> {
+        use sha2::{Digest, Sha256};
+
+        let digest = Sha256::digest(distribution).iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+
+        if !digest.eq_ignore_ascii_case(&self.sha256) {
+            bail!("The SHA-256 digest of the EDGAR distribution does not match its manifest. Not installing it.");
+        }
+        Ok(())
+    }
+}
+
+fn extract_executable(distribution: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut archive = tar::Archive::new(GzDecoder::new(distribution));
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let path = path.strip_prefix(".").unwrap_or(&path);
+
+        if path == Path::new(EXECUTABLE_IN_DISTRIBUTION) {
+            let mut executable = Vec::new();
+            entry.read_to_end(&mut executable)?;
+            return Ok(executable);
+        }
+    }
+    bail!("The EDGAR distribution does not contain an executable at '{EXECUTABLE_IN_DISTRIBUTION}'.")
+}
+
+/// Keeps a copy of the running executable for rolling back and replaces it atomically with the new one.
+fn install_executable(paths: &UpdatePaths, executable: &[u8], pending_update: &PendingUpdate) -> anyhow::Result<()> {
+    fs::write(&paths.new_executable, executable)?;
+    fs::set_permissions(&paths.new_executable, std::fs::Permissions::from_mode(0o755))?;
+
+    if paths.previous_executable.exists() {
+        fs::remove_file(&paths.previous_executable)?;
+    }
+    fs::copy(&paths.executable, &paths.previous_executable)
+        .context("Failed to keep a copy of the running executable for rolling back.")?;
+
+    pending::write(&paths.pending_update_file, pending_update)?;
+
+    fs::rename(&paths.new_executable, &paths.executable)
+        .context(format!("Failed to replace executable at '{}'.", paths.executable.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    use super::*;
+
+    fn distribution_containing(path: &str, content: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder.append_data(&mut header, path, content)?;
+        let mut encoder = builder.into_inner()?;
+        encoder.flush()?;
+        Ok(encoder.finish()?)
+    }
+
+    fn manifest_of(distribution: &[u8], version: &str) -> String {
+        use sha2::{Digest, Sha256};
+
+        let digest = Sha256::digest(distribution).iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        format!(r#"{{ "version": "{version}", "target": "x86_64-unknown-linux-gnu", "sha256": "{digest}" }}"#)
+    }
+
+    #[test]
+    fn should_verify_the_signature_of_a_manifest() -> anyhow::Result<()> {
+        let rng = ring::rand::SystemRandom::new();
+        let key_pair = Ed25519KeyPair::from_pkcs8(Ed25519KeyPair::generate_pkcs8(&rng).unwrap().as_ref()).unwrap();
+        let key = UpdateVerificationKey::try_from(key_pair.public_key().as_ref())?;
+
+        let distribution = distribution_containing(EXECUTABLE_IN_DISTRIBUTION, b"new executable")?;
+        let manifest = manifest_of(&distribution, "1.1.0");
+        let signature = key_pair.sign(manifest.as_bytes());
+
+        assert_that!(verify_manifest(&key, manifest.as_bytes(), signature.as_ref()), ok(anything()));
+
+        let tampered = manifest.replace("1.1.0", "1.2.0");
+        assert_that!(verify_manifest(&key, tampered.as_bytes(), signature.as_ref()), err(anything()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_distributions_not_matching_the_manifest() -> anyhow::Result<()> {
+        let distribution = distribution_containing(EXECUTABLE_IN_DISTRIBUTION, b"new executable")?;
+        let manifest: DistributionManifest = serde_json::from_str(&manifest_of(&distribution, "1.1.0"))?;
+        let target = "x86_64-unknown-linux-gnu";
+
+        let version = |version: &str| semver::Version::parse(version).unwrap();
+
+        assert_that!(manifest.check(&version("1.1.0"), &version("1.0.0"), target), ok(anything()));
+        assert_that!(manifest.check(&version("1.2.0"), &version("1.0.0"), target), err(anything()));
+        assert_that!(manifest.check(&version("1.1.0"), &version("1.1.0"), target), err(anything()));
+        assert_that!(manifest.check(&version("1.1.0"), &version("1.0.0"), "aarch64-unknown-linux-gnu"), err(anything()));
+
+        assert_that!(manifest.check_digest(&distribution), ok(anything()));
+        let tampered = distribution_containing(EXECUTABLE_IN_DISTRIBUTION, b"malicious executable")?;
+        assert_that!(manifest.check_digest(&tampered), err(anything()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_extract_the_executable_from_a_distribution() -> anyhow::Result<()> {
+        let distribution = distribution_containing(EXECUTABLE_IN_DISTRIBUTION, b"new executable")?;
+        assert_that!(extract_executable(&distribution)?, eq(b"new executable".to_vec()));
+
+        let distribution = distribution_containing("opendut-edgar/install/rperf", b"rperf")?;
+        assert_that!(extract_executable(&distribution), err(anything()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_replace_the_executable_and_keep_the_previous_one() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let executable = temp.child("opendut-edgar");
+        executable.write_binary(b"old executable")?;
+        let paths = UpdatePaths::of_executable(executable.to_path_buf());
+
+        let pending_update = PendingUpdate { previous_version: String::from("1.0.0"), version: String::from("1.1.0"), attempts: 0 };
+        install_executable(&paths, b"new executable", &pending_update)?;
+
+        assert_that!(fs::read(&paths.executable)?, eq(b"new executable".to_vec()));
+        assert_that!(fs::read(&paths.previous_executable)?, eq(b"old executable".to_vec()));
+        assert_that!(paths.new_executable.exists(), eq(false));
+        assert_that!(pending::read(&paths.pending_update_file)?, some(eq(&pending_update)));
+        Ok(())
+    }
+
+    #[test]
+    fn the_startup_guard_should_restore_the_previous_executable_after_repeated_start_failures() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let executable = temp.child("opendut-edgar");
+        executable.write_binary(b"old executable")?;
+        let paths = UpdatePaths::of_executable(executable.to_path_buf());
+        install_executable(&paths, b"new executable", &PendingUpdate { previous_version: String::from("1.0.0"), version: String::from("1.1.0"), attempts: 0 })?;
+
+        let run_startup_guard = || std::process::Command::new("sh")
+            .args(["-c", STARTUP_GUARD_SCRIPT, "startup-guard.sh"])
+            .arg(&paths.executable)
+            .status();
+
+        for _ in 0..5 {
+            assert_that!(run_startup_guard()?.success(), eq(true));
+            assert_that!(fs::read(&paths.executable)?, eq(b"new executable".to_vec()));
+        }
+        assert_that!(run_startup_guard()?.success(), eq(true));
+        assert_that!(fs::read(&paths.executable)?, eq(b"old executable".to_vec()));
+        assert_that!(paths.start_attempts_file.exists(), eq(false));
+        Ok(())
+    }
+}
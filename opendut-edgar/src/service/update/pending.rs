@@ -0,0 +1,204 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::fs;
+use super::UpdatePaths;
+
+/// How often the updated EDGAR may be started without reconnecting to CARL, before the previous version is restored.
+const MAX_START_ATTEMPTS: u32 = 3;
+
+/// Written next to the executable when installing an update. Removed once the new version connected to CARL.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PendingUpdate {
+    pub previous_version: String,
+    pub version: String,
+    pub attempts: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum StartupCheck {
+    NoUpdatePending,
+    AwaitingReconnect,
+    RolledBack,
+}
+
+pub fn check_on_startup(paths: &UpdatePaths, own_version: &str) -> anyhow::Result<StartupCheck> {
+    let Some(mut pending_update) = read(&paths.pending_update_file)? else {
+        return Ok(StartupCheck::NoUpdatePending);
+    };
+
+    if pending_update.version != own_version {
+        if pending_update.previous_version == own_version {
+            warn!("The update to EDGAR {} was rolled back before it could start. Not installing it again.", pending_update.version);
+            record_failure(paths, &pending_update.version)?;
+            remove_update_files(paths)?;
+            return Ok(StartupCheck::NoUpdatePending);
+        }
+
+        warn!("Found a pending update to EDGAR {}, but running EDGAR {own_version}. Restoring EDGAR {}.", pending_update.version, pending_update.previous_version);
+        rollback(paths)?;
+        return Ok(StartupCheck::RolledBack);
+    }
+
+    pending_update.attempts += 1;
+    if pending_update.attempts > MAX_START_ATTEMPTS {
+        rollback(paths)?;
+        return Ok(StartupCheck::RolledBack);
+    }
+
+    write(&paths.pending_update_file, &pending_update)?;
+    Ok(StartupCheck::AwaitingReconnect)
+}
+
+pub fn confirm(paths: &UpdatePaths) -> anyhow::Result<Option<PendingUpdate>> {
+    let pending_update = read(&paths.pending_update_file)?;
+
+    if pending_update.is_some() {
+        remove_update_files(paths)?;
+    }
+    Ok(pending_update)
+}
+
+/// Restores the executable, which was running before the update, and remembers the version of the update as failed.
+pub fn rollback(paths: &UpdatePaths) -> anyhow::Result<()> {
+    if let Some(pending_update) = read(&paths.pending_update_file)? {
+        record_failure(paths, &pending_update.version)?;
+    }
+
+    if paths.previous_executable.exists() {
+        fs::rename(&paths.previous_executable, &paths.executable)?;
+    } else {
+        warn!("No previous executable found at '{}'. Keeping the updated executable.", paths.previous_executable.display());
+    }
+
+    remove_update_files(paths)
+}
+
+/// Whether an update to this version was rolled back before. It is not installed again, as it would most likely fail again.
+pub fn has_failed(paths: &UpdatePaths, version: &str) -> anyhow::Result<bool> {
+    Ok(read_failed_versions(paths)?.iter().any(|failed_version| failed_version == version))
+}
+
+fn record_failure(paths: &UpdatePaths, version: &str) -> anyhow::Result<()> {
+    let mut failed_versions = read_failed_versions(paths)?;
+    if !failed_versions.iter().any(|failed_version| failed_version == version) {
+        failed_versions.push(version.to_owned());
+        fs::write(&paths.failed_updates_file, serde_json::to_string_pretty(&failed_versions)?)?;
+    }
+    Ok(())
+}
+
+fn read_failed_versions(paths: &UpdatePaths) -> anyhow::Result<Vec<String>> {
+    if !paths.failed_updates_file.exists() {
+        return Ok(Vec::new());
+    }
+    let failed_versions = serde_json::from_str(&fs::read_to_string(&paths.failed_updates_file)?)?;
+    Ok(failed_versions)
+}
+
+fn remove_update_files(paths: &UpdatePaths) -> anyhow::Result<()> {
+    for file in [&paths.pending_update_file, &paths.previous_executable, &paths.start_attempts_file] {
+        if file.exists() {
+            fs::remove_file(file)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn read(path: &Path) -> anyhow::Result<Option<PendingUpdate>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let pending_update = serde_json::from_str(&fs::read_to_string(path)?)?;
+    Ok(Some(pending_update))
+}
+
+pub fn write(path: &Path, pending_update: &PendingUpdate) -> anyhow::Result<()> {
+    fs::write(path, serde_json::to_string_pretty(pending_update)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn install_update(temp: &TempDir) -> anyhow::Result<UpdatePaths> {
+        let executable = temp.child("opendut-edgar");
+        executable.write_binary(b"new executable")?;
+        temp.child("opendut-edgar.previous").write_binary(b"old executable")?;
+
+        let paths = UpdatePaths::of_executable(executable.to_path_buf());
+        write(&paths.pending_update_file, &PendingUpdate {
+            previous_version: String::from("1.0.0"),
+            version: String::from("1.1.0"),
+            attempts: 0,
+        })?;
+        Ok(paths)
+    }
+
+    #[test]
+    fn should_roll_back_after_too_many_start_attempts() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let paths = install_update(&temp)?;
+
+        for _ in 0..MAX_START_ATTEMPTS {
+            assert_that!(check_on_startup(&paths, "1.1.0")?, eq(&StartupCheck::AwaitingReconnect));
+        }
+        assert_that!(check_on_startup(&paths, "1.1.0")?, eq(&StartupCheck::RolledBack));
+
+        assert_that!(fs::read(&paths.executable)?, eq(b"old executable".to_vec()));
+        assert_that!(paths.pending_update_file.exists(), eq(false));
+        assert_that!(has_failed(&paths, "1.1.0")?, eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn should_roll_back_when_running_an_unexpected_version() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let paths = install_update(&temp)?;
+
+        assert_that!(check_on_startup(&paths, "1.0.5")?, eq(&StartupCheck::RolledBack));
+
+        assert_that!(fs::read(&paths.executable)?, eq(b"old executable".to_vec()));
+        assert_that!(paths.pending_update_file.exists(), eq(false));
+        assert_that!(has_failed(&paths, "1.1.0")?, eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn should_remember_an_update_restored_by_the_startup_guard_as_failed() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let paths = install_update(&temp)?;
+        fs::rename(&paths.previous_executable, &paths.executable)?; //as done by the startup guard of the service
+
+        assert_that!(check_on_startup(&paths, "1.0.0")?, eq(&StartupCheck::NoUpdatePending));
+
+        assert_that!(fs::read(&paths.executable)?, eq(b"old executable".to_vec()));
+        assert_that!(paths.pending_update_file.exists(), eq(false));
+        assert_that!(has_failed(&paths, "1.1.0")?, eq(true));
+        assert_that!(has_failed(&paths, "1.2.0")?, eq(false));
+        Ok(())
+    }
+
+    #[test]
+    fn should_keep_the_update_when_confirmed() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let paths = install_update(&temp)?;
+
+        assert_that!(check_on_startup(&paths, "1.1.0")?, eq(&StartupCheck::AwaitingReconnect));
+        let confirmed = confirm(&paths)?;
+
+        assert_that!(confirmed.map(|update| update.version), some(eq("1.1.0")));
+        assert_that!(fs::read(&paths.executable)?, eq(b"new executable".to_vec()));
+        assert_that!(paths.previous_executable.exists(), eq(false));
+        assert_that!(check_on_startup(&paths, "1.1.0")?, eq(&StartupCheck::NoUpdatePending));
+        assert_that!(has_failed(&paths, "1.1.0")?, eq(false));
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+#!/bin/sh
+# Runs before each start of the EDGAR service, independently of the EDGAR executable.
+# Restores the previous executable, if an updated executable keeps failing to start,
+# before it gets far enough to roll back by itself.
+set -eu
+
+executable="$1"
+directory="$(dirname "$executable")"
+pending_update_file="$directory/update-pending.json"
+start_attempts_file="$directory/update-start-attempts"
+max_start_attempts=5 # more than EDGAR allows itself, so that EDGAR rolls back by itself, whenever it can
+
+if [ ! -f "$pending_update_file" ]; then
+    rm -f "$start_attempts_file"
+    exit 0
+fi
+
+attempts=$(( $(cat "$start_attempts_file" 2>/dev/null || echo 0) + 1 ))
+
+if [ "$attempts" -gt "$max_start_attempts" ] && [ -f "$executable.previous" ]; then
+    echo "The updated EDGAR failed to start $max_start_attempts times. Restoring the previous executable."
+    mv "$executable.previous" "$executable"
+    rm -f "$start_attempts_file"
+else
+    echo "$attempts" > "$start_attempts_file"
+fi
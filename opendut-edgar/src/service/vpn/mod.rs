@@ -8,17 +8,42 @@ use tracing::debug;
 
 use crate::common::settings;
 
+pub mod wireguard;
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all="kebab-case")]
 pub struct VpnConfig {
     pub enabled: bool,
+    #[serde(default)]
+    pub kind: VpnKind,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all="kebab-case")]
+pub enum VpnKind {
+    #[default]
+    Netbird,
+    #[serde(rename="wireguard")]
+    WireGuard,
+}
+
+impl VpnConfig {
+    pub fn load(settings: &LoadedConfig) -> anyhow::Result<Self> {
+        Ok(settings.config.get::<VpnConfig>(settings::key::vpn::table)?)
+    }
+
+    pub fn is_wireguard(&self) -> bool {
+        self.enabled && self.kind == VpnKind::WireGuard
+    }
 }
 
 
 pub async fn retrieve_remote_host(settings: &LoadedConfig) -> anyhow::Result<IpAddr> {
-    let vpn_config = settings.config.get::<VpnConfig>(settings::key::vpn::table)?;
+    let vpn_config = VpnConfig::load(settings)?;
 
-    let address = if vpn_config.enabled {
+    let address = if vpn_config.is_wireguard() {
+        wireguard::WireGuardConfig::load(settings)?.address
+    } else if vpn_config.enabled {
         debug!("Determining remote IP address of host in VPN network.");
         let mut client = opendut_netbird_client_api::client::Client::connect().await?;
 
@@ -39,3 +64,14 @@ pub async fn retrieve_remote_host(settings: &LoadedConfig) -> anyhow::Result<IpA
     };
     Ok(address)
 }
+
+/// Address under which other peers can reach this host. Only needed by VPNs, which rely on CARL to distribute it.
+pub fn endpoint_host(settings: &LoadedConfig) -> anyhow::Result<Option<IpAddr>> {
+    let vpn_config = VpnConfig::load(settings)?;
+
+    if vpn_config.is_wireguard() {
+        Ok(wireguard::WireGuardConfig::load(settings)?.endpoint_host)
+    } else {
+        Ok(None)
+    }
+}
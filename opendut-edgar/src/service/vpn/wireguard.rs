@@ -0,0 +1,152 @@
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use futures::StreamExt;
+use genetlink::GenetlinkHandle;
+use netlink_packet_core::{NetlinkMessage, NetlinkPayload, NLM_F_ACK, NLM_F_REQUEST};
+use netlink_packet_generic::GenlMessage;
+use netlink_packet_wireguard::constants::{AF_INET, AF_INET6, WGDEVICE_F_REPLACE_PEERS};
+use netlink_packet_wireguard::nlas::{WgAllowedIp, WgAllowedIpAttrs, WgDeviceAttrs, WgPeer, WgPeerAttrs};
+use netlink_packet_wireguard::{Wireguard, WireguardCmd};
+use tracing::{debug, info};
+
+use opendut_types::util::net::NetworkInterfaceName;
+use opendut_types::vpn::wireguard::{PrivateKey, WireGuardPeerDescriptor};
+use opendut_util::settings::LoadedConfig;
+
+use crate::common::settings;
+use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
+
+pub const INTERFACE_NAME: &str = "wg-opendut";
+
+/// Interval in which keepalive packets are sent, so that peers without an endpoint stay reachable behind NAT.
+const PERSISTENT_KEEPALIVE_SECONDS: u16 = 25;
+
+#[derive(Debug)]
+pub struct WireGuardConfig {
+    pub private_key: PrivateKey,
+    pub address: IpAddr,
+    pub prefix_length: u8,
+    pub listen_port: u16,
+    pub endpoint_host: Option<IpAddr>,
+}
+
+impl WireGuardConfig {
+    pub fn load(settings: &LoadedConfig) -> anyhow::Result<Self> {
+        use settings::key::vpn::wireguard as key;
+
+        let private_key = PrivateKey::from_str(&settings.config.get_string(key::private::key)?)
+            .with_context(|| format!("Configuration value '{}' must be a valid WireGuard key.", key::private::key))?;
+        let address = settings.config.get::<IpAddr>(key::address)
+            .map_err(|cause| anyhow!("Configuration value '{field}' must be a valid IP address: {cause}", field=key::address))?;
+        let prefix_length = settings.config.get::<u8>(key::prefix::length)?;
+        let listen_port = settings.config.get::<u16>(key::listen::port)?;
+
+        let endpoint_host = settings.config.get_string(key::endpoint::host)?;
+        let endpoint_host = if endpoint_host.trim().is_empty() {
+            None
+        } else {
+            let host = IpAddr::from_str(endpoint_host.trim())
+                .map_err(|cause| anyhow!("Configuration value '{field}' must be empty or a valid IP address: {cause}", field=key::endpoint::host))?;
+            Some(host)
+        };
+
+        Ok(Self { private_key, address, prefix_length, listen_port, endpoint_host })
+    }
+}
+
+/// (Re-)Creates the WireGuard interface with the key and address assigned by CARL, without any peers.
+/// The peers are configured, once CARL assigns this peer to a cluster.
+pub async fn create_interface(config: &WireGuardConfig, network_interface_manager: &NetworkInterfaceManagerRef) -> anyhow::Result<()> {
+    let name = interface_name();
+
+    if let Some(interface) = network_interface_manager.find_interface(&name).await? {
+        debug!("Deleting existing WireGuard interface {interface}.");
+        network_interface_manager.delete_interface(&interface).await?;
+    }
+
+    let interface = network_interface_manager.create_wireguard_interface(&name).await?;
+
+    let mut handle = connect()?;
+    set_device(&mut handle, vec![
+        WgDeviceAttrs::IfName(name.name()),
+        WgDeviceAttrs::PrivateKey(*config.private_key.as_bytes()),
+        WgDeviceAttrs::ListenPort(config.listen_port),
+    ]).await
+        .context("Failed to configure the key of the WireGuard interface.")?;
+
+    network_interface_manager.add_address(&interface, config.address, config.prefix_length).await?;
+    network_interface_manager.set_interface_up(&interface).await?;
+
+    info!("Created WireGuard interface {interface} with address {}/{}.", config.address, config.prefix_length);
+    Ok(())
+}
+
+/// Replaces the peers of the WireGuard interface, so that only the given peers are accepted.
+pub async fn configure_peers(peers: &[WireGuardPeerDescriptor]) -> anyhow::Result<()> {
+    let wireguard_peers = peers.iter()
+        .map(|peer| {
+            let (family, cidr) = match peer.address {
+                IpAddr::V4(_) => (AF_INET, 32),
+                IpAddr::V6(_) => (AF_INET6, 128),
+            };
+            let mut attributes = vec![
+                WgPeerAttrs::PublicKey(*peer.public_key.as_bytes()),
+                WgPeerAttrs::PersistentKeepalive(PERSISTENT_KEEPALIVE_SECONDS),
+                WgPeerAttrs::AllowedIps(vec![
+                    WgAllowedIp(vec![
+                        WgAllowedIpAttrs::Family(family),
+                        WgAllowedIpAttrs::IpAddr(peer.address),
+                        WgAllowedIpAttrs::Cidr(cidr),
+                    ]),
+                ]),
+            ];
+            if let Some(endpoint) = peer.endpoint {
+                attributes.push(WgPeerAttrs::Endpoint(endpoint));
+            }
+            WgPeer(attributes)
+        })
+        .collect::<Vec<_>>();
+
+    let mut handle = connect()?;
+    set_device(&mut handle, vec![
+        WgDeviceAttrs::IfName(String::from(INTERFACE_NAME)),
+        WgDeviceAttrs::Flags(WGDEVICE_F_REPLACE_PEERS),
+        WgDeviceAttrs::Peers(wireguard_peers),
+    ]).await
+        .context("Failed to configure the peers of the WireGuard interface.")?;
+
+    info!("Configured {} peer(s) on WireGuard interface '{INTERFACE_NAME}'.", peers.len());
+    Ok(())
+}
+
+pub fn interface_name() -> NetworkInterfaceName {
+    NetworkInterfaceName::try_from(INTERFACE_NAME)
+        .expect("WireGuard interface name should be valid.")
+}
+
+fn connect() -> anyhow::Result<GenetlinkHandle> {
+    let (connection, handle, _) = genetlink::new_connection()
+        .context("Failed to establish connection to generic netlink.")?;
+    tokio::spawn(connection);
+    Ok(handle)
+}
+
+async fn set_device(handle: &mut GenetlinkHandle, attributes: Vec<WgDeviceAttrs>) -> anyhow::Result<()> {
+    let mut message = NetlinkMessage::from(GenlMessage::from_payload(Wireguard {
+        cmd: WireguardCmd::SetDevice,
+        nlas: attributes,
+    }));
+    message.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+
+    let mut responses = handle.request(message).await?;
+    while let Some(response) = responses.next().await {
+        if let NetlinkPayload::Error(error) = response?.payload {
+            if let Some(code) = error.code {
+                return Err(anyhow!("Kernel rejected the WireGuard configuration with error code {code}."));
+            }
+        }
+    }
+    Ok(())
+}
@@ -12,6 +12,10 @@ pub fn executable_install_path() -> anyhow::Result<PathBuf> {
     Ok(edgar_install_directory().join(executable_name))
 }
 
+pub fn startup_guard_install_path() -> PathBuf {
+    edgar_install_directory().join("startup-guard.sh")
+}
+
 #[allow(non_snake_case)]
 pub fn PATH_dir() -> PathBuf { PathBuf::from("/usr/bin/") }
 
@@ -32,6 +36,13 @@ pub fn default_checksum_os_cert_store_ca_certificate_file() -> PathBuf {
     PathBuf::from("/usr/local/share/ca-certificates/.opendut-ca.crt.checksum")
 }
 
+pub fn default_client_certificate_path() -> PathBuf {
+    PathBuf::from("/etc/opendut/tls/edgar.pem")
+}
+pub fn default_client_certificate_key_path() -> PathBuf {
+    PathBuf::from("/etc/opendut/tls/edgar.key")
+}
+
 pub fn default_config_merge_suggestion_file_path() -> PathBuf {
     PathBuf::from("/etc/opendut/edgar-merge-suggestion.toml")
 }
@@ -65,6 +76,11 @@ pub mod netbird {
     pub fn unpacked_executable() -> anyhow::Result<PathBuf> {
         unpack_dir().map(|dir| dir.join("netbird"))
     }
+
+    /// Configuration of the NetBird client, which contains the private key of this peer.
+    pub fn client_config_file() -> PathBuf {
+        PathBuf::from("/etc/netbird/config.json")
+    }
 }
 
 pub mod rperf {
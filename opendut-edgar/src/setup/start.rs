@@ -10,7 +10,7 @@ use crate::common::task::{runner, Task};
 use crate::service::network_interface::manager::NetworkInterfaceManager;
 use crate::setup::write_configuration;
 use crate::setup::util::running_in_docker;
-use crate::setup::{tasks, Leader, User};
+use crate::setup::{constants, tasks, Leader, User};
 use opendut_types::peer::PeerSetup;
 use opendut_types::util::net::NetworkInterfaceName;
 use opendut_types::vpn::netbird::SetupKey;
@@ -26,6 +26,8 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
 
     let peer_setup = PeerSetup::decode(&setup_string)
         .context("Failed to decode Setup-String.")?;
+    let setup_id = peer_setup.setup_id
+        .context("Setup-String contains no Setup-ID, as it was generated by an older CARL. Generate a new Setup-String for the peer.")?;
 
     let service_user = determine_service_user_name();
     info!("Using service user '{}'.", service_user.name);
@@ -38,12 +40,28 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
         return Ok(());
     }
 
+    let wireguard = match &peer_setup.vpn {
+        VpnPeerConfiguration::WireGuard { private_key, address, prefix_length, listen_port } => Some(write_configuration::WireGuardConfigOverride {
+            private_key: Clone::clone(private_key),
+            address: *address,
+            prefix_length: *prefix_length,
+            listen_port: *listen_port,
+        }),
+        VpnPeerConfiguration::Disabled | VpnPeerConfiguration::Netbird { .. } => None,
+    };
+
     if dry_run.not() {
         write_configuration::WriteConfiguration::with_override(
             write_configuration::ConfigOverride {
                 peer_id: peer_setup.id,
                 carl_url: peer_setup.carl,
                 auth_config: peer_setup.auth_config,
+                update_verification_key: peer_setup.update_verification_key,
+                wireguard,
+                client_certificate: peer_setup.request_client_certificate.then(|| write_configuration::ClientCertificateConfigOverride {
+                    certificate: constants::default_client_certificate_path(),
+                    key: constants::default_client_certificate_key_path(),
+                }),
             },
             no_confirm,
         ).execute().await?;
@@ -57,8 +75,18 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
 
     tasks.append(&mut vec![
         Box::new(tasks::WriteCaCertificate::with_certificate(peer_setup.ca)),
+    ]);
+
+    if peer_setup.request_client_certificate {
+        tasks.push(Box::new(tasks::GenerateClientCertificateKey::default()));
+    }
+
+    tasks.append(&mut vec![
         Box::new(tasks::CheckCommandLinePrograms),
         Box::new(tasks::CheckCarlReachable),
+    ]);
+
+    tasks.append(&mut vec![
         Box::new(tasks::CopyExecutable),
         Box::new(tasks::copy_rperf::CopyRperf),
 
@@ -82,6 +110,9 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
                 Box::new(tasks::netbird::Connect { management_url, setup_key, mtu }),
             ]);
         }
+        VpnPeerConfiguration::WireGuard { .. } => {
+            info!("VPN is configured for WireGuard in PeerSetup. The service creates the WireGuard interface on startup.");
+        }
     };
 
     if service_user.is_root() {
@@ -97,6 +128,7 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
 
     tasks.append(&mut vec![
         Box::new(tasks::CreateServiceFile::with_service_user(service_user)),
+        Box::new(tasks::RedeemSetupString { setup_id, peer_id: peer_setup.id, request_client_certificate: peer_setup.request_client_certificate }),
         Box::new(tasks::RestartService),
     ]);
 
@@ -151,6 +183,46 @@ pub async fn unmanaged(
     runner::run(run_mode, &tasks).await
 }
 
+/// Reverses a managed setup by running the undo step of each setup task in reverse order.
+pub async fn uninstall(dry_run: DryRun, no_confirm: bool) -> anyhow::Result<()> {
+    let service_user = determine_service_user_name();
+
+    let should_run = no_confirm || uninstall_confirmation(&dry_run)?;
+    if should_run.not() {
+        return Ok(());
+    }
+
+    let mut setup_tasks: Vec<Box<dyn Task>> = vec![
+        Box::new(tasks::CopyExecutable),
+        Box::new(tasks::copy_rperf::CopyRperf),
+        Box::new(tasks::CreateKernelModuleLoadRule),
+        Box::new(tasks::netbird::Unpack::default()),
+        Box::new(tasks::netbird::InstallService),
+    ];
+    if service_user.is_root().not() {
+        setup_tasks.push(Box::new(tasks::CreateUser { service_user: service_user.clone() }));
+    }
+    setup_tasks.push(Box::new(tasks::CreateServiceFile::with_service_user(service_user)));
+
+    // The CA certificate and the NetBird setup key are only known from the Setup-String,
+    // so the undo steps of these setup tasks are created directly.
+    // Disconnecting from NetBird comes first, as it requires the NetBird service to still be running.
+    let mut tasks: Vec<Box<dyn Task>> = vec![
+        Box::new(tasks::netbird::Disconnect),
+    ];
+    tasks.extend(setup_tasks.iter().rev().filter_map(|task| task.undo()));
+    tasks.append(&mut vec![
+        Box::new(tasks::network_interface::DeleteWireGuardInterface { network_interface_manager: NetworkInterfaceManager::create()? }),
+        Box::new(tasks::RemoveCaCertificate::default()),
+        Box::new(tasks::RemoveRemainingFiles::default()),
+    ]);
+
+    let run_mode = match dry_run {
+        DryRun::Yes => RunMode::SetupDryRun,
+        DryRun::No => RunMode::Setup,
+    };
+    runner::run(run_mode, &tasks).await
+}
 
 pub async fn init_logging() -> anyhow::Result<()> {
 
@@ -198,3 +270,23 @@ fn user_confirmation(dry_run: &DryRun) -> anyhow::Result<bool> {
         }
     }
 }
+
+fn uninstall_confirmation(dry_run: &DryRun) -> anyhow::Result<bool> {
+    match dry_run {
+        DryRun::No => {
+            println!("This will remove EDGAR from your system, including its configuration and its registration in the VPN.");
+
+            let user_confirmed = crate::setup::user_confirmation_prompt("Do you want to continue?")?;
+
+            if user_confirmed.not() {
+                println!("Aborting.");
+                info!("Aborting, because user did not confirm execution.");
+            }
+            Ok(user_confirmed)
+        }
+        DryRun::Yes => {
+            println!("Pretending to remove EDGAR from your system.");
+            Ok(true)
+        }
+    }
+}
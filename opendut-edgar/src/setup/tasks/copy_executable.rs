@@ -1,5 +1,6 @@
 use crate::fs;
 use std::os::unix;
+use std::path::PathBuf;
 use std::process::Command;
 
 use anyhow::{Context, Result};
@@ -53,4 +54,49 @@ impl Task for CopyExecutable {
 
         Ok(Success::default())
     }
+
+    fn undo(&self) -> Option<Box<dyn Task>> {
+        Some(Box::new(RemoveExecutable))
+    }
+}
+
+pub struct RemoveExecutable;
+
+#[async_trait]
+impl Task for RemoveExecutable {
+    fn description(&self) -> String {
+        format!("Remove executable from \"{}\"", executable_install_path().unwrap().display())
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        let installed_path = executable_install_path()?;
+        let link_path = link_path()?;
+
+        if installed_path.exists() || link_path.symlink_metadata().is_ok() {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn execute(&self) -> Result<Success> {
+        let link_path = link_path()?;
+        if link_path.symlink_metadata().is_ok() {
+            fs::remove_file(&link_path)
+                .context(format!("Error while removing link to executable at '{}'", link_path.display()))?;
+        }
+
+        let installed_path = executable_install_path()?;
+        if installed_path.exists() {
+            fs::remove_file(&installed_path)
+                .context(format!("Error while removing executable at '{}'", installed_path.display()))?;
+        }
+
+        Ok(Success::default())
+    }
+}
+
+fn link_path() -> Result<PathBuf> {
+    let executable_path = std::env::current_exe()?;
+    let executable_name = executable_path.file_name()
+        .context("Failed to retrieve file name of executable.")?;
+    Ok(PATH_dir().join(executable_name))
 }
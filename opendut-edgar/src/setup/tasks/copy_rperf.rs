@@ -30,4 +30,34 @@ impl Task for CopyRperf {
 
         Ok(Success::default())
     }
+
+    fn undo(&self) -> Option<Box<dyn Task>> {
+        Some(Box::new(RemoveRperf))
+    }
+}
+
+pub struct RemoveRperf;
+
+#[async_trait]
+impl Task for RemoveRperf {
+    fn description(&self) -> String {
+        String::from("Remove the rperf distribution")
+    }
+
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        let rperf_path = crate::common::constants::rperf::executable_install_file();
+
+        if rperf_path.exists() {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+
+    async fn execute(&self) -> Result<Success> {
+        let rperf_path = crate::common::constants::rperf::executable_install_file();
+        fs::remove_file(rperf_path)?;
+
+        Ok(Success::default())
+    }
 }
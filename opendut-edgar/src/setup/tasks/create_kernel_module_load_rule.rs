@@ -74,4 +74,39 @@ impl Task for CreateKernelModuleLoadRule {
 
         Ok(Success::default())
     }
+
+    fn undo(&self) -> Option<Box<dyn Task>> {
+        Some(Box::new(RemoveKernelModuleLoadRule))
+    }
+}
+
+pub struct RemoveKernelModuleLoadRule;
+
+#[async_trait]
+impl Task for RemoveKernelModuleLoadRule {
+    fn description(&self) -> String {
+        String::from("Remove rules to load kernel modules at boot time")
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        let any_rule_exists = required_kernel_modules().iter()
+            .any(|kernel_module| load_rule_file_path(kernel_module).exists() || options_rule_file_path(kernel_module).exists());
+
+        if any_rule_exists {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn execute(&self) -> Result<Success> {
+        for kernel_module in required_kernel_modules() {
+            for path in [load_rule_file_path(&kernel_module), options_rule_file_path(&kernel_module)] {
+                if path.exists() {
+                    fs::remove_file(&path)
+                        .context(format!("Error while removing kernel module rule file '{}'", path.display()))?;
+                }
+            }
+        }
+
+        Ok(Success::default())
+    }
 }
@@ -1,11 +1,13 @@
 use crate::fs;
-use std::path::PathBuf;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use crate::common::task::{Success, Task, TaskFulfilled};
-use crate::setup::constants::executable_install_path;
+use crate::service::update::STARTUP_GUARD_SCRIPT;
+use crate::setup::constants::{executable_install_path, startup_guard_install_path};
 use crate::setup::constants::SYSTEMD_SERVICE_FILE_NAME;
 use crate::setup::{User, util};
 use crate::setup::util::{CommandRunner, DefaultCommandRunner};
@@ -18,9 +20,10 @@ pub fn default_checksum_systemd_file_path() -> PathBuf {
     PathBuf::from("/etc/systemd/system/.opendut-edgar.service.checksum")
 }
 
-fn systemd_file_content(service_user: &User) -> String {
+fn systemd_file_content(service_user: &User, startup_guard_file: &Path) -> String {
     let executable = executable_install_path().unwrap();
     let executable = executable.display();
+    let startup_guard_file = startup_guard_file.display();
     let service_user = &service_user.name;
 
     format!(r#"
@@ -32,11 +35,14 @@ StartLimitIntervalSec=5
 StartLimitBurst=0
 
 [Service]
+ExecStartPre=-/bin/sh {startup_guard_file} {executable}
 ExecStart={executable} service
 Restart=always
 RestartSec=30s
 User={service_user}
 Group={service_user}
+AmbientCapabilities=CAP_NET_ADMIN
+RuntimeDirectory=opendut-edgar
 
 
 [Install]
@@ -53,8 +59,15 @@ pub struct CreateServiceFile {
     pub service_user: User,
     pub systemd_file_path: PathBuf,
     pub checksum_systemd_file: PathBuf,
+    /// Script run before each start of the service, which rolls back updates failing to start. See [STARTUP_GUARD_SCRIPT].
+    pub startup_guard_file: PathBuf,
     pub command_runner: Box<dyn CommandRunner>,
 }
+impl CreateServiceFile {
+    fn content(&self) -> String {
+        systemd_file_content(&self.service_user, &self.startup_guard_file)
+    }
+}
 
 #[async_trait]
 impl Task for CreateServiceFile {
@@ -65,9 +78,12 @@ impl Task for CreateServiceFile {
         let unpacked_systemd_checksum_file = &self.checksum_systemd_file;
         if unpacked_systemd_checksum_file.exists() {
             let systemd_installed_digest = fs::read(unpacked_systemd_checksum_file)?;
-            let systemd_distribution_digest = util::checksum::string(systemd_file_content(&self.service_user))?;
+            let systemd_distribution_digest = util::checksum::string(self.content())?;
+
+            let startup_guard_installed = self.startup_guard_file.exists()
+                && fs::read_to_string(&self.startup_guard_file)? == STARTUP_GUARD_SCRIPT;
 
-            if systemd_installed_digest == systemd_distribution_digest {
+            if systemd_installed_digest == systemd_distribution_digest && startup_guard_installed {
                 return Ok(TaskFulfilled::Yes);
             }
         }
@@ -77,7 +93,12 @@ impl Task for CreateServiceFile {
         let out_path = &self.systemd_file_path;
         fs::create_dir_all(out_path.parent().unwrap())?;
         
-        let systemd_file_content = systemd_file_content(&self.service_user);
+        let systemd_file_content = self.content();
+
+        fs::create_dir_all(self.startup_guard_file.parent().unwrap())?;
+        fs::write(&self.startup_guard_file, STARTUP_GUARD_SCRIPT)
+            .context(format!("Error while writing startup guard of the service to '{}'", self.startup_guard_file.display()))?;
+        fs::set_permissions(&self.startup_guard_file, std::fs::Permissions::from_mode(0o755))?;
 
         fs::write(out_path, &systemd_file_content)
             .context(format!("Error while writing service file to '{}'", out_path.display()))?;
@@ -94,6 +115,15 @@ impl Task for CreateServiceFile {
 
         Ok(Success::default())
     }
+
+    fn undo(&self) -> Option<Box<dyn Task>> {
+        Some(Box::new(RemoveServiceFile {
+            systemd_file_path: self.systemd_file_path.clone(),
+            checksum_systemd_file: self.checksum_systemd_file.clone(),
+            startup_guard_file: self.startup_guard_file.clone(),
+            command_runner: Box::new(DefaultCommandRunner),
+        }))
+    }
 }
 
 impl CreateServiceFile {
@@ -102,11 +132,57 @@ impl CreateServiceFile {
             service_user,
             systemd_file_path: default_systemd_file_path(),
             checksum_systemd_file: default_checksum_systemd_file_path(),
+            startup_guard_file: startup_guard_install_path(),
             command_runner: Box::new(DefaultCommandRunner),
         }
     }
 }
 
+pub struct RemoveServiceFile {
+    pub systemd_file_path: PathBuf,
+    pub checksum_systemd_file: PathBuf,
+    pub startup_guard_file: PathBuf,
+    pub command_runner: Box<dyn CommandRunner>,
+}
+
+#[async_trait]
+impl Task for RemoveServiceFile {
+    fn description(&self) -> String {
+        String::from("Stop Service and Remove Service File")
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        if self.systemd_file_path.exists() || self.checksum_systemd_file.exists() || self.startup_guard_file.exists() {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn execute(&self) -> Result<Success> {
+        if self.systemd_file_path.exists() {
+            self.command_runner.run(
+                Command::new("systemctl").args(["disable", "--now"]).arg(SYSTEMD_SERVICE_FILE_NAME)
+            ).context("systemctl disable could not be executed successfully!")?;
+
+            fs::remove_file(&self.systemd_file_path)
+                .context(format!("Error while removing service file at '{}'", self.systemd_file_path.display()))?;
+
+            self.command_runner.run(
+                Command::new("systemctl").arg("daemon-reload")
+            ).context("systemctl daemon-reload could not be executed successfully!")?;
+        }
+
+        if self.checksum_systemd_file.exists() {
+            fs::remove_file(&self.checksum_systemd_file)?;
+        }
+
+        if self.startup_guard_file.exists() {
+            fs::remove_file(&self.startup_guard_file)?;
+        }
+
+        Ok(Success::default())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_fs::prelude::*;
@@ -115,6 +191,7 @@ mod tests {
     use crate::setup::constants::SYSTEMD_SERVICE_FILE_NAME;
     use crate::common::task::{Task, TaskFulfilled};
     use crate::setup::tasks::CreateServiceFile;
+    use super::RemoveServiceFile;
     use crate::setup::User;
     use crate::setup::util::NoopCommandRunner;
 
@@ -125,11 +202,13 @@ mod tests {
         let systemd_file_path = temp.child(SYSTEMD_SERVICE_FILE_NAME);
 
         let checksum_systemd_file_path = temp.child(format!("{SYSTEMD_SERVICE_FILE_NAME}.checksum"));
+        let startup_guard_file = temp.child("startup-guard.sh");
 
         let task = CreateServiceFile {
             service_user: User { name: "testUser".to_string() },
             systemd_file_path: systemd_file_path.to_path_buf(),
             checksum_systemd_file: checksum_systemd_file_path.to_path_buf(),
+            startup_guard_file: startup_guard_file.to_path_buf(),
             command_runner: Box::new(NoopCommandRunner),
         };
 
@@ -139,4 +218,42 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn should_remove_service_file_when_undone() -> anyhow::Result<()> {
+        let temp = TempDir::new().unwrap();
+
+        let systemd_file_path = temp.child(SYSTEMD_SERVICE_FILE_NAME);
+        let checksum_systemd_file_path = temp.child(format!("{SYSTEMD_SERVICE_FILE_NAME}.checksum"));
+        let startup_guard_file = temp.child("startup-guard.sh");
+
+        let task = CreateServiceFile {
+            service_user: User { name: "testUser".to_string() },
+            systemd_file_path: systemd_file_path.to_path_buf(),
+            checksum_systemd_file: checksum_systemd_file_path.to_path_buf(),
+            startup_guard_file: startup_guard_file.to_path_buf(),
+            command_runner: Box::new(NoopCommandRunner),
+        };
+        task.execute().await?;
+
+        startup_guard_file.assert(predicates::path::exists());
+
+        let undo = RemoveServiceFile {
+            systemd_file_path: systemd_file_path.to_path_buf(),
+            checksum_systemd_file: checksum_systemd_file_path.to_path_buf(),
+            startup_guard_file: startup_guard_file.to_path_buf(),
+            command_runner: Box::new(NoopCommandRunner),
+        };
+
+        assert_eq!(undo.check_fulfilled().await?, TaskFulfilled::No);
+        undo.execute().await?;
+        assert_eq!(undo.check_fulfilled().await?, TaskFulfilled::Yes);
+
+        systemd_file_path.assert(predicates::path::missing());
+        checksum_systemd_file_path.assert(predicates::path::missing());
+        startup_guard_file.assert(predicates::path::missing());
+        assert_eq!(task.check_fulfilled().await?, TaskFulfilled::No);
+
+        Ok(())
+    }
 }
@@ -22,15 +22,7 @@ impl Task for CreateUser {
     }
 
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
-        let passwd = fs::read_to_string(passwd_file())?;
-
-        let user_exists = passwd.lines()
-            .any(|line| match line.split(':').next() {
-                Some(user) => user == self.service_user.name,
-                None => false,
-            });
-
-        if user_exists {
+        if user_exists(&self.service_user)? {
             Ok(TaskFulfilled::Yes)
         } else {
             Ok(TaskFulfilled::No)
@@ -46,4 +38,47 @@ impl Task for CreateUser {
 
         Ok(Success::default())
     }
+
+    fn undo(&self) -> Option<Box<dyn Task>> {
+        Some(Box::new(DeleteUser { service_user: self.service_user.clone() }))
+    }
+}
+
+pub struct DeleteUser {
+    pub service_user: User,
+}
+
+#[async_trait]
+impl Task for DeleteUser {
+    fn description(&self) -> String {
+        format!("Delete User \"{}\"", self.service_user.name)
+    }
+
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        if user_exists(&self.service_user)? {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+
+    async fn execute(&self) -> Result<Success> {
+        Command::new("userdel")
+            .arg(&self.service_user.name)
+            .evaluate_requiring_success()
+            .context(format!("Error when trying to delete user '{}'.", self.service_user.name))?;
+
+        Ok(Success::default())
+    }
+}
+
+fn user_exists(user: &User) -> Result<bool> {
+    let passwd = fs::read_to_string(passwd_file())?;
+
+    let user_exists = passwd.lines()
+        .any(|line| match line.split(':').next() {
+            Some(name) => name == user.name,
+            None => false,
+        });
+    Ok(user_exists)
 }
@@ -20,6 +20,9 @@ pub mod netbird;
 
 pub mod network_interface;
 
+mod redeem_setup_string;
+pub use redeem_setup_string::RedeemSetupString;
+
 mod request_linux_network_capability;
 pub use request_linux_network_capability::RequestLinuxNetworkCapability;
 
@@ -33,6 +36,12 @@ pub mod create_kernel_module_load_rule;
 pub use create_kernel_module_load_rule::CreateKernelModuleLoadRule;
 
 pub mod write_ca_certificate;
-pub use write_ca_certificate::WriteCaCertificate;
+pub use write_ca_certificate::{RemoveCaCertificate, WriteCaCertificate};
+
+pub mod generate_client_certificate_key;
+pub use generate_client_certificate_key::GenerateClientCertificateKey;
 
 pub mod copy_rperf;
+
+mod remove_remaining_files;
+pub use remove_remaining_files::RemoveRemainingFiles;
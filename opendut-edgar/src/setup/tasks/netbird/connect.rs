@@ -8,6 +8,8 @@ use url::Url;
 use opendut_types::vpn::netbird::SetupKey;
 
 use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::fs;
+use crate::setup::constants;
 
 const UP_CHECK_RETRIES: usize = 50;
 const UP_CHECK_INTERVAL: Duration = Duration::from_millis(200);
@@ -51,4 +53,44 @@ impl Task for Connect {
         }
         Err(anyhow!("Connection to NetBird Management Service at '{}' was not up after {}*{} ms.", self.management_url, UP_CHECK_RETRIES, UP_CHECK_INTERVAL.as_millis()))
     }
+
+    fn undo(&self) -> Option<Box<dyn Task>> {
+        Some(Box::new(Disconnect))
+    }
+}
+
+/// Disconnects from the NetBird Management Service and removes the local NetBird configuration,
+/// which contains the private key this peer is registered with.
+/// The registration itself remains in the NetBird Management Service, until CARL deletes the peer.
+pub struct Disconnect;
+
+#[async_trait]
+impl Task for Disconnect {
+    fn description(&self) -> String {
+        String::from("NetBird - Disconnect")
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        if constants::netbird::client_config_file().exists() {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn execute(&self) -> Result<Success> {
+        let down = async {
+            let mut client = opendut_netbird_client_api::client::Client::connect().await?;
+            client.down().await
+        }.await;
+        if let Err(cause) = down {
+            debug!("Could not bring down NetBird connection, continuing as it is likely not running: {cause}");
+        }
+
+        let config_file = constants::netbird::client_config_file();
+        if config_file.exists() {
+            fs::remove_file(&config_file)
+                .context(format!("Error while removing NetBird configuration at '{}'.", config_file.display()))?;
+        }
+
+        Ok(Success::default())
+    }
 }
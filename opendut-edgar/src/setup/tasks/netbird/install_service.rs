@@ -14,12 +14,7 @@ impl Task for InstallService {
         String::from("NetBird - Install Service")
     }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
-        let output = Command::new("systemctl")
-            .arg("cat")
-            .arg("netbird")
-            .output()?;
-
-        if output.status.success() {
+        if service_installed()? {
             Ok(TaskFulfilled::Yes)
         } else {
             Ok(TaskFulfilled::No)
@@ -35,4 +30,48 @@ impl Task for InstallService {
 
         Ok(Success::default())
     }
+
+    fn undo(&self) -> Option<Box<dyn Task>> {
+        Some(Box::new(UninstallService))
+    }
+}
+
+pub struct UninstallService;
+
+#[async_trait]
+impl Task for UninstallService {
+    fn description(&self) -> String {
+        String::from("NetBird - Uninstall Service")
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        if service_installed()? {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn execute(&self) -> Result<Success> {
+        let netbird = netbird::unpacked_executable()?;
+
+        let _ = Command::new(&netbird)
+            .arg("service")
+            .arg("stop")
+            .output(); //ignore errors, e.g. when service is not running
+
+        let _ = Command::new(netbird)
+            .arg("service")
+            .arg("uninstall")
+            .evaluate_requiring_success()?;
+
+        Ok(Success::default())
+    }
+}
+
+fn service_installed() -> Result<bool> {
+    let output = Command::new("systemctl")
+        .arg("cat")
+        .arg("netbird")
+        .output()?;
+
+    Ok(output.status.success())
 }
@@ -1,5 +1,5 @@
 pub mod connect;
-pub use connect::Connect;
+pub use connect::{Connect, Disconnect};
 
 pub mod restart_service;
 pub use restart_service::RestartService;
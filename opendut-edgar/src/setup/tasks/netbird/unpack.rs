@@ -51,6 +51,13 @@ impl Task for Unpack {
 
         Ok(Success::default())
     }
+
+    fn undo(&self) -> Option<Box<dyn Task>> {
+        Some(Box::new(RemoveUnpacked {
+            dir: self.to_dir.clone(),
+            checksum_unpack_file: self.checksum_unpack_file.clone(),
+        }))
+    }
 }
 impl Default for Unpack {
     fn default() -> Self {
@@ -62,6 +69,36 @@ impl Default for Unpack {
     }
 }
 
+pub struct RemoveUnpacked {
+    dir: PathBuf,
+    checksum_unpack_file: PathBuf,
+}
+
+#[async_trait]
+impl Task for RemoveUnpacked {
+    fn description(&self) -> String {
+        String::from("NetBird - Remove Unpacked Files")
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        if self.dir.exists() || self.checksum_unpack_file.exists() {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn execute(&self) -> Result<Success> {
+        if self.dir.exists() {
+            fs::remove_dir_all(&self.dir)
+                .context(format!("Failed to remove unpacked NetBird distribution at '{}'.", self.dir.display()))?;
+        }
+        if self.checksum_unpack_file.exists() {
+            fs::remove_file(&self.checksum_unpack_file)?;
+        }
+
+        Ok(Success::default())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_fs::prelude::*;
@@ -0,0 +1,35 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
+use crate::service::vpn::wireguard;
+
+/// Deletes the WireGuard interface, which the EDGAR service creates when using WireGuard as VPN.
+/// The interface would otherwise remain until the next reboot, as the service does not remove it when stopping.
+pub struct DeleteWireGuardInterface {
+    pub network_interface_manager: NetworkInterfaceManagerRef,
+}
+
+#[async_trait]
+impl Task for DeleteWireGuardInterface {
+    fn description(&self) -> String {
+        format!("Delete WireGuard Interface \"{}\"", wireguard::INTERFACE_NAME)
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        let interface_exists = self.network_interface_manager.find_interface(&wireguard::interface_name()).await?
+            .is_some();
+
+        if interface_exists {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn execute(&self) -> Result<Success> {
+        if let Some(interface) = self.network_interface_manager.find_interface(&wireguard::interface_name()).await? {
+            self.network_interface_manager.delete_interface(&interface).await?;
+        }
+        Ok(Success::default())
+    }
+}
@@ -5,4 +5,7 @@ mod create_gre_interface;
 pub use create_gre_interface::CreateGreInterfaces;
 
 mod connect_device_interfaces;
-pub use connect_device_interfaces::ConnectDeviceInterfaces;
\ No newline at end of file
+pub use connect_device_interfaces::ConnectDeviceInterfaces;
+
+mod delete_wireguard_interface;
+pub use delete_wireguard_interface::DeleteWireGuardInterface;
\ No newline at end of file
@@ -0,0 +1,67 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use config::Config;
+
+use opendut_types::peer::PeerId;
+use opendut_types::peer::setup::PeerSetupId;
+
+use crate::common;
+use crate::common::settings;
+use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::service::credentials;
+use crate::setup::tasks::generate_client_certificate_key;
+
+/// Tells CARL that the Setup-String was used, so that it cannot be used for setting up another EDGAR.
+/// CARL replaces the OIDC client contained in the Setup-String, so the new credentials are stored in the configuration file.
+/// If requested, CARL issues the client certificate for the key generated by [`GenerateClientCertificateKey`](super::GenerateClientCertificateKey).
+///
+/// Runs as the last task before the service is started, so that the Setup-String stays usable, if an earlier task fails.
+pub struct RedeemSetupString {
+    pub setup_id: PeerSetupId,
+    pub peer_id: PeerId,
+    pub request_client_certificate: bool,
+}
+
+#[async_trait]
+impl Task for RedeemSetupString {
+    fn description(&self) -> String {
+        String::from("Redeem Setup-String")
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        Ok(TaskFulfilled::Unchecked)
+    }
+    async fn execute(&self) -> Result<Success> {
+        let config_file = settings::default_config_file_path();
+
+        let certificate_signing_request = if self.request_client_certificate {
+            let (_, key_path) = credentials::client_certificate_paths(&config_file)?;
+            Some(generate_client_certificate_key::certificate_signing_request(&key_path)?)
+        } else {
+            None
+        };
+
+        // The client certificate is only issued with this request, so connect without one.
+        let overrides = Config::builder()
+            .set_override(settings::key::network::tls::client::certificate, "")?
+            .set_override(settings::key::network::tls::client::key, "")?
+            .build()?;
+        let settings = settings::load_with_overrides(overrides)?;
+
+        let mut carl = common::carl::connect(&settings.config).await
+            .context("Failed to connect to CARL")?;
+
+        let redeemed = carl.peers.redeem_peer_setup(self.setup_id, self.peer_id, certificate_signing_request).await
+            .context("CARL did not accept the Setup-String")?;
+
+        if let Some(credentials) = redeemed.credentials {
+            credentials::store_rotated_credentials(&config_file, &credentials)
+                .context("Failed to store the OIDC client credentials issued by CARL. Generate a new Setup-String for the peer.")?;
+        }
+        if let Some(certificate) = redeemed.client_certificate {
+            credentials::store_issued_client_certificate(&config_file, &certificate)
+                .context("Failed to store the client certificate issued by CARL. Generate a new Setup-String for the peer.")?;
+        }
+
+        Ok(Success::default())
+    }
+}
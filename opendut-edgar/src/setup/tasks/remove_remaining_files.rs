@@ -0,0 +1,123 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+use crate::common::constants::edgar_install_directory;
+use crate::common::settings::default_config_file_path;
+use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::fs;
+use crate::setup::constants;
+
+/// Removes the files, which are not created by a setup task, like the configuration file written during setup.
+/// Runs last when uninstalling, to also remove what is left in the installation directory.
+pub struct RemoveRemainingFiles {
+    pub files: Vec<PathBuf>,
+    pub install_dir: PathBuf,
+    /// Removed only if they are empty, as they may be shared with other openDuT components.
+    pub parent_dirs: Vec<PathBuf>,
+}
+
+#[async_trait]
+impl Task for RemoveRemainingFiles {
+    fn description(&self) -> String {
+        String::from("Remove Configuration and Installation Directory")
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        let files_exist = self.files.iter().any(|file| file.exists());
+        let empty_parent_dir_exists = self.parent_dirs.iter().any(|dir| is_empty_dir(dir));
+
+        if files_exist || self.install_dir.exists() || empty_parent_dir_exists {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn execute(&self) -> Result<Success> {
+        for file in &self.files {
+            if file.exists() {
+                fs::remove_file(file)
+                    .context(format!("Error while removing file '{}'", file.display()))?;
+            }
+        }
+
+        if self.install_dir.exists() {
+            fs::remove_dir_all(&self.install_dir)
+                .context(format!("Error while removing installation directory '{}'", self.install_dir.display()))?;
+        }
+
+        for dir in &self.parent_dirs {
+            if is_empty_dir(dir) {
+                fs::remove_dir(dir)
+                    .context(format!("Error while removing directory '{}'", dir.display()))?;
+            }
+        }
+
+        Ok(Success::default())
+    }
+}
+
+impl Default for RemoveRemainingFiles {
+    fn default() -> Self {
+        Self {
+            files: vec![
+                default_config_file_path(),
+                constants::default_config_merge_suggestion_file_path(),
+                constants::default_client_certificate_path(),
+                constants::default_client_certificate_key_path(),
+            ],
+            install_dir: edgar_install_directory(),
+            parent_dirs: vec![
+                PathBuf::from("/etc/opendut/tls/"),
+                PathBuf::from("/etc/opendut/"),
+                PathBuf::from("/opt/opendut/"),
+            ],
+        }
+    }
+}
+
+fn is_empty_dir(dir: &Path) -> bool {
+    fs::read_dir(dir)
+        .map(|mut entries| entries.next().is_none())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    use crate::common::task::{Task, TaskFulfilled};
+
+    use super::RemoveRemainingFiles;
+
+    #[tokio::test]
+    async fn should_remove_files_and_only_empty_parent_dirs() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+
+        let config_dir = temp.child("etc");
+        let config_file = config_dir.child("edgar.toml");
+        config_file.write_str("[peer]")?;
+
+        let shared_dir = temp.child("opt");
+        let install_dir = shared_dir.child("edgar");
+        install_dir.child("plugins").create_dir_all()?;
+        shared_dir.child("other").create_dir_all()?;
+
+        let task = RemoveRemainingFiles {
+            files: vec![config_file.to_path_buf()],
+            install_dir: install_dir.to_path_buf(),
+            parent_dirs: vec![config_dir.to_path_buf(), shared_dir.to_path_buf()],
+        };
+
+        assert_eq!(task.check_fulfilled().await?, TaskFulfilled::No);
+        task.execute().await?;
+        assert_eq!(task.check_fulfilled().await?, TaskFulfilled::Yes);
+
+        config_dir.assert(predicates::path::missing());
+        install_dir.assert(predicates::path::missing());
+        shared_dir.assert(predicates::path::exists());
+
+        Ok(())
+    }
+}
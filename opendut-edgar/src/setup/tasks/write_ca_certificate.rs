@@ -79,6 +79,16 @@ impl Task for WriteCaCertificate {
 
         Ok(Success::default())
     }
+
+    fn undo(&self) -> Option<Box<dyn Task>> {
+        Some(Box::new(RemoveCaCertificate {
+            carl_ca_certificate_path: self.carl_ca_certificate_path.clone(),
+            os_cert_store_ca_certificate_path: self.os_cert_store_ca_certificate_path.clone(),
+            checksum_carl_ca_certificate_file: self.checksum_carl_ca_certificate_file.clone(),
+            checksum_os_cert_store_ca_certificate_file: self.checksum_os_cert_store_ca_certificate_file.clone(),
+            command_runner: Box::new(DefaultCommandRunner),
+        }))
+    }
 }
 
 impl WriteCaCertificate {
@@ -94,6 +104,72 @@ impl WriteCaCertificate {
     }
 }
 
+pub struct RemoveCaCertificate {
+    pub carl_ca_certificate_path: PathBuf,
+    pub os_cert_store_ca_certificate_path: PathBuf,
+    pub checksum_carl_ca_certificate_file: PathBuf,
+    pub checksum_os_cert_store_ca_certificate_file: PathBuf,
+    pub command_runner: Box<dyn CommandRunner>,
+}
+
+impl RemoveCaCertificate {
+    fn files(&self) -> [&Path; 4] {
+        [
+            &self.carl_ca_certificate_path,
+            &self.checksum_carl_ca_certificate_file,
+            &self.os_cert_store_ca_certificate_path,
+            &self.checksum_os_cert_store_ca_certificate_file,
+        ]
+    }
+}
+
+#[async_trait]
+impl Task for RemoveCaCertificate {
+
+    fn description(&self) -> String {
+        String::from("Remove CA Certificates")
+    }
+
+    async fn check_fulfilled(&self) -> anyhow::Result<TaskFulfilled> {
+        if self.files().iter().any(|file| file.exists()) {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+
+    async fn execute(&self) -> anyhow::Result<Success> {
+        let os_cert_store_certificate_existed = self.os_cert_store_ca_certificate_path.exists();
+
+        for file in self.files() {
+            if file.exists() {
+                fs::remove_file(file)
+                    .context(format!("Removing CA certificate file '{}' was not successful.", file.display()))?;
+            }
+        }
+
+        if os_cert_store_certificate_existed {
+            self.command_runner.run(
+                &mut Command::new("update-ca-certificates") //Remove certificate from the OS certificate store
+            ).context("update-ca-certificates could not be executed successfully!")?;
+        }
+
+        Ok(Success::default())
+    }
+}
+
+impl Default for RemoveCaCertificate {
+    fn default() -> Self {
+        Self {
+            carl_ca_certificate_path: constants::default_carl_ca_certificate_path(),
+            os_cert_store_ca_certificate_path: constants::default_os_cert_store_ca_certificate_path(),
+            checksum_carl_ca_certificate_file: constants::default_checksum_carl_ca_certificate_file(),
+            checksum_os_cert_store_ca_certificate_file: constants::default_checksum_os_cert_store_ca_certificate_file(),
+            command_runner: Box::new(DefaultCommandRunner),
+        }
+    }
+}
+
 fn write_carl_certificate(new_certificate: &Certificate, carl_ca_certificate_path: &Path, checksum_carl_ca_certificate_file: &Path) -> anyhow::Result<()> {
 
     let carl_ca_certificate_dir = carl_ca_certificate_path.parent().unwrap();
@@ -161,6 +237,7 @@ mod tests {
 
     use crate::common::task::{Task, TaskFulfilled};
     use crate::setup::tasks::WriteCaCertificate;
+    use crate::setup::tasks::write_ca_certificate::RemoveCaCertificate;
     use crate::setup::util;
     use crate::setup::util::NoopCommandRunner;
 
@@ -192,6 +269,38 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_remove_all_certificate_files_when_undone() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+
+        let carl_ca_certificate_path = temp.child("ca.pem");
+        let os_cert_store_ca_certificate_path = temp.child("opendut-ca.crt");
+
+        let checksum_carl_ca_certificate_file = temp.child("ca.pem.checksum");
+        let checksum_os_cert_store_ca_certificate_file = temp.child("opendut-ca.crt.checksum");
+
+        for file in [&carl_ca_certificate_path, &os_cert_store_ca_certificate_path, &checksum_carl_ca_certificate_file, &checksum_os_cert_store_ca_certificate_file] {
+            file.write_str(PEM_STRING_1)?;
+        }
+
+        let task = RemoveCaCertificate {
+            carl_ca_certificate_path: carl_ca_certificate_path.to_path_buf(),
+            os_cert_store_ca_certificate_path: os_cert_store_ca_certificate_path.to_path_buf(),
+            checksum_carl_ca_certificate_file: checksum_carl_ca_certificate_file.to_path_buf(),
+            checksum_os_cert_store_ca_certificate_file: checksum_os_cert_store_ca_certificate_file.to_path_buf(),
+            command_runner: Box::new(NoopCommandRunner),
+        };
+
+        assert_eq!(task.check_fulfilled().await?, TaskFulfilled::No);
+        task.execute().await?;
+        assert_eq!(task.check_fulfilled().await?, TaskFulfilled::Yes);
+
+        carl_ca_certificate_path.assert(predicates::path::missing());
+        os_cert_store_ca_certificate_path.assert(predicates::path::missing());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn should_report_task_as_unfulfilled_when_checksums_dont_match() -> anyhow::Result<()> {
         let temp = TempDir::new()?;
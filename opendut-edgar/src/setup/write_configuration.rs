@@ -1,5 +1,6 @@
 use crate::fs;
 use std::ops::Not;
+use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
@@ -8,7 +9,10 @@ use tracing::{debug, error, info};
 use url::Url;
 
 use opendut_types::peer::PeerId;
+use opendut_types::peer::update::UpdateVerificationKey;
 use opendut_types::util::net::AuthConfig;
+use opendut_types::util::Port;
+use opendut_types::vpn::wireguard::PrivateKey;
 
 use crate::common::settings;
 use crate::setup::constants;
@@ -25,6 +29,20 @@ pub struct ConfigOverride {
     pub peer_id: PeerId,
     pub carl_url: Url,
     pub auth_config: AuthConfig,
+    pub update_verification_key: Option<UpdateVerificationKey>,
+    pub wireguard: Option<WireGuardConfigOverride>,
+    pub client_certificate: Option<ClientCertificateConfigOverride>,
+}
+/// Paths of the client certificate and key, with which EDGAR authenticates towards CARL via mutual TLS.
+pub struct ClientCertificateConfigOverride {
+    pub certificate: PathBuf,
+    pub key: PathBuf,
+}
+pub struct WireGuardConfigOverride {
+    pub private_key: PrivateKey,
+    pub address: IpAddr,
+    pub prefix_length: u8,
+    pub listen_port: Port,
 }
 
 impl WriteConfiguration {
@@ -89,6 +107,60 @@ impl WriteConfiguration {
                 }
             };
 
+            match &self.config_override.client_certificate {
+                Some(client_certificate) => {
+                    if new_settings["network"].get("tls").is_none() {
+                        new_settings["network"]["tls"] = toml_edit::table();
+                    }
+                    if new_settings["network"]["tls"].get("client").is_none() {
+                        new_settings["network"]["tls"]["client"] = toml_edit::table();
+                        new_settings["network"]["tls"]["client"].as_table_mut().unwrap().set_dotted(true);
+                    }
+                    new_settings["network"]["tls"]["client"]["certificate"] = toml_edit::value(client_certificate.certificate.display().to_string());
+                    new_settings["network"]["tls"]["client"]["key"] = toml_edit::value(client_certificate.key.display().to_string());
+                }
+                None => {
+                    if let Some(tls) = new_settings.get_mut("network").and_then(|network| network.get_mut("tls")).and_then(|tls| tls.as_table_like_mut()) {
+                        tls.remove("client");
+                    }
+                }
+            }
+
+            if let Some(update_verification_key) = &self.config_override.update_verification_key {
+                if new_settings.get("update").is_none() {
+                    new_settings["update"] = toml_edit::table();
+                    new_settings["update"]["verification"] = toml_edit::table();
+                    new_settings["update"]["verification"].as_table_mut().unwrap().set_dotted(true);
+                }
+                new_settings["update"]["verification"]["key"] = toml_edit::value(update_verification_key.to_string());
+            }
+
+            match &self.config_override.wireguard {
+                Some(wireguard) => {
+                    if new_settings.get("vpn").is_none() {
+                        new_settings["vpn"] = toml_edit::table();
+                    }
+                    new_settings["vpn"]["kind"] = toml_edit::value("wireguard");
+
+                    if new_settings.get("vpn").and_then(|vpn| vpn.get("wireguard")).is_none() {
+                        new_settings["vpn"]["wireguard"] = toml_edit::table();
+                        for dotted in ["private", "prefix", "listen"] {
+                            new_settings["vpn"]["wireguard"][dotted] = toml_edit::table();
+                            new_settings["vpn"]["wireguard"][dotted].as_table_mut().unwrap().set_dotted(true);
+                        }
+                    }
+                    new_settings["vpn"]["wireguard"]["private"]["key"] = toml_edit::value(String::from(wireguard.private_key.clone()));
+                    new_settings["vpn"]["wireguard"]["address"] = toml_edit::value(wireguard.address.to_string());
+                    new_settings["vpn"]["wireguard"]["prefix"]["length"] = toml_edit::value(i64::from(wireguard.prefix_length));
+                    new_settings["vpn"]["wireguard"]["listen"]["port"] = toml_edit::value(i64::from(wireguard.listen_port.0));
+                }
+                None => {
+                    if let Some(vpn) = new_settings.get_mut("vpn").and_then(|vpn| vpn.as_table_like_mut()) {
+                        vpn.remove("kind");
+                    }
+                }
+            }
+
             new_settings.to_string()
         };
 
@@ -367,6 +439,77 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_write_the_wireguard_configuration() -> anyhow::Result<()> {
+        let fixture = Fixture::new();
+        let mut write_configuration = create_write_configuration(&fixture, AuthEnabled::No);
+        write_configuration.config_override.wireguard = Some(WireGuardConfigOverride {
+            private_key: PrivateKey::from_str("yAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=")?,
+            address: IpAddr::from_str("10.100.0.2")?,
+            prefix_length: 16,
+            listen_port: Port(51820),
+        });
+
+        let path = write_configuration.config_file_to_write_to.clone();
+        write_configuration.execute().await?;
+
+        let file_content = fs::read_to_string(&path)?;
+        assert_that!(file_content, eq(indoc!(r#"
+            [peer]
+            id = "dc72f6d9-d700-455f-8c31-9f15438e7503"
+
+            [network]
+            carl.host = "example.com"
+            carl.port = 1234
+
+            [network.oidc]
+            enabled = false
+
+            [vpn]
+            kind = "wireguard"
+
+            [vpn.wireguard]
+            private.key = "yAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk="
+            prefix.length = 16
+            listen.port = 51820
+            address = "10.100.0.2"
+        "#)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_write_the_paths_of_the_client_certificate() -> anyhow::Result<()> {
+        let fixture = Fixture::new();
+        let mut write_configuration = create_write_configuration(&fixture, AuthEnabled::No);
+        write_configuration.config_override.client_certificate = Some(ClientCertificateConfigOverride {
+            certificate: PathBuf::from("/etc/opendut/tls/edgar.pem"),
+            key: PathBuf::from("/etc/opendut/tls/edgar.key"),
+        });
+
+        let path = write_configuration.config_file_to_write_to.clone();
+        write_configuration.execute().await?;
+
+        let file_content = fs::read_to_string(&path)?;
+        assert_that!(file_content, eq(indoc!(r#"
+            [peer]
+            id = "dc72f6d9-d700-455f-8c31-9f15438e7503"
+
+            [network]
+            carl.host = "example.com"
+            carl.port = 1234
+
+            [network.oidc]
+            enabled = false
+
+            [network.tls]
+            client.certificate = "/etc/opendut/tls/edgar.pem"
+            client.key = "/etc/opendut/tls/edgar.key"
+        "#)));
+
+        Ok(())
+    }
+
     fn create_write_configuration(
         fixture: &Fixture,
         auth_enabled: AuthEnabled,
@@ -382,11 +525,17 @@ mod tests {
                     client_id: ClientId::from(CLIENT_ID),
                     scopes: vec![OAuthScope("test".to_string())],
                 },
+                update_verification_key: None,
+                wireguard: None,
+                client_certificate: None,
             },
             AuthEnabled::No => ConfigOverride {
                 peer_id: fixture.peer_id,
                 carl_url: Url::parse("https://example.com:1234").unwrap(),
                 auth_config: AuthConfig::Disabled,
+                update_verification_key: None,
+                wireguard: None,
+                client_certificate: None,
             },
         };
 
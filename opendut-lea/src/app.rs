@@ -7,9 +7,9 @@ use leptos_oidc::{Auth, AuthParameters, AuthSignal};
 use serde::{Deserialize, Deserializer};
 use tracing::{error, info};
 use url::Url;
-use opendut_auth::public::Authentication;
+use opendut_auth::public::{Authentication, LocalUserToken};
 use opendut_carl_api::carl::wasm::CarlClient;
-use opendut_types::lea::LeaConfig;
+use opendut_types::lea::{LeaConfig, LeaLocalUsersConfig};
 use crate::components::{AppGlobalsResource, Toaster};
 use crate::nav::Navbar;
 use crate::routing::AppRoutes;
@@ -32,6 +32,7 @@ pub fn use_app_globals() -> AppGlobals {
 pub struct AppConfig {
     pub carl_url: Url,
     pub auth_parameters: Option<AuthParameters>,
+    pub local_users: Option<LeaLocalUsersConfig>,
 }
 
 impl<'de> Deserialize<'de> for AppConfig {
@@ -55,11 +56,13 @@ impl<'de> Deserialize<'de> for AppConfig {
                         scope: Some(idp_config.scopes),
                         audience: None,
                     }),
+                    local_users: lea_config.local_users,
                 })
             },
             None => Ok(AppConfig {
                 carl_url: lea_config.carl_url,
                 auth_parameters: None,
+                local_users: lea_config.local_users,
             })
         }
     }
@@ -74,6 +77,7 @@ pub struct AppGlobalsError {
 #[component]
 pub fn LoadingApp() -> impl IntoView {
     let _ = provide_authentication_signals_in_context();
+    let local_user_token = use_context::<LocalUserToken>().expect("LocalUserToken should be provided in the context.");
 
     let app_globals: AppGlobalsResource = LocalResource::new(move || {
         async {
@@ -85,14 +89,15 @@ pub fn LoadingApp() -> impl IntoView {
 
             info!("Configuration: {config:?}");
 
-            let maybe_auth = match config.auth_parameters {
-                Some(ref auth_parameters) => {
+            let maybe_auth = match (&config.auth_parameters, &config.local_users) {
+                (Some(auth_parameters), _) => {
                     info!("Auth parameters: {auth_parameters:?}");
                     let _ = Auth::init(auth_parameters.clone());
                     let auth = use_context::<AuthSignal>().expect("AuthSignal should be provided in app_globals.");
                     Authentication::Enabled(auth)
                 },
-                None => Authentication::Disabled
+                (None, Some(_)) => Authentication::LocalUsers(local_user_token),
+                (None, None) => Authentication::Disabled
             };
             
             let auth_config_switch = use_context::<RwSignal<AuthenticationConfigSwitch>>().expect("RwSignal<AuthenticationConfigSwitch> should be provided in the context.");
@@ -103,6 +108,9 @@ pub fn LoadingApp() -> impl IntoView {
                 Authentication::Enabled(_) => {
                     auth_config_switch.set(AuthenticationConfigSwitch::Enabled);
                 }
+                Authentication::LocalUsers(_) => {
+                    auth_config_switch.set(AuthenticationConfigSwitch::LocalUsers);
+                }
             }
 
             let client = CarlClient::create(Clone::clone(&config.carl_url), maybe_auth.clone()).await
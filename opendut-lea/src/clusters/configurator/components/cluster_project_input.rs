@@ -0,0 +1,36 @@
+use leptos::prelude::*;
+
+use opendut_types::project::ProjectId;
+
+use crate::components::{UserInput, UserInputValue};
+use crate::clusters::configurator::types::UserClusterConfiguration;
+
+#[component]
+pub fn ClusterProjectInput(cluster_configuration: RwSignal<UserClusterConfiguration>) -> impl IntoView {
+
+    let (getter, setter) = create_slice(cluster_configuration,
+        |config| {
+            Clone::clone(&config.project)
+        },
+        |config, input| {
+            config.project = input;
+        }
+    );
+
+    let validator = |input: String| {
+        match ProjectId::try_from(input.clone()) {
+            Ok(_) => UserInputValue::Right(input),
+            Err(cause) => UserInputValue::Both(cause.to_string(), input),
+        }
+    };
+
+    view! {
+        <UserInput
+            getter=getter
+            setter=setter
+            label="Project"
+            placeholder="default"
+            validator=validator
+        />
+    }
+}
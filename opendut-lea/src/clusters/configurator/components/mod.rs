@@ -1,6 +1,7 @@
 mod controls;
 mod device_selector;
 mod cluster_name_input;
+mod cluster_project_input;
 mod leader_selector;
 
 use std::collections::HashSet;
@@ -8,6 +9,7 @@ use leptos::prelude::*;
 pub use controls::Controls;
 pub use device_selector::{DeviceSelector, DeviceSelection};
 pub use cluster_name_input::ClusterNameInput;
+pub use cluster_project_input::ClusterProjectInput;
 pub use leader_selector::{LeaderSelector, LeaderSelection};
 use opendut_types::topology::DeviceId;
 
@@ -2,6 +2,7 @@ use leptos::prelude::*;
 use leptos_router::hooks::use_params_map;
 use opendut_types::cluster::ClusterId;
 use opendut_types::peer::PeerDescriptor;
+use opendut_types::project::ProjectId;
 
 use crate::app::use_app_globals;
 use crate::clusters::configurator::components::{DeviceSelection, DeviceSelector, LeaderSelection};
@@ -31,8 +32,11 @@ pub fn ClusterConfigurator() -> impl IntoView {
         UserClusterConfiguration {
             id: cluster_id.get_untracked(),
             name: UserInputValue::Left(String::from("Enter a valid cluster name.")),
+            project: UserInputValue::Right(String::from(ProjectId::DEFAULT)),
             devices: DeviceSelection::Left(String::from("Select at least two devices.")),
             leader: LeaderSelection::Left(String::from("Select a leader.")),
+            labels: Default::default(),
+            device_selector: None,
         }
     );
 
@@ -49,8 +53,11 @@ pub fn ClusterConfigurator() -> impl IntoView {
                         UserClusterConfiguration {
                             id: cluster_id,
                             name: UserInputValue::Right(configuration.name.value()),
+                            project: UserInputValue::Right(configuration.project.to_string()),
                             devices: DeviceSelection::Right(configuration.devices),
                             leader: LeaderSelection::Right(configuration.leader),
+                            labels: configuration.labels,
+                            device_selector: configuration.device_selector,
                         }
                     )
                 } else {
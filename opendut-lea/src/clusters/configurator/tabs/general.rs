@@ -1,6 +1,6 @@
 use leptos::prelude::*;
 
-use crate::clusters::configurator::components::ClusterNameInput;
+use crate::clusters::configurator::components::{ClusterNameInput, ClusterProjectInput};
 use crate::clusters::configurator::types::UserClusterConfiguration;
 use crate::components::ReadOnlyInput;
 
@@ -18,6 +18,9 @@ pub fn GeneralTab(cluster_configuration: RwSignal<UserClusterConfiguration>) ->
             <ClusterNameInput
                 cluster_configuration=cluster_configuration
             />
+            <ClusterProjectInput
+                cluster_configuration=cluster_configuration
+            />
         </div>
     }
 }
@@ -1,4 +1,6 @@
 use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+use opendut_types::labels::{LabelSelector, Labels};
+use opendut_types::project::ProjectId;
 
 use crate::clusters::configurator::components::{DeviceSelection, LeaderSelection};
 use crate::components::UserInputValue;
@@ -13,21 +15,30 @@ pub enum ClusterMisconfiguration { // TODO: Maybe replace with IllegalClusterCon
     InvalidDeviceSelection,
 
     #[error("Invalid leader selection")]
-    InvalidLeaderSelection
+    InvalidLeaderSelection,
+
+    #[error("Invalid project")]
+    InvalidProject,
 }
 
 #[derive(Clone, Debug)]
 pub struct UserClusterConfiguration {
     pub id: ClusterId,
     pub name: UserInputValue,
+    /// Project the cluster belongs to. Its peers have to belong to or be shared with this project.
+    pub project: UserInputValue,
     pub devices: DeviceSelection,
     pub leader: LeaderSelection,
+    /// Labels and device selector are not editable in LEA, but kept when storing the cluster.
+    pub labels: Labels,
+    pub device_selector: Option<LabelSelector>,
 }
 
 impl UserClusterConfiguration {
 
     pub fn is_valid(&self) -> bool {
         self.name.is_right()
+            && self.project.is_right()
             && self.devices.is_right()
             && self.leader.is_right()
     }
@@ -42,6 +53,10 @@ impl TryFrom<UserClusterConfiguration> for ClusterConfiguration {
             .right_ok_or(ClusterMisconfiguration::InvalidClusterName)
             .and_then(|name| ClusterName::try_from(name)
                 .map_err(|_| ClusterMisconfiguration::InvalidClusterName))?;
+        let project = configuration.project
+            .right_ok_or(ClusterMisconfiguration::InvalidProject)
+            .and_then(|project| ProjectId::try_from(project)
+                .map_err(|_| ClusterMisconfiguration::InvalidProject))?;
         let devices = configuration.devices.right_ok_or(ClusterMisconfiguration::InvalidDeviceSelection)?;
         let leader = configuration.leader.right_ok_or(ClusterMisconfiguration::InvalidLeaderSelection)?;
         Ok(ClusterConfiguration {
@@ -49,6 +64,9 @@ impl TryFrom<UserClusterConfiguration> for ClusterConfiguration {
             name,
             leader,
             devices,
+            labels: configuration.labels,
+            device_selector: configuration.device_selector,
+            project,
         })
     }
 }
@@ -4,13 +4,18 @@ use leptos_use::on_click_outside;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error};
 use opendut_carl_api::carl::ClientError;
+use opendut_carl_api::carl::observer::WatchedResourceKind;
 use opendut_carl_api::carl::cluster::StoreClusterDeploymentError;
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::labels::LabelSelector;
+use opendut_types::project::ProjectId;
+use opendut_types::query::{ListQuery, QueryField, SortKey};
 
 use crate::app::use_app_globals;
 use crate::clusters::components::CreateClusterButton;
-use crate::components::{health, use_toaster, BasePageContainer, Breadcrumb, ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, IconButton, LoadingSpinner, Toast};
+use crate::components::{health, use_toaster, BasePageContainer, Breadcrumb, ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, IconButton, LabelSelectorInput, LoadingSpinner, Pages, Pagination, ProjectSelectorInput, project_filter, Toast};
 use crate::components::health::Health;
+use crate::util::watch::notify_on_modification;
 
 #[component]
 pub fn ClustersOverview() -> impl IntoView {
@@ -18,19 +23,44 @@ pub fn ClustersOverview() -> impl IntoView {
     let globals = use_app_globals();
     let carl = globals.client;
 
+    let refetch_clusters = RwSignal::new(());
+    notify_on_modification(&carl, WatchedResourceKind::ClusterConfiguration, refetch_clusters);
+
+    let pages = Pages::default();
+    let selector = RwSignal::new(None::<LabelSelector>);
+    let project = RwSignal::new(None::<ProjectId>);
+
     let clusters = {
         let carl = carl.clone();
 
         LocalResource::new(move || {
+            refetch_clusters.track();
+            let page_token = pages.current();
+            let selector = selector.get();
+            let project = project.get();
+
             let mut carl = carl.clone();
             async move {
-                carl.cluster.list_cluster_configurations().await
-                    .expect("Failed to request the list of clusters")
+                let query = ListQuery {
+                    filter: project_filter(project),
+                    selector,
+                    sort: vec![SortKey { field: QueryField::Name, descending: false }],
+                    page_size: Some(Pages::PAGE_SIZE),
+                    page_token,
+                };
+                let page = carl.cluster.query_cluster_configurations(query).await
+                    .expect("Failed to request the list of clusters");
+                pages.set_next(page.next_page_token);
+
+                page.items.into_iter()
+                    .map(|(configuration, _)| configuration)
+                    .collect::<Vec<_>>()
             }
         })
     };
 
     let refetch_cluster_deployments = RwSignal::new(());
+    notify_on_modification(&carl, WatchedResourceKind::ClusterDeployment, refetch_cluster_deployments);
 
     let cluster_deployments = {
         let carl = carl.clone();
@@ -79,6 +109,13 @@ pub fn ClustersOverview() -> impl IntoView {
                                             .error()
                                     );
                                 }
+                                ClientError::UsageError(StoreClusterDeploymentError::PeerOutsideProject { peer_id, project, .. }) => {
+                                    toaster.toast(
+                                        Toast::builder()
+                                            .simple(format!("Failed to store cluster deployment! Peer <{peer_id}> is not shared with project '{project}' anymore."))
+                                            .error()
+                                    );
+                                }
                                 _ => {
                                     toaster.toast(
                                         Toast::builder()
@@ -168,6 +205,14 @@ pub fn ClustersOverview() -> impl IntoView {
                 <CreateClusterButton />
             }
         >
+            <div class="columns mb-0">
+                <div class="column is-one-quarter">
+                    <ProjectSelectorInput project=project on_change=move || pages.reset() />
+                </div>
+                <div class="column">
+                    <LabelSelectorInput selector=selector on_change=move || pages.reset() />
+                </div>
+            </div>
             <Suspense
                 fallback=LoadingSpinner
             >
@@ -189,6 +234,7 @@ pub fn ClustersOverview() -> impl IntoView {
                                 { rows }
                             </tbody>
                         </table>
+                        <Pagination pages=pages />
                     }
                 })
             }}
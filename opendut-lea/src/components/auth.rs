@@ -1,10 +1,15 @@
+use std::time::Duration;
+
+use gloo_net::http;
 use leptos::either::Either;
 use leptos::prelude::*;
 use leptos_oidc::{LoginLink, LogoutLink};
-use opendut_auth::public::Authentication;
+use serde::Deserialize;
+use url::Url;
+use opendut_auth::public::{Authentication, LocalUserToken};
 
 use crate::app::use_app_globals;
-use crate::components::{AppGlobalsResource, BasePageContainer, Initialized, LoadingSpinner};
+use crate::components::{use_toaster, AppGlobalsResource, BasePageContainer, Initialized, LoadingSpinner, Toast};
 use crate::routing;
 
 #[must_use]
@@ -34,6 +39,19 @@ pub fn LeaAuthenticated(
                 </Transition>
             }.into_any())
         }
+        Authentication::LocalUsers(token) => {
+            let unauthenticated = move || unauthenticated.run();
+            let authenticated = move || token.with(Option::is_some);
+
+            Either::Right(view! {
+                <Show
+                    when=authenticated
+                    fallback=unauthenticated
+                >
+                    { children.read_value()() }
+                </Show>
+            }.into_any())
+        }
         Authentication::Disabled => {
             tracing::warn!("Warning: Authentication disabled - Neither an authentication config provided, nor is the user authenticated.");
             Either::Left(disabled_auth.run())
@@ -57,12 +75,18 @@ pub fn LoginPage(app_globals: AppGlobalsResource) -> impl IntoView {
             >
                 <LeaAuthenticated
                     unauthenticated=move || {
-                        view! {
-                            <p class="subtitle">"Please sign in."</p>
-                            <LoginLink class="button">
-                                <span class="is-size-6">"Sign in"</span>
-                            </LoginLink>
-                            }
+                        match use_app_globals().auth {
+                            Authentication::LocalUsers(token) => Either::Left(view! {
+                                <p class="subtitle">"Please sign in."</p>
+                                <LocalUserSignInForm token />
+                            }),
+                            _ => Either::Right(view! {
+                                <p class="subtitle">"Please sign in."</p>
+                                <LoginLink class="button">
+                                    <span class="is-size-6">"Sign in"</span>
+                                </LoginLink>
+                            }),
+                        }
                     }
                     disabled_auth=move || {
                         view! {
@@ -75,11 +99,154 @@ pub fn LoginPage(app_globals: AppGlobalsResource) -> impl IntoView {
                     loading=LoadingSpinner
                 >
                     <p class="subtitle">"Authenticated"</p>
-                    <LogoutLink class="button">
+                    <SignOutLink class="button">
                         <span class="is-size-6">"Sign out"</span>
-                    </LogoutLink>
+                    </SignOutLink>
                 </LeaAuthenticated>
             </Initialized>
         </BasePageContainer>
     }
 }
+
+/// Links to the sign-in of the configured authentication, i.e. the OIDC provider or the login page for users of the built-in user store.
+#[component]
+pub fn SignInLink(class: &'static str, children: Children) -> impl IntoView {
+    match use_app_globals().auth {
+        Authentication::LocalUsers(_) => Either::Left(view! {
+            <a href=routing::path::login class=class>{children()}</a>
+        }),
+        _ => Either::Right(view! {
+            <LoginLink class=class>{children()}</LoginLink>
+        }),
+    }
+}
+
+/// Signs out from the configured authentication. Users of the built-in user store are signed out by discarding their token.
+#[component]
+pub fn SignOutLink(class: &'static str, children: Children) -> impl IntoView {
+    match use_app_globals().auth {
+        Authentication::LocalUsers(token) => Either::Left(view! {
+            <a href=routing::path::login class=class on:click=move |_| token.set(None)>{children()}</a>
+        }),
+        _ => Either::Right(view! {
+            <LogoutLink class=class>{children()}</LogoutLink>
+        }),
+    }
+}
+
+#[component]
+fn LocalUserSignInForm(token: LocalUserToken) -> impl IntoView {
+    let toaster = use_toaster();
+    let token_url = use_app_globals().config.local_users
+        .map(|local_users| local_users.token_url);
+
+    let username = RwSignal::new(String::new());
+    let password = RwSignal::new(String::new());
+
+    let sign_in = Action::new_local(move |_: &()| {
+        let toaster = toaster.clone();
+        let token_url = token_url.clone();
+        let username = username.get_untracked();
+        let secret = password.get_untracked();
+
+        async move {
+            let result = match token_url {
+                Some(token_url) => request_local_user_token(&token_url, &username, &secret).await,
+                None => Err(String::from("Sign-in via the built-in user store is not configured.")),
+            };
+            match result {
+                Ok(issued) => {
+                    password.set(String::new());
+                    let access_token = issued.access_token.clone();
+                    token.set(Some(issued.access_token));
+
+                    // The token is only kept in memory, so it is discarded once it expires.
+                    set_timeout(move || {
+                        token.update(|current| {
+                            if current.as_ref() == Some(&access_token) {
+                                *current = None;
+                            }
+                        });
+                    }, Duration::from_secs(issued.expires_in));
+                }
+                Err(cause) => toaster.toast(
+                    Toast::builder()
+                        .simple(format!("Could not sign in: {cause}"))
+                        .error(),
+                ),
+            }
+        }
+    });
+
+    view! {
+        <form on:submit=move |event| {
+            event.prevent_default();
+            sign_in.dispatch(());
+        }>
+            <div class="field">
+                <label class="label">Username</label>
+                <div class="control">
+                    <input
+                        class="input"
+                        type="text"
+                        autocomplete="username"
+                        prop:value=move || username.get()
+                        on:input=move |event| username.set(event_target_value(&event))
+                    />
+                </div>
+            </div>
+            <div class="field">
+                <label class="label">Password</label>
+                <div class="control">
+                    <input
+                        class="input"
+                        type="password"
+                        autocomplete="current-password"
+                        prop:value=move || password.get()
+                        on:input=move |event| password.set(event_target_value(&event))
+                    />
+                </div>
+            </div>
+            <button class="button" type="submit" disabled=move || sign_in.pending().get()>
+                <span class="is-size-6">"Sign in"</span>
+            </button>
+        </form>
+    }
+}
+
+#[derive(Deserialize)]
+struct LocalUserTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+#[derive(Deserialize)]
+struct LocalUserTokenErrorResponse {
+    error_description: String,
+}
+
+async fn request_local_user_token(token_url: &Url, username: &str, password: &str) -> Result<LocalUserTokenResponse, String> {
+    let body = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair("grant_type", "password")
+        .append_pair("username", username)
+        .append_pair("password", password)
+        .finish();
+
+    let response = http::Request::post(token_url.as_str())
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(body)
+        .map_err(|cause| cause.to_string())?
+        .send().await
+        .map_err(|cause| format!("Could not reach CARL:\n  {cause}"))?;
+
+    if response.ok() {
+        response.json::<LocalUserTokenResponse>().await
+            .map_err(|cause| format!("Could not parse the token response:\n  {cause}"))
+    } else {
+        let status = response.status();
+        let description = response.json::<LocalUserTokenErrorResponse>().await
+            .map(|error| error.error_description)
+            .unwrap_or_else(|_| format!("CARL responded with status {status}."));
+        Err(description)
+    }
+}
@@ -77,7 +77,22 @@ fn InitializedAndAuthenticated(
                 >
                     {children.read_value()()}
                 </Show>
-            })
+            }.into_any())
+        }
+        Authentication::LocalUsers(token) => {
+
+            let show_component = move || {
+                token.with(Option::is_some) || authentication_required.not()
+            };
+
+            Either::Right(view! {
+                <Show
+                    when=show_component
+                    fallback=|| view! { <FallbackMessage message="You are currently not logged in."/> }
+                >
+                    {children.read_value()()}
+                </Show>
+            }.into_any())
         }
     }
 }
@@ -0,0 +1,56 @@
+use std::str::FromStr;
+
+use leptos::prelude::*;
+use opendut_types::labels::LabelSelector;
+
+use crate::util::NON_BREAKING_SPACE;
+
+/// Text input for a label selector, e.g. `team=chassis,site!=munich`.
+/// The selector is only updated with valid input, otherwise the error is shown.
+#[component]
+pub fn LabelSelectorInput<A>(
+    selector: RwSignal<Option<LabelSelector>>,
+    on_change: A,
+) -> impl IntoView
+where A: Fn() + 'static {
+
+    let error = RwSignal::new(None::<String>);
+
+    let help_text = move || error.get().unwrap_or_else(|| String::from(NON_BREAKING_SPACE));
+
+    let handle_change = move |ev| {
+        let value = event_target_value(&ev);
+        let value = value.trim();
+
+        let parsed = if value.is_empty() {
+            Ok(None)
+        } else {
+            LabelSelector::from_str(value).map(Some)
+        };
+
+        match parsed {
+            Ok(parsed) => {
+                error.set(None);
+                selector.set(parsed);
+                on_change();
+            }
+            Err(cause) => error.set(Some(cause.to_string())),
+        }
+    };
+
+    view! {
+        <div class="field">
+            <div class="control">
+                <input
+                    class="input"
+                    class=("is-danger", move || error.with(Option::is_some))
+                    type="text"
+                    aria-label="Label selector"
+                    placeholder="Filter by labels, e.g. team=chassis,site!=munich"
+                    on:change=handle_change
+                />
+            </div>
+            <p class="help has-text-danger">{ help_text }</p>
+        </div>
+    }
+}
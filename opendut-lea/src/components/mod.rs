@@ -11,8 +11,11 @@ pub use inputs::readonly_input::ReadOnlyInput;
 pub use inputs::user_input::UserInput;
 pub use inputs::user_textarea::UserTextarea;
 pub use inputs::vector_user_input::VectorUserInput;
+pub use label_selector::LabelSelectorInput;
 pub use loading_spinner::LoadingSpinner;
 pub use page::BasePageContainer;
+pub use pagination::{Pages, Pagination};
+pub use project_selector::{project_filter, ProjectSelectorInput};
 pub use toast::{Toast, ToastContent, Toaster, ToastKind, use_toaster};
 pub use util::ButtonStateSignalProvider;
 pub use util::Toggled;
@@ -29,8 +32,11 @@ mod breadcrumbs;
 mod doorhanger;
 mod generate_setup_string;
 mod inputs;
+mod label_selector;
 mod loading_spinner;
 mod page;
+mod pagination;
+mod project_selector;
 mod toast;
 mod util;
 mod warning_message;
@@ -0,0 +1,87 @@
+use leptos::prelude::*;
+use opendut_types::query::PageToken;
+
+use crate::components::{ButtonColor, ButtonState, SimpleButton};
+
+/// Tokens of the pages of a list, which is fetched page by page.
+#[derive(Clone, Copy)]
+pub struct Pages {
+    /// Tokens of the visited pages after the first page. The last one is the current page.
+    visited: RwSignal<Vec<PageToken>>,
+    next: RwSignal<Option<PageToken>>,
+}
+
+impl Default for Pages {
+    fn default() -> Self {
+        Self {
+            visited: RwSignal::new(Vec::new()),
+            next: RwSignal::new(None),
+        }
+    }
+}
+
+impl Pages {
+    pub const PAGE_SIZE: u32 = 25;
+
+    /// Token of the current page. Reading it tracks the page, so resources refetch when navigating.
+    pub fn current(&self) -> Option<PageToken> {
+        self.visited.with(|visited| visited.last().copied())
+    }
+
+    /// Remembers the token of the page after the current page, as received with the current page.
+    pub fn set_next(&self, token: Option<PageToken>) {
+        self.next.set(token);
+    }
+
+    /// Returns to the first page, e.g. when the query changed.
+    pub fn reset(&self) {
+        self.visited.set(Vec::new());
+        self.next.set(None);
+    }
+
+    fn has_previous(&self) -> bool {
+        self.visited.with(|visited| !visited.is_empty())
+    }
+
+    fn has_next(&self) -> bool {
+        self.next.with(Option::is_some)
+    }
+
+    fn go_previous(&self) {
+        self.visited.update(|visited| { visited.pop(); });
+    }
+
+    fn go_next(&self) {
+        if let Some(next) = self.next.get_untracked() {
+            self.visited.update(|visited| visited.push(next));
+        }
+    }
+}
+
+#[component]
+pub fn Pagination(pages: Pages) -> impl IntoView {
+
+    let previous_state = Signal::derive(move || if pages.has_previous() { ButtonState::Enabled } else { ButtonState::Disabled });
+    let next_state = Signal::derive(move || if pages.has_next() { ButtonState::Enabled } else { ButtonState::Disabled });
+
+    view! {
+        <nav
+            class="buttons is-centered"
+            class=("is-hidden", move || !pages.has_previous() && !pages.has_next())
+            aria-label="Pagination"
+        >
+            <SimpleButton
+                text="Previous"
+                color=ButtonColor::Light
+                state=previous_state
+                on_action=move || pages.go_previous()
+            />
+            <SimpleButton
+                text="Next"
+                color=ButtonColor::Light
+                state=next_state
+                on_action=move || pages.go_next()
+            />
+        </nav>
+    }
+}
@@ -0,0 +1,66 @@
+use std::str::FromStr;
+
+use leptos::prelude::*;
+use opendut_types::project::ProjectId;
+use opendut_types::query::{Filter, Predicate};
+
+use crate::util::NON_BREAKING_SPACE;
+
+/// Text input for the project, whose resources should be listed, e.g. `chassis`.
+/// The project is only updated with valid input, otherwise the error is shown. An empty input lists the resources of all projects.
+#[component]
+pub fn ProjectSelectorInput<A>(
+    project: RwSignal<Option<ProjectId>>,
+    on_change: A,
+) -> impl IntoView
+where A: Fn() + 'static {
+
+    let error = RwSignal::new(None::<String>);
+
+    let help_text = move || error.get().unwrap_or_else(|| String::from(NON_BREAKING_SPACE));
+
+    let handle_change = move |ev| {
+        let value = event_target_value(&ev);
+        let value = value.trim();
+
+        let parsed = if value.is_empty() {
+            Ok(None)
+        } else {
+            ProjectId::from_str(value).map(Some)
+        };
+
+        match parsed {
+            Ok(parsed) => {
+                error.set(None);
+                project.set(parsed);
+                on_change();
+            }
+            Err(cause) => error.set(Some(cause.to_string())),
+        }
+    };
+
+    view! {
+        <div class="field">
+            <div class="control">
+                <input
+                    class="input"
+                    class=("is-danger", move || error.with(Option::is_some))
+                    type="text"
+                    aria-label="Project"
+                    placeholder="Filter by project, e.g. chassis"
+                    on:change=handle_change
+                />
+            </div>
+            <p class="help has-text-danger">{ help_text }</p>
+        </div>
+    }
+}
+
+/// Filter for listing only the resources, which belong to or are shared with the project.
+pub fn project_filter(project: Option<ProjectId>) -> Option<Filter> {
+    project.map(|project| {
+        let predicate = Predicate::from_str(&format!("project={project}"))
+            .expect("Project ID should be a valid pattern.");
+        Filter { predicates: vec![predicate] }
+    })
+}
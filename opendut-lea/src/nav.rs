@@ -1,8 +1,8 @@
 use leptos::html::Div;
 use leptos::prelude::*;
-use leptos_oidc::components::{LoginLink, LogoutLink};
 use leptos_use::on_click_outside;
 
+use crate::components::auth::{SignInLink, SignOutLink};
 use crate::components::{AppGlobalsResource, ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, IconButton, Initialized, LeaAuthenticated};
 use crate::user::UserAuthenticationSignal;
 use crate::{routing, use_context};
@@ -108,9 +108,9 @@ pub fn Navbar(app_globals: AppGlobalsResource) -> impl IntoView {
                                     <LeaAuthenticated
                                         unauthenticated=move || {
                                             view! {
-                                                <LoginLink class="dut-nav-flyout-item">
+                                                <SignInLink class="dut-nav-flyout-item">
                                                     <span class="is-size-6">"Sign in"</span>
-                                                </LoginLink>
+                                                </SignInLink>
 
                                             }
                                         }
@@ -122,9 +122,9 @@ pub fn Navbar(app_globals: AppGlobalsResource) -> impl IntoView {
                                             }
                                         }>
                                         <LoggedInUser />
-                                        <LogoutLink class="dut-nav-flyout-item">
+                                        <SignOutLink class="dut-nav-flyout-item">
                                             <span class="ml-1 is-size-6">"Sign out"</span>
-                                        </LogoutLink>
+                                        </SignOutLink>
                                     </LeaAuthenticated>
                                 </div>
                             </div>
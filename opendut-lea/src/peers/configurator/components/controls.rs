@@ -6,6 +6,8 @@ use leptos::prelude::*;
 use leptos_router::hooks::use_navigate;
 use tracing::{debug, error, info};
 use opendut_types::cluster::ClusterId;
+use opendut_carl_api::carl::ClientError;
+use opendut_carl_api::carl::peer::{DeletePeerDescriptorError, StorePeerDescriptorError};
 use opendut_types::peer::PeerDescriptor;
 use crate::app::use_app_globals;
 use crate::components::{use_toaster, ButtonColor, ButtonSize, ButtonState, ButtonStateSignalProvider, ConfirmationButton, DoorhangerButton, FontAwesomeIcon, IconButton, Toast};
@@ -17,11 +19,12 @@ use crate::routing::{navigate_to, WellKnownRoutes};
 pub fn Controls(
     configuration: RwSignal<UserPeerConfiguration>,
     is_valid_peer_configuration: Signal<bool>,
+    revision_conflict: RwSignal<bool>,
 ) -> impl IntoView {
     view! {
         <div class="buttons">
-            <SavePeerButton configuration is_valid_peer_configuration />
-            <DeletePeerButton configuration=configuration.read_only() />
+            <SavePeerButton configuration is_valid_peer_configuration revision_conflict />
+            <DeletePeerButton configuration=configuration.read_only() revision_conflict />
         </div>
     }
 }
@@ -30,6 +33,7 @@ pub fn Controls(
 fn SavePeerButton(
     configuration: RwSignal<UserPeerConfiguration>,
     is_valid_peer_configuration: Signal<bool>,
+    revision_conflict: RwSignal<bool>,
 ) -> impl IntoView {
     let globals = use_app_globals();
     let toaster = use_toaster();
@@ -50,13 +54,18 @@ fn SavePeerButton(
         leptos::task::spawn_local(async move {
             pending.set(true);
 
-            let peer_descriptor = PeerDescriptor::try_from(configuration.get_untracked());
+            let user_configuration = configuration.get_untracked();
+            let expected_revision = user_configuration.revision;
+            let peer_descriptor = PeerDescriptor::try_from(user_configuration);
             match peer_descriptor {
                 Ok(peer_descriptor) => {
                     let peer_id = peer_descriptor.id;
-                    let result = carl.peers.store_peer_descriptor(peer_descriptor).await;
+                    let result = match expected_revision {
+                        Some(expected_revision) => carl.peers.store_peer_descriptor_if_unmodified(peer_descriptor, expected_revision).await.map(Some),
+                        None => carl.peers.store_peer_descriptor(peer_descriptor).await.map(|_| None),
+                    };
                     match result {
-                        Ok(_) => {
+                        Ok(revision) => {
                             debug!("Successfully stored peer: {peer_id}");
                             toaster.toast(
                                 Toast::builder()
@@ -64,6 +73,13 @@ fn SavePeerButton(
                                     .success(),
                             );
                             setter.set(false);
+                            configuration.update(|config| config.revision = revision);
+                            revision_conflict.set(false);
+                        }
+                        Err(ClientError::UsageError(cause @ StorePeerDescriptorError::RevisionConflict { .. })) => {
+                            error!("Failed to store peer <{peer_id}>, due to a conflicting modification: {cause}");
+                            toaster.toast(Toast::builder().simple("Peer was modified in the meantime!").warning());
+                            revision_conflict.set(true);
                         }
                         Err(cause) => {
                             error!("Failed to create peer <{peer_id}>, due to error: {cause:?}");
@@ -103,7 +119,10 @@ fn SavePeerButton(
 }
 
 #[component]
-fn DeletePeerButton(configuration: ReadSignal<UserPeerConfiguration>) -> impl IntoView {
+fn DeletePeerButton(
+    configuration: ReadSignal<UserPeerConfiguration>,
+    revision_conflict: RwSignal<bool>,
+) -> impl IntoView {
     let globals = use_app_globals();
     let use_navigate = use_navigate();
 
@@ -112,17 +131,24 @@ fn DeletePeerButton(configuration: ReadSignal<UserPeerConfiguration>) -> impl In
     let on_conform = move || {
         let use_navigate = use_navigate.clone();
         let mut carl = globals.client.clone();
-        let peer_id = configuration.get_untracked().id;
+        let UserPeerConfiguration { id: peer_id, revision, .. } = configuration.get_untracked();
 
         leptos::task::spawn_local(async move {
             pending.set(true);
 
-            let result = carl.peers.delete_peer_descriptor(peer_id).await;
+            let result = match revision {
+                Some(revision) => carl.peers.delete_peer_descriptor_if_unmodified(peer_id, revision).await,
+                None => carl.peers.delete_peer_descriptor(peer_id).await,
+            };
             match result {
                 Ok(_) => {
                     info!("Successfully deleted peer: {}", peer_id);
                     navigate_to(WellKnownRoutes::PeersOverview, use_navigate);
                 }
+                Err(ClientError::UsageError(cause @ DeletePeerDescriptorError::RevisionConflict { .. })) => {
+                    error!("Failed to delete peer <{peer_id}>, due to a conflicting modification: {cause}");
+                    revision_conflict.set(true);
+                }
                 Err(cause) => {
                     error!("Failed to delete peer <{peer_id}>, due to error: {cause:?}");
                 }
@@ -1,7 +1,9 @@
 mod controls;
 mod peer_name_input;
 mod peer_location_input;
+mod peer_project_input;
 
 pub use controls::Controls;
 pub use peer_name_input::PeerNameInput;
 pub use peer_location_input::PeerLocationInput;
+pub use peer_project_input::PeerProjectInput;
@@ -0,0 +1,32 @@
+use crate::components::{UserInput, UserInputValue};
+use crate::peers::configurator::types::UserPeerConfiguration;
+use leptos::prelude::*;
+use opendut_types::project::ProjectId;
+
+#[component]
+pub fn PeerProjectInput(peer_configuration: RwSignal<UserPeerConfiguration>) -> impl IntoView {
+    let (getter, setter) = create_slice(
+        peer_configuration,
+        |config| Clone::clone(&config.project),
+        |config, input| {
+            config.project = input;
+        },
+    );
+
+    let validator = |input: String| {
+        match ProjectId::try_from(input.clone()) {
+            Ok(_) => UserInputValue::Right(input),
+            Err(cause) => UserInputValue::Both(cause.to_string(), input),
+        }
+    };
+
+    view! {
+        <UserInput
+            getter=getter
+            setter=setter
+            label="Project"
+            placeholder="default"
+            validator=validator
+        />
+    }
+}
@@ -1,6 +1,6 @@
 use crate::app::use_app_globals;
 use crate::components::use_active_tab;
-use crate::components::{BasePageContainer, Breadcrumb, UserInputError, UserInputValue};
+use crate::components::{BasePageContainer, Breadcrumb, ButtonColor, ButtonState, SimpleButton, UserInputError, UserInputValue, WarningMessage};
 use crate::peers::configurator::components::Controls;
 use crate::peers::configurator::tabs::{DevicesTab, ExecutorTab, GeneralTab, NetworkTab, SetupTab, TabIdentifier};
 use crate::peers::configurator::types::{UserContainerEnv, UserDeviceConfiguration, UserNetworkInterface, UserPeerConfiguration, UserPeerExecutor, UserPeerExecutorKind, UserPeerNetwork};
@@ -10,6 +10,7 @@ use leptos::either::Either;
 use leptos::prelude::*;
 use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorKind};
 use opendut_types::peer::PeerId;
+use opendut_types::project::ProjectId;
 use std::collections::HashMap;
 use leptos_router::hooks::{use_navigate, use_params_map};
 
@@ -25,6 +26,8 @@ pub fn PeerConfigurator() -> impl IntoView {
 
     let active_tab = use_active_tab::<TabIdentifier>();
 
+    let reload_peer_configuration = RwSignal::new(());
+
     let (peer_configuration, peer_configuration_resource, is_valid_peer_configuration) = {
         let peer_id = {
             let peer_id = params.with_untracked(|params| {
@@ -50,6 +53,8 @@ pub fn PeerConfigurator() -> impl IntoView {
             id: peer_id,
             name: UserInputValue::Left(UserInputError::from("Enter a valid peer name.")),
             location: UserInputValue::Right(String::from("")),
+            project: UserInputValue::Right(String::from(ProjectId::DEFAULT)),
+            shared_with_projects: Default::default(),
             devices: Vec::new(),
             network: UserPeerNetwork {
                 network_interfaces: Vec::new(),
@@ -57,19 +62,26 @@ pub fn PeerConfigurator() -> impl IntoView {
             },
             is_new: true,
             executors: Vec::new(),
+            revision: None,
+            labels: Default::default(),
         });
 
         let peer_configuration_resource = LocalResource::new(move || {
+            reload_peer_configuration.track();
             let mut carl = globals.client.clone();
             async move {
-                if let Ok(configuration) = carl.peers.get_peer_descriptor(peer_id).await {
+                if let Ok((configuration, revision)) = carl.peers.get_peer_descriptor_with_revision(peer_id).await {
                     let clusters = carl.cluster.list_cluster_configurations().await
                         .unwrap_or(vec![]);
 
                     peer_configuration.update(|user_configuration| {
                         user_configuration.name = UserInputValue::Right(configuration.name.value());
                         user_configuration.is_new = false;
+                        user_configuration.revision = Some(revision);
+                        user_configuration.labels = configuration.labels;
                         user_configuration.location = UserInputValue::Right(configuration.location.unwrap_or_default().value());
+                        user_configuration.project = UserInputValue::Right(configuration.project.owner.to_string());
+                        user_configuration.shared_with_projects = configuration.project.shared_with;
                         user_configuration.devices = configuration.topology.devices.into_iter().map(|device| {
                             let mut configured_clusters = vec![];
 
@@ -87,6 +99,7 @@ pub fn PeerConfigurator() -> impl IntoView {
                                 description: UserInputValue::Right(device.description.unwrap_or_default().to_string()),
                                 is_collapsed: true,
                                 contained_in_clusters: configured_clusters,
+                                labels: device.labels,
                             })
                         }).collect::<Vec<_>>();
                         if let Some(bridge_name) = configuration.network.bridge_name {
@@ -97,6 +110,7 @@ pub fn PeerConfigurator() -> impl IntoView {
                                 RwSignal::new(UserNetworkInterface::from(interface))
                             })
                             .collect();
+                        user_configuration.executors.clear();
                         for executor in configuration.executors.executors {
                             let ExecutorDescriptor { id, kind, results_url } = executor;
 
@@ -216,6 +230,8 @@ pub fn PeerConfigurator() -> impl IntoView {
         (peer_configuration, peer_configuration_resource, is_valid_peer_configuration)
     };
 
+    let revision_conflict = RwSignal::new(false);
+
     let peer_id_string = create_read_slice(peer_configuration, |config| config.id.to_string());
     let setup_disabled = create_read_slice(peer_configuration, |config| config.is_new);
 
@@ -297,9 +313,23 @@ pub fn PeerConfigurator() -> impl IntoView {
         <BasePageContainer
             title="Configure Peer"
             breadcrumbs=breadcrumbs
-            controls=view! { <Controls configuration=peer_configuration is_valid_peer_configuration=is_valid_peer_configuration.into() /> }
+            controls=view! { <Controls configuration=peer_configuration is_valid_peer_configuration=is_valid_peer_configuration.into() revision_conflict /> }
         >
         <div> {cluster_columns} </div>
+        <Show when=move || revision_conflict.get()>
+            <WarningMessage>
+                "This peer was modified by someone else since it was loaded. Reload it to see the latest configuration; unsaved changes will be lost. "
+                <SimpleButton
+                    text="Reload"
+                    color=ButtonColor::Info
+                    state=ButtonState::Enabled
+                    on_action=move || {
+                        revision_conflict.set(false);
+                        reload_peer_configuration.notify();
+                    }
+                />
+            </WarningMessage>
+        </Show>
             <Suspense
                 fallback=move || view! { <p><i class="fa-solid fa-circle-notch fa-spin"></i></p> } // TODO: Display errors
             >
@@ -69,7 +69,8 @@ fn DevicesTable(peer_configuration: RwSignal<UserPeerConfiguration>) -> impl Int
                                     description: UserInputValue::Right(String::from("")),
                                     interface: None,
                                     is_collapsed: false,
-                                    contained_in_clusters: vec![],                     
+                                    contained_in_clusters: vec![],
+                                    labels: Default::default(),
                                 }
                             );
                             peer_configuration.devices.push(user_device_configuration);
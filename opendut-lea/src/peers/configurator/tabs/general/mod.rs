@@ -1,6 +1,6 @@
 use leptos::prelude::*;
 use crate::components::ReadOnlyInput;
-use crate::peers::configurator::components::{PeerLocationInput, PeerNameInput};
+use crate::peers::configurator::components::{PeerLocationInput, PeerNameInput, PeerProjectInput};
 use crate::peers::configurator::types::UserPeerConfiguration;
 
 #[component]
@@ -20,6 +20,9 @@ pub fn GeneralTab(peer_configuration: RwSignal<UserPeerConfiguration>) -> impl I
             <PeerLocationInput
                 peer_configuration=peer_configuration
             />
+            <PeerProjectInput
+                peer_configuration=peer_configuration
+            />
         </div>
     }
 }
@@ -1,7 +1,14 @@
+use std::time::SystemTime;
+
+use chrono::Utc;
 use leptos::prelude::*;
 
-use crate::components::{GenerateSetupStringForm, GenerateSetupStringKind, WarningMessage};
+use opendut_types::peer::setup::{PeerSetupId, PeerSetupRecord, PeerSetupStatus};
+
+use crate::app::use_app_globals;
+use crate::components::{use_toaster, ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, GenerateSetupStringForm, GenerateSetupStringKind, IconButton, LoadingSpinner, Toast, WarningMessage};
 use crate::peers::configurator::types::UserPeerConfiguration;
+use crate::util::format_time;
 
 #[component]
 pub fn SetupTab(peer_configuration: ReadSignal<UserPeerConfiguration>) -> impl IntoView {
@@ -13,5 +20,122 @@ pub fn SetupTab(peer_configuration: ReadSignal<UserPeerConfiguration>) -> impl I
             <GenerateSetupStringForm kind />
             <WarningMessage>"Setup-Strings may only be used to set up one host. For setting up multiple hosts, you should create a peer for each host."</WarningMessage>
         </div>
+        <IssuedSetupStrings peer_configuration />
+    }
+}
+
+#[component]
+fn IssuedSetupStrings(peer_configuration: ReadSignal<UserPeerConfiguration>) -> impl IntoView {
+
+    let globals = use_app_globals();
+    let toaster = use_toaster();
+
+    let refetch_setups = RwSignal::new(());
+
+    let setups: LocalResource<Vec<PeerSetupRecord>> = {
+        let carl = globals.client.clone();
+
+        LocalResource::new(move || {
+            refetch_setups.track();
+            let peer_id = peer_configuration.get().id;
+
+            let mut carl = carl.clone();
+            async move {
+                let mut setups = carl.peers.list_peer_setups().await
+                    .expect("Failed to request the list of Setup-Strings.")
+                    .into_iter()
+                    .filter(|setup| setup.peer_id == peer_id)
+                    .collect::<Vec<_>>();
+                setups.sort_by_key(|setup| std::cmp::Reverse(setup.issued_at));
+                setups
+            }
+        })
+    };
+
+    let revoke_setup = Action::new_local(move |setup_id: &PeerSetupId| {
+        let setup_id = *setup_id;
+        let mut carl = globals.client.clone();
+        let toaster = toaster.clone();
+
+        async move {
+            match carl.peers.revoke_peer_setup(setup_id).await {
+                Ok(_) => toaster.toast(
+                    Toast::builder()
+                        .simple("Successfully revoked Setup-String.")
+                        .success(),
+                ),
+                Err(cause) => toaster.toast(
+                    Toast::builder()
+                        .simple(format!("Error while revoking Setup-String: {cause}"))
+                        .error(),
+                ),
+            };
+            refetch_setups.notify();
+        }
+    });
+
+    view! {
+        <div class="field mt-5">
+            <div class="columns mb-0 is-align-items-center">
+                <div class="column"><label class="label">Issued Setup-Strings</label></div>
+                <div class="column is-narrow">
+                    <IconButton
+                        icon=FontAwesomeIcon::ArrowsRotate
+                        color=ButtonColor::Light
+                        size=ButtonSize::Normal
+                        state=ButtonState::Enabled
+                        label="Refresh list of Setup-Strings"
+                        on_action=move || refetch_setups.notify()
+                    />
+                </div>
+            </div>
+            <Transition fallback=LoadingSpinner>
+                {move || Suspend::new(async move {
+                    let setups = setups.await;
+                    let now = SystemTime::from(Utc::now());
+
+                    let rows = setups.into_iter().map(|setup| {
+                        let status = setup.status(now);
+                        let setup_id = setup.id;
+
+                        view! {
+                            <tr>
+                                <td>{setup_id.to_string()}</td>
+                                <td>{status.to_string()}</td>
+                                <td>{format_time(setup.issued_at)}</td>
+                                <td>{format_time(setup.expires_at)}</td>
+                                <td class="is-narrow">
+                                    <IconButton
+                                        icon=FontAwesomeIcon::XMark
+                                        color=ButtonColor::Light
+                                        size=ButtonSize::Small
+                                        state=if status == PeerSetupStatus::Pending { ButtonState::Enabled } else { ButtonState::Hidden }
+                                        label="Revoke Setup-String"
+                                        on_action=move || { revoke_setup.dispatch(setup_id); }
+                                    />
+                                </td>
+                            </tr>
+                        }
+                    }).collect_view();
+
+                    view! {
+                        <table class="table is-fullwidth">
+                            <thead>
+                                <tr>
+                                    <th>"ID"</th>
+                                    <th>"Status"</th>
+                                    <th>"Issued"</th>
+                                    <th>"Expires"</th>
+                                    <th class="is-narrow">"Action"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {rows}
+                            </tbody>
+                        </table>
+                    }
+                })}
+            </Transition>
+        </div>
     }
 }
@@ -1,7 +1,12 @@
+use std::collections::BTreeSet;
+
 use leptos::prelude::*;
 use opendut_types::cluster::ClusterConfiguration;
+use opendut_types::labels::Labels;
 use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorId};
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
+use opendut_types::project::{ProjectAssignment, ProjectId};
+use opendut_types::resources::Revision;
 use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ExecutorKind, ExecutorDescriptors, ResultsUrl};
 use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, Topology};
 use opendut_types::util::net::{NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
@@ -23,6 +28,8 @@ pub enum PeerMisconfigurationError {
     InvalidPeerNetwork,
     #[error("Invalid peer executor")]
     InvalidPeerExecutor,
+    #[error("Invalid project")]
+    InvalidProject,
 }
 
 #[derive(thiserror::Error, Clone, Debug)]
@@ -43,10 +50,18 @@ pub struct UserPeerConfiguration {
     pub id: PeerId,
     pub name: UserInputValue,
     pub location: UserInputValue,
+    /// Project the peer belongs to.
+    pub project: UserInputValue,
+    /// Projects the peer is shared with are not editable in LEA, but kept when storing the peer.
+    pub shared_with_projects: BTreeSet<ProjectId>,
     pub devices: Vec<RwSignal<UserDeviceConfiguration>>,
     pub network: UserPeerNetwork,
     pub executors: Vec<RwSignal<UserPeerExecutor>>,
     pub is_new: bool,
+    /// Revision of the stored peer this configuration is based on, if it was loaded from CARL.
+    pub revision: Option<Revision>,
+    /// Labels are not editable in LEA, but kept when storing the peer.
+    pub labels: Labels,
 }
 
 #[derive(Clone, Debug)]
@@ -99,6 +114,7 @@ pub struct UserDeviceConfiguration {
     pub interface: Option<NetworkInterfaceId>,
     pub is_collapsed: bool,
     pub contained_in_clusters: Vec<ClusterConfiguration>,
+    pub labels: Labels,
 }
 
 impl TryFrom<UserPeerConfiguration> for PeerDescriptor {
@@ -118,6 +134,13 @@ impl TryFrom<UserPeerConfiguration> for PeerDescriptor {
                 PeerLocation::try_from(location)
                     .map_err(|_| PeerMisconfigurationError::InvalidPeerName)
             })?;
+        let project = configuration
+            .project
+            .right_ok_or(PeerMisconfigurationError::InvalidProject)
+            .and_then(|project| {
+                ProjectId::try_from(project)
+                    .map_err(|_| PeerMisconfigurationError::InvalidProject)
+            })?;
         let bridge_name = configuration.network
             .bridge_name
             .right_ok_or(PeerMisconfigurationError::InvalidPeerNetwork)
@@ -167,6 +190,11 @@ impl TryFrom<UserPeerConfiguration> for PeerDescriptor {
             executors: ExecutorDescriptors {
                 executors
             },
+            labels: configuration.labels,
+            project: ProjectAssignment {
+                owner: project,
+                shared_with: configuration.shared_with_projects,
+            },
         })
     }
 }
@@ -197,6 +225,7 @@ impl TryFrom<UserDeviceConfiguration> for DeviceDescriptor {
             description: Some(description),
             interface,
             tags: vec![],
+            labels: configuration.labels,
         })
     }
 }
@@ -1,16 +1,21 @@
 use crate::app::use_app_globals;
 use crate::components::{health, LoadingSpinner};
 use crate::components::health::Health;
-use crate::components::{BasePageContainer, Breadcrumb, ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, IconButton};
+use crate::components::{BasePageContainer, Breadcrumb, ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, IconButton, LabelSelectorInput, Pages, Pagination, ProjectSelectorInput, project_filter};
 use crate::peers::components::CreatePeerButton;
 use crate::util;
+use crate::util::watch::notify_on_modification;
 use leptos::html::Div;
 use leptos::prelude::*;
 use leptos_use::on_click_outside;
 use tracing::trace;
+use opendut_carl_api::carl::observer::WatchedResourceKind;
 use opendut_types::cluster::ClusterConfiguration;
 use opendut_types::peer::state::{PeerConnectionState, PeerState};
 use opendut_types::peer::PeerDescriptor;
+use opendut_types::labels::LabelSelector;
+use opendut_types::project::ProjectId;
+use opendut_types::query::{ListQuery, QueryField, SortKey};
 
 #[component(transparent)]
 pub fn PeersOverview() -> impl IntoView {
@@ -18,23 +23,40 @@ pub fn PeersOverview() -> impl IntoView {
     let globals = use_app_globals();
 
     let refetch_registered_peers = RwSignal::new(());
+    notify_on_modification(&globals.client, WatchedResourceKind::PeerDescriptor, refetch_registered_peers);
+    notify_on_modification(&globals.client, WatchedResourceKind::PeerConnectionState, refetch_registered_peers);
+
+    let pages = Pages::default();
+    let selector = RwSignal::new(None::<LabelSelector>);
+    let project = RwSignal::new(None::<ProjectId>);
 
     let registered_peers: LocalResource<Vec<(PeerDescriptor, PeerState)>> = {
         let carl = globals.client.clone();
 
         LocalResource::new(move || {
             refetch_registered_peers.track();
+            let page_token = pages.current();
+            let selector = selector.get();
+            let project = project.get();
 
             let mut carl = carl.clone();
             async move {
-                let peers = carl.peers.list_peer_descriptors().await
+                let query = ListQuery {
+                    filter: project_filter(project),
+                    selector,
+                    sort: vec![SortKey { field: QueryField::Name, descending: false }],
+                    page_size: Some(Pages::PAGE_SIZE),
+                    page_token,
+                };
+                let page = carl.peers.query_peer_descriptors(query).await
                     .expect("Failed to request the list of peers.");
+                pages.set_next(page.next_page_token);
 
                 let mut peer_states = carl.peers.list_peer_states().await
                     .expect("Failed to request the list of peer states.");
 
                 let mut peers_with_state: Vec<(PeerDescriptor, PeerState)> = vec![];
-                for peer in peers {
+                for (peer, _) in page.items {
                     let peer_state = peer_states.remove(&peer.id)
                         .unwrap_or_else(|| {
                             trace!("Did not receive PeerState for peer <{peer_id}>. Treating it as down.", peer_id=peer.id);
@@ -103,6 +125,14 @@ pub fn PeersOverview() -> impl IntoView {
             }
         >
             <div class="mt-4">
+                <div class="columns mb-0">
+                    <div class="column is-one-quarter">
+                        <ProjectSelectorInput project=project on_change=move || pages.reset() />
+                    </div>
+                    <div class="column">
+                        <LabelSelectorInput selector=selector on_change=move || pages.reset() />
+                    </div>
+                </div>
                 <Transition
                     fallback=LoadingSpinner
                 >
@@ -124,6 +154,7 @@ pub fn PeersOverview() -> impl IntoView {
                                         { peers_table_rows }
                                     </tbody>
                                 </table>
+                                <Pagination pages=pages />
                             }
                         })
                     }}
@@ -20,6 +20,7 @@ pub mod path {
     pub const clusters_overview: &str = "/clusters";
     pub const error: &str = "/error";
     pub const licenses: &str = "/licenses";
+    pub const login: &str = "/login";
     pub const peers_overview: &str = "/peers";
     pub const user: &str = "/user";
 }
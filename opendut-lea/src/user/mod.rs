@@ -2,6 +2,7 @@ use leptos::context::provide_context;
 use leptos::prelude::{Effect, Get, RwSignal, Set};
 use leptos_oidc::{Algorithm, Auth, AuthSignal, TokenData};
 use leptos_router::hooks::use_navigate;
+use opendut_auth::public::LocalUserToken;
 use opendut_auth::types::Claims;
 pub use overview::UserOverview;
 use crate::routing::{navigate_to, WellKnownRoutes};
@@ -9,6 +10,7 @@ use leptos_oidc::AuthenticatedData as LeptosOidcAuthenticatedData;
 /// In case authentication is disabled the user identity is not known
 pub const UNAUTHENTICATED_USER: &str = "unknown-user";
 mod overview;
+mod tokens;
 const DEFAULT_TOKEN_AUDIENCE: &str = "account";
 
 #[derive(Debug, Clone, Default)]
@@ -17,6 +19,7 @@ pub enum AuthenticationConfigSwitch {
     Loading,
     Disabled,
     Enabled,
+    LocalUsers,
 }
 
 pub type UserAuthenticationSignal = RwSignal<UserAuthentication>;
@@ -28,6 +31,8 @@ pub enum UserAuthentication {
     Disabled,
     Unauthenticated,
     Authenticated(Box<AuthenticatedData>),
+    /// Signed in via the built-in user store of CARL. The claims are `None`, if the token could not be decoded.
+    LocalUser(Option<Box<Claims>>),
 }
 
 #[derive(Debug, Clone)]
@@ -59,6 +64,9 @@ impl UserAuthentication {
                     }
                 }
             }
+            UserAuthentication::LocalUser(claims) => {
+                Some(claims.as_ref().is_some_and(|claims| claims.additional_claims.has_group(group)))
+            }
         }
     }
     
@@ -70,6 +78,7 @@ impl UserAuthentication {
             UserAuthentication::Authenticated(data) => {
                 Some(data.auth.is_authenticated())
             }
+            UserAuthentication::LocalUser(_) => { Some(true) }
         }
     }
 
@@ -84,6 +93,12 @@ impl UserAuthentication {
                     Some(user) => { &user.claims.preferred_username }
                 }
             }
+            UserAuthentication::LocalUser(claims) => {
+                match claims.as_ref() {
+                    None => { UNAUTHENTICATED_USER }
+                    Some(claims) => { &claims.preferred_username }
+                }
+            }
         };
         name.to_string()
     }
@@ -97,6 +112,8 @@ pub(crate) fn provide_authentication_signals_in_context() -> AuthSignal {
     provide_context(user_auth);
     let auth_config_switch = RwSignal::new(AuthenticationConfigSwitch::Loading);
     provide_context(auth_config_switch);
+    let local_user_token: LocalUserToken = RwSignal::new(None);
+    provide_context(local_user_token);
 
     Effect::new(move || {
         let auth = auth.get();
@@ -111,6 +128,19 @@ pub(crate) fn provide_authentication_signals_in_context() -> AuthSignal {
                 tracing::debug!("user disabled");
                 user_auth.set(UserAuthentication::Disabled);
             }
+            AuthenticationConfigSwitch::LocalUsers => {
+                match local_user_token.get() {
+                    None => {
+                        tracing::trace!("local user signed out");
+                        user_auth.set(UserAuthentication::Unauthenticated);
+                    }
+                    Some(access_token) => {
+                        tracing::debug!("local user signed in");
+                        let claims = decode_local_user_claims(&access_token).map(Box::new);
+                        user_auth.set(UserAuthentication::LocalUser(claims));
+                    }
+                }
+            }
             AuthenticationConfigSwitch::Enabled => {
                 match auth {
                     Auth::Loading => {
@@ -150,3 +180,16 @@ pub(crate) fn provide_authentication_signals_in_context() -> AuthSignal {
     
     auth
 }
+
+/// Reads the claims of a token issued by the built-in user store of CARL.
+/// The signature is not checked here, since only CARL holds the key and validates the token on each request.
+fn decode_local_user_claims(access_token: &str) -> Option<Claims> {
+    let mut validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::HS256);
+    validation.insecure_disable_signature_validation();
+    validation.validate_aud = false;
+
+    jsonwebtoken::decode::<Claims>(access_token, &jsonwebtoken::DecodingKey::from_secret(&[]), &validation)
+        .inspect_err(|cause| tracing::warn!("Could not decode the token of the local user: {cause}"))
+        .map(|token| token.claims)
+        .ok()
+}
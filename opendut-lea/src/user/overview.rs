@@ -3,6 +3,7 @@ use leptos::either::EitherOf3;
 use leptos::prelude::*;
 use opendut_auth::types::Claims;
 use crate::components::{BasePageContainer, Breadcrumb};
+use crate::user::tokens::PersonalAccessTokens;
 use crate::user::{UserAuthentication, UserAuthenticationSignal, UNAUTHENTICATED_USER};
 
 const DEFAULT_KEYCLOAK_ROLES: [&str; 4] = [
@@ -32,6 +33,17 @@ pub fn UserOverview() -> impl IntoView {
                     }
                 }
             }
+            UserAuthentication::LocalUser(claims) => {
+                match claims {
+                    None => {
+                        EitherOf3::B(view! { <AbsentUserTableView/> })
+                    }
+                    Some(claims) => {
+                        let claims = *claims;
+                        EitherOf3::C(view! { <PresentUserTableView claims/> })
+                    }
+                }
+            }
         }
     }}
 }
@@ -73,6 +85,7 @@ fn PresentUserTableView(
             email=email
             groups=groups
             roles=roles
+            show_personal_access_tokens=true
         >
         </UserTableView>
     }
@@ -87,6 +100,7 @@ fn UserTableView(
     #[prop(into)] email: String,
     #[prop(into)] groups: String,
     #[prop(into)] roles: String,
+    #[prop(optional)] show_personal_access_tokens: bool,
 ) -> impl IntoView {
     let breadcrumbs = vec![
         Breadcrumb::new("Dashboard", "/"),
@@ -118,6 +132,9 @@ fn UserTableView(
                     </tr>
                 </tbody>
             </table>
+            <Show when=move || show_personal_access_tokens>
+                <PersonalAccessTokens />
+            </Show>
         </BasePageContainer>
     }
 }
@@ -0,0 +1,227 @@
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use chrono::Utc;
+use leptos::prelude::*;
+
+use opendut_types::user::token::{PersonalAccessTokenDescriptor, PersonalAccessTokenId, PersonalAccessTokenName, PersonalAccessTokenScope, PersonalAccessTokenStatus};
+
+use crate::app::use_app_globals;
+use crate::components::{use_toaster, ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, IconButton, LoadingSpinner, SimpleButton, Toast, WarningMessage};
+use crate::util::format_time;
+
+const VALIDITY_OPTIONS_IN_DAYS: [u64; 4] = [7, 30, 90, 365];
+const DEFAULT_VALIDITY_IN_DAYS: u64 = 30;
+
+#[component]
+pub fn PersonalAccessTokens() -> impl IntoView {
+
+    let globals = use_app_globals();
+    let toaster = use_toaster();
+
+    let refetch_tokens = RwSignal::new(());
+
+    let name = RwSignal::new(String::new());
+    let scope = RwSignal::new(PersonalAccessTokenScope::Viewer);
+    let validity_in_days = RwSignal::new(DEFAULT_VALIDITY_IN_DAYS);
+    let created_token = RwSignal::new(None::<String>);
+
+    let tokens: LocalResource<Vec<PersonalAccessTokenDescriptor>> = {
+        let carl = globals.client.clone();
+
+        LocalResource::new(move || {
+            refetch_tokens.track();
+
+            let mut carl = carl.clone();
+            async move {
+                let mut tokens = carl.tokens.list_personal_access_tokens(false).await
+                    .expect("Failed to request the list of personal access tokens.");
+                tokens.sort_by_key(|token| std::cmp::Reverse(token.created_at));
+                tokens
+            }
+        })
+    };
+
+    let create_token = Action::new_local({
+        let carl = globals.client.clone();
+        let toaster = toaster.clone();
+        move |_: &()| {
+            let mut carl = carl.clone();
+            let toaster = toaster.clone();
+            let name = name.get_untracked();
+            let scopes = vec![scope.get_untracked()];
+            let validity = Duration::from_secs(validity_in_days.get_untracked() * 24 * 60 * 60);
+
+            async move {
+                let result = match PersonalAccessTokenName::try_from(name) {
+                    Ok(name) => carl.tokens.create_personal_access_token(name, scopes, validity).await
+                        .map_err(|cause| cause.to_string()),
+                    Err(cause) => Err(cause.to_string()),
+                };
+                match result {
+                    Ok(created) => {
+                        created_token.set(Some(created.token));
+                        toaster.toast(
+                            Toast::builder()
+                                .simple("Successfully created personal access token.")
+                                .success(),
+                        );
+                    }
+                    Err(cause) => toaster.toast(
+                        Toast::builder()
+                            .simple(format!("Error while creating personal access token: {cause}"))
+                            .error(),
+                    ),
+                };
+                refetch_tokens.notify();
+            }
+        }
+    });
+
+    let revoke_token = Action::new_local(move |id: &PersonalAccessTokenId| {
+        let id = *id;
+        let mut carl = globals.client.clone();
+        let toaster = toaster.clone();
+
+        async move {
+            match carl.tokens.revoke_personal_access_token(id).await {
+                Ok(_) => toaster.toast(
+                    Toast::builder()
+                        .simple("Successfully revoked personal access token.")
+                        .success(),
+                ),
+                Err(cause) => toaster.toast(
+                    Toast::builder()
+                        .simple(format!("Error while revoking personal access token: {cause}"))
+                        .error(),
+                ),
+            };
+            refetch_tokens.notify();
+        }
+    });
+
+    let scope_options = PersonalAccessTokenScope::ALL.into_iter().map(|option| {
+        view! { <option value=option.name() selected=move || scope.get() == option>{option.name()}</option> }
+    }).collect_view();
+
+    let validity_options = VALIDITY_OPTIONS_IN_DAYS.into_iter().map(|days| {
+        view! { <option value=days.to_string() selected=move || validity_in_days.get() == days>{format!("{days} days")}</option> }
+    }).collect_view();
+
+    view! {
+        <div class="field mt-5">
+            <div class="columns mb-0 is-align-items-center">
+                <div class="column"><label class="label">Personal Access Tokens</label></div>
+                <div class="column is-narrow">
+                    <IconButton
+                        icon=FontAwesomeIcon::ArrowsRotate
+                        color=ButtonColor::Light
+                        size=ButtonSize::Normal
+                        state=ButtonState::Enabled
+                        label="Refresh list of personal access tokens"
+                        on_action=move || refetch_tokens.notify()
+                    />
+                </div>
+            </div>
+            <div class="field is-grouped">
+                <div class="control is-expanded">
+                    <input
+                        class="input"
+                        type="text"
+                        placeholder="Name, e.g. nightly-build"
+                        prop:value=move || name.get()
+                        on:input=move |event| name.set(event_target_value(&event))
+                    />
+                </div>
+                <div class="control">
+                    <div class="select">
+                        <select on:change=move |event| {
+                            if let Ok(value) = PersonalAccessTokenScope::from_str(&event_target_value(&event)) {
+                                scope.set(value);
+                            }
+                        }>
+                            {scope_options}
+                        </select>
+                    </div>
+                </div>
+                <div class="control">
+                    <div class="select">
+                        <select on:change=move |event| {
+                            if let Ok(value) = event_target_value(&event).parse::<u64>() {
+                                validity_in_days.set(value);
+                            }
+                        }>
+                            {validity_options}
+                        </select>
+                    </div>
+                </div>
+                <div class="control">
+                    <SimpleButton
+                        text="Create"
+                        color=ButtonColor::Info
+                        state=ButtonState::Enabled
+                        on_action=move || { create_token.dispatch(()); }
+                    />
+                </div>
+            </div>
+            <Show when=move || created_token.get().is_some()>
+                <div class="field">
+                    <div class="control">
+                        <input class="input" type="text" readonly prop:value=move || created_token.get().unwrap_or_default() />
+                    </div>
+                </div>
+                <WarningMessage>"This token is shown only once. Please store it securely, e.g. as secret variable of your CI pipeline."</WarningMessage>
+            </Show>
+            <Transition fallback=LoadingSpinner>
+                {move || Suspend::new(async move {
+                    let tokens = tokens.await;
+                    let now = SystemTime::from(Utc::now());
+
+                    let rows = tokens.into_iter().map(|token| {
+                        let status = token.status(now);
+                        let token_id = token.id;
+                        let scopes = token.scopes.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+
+                        view! {
+                            <tr>
+                                <td>{token.name.to_string()}</td>
+                                <td>{scopes}</td>
+                                <td>{status.to_string()}</td>
+                                <td>{format_time(token.created_at)}</td>
+                                <td>{format_time(token.expires_at)}</td>
+                                <td class="is-narrow">
+                                    <IconButton
+                                        icon=FontAwesomeIcon::XMark
+                                        color=ButtonColor::Light
+                                        size=ButtonSize::Small
+                                        state=if status == PersonalAccessTokenStatus::Active { ButtonState::Enabled } else { ButtonState::Hidden }
+                                        label="Revoke personal access token"
+                                        on_action=move || { revoke_token.dispatch(token_id); }
+                                    />
+                                </td>
+                            </tr>
+                        }
+                    }).collect_view();
+
+                    view! {
+                        <table class="table is-fullwidth">
+                            <thead>
+                                <tr>
+                                    <th>"Name"</th>
+                                    <th>"Scopes"</th>
+                                    <th>"Status"</th>
+                                    <th>"Created"</th>
+                                    <th>"Expires"</th>
+                                    <th class="is-narrow">"Action"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {rows}
+                            </tbody>
+                        </table>
+                    }
+                })}
+            </Transition>
+        </div>
+    }
+}
@@ -1,3 +1,7 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local};
+
 pub use ior::Ior;
 pub use tick::Tick;
 
@@ -6,5 +10,11 @@ mod ior;
 mod tick;
 pub mod net;
 pub mod view;
+pub mod watch;
 
 pub const NON_BREAKING_SPACE: &str = "\u{a0}";
+
+/// Formats a point in time in the local time zone of the browser, to the minute.
+pub fn format_time(time: SystemTime) -> String {
+    DateTime::<Local>::from(time).format("%Y-%m-%d %H:%M").to_string()
+}
@@ -0,0 +1,42 @@
+use futures::future::abortable;
+use futures::StreamExt;
+use leptos::prelude::*;
+use tracing::{debug, warn};
+use opendut_carl_api::carl::observer::{WatchEvent, WatchedResourceKind};
+use opendut_carl_api::carl::wasm::CarlClient;
+
+/// Notifies the trigger whenever resources of the given kind are modified in CARL, so that the data depending on them is fetched again.
+/// Watching stops, when the surrounding component is cleaned up.
+pub fn notify_on_modification(carl: &CarlClient, kind: WatchedResourceKind, trigger: RwSignal<()>) {
+    let mut carl = Clone::clone(carl);
+
+    let (watch, abort_handle) = abortable(async move {
+        let mut events = match carl.observer.watch(kind, None).await {
+            Ok(events) => events,
+            Err(cause) => {
+                warn!("Failed to watch modifications of <{kind:?}>. Changes will only show up after refreshing:\n  {cause}");
+                return;
+            }
+        };
+
+        let mut initial_snapshot = true;
+        while let Some(event) = events.next().await {
+            match event {
+                Ok(WatchEvent::Snapshot { .. }) if initial_snapshot => {
+                    initial_snapshot = false; //the data was just fetched anyway
+                }
+                Ok(_) => trigger.notify(),
+                Err(cause) => {
+                    warn!("Stopped watching modifications of <{kind:?}>:\n  {cause}");
+                    return;
+                }
+            }
+        }
+        debug!("CARL closed the watch of <{kind:?}>.");
+    });
+
+    leptos::task::spawn_local(async move {
+        let _ = watch.await;
+    });
+    on_cleanup(move || abort_handle.abort());
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AuditEntryId(pub Uuid);
+
+impl AuditEntryId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for AuditEntryId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for AuditEntryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Records a modification of a resource in CARL.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: AuditEntryId,
+    pub timestamp: SystemTime,
+    pub actor: AuditActor,
+    pub action: AuditAction,
+    pub resource: AuditedResource,
+}
+
+/// Who caused a modification.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AuditActor {
+    /// CARL itself, e.g. when a peer connects or disconnects.
+    System,
+    /// A request while authentication is disabled.
+    Anonymous,
+    User { name: String },
+}
+
+impl fmt::Display for AuditActor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditActor::System => write!(f, "system"),
+            AuditActor::Anonymous => write!(f, "anonymous"),
+            AuditActor::User { name } => write!(f, "{name}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AuditAction {
+    /// The resource was created or updated.
+    Inserted,
+    Removed,
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditAction::Inserted => write!(f, "inserted"),
+            AuditAction::Removed => write!(f, "removed"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AuditedResource {
+    /// Kind of the resource, e.g. `peer_descriptor` or `cluster_configuration`.
+    pub kind: String,
+    pub id: Uuid,
+}
+
+/// Criteria to select entries of the audit log. Unset criteria match every entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuditLogFilter {
+    pub resource_id: Option<Uuid>,
+    pub resource_kind: Option<String>,
+    pub actor: Option<AuditActor>,
+}
+
+impl AuditLogFilter {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.resource_id.is_none_or(|resource_id| entry.resource.id == resource_id)
+            && self.resource_kind.as_ref().is_none_or(|kind| &entry.resource.kind == kind)
+            && self.actor.as_ref().is_none_or(|actor| &entry.actor == actor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(actor: AuditActor, kind: &str, id: Uuid) -> AuditEntry {
+        AuditEntry {
+            id: AuditEntryId::random(),
+            timestamp: SystemTime::now(),
+            actor,
+            action: AuditAction::Inserted,
+            resource: AuditedResource { kind: String::from(kind), id },
+        }
+    }
+
+    #[test]
+    fn filter_should_match_by_all_criteria() {
+        let resource_id = Uuid::new_v4();
+        let entry = entry(AuditActor::User { name: String::from("alice") }, "peer_descriptor", resource_id);
+
+        assert!(AuditLogFilter::default().matches(&entry));
+        assert!(AuditLogFilter { resource_id: Some(resource_id), resource_kind: Some(String::from("peer_descriptor")), actor: Some(AuditActor::User { name: String::from("alice") }) }.matches(&entry));
+        assert!(!AuditLogFilter { resource_id: Some(Uuid::new_v4()), ..Default::default() }.matches(&entry));
+        assert!(!AuditLogFilter { resource_kind: Some(String::from("cluster_configuration")), ..Default::default() }.matches(&entry));
+        assert!(!AuditLogFilter { actor: Some(AuditActor::User { name: String::from("bob") }), ..Default::default() }.matches(&entry));
+    }
+
+    #[test]
+    fn filter_should_distinguish_users_from_components_of_the_same_name() {
+        let entry_by_system = entry(AuditActor::System, "peer_descriptor", Uuid::new_v4());
+        let entry_by_user = entry(AuditActor::User { name: String::from("system") }, "peer_descriptor", Uuid::new_v4());
+
+        let filter = AuditLogFilter { actor: Some(AuditActor::User { name: String::from("system") }), ..Default::default() };
+        assert!(!filter.matches(&entry_by_system));
+        assert!(filter.matches(&entry_by_user));
+
+        let filter = AuditLogFilter { actor: Some(AuditActor::System), ..Default::default() };
+        assert!(filter.matches(&entry_by_system));
+        assert!(!filter.matches(&entry_by_user));
+    }
+}
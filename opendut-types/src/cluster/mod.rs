@@ -8,8 +8,10 @@ use uuid::Uuid;
 
 pub use assignment::*;
 
+use crate::labels::{LabelSelector, Labels};
+use crate::project::ProjectId;
 use crate::peer::PeerId;
-use crate::topology::DeviceId;
+use crate::topology::{DeviceDescriptor, DeviceId};
 
 mod assignment;
 pub mod state;
@@ -148,6 +150,26 @@ pub struct ClusterConfiguration {
     pub name: ClusterName,
     pub leader: PeerId,
     pub devices: HashSet<DeviceId>,
+    #[serde(default)]
+    pub labels: Labels,
+    /// Devices with matching labels are added to `devices`, when the configuration is stored.
+    #[serde(default)]
+    pub device_selector: Option<LabelSelector>,
+    /// Devices of the cluster have to belong to peers, which belong to or are shared with this project.
+    #[serde(default)]
+    pub project: ProjectId,
+}
+
+impl ClusterConfiguration {
+    /// Adds the given devices with labels matching the `device_selector` to `devices`.
+    pub fn select_devices<'a>(&mut self, devices: impl IntoIterator<Item=&'a DeviceDescriptor>) {
+        if let Some(selector) = &self.device_selector {
+            let selected = devices.into_iter()
+                .filter(|device| selector.matches(&device.labels))
+                .map(|device| device.id);
+            self.devices.extend(selected);
+        }
+    }
 }
 
 #[derive(thiserror::Error, Clone, Debug)]
@@ -208,4 +230,35 @@ mod tests {
         let _peer_name = ClusterName::try_from("_asd123".to_string()).is_err();
         Ok(())
     }
+
+    #[test]
+    fn A_ClusterConfiguration_should_select_devices_by_labels() -> anyhow::Result<()> {
+        let device = |labels: &str| -> anyhow::Result<DeviceDescriptor> {
+            Ok(DeviceDescriptor {
+                id: DeviceId::random(),
+                name: crate::topology::DeviceName::try_from("device")?,
+                description: None,
+                interface: crate::util::net::NetworkInterfaceId::random(),
+                tags: vec![],
+                labels: Labels::from_str(labels)?,
+            })
+        };
+        let chassis = device("team=chassis")?;
+        let body = device("team=body")?;
+        let explicit = DeviceId::random();
+
+        let mut configuration = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("cluster")?,
+            leader: PeerId::random(),
+            devices: HashSet::from([explicit]),
+            labels: Labels::default(),
+            device_selector: Some(LabelSelector::from_str("team=chassis")?),
+            project: Default::default(),
+        };
+        configuration.select_devices([&chassis, &body]);
+
+        assert_that!(configuration.devices, unordered_elements_are![eq(&explicit), eq(&chassis.id)]);
+        Ok(())
+    }
 }
@@ -0,0 +1,286 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Free-form key-value labels for organizing resources, e.g. `team=chassis`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "BTreeMap<String, String>", into = "BTreeMap<String, String>")]
+pub struct Labels(BTreeMap<String, String>);
+
+impl Labels {
+    pub const MAX_KEY_LENGTH: usize = 63;
+    pub const MAX_VALUE_LENGTH: usize = 63;
+
+    /// Sets the label, returning the previous value of the key.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Result<Option<String>, IllegalLabel> {
+        let key = key.into();
+        let value = value.into();
+        validate_key(&key)?;
+        validate_value(&key, &value)?;
+        Ok(self.0.insert(key, value))
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.0.remove(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(&str, &str)> {
+        self.0.iter().map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+fn validate_key(key: &str) -> Result<(), IllegalLabel> {
+    if key.is_empty() {
+        Err(IllegalLabel::EmptyKey)
+    } else if key.len() > Labels::MAX_KEY_LENGTH {
+        Err(IllegalLabel::KeyTooLong { key: key.to_owned(), expected: Labels::MAX_KEY_LENGTH, actual: key.len() })
+    } else if !key.starts_with(|c: char| c.is_ascii_alphanumeric()) || !key.chars().all(valid_character) {
+        Err(IllegalLabel::InvalidKey { key: key.to_owned() })
+    } else {
+        Ok(())
+    }
+}
+
+fn validate_value(key: &str, value: &str) -> Result<(), IllegalLabel> {
+    if value.len() > Labels::MAX_VALUE_LENGTH {
+        Err(IllegalLabel::ValueTooLong { key: key.to_owned(), expected: Labels::MAX_VALUE_LENGTH, actual: value.len() })
+    } else if !value.chars().all(valid_character) {
+        Err(IllegalLabel::InvalidValue { key: key.to_owned(), value: value.to_owned() })
+    } else {
+        Ok(())
+    }
+}
+
+fn valid_character(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/')
+}
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+pub enum IllegalLabel {
+    #[error("Label key must not be empty.")]
+    EmptyKey,
+    #[error("Label key '{key}' is too long. Expected at most {expected} characters, got {actual}.")]
+    KeyTooLong { key: String, expected: usize, actual: usize },
+    #[error("Value of label '{key}' is too long. Expected at most {expected} characters, got {actual}.")]
+    ValueTooLong { key: String, expected: usize, actual: usize },
+    #[error("Label key '{key}' is invalid. It has to start with a letter or digit and may only contain letters, digits, '-', '_', '.' and '/'.")]
+    InvalidKey { key: String },
+    #[error("Value '{value}' of label '{key}' is invalid. It may only contain letters, digits, '-', '_', '.' and '/'.")]
+    InvalidValue { key: String, value: String },
+    #[error("Label '{label}' is invalid. Expected '<key>=<value>'.")]
+    MissingValue { label: String },
+}
+
+impl TryFrom<BTreeMap<String, String>> for Labels {
+    type Error = IllegalLabel;
+
+    fn try_from(labels: BTreeMap<String, String>) -> Result<Self, Self::Error> {
+        labels.into_iter()
+            .try_fold(Labels::default(), |mut labels, (key, value)| {
+                labels.insert(key, value)?;
+                Ok(labels)
+            })
+    }
+}
+
+impl From<Labels> for BTreeMap<String, String> {
+    fn from(labels: Labels) -> Self {
+        labels.0
+    }
+}
+
+impl fmt::Display for Labels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels = self.iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{labels}")
+    }
+}
+
+impl FromStr for Labels {
+    type Err = IllegalLabel;
+
+    /// Parses comma-separated labels, e.g. `team=chassis,site=munich`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value.split(',')
+            .filter(|label| !label.trim().is_empty())
+            .try_fold(Labels::default(), |mut labels, label| {
+                let (key, value) = label.split_once('=')
+                    .ok_or_else(|| IllegalLabel::MissingValue { label: label.trim().to_owned() })?;
+                labels.insert(key.trim(), value.trim())?;
+                Ok(labels)
+            })
+    }
+}
+
+
+/// Selects resources by their labels, written as comma-separated requirements, which all have to be fulfilled.
+/// A requirement is either `key=value`, `key!=value`, `key` (label exists) or `!key` (label does not exist).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct LabelSelector {
+    pub requirements: Vec<LabelRequirement>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LabelRequirement {
+    Equals { key: String, value: String },
+    /// Also fulfilled, if the label does not exist.
+    NotEquals { key: String, value: String },
+    Exists { key: String },
+    NotExists { key: String },
+}
+
+impl LabelSelector {
+    pub fn matches(&self, labels: &Labels) -> bool {
+        self.requirements.iter()
+            .all(|requirement| requirement.matches(labels))
+    }
+}
+
+impl LabelRequirement {
+    pub fn matches(&self, labels: &Labels) -> bool {
+        match self {
+            LabelRequirement::Equals { key, value } => labels.get(key) == Some(value.as_str()),
+            LabelRequirement::NotEquals { key, value } => labels.get(key) != Some(value.as_str()),
+            LabelRequirement::Exists { key } => labels.get(key).is_some(),
+            LabelRequirement::NotExists { key } => labels.get(key).is_none(),
+        }
+    }
+}
+
+impl fmt::Display for LabelSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let requirements = self.requirements.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{requirements}")
+    }
+}
+
+impl fmt::Display for LabelRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LabelRequirement::Equals { key, value } => write!(f, "{key}={value}"),
+            LabelRequirement::NotEquals { key, value } => write!(f, "{key}!={value}"),
+            LabelRequirement::Exists { key } => write!(f, "{key}"),
+            LabelRequirement::NotExists { key } => write!(f, "!{key}"),
+        }
+    }
+}
+
+impl FromStr for LabelSelector {
+    type Err = IllegalLabelSelector;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let requirements = value.split(',')
+            .map(LabelRequirement::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(LabelSelector { requirements })
+    }
+}
+
+impl FromStr for LabelRequirement {
+    type Err = IllegalLabelSelector;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let requirement = value.trim();
+        let invalid = |cause: IllegalLabel| IllegalLabelSelector { requirement: requirement.to_owned(), cause };
+
+        let requirement = if let Some((key, value)) = requirement.split_once("!=") {
+            let (key, value) = (key.trim(), value.trim());
+            validate_key(key).and_then(|_| validate_value(key, value)).map_err(invalid)?;
+            LabelRequirement::NotEquals { key: key.to_owned(), value: value.to_owned() }
+        } else if let Some((key, value)) = requirement.split_once('=') {
+            let (key, value) = (key.trim(), value.trim());
+            validate_key(key).and_then(|_| validate_value(key, value)).map_err(invalid)?;
+            LabelRequirement::Equals { key: key.to_owned(), value: value.to_owned() }
+        } else if let Some(key) = requirement.strip_prefix('!') {
+            let key = key.trim();
+            validate_key(key).map_err(invalid)?;
+            LabelRequirement::NotExists { key: key.to_owned() }
+        } else {
+            validate_key(requirement).map_err(invalid)?;
+            LabelRequirement::Exists { key: requirement.to_owned() }
+        };
+        Ok(requirement)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+#[error("Invalid label selector requirement '{requirement}': {cause}")]
+pub struct IllegalLabelSelector {
+    pub requirement: String,
+    pub cause: IllegalLabel,
+}
+
+impl TryFrom<String> for LabelSelector {
+    type Error = IllegalLabelSelector;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        LabelSelector::from_str(&value)
+    }
+}
+
+impl From<LabelSelector> for String {
+    fn from(selector: LabelSelector) -> Self {
+        selector.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("team=chassis", true)]
+    #[case("team=body", false)]
+    #[case("team=chassis,site!=munich", true)]
+    #[case("site!=berlin", false)]
+    #[case("site", true)]
+    #[case("!site", false)]
+    #[case("!owner", true)]
+    #[case("owner!=someone", true)]
+    fn should_match_label_selector(#[case] selector: &str, #[case] expected: bool) -> anyhow::Result<()> {
+        let labels = Labels::from_str("team=chassis,site=berlin")?;
+
+        assert_that!(LabelSelector::from_str(selector)?.matches(&labels), eq(expected));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("=chassis")]
+    #[case("team=chas sis")]
+    #[case("-team=chassis")]
+    #[case("team=chassis,")]
+    fn should_reject_invalid_label_selectors(#[case] selector: &str) {
+        assert_that!(LabelSelector::from_str(selector), err(anything()));
+    }
+
+    #[test]
+    fn should_validate_labels() {
+        let mut labels = Labels::default();
+
+        assert_that!(labels.insert("opendut.eclipse.dev/team", "chassis"), ok(none()));
+        assert_that!(labels.insert("team", "a,b"), err(anything()));
+        assert_that!(labels.insert("", "chassis"), err(eq(&IllegalLabel::EmptyKey)));
+        assert_that!(labels.insert("x".repeat(Labels::MAX_KEY_LENGTH + 1), ""), err(anything()));
+        assert_that!(labels.to_string(), eq("opendut.eclipse.dev/team=chassis"));
+    }
+}
@@ -9,8 +9,17 @@ pub struct LeaIdentityProviderConfig {
     pub issuer_url: Url,
     pub scopes: String,
 }
+/// Present, if CARL authenticates users via its built-in user store instead of an OIDC provider.
+/// LEA then signs users in with their password via the token endpoint of CARL.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeaLocalUsersConfig {
+    pub token_url: Url,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct LeaConfig {
     pub carl_url: Url,
     pub idp_config: Option<LeaIdentityProviderConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_users: Option<LeaLocalUsersConfig>,
 }
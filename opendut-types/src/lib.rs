@@ -1,14 +1,20 @@
 use uuid::{Uuid, uuid};
 
+pub mod audit;
 pub mod cleo;
 pub mod cluster;
+pub mod labels;
 pub mod lea;
+pub mod notification;
 pub mod peer;
+pub mod project;
 pub mod proto;
+pub mod query;
 pub mod resources;
 #[cfg(feature = "specs")]
 pub mod specs;
 pub mod topology;
+pub mod user;
 pub mod util;
 pub mod vpn;
 
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::PeerId;
+use crate::util::net::ClientId;
+
+/// Identifies an issued client certificate. Used as the serial number of the certificate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ClientCertificateId(pub Uuid);
+
+impl ClientCertificateId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for ClientCertificateId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for ClientCertificateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Tracks a client certificate issued to a peer, so that it can be revoked before it expires.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientCertificateRecord {
+    pub id: ClientCertificateId,
+    pub peer_id: PeerId,
+    pub issued_at: SystemTime,
+    pub expires_at: SystemTime,
+    /// When the certificate was revoked, either by decommissioning the peer or by rotating its credentials.
+    pub revoked_at: Option<SystemTime>,
+}
+
+impl ClientCertificateRecord {
+    pub fn is_valid(&self, now: SystemTime) -> bool {
+        self.revoked_at.is_none() && now < self.expires_at
+    }
+}
+
+/// The new credentials of a peer, after they were rotated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RotatedPeerCredentials {
+    /// The new OIDC client, unless OIDC is disabled or peers share statically configured credentials.
+    pub client_id: Option<ClientId>,
+    /// The new client certificate, unless CARL is configured to not issue client certificates.
+    pub client_certificate_id: Option<ClientCertificateId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_only_consider_unrevoked_and_unexpired_certificates_valid() {
+        let issued_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let record = ClientCertificateRecord {
+            id: ClientCertificateId::random(),
+            peer_id: PeerId::random(),
+            issued_at,
+            expires_at: issued_at + Duration::from_secs(60),
+            revoked_at: None,
+        };
+
+        assert_that!(record.is_valid(issued_at), eq(true));
+        assert_that!(record.is_valid(issued_at + Duration::from_secs(60)), eq(false));
+
+        let revoked = ClientCertificateRecord { revoked_at: Some(issued_at), ..record };
+        assert_that!(revoked.is_valid(issued_at), eq(false));
+    }
+}
@@ -65,6 +65,15 @@ impl ParameterValue for parameter::Executor {
         &mut peer_configuration.executors
     }
 }
+impl ParameterValue for parameter::WireGuardPeer {
+    fn parameter_identifier(&self) -> ParameterId {
+        let id = Uuid::new_v5(&OPENDUT_UUID_NAMESPACE, self.descriptor.peer_id.uuid.as_bytes());
+        ParameterId(id)
+    }
+    fn peer_configuration_field(peer_configuration: &mut PeerConfiguration) -> &mut Vec<Parameter<Self>> {
+        &mut peer_configuration.wireguard_peers
+    }
+}
 
 
 #[cfg(test)]
@@ -16,6 +16,7 @@ pub struct PeerConfiguration {
     pub device_interfaces: Vec<Parameter<parameter::DeviceInterface>>,
     pub ethernet_bridges: Vec<Parameter<parameter::EthernetBridge>>,
     pub executors: Vec<Parameter<parameter::Executor>>,
+    pub wireguard_peers: Vec<Parameter<parameter::WireGuardPeer>>,
     //TODO migrate more parameters
 }
 impl PeerConfiguration {
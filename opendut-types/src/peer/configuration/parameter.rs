@@ -1,5 +1,6 @@
 use crate::peer::executor::ExecutorDescriptor;
 use crate::util::net::{NetworkInterfaceDescriptor, NetworkInterfaceName};
+use crate::vpn::wireguard::WireGuardPeerDescriptor;
 
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -16,3 +17,8 @@ pub struct EthernetBridge {
 pub struct Executor {
     pub descriptor: ExecutorDescriptor,
 }
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WireGuardPeer {
+    pub descriptor: WireGuardPeerDescriptor,
+}
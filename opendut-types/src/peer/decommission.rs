@@ -0,0 +1,77 @@
+use std::fmt::{Display, Formatter};
+use std::time::SystemTime;
+
+use crate::peer::PeerId;
+
+/// Persisted when a peer is decommissioned, so that it cannot connect anymore with credentials it obtained before,
+/// e.g. OIDC tokens, which remain valid until they expire. Removed when a new Setup-String of the peer is redeemed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerDecommissionRecord {
+    pub peer_id: PeerId,
+    pub decommissioned_at: SystemTime,
+}
+
+/// Result of decommissioning a peer, i.e. revoking everything which grants the peer access.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeerDecommissionReport {
+    pub peer_id: PeerId,
+    pub steps: Vec<PeerDecommissionStep>,
+}
+
+impl PeerDecommissionReport {
+    /// Whether none of the steps failed. A decommissioning, which is not complete, can be retried.
+    pub fn is_complete(&self) -> bool {
+        self.steps.iter()
+            .all(|step| !matches!(step.outcome, PeerDecommissionOutcome::Failed { .. }))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeerDecommissionStep {
+    pub action: PeerDecommissionAction,
+    pub outcome: PeerDecommissionOutcome,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PeerDecommissionAction {
+    /// Setup-Strings, which were not used yet, are revoked.
+    RevokeSetupStrings,
+    /// The stream of a connected EDGAR is closed.
+    DisconnectStream,
+    /// The OIDC clients, which EDGAR authenticates with, are deleted.
+    DeleteOidcClients,
+    /// The device and self-group of the peer are deleted from the VPN.
+    DeleteVpnPeer,
+    /// The client certificates issued to the peer are revoked.
+    RevokeClientCertificates,
+}
+
+impl Display for PeerDecommissionAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PeerDecommissionAction::RevokeSetupStrings => write!(f, "Revoke Setup-Strings"),
+            PeerDecommissionAction::DisconnectStream => write!(f, "Disconnect stream"),
+            PeerDecommissionAction::DeleteOidcClients => write!(f, "Delete OIDC clients"),
+            PeerDecommissionAction::DeleteVpnPeer => write!(f, "Delete VPN peer"),
+            PeerDecommissionAction::RevokeClientCertificates => write!(f, "Revoke client certificates"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PeerDecommissionOutcome {
+    Done,
+    /// The step was not necessary, e.g. because the peer was not connected.
+    Skipped { reason: String },
+    Failed { cause: String },
+}
+
+impl Display for PeerDecommissionOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PeerDecommissionOutcome::Done => write!(f, "done"),
+            PeerDecommissionOutcome::Skipped { reason } => write!(f, "skipped: {reason}"),
+            PeerDecommissionOutcome::Failed { cause } => write!(f, "failed: {cause}"),
+        }
+    }
+}
@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 use url::Url;
 use uuid::Uuid;
 
+use crate::labels::Labels;
+use crate::project::ProjectAssignment;
 use crate::peer::executor::ExecutorDescriptors;
 use crate::topology::{DeviceDescriptor, Topology};
 use crate::util::net::{AuthConfig, Certificate, NetworkInterfaceDescriptor, NetworkInterfaceName};
@@ -15,6 +17,10 @@ use crate::vpn::VpnPeerConfiguration;
 pub mod state;
 pub mod executor;
 pub mod configuration;
+pub mod update;
+pub mod setup;
+pub mod decommission;
+pub mod certificate;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -296,6 +302,10 @@ pub struct PeerDescriptor {
     pub network: PeerNetworkDescriptor,
     pub topology: Topology,
     pub executors: ExecutorDescriptors,
+    #[serde(default)]
+    pub labels: Labels,
+    #[serde(default)]
+    pub project: ProjectAssignment,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -305,6 +315,16 @@ pub struct PeerSetup {
     pub ca: Certificate,
     pub auth_config: AuthConfig,
     pub vpn: VpnPeerConfiguration,
+    /// Key for verifying updates of EDGAR. If missing, EDGAR does not update itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_verification_key: Option<update::UpdateVerificationKey>,
+    /// Identifies this Setup-String towards CARL, which only accepts it once. Missing in Setup-Strings of older versions of CARL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup_id: Option<setup::PeerSetupId>,
+    /// Whether EDGAR requests a certificate for authenticating towards CARL via mutual TLS, when redeeming the Setup-String.
+    /// The key of the certificate is generated by EDGAR and never leaves the peer.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub request_client_certificate: bool,
 }
 
 impl PeerSetup {
@@ -391,6 +411,9 @@ mod tests {
                 management_url: Url::parse("https://netbird.opendut.local/api")?,
                 setup_key: SetupKey::from(Uuid::parse_str("d79c202f-bbbf-4997-844e-678f27606e1c")?),
             },
+            update_verification_key: None,
+            setup_id: None,
+            request_client_certificate: false,
         };
 
         let encoded = setup.encode()?;
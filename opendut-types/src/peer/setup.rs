@@ -0,0 +1,135 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::PeerId;
+
+/// Identifies an issued Setup-String. Contained in the Setup-String, so that CARL can tell whether it was already used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PeerSetupId(pub Uuid);
+
+impl PeerSetupId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for PeerSetupId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal PeerSetupId: {value}")]
+pub struct IllegalPeerSetupId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for PeerSetupId {
+    type Error = IllegalPeerSetupId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value).map(Self).map_err(|_| IllegalPeerSetupId {
+            value: String::from(value),
+        })
+    }
+}
+
+impl FromStr for PeerSetupId {
+    type Err = IllegalPeerSetupId;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::try_from(value)
+    }
+}
+
+impl fmt::Display for PeerSetupId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Tracks an issued Setup-String, which may only be used once and only until it expires.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerSetupRecord {
+    pub id: PeerSetupId,
+    pub peer_id: PeerId,
+    pub issued_at: SystemTime,
+    pub expires_at: SystemTime,
+    /// When EDGAR was set up with the Setup-String.
+    pub used_at: Option<SystemTime>,
+    /// When the Setup-String was revoked, either explicitly or by generating a newer one for the same peer.
+    pub revoked_at: Option<SystemTime>,
+}
+
+impl PeerSetupRecord {
+    pub fn status(&self, now: SystemTime) -> PeerSetupStatus {
+        if self.used_at.is_some() {
+            PeerSetupStatus::Used
+        } else if self.revoked_at.is_some() {
+            PeerSetupStatus::Revoked
+        } else if now >= self.expires_at {
+            PeerSetupStatus::Expired
+        } else {
+            PeerSetupStatus::Pending
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PeerSetupStatus {
+    /// Can still be used for setting up EDGAR.
+    Pending,
+    Used,
+    Expired,
+    Revoked,
+}
+
+impl fmt::Display for PeerSetupStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PeerSetupStatus::Pending => write!(f, "pending"),
+            PeerSetupStatus::Used => write!(f, "used"),
+            PeerSetupStatus::Expired => write!(f, "expired"),
+            PeerSetupStatus::Revoked => write!(f, "revoked"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_determine_the_status_of_a_setup_record() {
+        let issued_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let record = PeerSetupRecord {
+            id: PeerSetupId::random(),
+            peer_id: PeerId::random(),
+            issued_at,
+            expires_at: issued_at + Duration::from_secs(60),
+            used_at: None,
+            revoked_at: None,
+        };
+        let before_expiry = issued_at + Duration::from_secs(30);
+        let after_expiry = issued_at + Duration::from_secs(90);
+
+        assert_that!(record.status(before_expiry), eq(PeerSetupStatus::Pending));
+        assert_that!(record.status(after_expiry), eq(PeerSetupStatus::Expired));
+
+        let used = PeerSetupRecord { used_at: Some(before_expiry), ..Clone::clone(&record) };
+        assert_that!(used.status(after_expiry), eq(PeerSetupStatus::Used));
+
+        let revoked = PeerSetupRecord { revoked_at: Some(before_expiry), ..record };
+        assert_that!(revoked.status(before_expiry), eq(PeerSetupStatus::Revoked));
+    }
+}
@@ -0,0 +1,100 @@
+use std::fmt;
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use serde::{Deserialize, Serialize};
+
+/// Ed25519 public key, against which EDGAR verifies the signature of an EDGAR distribution before updating itself.
+/// Written as the Base64 encoding of the 32 bytes of the raw key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct UpdateVerificationKey {
+    bytes: [u8; Self::LENGTH],
+}
+
+impl UpdateVerificationKey {
+    pub const LENGTH: usize = 32;
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl TryFrom<&[u8]> for UpdateVerificationKey {
+    type Error = IllegalUpdateVerificationKey;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let bytes = <[u8; Self::LENGTH]>::try_from(value)
+            .map_err(|_| IllegalUpdateVerificationKey {
+                value: BASE64_STANDARD.encode(value),
+                details: format!("Expected a key with a length of {} bytes, but got {} bytes.", Self::LENGTH, value.len()),
+            })?;
+        Ok(Self { bytes })
+    }
+}
+
+impl FromStr for UpdateVerificationKey {
+    type Err = IllegalUpdateVerificationKey;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let bytes = BASE64_STANDARD.decode(value.trim())
+            .map_err(|cause| IllegalUpdateVerificationKey {
+                value: value.to_owned(),
+                details: format!("Base64 decoding failed due to: {cause}"),
+            })?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl TryFrom<String> for UpdateVerificationKey {
+    type Error = IllegalUpdateVerificationKey;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl From<UpdateVerificationKey> for String {
+    fn from(value: UpdateVerificationKey) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for UpdateVerificationKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", BASE64_STANDARD.encode(self.bytes))
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal UpdateVerificationKey '{value}': {details}")]
+pub struct IllegalUpdateVerificationKey {
+    pub value: String,
+    pub details: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_and_print_a_base64_encoded_key() -> anyhow::Result<()> {
+        let encoded = "11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo=";
+
+        let key = UpdateVerificationKey::from_str(encoded)?;
+
+        assert_that!(key.as_bytes().len(), eq(UpdateVerificationKey::LENGTH));
+        assert_that!(key.to_string(), eq(encoded));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_a_key_with_the_wrong_length() {
+        let result = UpdateVerificationKey::from_str("AAEC");
+
+        assert_that!(result, err(anything()));
+    }
+}
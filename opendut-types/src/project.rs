@@ -0,0 +1,144 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Identifies a project, i.e. the namespace of a team, to which peers and cluster configurations belong, e.g. `chassis`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ProjectId(String);
+
+impl ProjectId {
+    /// Project of all resources, which were not assigned to another project. Every user is a member of it.
+    pub const DEFAULT: &'static str = "default";
+    pub const MAX_LENGTH: usize = 63;
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.0 == Self::DEFAULT
+    }
+}
+
+impl Default for ProjectId {
+    fn default() -> Self {
+        Self(String::from(Self::DEFAULT))
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+pub enum IllegalProjectId {
+    #[error("Project ID must not be empty.")]
+    Empty,
+    #[error("Project ID '{value}' is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong { value: String, expected: usize, actual: usize },
+    #[error("Project ID '{value}' is invalid. It has to start with a lowercase letter or digit and may only contain lowercase letters, digits and '-'.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for ProjectId {
+    type Error = IllegalProjectId;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let valid_character = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-';
+
+        if value.is_empty() {
+            Err(IllegalProjectId::Empty)
+        } else if value.len() > Self::MAX_LENGTH {
+            Err(IllegalProjectId::TooLong { expected: Self::MAX_LENGTH, actual: value.len(), value })
+        } else if value.starts_with('-') || !value.chars().all(valid_character) {
+            Err(IllegalProjectId::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ProjectId {
+    type Error = IllegalProjectId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ProjectId::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ProjectId {
+    type Err = IllegalProjectId;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::try_from(value.trim())
+    }
+}
+
+impl From<ProjectId> for String {
+    fn from(value: ProjectId) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ProjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Project, which a peer belongs to, and further projects, which may read the peer and use it in their clusters, but not modify it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectAssignment {
+    pub owner: ProjectId,
+    #[serde(default)]
+    pub shared_with: BTreeSet<ProjectId>,
+}
+
+impl ProjectAssignment {
+    pub fn owned_by(owner: ProjectId) -> Self {
+        Self { owner, shared_with: BTreeSet::new() }
+    }
+
+    /// Whether members of the project may read the resource, because it belongs to or is shared with the project.
+    pub fn is_visible_to(&self, project: &ProjectId) -> bool {
+        self.owner == *project || self.shared_with.contains(project)
+    }
+
+    /// The owning project, followed by the projects the resource is shared with.
+    pub fn projects(&self) -> impl Iterator<Item=&ProjectId> {
+        std::iter::once(&self.owner).chain(self.shared_with.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("chassis", true)]
+    #[case("team-42", true)]
+    #[case("", false)]
+    #[case("-chassis", false)]
+    #[case("Chassis", false)]
+    #[case("chassis/rear", false)]
+    fn should_validate_project_ids(#[case] value: &str, #[case] valid: bool) {
+        assert_that!(ProjectId::try_from(value).is_ok(), eq(valid));
+    }
+
+    #[test]
+    fn shared_peers_should_be_visible_to_the_projects_they_are_shared_with() -> anyhow::Result<()> {
+        let chassis = ProjectId::try_from("chassis")?;
+        let powertrain = ProjectId::try_from("powertrain")?;
+
+        let mut assignment = ProjectAssignment::owned_by(chassis.clone());
+        assert!(assignment.is_visible_to(&chassis));
+        assert!(!assignment.is_visible_to(&powertrain));
+
+        assignment.shared_with.insert(powertrain.clone());
+        assert!(assignment.is_visible_to(&powertrain));
+        assert_that!(assignment.projects().collect::<Vec<_>>(), elements_are![eq(&chassis), eq(&powertrain)]);
+        Ok(())
+    }
+}
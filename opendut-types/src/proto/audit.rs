@@ -0,0 +1,127 @@
+use std::time::{Duration, SystemTime};
+
+use crate::proto::{conversion, ConversionError, ConversionErrorBuilder, ConversionResult};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.audit.rs"));
+
+
+conversion! {
+    type Model = crate::audit::AuditEntryId;
+    type Proto = AuditEntryId;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            uuid: Some(value.0.into())
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        extract!(value.uuid)
+            .map(|uuid| crate::audit::AuditEntryId(uuid.into()))
+    }
+}
+
+conversion! {
+    type Model = crate::audit::AuditEntry;
+    type Proto = AuditEntry;
+
+    fn from(value: Model) -> Proto {
+        let timestamp_unix_millis = value.timestamp
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        Proto {
+            id: Some(value.id.into()),
+            timestamp_unix_millis,
+            actor: Some(value.actor.into()),
+            action: Some(value.action.into()),
+            resource: Some(value.resource.into()),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let id = extract!(value.id)?.try_into()?;
+        let timestamp = SystemTime::UNIX_EPOCH + Duration::from_millis(value.timestamp_unix_millis);
+        let actor = extract!(value.actor)?.try_into()?;
+        let action = extract!(value.action)?.try_into()?;
+        let resource = extract!(value.resource)?.try_into()?;
+
+        Ok(Model {
+            id,
+            timestamp,
+            actor,
+            action,
+            resource,
+        })
+    }
+}
+
+conversion! {
+    type Model = crate::audit::AuditActor;
+    type Proto = AuditActor;
+
+    fn from(value: Model) -> Proto {
+        let inner = match value {
+            Model::System => audit_actor::Inner::System(AuditActorSystem {}),
+            Model::Anonymous => audit_actor::Inner::Anonymous(AuditActorAnonymous {}),
+            Model::User { name } => audit_actor::Inner::User(AuditActorUser { name }),
+        };
+        Proto {
+            inner: Some(inner)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let actor = match extract!(value.inner)? {
+            audit_actor::Inner::System(_) => Model::System,
+            audit_actor::Inner::Anonymous(_) => Model::Anonymous,
+            audit_actor::Inner::User(AuditActorUser { name }) => Model::User { name },
+        };
+        Ok(actor)
+    }
+}
+
+conversion! {
+    type Model = crate::audit::AuditAction;
+    type Proto = AuditAction;
+
+    fn from(value: Model) -> Proto {
+        let inner = match value {
+            Model::Inserted => audit_action::Inner::Inserted(AuditActionInserted {}),
+            Model::Removed => audit_action::Inner::Removed(AuditActionRemoved {}),
+        };
+        Proto {
+            inner: Some(inner)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let action = match extract!(value.inner)? {
+            audit_action::Inner::Inserted(_) => Model::Inserted,
+            audit_action::Inner::Removed(_) => Model::Removed,
+        };
+        Ok(action)
+    }
+}
+
+conversion! {
+    type Model = crate::audit::AuditedResource;
+    type Proto = AuditedResource;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            kind: value.kind,
+            id: Some(value.id.into()),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let id = extract!(value.id)?.into();
+
+        Ok(Model {
+            kind: value.kind,
+            id,
+        })
+    }
+}
@@ -48,6 +48,9 @@ conversion! {
             devices: configuration.devices.into_iter()
                 .map(DeviceId::from)
                 .collect(),
+            labels: Some(configuration.labels.into()),
+            device_selector: configuration.device_selector.map(Into::into),
+            project: Some(configuration.project.into()),
         }
     }
 
@@ -58,6 +61,20 @@ conversion! {
 
         let leader: crate::peer::PeerId = extract!(configuration.leader)?.try_into()?;
 
+        let labels = configuration.labels
+            .map(TryInto::try_into)
+            .transpose()?
+            .unwrap_or_default();
+
+        let device_selector = configuration.device_selector
+            .map(TryInto::try_into)
+            .transpose()?;
+
+        let project = configuration.project
+            .map(TryInto::try_into)
+            .transpose()?
+            .unwrap_or_default();
+
         Ok(Model {
             id: cluster_id,
             name: cluster_name,
@@ -65,6 +82,9 @@ conversion! {
             devices: configuration.devices.into_iter()
                 .map(DeviceId::try_into)
                 .collect::<Result<_, _>>()?,
+            labels,
+            device_selector,
+            project,
         })
     }
 }
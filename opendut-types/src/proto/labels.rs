@@ -0,0 +1,40 @@
+use std::str::FromStr;
+
+use crate::proto::{conversion, ConversionError, ConversionErrorBuilder, ConversionResult};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.labels.rs"));
+
+
+conversion! {
+    type Model = crate::labels::Labels;
+    type Proto = Labels;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            entries: value.iter()
+                .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                .collect(),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        Model::try_from(value.entries.into_iter().collect::<std::collections::BTreeMap<_, _>>())
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+conversion! {
+    type Model = crate::labels::LabelSelector;
+    type Proto = LabelSelector;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            value: value.to_string(),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        Model::from_str(&value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
@@ -1,6 +1,12 @@
+pub mod audit;
 pub mod cluster;
+pub mod labels;
+pub mod notification;
 pub mod peer;
+pub mod project;
+pub mod query;
 pub mod topology;
+pub mod user;
 pub mod util;
 pub mod vpn;
 pub mod cleo;
@@ -0,0 +1,138 @@
+use crate::proto::{conversion, ConversionError, ConversionErrorBuilder, ConversionResult};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.notification.rs"));
+
+
+conversion! {
+    type Model = crate::notification::NotificationSubscriptionId;
+    type Proto = NotificationSubscriptionId;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            uuid: Some(value.0.into())
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        extract!(value.uuid)
+            .map(|uuid| crate::notification::NotificationSubscriptionId(uuid.into()))
+    }
+}
+
+conversion! {
+    type Model = crate::notification::NotificationSubscriptionName;
+    type Proto = NotificationSubscriptionName;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            value: value.0
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        Model::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+conversion! {
+    type Model = crate::notification::NotificationEventKind;
+    type Proto = NotificationEventKind;
+
+    fn from(value: Model) -> Proto {
+        let inner = match value {
+            Model::ClusterDeployed => notification_event_kind::Inner::ClusterDeployed(NotificationEventKindClusterDeployed {}),
+            Model::ClusterUndeployed => notification_event_kind::Inner::ClusterUndeployed(NotificationEventKindClusterUndeployed {}),
+            Model::PeerOnline => notification_event_kind::Inner::PeerOnline(NotificationEventKindPeerOnline {}),
+            Model::PeerOffline => notification_event_kind::Inner::PeerOffline(NotificationEventKindPeerOffline {}),
+            Model::ExecutorFinished => notification_event_kind::Inner::ExecutorFinished(NotificationEventKindExecutorFinished {}),
+        };
+        Proto {
+            inner: Some(inner)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let kind = match extract!(value.inner)? {
+            notification_event_kind::Inner::ClusterDeployed(_) => Model::ClusterDeployed,
+            notification_event_kind::Inner::ClusterUndeployed(_) => Model::ClusterUndeployed,
+            notification_event_kind::Inner::PeerOnline(_) => Model::PeerOnline,
+            notification_event_kind::Inner::PeerOffline(_) => Model::PeerOffline,
+            notification_event_kind::Inner::ExecutorFinished(_) => Model::ExecutorFinished,
+        };
+        Ok(kind)
+    }
+}
+
+conversion! {
+    type Model = crate::notification::NotificationTarget;
+    type Proto = NotificationTarget;
+
+    fn from(value: Model) -> Proto {
+        let inner = match value {
+            Model::Webhook { url, secret } => notification_target::Inner::Webhook(WebhookNotificationTarget {
+                url: Some(url.into()),
+                secret: secret.0,
+            }),
+            Model::Mqtt { broker_url, topic } => notification_target::Inner::Mqtt(MqttNotificationTarget {
+                broker_url: Some(broker_url.into()),
+                topic,
+            }),
+        };
+        Proto {
+            inner: Some(inner)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let target = match extract!(value.inner)? {
+            notification_target::Inner::Webhook(WebhookNotificationTarget { url, secret }) => {
+                let url = extract!(url)?.try_into()?;
+                Model::webhook(url, crate::notification::NotificationSecret(secret))
+            }
+            notification_target::Inner::Mqtt(MqttNotificationTarget { broker_url, topic }) => {
+                let broker_url = extract!(broker_url)?.try_into()?;
+                Model::mqtt(broker_url, topic)
+            }
+        };
+        target.map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+conversion! {
+    type Model = crate::notification::NotificationSubscription;
+    type Proto = NotificationSubscription;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            id: Some(value.id.into()),
+            name: Some(value.name.into()),
+            project: Some(value.project.into()),
+            events: value.events.into_iter().map(Into::into).collect(),
+            selector: value.selector.map(Into::into),
+            target: Some(value.target.into()),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let id = extract!(value.id)?.try_into()?;
+        let name = extract!(value.name)?.try_into()?;
+        let project = extract!(value.project)?.try_into()?;
+        let events = value.events.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+        let selector = value.selector
+            .map(TryInto::try_into)
+            .transpose()?;
+        let target = extract!(value.target)?.try_into()?;
+
+        Ok(Model {
+            id,
+            name,
+            project,
+            events,
+            selector,
+            target,
+        })
+    }
+}
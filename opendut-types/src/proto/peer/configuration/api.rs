@@ -33,6 +33,7 @@ conversion! {
             executors: value.executors.into_iter().map(From::from).collect(),
             ethernet_bridges: value.ethernet_bridges.into_iter().map(From::from).collect(),
             device_interfaces: value.device_interfaces.into_iter().map(From::from).collect(),
+            wireguard_peers: value.wireguard_peers.into_iter().map(From::from).collect(),
         }
     }
 
@@ -41,6 +42,7 @@ conversion! {
             executors: value.executors.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
             ethernet_bridges: value.ethernet_bridges.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
             device_interfaces: value.device_interfaces.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            wireguard_peers: value.wireguard_peers.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
         })
     }
 }
@@ -129,6 +131,34 @@ conversion! {
     }
 }
 
+conversion! {
+    type Model = crate::peer::configuration::Parameter<crate::peer::configuration::parameter::WireGuardPeer>;
+    type Proto = PeerConfigurationParameterWireGuardPeer;
+
+    fn from(model: Model) -> Proto {
+        let value: crate::proto::peer::configuration::parameter::WireGuardPeer = model.value.clone().into();
+        let parameter = PeerConfigurationParameter::from(model);
+
+        Proto {
+            parameter: Some(parameter),
+            value: Some(value),
+        }
+    }
+
+    fn try_from(proto: Proto) -> ConversionResult<Model> {
+        let parameter = extract!(proto.parameter)?;
+
+        let value: crate::peer::configuration::parameter::WireGuardPeer = extract!(proto.value)?.try_into()?;
+
+        Ok(Model {
+            id: extract!(parameter.id)?.try_into()?,
+            dependencies: parameter.dependencies.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            target: extract!(parameter.target)?.into(),
+            value,
+        })
+    }
+}
+
 impl<V: crate::peer::configuration::ParameterValue> From<crate::peer::configuration::Parameter<V>> for PeerConfigurationParameter {
     fn from(value: crate::peer::configuration::Parameter<V>) -> Self {
         Self {
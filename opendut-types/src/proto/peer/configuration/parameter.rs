@@ -60,3 +60,46 @@ conversion! {
         })
     }
 }
+
+conversion! {
+    type Model = crate::peer::configuration::parameter::WireGuardPeer;
+    type Proto = WireGuardPeer;
+
+    fn from(value: Model) -> Proto {
+        let crate::vpn::wireguard::WireGuardPeerDescriptor { peer_id, public_key, address, endpoint } = value.descriptor;
+
+        Proto {
+            peer_id: Some(peer_id.into()),
+            public_key: Some(public_key.into()),
+            address: Some(address.into()),
+            endpoint: endpoint.map(|endpoint| WireGuardEndpoint {
+                host: Some(endpoint.ip().into()),
+                port: Some(crate::util::Port(endpoint.port()).into()),
+            }),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let peer_id = extract!(value.peer_id)?.try_into()?;
+        let public_key = extract!(value.public_key)?.try_into()?;
+        let address = extract!(value.address)?.try_into()?;
+
+        let endpoint = match value.endpoint {
+            Some(endpoint) => {
+                let host: std::net::IpAddr = extract!(endpoint.host)?.try_into()?;
+                let port: crate::util::Port = extract!(endpoint.port)?.try_into()?;
+                Some(std::net::SocketAddr::new(host, port.0))
+            }
+            None => None,
+        };
+
+        Ok(crate::peer::configuration::parameter::WireGuardPeer {
+            descriptor: crate::vpn::wireguard::WireGuardPeerDescriptor {
+                peer_id,
+                public_key,
+                address,
+                endpoint,
+            },
+        })
+    }
+}
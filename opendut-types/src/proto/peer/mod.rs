@@ -1,3 +1,5 @@
+use std::time::{Duration, SystemTime};
+
 use crate::proto::{conversion, ConversionError, ConversionErrorBuilder, ConversionResult};
 use crate::proto::vpn::VpnPeerConfig;
 
@@ -112,6 +114,8 @@ conversion! {
             network: Some(value.network.into()),
             topology: Some(value.topology.into()),
             executors: Some(value.executors.into()),
+            labels: Some(value.labels.into()),
+            project: Some(value.project.into()),
         }
     }
 
@@ -134,7 +138,18 @@ conversion! {
 
         let executors = extract!(value.executors)?
             .try_into()?;
-        
+
+        let labels = value.labels
+            .map(TryInto::try_into)
+            .transpose()?
+            .unwrap_or_default();
+
+        // Peers, which were stored before projects were introduced, belong to the default project.
+        let project = value.project
+            .map(TryInto::try_into)
+            .transpose()?
+            .unwrap_or_default();
+
         Ok(Model {
             id,
             name,
@@ -142,6 +157,8 @@ conversion! {
             network,
             topology,
             executors,
+            labels,
+            project,
         })
     }
 }
@@ -157,6 +174,10 @@ conversion! {
             ca: Some(value.ca.into()),
             vpn: Some(value.vpn.into()),
             auth_config: Some(value.auth_config.into()),
+            update_verification_key: value.update_verification_key
+                .map(|key| UpdateVerificationKey { value: key.as_bytes().to_vec() }),
+            setup_id: value.setup_id.map(Into::into),
+            request_client_certificate: value.request_client_certificate,
         }
     }
 
@@ -179,8 +200,121 @@ conversion! {
         let auth_config = extract!(value.auth_config)?
             .try_into()?;
 
+        let update_verification_key = value.update_verification_key
+            .map(|key| crate::peer::update::UpdateVerificationKey::try_from(key.value.as_slice()))
+            .transpose()
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
+        let setup_id = value.setup_id
+            .map(crate::peer::setup::PeerSetupId::try_from)
+            .transpose()?;
+
+        Ok(Model {
+            id, carl, ca, auth_config, vpn, update_verification_key, setup_id, request_client_certificate: value.request_client_certificate,
+        })
+    }
+}
+
+conversion! {
+    type Model = crate::peer::setup::PeerSetupId;
+    type Proto = PeerSetupId;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            uuid: Some(value.0.into())
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        extract!(value.uuid)
+            .map(|uuid| crate::peer::setup::PeerSetupId(uuid.into()))
+    }
+}
+
+conversion! {
+    type Model = crate::peer::setup::PeerSetupRecord;
+    type Proto = PeerSetupRecord;
+
+    fn from(value: Model) -> Proto {
+        let unix_millis = |time: SystemTime| time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        Proto {
+            id: Some(value.id.into()),
+            peer_id: Some(value.peer_id.into()),
+            issued_at_unix_millis: unix_millis(value.issued_at),
+            expires_at_unix_millis: unix_millis(value.expires_at),
+            used_at_unix_millis: value.used_at.map(unix_millis),
+            revoked_at_unix_millis: value.revoked_at.map(unix_millis),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let system_time = |unix_millis: u64| SystemTime::UNIX_EPOCH + Duration::from_millis(unix_millis);
+
+        let id = extract!(value.id)?.try_into()?;
+        let peer_id = extract!(value.peer_id)?.try_into()?;
+
+        Ok(Model {
+            id,
+            peer_id,
+            issued_at: system_time(value.issued_at_unix_millis),
+            expires_at: system_time(value.expires_at_unix_millis),
+            used_at: value.used_at_unix_millis.map(system_time),
+            revoked_at: value.revoked_at_unix_millis.map(system_time),
+        })
+    }
+}
+
+conversion! {
+    type Model = crate::peer::certificate::ClientCertificateId;
+    type Proto = ClientCertificateId;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            uuid: Some(value.0.into())
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        extract!(value.uuid)
+            .map(|uuid| crate::peer::certificate::ClientCertificateId(uuid.into()))
+    }
+}
+
+conversion! {
+    type Model = crate::peer::certificate::ClientCertificateRecord;
+    type Proto = ClientCertificateRecord;
+
+    fn from(value: Model) -> Proto {
+        let unix_millis = |time: SystemTime| time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        Proto {
+            id: Some(value.id.into()),
+            peer_id: Some(value.peer_id.into()),
+            issued_at_unix_millis: unix_millis(value.issued_at),
+            expires_at_unix_millis: unix_millis(value.expires_at),
+            revoked_at_unix_millis: value.revoked_at.map(unix_millis),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let system_time = |unix_millis: u64| SystemTime::UNIX_EPOCH + Duration::from_millis(unix_millis);
+
+        let id = extract!(value.id)?.try_into()?;
+        let peer_id = extract!(value.peer_id)?.try_into()?;
+
         Ok(Model {
-            id, carl, ca, auth_config, vpn
+            id,
+            peer_id,
+            issued_at: system_time(value.issued_at_unix_millis),
+            expires_at: system_time(value.expires_at_unix_millis),
+            revoked_at: value.revoked_at_unix_millis.map(system_time),
         })
     }
 }
@@ -305,6 +439,130 @@ conversion! {
     }
 }
 
+conversion! {
+    type Model = crate::peer::decommission::PeerDecommissionRecord;
+    type Proto = PeerDecommissionRecord;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            peer_id: Some(value.peer_id.into()),
+            decommissioned_at_unix_millis: value.decommissioned_at
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let peer_id = extract!(value.peer_id)?.try_into()?;
+
+        Ok(Model {
+            peer_id,
+            decommissioned_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.decommissioned_at_unix_millis),
+        })
+    }
+}
+
+conversion! {
+    type Model = crate::peer::decommission::PeerDecommissionReport;
+    type Proto = PeerDecommissionReport;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            peer_id: Some(value.peer_id.into()),
+            steps: value.steps.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let peer_id = extract!(value.peer_id)?.try_into()?;
+        let steps = value.steps.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Model {
+            peer_id,
+            steps,
+        })
+    }
+}
+
+conversion! {
+    type Model = crate::peer::decommission::PeerDecommissionStep;
+    type Proto = PeerDecommissionStep;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            action: Some(value.action.into()),
+            outcome: Some(value.outcome.into()),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let action = extract!(value.action)?.try_into()?;
+        let outcome = extract!(value.outcome)?.try_into()?;
+
+        Ok(Model {
+            action,
+            outcome,
+        })
+    }
+}
+
+conversion! {
+    type Model = crate::peer::decommission::PeerDecommissionAction;
+    type Proto = PeerDecommissionAction;
+
+    fn from(value: Model) -> Proto {
+        let inner = match value {
+            Model::RevokeSetupStrings => peer_decommission_action::Inner::RevokeSetupStrings(PeerDecommissionActionRevokeSetupStrings {}),
+            Model::DisconnectStream => peer_decommission_action::Inner::DisconnectStream(PeerDecommissionActionDisconnectStream {}),
+            Model::DeleteOidcClients => peer_decommission_action::Inner::DeleteOidcClients(PeerDecommissionActionDeleteOidcClients {}),
+            Model::DeleteVpnPeer => peer_decommission_action::Inner::DeleteVpnPeer(PeerDecommissionActionDeleteVpnPeer {}),
+            Model::RevokeClientCertificates => peer_decommission_action::Inner::RevokeClientCertificates(PeerDecommissionActionRevokeClientCertificates {}),
+        };
+        Proto {
+            inner: Some(inner)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let action = match extract!(value.inner)? {
+            peer_decommission_action::Inner::RevokeSetupStrings(_) => Model::RevokeSetupStrings,
+            peer_decommission_action::Inner::DisconnectStream(_) => Model::DisconnectStream,
+            peer_decommission_action::Inner::DeleteOidcClients(_) => Model::DeleteOidcClients,
+            peer_decommission_action::Inner::DeleteVpnPeer(_) => Model::DeleteVpnPeer,
+            peer_decommission_action::Inner::RevokeClientCertificates(_) => Model::RevokeClientCertificates,
+        };
+        Ok(action)
+    }
+}
+
+conversion! {
+    type Model = crate::peer::decommission::PeerDecommissionOutcome;
+    type Proto = PeerDecommissionOutcome;
+
+    fn from(value: Model) -> Proto {
+        let inner = match value {
+            Model::Done => peer_decommission_outcome::Inner::Done(PeerDecommissionOutcomeDone {}),
+            Model::Skipped { reason } => peer_decommission_outcome::Inner::Skipped(PeerDecommissionOutcomeSkipped { reason }),
+            Model::Failed { cause } => peer_decommission_outcome::Inner::Failed(PeerDecommissionOutcomeFailed { cause }),
+        };
+        Proto {
+            inner: Some(inner)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let outcome = match extract!(value.inner)? {
+            peer_decommission_outcome::Inner::Done(_) => Model::Done,
+            peer_decommission_outcome::Inner::Skipped(PeerDecommissionOutcomeSkipped { reason }) => Model::Skipped { reason },
+            peer_decommission_outcome::Inner::Failed(PeerDecommissionOutcomeFailed { cause }) => Model::Failed { cause },
+        };
+        Ok(outcome)
+    }
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
@@ -0,0 +1,48 @@
+use crate::proto::{conversion, ConversionError, ConversionErrorBuilder, ConversionResult};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.project.rs"));
+
+
+conversion! {
+    type Model = crate::project::ProjectId;
+    type Proto = ProjectId;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            value: value.into(),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        Model::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+conversion! {
+    type Model = crate::project::ProjectAssignment;
+    type Proto = ProjectAssignment;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            owner: Some(value.owner.into()),
+            shared_with: value.shared_with.into_iter()
+                .map(ProjectId::from)
+                .collect(),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let owner = extract!(value.owner)?
+            .try_into()?;
+
+        let shared_with = value.shared_with.into_iter()
+            .map(crate::project::ProjectId::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Model {
+            owner,
+            shared_with,
+        })
+    }
+}
@@ -0,0 +1,75 @@
+use std::str::FromStr;
+
+use crate::proto::{conversion, ConversionError, ConversionErrorBuilder, ConversionResult};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.query.rs"));
+
+
+conversion! {
+    type Model = crate::query::ListQuery;
+    type Proto = ListQuery;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            filter: value.filter.map(|filter| filter.to_string()),
+            sort: value.sort.into_iter()
+                .map(|key| key.to_string())
+                .collect(),
+            page_size: value.page_size,
+            page_token: value.page_token.map(|token| token.to_string()),
+            selector: value.selector.map(|selector| selector.to_string()),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let filter = value.filter
+            .map(|filter| crate::query::Filter::from_str(&filter))
+            .transpose()
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
+        let sort = value.sort.iter()
+            .map(|key| crate::query::SortKey::from_str(key))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
+        let page_token = value.page_token
+            .map(|token| crate::query::PageToken::from_str(&token))
+            .transpose()
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
+        let selector = value.selector
+            .map(|selector| crate::labels::LabelSelector::from_str(&selector))
+            .transpose()
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
+        Ok(Model {
+            filter,
+            selector,
+            sort,
+            page_size: value.page_size,
+            page_token,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[test]
+    fn should_convert_list_query_back_and_forth() -> anyhow::Result<()> {
+        let model = crate::query::ListQuery {
+            filter: Some(crate::query::Filter::from_str("name=edgar-*,tag!=rear")?),
+            selector: Some(crate::labels::LabelSelector::from_str("team=chassis,!deprecated")?),
+            sort: crate::query::SortKey::parse_list("-name")?,
+            page_size: Some(10),
+            page_token: Some(crate::query::PageToken::from_str("20")?),
+        };
+
+        let proto = ListQuery::from(model.clone());
+
+        assert_that!(crate::query::ListQuery::try_from(proto)?, eq(&model));
+        Ok(())
+    }
+}
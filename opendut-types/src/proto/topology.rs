@@ -112,6 +112,7 @@ conversion! {
             description: Some(value.description.into()),
             interface: Some(value.interface.into()),
             tags: value.tags.into_iter().map(|value| value.into()).collect(),
+            labels: Some(value.labels.into()),
         }
     }
 
@@ -130,12 +131,18 @@ conversion! {
             .map(TryFrom::try_from)
             .collect::<Result<_, _>>()?;
 
+        let labels = value.labels
+            .map(TryInto::try_into)
+            .transpose()?
+            .unwrap_or_default();
+
         Ok(Model {
             id: device_id,
             name: device_name,
             description: device_description,
             interface,
             tags: device_tags,
+            labels,
         })
     }
 }
@@ -165,6 +172,7 @@ mod tests {
                     description: DeviceDescription::try_from("Some device").ok(),
                     interface: network_interface_id1.into(),
                     tags: vec![DeviceTag::try_from("tag-1")?, DeviceTag::try_from("tag-2")?],
+                    labels: Default::default(),
                 },
                 crate::topology::DeviceDescriptor {
                     id: Clone::clone(&device_id_2).into(),
@@ -172,6 +180,7 @@ mod tests {
                     description: DeviceDescription::try_from("Some other device").ok(),
                     interface: network_interface_id2.into(),
                     tags: vec![DeviceTag::try_from("tag-2")?],
+                    labels: Default::default(),
                 },
             ],
         };
@@ -199,6 +208,7 @@ mod tests {
                         })
                         .unwrap(),
                     ],
+                    labels: Some(Default::default()),
                 },
                 DeviceDescriptor {
                     id: Some(Clone::clone(&device_id_2).into()),
@@ -215,6 +225,7 @@ mod tests {
                         value: String::from("tag-2"),
                     })
                     .unwrap()],
+                    labels: Some(Default::default()),
                 },
             ],
         };
@@ -253,6 +264,7 @@ mod tests {
                     })
                     .unwrap(),
                 ],
+                labels: Default::default(),
             }],
         };
 
@@ -0,0 +1,238 @@
+use std::time::{Duration, SystemTime};
+
+use crate::proto::{conversion, ConversionError, ConversionErrorBuilder, ConversionResult};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.user.rs"));
+
+
+conversion! {
+    type Model = crate::user::LocalUserId;
+    type Proto = LocalUserId;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            uuid: Some(value.0.into())
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        extract!(value.uuid)
+            .map(|uuid| crate::user::LocalUserId(uuid.into()))
+    }
+}
+
+conversion! {
+    type Model = crate::user::LocalUserName;
+    type Proto = LocalUserName;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            value: value.0
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        Model::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+conversion! {
+    type Model = crate::user::LocalUserRole;
+    type Proto = LocalUserRole;
+
+    fn from(value: Model) -> Proto {
+        let inner = match value {
+            Model::Administrator => local_user_role::Inner::Administrator(LocalUserRoleAdministrator {}),
+            Model::Editor => local_user_role::Inner::Editor(LocalUserRoleEditor {}),
+            Model::Viewer => local_user_role::Inner::Viewer(LocalUserRoleViewer {}),
+        };
+        Proto {
+            inner: Some(inner)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let role = match extract!(value.inner)? {
+            local_user_role::Inner::Administrator(_) => Model::Administrator,
+            local_user_role::Inner::Editor(_) => Model::Editor,
+            local_user_role::Inner::Viewer(_) => Model::Viewer,
+        };
+        Ok(role)
+    }
+}
+
+conversion! {
+    type Model = crate::user::LocalUser;
+    type Proto = LocalUser;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            id: Some(value.id.into()),
+            name: Some(value.name.into()),
+            roles: value.roles.into_iter().map(Into::into).collect(),
+            secret_hash: value.secret_hash.0,
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let id = extract!(value.id)?.try_into()?;
+        let name = extract!(value.name)?.try_into()?;
+        let roles = value.roles.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Model {
+            id,
+            name,
+            roles,
+            secret_hash: crate::user::LocalUserSecretHash(value.secret_hash),
+        })
+    }
+}
+
+conversion! {
+    type Model = crate::user::token::PersonalAccessTokenId;
+    type Proto = PersonalAccessTokenId;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            uuid: Some(value.0.into())
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        extract!(value.uuid)
+            .map(|uuid| crate::user::token::PersonalAccessTokenId(uuid.into()))
+    }
+}
+
+conversion! {
+    type Model = crate::user::token::PersonalAccessTokenName;
+    type Proto = PersonalAccessTokenName;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            value: value.0
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        Model::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+conversion! {
+    type Model = crate::user::token::PersonalAccessTokenScope;
+    type Proto = PersonalAccessTokenScope;
+
+    fn from(value: Model) -> Proto {
+        let inner = match value {
+            Model::Administrator => personal_access_token_scope::Inner::Administrator(PersonalAccessTokenScopeAdministrator {}),
+            Model::Editor => personal_access_token_scope::Inner::Editor(PersonalAccessTokenScopeEditor {}),
+            Model::Viewer => personal_access_token_scope::Inner::Viewer(PersonalAccessTokenScopeViewer {}),
+        };
+        Proto {
+            inner: Some(inner)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let scope = match extract!(value.inner)? {
+            personal_access_token_scope::Inner::Administrator(_) => Model::Administrator,
+            personal_access_token_scope::Inner::Editor(_) => Model::Editor,
+            personal_access_token_scope::Inner::Viewer(_) => Model::Viewer,
+        };
+        Ok(scope)
+    }
+}
+
+conversion! {
+    type Model = crate::user::token::PersonalAccessTokenDescriptor;
+    type Proto = PersonalAccessTokenDescriptor;
+
+    fn from(value: Model) -> Proto {
+        let unix_millis = |time: SystemTime| time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        Proto {
+            id: Some(value.id.into()),
+            name: Some(value.name.into()),
+            owner: value.owner,
+            scopes: value.scopes.into_iter().map(Into::into).collect(),
+            created_at_unix_millis: unix_millis(value.created_at),
+            expires_at_unix_millis: unix_millis(value.expires_at),
+            revoked_at_unix_millis: value.revoked_at.map(unix_millis),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let system_time = |unix_millis: u64| SystemTime::UNIX_EPOCH + Duration::from_millis(unix_millis);
+
+        let id = extract!(value.id)?.try_into()?;
+        let name = extract!(value.name)?.try_into()?;
+        let scopes = value.scopes.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Model {
+            id,
+            name,
+            owner: value.owner,
+            scopes,
+            created_at: system_time(value.created_at_unix_millis),
+            expires_at: system_time(value.expires_at_unix_millis),
+            revoked_at: value.revoked_at_unix_millis.map(system_time),
+        })
+    }
+}
+
+conversion! {
+    type Model = crate::user::token::PersonalAccessToken;
+    type Proto = PersonalAccessToken;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            descriptor: Some(value.descriptor.into()),
+            secret_hash: value.secret_hash.0,
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let descriptor = extract!(value.descriptor)?.try_into()?;
+
+        Ok(Model {
+            descriptor,
+            secret_hash: crate::user::token::PersonalAccessTokenSecretHash(value.secret_hash),
+        })
+    }
+}
+
+conversion! {
+    type Model = crate::user::token::PersonalAccessTokenOwner;
+    type Proto = PersonalAccessTokenOwner;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            owner: value.owner,
+            roles: value.roles,
+            groups: value.groups,
+            recorded_at_unix_millis: value.recorded_at
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        Ok(Model {
+            owner: value.owner,
+            roles: value.roles,
+            groups: value.groups,
+            recorded_at: SystemTime::UNIX_EPOCH + Duration::from_millis(value.recorded_at_unix_millis),
+        })
+    }
+}
@@ -179,6 +179,31 @@ conversion! {
     }
 }
 
+conversion! {
+    type Model = crate::util::net::ClientCertificate;
+    type Proto = ClientCertificate;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            certificate: Some(value.certificate.into()),
+            key: Some(ClientCertificateKey {
+                tag: value.key.0.tag().to_owned(),
+                content: Vec::from(value.key.0.contents()),
+            }),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let certificate = extract!(value.certificate)?.try_into()?;
+        let key = extract!(value.key)?;
+
+        Ok(Model {
+            certificate,
+            key: util::net::ClientCertificateKey(Pem::new(key.tag, key.content)),
+        })
+    }
+}
+
 conversion! {
     type Model = crate::util::net::NetworkInterfaceDescriptor;
     type Proto = NetworkInterfaceDescriptor;
@@ -1,3 +1,5 @@
+use std::time::{Duration, SystemTime};
+
 use crate::proto::{conversion, ConversionError, ConversionErrorBuilder, ConversionResult};
 
 include!(concat!(env!("OUT_DIR"), "/opendut.types.vpn.rs"));
@@ -26,6 +28,18 @@ conversion! {
                     ))
                 }
             }
+            crate::vpn::VpnPeerConfiguration::WireGuard { private_key, address, prefix_length, listen_port } => {
+                VpnPeerConfig {
+                    config: Some(vpn_peer_config::Config::Wireguard(
+                        VpnPeerConfigWireGuard {
+                            private_key: Some(private_key.into()),
+                            address: Some(address.into()),
+                            prefix_length: u32::from(prefix_length),
+                            listen_port: Some(listen_port.into()),
+                        }
+                    ))
+                }
+            }
         }
     }
 
@@ -47,6 +61,22 @@ conversion! {
                     setup_key,
                 }
             },
+            vpn_peer_config::Config::Wireguard(config) => {
+                let VpnPeerConfigWireGuard { private_key, address, prefix_length, listen_port } = config;
+
+                let private_key = extract!(private_key)?.try_into()?;
+                let address = extract!(address)?.try_into()?;
+                let prefix_length = u8::try_from(prefix_length)
+                    .map_err(|_| ErrorBuilder::message(format!("Prefix length {prefix_length} is out of range")))?;
+                let listen_port = extract!(listen_port)?.try_into()?;
+
+                crate::vpn::VpnPeerConfiguration::WireGuard {
+                    private_key,
+                    address,
+                    prefix_length,
+                    listen_port,
+                }
+            },
         };
 
         Ok(result)
@@ -69,3 +99,140 @@ conversion! {
         Ok(result)
     }
 }
+
+conversion! {
+    type Model = crate::vpn::wireguard::PrivateKey;
+    type Proto = WireGuardKey;
+
+    fn from(value: Model) -> Proto {
+        Proto { value: value.as_bytes().to_vec() }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        crate::vpn::wireguard::PrivateKey::try_from(value.value.as_slice())
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+conversion! {
+    type Model = crate::vpn::wireguard::PublicKey;
+    type Proto = WireGuardKey;
+
+    fn from(value: Model) -> Proto {
+        Proto { value: value.as_bytes().to_vec() }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        crate::vpn::wireguard::PublicKey::try_from(value.value.as_slice())
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+conversion! {
+    type Model = crate::vpn::reconciliation::VpnReconciliationReport;
+    type Proto = VpnReconciliationReport;
+
+    fn from(value: Model) -> Proto {
+        let timestamp_unix_millis = value.timestamp
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        Proto {
+            timestamp_unix_millis,
+            findings: value.findings.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let timestamp = SystemTime::UNIX_EPOCH + Duration::from_millis(value.timestamp_unix_millis);
+        let findings = value.findings.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Model {
+            timestamp,
+            findings,
+        })
+    }
+}
+
+conversion! {
+    type Model = crate::vpn::reconciliation::VpnReconciliationFinding;
+    type Proto = VpnReconciliationFinding;
+
+    fn from(value: Model) -> Proto {
+        Proto {
+            mismatch: Some(value.mismatch.into()),
+            outcome: Some(value.outcome.into()),
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let mismatch = extract!(value.mismatch)?.try_into()?;
+        let outcome = extract!(value.outcome)?.try_into()?;
+
+        Ok(Model {
+            mismatch,
+            outcome,
+        })
+    }
+}
+
+conversion! {
+    type Model = crate::vpn::reconciliation::VpnMismatch;
+    type Proto = VpnMismatch;
+
+    fn from(value: Model) -> Proto {
+        let inner = match value {
+            Model::MissingPeer { peer_id } => vpn_mismatch::Inner::MissingPeer(VpnMismatchMissingPeer { peer_id: Some(peer_id.into()) }),
+            Model::OrphanedPeer { peer_id } => vpn_mismatch::Inner::OrphanedPeer(VpnMismatchOrphanedPeer { peer_id: Some(peer_id.into()) }),
+            Model::PeerWithoutDevice { peer_id } => vpn_mismatch::Inner::PeerWithoutDevice(VpnMismatchPeerWithoutDevice { peer_id: Some(peer_id.into()) }),
+            Model::MissingCluster { cluster_id } => vpn_mismatch::Inner::MissingCluster(VpnMismatchMissingCluster { cluster_id: Some(cluster_id.into()) }),
+            Model::OutdatedCluster { cluster_id } => vpn_mismatch::Inner::OutdatedCluster(VpnMismatchOutdatedCluster { cluster_id: Some(cluster_id.into()) }),
+            Model::OrphanedCluster { cluster_id } => vpn_mismatch::Inner::OrphanedCluster(VpnMismatchOrphanedCluster { cluster_id: Some(cluster_id.into()) }),
+            Model::StaleSetupKey { name } => vpn_mismatch::Inner::StaleSetupKey(VpnMismatchStaleSetupKey { name }),
+        };
+        Proto {
+            inner: Some(inner)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let mismatch = match extract!(value.inner)? {
+            vpn_mismatch::Inner::MissingPeer(VpnMismatchMissingPeer { peer_id }) => Model::MissingPeer { peer_id: extract!(peer_id)?.try_into()? },
+            vpn_mismatch::Inner::OrphanedPeer(VpnMismatchOrphanedPeer { peer_id }) => Model::OrphanedPeer { peer_id: extract!(peer_id)?.try_into()? },
+            vpn_mismatch::Inner::PeerWithoutDevice(VpnMismatchPeerWithoutDevice { peer_id }) => Model::PeerWithoutDevice { peer_id: extract!(peer_id)?.try_into()? },
+            vpn_mismatch::Inner::MissingCluster(VpnMismatchMissingCluster { cluster_id }) => Model::MissingCluster { cluster_id: extract!(cluster_id)?.try_into()? },
+            vpn_mismatch::Inner::OutdatedCluster(VpnMismatchOutdatedCluster { cluster_id }) => Model::OutdatedCluster { cluster_id: extract!(cluster_id)?.try_into()? },
+            vpn_mismatch::Inner::OrphanedCluster(VpnMismatchOrphanedCluster { cluster_id }) => Model::OrphanedCluster { cluster_id: extract!(cluster_id)?.try_into()? },
+            vpn_mismatch::Inner::StaleSetupKey(VpnMismatchStaleSetupKey { name }) => Model::StaleSetupKey { name },
+        };
+        Ok(mismatch)
+    }
+}
+
+conversion! {
+    type Model = crate::vpn::reconciliation::VpnReconciliationOutcome;
+    type Proto = VpnReconciliationOutcome;
+
+    fn from(value: Model) -> Proto {
+        let inner = match value {
+            Model::Repaired => vpn_reconciliation_outcome::Inner::Repaired(VpnReconciliationOutcomeRepaired {}),
+            Model::Unrepaired => vpn_reconciliation_outcome::Inner::Unrepaired(VpnReconciliationOutcomeUnrepaired {}),
+            Model::Failed { cause } => vpn_reconciliation_outcome::Inner::Failed(VpnReconciliationOutcomeFailed { cause }),
+        };
+        Proto {
+            inner: Some(inner)
+        }
+    }
+
+    fn try_from(value: Proto) -> ConversionResult<Model> {
+        let outcome = match extract!(value.inner)? {
+            vpn_reconciliation_outcome::Inner::Repaired(_) => Model::Repaired,
+            vpn_reconciliation_outcome::Inner::Unrepaired(_) => Model::Unrepaired,
+            vpn_reconciliation_outcome::Inner::Failed(VpnReconciliationOutcomeFailed { cause }) => Model::Failed { cause },
+        };
+        Ok(outcome)
+    }
+}
@@ -0,0 +1,511 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use glob::{MatchOptions, Pattern};
+
+use crate::cluster::ClusterConfiguration;
+use crate::labels::{LabelSelector, Labels};
+use crate::peer::PeerDescriptor;
+use crate::topology::DeviceDescriptor;
+
+/// Query for listing resources.
+/// Filtering, sorting and pagination are evaluated in CARL, so only the requested page is transferred.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListQuery {
+    pub filter: Option<Filter>,
+    /// Only resources with labels matching the selector are listed.
+    pub selector: Option<LabelSelector>,
+    /// Resources are sorted by these keys, in order of precedence, and finally by their ID.
+    pub sort: Vec<SortKey>,
+    /// Maximum number of resources in a page. All resources are returned, if not set.
+    pub page_size: Option<u32>,
+    /// Token of the page to return, as received with the previous page. The first page is returned, if not set.
+    pub page_token: Option<PageToken>,
+}
+
+impl ListQuery {
+    /// Filters, sorts and paginates the given resources.
+    pub fn apply<T: Queryable>(&self, resources: impl IntoIterator<Item=T>) -> Result<Page<T>, QueryError> {
+        let fields = self.filter.iter()
+            .flat_map(|filter| filter.predicates.iter().map(|predicate| predicate.field))
+            .chain(self.sort.iter().map(|key| key.field));
+        for field in fields {
+            if !T::FIELDS.contains(&field) {
+                return Err(QueryError::UnsupportedField { kind: T::KIND, field, supported: T::FIELDS });
+            }
+        }
+
+        let mut resources = resources.into_iter()
+            .filter(|resource| self.filter.as_ref().is_none_or(|filter| filter.matches(resource)))
+            .filter(|resource| self.selector.as_ref().is_none_or(|selector| selector.matches(resource.labels())))
+            .map(|resource| {
+                let keys = self.sort.iter()
+                    .map(|key| sort_value(&resource, key.field))
+                    .collect::<Vec<_>>();
+                let id = sort_value(&resource, QueryField::Id);
+                (keys, id, resource)
+            })
+            .collect::<Vec<_>>();
+
+        resources.sort_by(|(keys_a, id_a, _), (keys_b, id_b, _)| {
+            self.sort.iter().zip(keys_a.iter().zip(keys_b))
+                .map(|(key, (a, b))| if key.descending { b.cmp(a) } else { a.cmp(b) })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| id_a.cmp(id_b))
+        });
+
+        let offset = self.page_token.map(|token| token.offset).unwrap_or_default();
+        let total = resources.len();
+        let resources = resources.into_iter()
+            .skip(offset)
+            .map(|(_, _, resource)| resource);
+
+        let page = match self.page_size {
+            Some(page_size) => {
+                let page_size = usize::try_from(page_size).unwrap_or(usize::MAX).max(1);
+                let items = resources.take(page_size).collect();
+                let next_offset = offset.saturating_add(page_size);
+                let next_page_token = (next_offset < total).then_some(PageToken { offset: next_offset });
+                Page { items, next_page_token }
+            }
+            None => Page { items: resources.collect(), next_page_token: None },
+        };
+        Ok(page)
+    }
+}
+
+fn sort_value<T: Queryable>(resource: &T, field: QueryField) -> String {
+    resource.values(field).join(",").to_lowercase()
+}
+
+/// A page of resources as result of a [`ListQuery`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Token for requesting the next page. Not set, if this is the last page.
+    pub next_page_token: Option<PageToken>,
+}
+
+/// A resource, which can be filtered and sorted by a [`ListQuery`].
+pub trait Queryable {
+    /// Name of the kind of resource, used in error messages.
+    const KIND: &'static str;
+    /// Fields which can be used in filters and sort keys.
+    const FIELDS: &'static [QueryField];
+
+    /// Returns the values of the field. Empty, if the resource has no value for the field.
+    fn values(&self, field: QueryField) -> Vec<String>;
+
+    /// Labels, which are matched by the label selector of a [`ListQuery`].
+    fn labels(&self) -> &Labels;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QueryField {
+    Id,
+    Name,
+    Description,
+    Location,
+    Tag,
+    State,
+    /// Project, which the resource belongs to or is shared with.
+    Project,
+}
+
+impl QueryField {
+    pub const ALL: [QueryField; 7] = [QueryField::Id, QueryField::Name, QueryField::Description, QueryField::Location, QueryField::Tag, QueryField::State, QueryField::Project];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueryField::Id => "id",
+            QueryField::Name => "name",
+            QueryField::Description => "description",
+            QueryField::Location => "location",
+            QueryField::Tag => "tag",
+            QueryField::State => "state",
+            QueryField::Project => "project",
+        }
+    }
+}
+
+impl fmt::Display for QueryField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for QueryField {
+    type Err = QueryParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        QueryField::ALL.into_iter()
+            .find(|field| field.as_str().eq_ignore_ascii_case(value))
+            .ok_or_else(|| QueryParseError::UnknownField { field: value.to_owned() })
+    }
+}
+
+/// Conjunction of predicates, written as comma-separated list, e.g. `name=edgar-*,state!=offline`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filter {
+    pub predicates: Vec<Predicate>,
+}
+
+impl Filter {
+    pub fn matches<T: Queryable>(&self, resource: &T) -> bool {
+        self.predicates.iter()
+            .all(|predicate| predicate.matches(&resource.values(predicate.field)))
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let predicates = self.predicates.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "{predicates}")
+    }
+}
+
+impl FromStr for Filter {
+    type Err = QueryParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let predicates = value.split(',')
+            .map(Predicate::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Filter { predicates })
+    }
+}
+
+/// Compares the values of a field with a case-insensitive glob pattern, e.g. `name=edgar-*`.
+/// With `!=`, the predicate matches, if none of the values match the pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Predicate {
+    pub field: QueryField,
+    pub negated: bool,
+    pub pattern: Pattern,
+}
+
+impl Predicate {
+    const MATCH_OPTIONS: MatchOptions = MatchOptions {
+        case_sensitive: false,
+        require_literal_separator: false,
+        require_literal_leading_dot: false,
+    };
+
+    fn matches(&self, values: &[String]) -> bool {
+        let matched = values.iter()
+            .any(|value| self.pattern.matches_with(value, Self::MATCH_OPTIONS));
+        matched != self.negated
+    }
+}
+
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = if self.negated { "!=" } else { "=" };
+        write!(f, "{}{operator}{}", self.field, self.pattern.as_str())
+    }
+}
+
+impl FromStr for Predicate {
+    type Err = QueryParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (field, negated, pattern) = if let Some((field, pattern)) = value.split_once("!=") {
+            (field, true, pattern)
+        } else if let Some((field, pattern)) = value.split_once('=') {
+            (field, false, pattern)
+        } else {
+            return Err(QueryParseError::MissingOperator { predicate: value.trim().to_owned() });
+        };
+
+        let field = QueryField::from_str(field)?;
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Err(QueryParseError::EmptyPattern { field });
+        }
+        let pattern = Pattern::new(pattern)
+            .map_err(|cause| QueryParseError::InvalidPattern { pattern: pattern.to_owned(), cause: cause.msg })?;
+
+        Ok(Predicate { field, negated, pattern })
+    }
+}
+
+/// Key for sorting resources, written as field name, optionally prefixed with `-` for descending order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: QueryField,
+    pub descending: bool,
+}
+
+impl SortKey {
+    /// Parses a comma-separated list of sort keys, e.g. `location,-name`.
+    pub fn parse_list(value: &str) -> Result<Vec<SortKey>, QueryParseError> {
+        value.split(',')
+            .map(SortKey::from_str)
+            .collect()
+    }
+
+    pub fn format_list(keys: &[SortKey]) -> String {
+        keys.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = if self.descending { "-" } else { "" };
+        write!(f, "{direction}{}", self.field)
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = QueryParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (field, descending) = match value.strip_prefix('-') {
+            Some(field) => (field, true),
+            None => (value, false),
+        };
+        let field = QueryField::from_str(field)?;
+        Ok(SortKey { field, descending })
+    }
+}
+
+/// Opaque token for requesting the next page of a [`ListQuery`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageToken {
+    offset: usize,
+}
+
+impl fmt::Display for PageToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.offset)
+    }
+}
+
+impl FromStr for PageToken {
+    type Err = QueryParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let offset = usize::from_str(value)
+            .map_err(|_| QueryParseError::InvalidPageToken { token: value.to_owned() })?;
+        Ok(PageToken { offset })
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+pub enum QueryParseError {
+    #[error("Unknown field '{field}'. Expected one of: {}", QueryField::ALL.map(|field| field.as_str()).join(", "))]
+    UnknownField { field: String },
+    #[error("Predicate '{predicate}' has no operator. Expected '<field>=<pattern>' or '<field>!=<pattern>'.")]
+    MissingOperator { predicate: String },
+    #[error("Predicate on field '{field}' has an empty pattern.")]
+    EmptyPattern { field: QueryField },
+    #[error("Invalid pattern '{pattern}': {cause}")]
+    InvalidPattern { pattern: String, cause: &'static str },
+    #[error("Invalid page token '{token}'.")]
+    InvalidPageToken { token: String },
+}
+
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+pub enum QueryError {
+    #[error(
+        "Field '{field}' is not supported for {kind}s. Supported fields: {supported}",
+        supported = supported.iter().map(QueryField::as_str).collect::<Vec<_>>().join(", "),
+    )]
+    UnsupportedField { kind: &'static str, field: QueryField, supported: &'static [QueryField] },
+}
+
+impl Queryable for PeerDescriptor {
+    const KIND: &'static str = "peer";
+    const FIELDS: &'static [QueryField] = &[QueryField::Id, QueryField::Name, QueryField::Location, QueryField::Project];
+
+    fn values(&self, field: QueryField) -> Vec<String> {
+        match field {
+            QueryField::Id => vec![self.id.to_string()],
+            QueryField::Name => vec![self.name.to_string()],
+            QueryField::Location => self.location.iter().map(ToString::to_string).collect(),
+            QueryField::Project => self.project.projects().map(ToString::to_string).collect(),
+            QueryField::Description
+            | QueryField::Tag
+            | QueryField::State => vec![],
+        }
+    }
+
+    fn labels(&self) -> &Labels {
+        &self.labels
+    }
+}
+
+impl Queryable for DeviceDescriptor {
+    const KIND: &'static str = "device";
+    const FIELDS: &'static [QueryField] = &[QueryField::Id, QueryField::Name, QueryField::Description, QueryField::Tag];
+
+    fn values(&self, field: QueryField) -> Vec<String> {
+        match field {
+            QueryField::Id => vec![self.id.to_string()],
+            QueryField::Name => vec![self.name.to_string()],
+            QueryField::Description => self.description.iter().map(ToString::to_string).collect(),
+            QueryField::Tag => self.tags.iter().map(ToString::to_string).collect(),
+            QueryField::Location
+            | QueryField::State
+            | QueryField::Project => vec![],
+        }
+    }
+
+    fn labels(&self) -> &Labels {
+        &self.labels
+    }
+}
+
+impl Queryable for ClusterConfiguration {
+    const KIND: &'static str = "cluster configuration";
+    const FIELDS: &'static [QueryField] = &[QueryField::Id, QueryField::Name, QueryField::Project];
+
+    fn values(&self, field: QueryField) -> Vec<String> {
+        match field {
+            QueryField::Id => vec![self.id.to_string()],
+            QueryField::Name => vec![self.name.to_string()],
+            QueryField::Project => vec![self.project.to_string()],
+            QueryField::Description
+            | QueryField::Location
+            | QueryField::Tag
+            | QueryField::State => vec![],
+        }
+    }
+
+    fn labels(&self) -> &Labels {
+        &self.labels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use rstest::rstest;
+    use crate::topology::{DeviceId, DeviceName, DeviceTag};
+    use crate::util::net::NetworkInterfaceId;
+
+    #[rstest]
+    #[case("name=edgar-*", "edgar-1", true)]
+    #[case("name=EDGAR-*", "edgar-1", true)]
+    #[case("name=edgar-*", "carl", false)]
+    #[case("name!=edgar-*", "carl", true)]
+    #[case("tag=front", "edgar-1", true)]
+    #[case("tag!=rear", "edgar-1", true)]
+    #[case("description=*", "edgar-1", false)]
+    #[case("description!=*", "edgar-1", true)]
+    #[case("name=edgar-?,tag=left", "edgar-1", true)]
+    #[case("name=edgar-?,tag=right", "edgar-1", false)]
+    fn should_evaluate_filter(#[case] filter: &str, #[case] name: &str, #[case] expected: bool) -> anyhow::Result<()> {
+        let filter = Filter::from_str(filter)?;
+        let device = device(name, &["front", "left"])?;
+
+        assert_that!(filter.matches(&device), eq(expected));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("name")]
+    #[case("name~foo")]
+    #[case("color=red")]
+    #[case("name=")]
+    #[case("name=foo,")]
+    #[case("name=[")]
+    fn should_reject_invalid_filter(#[case] filter: &str) {
+        assert_that!(Filter::from_str(filter), err(anything()));
+    }
+
+    #[test]
+    fn should_format_filter_and_sort_keys_as_parsed() -> anyhow::Result<()> {
+        let filter = "name=edgar-*,tag!=rear";
+        assert_that!(Filter::from_str(filter)?.to_string(), eq(filter));
+
+        let sort = "tag,-name";
+        assert_that!(SortKey::format_list(&SortKey::parse_list(sort)?), eq(sort));
+        Ok(())
+    }
+
+    #[test]
+    fn should_sort_and_paginate() -> anyhow::Result<()> {
+        let devices = vec![
+            device("b", &["x"])?,
+            device("c", &["y"])?,
+            device("a", &["y"])?,
+            device("d", &["x"])?,
+        ];
+
+        let mut query = ListQuery {
+            filter: None,
+            selector: None,
+            sort: SortKey::parse_list("-tag,name")?,
+            page_size: Some(3),
+            page_token: None,
+        };
+
+        let first = query.apply(devices.clone())?;
+        let names = first.items.iter().map(|device| device.name.to_string()).collect::<Vec<_>>();
+        assert_that!(names, elements_are![eq("a"), eq("c"), eq("b")]);
+        assert_that!(first.next_page_token, some(anything()));
+
+        query.page_token = first.next_page_token;
+        let second = query.apply(devices)?;
+        let names = second.items.iter().map(|device| device.name.to_string()).collect::<Vec<_>>();
+        assert_that!(names, elements_are![eq("d")]);
+        assert_that!(second.next_page_token, none());
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_fields_not_supported_by_the_resource() -> anyhow::Result<()> {
+        let query = ListQuery {
+            filter: Some(Filter::from_str("location=berlin")?),
+            ..Default::default()
+        };
+
+        let error = query.apply(vec![device("a", &[])?]).unwrap_err();
+
+        assert_that!(error.to_string(), contains_substring("'location' is not supported for devices"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_select_resources_by_labels() -> anyhow::Result<()> {
+        let mut chassis = device("a", &[])?;
+        chassis.labels = Labels::from_str("team=chassis,site=berlin")?;
+        let mut body = device("b", &[])?;
+        body.labels = Labels::from_str("team=body")?;
+
+        let query = ListQuery {
+            selector: Some(LabelSelector::from_str("team=chassis,site!=munich")?),
+            ..Default::default()
+        };
+
+        let page = query.apply(vec![chassis.clone(), body])?;
+
+        assert_that!(page.items, elements_are![eq(&chassis)]);
+        Ok(())
+    }
+
+    fn device(name: &str, tags: &[&str]) -> anyhow::Result<DeviceDescriptor> {
+        Ok(DeviceDescriptor {
+            id: DeviceId::random(),
+            name: DeviceName::try_from(name)?,
+            description: None,
+            interface: NetworkInterfaceId::random(),
+            tags: tags.iter()
+                .map(|tag| DeviceTag::try_from(*tag))
+                .collect::<Result<_, _>>()?,
+            labels: Default::default(),
+        })
+    }
+}
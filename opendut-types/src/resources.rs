@@ -47,3 +47,27 @@ impl From<Uuid> for Id {
         }
     }
 }
+
+/// Revision of a resource stored in CARL. It changes with every modification of the resource,
+/// which allows clients to detect that a resource was modified since they last read it.
+#[derive(Copy, Debug, Clone, Default, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Revision(pub u64);
+
+impl fmt::Display for Revision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for Revision {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Revision> for u64 {
+    fn from(value: Revision) -> Self {
+        value.0
+    }
+}
@@ -1,15 +1,100 @@
-use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::cluster::ClusterConfiguration;
+use crate::specs::ReferenceSpecification;
+
 #[derive(Debug)]
 pub enum ClusterConfigurationSpecification {
-    V1(ClusterConfigurationSpecificationV1)
+    V1(ClusterConfigurationSpecificationV1),
+    V2(ClusterConfigurationSpecificationV2),
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub struct ClusterConfigurationSpecificationV1 {
     #[serde(default)]
     pub leader_id: Uuid,
     pub devices: Vec<Uuid>
 }
+
+impl From<ClusterConfiguration> for ClusterConfigurationSpecificationV1 {
+    fn from(configuration: ClusterConfiguration) -> Self {
+        let mut devices = configuration.devices.into_iter()
+            .map(|device| device.0)
+            .collect::<Vec<_>>();
+        devices.sort(); //HashSet has no stable order
+
+        Self {
+            leader_id: configuration.leader.uuid,
+            devices,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all="kebab-case", deny_unknown_fields)]
+pub struct ClusterConfigurationSpecificationV2 {
+    /// Name or ID of the peer, which leads the cluster.
+    pub leader: ReferenceSpecification,
+    /// Names or IDs of the devices, which are part of the cluster.
+    #[serde(default)]
+    pub devices: Vec<ReferenceSpecification>,
+    /// Label selector, e.g. `team=chassis,site!=munich`. Devices with matching labels are added to the cluster, when it is applied.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub device_selector: Option<String>,
+    /// Free-form key-value labels, e.g. `team: chassis`, which can be used to select cluster configurations.
+    #[serde(default, skip_serializing_if="BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// ID of the project, which the cluster belongs to, e.g. `chassis`. Clusters without a project belong to the `default` project.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub project: Option<String>,
+}
+
+impl ClusterConfigurationSpecification {
+    /// Upgrades the specification to the latest version.
+    pub fn into_latest(self) -> ClusterConfigurationSpecificationV2 {
+        match self {
+            ClusterConfigurationSpecification::V1(specification) => ClusterConfigurationSpecificationV2::from(specification),
+            ClusterConfigurationSpecification::V2(specification) => specification,
+        }
+    }
+}
+
+impl From<ClusterConfigurationSpecificationV1> for ClusterConfigurationSpecificationV2 {
+    fn from(specification: ClusterConfigurationSpecificationV1) -> Self {
+        Self {
+            leader: ReferenceSpecification::Id(specification.leader_id),
+            devices: specification.devices.into_iter()
+                .map(ReferenceSpecification::Id)
+                .collect(),
+            device_selector: None,
+            labels: BTreeMap::new(),
+            project: None,
+        }
+    }
+}
+
+impl From<ClusterConfiguration> for ClusterConfigurationSpecificationV2 {
+    fn from(configuration: ClusterConfiguration) -> Self {
+        let mut devices = configuration.devices.into_iter()
+            .map(|device| device.0)
+            .collect::<Vec<_>>();
+        devices.sort(); //HashSet has no stable order
+        let devices = devices.into_iter()
+            .map(ReferenceSpecification::Id)
+            .collect();
+
+        Self {
+            leader: ReferenceSpecification::Id(configuration.leader.uuid),
+            devices,
+            device_selector: configuration.device_selector.map(String::from),
+            labels: configuration.labels.into(),
+            project: (!configuration.project.is_default()).then(|| configuration.project.into()),
+        }
+    }
+}
@@ -2,11 +2,16 @@
 pub mod cluster;
 pub mod parse;
 pub mod peer;
+#[cfg(feature = "specs-schema")]
+pub mod schema;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use strum::Display;
 use uuid::Uuid;
 
+use crate::cluster::ClusterConfiguration;
+use crate::peer::PeerDescriptor;
+
 #[derive(Debug)]
 pub struct SpecificationDocument {
     pub version: String,
@@ -14,13 +19,26 @@ pub struct SpecificationDocument {
     pub spec: Specification,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 pub struct SpecificationMetadata {
-    pub id: Uuid,
+    /// ID of the resource. If omitted, the ID of the existing resource with the same name is used,
+    /// or a new ID is generated, if no such resource exists.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub id: Option<Uuid>,
     pub name: String,
 }
 
-#[allow(dead_code)]
+/// Reference to another resource, either by its ID or by its name.
+/// A value which can be parsed as UUID is treated as ID.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum ReferenceSpecification {
+    Id(Uuid),
+    Name(String),
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Display)]
 #[serde(rename_all = "camelCase")]
 pub enum SpecificationVersion {
@@ -28,8 +46,52 @@ pub enum SpecificationVersion {
     V2,
 }
 
+impl SpecificationVersion {
+    /// Version in which specifications are written, e.g. when exporting resources.
+    pub const LATEST: SpecificationVersion = SpecificationVersion::V2;
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpecificationVersion::V1 => "v1",
+            SpecificationVersion::V2 => "v2",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Specification {
     PeerDescriptorSpecification(peer::PeerDescriptorSpecification),
     ClusterConfigurationSpecification(cluster::ClusterConfigurationSpecification),
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpgradeSpecificationError {
+    #[error("Failed to upgrade specification. Parameters for the {subject} <{id}> were not provided.")]
+    MissingParameters { subject: &'static str, id: Uuid },
+}
+
+impl From<PeerDescriptor> for SpecificationDocument {
+    fn from(peer: PeerDescriptor) -> Self {
+        SpecificationDocument {
+            version: String::from(SpecificationVersion::LATEST.as_str()),
+            metadata: SpecificationMetadata {
+                id: Some(peer.id.uuid),
+                name: peer.name.clone().value(),
+            },
+            spec: Specification::PeerDescriptorSpecification(peer::PeerDescriptorSpecification::V2(peer.into())),
+        }
+    }
+}
+
+impl From<ClusterConfiguration> for SpecificationDocument {
+    fn from(configuration: ClusterConfiguration) -> Self {
+        SpecificationDocument {
+            version: String::from(SpecificationVersion::LATEST.as_str()),
+            metadata: SpecificationMetadata {
+                id: Some(configuration.id.0),
+                name: configuration.name.clone().value(),
+            },
+            spec: Specification::ClusterConfigurationSpecification(cluster::ClusterConfigurationSpecification::V2(configuration.into())),
+        }
+    }
+}
@@ -1,9 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use crate::specs::parse::{ParseSpecificationError, ResourceKind};
 use crate::specs::{self, Specification, SpecificationDocument, SpecificationMetadata, SpecificationVersion};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct JsonSpecificationDocument {
     pub kind: String,
     pub version: String,
@@ -62,6 +62,32 @@ impl TryFrom<JsonSpecificationDocument> for SpecificationDocument {
     }
 }
 
+impl TryFrom<SpecificationDocument> for JsonSpecificationDocument {
+    type Error = serde_json::Error;
+    fn try_from(document: SpecificationDocument) -> Result<Self, Self::Error> {
+        let (kind, spec) = match document.spec {
+            Specification::PeerDescriptorSpecification(specs::peer::PeerDescriptorSpecification::V1(spec)) => {
+                (ResourceKind::PeerDescriptor, serde_json::to_value(spec)?)
+            }
+            Specification::PeerDescriptorSpecification(specs::peer::PeerDescriptorSpecification::V2(spec)) => {
+                (ResourceKind::PeerDescriptor, serde_json::to_value(spec)?)
+            }
+            Specification::ClusterConfigurationSpecification(specs::cluster::ClusterConfigurationSpecification::V1(spec)) => {
+                (ResourceKind::ClusterConfiguration, serde_json::to_value(spec)?)
+            }
+            Specification::ClusterConfigurationSpecification(specs::cluster::ClusterConfigurationSpecification::V2(spec)) => {
+                (ResourceKind::ClusterConfiguration, serde_json::to_value(spec)?)
+            }
+        };
+        Ok(JsonSpecificationDocument {
+            kind: kind.to_string(),
+            version: document.version,
+            metadata: document.metadata,
+            spec,
+        })
+    }
+}
+
 fn parse_kind(s: &str) -> Result<ResourceKind, ParseSpecificationError> {
     serde_json::from_str::<ResourceKind>(s)
         .map_err(|_| ParseSpecificationError::IllegalResourceKind { kind: String::from(s) })
@@ -79,16 +105,20 @@ fn parse_spec(kind: ResourceKind, version: SpecificationVersion, spec: Value) ->
                 .map_err(|cause| ParseSpecificationError::IllegalJsonSpecification { cause } )?;
             Ok(Specification::ClusterConfigurationSpecification(specs::cluster::ClusterConfigurationSpecification::V1(spec)))
         }
-        (ResourceKind::ClusterConfiguration, _) => {
-            Err(ParseSpecificationError::UnknownVersion { kind, version })
+        (ResourceKind::ClusterConfiguration, SpecificationVersion::V2) => {
+            let spec = serde_json::from_value::<specs::cluster::ClusterConfigurationSpecificationV2>(spec)
+                .map_err(|cause| ParseSpecificationError::IllegalJsonSpecification { cause } )?;
+            Ok(Specification::ClusterConfigurationSpecification(specs::cluster::ClusterConfigurationSpecification::V2(spec)))
         }
         (ResourceKind::PeerDescriptor, SpecificationVersion::V1) => {
             let spec = serde_json::from_value::<specs::peer::PeerDescriptorSpecificationV1>(spec)
                 .map_err(|cause| ParseSpecificationError::IllegalJsonSpecification { cause } )?;
             Ok(Specification::PeerDescriptorSpecification(specs::peer::PeerDescriptorSpecification::V1(spec)))
         }
-        (ResourceKind::PeerDescriptor, _) => {
-            Err(ParseSpecificationError::UnknownVersion { kind, version })
+        (ResourceKind::PeerDescriptor, SpecificationVersion::V2) => {
+            let spec = serde_json::from_value::<specs::peer::PeerDescriptorSpecificationV2>(spec)
+                .map_err(|cause| ParseSpecificationError::IllegalJsonSpecification { cause } )?;
+            Ok(Specification::PeerDescriptorSpecification(specs::peer::PeerDescriptorSpecification::V2(spec)))
         }
     }
 }
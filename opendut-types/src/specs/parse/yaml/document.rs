@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
 
 use crate::specs::*;
@@ -6,7 +6,7 @@ use crate::specs::*;
 use parse::ResourceKind;
 use crate::specs::parse::ParseSpecificationError;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct YamlSpecificationDocument {
     pub kind: String,
     pub version: String,
@@ -63,6 +63,32 @@ impl TryFrom<YamlSpecificationDocument> for SpecificationDocument {
     }
 }
 
+impl TryFrom<SpecificationDocument> for YamlSpecificationDocument {
+    type Error = serde_yaml::Error;
+    fn try_from(document: SpecificationDocument) -> Result<Self, Self::Error> {
+        let (kind, spec) = match document.spec {
+            Specification::PeerDescriptorSpecification(peer::PeerDescriptorSpecification::V1(spec)) => {
+                (ResourceKind::PeerDescriptor, serde_yaml::to_value(spec)?)
+            }
+            Specification::PeerDescriptorSpecification(peer::PeerDescriptorSpecification::V2(spec)) => {
+                (ResourceKind::PeerDescriptor, serde_yaml::to_value(spec)?)
+            }
+            Specification::ClusterConfigurationSpecification(cluster::ClusterConfigurationSpecification::V1(spec)) => {
+                (ResourceKind::ClusterConfiguration, serde_yaml::to_value(spec)?)
+            }
+            Specification::ClusterConfigurationSpecification(cluster::ClusterConfigurationSpecification::V2(spec)) => {
+                (ResourceKind::ClusterConfiguration, serde_yaml::to_value(spec)?)
+            }
+        };
+        Ok(YamlSpecificationDocument {
+            kind: kind.to_string(),
+            version: document.version,
+            metadata: document.metadata,
+            spec,
+        })
+    }
+}
+
 fn parse_kind(s: &str) -> Result<ResourceKind, ParseSpecificationError> {
     serde_yaml::from_str::<ResourceKind>(s)
         .map_err(|_| ParseSpecificationError::IllegalResourceKind { kind: String::from(s) })
@@ -89,16 +115,20 @@ fn parse_spec(kind: ResourceKind, version: SpecificationVersion, spec: Value) ->
                 .map_err(|cause| ParseSpecificationError::IllegalYamlSpecification { cause } )?;
             Ok(Specification::ClusterConfigurationSpecification(cluster::ClusterConfigurationSpecification::V1(spec)))
         }
-        (ResourceKind::ClusterConfiguration, _) => {
-            Err(ParseSpecificationError::UnknownVersion { kind, version })
+        (ResourceKind::ClusterConfiguration, SpecificationVersion::V2) => {
+            let spec = serde_yaml::from_value::<cluster::ClusterConfigurationSpecificationV2>(spec)
+                .map_err(|cause| ParseSpecificationError::IllegalYamlSpecification { cause } )?;
+            Ok(Specification::ClusterConfigurationSpecification(cluster::ClusterConfigurationSpecification::V2(spec)))
         }
         (ResourceKind::PeerDescriptor, SpecificationVersion::V1) => {
             let spec = serde_yaml::from_value::<peer::PeerDescriptorSpecificationV1>(spec)
                 .map_err(|cause| ParseSpecificationError::IllegalYamlSpecification { cause } )?;
             Ok(Specification::PeerDescriptorSpecification(peer::PeerDescriptorSpecification::V1(spec)))
         }
-        (ResourceKind::PeerDescriptor, _) => {
-            Err(ParseSpecificationError::UnknownVersion { kind, version })
+        (ResourceKind::PeerDescriptor, SpecificationVersion::V2) => {
+            let spec = serde_yaml::from_value::<peer::PeerDescriptorSpecificationV2>(spec)
+                .map_err(|cause| ParseSpecificationError::IllegalYamlSpecification { cause } )?;
+            Ok(Specification::PeerDescriptorSpecification(peer::PeerDescriptorSpecification::V2(spec)))
         }
     }
 }
@@ -273,4 +303,139 @@ mod tests {
         
         Ok(())
     }
-}
+
+    #[test]
+    pub fn test_try_from_v2() -> Result<()> {
+        let document = YamlSpecificationDocument::try_from_yaml_str(r#"
+            kind: PeerDescriptor
+            version: v2
+            metadata:
+              id: 140f29fd-336b-48f7-9936-6b1892574543
+              name: TheForgottenName
+            spec:
+              network:
+                interfaces:
+                - id: a4a3c74c-71e5-49ea-9c2e-afb387951970
+                  name: can0
+                  kind: can
+                  bitrate: 500000
+                  sample-point: 0.7
+                  fd: true
+                  data-bitrate: 2000000
+                  data-sample-point: 0.8
+              topology:
+                devices:
+                - id: 61e79c59-ca35-4c6c-bb40-9175730a1de8
+                  name: MyDevice
+                  interface-id: a4a3c74c-71e5-49ea-9c2e-afb387951970
+              executors:
+                - id: da6ad5f7-ea45-4a11-aadf-4408bdb69e8e
+                  kind: container
+                  engine: podman
+                  image: debian
+        "#)?;
+
+        let result = SpecificationDocument::try_from(document)?;
+
+        let Specification::PeerDescriptorSpecification(peer::PeerDescriptorSpecification::V2(spec)) = &result.spec else {
+            panic!("Expected a peer descriptor specification in version 2, but got: {:?}", result.spec);
+        };
+        let [interface] = spec.network.interfaces.as_slice() else {
+            panic!("Expected exactly one network interface, but got: {:?}", spec.network.interfaces);
+        };
+        let peer::NetworkInterfaceConfigurationSpecificationV2::Can(can) = &interface.configuration else {
+            panic!("Expected a CAN interface, but got: {:?}", interface.configuration);
+        };
+        verify_that!(can.data_bitrate, eq(2000000))?;
+
+        let [executor] = spec.executors.as_slice() else {
+            panic!("Expected exactly one executor, but got: {:?}", spec.executors);
+        };
+        let peer::ExecutorKindSpecificationV2::Container(container) = &executor.kind else {
+            panic!("Expected a container executor, but got: {:?}", executor.kind);
+        };
+        verify_that!(container.volumes.len(), eq(0))?;
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_failing_try_from_v2_with_wrong_keys() -> Result<()> {
+        let document = YamlSpecificationDocument::try_from_yaml_str(r#"
+            kind: PeerDescriptor
+            version: v2
+            metadata:
+              id: 140f29fd-336b-48f7-9936-6b1892574543
+              name: TheForgottenName
+            spec:
+              ort: Ulm
+        "#)?;
+
+        let result = SpecificationDocument::try_from(document);
+        verify_that!(result, err(anything()))?;
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_try_from_cluster_v2_with_leader_referenced_by_name() -> Result<()> {
+        let document = YamlSpecificationDocument::try_from_yaml_str(r#"
+            kind: ClusterConfiguration
+            version: v2
+            metadata:
+              id: eb89f630-d585-43b9-8934-4db469ce66c5
+              name: TheForgottenName
+            spec:
+              leader: MyPeer
+              devices:
+                - d6cd3021-0d9f-423c-862e-f30b29438cbb
+                - MyDevice
+        "#)?;
+
+        let result = SpecificationDocument::try_from(document)?;
+
+        let Specification::ClusterConfigurationSpecification(cluster::ClusterConfigurationSpecification::V2(spec)) = &result.spec else {
+            panic!("Expected a cluster configuration specification in version 2, but got: {:?}", result.spec);
+        };
+        verify_that!(spec.leader, eq(&ReferenceSpecification::Name(String::from("MyPeer"))))?;
+        verify_that!(spec.devices, elements_are![
+            eq(&ReferenceSpecification::Id(uuid::uuid!("d6cd3021-0d9f-423c-862e-f30b29438cbb"))),
+            eq(&ReferenceSpecification::Name(String::from("MyDevice"))),
+        ])?;
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_try_from_v2_without_ids() -> Result<()> {
+        let document = YamlSpecificationDocument::try_from_yaml_str(r#"
+            kind: PeerDescriptor
+            version: v2
+            metadata:
+              name: TheForgottenName
+            spec:
+              network:
+                interfaces:
+                - name: eth0
+                  kind: ethernet
+              topology:
+                devices:
+                - name: MyDevice
+                  interface: eth0
+        "#)?;
+
+        let result = SpecificationDocument::try_from(document)?;
+
+        let Specification::PeerDescriptorSpecification(peer::PeerDescriptorSpecification::V2(spec)) = &result.spec else {
+            panic!("Expected a peer descriptor specification in version 2, but got: {:?}", result.spec);
+        };
+        let [device] = spec.topology.devices.as_slice() else {
+            panic!("Expected exactly one device, but got: {:?}", spec.topology.devices);
+        };
+        verify_that!(result.metadata.id, none())?;
+        verify_that!(device.id, none())?;
+        verify_that!(device.interface, eq(&ReferenceSpecification::Name(String::from("eth0"))))?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
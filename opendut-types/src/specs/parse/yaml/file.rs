@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::specs::parse::ParseSpecificationError;
 use crate::specs::parse::yaml::YamlSpecificationDocument;
@@ -56,6 +56,18 @@ impl YamlSpecificationFile {
             .map_err(|cause| ParseSpecificationError::IllegalYamlSpecification { cause })?;
         Ok(Self { documents })
     }
+
+    /// Serializes all documents into a string of YAML text, separating them with `---`.
+    ///
+    /// The result can be read again with [`YamlSpecificationFile::try_from_yaml_str`].
+    pub fn try_to_yaml_string(&self) -> Result<String, serde_yaml::Error> {
+        let mut yaml = String::new();
+        for document in &self.documents {
+            yaml.push_str("---\n");
+            yaml.push_str(&serde_yaml::to_string(document)?);
+        }
+        Ok(yaml)
+    }
 }
 
 #[allow(non_snake_case)]
@@ -91,7 +103,7 @@ mod tests {
             kind: eq("AKnownKind"),
             version: eq("SomeVersion"),
             metadata: matches_pattern!(SpecificationMetadata {
-                id: eq(&uuid!("661f0c35-722e-4656-a7e7-6e976476b53b")),
+                id: some(eq(&uuid!("661f0c35-722e-4656-a7e7-6e976476b53b"))),
                 name: eq("MyResource")
             })
         }))?;
@@ -125,4 +137,38 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    pub fn test_that_serialized_documents_can_be_deserialized_again() -> Result<()> {
+        let file = YamlSpecificationFile::try_from_yaml_str(indoc!("
+            ---
+            kind: ClusterConfiguration
+            version: v1
+            metadata:
+              id: 00000000-0000-0000-0000-000000000000
+              name: ClusterA
+            spec:
+              leader-id: e541e3cd-c965-4dfe-9727-896b63be2bfb
+              devices: []
+            ---
+            kind: ClusterConfiguration
+            version: v1
+            metadata:
+              id: 6d727331-8b2c-4300-abfa-45141d98c554
+              name: ClusterB
+            spec:
+              leader-id: e541e3cd-c965-4dfe-9727-896b63be2bfb
+              devices: []
+        "))?;
+
+        let yaml = file.try_to_yaml_string()?;
+        let result = YamlSpecificationFile::try_from_yaml_str(&yaml)?;
+
+        verify_that!(result.documents, elements_are![
+            field!(YamlSpecificationDocument.metadata, field!(SpecificationMetadata.name, eq("ClusterA"))),
+            field!(YamlSpecificationDocument.metadata, field!(SpecificationMetadata.name, eq("ClusterB"))),
+        ])?;
+
+        Ok(())
+    }
 }
@@ -1,12 +1,23 @@
-use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::peer::executor::container::{ContainerCommand, ContainerName, Engine};
+use crate::peer::executor::{ExecutorDescriptor, ExecutorKind};
+use crate::peer::PeerDescriptor;
+use crate::topology::DeviceDescriptor;
+use crate::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor};
+use crate::specs::{ReferenceSpecification, UpgradeSpecificationError};
+
 #[derive(Debug)]
 pub enum PeerDescriptorSpecification {
-    V1(PeerDescriptorSpecificationV1)
+    V1(PeerDescriptorSpecificationV1),
+    V2(PeerDescriptorSpecificationV2),
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub struct PeerDescriptorSpecificationV1 {
     #[serde(default)]
@@ -17,14 +28,16 @@ pub struct PeerDescriptorSpecificationV1 {
     pub executors: Vec<ExecutorSpecificationV1>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub struct NetworkDescriptorSpecificationV1 {
     pub interfaces: Vec<NetworkInterfaceDescriptorSpecificationV1>,
     pub bridge_name: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub struct NetworkInterfaceDescriptorSpecificationV1 {
     pub id: Uuid,
@@ -33,14 +46,16 @@ pub struct NetworkInterfaceDescriptorSpecificationV1 {
     pub parameters: Option<NetworkInterfaceConfigurationSpecification>,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub enum NetworkInterfaceKind {
     Ethernet,
     Can,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub struct NetworkInterfaceConfigurationSpecification {
     pub bitrate_kbps: u32,
@@ -50,13 +65,15 @@ pub struct NetworkInterfaceConfigurationSpecification {
     pub data_sample_point: f32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub struct TopologySpecificationV1 {
     pub devices: Vec<DeviceSpecificationV1>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub struct DeviceSpecificationV1 {
     pub id: Uuid,
@@ -66,7 +83,8 @@ pub struct DeviceSpecificationV1 {
     pub tags: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub struct ExecutorSpecificationV1 {
     pub id: Uuid,
@@ -75,14 +93,16 @@ pub struct ExecutorSpecificationV1 {
     pub parameters: Option<ExecutorConfigurationSpecification>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub enum SpecificationExecutorKind {
     Executable,
     Container,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub struct ExecutorConfigurationSpecification {
     pub engine: SpecificationEngineKind,
@@ -96,16 +116,575 @@ pub struct ExecutorConfigurationSpecification {
     pub command_args: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub enum SpecificationEngineKind {
     Docker,
     Podman
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all="kebab-case")]
 pub struct SpecificationEnvVariable {
     pub name: String,
     pub value: String,
 }
+
+impl From<PeerDescriptor> for PeerDescriptorSpecificationV1 {
+    fn from(peer: PeerDescriptor) -> Self {
+        Self {
+            location: peer.location.map(|location| location.value()),
+            network: NetworkDescriptorSpecificationV1 {
+                interfaces: peer.network.interfaces.into_iter()
+                    .map(NetworkInterfaceDescriptorSpecificationV1::from)
+                    .collect(),
+                bridge_name: peer.network.bridge_name.map(|name| name.name()),
+            },
+            topology: TopologySpecificationV1 {
+                devices: peer.topology.devices.into_iter()
+                    .map(DeviceSpecificationV1::from)
+                    .collect(),
+            },
+            executors: peer.executors.executors.into_iter()
+                .map(ExecutorSpecificationV1::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<NetworkInterfaceDescriptor> for NetworkInterfaceDescriptorSpecificationV1 {
+    /// The bitrates of CAN interfaces are specified in kbit/s, therefore remainders below 1 kbit/s are cut off.
+    fn from(interface: NetworkInterfaceDescriptor) -> Self {
+        let (kind, parameters) = match interface.configuration {
+            NetworkInterfaceConfiguration::Ethernet => (NetworkInterfaceKind::Ethernet, None),
+            NetworkInterfaceConfiguration::Can { bitrate, sample_point, fd, data_bitrate, data_sample_point } => {
+                let parameters = NetworkInterfaceConfigurationSpecification {
+                    bitrate_kbps: bitrate / 1000,
+                    sample_point: sample_point.sample_point(),
+                    fd,
+                    data_bitrate_kbps: data_bitrate / 1000,
+                    data_sample_point: data_sample_point.sample_point(),
+                };
+                (NetworkInterfaceKind::Can, Some(parameters))
+            }
+        };
+
+        Self {
+            id: interface.id.uuid,
+            name: interface.name.name(),
+            kind,
+            parameters,
+        }
+    }
+}
+
+impl From<DeviceDescriptor> for DeviceSpecificationV1 {
+    fn from(device: DeviceDescriptor) -> Self {
+        Self {
+            id: device.id.0,
+            name: device.name.to_string(),
+            description: device.description.map(|description| description.to_string()),
+            interface_id: device.interface.uuid,
+            tags: device.tags.into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<ExecutorDescriptor> for ExecutorSpecificationV1 {
+    fn from(executor: ExecutorDescriptor) -> Self {
+        let (kind, parameters) = match executor.kind {
+            ExecutorKind::Executable => (SpecificationExecutorKind::Executable, None),
+            ExecutorKind::Container { engine, name, image, volumes, devices, envs, ports, command, args } => {
+                let parameters = ExecutorConfigurationSpecification {
+                    engine: match engine {
+                        Engine::Docker => SpecificationEngineKind::Docker,
+                        Engine::Podman => SpecificationEngineKind::Podman,
+                    },
+                    name: match name {
+                        ContainerName::Empty => None,
+                        ContainerName::Value(name) => Some(name),
+                    },
+                    image: String::from(image),
+                    volumes: volumes.into_iter().map(String::from).collect(),
+                    devices: devices.into_iter().map(String::from).collect(),
+                    envs: envs.into_iter()
+                        .map(|env| SpecificationEnvVariable {
+                            name: String::from(env.name()),
+                            value: String::from(env.value()),
+                        })
+                        .collect(),
+                    ports: ports.into_iter().map(String::from).collect(),
+                    command: match command {
+                        ContainerCommand::Default => None,
+                        ContainerCommand::Value(command) => Some(command),
+                    },
+                    command_args: args.into_iter().map(String::from).collect(),
+                };
+                (SpecificationExecutorKind::Container, Some(parameters))
+            }
+        };
+
+        Self {
+            id: executor.id.uuid,
+            results_url: executor.results_url.map(String::from),
+            kind,
+            parameters,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all="kebab-case", deny_unknown_fields)]
+pub struct PeerDescriptorSpecificationV2 {
+    /// Free-text location of the peer, e.g. the site or test bench it belongs to.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub location: Option<String>,
+    /// Free-form key-value labels, e.g. `team: chassis`, which can be used to select peers.
+    #[serde(default, skip_serializing_if="BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// ID of the project, which the peer belongs to, e.g. `chassis`. Peers without a project belong to the `default` project.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub project: Option<String>,
+    /// IDs of further projects, which may read the peer and use it in their clusters, but not modify it.
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    pub shared_with_projects: Vec<String>,
+    #[serde(default)]
+    pub network: NetworkDescriptorSpecificationV2,
+    #[serde(default)]
+    pub topology: TopologySpecificationV2,
+    #[serde(default)]
+    pub executors: Vec<ExecutorSpecificationV2>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all="kebab-case", deny_unknown_fields)]
+pub struct NetworkDescriptorSpecificationV2 {
+    #[serde(default)]
+    pub interfaces: Vec<NetworkInterfaceDescriptorSpecificationV2>,
+    /// Name of the bridge, which connects the interfaces to the VPN. Bridges with custom names are not removed automatically.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub bridge_name: Option<String>,
+}
+
+/// Unknown fields are rejected by the flattened configuration, as `deny_unknown_fields` cannot be combined with `flatten`.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all="kebab-case")]
+pub struct NetworkInterfaceDescriptorSpecificationV2 {
+    /// ID of the interface. If omitted, the ID of the existing interface with the same name is used, or a new ID is generated.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub id: Option<Uuid>,
+    /// Name of the interface in the operating system of the peer, e.g. `eth0`.
+    pub name: String,
+    #[serde(flatten)]
+    pub configuration: NetworkInterfaceConfigurationSpecificationV2,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(tag="kind", rename_all="kebab-case", deny_unknown_fields)]
+pub enum NetworkInterfaceConfigurationSpecificationV2 {
+    /// Without fields, but not a unit variant, as these ignore unknown fields.
+    Ethernet {},
+    Can(CanInterfaceSpecificationV2),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all="kebab-case", deny_unknown_fields)]
+pub struct CanInterfaceSpecificationV2 {
+    /// CAN 2.0 bitrate in bit/s.
+    pub bitrate: u32,
+    /// CAN 2.0 sample point between 0.0 and 1.0.
+    pub sample_point: f32,
+    /// Whether CAN FD should be used.
+    #[serde(default)]
+    pub fd: bool,
+    /// CAN FD data bitrate in bit/s.
+    pub data_bitrate: u32,
+    /// CAN FD data sample point between 0.0 and 1.0.
+    pub data_sample_point: f32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all="kebab-case", deny_unknown_fields)]
+pub struct TopologySpecificationV2 {
+    #[serde(default)]
+    pub devices: Vec<DeviceSpecificationV2>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all="kebab-case", deny_unknown_fields)]
+pub struct DeviceSpecificationV2 {
+    /// ID of the device. If omitted, the ID of the existing device with the same name is used, or a new ID is generated.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub id: Option<Uuid>,
+    pub name: String,
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub description: Option<String>,
+    /// Name or ID of the network interface of this peer, via which the device is connected.
+    #[serde(alias="interface-id")]
+    pub interface: ReferenceSpecification,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Free-form key-value labels, which can be used to select devices, e.g. in cluster configurations.
+    #[serde(default, skip_serializing_if="BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+}
+
+/// Unknown fields are rejected by the flattened kind, as `deny_unknown_fields` cannot be combined with `flatten`.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all="kebab-case")]
+pub struct ExecutorSpecificationV2 {
+    pub id: Uuid,
+    /// URL of a WebDAV directory, into which the results of the executor are uploaded.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub results_url: Option<String>,
+    #[serde(flatten)]
+    pub kind: ExecutorKindSpecificationV2,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(tag="kind", rename_all="kebab-case", deny_unknown_fields)]
+pub enum ExecutorKindSpecificationV2 {
+    /// Without fields, but not a unit variant, as these ignore unknown fields.
+    Executable {},
+    Container(ContainerExecutorSpecificationV2),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "specs-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all="kebab-case", deny_unknown_fields)]
+pub struct ContainerExecutorSpecificationV2 {
+    pub engine: SpecificationEngineKind,
+    /// Name of the container. A name is generated, if none is given.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub name: Option<String>,
+    pub image: String,
+    #[serde(default)]
+    pub volumes: Vec<String>,
+    /// Names of the devices of this peer, which the container accesses.
+    #[serde(default)]
+    pub devices: Vec<String>,
+    #[serde(default)]
+    pub envs: Vec<SpecificationEnvVariable>,
+    #[serde(default)]
+    pub ports: Vec<String>,
+    /// Command to run in the container instead of the default command of the image.
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub command_args: Vec<String>,
+}
+
+impl PeerDescriptorSpecification {
+    /// Upgrades the specification to the latest version.
+    pub fn into_latest(self) -> Result<PeerDescriptorSpecificationV2, UpgradeSpecificationError> {
+        match self {
+            PeerDescriptorSpecification::V1(specification) => PeerDescriptorSpecificationV2::try_from(specification),
+            PeerDescriptorSpecification::V2(specification) => Ok(specification),
+        }
+    }
+}
+
+impl TryFrom<PeerDescriptorSpecificationV1> for PeerDescriptorSpecificationV2 {
+    type Error = UpgradeSpecificationError;
+
+    fn try_from(specification: PeerDescriptorSpecificationV1) -> Result<Self, Self::Error> {
+        let interfaces = specification.network.interfaces.into_iter()
+            .map(NetworkInterfaceDescriptorSpecificationV2::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let executors = specification.executors.into_iter()
+            .map(ExecutorSpecificationV2::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            location: specification.location,
+            labels: BTreeMap::new(),
+            project: None,
+            shared_with_projects: Vec::new(),
+            network: NetworkDescriptorSpecificationV2 {
+                interfaces,
+                bridge_name: specification.network.bridge_name,
+            },
+            topology: TopologySpecificationV2 {
+                devices: specification.topology.devices.into_iter()
+                    .map(|device| DeviceSpecificationV2 {
+                        id: Some(device.id),
+                        name: device.name,
+                        description: device.description,
+                        interface: ReferenceSpecification::Id(device.interface_id),
+                        tags: device.tags,
+                        labels: BTreeMap::new(),
+                    })
+                    .collect(),
+            },
+            executors,
+        })
+    }
+}
+
+impl TryFrom<NetworkInterfaceDescriptorSpecificationV1> for NetworkInterfaceDescriptorSpecificationV2 {
+    type Error = UpgradeSpecificationError;
+
+    /// The bitrates of CAN interfaces are converted from kbit/s to bit/s.
+    fn try_from(interface: NetworkInterfaceDescriptorSpecificationV1) -> Result<Self, Self::Error> {
+        let configuration = match (interface.kind, interface.parameters) {
+            (NetworkInterfaceKind::Ethernet, _) => NetworkInterfaceConfigurationSpecificationV2::Ethernet {},
+            (NetworkInterfaceKind::Can, Some(parameters)) => {
+                NetworkInterfaceConfigurationSpecificationV2::Can(CanInterfaceSpecificationV2 {
+                    bitrate: parameters.bitrate_kbps.saturating_mul(1000),
+                    sample_point: parameters.sample_point,
+                    fd: parameters.fd,
+                    data_bitrate: parameters.data_bitrate_kbps.saturating_mul(1000),
+                    data_sample_point: parameters.data_sample_point,
+                })
+            }
+            (NetworkInterfaceKind::Can, None) => {
+                return Err(UpgradeSpecificationError::MissingParameters { subject: "CAN interface", id: interface.id });
+            }
+        };
+
+        Ok(Self {
+            id: Some(interface.id),
+            name: interface.name,
+            configuration,
+        })
+    }
+}
+
+impl TryFrom<ExecutorSpecificationV1> for ExecutorSpecificationV2 {
+    type Error = UpgradeSpecificationError;
+
+    fn try_from(executor: ExecutorSpecificationV1) -> Result<Self, Self::Error> {
+        let kind = match (executor.kind, executor.parameters) {
+            (SpecificationExecutorKind::Executable, _) => ExecutorKindSpecificationV2::Executable {},
+            (SpecificationExecutorKind::Container, Some(parameters)) => {
+                ExecutorKindSpecificationV2::Container(ContainerExecutorSpecificationV2 {
+                    engine: parameters.engine,
+                    name: parameters.name,
+                    image: parameters.image,
+                    volumes: parameters.volumes,
+                    devices: parameters.devices,
+                    envs: parameters.envs,
+                    ports: parameters.ports,
+                    command: parameters.command,
+                    command_args: parameters.command_args,
+                })
+            }
+            (SpecificationExecutorKind::Container, None) => {
+                return Err(UpgradeSpecificationError::MissingParameters { subject: "container executor", id: executor.id });
+            }
+        };
+
+        Ok(Self {
+            id: executor.id,
+            results_url: executor.results_url,
+            kind,
+        })
+    }
+}
+
+impl From<PeerDescriptor> for PeerDescriptorSpecificationV2 {
+    fn from(peer: PeerDescriptor) -> Self {
+        Self {
+            location: peer.location.map(|location| location.value()),
+            labels: peer.labels.into(),
+            project: (!peer.project.owner.is_default()).then(|| peer.project.owner.into()),
+            shared_with_projects: peer.project.shared_with.into_iter().map(String::from).collect(),
+            network: NetworkDescriptorSpecificationV2 {
+                interfaces: peer.network.interfaces.into_iter()
+                    .map(NetworkInterfaceDescriptorSpecificationV2::from)
+                    .collect(),
+                bridge_name: peer.network.bridge_name.map(|name| name.name()),
+            },
+            topology: TopologySpecificationV2 {
+                devices: peer.topology.devices.into_iter()
+                    .map(DeviceSpecificationV2::from)
+                    .collect(),
+            },
+            executors: peer.executors.executors.into_iter()
+                .map(ExecutorSpecificationV2::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<NetworkInterfaceDescriptor> for NetworkInterfaceDescriptorSpecificationV2 {
+    fn from(interface: NetworkInterfaceDescriptor) -> Self {
+        let configuration = match interface.configuration {
+            NetworkInterfaceConfiguration::Ethernet => NetworkInterfaceConfigurationSpecificationV2::Ethernet {},
+            NetworkInterfaceConfiguration::Can { bitrate, sample_point, fd, data_bitrate, data_sample_point } => {
+                NetworkInterfaceConfigurationSpecificationV2::Can(CanInterfaceSpecificationV2 {
+                    bitrate,
+                    sample_point: sample_point.sample_point(),
+                    fd,
+                    data_bitrate,
+                    data_sample_point: data_sample_point.sample_point(),
+                })
+            }
+        };
+
+        Self {
+            id: Some(interface.id.uuid),
+            name: interface.name.name(),
+            configuration,
+        }
+    }
+}
+
+impl From<DeviceDescriptor> for DeviceSpecificationV2 {
+    fn from(device: DeviceDescriptor) -> Self {
+        Self {
+            id: Some(device.id.0),
+            name: device.name.to_string(),
+            description: device.description.map(|description| description.to_string()),
+            interface: ReferenceSpecification::Id(device.interface.uuid),
+            tags: device.tags.into_iter()
+                .map(String::from)
+                .collect(),
+            labels: device.labels.into(),
+        }
+    }
+}
+
+impl From<ExecutorDescriptor> for ExecutorSpecificationV2 {
+    fn from(executor: ExecutorDescriptor) -> Self {
+        let kind = match executor.kind {
+            ExecutorKind::Executable => ExecutorKindSpecificationV2::Executable {},
+            ExecutorKind::Container { engine, name, image, volumes, devices, envs, ports, command, args } => {
+                ExecutorKindSpecificationV2::Container(ContainerExecutorSpecificationV2 {
+                    engine: match engine {
+                        Engine::Docker => SpecificationEngineKind::Docker,
+                        Engine::Podman => SpecificationEngineKind::Podman,
+                    },
+                    name: match name {
+                        ContainerName::Empty => None,
+                        ContainerName::Value(name) => Some(name),
+                    },
+                    image: String::from(image),
+                    volumes: volumes.into_iter().map(String::from).collect(),
+                    devices: devices.into_iter().map(String::from).collect(),
+                    envs: envs.into_iter()
+                        .map(|env| SpecificationEnvVariable {
+                            name: String::from(env.name()),
+                            value: String::from(env.value()),
+                        })
+                        .collect(),
+                    ports: ports.into_iter().map(String::from).collect(),
+                    command: match command {
+                        ContainerCommand::Default => None,
+                        ContainerCommand::Value(command) => Some(command),
+                    },
+                    command_args: args.into_iter().map(String::from).collect(),
+                })
+            }
+        };
+
+        Self {
+            id: executor.id.uuid,
+            results_url: executor.results_url.map(String::from),
+            kind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[test]
+    fn should_upgrade_can_bitrates_from_kbps_to_bps() -> Result<()> {
+        let interface = NetworkInterfaceDescriptorSpecificationV1 {
+            id: Uuid::new_v4(),
+            name: String::from("can0"),
+            kind: NetworkInterfaceKind::Can,
+            parameters: Some(NetworkInterfaceConfigurationSpecification {
+                bitrate_kbps: 500,
+                sample_point: 0.7,
+                fd: true,
+                data_bitrate_kbps: 2000,
+                data_sample_point: 0.8,
+            }),
+        };
+
+        let upgraded = NetworkInterfaceDescriptorSpecificationV2::try_from(interface)?;
+
+        let NetworkInterfaceConfigurationSpecificationV2::Can(can) = &upgraded.configuration else {
+            panic!("Expected a CAN interface, but got: {:?}", upgraded.configuration);
+        };
+        verify_that!(can.bitrate, eq(500000))?;
+        verify_that!(can.data_bitrate, eq(2000000))?;
+        verify_that!(can.fd, eq(true))?;
+        Ok(())
+    }
+
+    #[test]
+    fn should_fail_to_upgrade_container_executor_without_parameters() -> Result<()> {
+        let id = Uuid::new_v4();
+        let specification = PeerDescriptorSpecification::V1(PeerDescriptorSpecificationV1 {
+            location: None,
+            network: NetworkDescriptorSpecificationV1 { interfaces: vec![], bridge_name: None },
+            topology: TopologySpecificationV1 { devices: vec![] },
+            executors: vec![
+                ExecutorSpecificationV1 {
+                    id,
+                    results_url: None,
+                    kind: SpecificationExecutorKind::Container,
+                    parameters: None,
+                }
+            ],
+        });
+
+        let result = specification.into_latest();
+
+        verify_that!(result, err(matches_pattern!(UpgradeSpecificationError::MissingParameters { id: eq(&id), .. })))?;
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_unknown_fields_of_network_interfaces() -> Result<()> {
+        let ethernet = serde_yaml::from_str::<NetworkInterfaceDescriptorSpecificationV2>("name: eth0\nkind: ethernet\n");
+        verify_that!(ethernet, ok(anything()))?;
+
+        let ethernet = serde_yaml::from_str::<NetworkInterfaceDescriptorSpecificationV2>("name: eth0\nkind: ethernet\nmtu: 1500\n");
+        verify_that!(ethernet, err(displays_as(contains_substring("unknown field `mtu`"))))?;
+
+        let can = "name: can0\nkind: can\nbitrate: 500000\nsample-point: 0.7\ndata-bitrate: 2000000\ndata-sample-point: 0.8\n";
+        verify_that!(serde_yaml::from_str::<NetworkInterfaceDescriptorSpecificationV2>(can), ok(anything()))?;
+
+        let can = format!("{can}bitrate-kbps: 500\n");
+        verify_that!(serde_yaml::from_str::<NetworkInterfaceDescriptorSpecificationV2>(&can), err(displays_as(contains_substring("unknown field `bitrate-kbps`"))))?;
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_unknown_fields_of_executors() -> Result<()> {
+        let id = Uuid::new_v4();
+
+        let executable = serde_yaml::from_str::<ExecutorSpecificationV2>(&format!("id: {id}\nkind: executable\n"));
+        verify_that!(executable, ok(anything()))?;
+
+        let executable = serde_yaml::from_str::<ExecutorSpecificationV2>(&format!("id: {id}\nkind: executable\npath: /usr/bin/test\n"));
+        verify_that!(executable, err(displays_as(contains_substring("unknown field `path`"))))?;
+
+        let container = format!("id: {id}\nkind: container\nengine: podman\nimage: testenv\n");
+        verify_that!(serde_yaml::from_str::<ExecutorSpecificationV2>(&container), ok(anything()))?;
+
+        let container = format!("{container}environment: []\n");
+        verify_that!(serde_yaml::from_str::<ExecutorSpecificationV2>(&container), err(displays_as(contains_substring("unknown field `environment`"))))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use schemars::schema::RootSchema;
+use schemars::{schema_for, JsonSchema};
+
+use crate::specs::parse::ResourceKind;
+use crate::specs::{cluster, peer, SpecificationMetadata, SpecificationVersion};
+
+/// Generates a JSON Schema for specification documents of the given kind and version,
+/// which editors can use for completion and validation.
+pub fn json_schema(kind: ResourceKind, version: SpecificationVersion) -> RootSchema {
+    let mut schema = match (kind, version) {
+        (ResourceKind::PeerDescriptor, SpecificationVersion::V1) => {
+            schema_for!(Document<PeerDescriptorKind, Version1, peer::PeerDescriptorSpecificationV1>)
+        }
+        (ResourceKind::PeerDescriptor, SpecificationVersion::V2) => {
+            schema_for!(Document<PeerDescriptorKind, Version2, peer::PeerDescriptorSpecificationV2>)
+        }
+        (ResourceKind::ClusterConfiguration, SpecificationVersion::V1) => {
+            schema_for!(Document<ClusterConfigurationKind, Version1, cluster::ClusterConfigurationSpecificationV1>)
+        }
+        (ResourceKind::ClusterConfiguration, SpecificationVersion::V2) => {
+            schema_for!(Document<ClusterConfigurationKind, Version2, cluster::ClusterConfigurationSpecificationV2>)
+        }
+    };
+    schema.schema.metadata().title = Some(format!("openDuT {kind} specification {}", version.as_str()));
+    schema
+}
+
+/// Mirrors the structure of a specification document, restricted to one kind and version.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+struct Document<Kind, Version, Spec> {
+    kind: Kind,
+    version: Version,
+    metadata: SpecificationMetadata,
+    spec: Spec,
+}
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+enum PeerDescriptorKind {
+    PeerDescriptor,
+}
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+enum ClusterConfigurationKind {
+    ClusterConfiguration,
+}
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+enum Version1 {
+    #[serde(rename = "v1")]
+    V1,
+}
+
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+enum Version2 {
+    #[serde(rename = "v2")]
+    V2,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+    use serde_json::Value;
+
+    #[test]
+    fn should_restrict_kind_and_version_of_the_document() -> Result<()> {
+        let schema = serde_json::to_value(json_schema(ResourceKind::PeerDescriptor, SpecificationVersion::V2))?;
+
+        let definitions = &schema["definitions"];
+        let kind = &definitions["PeerDescriptorKind"]["enum"];
+        let version = &definitions["Version2"]["enum"];
+
+        verify_that!(kind, eq(&Value::from(vec!["PeerDescriptor"])))?;
+        verify_that!(version, eq(&Value::from(vec!["v2"])))?;
+        verify_that!(schema["title"].as_str(), some(eq("openDuT PeerDescriptor specification v2")))
+    }
+
+    #[test]
+    fn should_describe_the_cluster_leader_as_name_or_id_in_version_2() -> Result<()> {
+        let schema = serde_json::to_value(json_schema(ResourceKind::ClusterConfiguration, SpecificationVersion::V2))?;
+
+        let leader = &schema["definitions"]["ReferenceSpecification"]["anyOf"];
+        verify_that!(leader.as_array().map(Vec::len), some(eq(2)))
+    }
+}
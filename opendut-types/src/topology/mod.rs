@@ -2,6 +2,7 @@ use std::fmt;
 use std::ops::Not;
 use std::str::FromStr;
 
+use crate::labels::Labels;
 use crate::util::net::NetworkInterfaceId;
 use serde::{Deserialize, Serialize};
 
@@ -280,4 +281,6 @@ pub struct DeviceDescriptor {
     pub description: Option<DeviceDescription>,
     pub interface: NetworkInterfaceId,
     pub tags: Vec<DeviceTag>,
+    #[serde(default)]
+    pub labels: Labels,
 }
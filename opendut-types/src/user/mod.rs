@@ -0,0 +1,177 @@
+use std::fmt;
+use std::ops::Not;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::OPENDUT_UUID_NAMESPACE;
+
+pub mod token;
+
+/// Identifies a user of CARL's built-in user store. Derived from the name of the user, so that users can be looked up by name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LocalUserId(pub Uuid);
+
+impl LocalUserId {
+    pub fn of(name: &LocalUserName) -> Self {
+        Self(Uuid::new_v5(&OPENDUT_UUID_NAMESPACE, format!("local-user:{}", name.0).as_bytes()))
+    }
+}
+
+impl From<Uuid> for LocalUserId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for LocalUserId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LocalUserName(pub(crate) String);
+
+impl LocalUserName {
+    pub const MIN_LENGTH: usize = 1;
+    pub const MAX_LENGTH: usize = 64;
+
+    pub fn value(self) -> String {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalLocalUserName {
+    #[error("User name '{value}' is too short. Expected at least {expected} characters, got {actual}.")]
+    TooShort { value: String, expected: usize, actual: usize },
+    #[error("User name '{value}' is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong { value: String, expected: usize, actual: usize },
+    #[error("User name '{value}' contains invalid characters. Only ASCII letters, digits and '.', '_', '-', '@' are allowed.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for LocalUserName {
+    type Error = IllegalLocalUserName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if length < Self::MIN_LENGTH {
+            Err(IllegalLocalUserName::TooShort { value, expected: Self::MIN_LENGTH, actual: length })
+        } else if length > Self::MAX_LENGTH {
+            Err(IllegalLocalUserName::TooLong { value, expected: Self::MAX_LENGTH, actual: length })
+        } else if value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '@')).not() {
+            Err(IllegalLocalUserName::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for LocalUserName {
+    type Error = IllegalLocalUserName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        LocalUserName::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for LocalUserName {
+    type Err = IllegalLocalUserName;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::try_from(value)
+    }
+}
+
+impl fmt::Display for LocalUserName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Role of a user of the built-in user store, which grants the permissions of the equally named role in CARL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LocalUserRole {
+    Administrator,
+    Editor,
+    Viewer,
+}
+
+impl LocalUserRole {
+    pub const ALL: [LocalUserRole; 3] = [LocalUserRole::Administrator, LocalUserRole::Editor, LocalUserRole::Viewer];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LocalUserRole::Administrator => "administrator",
+            LocalUserRole::Editor => "editor",
+            LocalUserRole::Viewer => "viewer",
+        }
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Unknown role '{value}'. Expected one of: administrator, editor, viewer.")]
+pub struct IllegalLocalUserRole {
+    pub value: String,
+}
+
+impl FromStr for LocalUserRole {
+    type Err = IllegalLocalUserRole;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter()
+            .find(|role| role.name() == value)
+            .ok_or_else(|| IllegalLocalUserRole { value: value.to_owned() })
+    }
+}
+
+impl fmt::Display for LocalUserRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Salted hash of the password or API token of a user, in the format `<algorithm>$<parameters>$<salt>$<hash>`.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalUserSecretHash(pub String);
+
+impl fmt::Debug for LocalUserSecretHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LocalUserSecretHash(<redacted>)")
+    }
+}
+
+/// User of CARL's built-in user store, which can be used instead of an OIDC provider.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalUser {
+    pub id: LocalUserId,
+    pub name: LocalUserName,
+    pub roles: Vec<LocalUserRole>,
+    pub secret_hash: LocalUserSecretHash,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_derive_the_same_id_from_the_same_name() -> anyhow::Result<()> {
+        let alice = LocalUserName::try_from("alice")?;
+
+        assert_eq!(LocalUserId::of(&alice), LocalUserId::of(&LocalUserName::try_from("alice")?));
+        assert_ne!(LocalUserId::of(&alice), LocalUserId::of(&LocalUserName::try_from("bob")?));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_invalid_user_names() {
+        assert!(LocalUserName::try_from("").is_err());
+        assert!(LocalUserName::try_from("alice smith").is_err());
+        assert!(LocalUserName::try_from("a".repeat(65)).is_err());
+        assert!(LocalUserName::try_from("ci-pipeline_1@lab.example").is_ok());
+    }
+}
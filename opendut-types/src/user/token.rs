@@ -0,0 +1,300 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::OPENDUT_UUID_NAMESPACE;
+
+/// Identifies a personal access token. Contained in the token itself, so that CARL can look it up without scanning all tokens.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PersonalAccessTokenId(pub Uuid);
+
+impl PersonalAccessTokenId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for PersonalAccessTokenId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal PersonalAccessTokenId: {value}")]
+pub struct IllegalPersonalAccessTokenId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for PersonalAccessTokenId {
+    type Error = IllegalPersonalAccessTokenId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value).map(Self).map_err(|_| IllegalPersonalAccessTokenId {
+            value: String::from(value),
+        })
+    }
+}
+
+impl FromStr for PersonalAccessTokenId {
+    type Err = IllegalPersonalAccessTokenId;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::try_from(value)
+    }
+}
+
+impl fmt::Display for PersonalAccessTokenId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Name of a personal access token, chosen by its owner to tell their tokens apart, e.g. by the CI pipeline using it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PersonalAccessTokenName(pub(crate) String);
+
+impl PersonalAccessTokenName {
+    pub const MIN_LENGTH: usize = 1;
+    pub const MAX_LENGTH: usize = 64;
+
+    pub fn value(self) -> String {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalPersonalAccessTokenName {
+    #[error("Token name '{value}' is too short. Expected at least {expected} characters, got {actual}.")]
+    TooShort { value: String, expected: usize, actual: usize },
+    #[error("Token name '{value}' is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong { value: String, expected: usize, actual: usize },
+    #[error("Token name '{value}' contains control characters.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for PersonalAccessTokenName {
+    type Error = IllegalPersonalAccessTokenName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.chars().count();
+        if length < Self::MIN_LENGTH {
+            Err(IllegalPersonalAccessTokenName::TooShort { value, expected: Self::MIN_LENGTH, actual: length })
+        } else if length > Self::MAX_LENGTH {
+            Err(IllegalPersonalAccessTokenName::TooLong { value, expected: Self::MAX_LENGTH, actual: length })
+        } else if value.chars().any(char::is_control) {
+            Err(IllegalPersonalAccessTokenName::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for PersonalAccessTokenName {
+    type Error = IllegalPersonalAccessTokenName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        PersonalAccessTokenName::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for PersonalAccessTokenName {
+    type Err = IllegalPersonalAccessTokenName;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::try_from(value)
+    }
+}
+
+impl fmt::Display for PersonalAccessTokenName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Limits a personal access token to the permissions of the equally named role in CARL.
+/// A token can only be scoped to roles, which its owner has themselves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PersonalAccessTokenScope {
+    Administrator,
+    Editor,
+    Viewer,
+}
+
+impl PersonalAccessTokenScope {
+    pub const ALL: [PersonalAccessTokenScope; 3] = [PersonalAccessTokenScope::Administrator, PersonalAccessTokenScope::Editor, PersonalAccessTokenScope::Viewer];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PersonalAccessTokenScope::Administrator => "administrator",
+            PersonalAccessTokenScope::Editor => "editor",
+            PersonalAccessTokenScope::Viewer => "viewer",
+        }
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Unknown scope '{value}'. Expected one of: administrator, editor, viewer.")]
+pub struct IllegalPersonalAccessTokenScope {
+    pub value: String,
+}
+
+impl FromStr for PersonalAccessTokenScope {
+    type Err = IllegalPersonalAccessTokenScope;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter()
+            .find(|scope| scope.name() == value)
+            .ok_or_else(|| IllegalPersonalAccessTokenScope { value: value.to_owned() })
+    }
+}
+
+impl fmt::Display for PersonalAccessTokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Describes a personal access token without its secret, as shown to its owner when listing their tokens.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonalAccessTokenDescriptor {
+    pub id: PersonalAccessTokenId,
+    pub name: PersonalAccessTokenName,
+    /// Name of the user, who created the token and on whose behalf it acts.
+    pub owner: String,
+    pub scopes: Vec<PersonalAccessTokenScope>,
+    pub created_at: SystemTime,
+    pub expires_at: SystemTime,
+    pub revoked_at: Option<SystemTime>,
+}
+
+impl PersonalAccessTokenDescriptor {
+    pub fn status(&self, now: SystemTime) -> PersonalAccessTokenStatus {
+        if self.revoked_at.is_some() {
+            PersonalAccessTokenStatus::Revoked
+        } else if now >= self.expires_at {
+            PersonalAccessTokenStatus::Expired
+        } else {
+            PersonalAccessTokenStatus::Active
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PersonalAccessTokenStatus {
+    /// Is accepted by CARL.
+    Active,
+    Expired,
+    Revoked,
+}
+
+impl fmt::Display for PersonalAccessTokenStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersonalAccessTokenStatus::Active => write!(f, "active"),
+            PersonalAccessTokenStatus::Expired => write!(f, "expired"),
+            PersonalAccessTokenStatus::Revoked => write!(f, "revoked"),
+        }
+    }
+}
+
+/// Hash of the secret part of a personal access token, in the format `<algorithm>$<hash>`.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonalAccessTokenSecretHash(pub String);
+
+impl fmt::Debug for PersonalAccessTokenSecretHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PersonalAccessTokenSecretHash(<redacted>)")
+    }
+}
+
+/// Personal access token as stored by CARL. The token itself is only shown once when it is created.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonalAccessToken {
+    pub descriptor: PersonalAccessTokenDescriptor,
+    pub secret_hash: PersonalAccessTokenSecretHash,
+}
+
+/// Identifies the recorded roles and groups of the owner of personal access tokens.
+/// Derived from the name of the owner, so that CARL looks them up without scanning all tokens.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PersonalAccessTokenOwnerId(pub Uuid);
+
+impl PersonalAccessTokenOwnerId {
+    pub fn of(owner: &str) -> Self {
+        Self(Uuid::new_v5(&OPENDUT_UUID_NAMESPACE, format!("personal-access-token-owner:{owner}").as_bytes()))
+    }
+}
+
+impl From<Uuid> for PersonalAccessTokenOwnerId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for PersonalAccessTokenOwnerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// OIDC roles and groups of the owner of personal access tokens, when they last authenticated with a token of the identity provider.
+/// Shared by all tokens of the owner. Limit the roles these grant and determine the projects of the owner.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonalAccessTokenOwner {
+    pub owner: String,
+    pub roles: Vec<String>,
+    pub groups: Vec<String>,
+    pub recorded_at: SystemTime,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_determine_the_status_of_a_personal_access_token() -> anyhow::Result<()> {
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let descriptor = PersonalAccessTokenDescriptor {
+            id: PersonalAccessTokenId::random(),
+            name: PersonalAccessTokenName::try_from("nightly-build")?,
+            owner: String::from("alice"),
+            scopes: vec![PersonalAccessTokenScope::Viewer],
+            created_at,
+            expires_at: created_at + Duration::from_secs(60),
+            revoked_at: None,
+        };
+        let before_expiry = created_at + Duration::from_secs(30);
+        let after_expiry = created_at + Duration::from_secs(90);
+
+        assert_that!(descriptor.status(before_expiry), eq(PersonalAccessTokenStatus::Active));
+        assert_that!(descriptor.status(after_expiry), eq(PersonalAccessTokenStatus::Expired));
+
+        let revoked = PersonalAccessTokenDescriptor { revoked_at: Some(before_expiry), ..descriptor };
+        assert_that!(revoked.status(before_expiry), eq(PersonalAccessTokenStatus::Revoked));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_invalid_token_names() {
+        assert!(PersonalAccessTokenName::try_from("").is_err());
+        assert!(PersonalAccessTokenName::try_from("line\nbreak").is_err());
+        assert!(PersonalAccessTokenName::try_from("a".repeat(65)).is_err());
+        assert!(PersonalAccessTokenName::try_from("Nightly build (main)").is_ok());
+    }
+
+    #[test]
+    fn should_derive_the_same_owner_id_from_the_same_owner() {
+        assert_that!(PersonalAccessTokenOwnerId::of("alice"), eq(PersonalAccessTokenOwnerId::of("alice")));
+        assert_that!(PersonalAccessTokenOwnerId::of("alice"), not(eq(PersonalAccessTokenOwnerId::of("bob"))));
+    }
+}
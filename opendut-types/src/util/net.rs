@@ -77,6 +77,39 @@ impl FromStr for Certificate {
     }
 }
 
+/// Certificate and private key, with which EDGAR authenticates towards CARL via mutual TLS.
+/// Issued by CARL with the ID of the peer as common name of the subject.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClientCertificate {
+    pub certificate: Certificate,
+    pub key: ClientCertificateKey,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ClientCertificateKey(pub pem::Pem);
+impl ClientCertificateKey {
+    pub fn encode_as_string(&self) -> String {
+        let encode_config = pem::EncodeConfig::default()
+            .set_line_ending(pem::LineEnding::LF);
+
+        pem::encode_config(&self.0, encode_config)
+    }
+}
+impl Debug for ClientCertificateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("ClientCertificateKey([redacted])")
+    }
+}
+impl FromStr for ClientCertificateKey {
+    type Err = pem::PemError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        pem::Pem::from_str(value)
+            .map(ClientCertificateKey)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub struct CanSamplePoint {
     sample_point_times_1000: u32
@@ -1,6 +1,13 @@
+use std::net::IpAddr;
+
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::util::Port;
+
+pub mod reconciliation;
+pub mod wireguard;
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum VpnPeerConfiguration {
@@ -9,7 +16,16 @@ pub enum VpnPeerConfiguration {
     Netbird {
         management_url: Url,
         setup_key: netbird::SetupKey,
-    }
+    },
+    #[serde(rename_all = "kebab-case")]
+    WireGuard {
+        private_key: wireguard::PrivateKey,
+        /// Address of the peer in the overlay network.
+        address: IpAddr,
+        /// Prefix length of the overlay network, used for routing traffic to the other peers.
+        prefix_length: u8,
+        listen_port: Port,
+    },
 }
 
 pub mod netbird {
@@ -0,0 +1,96 @@
+use std::fmt::{Display, Formatter};
+use std::time::SystemTime;
+
+use crate::cluster::ClusterId;
+use crate::peer::PeerId;
+
+/// Result of comparing the state of the VPN with the peers and cluster deployments known to CARL.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VpnReconciliationReport {
+    pub timestamp: SystemTime,
+    pub findings: Vec<VpnReconciliationFinding>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VpnReconciliationFinding {
+    pub mismatch: VpnMismatch,
+    pub outcome: VpnReconciliationOutcome,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum VpnMismatch {
+    /// A registered peer is not known to the VPN.
+    MissingPeer { peer_id: PeerId },
+    /// The VPN contains a peer, which is not registered in CARL.
+    /// Only reported, as it may belong to a peer registered after CARL's state was read. Delete it in the VPN, if it is not needed.
+    OrphanedPeer { peer_id: PeerId },
+    /// A peer is known to the VPN, but has no device logged in, e.g. because the device was deleted in the VPN.
+    /// Requires setting up the peer again.
+    PeerWithoutDevice { peer_id: PeerId },
+    /// A deployed cluster is not known to the VPN.
+    MissingCluster { cluster_id: ClusterId },
+    /// The members of a deployed cluster differ from the peers allowed to communicate in the VPN.
+    OutdatedCluster { cluster_id: ClusterId },
+    /// The VPN contains a cluster, which is not deployed.
+    OrphanedCluster { cluster_id: ClusterId },
+    /// An expired or revoked setup key, which can no longer be used.
+    StaleSetupKey { name: String },
+}
+
+impl VpnMismatch {
+    /// Short identifier of the kind of mismatch, e.g. for labeling metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            VpnMismatch::MissingPeer { .. } => "missing-peer",
+            VpnMismatch::OrphanedPeer { .. } => "orphaned-peer",
+            VpnMismatch::PeerWithoutDevice { .. } => "peer-without-device",
+            VpnMismatch::MissingCluster { .. } => "missing-cluster",
+            VpnMismatch::OutdatedCluster { .. } => "outdated-cluster",
+            VpnMismatch::OrphanedCluster { .. } => "orphaned-cluster",
+            VpnMismatch::StaleSetupKey { .. } => "stale-setup-key",
+        }
+    }
+}
+
+impl Display for VpnMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VpnMismatch::MissingPeer { peer_id } => write!(f, "Peer <{peer_id}> is missing in the VPN"),
+            VpnMismatch::OrphanedPeer { peer_id } => write!(f, "Peer <{peer_id}> exists in the VPN, but is not registered"),
+            VpnMismatch::PeerWithoutDevice { peer_id } => write!(f, "Peer <{peer_id}> has no device logged into the VPN"),
+            VpnMismatch::MissingCluster { cluster_id } => write!(f, "Cluster <{cluster_id}> is deployed, but missing in the VPN"),
+            VpnMismatch::OutdatedCluster { cluster_id } => write!(f, "Cluster <{cluster_id}> has different members in the VPN than deployed"),
+            VpnMismatch::OrphanedCluster { cluster_id } => write!(f, "Cluster <{cluster_id}> exists in the VPN, but is not deployed"),
+            VpnMismatch::StaleSetupKey { name } => write!(f, "Setup key '{name}' is expired or revoked"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum VpnReconciliationOutcome {
+    Repaired,
+    /// The mismatch cannot be repaired by CARL and requires manual intervention.
+    Unrepaired,
+    Failed { cause: String },
+}
+
+impl VpnReconciliationOutcome {
+    /// Short identifier of the outcome, e.g. for labeling metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            VpnReconciliationOutcome::Repaired => "repaired",
+            VpnReconciliationOutcome::Unrepaired => "unrepaired",
+            VpnReconciliationOutcome::Failed { .. } => "failed",
+        }
+    }
+}
+
+impl Display for VpnReconciliationOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VpnReconciliationOutcome::Repaired => write!(f, "repaired"),
+            VpnReconciliationOutcome::Unrepaired => write!(f, "requires manual intervention"),
+            VpnReconciliationOutcome::Failed { cause } => write!(f, "repair failed: {cause}"),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use serde::{Deserialize, Serialize};
+
+use crate::peer::PeerId;
+
+const KEY_LENGTH: usize = 32;
+
+/// Private Curve25519 key of a WireGuard interface. Generated by CARL and passed to EDGAR via the Setup-String.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PrivateKey {
+    bytes: [u8; KEY_LENGTH],
+}
+
+/// Public Curve25519 key of a WireGuard interface, which other peers use to authenticate it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PublicKey {
+    bytes: [u8; KEY_LENGTH],
+}
+
+/// A peer, which the WireGuard interface of another peer in the same cluster should accept traffic from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WireGuardPeerDescriptor {
+    pub peer_id: PeerId,
+    pub public_key: PublicKey,
+    /// Address of the peer in the overlay network.
+    pub address: IpAddr,
+    /// Address under which the peer can be reached. If unknown, the peer has to initiate the connection itself.
+    pub endpoint: Option<SocketAddr>,
+}
+
+macro_rules! impl_key {
+    ($key:ident) => {
+        impl $key {
+            pub const LENGTH: usize = KEY_LENGTH;
+
+            pub fn from_bytes(bytes: [u8; KEY_LENGTH]) -> Self {
+                Self { bytes }
+            }
+
+            pub fn as_bytes(&self) -> &[u8; KEY_LENGTH] {
+                &self.bytes
+            }
+        }
+
+        impl TryFrom<&[u8]> for $key {
+            type Error = IllegalWireGuardKey;
+
+            fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+                let bytes = <[u8; KEY_LENGTH]>::try_from(value)
+                    .map_err(|_| IllegalWireGuardKey {
+                        details: format!("Expected a key with a length of {KEY_LENGTH} bytes, but got {} bytes.", value.len()),
+                    })?;
+                Ok(Self { bytes })
+            }
+        }
+
+        impl FromStr for $key {
+            type Err = IllegalWireGuardKey;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                let bytes = BASE64_STANDARD.decode(value.trim())
+                    .map_err(|cause| IllegalWireGuardKey {
+                        details: format!("Base64 decoding failed due to: {cause}"),
+                    })?;
+                Self::try_from(bytes.as_slice())
+            }
+        }
+
+        impl TryFrom<String> for $key {
+            type Error = IllegalWireGuardKey;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                Self::from_str(&value)
+            }
+        }
+
+        impl From<$key> for String {
+            fn from(value: $key) -> Self {
+                BASE64_STANDARD.encode(value.bytes)
+            }
+        }
+    };
+}
+impl_key!(PrivateKey);
+impl_key!(PublicKey);
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PrivateKey(<redacted>)")
+    }
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", BASE64_STANDARD.encode(self.bytes))
+    }
+}
+
+/// Does not contain the value, as it may be a private key.
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal WireGuard key: {details}")]
+pub struct IllegalWireGuardKey {
+    pub details: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_and_print_a_base64_encoded_public_key() -> anyhow::Result<()> {
+        let encoded = "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=";
+
+        let key = PublicKey::from_str(encoded)?;
+
+        assert_that!(key.to_string(), eq(encoded));
+        assert_that!(String::from(key), eq(encoded));
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_print_the_private_key() -> anyhow::Result<()> {
+        let encoded = "yAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=";
+
+        let key = PrivateKey::from_str(encoded)?;
+
+        assert_that!(format!("{key:?}"), not(contains_substring(encoded)));
+        assert_that!(String::from(key), eq(encoded));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_a_key_with_the_wrong_length() {
+        assert_that!(PublicKey::from_str("AAEC"), err(anything()));
+    }
+}
@@ -120,6 +120,30 @@ mod auth_tests {
         assert!(filtered_client_list.is_empty());
     }
 
+    #[test_with::env(RUN_KEYCLOAK_INTEGRATION_TESTS)]
+    #[rstest]
+    #[tokio::test]
+    async fn test_rotate_oidc_client(#[future] registration_client: RegistrationClientRef) {
+        let client: RegistrationClientRef = registration_client.await;
+        let resource_id = Id::random();
+        let user_id = UserId { value: String::from("rotateTest") };
+        let old_credentials = client.register_new_client_for_user(resource_id, user_id.clone()).await.unwrap();
+        let new_credentials = client.register_additional_client_for_user(resource_id, user_id).await.unwrap();
+        let client_list: Clients = client.list_clients().await.unwrap();
+        assert_eq!(client_list.filter_clients_by_resource_id(resource_id).len(), 2);
+
+        client.delete_other_clients_by_resource_id(resource_id, &new_credentials.client_id).await.unwrap();
+        let client_list: Clients = client.list_clients().await.unwrap();
+        let remaining_client_ids = client_list.filter_clients_by_resource_id(resource_id)
+            .into_iter()
+            .map(|client| client.client_id)
+            .collect::<Vec<_>>();
+        assert_eq!(remaining_client_ids, vec![new_credentials.client_id.value()]);
+        assert_ne!(old_credentials.client_id.value(), remaining_client_ids[0]);
+
+        client.delete_client_by_resource_id(resource_id).await.unwrap();
+    }
+
     /*
     use opendut_auth::registration::resources::ResourceHomeUrl;
  
@@ -186,6 +186,13 @@ impl ConfidentialClient {
         Ok(access_token)
     }
 
+    /// Discards the cached token, so that the next call to `get_token()` fetches a new one.
+    /// Useful when a service rejected the token before its expiry, e.g. because it was revoked.
+    pub async fn invalidate_token(&self) {
+        let mut state = self.state.write().await;
+        *state = None;
+    }
+
     pub async fn check_login(&self) -> Result<bool, AuthError> {
         let token = self.get_token().await?;
         Ok(!token.value.is_empty())
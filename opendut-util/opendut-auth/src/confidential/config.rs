@@ -1,7 +1,6 @@
 use config::Config;
 use oauth2::{AuthUrl, ClientId as OAuthClientId, ClientSecret as OAuthClientSecret, EndpointNotSet, EndpointSet, Scope as OAuthScope, TokenUrl};
 use oauth2::basic::BasicClient;
-use shadow_rs::formatcp;
 use url::Url;
 
 use crate::confidential::error::ConfidentialClientError;
@@ -51,11 +50,6 @@ pub const CONFIG_KEY_OIDC_ENABLED: &str = "network.oidc.enabled";
 const OIDC_CLIENT_CONFIG_PREFIX: &str = "network.oidc.client";
 
 impl ConfidentialClientConfigData {
-    const CLIENT_ID: &'static str = formatcp!("{OIDC_CLIENT_CONFIG_PREFIX}.id");
-    const CLIENT_SECRET: &'static str = formatcp!("{OIDC_CLIENT_CONFIG_PREFIX}.secret");
-    const ISSUER_URL: &'static str = formatcp!("{OIDC_CLIENT_CONFIG_PREFIX}.issuer.url");
-    const SCOPES: &'static str = formatcp!("{OIDC_CLIENT_CONFIG_PREFIX}.scopes");
-
     pub fn parse_scopes(client_id: &str, raw_scopes: String) -> Vec<OAuthScope> {
         let raw_scopes_no_quotations = raw_scopes.replace('\"', "");
         let scopes = raw_scopes_no_quotations.split(',').collect::<Vec<_>>();
@@ -92,19 +86,30 @@ impl ConfidentialClientConfigData {
 
 impl ConfidentialClientConfigData {
     pub fn from_settings(settings: &Config) -> Result<Self, ConfidentialClientError> {
-        let client_id = settings.get_string(ConfidentialClientConfigData::CLIENT_ID)
-            .map_err(|error| ConfidentialClientError::Configuration { message: format!("Failed to find configuration for `{}`.", ConfidentialClientConfigData::CLIENT_ID), cause: error.into() })?;
-        let client_secret = settings.get_string(ConfidentialClientConfigData::CLIENT_SECRET)
-            .map_err(|error| ConfidentialClientError::Configuration { message: format!("Failed to find configuration for `{}`.", ConfidentialClientConfigData::CLIENT_SECRET), cause: error.into() })?;
-        let issuer = settings.get_string(ConfidentialClientConfigData::ISSUER_URL)
-            .map_err(|error| ConfidentialClientError::Configuration { message: format!("Failed to find configuration for `{}`.", ConfidentialClientConfigData::ISSUER_URL), cause: error.into() })?;
+        Self::from_settings_with_prefix(settings, OIDC_CLIENT_CONFIG_PREFIX)
+    }
+
+    /// Loads a client configured with the keys `id`, `secret`, `issuer.url` and `scopes` below the given prefix,
+    /// e.g. for a service which uses a different identity provider than the rest of openDuT.
+    pub fn from_settings_with_prefix(settings: &Config, prefix: &str) -> Result<Self, ConfidentialClientError> {
+        let client_id_key = format!("{prefix}.id");
+        let client_secret_key = format!("{prefix}.secret");
+        let issuer_url_key = format!("{prefix}.issuer.url");
+        let scopes_key = format!("{prefix}.scopes");
+
+        let client_id = settings.get_string(&client_id_key)
+            .map_err(|error| ConfidentialClientError::Configuration { message: format!("Failed to find configuration for `{}`.", client_id_key), cause: error.into() })?;
+        let client_secret = settings.get_string(&client_secret_key)
+            .map_err(|error| ConfidentialClientError::Configuration { message: format!("Failed to find configuration for `{}`.", client_secret_key), cause: error.into() })?;
+        let issuer = settings.get_string(&issuer_url_key)
+            .map_err(|error| ConfidentialClientError::Configuration { message: format!("Failed to find configuration for `{}`.", issuer_url_key), cause: error.into() })?;
 
         let issuer_url = Url::parse(&issuer)
             .map_err(|error| ConfidentialClientError::Configuration { message: format!("Failed to parse issuer URL: `{}`.", issuer), cause: error.into() })?;
         // TODO: add validation for issuer url to new type
         if issuer_url.as_str().ends_with('/') {
-            let raw_scopes = settings.get_string(ConfidentialClientConfigData::SCOPES)
-                .map_err(|error| ConfidentialClientError::Configuration { message: format!("Failed to find configuration for `{}`.", ConfidentialClientConfigData::SCOPES), cause: error.into() })?;
+            let raw_scopes = settings.get_string(&scopes_key)
+                .map_err(|error| ConfidentialClientError::Configuration { message: format!("Failed to find configuration for `{}`.", scopes_key), cause: error.into() })?;
             let scopes = ConfidentialClientConfigData::parse_scopes(&client_id, raw_scopes);
 
             Ok(Self {
@@ -13,6 +13,8 @@ use crate::confidential::client::ConfidentialClient;
 pub struct TonicAuthenticationService {
     inner: Channel,
     confidential_client: Option<Arc<ConfidentialClient>>,
+    /// Sent as bearer token instead of fetching a token with the confidential client, e.g. a personal access token issued by CARL.
+    static_token: Option<Arc<str>>,
 }
 
 impl TonicAuthenticationService {
@@ -23,6 +25,15 @@ impl TonicAuthenticationService {
         TonicAuthenticationService {
             inner,
             confidential_client,
+            static_token: None,
+        }
+    }
+
+    pub fn with_static_token(inner: Channel, token: &str) -> Self {
+        TonicAuthenticationService {
+            inner,
+            confidential_client: None,
+            static_token: Some(Arc::from(token)),
         }
     }
 }
@@ -45,8 +56,15 @@ impl Service<Request<BoxBody>> for TonicAuthenticationService {
         let clone = self.inner.clone();
         let mut inner = std::mem::replace(&mut self.inner, clone);
         let confidential_client = self.confidential_client.clone();
+        let static_token = self.static_token.clone();
 
         Box::pin(async move {
+            if let Some(token) = static_token {
+                let bearer_header = HeaderValue::from_str(&format!("Bearer {token}"))?;
+                request.headers_mut().insert("Authorization", bearer_header);
+                return Ok(inner.call(request).await?);
+            }
+
             let token_result = confidential_client.as_ref()
                 .map(|manager| manager.get_token());
 
@@ -1,4 +1,4 @@
-use leptos::prelude::Get;
+use leptos::prelude::{Get, GetUntracked, RwSignal};
 pub use leptos_oidc::Auth;
 use leptos_oidc::AuthSignal;
 use tonic::service::Interceptor;
@@ -18,11 +18,16 @@ impl AuthInterceptor {
 #[derive(Clone, Debug)]
 pub enum Authentication {
     Disabled,
-    Enabled(AuthSignal)
+    Enabled(AuthSignal),
+    /// Users sign in with their password via the built-in user store of CARL instead of an OIDC provider.
+    LocalUsers(LocalUserToken),
 }
 
+/// Access token of the user, who signed in via the built-in user store of CARL. `None`, if no user is signed in.
+pub type LocalUserToken = RwSignal<Option<String>>;
+
 impl Interceptor for AuthInterceptor {
-    fn call(&mut self, mut request: tonic::Request<()>) -> Result<tonic::Request<()>, Status> {
+    fn call(&mut self, request: tonic::Request<()>) -> Result<tonic::Request<()>, Status> {
         match &self.auth {
             Authentication::Enabled(auth) => {
                 let auth = auth.read_only();
@@ -31,15 +36,26 @@ impl Interceptor for AuthInterceptor {
                         tracing::debug!("AuthInterceptor: No access token present.");
                         Err(Status::unauthenticated("No access token present."))
                     }
-                    Some(token) => {
-                        let bearer_token: tonic::metadata::MetadataValue<_> = format!("Bearer {}", token).parse()
-                            .map_err(|_err| Status::unauthenticated("could not parse token"))?;
-                        request.metadata_mut().insert(http::header::AUTHORIZATION.as_str(), bearer_token);
-                        Ok(request)
+                    Some(token) => with_bearer_token(request, &token),
+                }
+            }
+            Authentication::LocalUsers(token) => {
+                match token.get_untracked() {
+                    None => {
+                        tracing::debug!("AuthInterceptor: No access token of a local user present.");
+                        Err(Status::unauthenticated("No access token present."))
                     }
+                    Some(token) => with_bearer_token(request, &token),
                 }
             }
             Authentication::Disabled => Ok(request)
         }
     }
 }
+
+fn with_bearer_token(mut request: tonic::Request<()>, token: &str) -> Result<tonic::Request<()>, Status> {
+    let bearer_token: tonic::metadata::MetadataValue<_> = format!("Bearer {}", token).parse()
+        .map_err(|_err| Status::unauthenticated("could not parse token"))?;
+    request.metadata_mut().insert(http::header::AUTHORIZATION.as_str(), bearer_token);
+    Ok(request)
+}
@@ -82,6 +82,16 @@ impl RegistrationClient {
     }
 
     pub async fn register_new_client_for_user(&self, resource_id: Id, user_id: UserId) -> Result<ClientCredentials, RegistrationClientError> {
+        if self.config.peer_credentials.is_none() {
+            // delete client for given resource id if it exists
+            self.delete_client_by_resource_id(resource_id).await?;
+        }
+        self.register_additional_client_for_user(resource_id, user_id).await
+    }
+
+    /// Registers a new client for the given resource, while keeping previously registered clients of the resource intact.
+    /// This allows replacing credentials without interruption, by deleting the old clients via [`Self::delete_other_clients_by_resource_id`] afterwards.
+    pub async fn register_additional_client_for_user(&self, resource_id: Id, user_id: UserId) -> Result<ClientCredentials, RegistrationClientError> {
         match self.config.peer_credentials.clone() {
             Some(peer_credentials) => {
                 Ok(peer_credentials)
@@ -96,9 +106,6 @@ impl RegistrationClient {
                     openidconnect::registration::ClientRegistrationRequest::new(redirect_uris, additional_metadata)
                         .set_grant_types(Some(grant_types));
                 let registration_url = self.config.registration_url.clone();
-                
-                // delete client for given resource id if it exists
-                self.delete_client_by_resource_id(resource_id).await?;
 
                 let client_name: ClientName = ClientName::new(resource_id.to_string());
                 let resource_uri = self.config.client_home_base_url.resource_url(resource_id, user_id)
@@ -172,6 +179,21 @@ impl RegistrationClient {
         let clients = self.list_clients().await?;
         let filtered_clients = clients.filter_clients_by_resource_id(resource_id);
 
+        self.delete_clients(clients, filtered_clients).await
+    }
+
+    /// Deletes all clients of the given resource, except for the client with the given ID.
+    pub async fn delete_other_clients_by_resource_id(&self, resource_id: Id, retained_client_id: &ClientId) -> Result<Clients, RegistrationClientError> {
+        let clients = self.list_clients().await?;
+        let filtered_clients = clients.filter_clients_by_resource_id(resource_id)
+            .into_iter()
+            .filter(|client| client.client_id != retained_client_id.0)
+            .collect::<Vec<_>>();
+
+        self.delete_clients(clients, filtered_clients).await
+    }
+
+    async fn delete_clients(&self, clients: Clients, filtered_clients: Vec<Client>) -> Result<Clients, RegistrationClientError> {
         let mut failed_deletion_clients = Vec::new();
 
         for client in filtered_clients {
@@ -28,7 +28,8 @@ pub struct Claims {
     pub subject: String,
     // Name of the user
     pub name: String,
-    // Email address of the user
+    // Email address of the user, not contained in tokens of the built-in user store of CARL
+    #[serde(default)]
     pub email: String,
     // Username of the user
     pub preferred_username: String,
@@ -0,0 +1,238 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use http::{header, StatusCode};
+use reqwest::{Request, Response};
+use tracing::debug;
+
+use opendut_auth::confidential::client::ConfidentialClient;
+
+use crate::client::request_handler::RequestHandler;
+use crate::netbird;
+use crate::netbird::error::RequestError;
+
+/// Source of the token, with which CARL authenticates towards the NetBird Management API.
+#[async_trait]
+pub trait TokenProvider: Send + Sync {
+    async fn token(&self) -> Result<netbird::Token, RequestError>;
+
+    /// Discards a cached token, after NetBird rejected it.
+    /// Returns whether a different token may be obtained by calling `token()` again.
+    async fn invalidate(&self) -> bool;
+}
+
+pub type TokenProviderRef = Arc<dyn TokenProvider>;
+
+/// Static token, e.g. a personal access token, which has to be rotated manually.
+#[async_trait]
+impl TokenProvider for netbird::Token {
+    async fn token(&self) -> Result<netbird::Token, RequestError> {
+        Ok(Clone::clone(self))
+    }
+
+    async fn invalidate(&self) -> bool {
+        false
+    }
+}
+
+/// Bearer tokens obtained via the client-credentials flow. Cached until shortly before they expire.
+#[async_trait]
+impl TokenProvider for ConfidentialClient {
+    async fn token(&self) -> Result<netbird::Token, RequestError> {
+        let token = self.get_token().await
+            .map_err(|cause| RequestError::Authentication(cause.to_string()))?;
+        Ok(netbird::Token::new_bearer(token.value))
+    }
+
+    async fn invalidate(&self) -> bool {
+        self.invalidate_token().await;
+        true
+    }
+}
+
+/// Adds the token to each request. If NetBird responds with `401 Unauthorized`, the token is refreshed and the request sent once more.
+pub struct AuthenticatingRequestHandler {
+    inner: Box<dyn RequestHandler + Send + Sync>,
+    token_provider: TokenProviderRef,
+}
+
+impl AuthenticatingRequestHandler {
+    pub fn new(inner: Box<dyn RequestHandler + Send + Sync>, token_provider: TokenProviderRef) -> Self {
+        Self { inner, token_provider }
+    }
+
+    async fn handle_with_token(&self, mut request: Request) -> Result<Response, RequestError> {
+        let token = self.token_provider.token().await?;
+        let auth_header = token.sensitive_header()
+            .map_err(|cause| RequestError::Authentication(format!("Token cannot be used as header value: {cause}")))?;
+        request.headers_mut().insert(header::AUTHORIZATION, auth_header);
+
+        self.inner.handle(request).await
+    }
+}
+
+#[async_trait]
+impl RequestHandler for AuthenticatingRequestHandler {
+    async fn handle(&self, request: Request) -> Result<Response, RequestError> {
+        let retry_request = request.try_clone();
+
+        let response = self.handle_with_token(request).await?;
+
+        if response.status() == StatusCode::UNAUTHORIZED {
+            if let Some(retry_request) = retry_request {
+                if self.token_provider.invalidate().await {
+                    debug!("NetBird rejected the authentication token. Retrying with a new token.");
+                    return self.handle_with_token(retry_request).await;
+                }
+            }
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use googletest::prelude::*;
+    use oauth2::{ClientId, ClientSecret};
+    use reqwest::Url;
+
+    use opendut_auth::confidential::client::ConfidentialClientRef;
+    use opendut_auth::confidential::config::ConfidentialClientConfigData;
+    use opendut_auth::confidential::pem::read_pem_from_file_path;
+    use opendut_auth::confidential::reqwest_client::OidcReqwestClient;
+    use opendut_util::project;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_reuse_the_cached_bearer_token() -> anyhow::Result<()> {
+        let token_endpoint = MockTokenEndpoint::start().await?;
+        let netbird = MockNetbird::rejecting(vec![]);
+        let handler = AuthenticatingRequestHandler::new(Box::new(Arc::clone(&netbird)), create_confidential_client(&token_endpoint).await?);
+
+        for _ in 0..2 {
+            let response = handler.handle(netbird_request()?).await?;
+            assert_that!(response.status(), eq(StatusCode::OK));
+        }
+
+        assert_that!(token_endpoint.issued_tokens(), eq(1));
+        assert_that!(netbird.received_authorizations(), elements_are![eq("Bearer token-1"), eq("Bearer token-1")]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fetch_a_new_bearer_token_when_netbird_rejects_the_cached_one() -> anyhow::Result<()> {
+        let token_endpoint = MockTokenEndpoint::start().await?;
+        let netbird = MockNetbird::rejecting(vec![String::from("Bearer token-1")]);
+        let handler = AuthenticatingRequestHandler::new(Box::new(Arc::clone(&netbird)), create_confidential_client(&token_endpoint).await?);
+
+        let response = handler.handle(netbird_request()?).await?;
+
+        assert_that!(response.status(), eq(StatusCode::OK));
+        assert_that!(token_endpoint.issued_tokens(), eq(2));
+        assert_that!(netbird.received_authorizations(), elements_are![eq("Bearer token-1"), eq("Bearer token-2")]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_with_a_static_token() -> anyhow::Result<()> {
+        let netbird = MockNetbird::rejecting(vec![String::from("Token personal-access-token")]);
+        let handler = AuthenticatingRequestHandler::new(Box::new(Arc::clone(&netbird)), Arc::new(netbird::Token::new_personal_access("personal-access-token")));
+
+        let response = handler.handle(netbird_request()?).await?;
+
+        assert_that!(response.status(), eq(StatusCode::UNAUTHORIZED));
+        assert_that!(netbird.received_authorizations(), elements_are![eq("Token personal-access-token")]);
+        Ok(())
+    }
+
+    fn netbird_request() -> anyhow::Result<Request> {
+        Ok(Request::new(http::Method::GET, Url::parse("https://localhost/api/groups")?))
+    }
+
+    async fn create_confidential_client(token_endpoint: &MockTokenEndpoint) -> anyhow::Result<ConfidentialClientRef> {
+        let client_config = ConfidentialClientConfigData::new(
+            ClientId::new(String::from("opendut-carl-client")),
+            ClientSecret::new(String::from("secret")),
+            token_endpoint.issuer_url.clone(),
+            vec![],
+        );
+        let ca = read_pem_from_file_path(&project::make_path_absolute("resources/development/tls/insecure-development-ca.pem")?)?;
+        let reqwest_client = OidcReqwestClient::from_pem(ca)?;
+
+        Ok(ConfidentialClient::from_client_config(client_config, reqwest_client).await?)
+    }
+
+    /// Token endpoint of an OIDC identity provider, which issues the tokens `token-1`, `token-2` and so on.
+    struct MockTokenEndpoint {
+        issuer_url: Url,
+        issued_tokens: Arc<AtomicUsize>,
+    }
+    impl MockTokenEndpoint {
+        async fn start() -> anyhow::Result<Self> {
+            let issued_tokens = Arc::new(AtomicUsize::new(0));
+
+            let router = axum::Router::new()
+                .route("/realms/opendut/protocol/openid-connect/token", axum::routing::post({
+                    let issued_tokens = Arc::clone(&issued_tokens);
+                    move || async move {
+                        let count = issued_tokens.fetch_add(1, Ordering::SeqCst) + 1;
+                        axum::Json(serde_json::json!({
+                            "access_token": format!("token-{count}"),
+                            "token_type": "bearer",
+                            "expires_in": 300,
+                        }))
+                    }
+                }));
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+            let address = listener.local_addr()?;
+            tokio::spawn(async move { axum::serve(listener, router).await });
+
+            Ok(Self {
+                issuer_url: Url::parse(&format!("http://{address}/realms/opendut/"))?,
+                issued_tokens,
+            })
+        }
+
+        fn issued_tokens(&self) -> usize {
+            self.issued_tokens.load(Ordering::SeqCst)
+        }
+    }
+
+    /// NetBird Management API, which responds with `401 Unauthorized` to the given authorization headers.
+    struct MockNetbird {
+        rejected_authorizations: Vec<String>,
+        received_authorizations: Mutex<Vec<String>>,
+    }
+    impl MockNetbird {
+        fn rejecting(rejected_authorizations: Vec<String>) -> Arc<Self> {
+            Arc::new(Self { rejected_authorizations, received_authorizations: Mutex::default() })
+        }
+
+        fn received_authorizations(&self) -> Vec<String> {
+            self.received_authorizations.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl RequestHandler for Arc<MockNetbird> {
+        async fn handle(&self, request: Request) -> Result<Response, RequestError> {
+            let authorization = request.headers().get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_owned();
+            self.received_authorizations.lock().unwrap().push(Clone::clone(&authorization));
+
+            let status = if self.rejected_authorizations.contains(&authorization) {
+                StatusCode::UNAUTHORIZED
+            } else {
+                StatusCode::OK
+            };
+            Ok(Response::from(http::Response::builder().status(status).body("[]").unwrap()))
+        }
+    }
+}
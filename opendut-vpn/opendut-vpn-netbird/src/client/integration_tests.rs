@@ -3,6 +3,7 @@
 use std::io::Read;
 use std::fs::File;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
 use reqwest::Url;
@@ -13,7 +14,7 @@ use opendut_types::peer::PeerId;
 use opendut_util::project;
 use opendut_vpn::VpnManagementClient;
 
-use crate::{netbird, NetbirdManagementClient, NetbirdManagementClientConfiguration, NetbirdToken};
+use crate::{netbird, NetbirdManagementClient, NetbirdManagementClientConfiguration, NetbirdToken, TokenProviderRef};
 use crate::client::{Client, DefaultClient};
 use crate::netbird::error::CreateClientError;
 
@@ -39,7 +40,7 @@ async fn test_netbird_management_client() {
     let netbird_management_client = NetbirdManagementClient::create_client_and_delete_default_policy(
         NetbirdManagementClientConfiguration {
             management_url,
-            authentication_token: Some(authentication_token),
+            authentication: Some(authentication_token),
             ca: Some(ca),
             timeout,
             retries,
@@ -115,7 +116,7 @@ async fn test_netbird_vpn_client_list_keys() -> anyhow::Result<()> {
     let netbird_management_client = NetbirdManagementClient::create_client_and_delete_default_policy(
         NetbirdManagementClientConfiguration {
             management_url: management_url.clone(),
-            authentication_token: Some(authentication_token.clone()),
+            authentication: Some(authentication_token.clone()),
             ca: Some(ca),
             timeout,
             retries,
@@ -146,7 +147,7 @@ async fn test_netbird_vpn_client_list_keys() -> anyhow::Result<()> {
 
 struct Fixture {
     pub management_url: Url,
-    pub authentication_token: NetbirdToken,
+    pub authentication_token: TokenProviderRef,
     pub ca: PathBuf,
     pub timeout: Duration,
     pub retries: u32,
@@ -157,7 +158,7 @@ impl Default for Fixture {
         let management_url = std::env::var("NETBIRD_INTEGRATION_API_URL").unwrap_or("https://netbird-management/api/".to_string());
         let netbird_api_token = std::env::var("NETBIRD_INTEGRATION_API_TOKEN").expect("Could not get netbird api token!");
         let management_url = Url::parse(&management_url).unwrap();
-        let authentication_token = NetbirdToken::new_personal_access(netbird_api_token);
+        let authentication_token: TokenProviderRef = Arc::new(NetbirdToken::new_personal_access(netbird_api_token));
         let timeout = Duration::from_millis(10000);
         let ca = project::make_path_absolute("resources/development/tls/insecure-development-ca.pem").expect("Could not determine ca path.");
 
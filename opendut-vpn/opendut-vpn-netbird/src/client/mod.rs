@@ -9,10 +9,12 @@ use tracing::error;
 use opendut_types::peer::PeerId;
 
 use crate::{netbird, routes};
+pub use crate::client::authentication::{TokenProvider, TokenProviderRef};
+use crate::client::authentication::AuthenticatingRequestHandler;
 use crate::client::request_handler::{DefaultRequestHandler, RequestHandler, RequestHandlerConfig};
-use crate::netbird::error;
 use crate::netbird::error::{CreateClientError, CreateSetupKeyError, GetGroupError, GetPoliciesError, RequestError};
 
+mod authentication;
 mod request_handler;
 
 mod tests;
@@ -21,15 +23,18 @@ mod integration_tests;
 #[async_trait]
 pub trait Client {
     async fn create_netbird_group(&self, name: netbird::GroupName, peers: Vec<netbird::PeerId>) -> Result<netbird::Group, RequestError>;
+    async fn list_netbird_groups(&self) -> Result<Vec<netbird::Group>, RequestError>;
     async fn get_netbird_group(&self, group_name: &netbird::GroupName) -> Result<netbird::Group, GetGroupError>;
     async fn delete_netbird_group(&self, group_id: &netbird::GroupId) -> Result<(), RequestError>;
     async fn list_setup_keys(&self) -> Result<Vec<netbird::SetupKey>, RequestError>;
     async fn get_setup_key(&self, peer_id: PeerId) -> Result<Vec<netbird::SetupKey>, RequestError>;
     async fn delete_setup_key(&self, peer_id: PeerId) -> Result<Vec<netbird::SetupKey>, RequestError>;
+    async fn delete_netbird_setup_key(&self, setup_key_id: &netbird::SetupKeyId) -> Result<(), RequestError>;
     #[allow(unused)] //Currently unused, but expected to be needed again
     async fn get_netbird_peer(&self, peer_id: &netbird::PeerId) -> Result<netbird::Peer, RequestError>;
     async fn delete_netbird_peer(&self, peer_id: &netbird::PeerId) -> Result<(), RequestError>;
     async fn create_netbird_self_policy(&self, group: netbird::Group, policy_name: netbird::PolicyName) -> Result<(), RequestError>;
+    async fn list_netbird_policies(&self) -> Result<Vec<netbird::Policy>, RequestError>;
     async fn get_netbird_policy(&self, policy_name: &netbird::PolicyName) -> Result<netbird::Policy, GetPoliciesError>;
     async fn delete_netbird_policy(&self, policy_id: &netbird::PolicyId) -> Result<(), RequestError>;
     async fn generate_netbird_setup_key(&self, peer_id: PeerId) -> Result<netbird::SetupKey, CreateSetupKeyError>;
@@ -70,7 +75,7 @@ impl DefaultClient {
     pub fn create(
         netbird_url: Url,
         ca: Option<&[u8]>,
-        token: Option<netbird::Token>,
+        token_provider: Option<TokenProviderRef>,
         requester: Option<Box<dyn RequestHandler + Send + Sync>>,
         timeout: Duration,
         retries: u32,
@@ -80,11 +85,6 @@ impl DefaultClient {
         let headers = {
             let mut headers = HeaderMap::new();
             headers.append(header::ACCEPT, DefaultClient::APPLICATION_JSON.parse().unwrap());
-            if let Some(ref token) = token {
-                let auth_header = token.sensitive_header()
-                    .map_err(error::CreateClientError::InvalidHeader)?;
-                headers.append(header::AUTHORIZATION, auth_header);
-            }
             headers
         };
 
@@ -112,6 +112,11 @@ impl DefaultClient {
             ))
         });
 
+        let requester: Box<dyn RequestHandler + Send + Sync> = match token_provider {
+            Some(token_provider) => Box::new(AuthenticatingRequestHandler::new(requester, token_provider)),
+            None => requester,
+        };
+
         Ok(Self {
             netbird_url,
             setup_key_expiration,
@@ -153,15 +158,22 @@ impl Client for DefaultClient {
     }
 
     #[tracing::instrument(skip(self), level="trace")]
-    async fn get_netbird_group(&self, group_name: &netbird::GroupName) -> Result<netbird::Group, GetGroupError> {
+    async fn list_netbird_groups(&self) -> Result<Vec<netbird::Group>, RequestError> {
         let url = routes::groups(self.netbird_url.clone());
         let request = Request::new(Method::GET, url);
 
-        let response = self.requester.handle(request).await
-            .map_err(|cause| GetGroupError::RequestFailure { group_name: group_name.to_owned(), cause })?;
+        let response = self.requester.handle(request).await?;
 
-        let result = response.json::<Vec<netbird::Group>>().await
-            .map_err(|cause| GetGroupError::RequestFailure { group_name: group_name.to_owned(), cause: RequestError::JsonDeserialization(cause) })?;
+        let groups = response.json::<Vec<netbird::Group>>().await
+            .map_err(RequestError::JsonDeserialization)?;
+
+        Ok(groups)
+    }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn get_netbird_group(&self, group_name: &netbird::GroupName) -> Result<netbird::Group, GetGroupError> {
+        let result = self.list_netbird_groups().await
+            .map_err(|cause| GetGroupError::RequestFailure { group_name: group_name.to_owned(), cause })?;
 
         let groups = result.into_iter()
             .filter(|group| group.name == *group_name)
@@ -218,6 +230,17 @@ impl Client for DefaultClient {
         Ok(found_setup_keys)
     }
 
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn delete_netbird_setup_key(&self, setup_key_id: &netbird::SetupKeyId) -> Result<(), RequestError> {
+        let url = routes::setup_key(Clone::clone(&self.netbird_url), setup_key_id);
+
+        let request = Request::new(Method::DELETE, url);
+
+        let response = self.requester.handle(request).await?;
+
+        parse_response_status(response, format!("NetBird setup key with ID <{:?}>", setup_key_id)).await
+    }
+
     #[tracing::instrument(skip(self), level="trace")]
     async fn get_netbird_peer(&self, peer_id: &netbird::PeerId) -> Result<netbird::Peer, RequestError> {
         let url = routes::peer(Clone::clone(&self.netbird_url), peer_id);
@@ -299,13 +322,19 @@ impl Client for DefaultClient {
     }
 
     #[tracing::instrument(skip(self), level="trace")]
-    async fn get_netbird_policy(&self, policy_name: &netbird::PolicyName) -> Result<netbird::Policy, GetPoliciesError> {
+    async fn list_netbird_policies(&self) -> Result<Vec<netbird::Policy>, RequestError> {
         let url = routes::policies(self.netbird_url.clone());
         let request = Request::new(Method::GET, url);
-        let response = self.requester.handle(request).await
+        let response = self.requester.handle(request).await?;
+        let policies = response.json::<Vec<netbird::Policy>>().await
+            .map_err(RequestError::JsonDeserialization)?;
+        Ok(policies)
+    }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn get_netbird_policy(&self, policy_name: &netbird::PolicyName) -> Result<netbird::Policy, GetPoliciesError> {
+        let result = self.list_netbird_policies().await
             .map_err(|cause| GetPoliciesError::RequestFailure { policy_name: policy_name.to_owned(), cause })?;
-        let result = response.json::<Vec<netbird::Policy>>().await
-            .map_err(|cause| GetPoliciesError::RequestFailure { policy_name: policy_name.to_owned(), cause: RequestError::JsonDeserialization(cause) })?;
 
         let policies = result.into_iter()
             .filter(|policy| policy.name == *policy_name)
@@ -342,13 +371,13 @@ impl Client for DefaultClient {
 
             CreateSetupKey {
                 name: netbird::setup_key_name_format(peer_id),
-                r#type: netbird::SetupKeyType::Reusable,
+                r#type: netbird::SetupKeyType::OneOff, //the Setup-String may only set up a single EDGAR
                 expires_in: self.setup_key_expiration.as_secs(),
                 revoked: false,
                 auto_groups: vec![
                     peer_group.id.0
                 ],
-                usage_limit: 0,
+                usage_limit: 1,
             }
         };
 
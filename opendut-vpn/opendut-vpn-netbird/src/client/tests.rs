@@ -170,6 +170,28 @@ async fn delete_group(fixture: Fixture) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[rstest]
+#[tokio::test]
+async fn delete_setup_key(fixture: Fixture) -> anyhow::Result<()> {
+
+    let requester = fixture.requester(|_, request| {
+        assert_that!(request.method(), eq(&Method::DELETE));
+        assert_that!(request.url().path(), eq("/api/setup-keys/2531583362"));
+        assert_that!(request.body(), none());
+        Ok(Response::from(http::Response::builder()
+            .body("")
+            .unwrap()))
+    });
+
+    let client = DefaultClient::create(fixture.base_url(), None, None, Some(Box::new(requester)), TIMEOUT, RETRIES, SETUP_KEY_EXPIRATION)?;
+
+    let result = client.delete_netbird_setup_key(&netbird::SetupKeyId(String::from("2531583362"))).await;
+
+    assert_that!(result, ok(anything()));
+
+    Ok(())
+}
+
 #[rstest]
 #[tokio::test]
 async fn create_a_setup_key(fixture: Fixture) -> anyhow::Result<()> {
@@ -200,13 +222,13 @@ async fn create_a_setup_key(fixture: Fixture) -> anyhow::Result<()> {
 
                 let expectation = json!({
                         "name": fixture.peer_setup_key_name(),
-                        "type": "reusable",
+                        "type": "one-off",
                         "expires_in": 86400,
                         "revoked": false,
                         "auto_groups": [
                             fixture.netbird_group_id()
                         ],
-                        "usage_limit": 0,
+                        "usage_limit": 1,
                     });
 
                 assert_that!(request, eq(&expectation));
@@ -218,17 +240,17 @@ async fn create_a_setup_key(fixture: Fixture) -> anyhow::Result<()> {
                             "key": "A616097E-FCF0-48FA-9354-CA4A61142761",
                             "name": fixture.peer_setup_key_name(),
                             "expires": "2023-06-01T14:47:22.291057Z",
-                            "type": "reusable",
+                            "type": "one-off",
                             "valid": true,
                             "revoked": false,
-                            "used_times": 2,
+                            "used_times": 0,
                             "last_used": "2023-05-05T09:00:35.477782Z",
                             "state": "valid",
                             "auto_groups": [
                                 fixture.netbird_group_id()
                             ],
                             "updated_at": "2023-05-05T09:00:35.477782Z",
-                            "usage_limit": 0
+                            "usage_limit": 1
                         }).to_string()
                     ).unwrap();
 
@@ -252,7 +274,7 @@ async fn create_a_setup_key(fixture: Fixture) -> anyhow::Result<()> {
                     key: anything(),
                     name: eq(&fixture.peer_setup_key_name()),
                     expires: anything(),
-                    r#type: eq(&netbird::SetupKeyType::Reusable),
+                    r#type: eq(&netbird::SetupKeyType::OneOff),
                     valid: eq(&true),
                     revoked: eq(&false),
                     used_times: anything(),
@@ -260,7 +282,7 @@ async fn create_a_setup_key(fixture: Fixture) -> anyhow::Result<()> {
                     state: eq(&netbird::SetupKeyState::Valid),
                     auto_groups: eq(&vec![fixture.netbird_group_id.0]),
                     updated_at: anything(),
-                    usage_limit: eq(&0),
+                    usage_limit: eq(&1),
                 }
             )
         );
@@ -9,10 +9,12 @@ use reqwest::Url;
 use tracing::{debug, error, trace, warn};
 
 pub use netbird::Token as NetbirdToken;
+pub use client::{TokenProvider, TokenProviderRef};
 use opendut_types::cluster::ClusterId;
 use opendut_types::peer::PeerId;
 use opendut_types::vpn::VpnPeerConfiguration;
-use opendut_vpn::{CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, VpnManagementClient};
+use opendut_types::vpn::reconciliation::VpnReconciliationFinding;
+use opendut_vpn::{CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, ExpectedVpnState, ReconcileError, RevokeVpnPeerConfigurationError, VpnManagementClient};
 
 use crate::client::{Client, DefaultClient};
 use crate::netbird::error::{CreateClientError, CreateSetupKeyError, GetGroupError, GetPoliciesError, RequestError};
@@ -21,12 +23,13 @@ use crate::netbird::{GroupName, PolicyName};
 mod client;
 mod routes;
 mod netbird;
+mod reconciliation;
 
 type Inner = Box<dyn Client + Send + Sync>;
 
 pub struct NetbirdManagementClientConfiguration {
     pub management_url: Url,
-    pub authentication_token: Option<NetbirdToken>,
+    pub authentication: Option<TokenProviderRef>,
     pub ca: Option<PathBuf>,
     pub timeout: Duration,
     pub retries: u32,
@@ -63,7 +66,7 @@ impl NetbirdManagementClient {
         let inner = Box::new(DefaultClient::create(
             Clone::clone(&management_url),
             Some(management_ca.as_slice()),
-            configuration.authentication_token,
+            configuration.authentication,
             None,
             configuration.timeout,
             configuration.retries,
@@ -282,6 +285,19 @@ impl VpnManagementClient for NetbirdManagementClient {
             setup_key: opendut_types::vpn::netbird::SetupKey::from(setup_key.key),
         })
     }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn revoke_vpn_peer_configuration(&self, peer_id: PeerId) -> Result<(), RevokeVpnPeerConfigurationError> {
+        let deleted_setup_keys = self.inner.delete_setup_key(peer_id).await
+            .map_err(|error| RevokeVpnPeerConfigurationError::RevocationFailure { peer_id, error: error.into() })?;
+        debug!("Deleted {} setup key(s) of peer <{peer_id}>.", deleted_setup_keys.len());
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn reconcile(&self, expected: &ExpectedVpnState) -> Result<Vec<VpnReconciliationFinding>, ReconcileError> {
+        reconciliation::reconcile(self, expected).await
+    }
 }
 
 #[cfg(test)]
@@ -297,7 +313,8 @@ mod test {
     use opendut_types::peer::PeerId;
     use opendut_types::vpn;
     use opendut_types::vpn::VpnPeerConfiguration;
-    use opendut_vpn::VpnManagementClient;
+    use opendut_types::vpn::reconciliation::{VpnMismatch, VpnReconciliationFinding, VpnReconciliationOutcome};
+    use opendut_vpn::{ExpectedVpnState, VpnManagementClient};
 
     use crate::{netbird, NetbirdManagementClient};
     use crate::client::Client;
@@ -451,6 +468,227 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn A_NetbirdManagementClient_should_remove_the_group_and_policy_of_a_cluster_which_is_not_deployed() -> Result<()> {
+
+        let cluster_id = ClusterId::from(uuid!("6a6510a9-031b-4834-a4f7-454cc401fe13"));
+        let cluster_group = netbird::Group {
+            id: netbird::GroupId::from("cluster-group"),
+            name: netbird::GroupName::from(cluster_id),
+            peers_count: 0,
+            peers: vec![],
+        };
+        let cluster_policy = netbird::Policy {
+            id: netbird::PolicyId::from("cluster-policy"),
+            name: netbird::PolicyName::from(cluster_id),
+        };
+
+        let fixture = Fixture::setup(|mock_client| {
+            mock_client.expect_list_netbird_groups()
+                .returning({
+                    let cluster_group = Clone::clone(&cluster_group);
+                    move || Ok(vec![Clone::clone(&cluster_group)])
+                });
+            mock_client.expect_list_netbird_policies()
+                .returning({
+                    let cluster_policy = Clone::clone(&cluster_policy);
+                    move || Ok(vec![Clone::clone(&cluster_policy)])
+                });
+            mock_client.expect_list_setup_keys()
+                .returning(|| Ok(vec![]));
+            mock_client.expect_get_netbird_policy()
+                .withf(move |policy_name| *policy_name == netbird::PolicyName::Cluster(cluster_id))
+                .returning(move |_| Ok(Clone::clone(&cluster_policy)));
+            mock_client.expect_delete_netbird_policy()
+                .times(1)
+                .withf(|policy_id| *policy_id == netbird::PolicyId::from("cluster-policy"))
+                .returning(|_| Ok(()));
+            mock_client.expect_get_netbird_group()
+                .withf(move |group_name| *group_name == netbird::GroupName::Cluster(cluster_id))
+                .returning(move |_| Ok(Clone::clone(&cluster_group)));
+            mock_client.expect_delete_netbird_group()
+                .times(1)
+                .withf(|group_id| *group_id == netbird::GroupId::from("cluster-group"))
+                .returning(|_| Ok(()));
+        });
+
+        assert_that!(fixture.testee.reconcile(&ExpectedVpnState::default()).await,
+            ok(elements_are![
+                eq(&VpnReconciliationFinding { mismatch: VpnMismatch::OrphanedCluster { cluster_id }, outcome: VpnReconciliationOutcome::Repaired }),
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn A_NetbirdManagementClient_should_recreate_a_deployed_cluster_which_is_missing_and_delete_expired_setup_keys() -> Result<()> {
+
+        let cluster_id = ClusterId::from(uuid!("6a6510a9-031b-4834-a4f7-454cc401fe13"));
+        let peer_a_id = PeerId::from(uuid!("d61bed7b-2fec-4a5b-a937-d6a791cb5ff9"));
+        let peer_b_id = PeerId::from(uuid!("267ca2c0-942e-4780-857e-c153ddcf2328"));
+        let peer_a_group = netbird::Group {
+            id: netbird::GroupId::from("peer-a-group"),
+            name: netbird::GroupName::from(peer_a_id),
+            peers_count: 1,
+            peers: vec![GroupPeerInfo { id: netbird::PeerId::from("peer-a"), name: String::from("peer-a")}],
+        };
+        let peer_b_group = netbird::Group {
+            id: netbird::GroupId::from("peer-b-group"),
+            name: netbird::GroupName::from(peer_b_id),
+            peers_count: 1,
+            peers: vec![GroupPeerInfo { id: netbird::PeerId::from("peer-b"), name: String::from("peer-b")}],
+        };
+        let cluster_group = netbird::Group {
+            id: netbird::GroupId::from("cluster-group"),
+            name: netbird::GroupName::from(cluster_id),
+            peers_count: 2,
+            peers: vec![],
+        };
+        let expired_setup_key = setup_key("expired-key", peer_a_id, netbird::SetupKeyState::Expired);
+        let valid_setup_key = setup_key("valid-key", peer_b_id, netbird::SetupKeyState::Valid);
+
+        let fixture = Fixture::setup(|mock_client| {
+            mock_client.expect_list_netbird_groups()
+                .returning({
+                    let groups = vec![Clone::clone(&peer_a_group), Clone::clone(&peer_b_group)];
+                    move || Ok(Clone::clone(&groups))
+                });
+            mock_client.expect_list_netbird_policies()
+                .returning(|| Ok(vec![]));
+            mock_client.expect_list_setup_keys()
+                .returning({
+                    let setup_keys = vec![Clone::clone(&expired_setup_key), valid_setup_key];
+                    move || Ok(Clone::clone(&setup_keys))
+                });
+            mock_client.expect_get_netbird_policy()
+                .returning(|policy_name| Err(GetPoliciesError::PolicyNotFound { policy_name: Clone::clone(policy_name) }));
+            mock_client.expect_get_netbird_group()
+                .returning(move |group_name| {
+                    [&peer_a_group, &peer_b_group].into_iter()
+                        .find(|group| group.name == *group_name)
+                        .cloned()
+                        .ok_or(GetGroupError::GroupNotFound { group_name: Clone::clone(group_name) })
+                });
+            mock_client.expect_create_netbird_group()
+                .times(1)
+                .withf(move |group_name, peers| {
+                    *group_name == netbird::GroupName::Cluster(cluster_id)
+                        && *peers == vec![netbird::PeerId::from("peer-a"), netbird::PeerId::from("peer-b")]
+                })
+                .returning(move |_, _| Ok(Clone::clone(&cluster_group)));
+            mock_client.expect_create_netbird_self_policy()
+                .times(1)
+                .withf(move |_, policy_name| *policy_name == netbird::PolicyName::Cluster(cluster_id))
+                .returning(|_, _| Ok(()));
+            mock_client.expect_delete_netbird_setup_key()
+                .times(1)
+                .withf(|setup_key_id| setup_key_id.0 == "expired-key")
+                .returning(|_| Ok(()));
+        });
+
+        let expected = ExpectedVpnState {
+            peers: [peer_a_id, peer_b_id].into(),
+            clusters: [(cluster_id, vec![peer_a_id, peer_b_id])].into(),
+        };
+
+        assert_that!(fixture.testee.reconcile(&expected).await,
+            ok(elements_are![
+                eq(&VpnReconciliationFinding { mismatch: VpnMismatch::MissingCluster { cluster_id }, outcome: VpnReconciliationOutcome::Repaired }),
+                eq(&VpnReconciliationFinding { mismatch: VpnMismatch::StaleSetupKey { name: expired_setup_key.name }, outcome: VpnReconciliationOutcome::Repaired }),
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn A_NetbirdManagementClient_should_report_unknown_peers_and_deployed_peers_without_device() -> Result<()> {
+
+        let cluster_id = ClusterId::from(uuid!("6a6510a9-031b-4834-a4f7-454cc401fe13"));
+        let peer_id = PeerId::from(uuid!("d61bed7b-2fec-4a5b-a937-d6a791cb5ff9"));
+        let unknown_peer_id = PeerId::from(uuid!("267ca2c0-942e-4780-857e-c153ddcf2328"));
+        let peer_group = netbird::Group {
+            id: netbird::GroupId::from("peer-group"),
+            name: netbird::GroupName::from(peer_id),
+            peers_count: 0,
+            peers: vec![],
+        };
+        let unknown_peer_group = netbird::Group {
+            id: netbird::GroupId::from("unknown-peer-group"),
+            name: netbird::GroupName::from(unknown_peer_id),
+            peers_count: 0,
+            peers: vec![],
+        };
+
+        let fixture = Fixture::setup(|mock_client| {
+            mock_client.expect_list_netbird_groups()
+                .returning({
+                    let groups = vec![peer_group, unknown_peer_group];
+                    move || Ok(Clone::clone(&groups))
+                });
+            mock_client.expect_list_netbird_policies()
+                .returning(|| Ok(vec![]));
+            mock_client.expect_list_setup_keys()
+                .returning(|| Ok(vec![]));
+            mock_client.expect_delete_setup_key()
+                .never();
+            mock_client.expect_delete_netbird_group()
+                .never();
+            mock_client.expect_create_netbird_group()
+                .never();
+        });
+
+        let expected = ExpectedVpnState {
+            peers: [peer_id].into(),
+            clusters: [(cluster_id, vec![peer_id])].into(),
+        };
+
+        assert_that!(fixture.testee.reconcile(&expected).await,
+            ok(elements_are![
+                eq(&VpnReconciliationFinding { mismatch: VpnMismatch::OrphanedPeer { peer_id: unknown_peer_id }, outcome: VpnReconciliationOutcome::Unrepaired }),
+                eq(&VpnReconciliationFinding { mismatch: VpnMismatch::PeerWithoutDevice { peer_id }, outcome: VpnReconciliationOutcome::Unrepaired }),
+                eq(&VpnReconciliationFinding { mismatch: VpnMismatch::MissingCluster { cluster_id }, outcome: VpnReconciliationOutcome::Unrepaired }),
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn A_NetbirdManagementClient_should_delete_the_setup_keys_of_a_peer_when_revoking_its_configuration() -> Result<()> {
+        let peer_id = PeerId::random();
+
+        let fixture = Fixture::setup(|mock_client| {
+            mock_client.expect_delete_setup_key()
+                .times(1)
+                .withf(move |actual_peer_id| *actual_peer_id == peer_id)
+                .returning(move |_| Ok(vec![setup_key("revoked-key", peer_id, netbird::SetupKeyState::Valid)]));
+        });
+
+        assert_that!(fixture.testee.revoke_vpn_peer_configuration(peer_id).await, ok(eq(&())));
+
+        Ok(())
+    }
+
+    fn setup_key(id: &str, peer_id: PeerId, state: netbird::SetupKeyState) -> SetupKey {
+        SetupKey {
+            id: netbird::SetupKeyId(String::from(id)),
+            key: uuid!("4626c02a-bee7-4468-91c3-73c47fd0116c"),
+            name: netbird::setup_key_name_format(peer_id),
+            expires: netbird::SetupKeyTimeStamp { inner: OffsetDateTime::now_utc() },
+            r#type: netbird::SetupKeyType::Reusable,
+            valid: state == netbird::SetupKeyState::Valid,
+            revoked: false,
+            used_times: 0,
+            last_used: netbird::SetupKeyTimeStamp { inner: OffsetDateTime::now_utc() },
+            state,
+            auto_groups: vec![],
+            updated_at: netbird::SetupKeyTimeStamp { inner: OffsetDateTime::now_utc() },
+            usage_limit: 0,
+        }
+    }
+
     struct Fixture {
         testee: NetbirdManagementClient,
     }
@@ -478,17 +716,20 @@ mod test {
         #[async_trait]
         impl Client for MockClient {
             async fn create_netbird_group(&self, name: netbird::GroupName, peers: Vec<netbird::PeerId>) -> std::result::Result<netbird::Group, RequestError>;
+            async fn list_netbird_groups(&self) -> std::result::Result<Vec<netbird::Group>, RequestError>;
             async fn get_netbird_group(&self, group_name: &netbird::GroupName) -> std::result::Result<netbird::Group, GetGroupError>;
             async fn delete_netbird_group(&self, group_id: &netbird::GroupId) -> std::result::Result<(), RequestError>;
             async fn get_netbird_peer(&self, peer_id: &netbird::PeerId) -> std::result::Result<netbird::Peer, RequestError>;
             async fn delete_netbird_peer(&self, peer_id: &netbird::PeerId) -> std::result::Result<(), RequestError>;
             async fn create_netbird_self_policy(&self, group: netbird::Group, policy_name: netbird::PolicyName) -> std::result::Result<(), RequestError>;
+            async fn list_netbird_policies(&self) -> std::result::Result<Vec<netbird::Policy>, RequestError>;
             async fn get_netbird_policy(&self, policy_name: &netbird::PolicyName) -> std::result::Result<netbird::Policy, GetPoliciesError>;
             async fn delete_netbird_policy(&self, policy_id: &netbird::PolicyId) -> std::result::Result<(), RequestError>;
             async fn generate_netbird_setup_key(&self, peer_id: PeerId) -> std::result::Result<netbird::SetupKey, CreateSetupKeyError>;
             async fn list_setup_keys(&self) -> std::result::Result<Vec<netbird::SetupKey>, RequestError>;
             async fn get_setup_key(&self, peer_id: PeerId) -> std::result::Result<Vec<SetupKey>, RequestError>;
             async fn delete_setup_key(&self, peer_id: PeerId) -> std::result::Result<Vec<SetupKey>, RequestError>;
+            async fn delete_netbird_setup_key(&self, setup_key_id: &netbird::SetupKeyId) -> std::result::Result<(), RequestError>;
         }
     }
 }
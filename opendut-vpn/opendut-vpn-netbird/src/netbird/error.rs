@@ -1,7 +1,6 @@
 use std::fmt::Debug;
 
 use http::StatusCode;
-use reqwest::header::InvalidHeaderValue;
 
 use opendut_types::peer::PeerId;
 
@@ -57,12 +56,12 @@ pub enum RequestError {
     JsonDeserialization(reqwest::Error),
     #[error("JSON serialization error: {0}")]
     JsonSerialization(serde_json::Error),
+    #[error("Failed to obtain authentication token: {0}")]
+    Authentication(String),
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum CreateClientError {
-    #[error("Invalid header: {0}")]
-    InvalidHeader(InvalidHeaderValue),
     #[error("Failed to instantiated client, due to an error: {cause}")]
     InstantiationFailure {
         cause: String
@@ -100,7 +100,7 @@ impl From<String> for PolicyId {
 }
 
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct Policy {
     pub id: PolicyId,
     pub name: PolicyName,
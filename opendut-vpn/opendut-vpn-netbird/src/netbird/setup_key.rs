@@ -5,9 +5,11 @@ use uuid::Uuid;
 
 use opendut_types::peer::PeerId;
 
+const NAME_PREFIX: &str = "opendut-peer-";
+
 pub fn name_format(peer_id: PeerId) -> String {
     // Fixed format, do not change. Allows resolving EDGAR's NetBird peer without a mapping table.
-    format!("opendut-peer-{peer_id}")
+    format!("{NAME_PREFIX}{peer_id}")
 }
 
 #[allow(unused)]
@@ -28,6 +30,18 @@ pub struct SetupKey {
     pub usage_limit: u64,
 }
 
+impl SetupKey {
+    /// Whether the key was generated by CARL for a peer, rather than created manually in NetBird.
+    pub fn is_generated_for_peer(&self) -> bool {
+        self.name.starts_with(NAME_PREFIX)
+    }
+
+    /// Whether the key can no longer be used for logging in.
+    pub fn is_stale(&self) -> bool {
+        matches!(self.state, State::Expired | State::Revoked)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct SetupKeyId(pub String);
 
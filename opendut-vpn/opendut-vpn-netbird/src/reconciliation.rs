@@ -0,0 +1,133 @@
+use std::collections::{HashMap, HashSet};
+
+use tracing::debug;
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::vpn::reconciliation::{VpnMismatch, VpnReconciliationFinding, VpnReconciliationOutcome};
+use opendut_vpn::{ExpectedVpnState, ReconcileError, VpnManagementClient};
+
+use crate::netbird::error::RequestError;
+use crate::netbird::{GroupName, PolicyName};
+use crate::{netbird, NetbirdManagementClient};
+
+/// Compares the groups, policies and setup keys, which CARL created in NetBird, with the expected state.
+/// Missing groups and policies are re-created, orphaned ones of clusters removed, as well as setup keys which can no longer be used.
+/// Groups of unknown peers are only reported, as deleting them would disconnect a peer, if CARL's state is not the latest.
+/// Groups and policies not created by CARL are left untouched.
+pub(crate) async fn reconcile(client: &NetbirdManagementClient, expected: &ExpectedVpnState) -> Result<Vec<VpnReconciliationFinding>, ReconcileError> {
+    let groups = client.inner.list_netbird_groups().await
+        .map_err(retrieval_failure)?;
+    let policies = client.inner.list_netbird_policies().await
+        .map_err(retrieval_failure)?;
+    let setup_keys = client.inner.list_setup_keys().await
+        .map_err(retrieval_failure)?;
+
+    let mut peer_groups = HashMap::<PeerId, netbird::Group>::new();
+    let mut cluster_groups = HashMap::<ClusterId, netbird::Group>::new();
+    for group in groups {
+        match Clone::clone(&group.name) {
+            GroupName::Peer(peer_id) => { peer_groups.insert(peer_id, group); }
+            GroupName::Cluster(cluster_id) => { cluster_groups.insert(cluster_id, group); }
+            GroupName::Other(_) => {}
+        }
+    }
+    let cluster_policies = policies.into_iter()
+        .filter_map(|policy| match policy.name {
+            PolicyName::Cluster(cluster_id) => Some(cluster_id),
+            PolicyName::Other(_) => None,
+        })
+        .collect::<HashSet<_>>();
+
+    let mut findings = Vec::new();
+
+    for peer_id in &expected.peers {
+        if !peer_groups.contains_key(peer_id) {
+            let outcome = outcome_of(client.create_peer(*peer_id).await);
+            findings.push(finding(VpnMismatch::MissingPeer { peer_id: *peer_id }, outcome));
+        }
+    }
+
+    for peer_id in peer_groups.keys() {
+        if !expected.peers.contains(peer_id) {
+            findings.push(finding(VpnMismatch::OrphanedPeer { peer_id: *peer_id }, VpnReconciliationOutcome::Unrepaired));
+        }
+    }
+
+    let mut peers_without_device = HashSet::new();
+    for (cluster_id, members) in &expected.clusters {
+        let mut expected_netbird_peers = HashSet::new();
+        let mut all_members_logged_in = true;
+
+        for member in members {
+            match peer_groups.get(member).and_then(|group| group.peers.first()) {
+                Some(netbird_peer) => {
+                    expected_netbird_peers.insert(Clone::clone(&netbird_peer.id));
+                }
+                None => {
+                    all_members_logged_in = false;
+                    if peers_without_device.insert(*member) {
+                        findings.push(finding(VpnMismatch::PeerWithoutDevice { peer_id: *member }, VpnReconciliationOutcome::Unrepaired));
+                    }
+                }
+            }
+        }
+
+        let mismatch = match cluster_groups.get(cluster_id) {
+            None => Some(VpnMismatch::MissingCluster { cluster_id: *cluster_id }),
+            Some(_) if !cluster_policies.contains(cluster_id) => Some(VpnMismatch::MissingCluster { cluster_id: *cluster_id }),
+            Some(group) => {
+                let actual_netbird_peers = group.peers.iter()
+                    .map(|peer| Clone::clone(&peer.id))
+                    .collect::<HashSet<_>>();
+
+                (all_members_logged_in && actual_netbird_peers != expected_netbird_peers)
+                    .then_some(VpnMismatch::OutdatedCluster { cluster_id: *cluster_id })
+            }
+        };
+
+        if let Some(mismatch) = mismatch {
+            let outcome = if all_members_logged_in {
+                outcome_of(client.create_cluster(*cluster_id, members).await)
+            } else {
+                // The cluster can only be re-created, once all of its peers are set up again.
+                VpnReconciliationOutcome::Unrepaired
+            };
+            findings.push(finding(mismatch, outcome));
+        }
+    }
+
+    let orphaned_clusters = cluster_groups.keys()
+        .chain(cluster_policies.iter())
+        .filter(|cluster_id| !expected.clusters.contains_key(*cluster_id))
+        .copied()
+        .collect::<HashSet<_>>();
+
+    for cluster_id in orphaned_clusters {
+        let outcome = outcome_of(client.delete_cluster(cluster_id).await);
+        findings.push(finding(VpnMismatch::OrphanedCluster { cluster_id }, outcome));
+    }
+
+    for setup_key in setup_keys.iter().filter(|setup_key| setup_key.is_generated_for_peer() && setup_key.is_stale()) {
+        let outcome = outcome_of(client.inner.delete_netbird_setup_key(&setup_key.id).await);
+        findings.push(finding(VpnMismatch::StaleSetupKey { name: Clone::clone(&setup_key.name) }, outcome));
+    }
+
+    debug!("Reconciled NetBird state with {} finding(s).", findings.len());
+    Ok(findings)
+}
+
+fn finding(mismatch: VpnMismatch, outcome: VpnReconciliationOutcome) -> VpnReconciliationFinding {
+    VpnReconciliationFinding { mismatch, outcome }
+}
+
+fn outcome_of<E: std::error::Error>(result: Result<(), E>) -> VpnReconciliationOutcome {
+    match result {
+        Ok(()) => VpnReconciliationOutcome::Repaired,
+        Err(cause) => VpnReconciliationOutcome::Failed { cause: cause.to_string() },
+    }
+}
+
+fn retrieval_failure(cause: RequestError) -> ReconcileError {
+    ReconcileError::StateRetrievalFailure { error: cause.into() }
+}
@@ -0,0 +1,259 @@
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use tokio::sync::{Mutex, MutexGuard};
+use tracing::debug;
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::util::Port;
+use opendut_types::vpn::VpnPeerConfiguration;
+use opendut_types::vpn::wireguard::{PrivateKey, PublicKey, WireGuardPeerDescriptor};
+use opendut_vpn::{CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, GenerateWireGuardPeersError, UpdatePeerEndpointError, VpnManagementClient};
+
+pub use crate::network::{IllegalOverlayNetwork, OverlayNetwork};
+pub use crate::state::StateError;
+use crate::state::State;
+
+mod network;
+mod state;
+
+pub struct WireGuardManagementClientConfiguration {
+    pub network: OverlayNetwork,
+    /// Port on which the WireGuard interfaces of all peers listen.
+    pub listen_port: u16,
+    pub state_file: PathBuf,
+}
+
+/// Manages a WireGuard overlay network without an external coordination service.
+/// CARL generates the keys and addresses of the peers and tells each peer, which other peers belong to its cluster.
+pub struct WireGuardManagementClient {
+    configuration: WireGuardManagementClientConfiguration,
+    state: Mutex<State>,
+}
+
+impl WireGuardManagementClient {
+    pub fn create(configuration: WireGuardManagementClientConfiguration) -> Result<Self, StateError> {
+        let state = State::load(&configuration.state_file)?;
+        debug!("Loaded WireGuard state with {} peers and {} clusters from '{}'.", state.peers.len(), state.clusters.len(), configuration.state_file.display());
+
+        Ok(Self {
+            configuration,
+            state: Mutex::new(state),
+        })
+    }
+
+    async fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().await
+    }
+
+    fn endpoint(&self, host: IpAddr) -> SocketAddr {
+        SocketAddr::new(host, self.configuration.listen_port)
+    }
+}
+
+#[async_trait]
+impl VpnManagementClient for WireGuardManagementClient {
+
+    async fn create_cluster(&self, cluster_id: ClusterId, peers: &[PeerId]) -> Result<(), CreateClusterError> {
+        let mut state = self.state().await;
+
+        for peer_id in peers {
+            if !state.peers.contains_key(peer_id) {
+                return Err(CreateClusterError::PeerResolutionFailure {
+                    peer_id: *peer_id,
+                    cluster_id,
+                    error: format!("No WireGuard address assigned to peer <{peer_id}>.").into(),
+                });
+            }
+        }
+        state.clusters.insert(cluster_id, peers.to_vec());
+
+        state.save(&self.configuration.state_file).await
+            .map_err(|cause| CreateClusterError::CreationFailure { cluster_id, error: cause.into() })
+    }
+
+    async fn delete_cluster(&self, cluster_id: ClusterId) -> Result<(), DeleteClusterError> {
+        let mut state = self.state().await;
+
+        if state.clusters.remove(&cluster_id).is_none() {
+            return Err(DeleteClusterError::NotFound { cluster_id, message: String::from("No members recorded for this cluster.") });
+        }
+
+        state.save(&self.configuration.state_file).await
+            .map_err(|cause| DeleteClusterError::DeletionFailure { cluster_id, error: cause.into() })
+    }
+
+    async fn create_peer(&self, peer_id: PeerId) -> Result<(), CreatePeerError> {
+        let mut state = self.state().await;
+
+        let address = state.get_or_create_peer(peer_id, &self.configuration.network)
+            .map_err(|cause| CreatePeerError::CreationFailure { peer_id, error: cause.into() })?
+            .address;
+        debug!("Assigned WireGuard address {address} to peer <{peer_id}>.");
+
+        state.save(&self.configuration.state_file).await
+            .map_err(|cause| CreatePeerError::CreationFailure { peer_id, error: cause.into() })
+    }
+
+    async fn delete_peer(&self, peer_id: PeerId) -> Result<(), DeletePeerError> {
+        let mut state = self.state().await;
+
+        if state.remove_peer(peer_id).is_none() {
+            debug!("No WireGuard address assigned to peer <{peer_id}>. Nothing to delete.");
+            return Ok(());
+        }
+
+        state.save(&self.configuration.state_file).await
+            .map_err(|cause| DeletePeerError::DeletionFailure { peer_id, error: cause.into() })
+    }
+
+    /// Generates a new key pair for every Setup-String, so that a previously generated Setup-String stops working.
+    async fn generate_vpn_peer_configuration(&self, peer_id: PeerId) -> Result<VpnPeerConfiguration, CreateVpnPeerConfigurationError> {
+        let mut state = self.state().await;
+
+        let (private_key, public_key) = generate_key_pair();
+
+        let peer = state.get_or_create_peer(peer_id, &self.configuration.network)
+            .map_err(|cause| CreateVpnPeerConfigurationError::CreationFailure { peer_id, error: cause.into() })?;
+        peer.public_key = Some(public_key);
+        let address = peer.address;
+
+        state.save(&self.configuration.state_file).await
+            .map_err(|cause| CreateVpnPeerConfigurationError::CreationFailure { peer_id, error: cause.into() })?;
+
+        Ok(VpnPeerConfiguration::WireGuard {
+            private_key,
+            address: IpAddr::V4(address),
+            prefix_length: self.configuration.network.prefix_length(),
+            listen_port: Port(self.configuration.listen_port),
+        })
+    }
+
+    async fn update_peer_endpoint(&self, peer_id: PeerId, host: IpAddr) -> Result<(), UpdatePeerEndpointError> {
+        let mut state = self.state().await;
+
+        let peer = state.peers.get_mut(&peer_id)
+            .ok_or_else(|| UpdatePeerEndpointError::ResolutionFailure { peer_id, error: String::from("No WireGuard address assigned to peer.").into() })?;
+
+        if peer.endpoint_host == Some(host) {
+            return Ok(());
+        }
+        peer.endpoint_host = Some(host);
+        debug!("Peer <{peer_id}> is reachable for WireGuard via {host}.");
+
+        state.save(&self.configuration.state_file).await
+            .map_err(|cause| UpdatePeerEndpointError::ResolutionFailure { peer_id, error: cause.into() })
+    }
+
+    async fn generate_wireguard_peers(&self, cluster_id: ClusterId, peer_id: PeerId) -> Result<Vec<WireGuardPeerDescriptor>, GenerateWireGuardPeersError> {
+        let state = self.state().await;
+
+        let members = state.clusters.get(&cluster_id)
+            .ok_or(GenerateWireGuardPeersError::ClusterNotFound { cluster_id })?;
+
+        members.iter()
+            .filter(|member| **member != peer_id)
+            .map(|member| {
+                let peer = state.peers.get(member)
+                    .ok_or(GenerateWireGuardPeersError::PeerResolutionFailure { peer_id: *member, cluster_id })?;
+                let public_key = Clone::clone(&peer.public_key)
+                    .ok_or(GenerateWireGuardPeersError::PeerResolutionFailure { peer_id: *member, cluster_id })?;
+
+                Ok(WireGuardPeerDescriptor {
+                    peer_id: *member,
+                    public_key,
+                    address: IpAddr::V4(peer.address),
+                    endpoint: peer.endpoint_host.map(|host| self.endpoint(host)),
+                })
+            })
+            .collect()
+    }
+}
+
+fn generate_key_pair() -> (PrivateKey, PublicKey) {
+    let secret = x25519_dalek::StaticSecret::random();
+    let public = x25519_dalek::PublicKey::from(&secret);
+    (PrivateKey::from_bytes(secret.to_bytes()), PublicKey::from_bytes(public.to_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::str::FromStr;
+
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn create_client(temp: &TempDir) -> anyhow::Result<WireGuardManagementClient> {
+        let client = WireGuardManagementClient::create(WireGuardManagementClientConfiguration {
+            network: OverlayNetwork::from_str("10.100.0.0/24")?,
+            listen_port: 51820,
+            state_file: temp.child("wireguard.json").to_path_buf(),
+        })?;
+        Ok(client)
+    }
+
+    #[tokio::test]
+    async fn should_assign_distinct_addresses_and_keep_them_after_a_restart() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+
+        let client = create_client(&temp)?;
+        client.create_peer(peer_a).await?;
+        let configuration = client.generate_vpn_peer_configuration(peer_b).await?;
+
+        assert_that!(configuration, matches_pattern!(VpnPeerConfiguration::WireGuard {
+            address: eq(&IpAddr::V4(Ipv4Addr::new(10, 100, 0, 2))),
+            prefix_length: eq(&24),
+            listen_port: eq(&Port(51820)),
+            ..
+        }));
+
+        let restarted_client = create_client(&temp)?;
+        let peers = restarted_client.state().await.peers.clone();
+        assert_that!(peers.get(&peer_a).map(|peer| peer.address), some(eq(Ipv4Addr::new(10, 100, 0, 1))));
+        assert_that!(peers.get(&peer_b).map(|peer| peer.address), some(eq(Ipv4Addr::new(10, 100, 0, 2))));
+
+        restarted_client.delete_peer(peer_a).await?;
+        let peer_c = PeerId::random();
+        restarted_client.create_peer(peer_c).await?;
+        assert_that!(restarted_client.state().await.peers.get(&peer_c).map(|peer| peer.address), some(eq(Ipv4Addr::new(10, 100, 0, 1))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_list_the_other_peers_of_a_cluster_with_their_endpoint() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let client = create_client(&temp)?;
+        let cluster_id = ClusterId::random();
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+
+        client.generate_vpn_peer_configuration(peer_a).await?;
+        client.generate_vpn_peer_configuration(peer_b).await?;
+        client.update_peer_endpoint(peer_b, IpAddr::from_str("192.0.2.10")?).await?;
+        client.create_cluster(cluster_id, &[peer_a, peer_b]).await?;
+
+        let peers = client.generate_wireguard_peers(cluster_id, peer_a).await?;
+
+        assert_that!(peers, elements_are![
+            matches_pattern!(WireGuardPeerDescriptor {
+                peer_id: eq(&peer_b),
+                address: eq(&IpAddr::V4(Ipv4Addr::new(10, 100, 0, 2))),
+                endpoint: some(eq(&SocketAddr::from_str("192.0.2.10:51820")?)),
+                ..
+            }),
+        ]);
+
+        client.delete_cluster(cluster_id).await?;
+        assert_that!(client.generate_wireguard_peers(cluster_id, peer_a).await, err(anything()));
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+/// IPv4 network, from which CARL assigns the addresses of the peers in the WireGuard overlay network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OverlayNetwork {
+    address: Ipv4Addr,
+    prefix_length: u8,
+}
+
+impl OverlayNetwork {
+    /// Networks smaller than this contain no addresses, which could be assigned to two peers.
+    pub const MAX_PREFIX_LENGTH: u8 = 30;
+
+    pub fn new(address: Ipv4Addr, prefix_length: u8) -> Result<Self, IllegalOverlayNetwork> {
+        if prefix_length > Self::MAX_PREFIX_LENGTH {
+            return Err(IllegalOverlayNetwork {
+                value: format!("{address}/{prefix_length}"),
+                details: format!("Prefix length must not be larger than {}.", Self::MAX_PREFIX_LENGTH),
+            });
+        }
+        let address = Ipv4Addr::from(u32::from(address) & Self::mask(prefix_length));
+        Ok(Self { address, prefix_length })
+    }
+
+    pub fn prefix_length(&self) -> u8 {
+        self.prefix_length
+    }
+
+    pub fn contains(&self, address: Ipv4Addr) -> bool {
+        u32::from(address) & Self::mask(self.prefix_length) == u32::from(self.address)
+    }
+
+    /// Addresses, which can be assigned to peers. Excludes the network and broadcast address.
+    pub fn hosts(&self) -> impl Iterator<Item = Ipv4Addr> {
+        let network = u32::from(self.address);
+        let broadcast = network | !Self::mask(self.prefix_length);
+        (network + 1..broadcast).map(Ipv4Addr::from)
+    }
+
+    fn mask(prefix_length: u8) -> u32 {
+        u32::MAX.checked_shl(u32::from(32 - prefix_length)).unwrap_or(0)
+    }
+}
+
+impl FromStr for OverlayNetwork {
+    type Err = IllegalOverlayNetwork;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let illegal = |details: &str| IllegalOverlayNetwork { value: value.to_owned(), details: details.to_owned() };
+
+        let (address, prefix_length) = value.trim().split_once('/')
+            .ok_or_else(|| illegal("Expected a network in CIDR notation, e.g. '10.100.0.0/16'."))?;
+        let address = Ipv4Addr::from_str(address)
+            .map_err(|_| illegal("Expected an IPv4 address in front of the '/'."))?;
+        let prefix_length = u8::from_str(prefix_length)
+            .map_err(|_| illegal("Expected a prefix length after the '/'."))?;
+
+        Self::new(address, prefix_length)
+    }
+}
+
+impl fmt::Display for OverlayNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix_length)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal overlay network '{value}': {details}")]
+pub struct IllegalOverlayNetwork {
+    pub value: String,
+    pub details: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_a_network_and_list_its_hosts() -> anyhow::Result<()> {
+        let network = OverlayNetwork::from_str("10.100.0.5/30")?;
+
+        assert_that!(network.to_string(), eq("10.100.0.4/30"));
+        assert_that!(network.hosts().collect::<Vec<_>>(), elements_are![
+            eq(&Ipv4Addr::new(10, 100, 0, 5)),
+            eq(&Ipv4Addr::new(10, 100, 0, 6)),
+        ]);
+        assert_that!(network.contains(Ipv4Addr::new(10, 100, 0, 7)), eq(true));
+        assert_that!(network.contains(Ipv4Addr::new(10, 100, 0, 8)), eq(false));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_an_invalid_network() {
+        assert_that!(OverlayNetwork::from_str("10.100.0.0"), err(anything()));
+        assert_that!(OverlayNetwork::from_str("10.100.0.0/31"), err(anything()));
+        assert_that!(OverlayNetwork::from_str("fd00::/64"), err(anything()));
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::vpn::wireguard::PublicKey;
+
+use crate::network::OverlayNetwork;
+
+/// Keys, addresses and cluster memberships assigned by CARL.
+/// Persisted, as the peers keep using them after CARL restarts.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct State {
+    pub peers: HashMap<PeerId, PeerEntry>,
+    pub clusters: HashMap<ClusterId, Vec<PeerId>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PeerEntry {
+    pub address: Ipv4Addr,
+    /// Not known until a Setup-String was generated for the peer.
+    pub public_key: Option<PublicKey>,
+    pub endpoint_host: Option<IpAddr>,
+}
+
+impl State {
+    pub fn load(path: &Path) -> Result<Self, StateError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|source| StateError::Io { path: path.display().to_string(), source })?;
+        serde_json::from_str(&content)
+            .map_err(|source| StateError::Format { path: path.display().to_string(), source })
+    }
+
+    /// Writes the state to a temporary file and renames it afterwards, so that a crash while writing cannot leave a truncated state file behind.
+    /// The blocking file operations run on a separate thread.
+    pub async fn save(&self, path: &Path) -> Result<(), StateError> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|source| StateError::Format { path: path.display().to_string(), source })?;
+
+        let path = path.to_owned();
+        let error_path = path.display().to_string();
+        tokio::task::spawn_blocking(move || write_atomically(&path, content.as_bytes()))
+            .await
+            .map_err(|cause| StateError::Io { path: error_path, source: std::io::Error::other(cause) })?
+    }
+
+    /// Returns the entry of the peer, assigning it the lowest free address of the network, if it has none yet.
+    pub fn get_or_create_peer(&mut self, peer_id: PeerId, network: &OverlayNetwork) -> Result<&mut PeerEntry, StateError> {
+        if !self.peers.contains_key(&peer_id) {
+            let address = network.hosts()
+                .find(|address| self.peers.values().all(|peer| peer.address != *address))
+                .ok_or_else(|| StateError::AddressesExhausted { network: network.to_string() })?;
+
+            self.peers.insert(peer_id, PeerEntry { address, public_key: None, endpoint_host: None });
+        }
+        Ok(self.peers.get_mut(&peer_id).expect("Peer entry should have been inserted."))
+    }
+
+    pub fn remove_peer(&mut self, peer_id: PeerId) -> Option<PeerEntry> {
+        for members in self.clusters.values_mut() {
+            members.retain(|member| *member != peer_id);
+        }
+        self.peers.remove(&peer_id)
+    }
+}
+
+fn write_atomically(path: &Path, content: &[u8]) -> Result<(), StateError> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(io_error(parent))?;
+    }
+
+    let temporary_path = {
+        let mut temporary_path = path.as_os_str().to_owned();
+        temporary_path.push(".tmp");
+        PathBuf::from(temporary_path)
+    };
+    let mut file = std::fs::File::create(&temporary_path)
+        .map_err(io_error(&temporary_path))?;
+    file.write_all(content)
+        .and_then(|()| file.sync_all())
+        .map_err(io_error(&temporary_path))?;
+
+    std::fs::rename(&temporary_path, path)
+        .map_err(io_error(path))
+}
+
+fn io_error(path: &Path) -> impl FnOnce(std::io::Error) -> StateError {
+    let path = path.display().to_string();
+    move |source| StateError::Io { path, source }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum StateError {
+    #[error("All addresses of the overlay network {network} are assigned")]
+    AddressesExhausted { network: String },
+    #[error("Failed to access WireGuard state file at '{path}'")]
+    Io { path: String, #[source] source: std::io::Error },
+    #[error("WireGuard state file at '{path}' has an invalid format")]
+    Format { path: String, #[source] source: serde_json::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_replace_the_state_file_without_leaving_a_temporary_file() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let state_file = temp.child("wireguard.json");
+        state_file.write_str("{ \"peers\": {}, \"clusters\": {} }")?;
+
+        let mut state = State::default();
+        state.clusters.insert(ClusterId::random(), vec![PeerId::random()]);
+        state.save(&state_file).await?;
+
+        assert_that!(State::load(&state_file)?, eq(&state));
+        assert_that!(temp.child("wireguard.json.tmp").exists(), eq(false));
+        Ok(())
+    }
+}
@@ -1,8 +1,13 @@
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+
 use async_trait::async_trait;
 
 use opendut_types::cluster::ClusterId;
 use opendut_types::peer::PeerId;
 use opendut_types::vpn::VpnPeerConfiguration;
+use opendut_types::vpn::reconciliation::VpnReconciliationFinding;
+use opendut_types::vpn::wireguard::WireGuardPeerDescriptor;
 
 #[async_trait]
 pub trait VpnManagementClient {
@@ -16,6 +21,38 @@ pub trait VpnManagementClient {
     async fn delete_peer(&self, peer_id: PeerId) -> Result<(), DeletePeerError>;
 
     async fn generate_vpn_peer_configuration(&self, peer_id: PeerId) -> Result<VpnPeerConfiguration, CreateVpnPeerConfigurationError>;
+
+    /// Invalidates the credentials of the last generated configuration of a peer, which was not used for logging in yet,
+    /// e.g. the setup key of a revoked Setup-String.
+    /// Nothing to do for VPNs, which replace the credentials when generating the next configuration.
+    async fn revoke_vpn_peer_configuration(&self, _peer_id: PeerId) -> Result<(), RevokeVpnPeerConfigurationError> {
+        Ok(())
+    }
+
+    /// Records the host under which a peer can be reached by the other peers, when it connects to CARL.
+    /// Only needed by VPNs, which do not discover this themselves.
+    async fn update_peer_endpoint(&self, _peer_id: PeerId, _host: IpAddr) -> Result<(), UpdatePeerEndpointError> {
+        Ok(())
+    }
+
+    /// Lists the other peers of a cluster, which a peer has to configure on its WireGuard interface.
+    /// Empty for VPNs, which distribute this information themselves.
+    async fn generate_wireguard_peers(&self, _cluster_id: ClusterId, _peer_id: PeerId) -> Result<Vec<WireGuardPeerDescriptor>, GenerateWireGuardPeersError> {
+        Ok(Vec::new())
+    }
+
+    /// Compares the state of the VPN with the peers and cluster deployments known to CARL, and repairs or removes mismatches.
+    /// Empty for VPNs, whose state is managed exclusively by CARL and therefore cannot drift.
+    async fn reconcile(&self, _expected: &ExpectedVpnState) -> Result<Vec<VpnReconciliationFinding>, ReconcileError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Peers and deployed clusters known to CARL, which the VPN is expected to contain.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExpectedVpnState {
+    pub peers: HashSet<PeerId>,
+    pub clusters: HashMap<ClusterId, Vec<PeerId>>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -83,3 +120,42 @@ pub enum CreateVpnPeerConfigurationError {
         #[source] error: Box<dyn std::error::Error + Send + Sync>
     },
 }
+
+#[derive(thiserror::Error, Debug)]
+pub enum RevokeVpnPeerConfigurationError {
+    #[error("An error occurred while revoking the vpn configuration of peer <{peer_id}>")]
+    RevocationFailure {
+        peer_id: PeerId,
+        #[source] error: Box<dyn std::error::Error + Send + Sync>
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum UpdatePeerEndpointError {
+    #[error("Peer <{peer_id}> could not be resolved")]
+    ResolutionFailure {
+        peer_id: PeerId,
+        #[source] error: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum GenerateWireGuardPeersError {
+    #[error("No cluster <{cluster_id}> could be found")]
+    ClusterNotFound {
+        cluster_id: ClusterId,
+    },
+    #[error("Peer <{peer_id}> of cluster <{cluster_id}> could not be resolved")]
+    PeerResolutionFailure {
+        peer_id: PeerId,
+        cluster_id: ClusterId,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ReconcileError {
+    #[error("The state of the VPN could not be retrieved")]
+    StateRetrievalFailure {
+        #[source] error: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
@@ -65,6 +65,7 @@ async fn carl_should_send_peer_configurations_in_happy_flow() -> anyhow::Result<
                     })
                 ),
                 executors: empty(),
+                wireguard_peers: empty(),
             }));
             Ok::<_, anyhow::Error>(())
         };
@@ -156,6 +157,7 @@ async fn carl_should_send_cluster_related_peer_configuration_if_a_peer_comes_onl
                     })
                 ),
                 executors: empty(),
+                wireguard_peers: empty(),
             }));
             Ok::<_, anyhow::Error>(())
         };
@@ -236,6 +238,9 @@ async fn store_cluster_configuration(leader: PeerId, devices: impl Iterator<Item
         name: ClusterName::try_from(format!("cluster-{cluster_id}"))?,
         leader,
         devices,
+        labels: Default::default(),
+        device_selector: None,
+        project: Default::default(),
     };
 
     carl_client.inner().await.cluster.store_cluster_configuration(cluster_configuration.clone()).await?;
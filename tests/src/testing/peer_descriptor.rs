@@ -31,12 +31,15 @@ pub async fn store_peer_descriptor(carl_client: &TestCarlClient) -> anyhow::Resu
                     description: None,
                     interface: network_interface_id,
                     tags: vec![],
+                    labels: Default::default(),
                 }
             ],
         },
         executors: ExecutorDescriptors {
             executors: vec![],
         },
+        labels: Default::default(),
+        project: Default::default(),
     };
 
     carl_client.inner().await.peers
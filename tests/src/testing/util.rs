@@ -43,8 +43,9 @@ pub async fn spawn_edgar_with_peer_configuration_receiver(peer_id: PeerId, carl_
     let edgar_config = load_edgar_config(carl_port, peer_id)?;
 
     let (tx_peer_configuration, rx_peer_configuration) = mpsc::channel(100);
+    let service_status = opendut_edgar::testing::service::status::ServiceStatus::create(peer_id);
     tokio::spawn(async move {
-        opendut_edgar::testing::service::start::run_stream_receiver(peer_id, edgar_config, tx_peer_configuration).await
+        opendut_edgar::testing::service::start::run_stream_receiver(peer_id, edgar_config, tx_peer_configuration, service_status).await
             .expect("EDGAR crashed")
     });
     Ok(PeerConfigurationReceiver { inner: rx_peer_configuration })